@@ -129,6 +129,28 @@ class NostrConnectIntentTest {
         assertNull(AppManager.extractChatDeepLinkNpub(intent))
     }
 
+    // ── Group invite link intent tests ──
+
+    @Test
+    fun extractGroupInviteLink_returnsLinkForValidJoinIntent() {
+        val s = scheme()
+        val link = "$s://join/$validHexPubkey/${"0".repeat(32)}"
+        val intent = Intent(Intent.ACTION_VIEW).apply {
+            data = Uri.parse(link)
+        }
+        assertEquals(link, AppManager.extractGroupInviteLink(intent))
+        assertNull(AppManager.extractChatDeepLinkNpub(intent))
+    }
+
+    @Test
+    fun extractGroupInviteLink_returnsNullForBadToken() {
+        val s = scheme()
+        val intent = Intent(Intent.ACTION_VIEW).apply {
+            data = Uri.parse("$s://join/$validHexPubkey/garbage")
+        }
+        assertNull(AppManager.extractGroupInviteLink(intent))
+    }
+
     private fun String.countOccurrences(fragment: String): Int {
         if (fragment.isEmpty()) return 0
         var count = 0
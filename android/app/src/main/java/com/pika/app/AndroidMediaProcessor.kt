@@ -0,0 +1,192 @@
+package com.pika.app
+
+import android.graphics.Bitmap
+import android.media.MediaCodec
+import android.media.MediaExtractor
+import android.media.MediaFormat
+import android.media.MediaMetadataRetriever
+import android.util.Log
+import com.pika.app.rust.AudioWaveform
+import com.pika.app.rust.MediaProcessor
+import com.pika.app.rust.VideoPoster
+import java.io.ByteArrayOutputStream
+import java.nio.ByteOrder
+import kotlin.math.log10
+import kotlin.math.max
+import kotlin.math.sqrt
+
+/**
+ * Poster extraction for outgoing videos and waveform decoding for voice notes.
+ * Rust calls this from a background thread.
+ *
+ * Transcoding is not wired up on Android yet; returning false makes the core send
+ * the original file.
+ */
+internal class AndroidMediaProcessor : MediaProcessor {
+    override fun extractPoster(path: String): VideoPoster? {
+        val retriever = MediaMetadataRetriever()
+        return try {
+            retriever.setDataSource(path)
+            val frame =
+                retriever.getFrameAtTime(0, MediaMetadataRetriever.OPTION_CLOSEST_SYNC)
+                    ?: return null
+            val jpeg =
+                ByteArrayOutputStream().use { out ->
+                    frame.compress(Bitmap.CompressFormat.JPEG, 80, out)
+                    out.toByteArray()
+                }
+            val durationMs =
+                retriever
+                    .extractMetadata(MediaMetadataRetriever.METADATA_KEY_DURATION)
+                    ?.toLongOrNull()
+                    ?: 0L
+            // getFrameAtTime already applies the rotation, so the bitmap has display dimensions.
+            VideoPoster(
+                imageData = jpeg,
+                width = frame.width.toUInt(),
+                height = frame.height.toUInt(),
+                durationMs = durationMs.coerceAtLeast(0L).toULong(),
+            )
+        } catch (e: Exception) {
+            Log.w(TAG, "poster extraction failed", e)
+            null
+        } finally {
+            retriever.release()
+        }
+    }
+
+    override fun transcode(
+        inputPath: String,
+        outputPath: String,
+        maxBytes: ULong,
+    ): Boolean = false
+
+    override fun audioWaveform(
+        path: String,
+        bars: UInt,
+    ): AudioWaveform? {
+        val barCount = bars.toInt()
+        if (barCount <= 0) return null
+        val extractor = MediaExtractor()
+        var decoder: MediaCodec? = null
+        return try {
+            extractor.setDataSource(path)
+            val track =
+                (0 until extractor.trackCount).firstOrNull { index ->
+                    extractor.getTrackFormat(index).getString(MediaFormat.KEY_MIME)?.startsWith("audio/") == true
+                } ?: return null
+            extractor.selectTrack(track)
+            val format = extractor.getTrackFormat(track)
+            val mime = format.getString(MediaFormat.KEY_MIME) ?: return null
+            val durationMs =
+                if (format.containsKey(MediaFormat.KEY_DURATION)) {
+                    format.getLong(MediaFormat.KEY_DURATION) / 1_000L
+                } else {
+                    0L
+                }
+            val codec = MediaCodec.createDecoderByType(mime).also { decoder = it }
+            codec.configure(format, null, null, 0)
+            codec.start()
+            val windows = decodeRmsWindows(extractor, codec)
+            if (windows.isEmpty()) return null
+            AudioWaveform(
+                levels = peaks(windows, barCount),
+                durationMs = durationMs.coerceAtLeast(0L).toULong(),
+            )
+        } catch (e: Exception) {
+            Log.w(TAG, "waveform decode failed", e)
+            null
+        } finally {
+            decoder?.let { codec ->
+                runCatching { codec.stop() }
+                codec.release()
+            }
+            extractor.release()
+        }
+    }
+
+    /** Decode to 16-bit PCM and return one level per [WINDOW_SAMPLES] samples. */
+    private fun decodeRmsWindows(
+        extractor: MediaExtractor,
+        codec: MediaCodec,
+    ): List<Float> {
+        val windows = ArrayList<Float>()
+        val info = MediaCodec.BufferInfo()
+        var sumSquares = 0.0
+        var count = 0
+        var inputDone = false
+        var idlePolls = 0
+        while (idlePolls < MAX_IDLE_POLLS) {
+            if (!inputDone) {
+                val inputIndex = codec.dequeueInputBuffer(CODEC_TIMEOUT_US)
+                val input = if (inputIndex >= 0) codec.getInputBuffer(inputIndex) else null
+                if (input != null) {
+                    val size = extractor.readSampleData(input, 0)
+                    if (size < 0) {
+                        codec.queueInputBuffer(inputIndex, 0, 0, 0, MediaCodec.BUFFER_FLAG_END_OF_STREAM)
+                        inputDone = true
+                    } else {
+                        codec.queueInputBuffer(inputIndex, 0, size, extractor.sampleTime, 0)
+                        extractor.advance()
+                    }
+                }
+            }
+
+            val outputIndex = codec.dequeueOutputBuffer(info, CODEC_TIMEOUT_US)
+            if (outputIndex < 0) {
+                if (inputDone) idlePolls++
+                continue
+            }
+            idlePolls = 0
+            val output = codec.getOutputBuffer(outputIndex)
+            if (output != null && info.size > 0) {
+                output.position(info.offset)
+                output.limit(info.offset + info.size)
+                val samples = output.order(ByteOrder.LITTLE_ENDIAN).asShortBuffer()
+                while (samples.hasRemaining()) {
+                    val sample = samples.get() / 32_768.0
+                    sumSquares += sample * sample
+                    count++
+                    if (count == WINDOW_SAMPLES) {
+                        windows.add(rmsLevel(sumSquares, count))
+                        sumSquares = 0.0
+                        count = 0
+                    }
+                }
+            }
+            codec.releaseOutputBuffer(outputIndex, false)
+            if (info.flags and MediaCodec.BUFFER_FLAG_END_OF_STREAM != 0) break
+        }
+        if (count > 0) windows.add(rmsLevel(sumSquares, count))
+        return windows
+    }
+
+    /** Same dB mapping as the recorder's live levels. */
+    private fun rmsLevel(
+        sumSquares: Double,
+        count: Int,
+    ): Float {
+        val rms = max(sqrt(sumSquares / count), 1e-6)
+        val db = 20.0 * log10(rms)
+        return ((db + 50.0) / 50.0).coerceIn(0.0, 1.0).toFloat()
+    }
+
+    private fun peaks(
+        windows: List<Float>,
+        bars: Int,
+    ): List<Float> {
+        val barCount = minOf(bars, windows.size)
+        return List(barCount) { bar ->
+            val start = bar * windows.size / barCount
+            val end = max(start + 1, (bar + 1) * windows.size / barCount)
+            windows.subList(start, end).max()
+        }
+    }
+
+    private companion object {
+        const val TAG = "PikaMediaProcessor"
+        const val CODEC_TIMEOUT_US = 10_000L
+        const val MAX_IDLE_POLLS = 100
+        const val WINDOW_SAMPLES = 1_024
+    }
+}
@@ -25,7 +25,9 @@ import com.pika.app.rust.ExternalSignerHandshakeResult
 import com.pika.app.rust.ExternalSignerResult
 import com.pika.app.rust.FfiApp
 import com.pika.app.rust.MediaBatchItem
+import com.pika.app.rust.isGroupInviteLink
 import com.pika.app.rust.isValidPeerKey
+import com.pika.app.rust.pubkeyForNsec
 import com.pika.app.rust.MyProfileState
 import com.pika.app.rust.Screen
 import com.pika.app.rust.share.ShareAckStatus
@@ -79,18 +81,26 @@ class AppManager private constructor(context: Context) : AppReconciler {
                 fetchingFollowList = false,
             ),
             chatList = emptyList(),
+            invitations = emptyList(),
             currentChat = null,
             followList = emptyList(),
+            blockedUsers = emptyList(),
             peerProfile = null,
             activeCall = null,
             callTimeline = emptyList(),
+            callReport = null,
             toast = null,
             developerMode = false,
+            readReceiptsEnabled = false,
+            autoAcceptFollowedInvites = true,
+            blockListSyncEnabled = false,
             updateRequired = false,
             agentButton = null,
             agentProvisioning = null,
             voiceRecording = null,
             mediaGallery = null,
+            messageSearch = null,
+            accounts = emptyList(),
         ),
     )
         private set
@@ -106,6 +116,7 @@ class AppManager private constructor(context: Context) : AppReconciler {
         } catch (_: Exception) { "0.0.0" }
         rust = FfiApp(dataDir, "", appVersion)
         rust.setExternalSignerBridge(AmberRustBridge())
+        rust.setMediaProcessor(AndroidMediaProcessor())
         val initial = rust.state()
         state = initial
         audioFocus.syncForCall(initial.activeCall)
@@ -195,10 +206,52 @@ class AppManager private constructor(context: Context) : AppReconciler {
     }
 
     fun logout() {
+        val active = activeAccountPubkey()
+        active?.let { secureStore.removeAccountNsec(it) }
         secureStore.clear()
         rust.dispatch(AppAction.Logout)
+
+        // Rust falls back to another account it still holds a signer for; after a restart it
+        // only has what we stored, so sign the next one back in.
+        val fallback =
+            state.accounts
+                .asSequence()
+                .filter { it.pubkey != active }
+                .mapNotNull { secureStore.accountNsec(it.pubkey) }
+                .firstOrNull()
+        if (fallback != null) {
+            rust.dispatch(AppAction.AddAccount(fallback))
+        }
+    }
+
+    /** Signs in another account alongside the current one and switches to it. */
+    fun addAccount(nsec: String) {
+        val trimmed = nsec.trim()
+        pubkeyForNsec(trimmed)?.let { secureStore.saveAccountNsec(it, trimmed) }
+        rust.dispatch(AppAction.AddAccount(trimmed))
+    }
+
+    fun switchAccount(pubkey: String) {
+        // A stored nsec works even if Rust hasn't seen this account's signer since launch.
+        val nsec = secureStore.accountNsec(pubkey)
+        if (nsec != null) {
+            rust.dispatch(AppAction.AddAccount(nsec))
+        } else {
+            rust.dispatch(AppAction.SwitchAccount(pubkey))
+        }
     }
 
+    fun removeAccount(pubkey: String) {
+        if (pubkey == activeAccountPubkey()) {
+            logout()
+            return
+        }
+        secureStore.removeAccountNsec(pubkey)
+        rust.dispatch(AppAction.RemoveAccount(pubkey))
+    }
+
+    private fun activeAccountPubkey(): String? = (state.auth as? AuthState.LoggedIn)?.pubkey
+
     fun isDeveloperModeEnabled(): Boolean = state.developerMode
 
     fun enableDeveloperMode() {
@@ -264,6 +317,11 @@ class AppManager private constructor(context: Context) : AppReconciler {
             return
         }
 
+        extractGroupInviteLink(intent)?.let { link ->
+            rust.dispatch(AppAction.JoinGroupViaInviteLink(link = link))
+            return
+        }
+
         extractIncomingShareDraft(intent)?.let { draft ->
             pendingShareDraft = draft
             maybePresentShareChooser()
@@ -285,6 +343,7 @@ class AppManager private constructor(context: Context) : AppReconciler {
                 if (existing.isBlank() && update.nsec.isNotBlank()) {
                     secureStore.saveLocalNsec(update.nsec)
                 }
+                secureStore.saveAccountNsec(update.pubkey, update.nsec)
             } else if (update is AppUpdate.BunkerSessionDescriptor) {
                 if (update.bunkerUri.isNotBlank() && update.clientNsec.isNotBlank()) {
                     secureStore.saveBunker(update.bunkerUri, update.clientNsec)
@@ -369,6 +428,15 @@ class AppManager private constructor(context: Context) : AppReconciler {
                         if (secureStore.load()?.mode != StoredAuthMode.LOCAL_NSEC) {
                             secureStore.clear()
                         }
+                        // File the nsec we logged in with under its account, and make the
+                        // active account's nsec the one restored on next launch.
+                        val accountNsec = secureStore.accountNsec(auth.pubkey)
+                        val activeNsec = secureStore.load()?.nsec
+                        if (accountNsec != null) {
+                            if (activeNsec != accountNsec) secureStore.saveLocalNsec(accountNsec)
+                        } else if (!activeNsec.isNullOrBlank()) {
+                            secureStore.saveAccountNsec(auth.pubkey, activeNsec)
+                        }
                     }
                     is AuthMode.ExternalSigner -> {
                         secureStore.saveExternalSigner(
@@ -832,6 +900,15 @@ class AppManager private constructor(context: Context) : AppReconciler {
             return npub
         }
 
+        internal fun extractGroupInviteLink(intent: Intent?): String? {
+            if (intent?.action != Intent.ACTION_VIEW) return null
+            val data = intent.data ?: return null
+            if (!data.scheme.equals(NOSTR_CONNECT_CALLBACK_SCHEME, ignoreCase = true)) return null
+            val link = data.toString()
+            if (!isGroupInviteLink(link)) return null
+            return link
+        }
+
         internal fun extractNostrConnectCallback(intent: Intent?): String? {
             if (intent?.action != Intent.ACTION_VIEW) return null
             val data = intent.data ?: return null
@@ -113,6 +113,19 @@ class SecureAuthStore(context: Context) {
             .apply()
     }
 
+    /** Per-account nsecs, kept so signed-in accounts can be switched back to after a restart. */
+    fun saveAccountNsec(pubkey: String, nsec: String) {
+        if (pubkey.isBlank() || nsec.isBlank()) return
+        prefs.edit().putString(KEY_ACCOUNT_NSEC_PREFIX + pubkey, nsec).apply()
+    }
+
+    fun accountNsec(pubkey: String): String? =
+        prefs.getString(KEY_ACCOUNT_NSEC_PREFIX + pubkey, null)?.takeIf { it.isNotBlank() }
+
+    fun removeAccountNsec(pubkey: String) {
+        prefs.edit().remove(KEY_ACCOUNT_NSEC_PREFIX + pubkey).apply()
+    }
+
     fun clear() {
         prefs
             .edit()
@@ -134,6 +147,7 @@ class SecureAuthStore(context: Context) {
         private const val KEY_EXT_CURRENT_USER = "external_current_user"
         private const val KEY_BUNKER_URI = "bunker_uri"
         private const val KEY_BUNKER_CLIENT_NSEC = "bunker_client_nsec"
+        private const val KEY_ACCOUNT_NSEC_PREFIX = "account_nsec."
 
         private const val MODE_LOCAL_NSEC = "local_nsec"
         private const val MODE_EXTERNAL_SIGNER = "external_signer"
@@ -25,10 +25,15 @@ object TestTags {
     const val CHAT_SEND = "chat_send"
     const val CHAT_CALL_START = "chat_call_start"
     const val CHAT_CALL_OPEN = "chat_call_open"
+    const val CHAT_DISAPPEARING_TIMER = "chat_disappearing_timer"
+    const val CHAT_NOTIFICATION_MODE = "chat_notification_mode"
     const val CHAT_CALL_ACCEPT = "chat_call_accept"
     const val CHAT_CALL_REJECT = "chat_call_reject"
     const val CHAT_CALL_END = "chat_call_end"
     const val CHAT_CALL_MUTE = "chat_call_mute"
+    const val CHAT_CALL_AUDIO_PROCESSING = "chat_call_audio_processing"
+    const val CHAT_CALL_RECORD = "chat_call_record"
+    const val CHAT_CALL_SAVE_CAPTIONS = "chat_call_save_captions"
 
     const val NEWGROUP_NAME = "newgroup_name"
     const val NEWGROUP_PEER_NPUB = "newgroup_peer_npub"
@@ -156,9 +156,48 @@ fun CallSurface(manager: AppManager, chatId: String, onDismiss: () -> Unit) {
                                 text = callStatusText(callForChat),
                                 style = MaterialTheme.typography.titleMedium,
                             )
+                            if (callForChat.isGroupCall) {
+                                val members = manager.state.currentChat?.members.orEmpty()
+                                callForChat.participants.forEach { participant ->
+                                    val name =
+                                        members.firstOrNull { it.npub == participant.npub }?.name
+                                            ?: participant.npub.take(12)
+                                    val flags =
+                                        listOfNotNull(
+                                            "speaking".takeIf { participant.isSpeaking },
+                                            "muted".takeIf { participant.isMuted },
+                                        )
+                                    Text(
+                                        text = (listOf(name) + flags).joinToString("  \u00B7  "),
+                                        style = MaterialTheme.typography.bodyMedium,
+                                        color =
+                                            if (participant.isSpeaking) {
+                                                MaterialTheme.colorScheme.primary
+                                            } else {
+                                                MaterialTheme.colorScheme.onSurface
+                                            },
+                                    )
+                                }
+                            }
+                            if (callForChat.isRecording || callForChat.participants.any { it.isRecording }) {
+                                Text(
+                                    text = "\u25CF Recording",
+                                    style = MaterialTheme.typography.bodyMedium,
+                                    color = MaterialTheme.colorScheme.error,
+                                )
+                            }
+                            callForChat.captions.forEach { caption ->
+                                Text(
+                                    text = "${if (caption.isLocal) "You" else caption.speakerName}: ${caption.text}",
+                                    style = MaterialTheme.typography.bodyLarge,
+                                )
+                            }
                             callForChat.debug?.let { debug ->
                                 Text(
-                                    text = "tx ${debug.txFrames}  rx ${debug.rxFrames}  drop ${debug.rxDropped}",
+                                    text =
+                                        "tx ${debug.txFrames}  rx ${debug.rxFrames}  drop ${debug.rxDropped}" +
+                                            "  jb ${debug.jitterBufferMs}/${debug.jitterTargetMs}ms" +
+                                            "  plc ${debug.rxConcealed}",
                                     style = MaterialTheme.typography.bodySmall,
                                     color = MaterialTheme.colorScheme.onSurfaceVariant,
                                 )
@@ -211,6 +250,25 @@ fun CallSurface(manager: AppManager, chatId: String, onDismiss: () -> Unit) {
                                 ) {
                                     Text(if (callForChat.isMuted) "Unmute" else "Mute")
                                 }
+                                Button(
+                                    onClick = { manager.dispatch(AppAction.ToggleAudioProcessing) },
+                                    modifier = Modifier.fillMaxWidth().testTag(TestTags.CHAT_CALL_AUDIO_PROCESSING),
+                                ) {
+                                    Text(
+                                        if (callForChat.isAudioProcessingEnabled) {
+                                            "Turn Off Noise & Echo Reduction"
+                                        } else {
+                                            "Turn On Noise & Echo Reduction"
+                                        },
+                                    )
+                                }
+                                Button(
+                                    onClick = { manager.dispatch(AppAction.ToggleCallRecording) },
+                                    enabled = callForChat.status !is CallStatus.Offering,
+                                    modifier = Modifier.fillMaxWidth().testTag(TestTags.CHAT_CALL_RECORD),
+                                ) {
+                                    Text(if (callForChat.isRecording) "Stop Recording" else "Record Call")
+                                }
                                 Button(
                                     onClick = { manager.dispatch(AppAction.EndCall) },
                                     modifier = Modifier.fillMaxWidth().testTag(TestTags.CHAT_CALL_END),
@@ -225,6 +283,14 @@ fun CallSurface(manager: AppManager, chatId: String, onDismiss: () -> Unit) {
                                 ) {
                                     Text("Start Again")
                                 }
+                                if (callForChat.captions.isNotEmpty()) {
+                                    Button(
+                                        onClick = { manager.dispatch(AppAction.SaveCallCaptions(callForChat.callId)) },
+                                        modifier = Modifier.fillMaxWidth().testTag(TestTags.CHAT_CALL_SAVE_CAPTIONS),
+                                    ) {
+                                        Text("Save Captions to Chat")
+                                    }
+                                }
                             }
                         }
 
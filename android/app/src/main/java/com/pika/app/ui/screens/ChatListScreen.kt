@@ -11,9 +11,12 @@ import androidx.compose.foundation.layout.Spacer
 import androidx.compose.foundation.layout.fillMaxWidth
 import androidx.compose.foundation.layout.height
 import androidx.compose.foundation.layout.padding
+import androidx.compose.foundation.layout.size
+import androidx.compose.foundation.layout.width
 import androidx.compose.foundation.lazy.LazyColumn
 import androidx.compose.foundation.lazy.items
 import androidx.compose.material3.Badge
+import androidx.compose.material3.Button
 import androidx.compose.material3.BadgedBox
 import androidx.compose.material3.CenterAlignedTopAppBar
 import androidx.compose.material3.Icon
@@ -42,6 +45,7 @@ import com.pika.app.AppManager
 import com.pika.app.rust.AppAction
 import com.pika.app.rust.AuthState
 import com.pika.app.rust.ChatSummary
+import com.pika.app.rust.InvitationSummary
 import com.pika.app.rust.Screen
 import com.pika.app.ui.Avatar
 import com.pika.app.ui.TestTags
@@ -49,6 +53,8 @@ import androidx.compose.material.icons.Icons
 import androidx.compose.material.icons.filled.Add
 import androidx.compose.material.icons.filled.Archive
 import androidx.compose.material.icons.filled.GroupAdd
+import androidx.compose.material.icons.filled.NotificationsOff
+import androidx.compose.material.icons.filled.Timer
 
 @Composable
 @OptIn(ExperimentalMaterial3Api::class)
@@ -113,6 +119,23 @@ fun ChatListScreen(manager: AppManager, padding: PaddingValues) {
                     PendingShareBanner(summary = shareSummary)
                 }
             }
+            if (!isShareSelectionMode && manager.state.invitations.isNotEmpty()) {
+                item {
+                    Text(
+                        text = "Invitations",
+                        style = MaterialTheme.typography.labelLarge,
+                        color = MaterialTheme.colorScheme.onSurfaceVariant,
+                        modifier = Modifier.padding(horizontal = 16.dp, vertical = 8.dp),
+                    )
+                }
+                items(manager.state.invitations, key = { it.inviteId }) { invite ->
+                    InviteRow(
+                        invite = invite,
+                        onAccept = { manager.dispatch(AppAction.AcceptInvite(invite.inviteId)) },
+                        onDecline = { manager.dispatch(AppAction.DeclineInvite(invite.inviteId)) },
+                    )
+                }
+            }
             items(manager.state.chatList, key = { it.chatId }) { chat ->
                 if (isShareSelectionMode) {
                     Box(
@@ -227,6 +250,44 @@ private fun ArchiveSwipeBackground() {
     }
 }
 
+@Composable
+private fun InviteRow(invite: InvitationSummary, onAccept: () -> Unit, onDecline: () -> Unit) {
+    Row(
+        modifier =
+            Modifier
+                .fillMaxWidth()
+                .padding(horizontal = 16.dp, vertical = 12.dp),
+        horizontalArrangement = Arrangement.spacedBy(12.dp),
+        verticalAlignment = Alignment.CenterVertically,
+    ) {
+        Avatar(
+            name = invite.inviterName,
+            npub = invite.inviterNpub,
+            pictureUrl = invite.inviterPictureUrl,
+        )
+
+        Column(modifier = Modifier.weight(1f)) {
+            Text(
+                text = invite.groupName ?: "Group invite",
+                maxLines = 1,
+                overflow = TextOverflow.Ellipsis,
+                style = MaterialTheme.typography.titleMedium,
+            )
+            Spacer(modifier = Modifier.height(2.dp))
+            Text(
+                text = "From ${invite.inviterName ?: invite.inviterNpub.take(12)} · ${invite.memberCount} members",
+                maxLines = 1,
+                overflow = TextOverflow.Ellipsis,
+                style = MaterialTheme.typography.bodySmall,
+                color = MaterialTheme.colorScheme.onSurfaceVariant,
+            )
+        }
+
+        TextButton(onClick = onDecline) { Text("Decline") }
+        Button(onClick = onAccept) { Text("Accept") }
+    }
+}
+
 @Composable
 private fun ChatRow(chat: ChatSummary, onClick: () -> Unit) {
     val peer = if (!chat.isGroup) chat.members.firstOrNull() else null
@@ -254,12 +315,33 @@ private fun ChatRow(chat: ChatSummary, onClick: () -> Unit) {
         }
 
         Column(modifier = Modifier.weight(1f)) {
-            Text(
-                text = chat.displayName,
-                maxLines = 1,
-                overflow = TextOverflow.Ellipsis,
-                style = MaterialTheme.typography.titleMedium,
-            )
+            Row(verticalAlignment = Alignment.CenterVertically) {
+                Text(
+                    text = chat.displayName,
+                    maxLines = 1,
+                    overflow = TextOverflow.Ellipsis,
+                    style = MaterialTheme.typography.titleMedium,
+                    modifier = Modifier.weight(1f, fill = false),
+                )
+                if (chat.disappearingTimerSecs != null) {
+                    Spacer(modifier = Modifier.width(4.dp))
+                    Icon(
+                        Icons.Default.Timer,
+                        contentDescription = "Disappearing messages on",
+                        tint = MaterialTheme.colorScheme.onSurfaceVariant,
+                        modifier = Modifier.size(14.dp),
+                    )
+                }
+                if (chat.isMuted) {
+                    Spacer(modifier = Modifier.width(4.dp))
+                    Icon(
+                        Icons.Default.NotificationsOff,
+                        contentDescription = "Muted",
+                        tint = MaterialTheme.colorScheme.onSurfaceVariant,
+                        modifier = Modifier.size(14.dp),
+                    )
+                }
+            }
             chat.subtitle?.let { subtitle ->
                 Spacer(modifier = Modifier.height(2.dp))
                 Text(
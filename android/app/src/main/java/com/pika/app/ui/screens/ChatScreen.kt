@@ -63,6 +63,7 @@ import androidx.compose.material3.DropdownMenuItem
 import androidx.compose.material3.FilledIconButton
 import androidx.compose.material3.HorizontalDivider
 import androidx.compose.material3.Icon
+import androidx.compose.material3.LocalContentColor
 import androidx.compose.material3.IconButton
 import androidx.compose.material3.ListItem
 import androidx.compose.material3.MaterialTheme
@@ -104,6 +105,7 @@ import androidx.compose.ui.platform.LocalDensity
 import androidx.compose.ui.platform.LocalLayoutDirection
 import androidx.compose.ui.platform.testTag
 import androidx.compose.ui.text.AnnotatedString
+import androidx.compose.ui.text.font.FontStyle
 import androidx.compose.ui.text.input.ImeAction
 import androidx.compose.ui.text.input.KeyboardType
 import androidx.compose.ui.text.style.TextAlign
@@ -114,7 +116,9 @@ import com.pika.app.rust.AppAction
 import com.pika.app.rust.ChatMediaAttachment
 import com.pika.app.rust.ChatMediaKind
 import com.pika.app.rust.ChatMessage
+import com.pika.app.rust.ChatNotificationMode
 import com.pika.app.rust.MessageDeliveryState
+import com.pika.app.rust.MessageReader
 import com.pika.app.rust.MessageSegment
 import com.pika.app.rust.ReactionSummary
 import com.pika.app.rust.TypingMember
@@ -129,13 +133,17 @@ import androidx.compose.material.icons.filled.Call
 import androidx.compose.material.icons.filled.Close
 import androidx.compose.material.icons.filled.Download
 import androidx.compose.material.icons.filled.Done
+import androidx.compose.material.icons.filled.DoneAll
 import androidx.compose.material.icons.filled.InsertDriveFile
 import androidx.compose.material.icons.filled.ErrorOutline
 import androidx.compose.material.icons.filled.Group
 import androidx.compose.material.icons.filled.Info
 import androidx.compose.material.icons.filled.Mic
+import androidx.compose.material.icons.filled.Notifications
+import androidx.compose.material.icons.filled.NotificationsOff
 import androidx.compose.material.icons.filled.PhotoLibrary
 import androidx.compose.material.icons.filled.Schedule
+import androidx.compose.material.icons.filled.Timer
 import androidx.compose.ui.hapticfeedback.HapticFeedbackType
 import androidx.compose.ui.platform.LocalHapticFeedback
 import androidx.compose.ui.unit.IntOffset
@@ -219,6 +227,9 @@ fun ChatScreen(
     val ctx = LocalContext.current
     var draft by remember { mutableStateOf("") }
     var replyDraft by remember(chat.chatId) { mutableStateOf<ChatMessage?>(null) }
+    var editDraft by remember(chat.chatId) { mutableStateOf<ChatMessage?>(null) }
+    var showDisappearingMenu by remember(chat.chatId) { mutableStateOf(false) }
+    var showNotificationMenu by remember(chat.chatId) { mutableStateOf(false) }
     var showAttachmentSheet by remember(chat.chatId) { mutableStateOf(false) }
     var stagedMedia by remember(chat.chatId) { mutableStateOf<List<StagedMedia>>(emptyList()) }
     var fullscreenImageAttachment by remember(chat.chatId) { mutableStateOf<ChatMediaAttachment?>(null) }
@@ -382,6 +393,12 @@ fun ChatScreen(
         val text = draft.trim()
         if (text.isBlank()) return
         draft = ""
+        val editing = editDraft
+        if (editing != null) {
+            manager.dispatch(AppAction.EditMessage(chat.chatId, editing.id, text))
+            editDraft = null
+            return
+        }
         manager.dispatch(
             AppAction.SendMessage(chat.chatId, text, null, replyDraft?.id),
         )
@@ -641,11 +658,29 @@ fun ChatScreen(
                                 size = 30.dp,
                             )
                         }
-                        Text(
-                            text = title,
-                            maxLines = 1,
-                            overflow = TextOverflow.Ellipsis,
-                        )
+                        Column {
+                            Text(
+                                text = title,
+                                maxLines = 1,
+                                overflow = TextOverflow.Ellipsis,
+                            )
+                            chat.disappearingTimerSecs?.let { secs ->
+                                Text(
+                                    text = "Disappearing · ${disappearingTimerLabel(secs)}",
+                                    style = MaterialTheme.typography.labelSmall,
+                                    color = MaterialTheme.colorScheme.onSurfaceVariant,
+                                    maxLines = 1,
+                                )
+                            }
+                            notificationModeLabel(chat.notificationMode)?.let { label ->
+                                Text(
+                                    text = label,
+                                    style = MaterialTheme.typography.labelSmall,
+                                    color = MaterialTheme.colorScheme.onSurfaceVariant,
+                                    maxLines = 1,
+                                )
+                            }
+                        }
                     }
                 },
                 navigationIcon = {
@@ -674,6 +709,109 @@ fun ChatScreen(
                         Icon(Icons.Default.Call, contentDescription = "Call")
                     }
 
+                    Box {
+                        val notificationMode = chat.notificationMode
+                        IconButton(
+                            onClick = { showNotificationMenu = true },
+                            modifier = Modifier.testTag(TestTags.CHAT_NOTIFICATION_MODE),
+                        ) {
+                            Icon(
+                                if (notificationMode is ChatNotificationMode.All) {
+                                    Icons.Default.Notifications
+                                } else {
+                                    Icons.Default.NotificationsOff
+                                },
+                                contentDescription = "Notifications",
+                            )
+                        }
+                        DropdownMenu(
+                            expanded = showNotificationMenu,
+                            onDismissRequest = { showNotificationMenu = false },
+                        ) {
+                            fun select(mode: ChatNotificationMode) {
+                                showNotificationMenu = false
+                                manager.dispatch(AppAction.SetChatNotificationMode(chat.chatId, mode))
+                            }
+                            DropdownMenuItem(
+                                text = { Text("All messages") },
+                                trailingIcon = {
+                                    if (notificationMode is ChatNotificationMode.All) {
+                                        Icon(Icons.Default.Done, contentDescription = null)
+                                    }
+                                },
+                                onClick = { select(ChatNotificationMode.All) },
+                            )
+                            DropdownMenuItem(
+                                text = { Text("Mentions only") },
+                                trailingIcon = {
+                                    if (notificationMode is ChatNotificationMode.MentionsOnly) {
+                                        Icon(Icons.Default.Done, contentDescription = null)
+                                    }
+                                },
+                                onClick = { select(ChatNotificationMode.MentionsOnly) },
+                            )
+                            SNOOZE_OPTIONS.forEach { (label, secs) ->
+                                DropdownMenuItem(
+                                    text = { Text("Mute for $label") },
+                                    onClick = {
+                                        val until = System.currentTimeMillis() / 1000 + secs
+                                        select(ChatNotificationMode.MutedUntil(until))
+                                    },
+                                )
+                            }
+                            DropdownMenuItem(
+                                text = { Text("Mute") },
+                                trailingIcon = {
+                                    if (notificationMode is ChatNotificationMode.Muted) {
+                                        Icon(Icons.Default.Done, contentDescription = null)
+                                    }
+                                },
+                                onClick = { select(ChatNotificationMode.Muted) },
+                            )
+                        }
+                    }
+
+                    Box {
+                        IconButton(
+                            onClick = { showDisappearingMenu = true },
+                            modifier = Modifier.testTag(TestTags.CHAT_DISAPPEARING_TIMER),
+                        ) {
+                            Icon(
+                                Icons.Default.Timer,
+                                contentDescription = "Disappearing messages",
+                                tint =
+                                    if (chat.disappearingTimerSecs != null) {
+                                        MaterialTheme.colorScheme.primary
+                                    } else {
+                                        LocalContentColor.current
+                                    },
+                            )
+                        }
+                        DropdownMenu(
+                            expanded = showDisappearingMenu,
+                            onDismissRequest = { showDisappearingMenu = false },
+                        ) {
+                            DISAPPEARING_TIMER_OPTIONS.forEach { option ->
+                                DropdownMenuItem(
+                                    text = {
+                                        Text(option?.let { disappearingTimerLabel(it) } ?: "Off")
+                                    },
+                                    trailingIcon = {
+                                        if (chat.disappearingTimerSecs == option) {
+                                            Icon(Icons.Default.Done, contentDescription = null)
+                                        }
+                                    },
+                                    onClick = {
+                                        showDisappearingMenu = false
+                                        manager.dispatch(
+                                            AppAction.SetDisappearingTimer(chat.chatId, option),
+                                        )
+                                    },
+                                )
+                            }
+                        }
+                    }
+
                     if (chat.isGroup) {
                         IconButton(
                             onClick = {
@@ -736,8 +874,21 @@ fun ChatScreen(
                                 showSender = chat.isGroup,
                                 messagesById = messagesById,
                                 onReplyTo = { replyMessage ->
+                                    editDraft = null
                                     replyDraft = replyMessage
                                 },
+                                onEdit = { editMessage ->
+                                    replyDraft = null
+                                    editDraft = editMessage
+                                    draft = editMessage.content
+                                },
+                                onDelete = { messageId ->
+                                    if (editDraft?.id == messageId) {
+                                        editDraft = null
+                                        draft = ""
+                                    }
+                                    manager.dispatch(AppAction.DeleteMessage(chat.chatId, messageId))
+                                },
                                 onJumpToMessage = { targetId ->
                                     val index = reversedIndexById[targetId] ?: return@MessageBubble
                                     coroutineScope.launch {
@@ -867,6 +1018,16 @@ fun ChatScreen(
                 }
 
                 if (activeVoiceRecording == null) {
+                    editDraft?.let { editing ->
+                        ReplyComposerPreview(
+                            message = editing,
+                            title = "Editing message",
+                            onClear = {
+                                editDraft = null
+                                draft = ""
+                            },
+                        )
+                    }
                     replyDraft?.let { replying ->
                         ReplyComposerPreview(
                             message = replying,
@@ -1095,6 +1256,35 @@ private fun LazyListState.isNearBottomForReverseLayout(tolerancePx: Int = 100):
     return firstVisibleItemScrollOffset <= tolerancePx
 }
 
+private val DISAPPEARING_TIMER_OPTIONS: List<ULong?> =
+    listOf(null, 300uL, 3_600uL, 86_400uL, 604_800uL)
+
+private fun disappearingTimerLabel(secs: ULong): String =
+    when {
+        secs >= 604_800uL && secs % 604_800uL == 0uL -> plural(secs / 604_800uL, "week")
+        secs >= 86_400uL && secs % 86_400uL == 0uL -> plural(secs / 86_400uL, "day")
+        secs >= 3_600uL && secs % 3_600uL == 0uL -> plural(secs / 3_600uL, "hour")
+        secs >= 60uL && secs % 60uL == 0uL -> plural(secs / 60uL, "minute")
+        else -> plural(secs, "second")
+    }
+
+private val SNOOZE_OPTIONS: List<Pair<String, Long>> =
+    listOf("1 hour" to 3_600L, "8 hours" to 28_800L, "1 day" to 86_400L, "1 week" to 604_800L)
+
+private fun notificationModeLabel(mode: ChatNotificationMode): String? =
+    when (mode) {
+        is ChatNotificationMode.All -> null
+        is ChatNotificationMode.MentionsOnly -> "Mentions only"
+        is ChatNotificationMode.MutedUntil -> {
+            val end = java.text.DateFormat.getTimeInstance(java.text.DateFormat.SHORT)
+                .format(java.util.Date(mode.until * 1000))
+            "Muted until $end"
+        }
+        is ChatNotificationMode.Muted -> "Muted"
+    }
+
+private fun plural(count: ULong, unit: String): String = if (count == 1uL) "1 $unit" else "$count ${unit}s"
+
 private fun chatTitle(chat: com.pika.app.rust.ChatViewState, selfPubkey: String?): String {
     if (chat.isGroup) {
         return chat.groupName?.trim().takeIf { !it.isNullOrBlank() } ?: "Group chat"
@@ -1172,6 +1362,7 @@ private fun messageBubbleShape(position: GroupedBubblePosition, isMine: Boolean)
 @Composable
 private fun DeliveryStateIcon(
     delivery: MessageDeliveryState,
+    seenBy: List<MessageReader>,
     tint: androidx.compose.ui.graphics.Color,
     onClick: (() -> Unit)? = null,
 ) {
@@ -1195,8 +1386,8 @@ private fun DeliveryStateIcon(
             )
         is MessageDeliveryState.Sent ->
             Icon(
-                imageVector = Icons.Default.Done,
-                contentDescription = "Sent",
+                imageVector = if (seenBy.isEmpty()) Icons.Default.Done else Icons.Default.DoneAll,
+                contentDescription = if (seenBy.isEmpty()) "Sent" else "Seen",
                 tint = tint,
                 modifier = iconModifier,
             )
@@ -1210,6 +1401,17 @@ private fun DeliveryStateIcon(
     }
 }
 
+private fun seenByText(readers: List<MessageReader>): String? {
+    if (readers.isEmpty()) return null
+    val names = readers.mapNotNull { it.name }
+    val first = names.firstOrNull() ?: return "Seen"
+    return when {
+        readers.size == 1 -> "Seen by $first"
+        readers.size == 2 && names.size == 2 -> "Seen by $first, ${names[1]}"
+        else -> "Seen by $first and ${readers.size - 1} others"
+    }
+}
+
 @Composable
 private fun NewMessagesDividerRow() {
     Row(
@@ -1517,6 +1719,8 @@ private fun MediaAttachmentContent(
             if (hasLocalFile) {
                 VoiceAttachmentPlayerRow(
                     localPath = attachment.localPath ?: "",
+                    waveform = attachment.waveform.orEmpty(),
+                    durationMs = attachment.durationMs,
                     isMine = isMine,
                     modifier = Modifier.padding(horizontal = 10.dp, vertical = 8.dp),
                 )
@@ -1542,8 +1746,15 @@ private fun MediaAttachmentContent(
                             style = MaterialTheme.typography.bodyMedium,
                             color = primaryContentColor,
                         )
+                        val durationMs = attachment.durationMs
                         Text(
-                            text = attachment.mimeType.ifBlank { "audio/mp4" },
+                            text =
+                                if (durationMs != null) {
+                                    val totalSeconds = (durationMs.toLong() / 1_000L).toInt()
+                                    "%d:%02d".format(totalSeconds / 60, totalSeconds % 60)
+                                } else {
+                                    attachment.mimeType.ifBlank { "audio/mp4" }
+                                },
                             maxLines = 1,
                             overflow = TextOverflow.Ellipsis,
                             style = MaterialTheme.typography.bodySmall,
@@ -1670,6 +1881,8 @@ private fun MessageBubble(
     showSender: Boolean,
     messagesById: Map<String, ChatMessage>,
     onReplyTo: (ChatMessage) -> Unit,
+    onEdit: (ChatMessage) -> Unit,
+    onDelete: (String) -> Unit,
     onJumpToMessage: (String) -> Unit,
     onRetryMessage: (String) -> Unit,
     onReact: (String, String) -> Unit,
@@ -1699,7 +1912,12 @@ private fun MessageBubble(
     val replyTarget = remember(message.replyToMessageId, messagesById) {
         message.replyToMessageId?.let { messagesById[it] }
     }
-    val formattedTime = message.displayTimestamp
+    val formattedTime =
+        if (message.editedAt != null && !message.deleted) {
+            "${message.displayTimestamp} · edited"
+        } else {
+            message.displayTimestamp
+        }
     val showFooter = position == GroupedBubblePosition.Bottom || position == GroupedBubblePosition.Single
     // Swipe-to-reply state
     val swipeOffset = remember { Animatable(0f) }
@@ -1729,7 +1947,28 @@ private fun MessageBubble(
         }
 
         val hypernote = message.hypernote
-        if (hypernote != null) {
+        if (message.deleted) {
+            Row(
+                modifier = Modifier.fillMaxWidth(),
+                horizontalArrangement = if (isMine) Arrangement.End else Arrangement.Start,
+            ) {
+                Box(
+                    modifier =
+                        Modifier
+                            .clip(messageBubbleShape(position = position, isMine = isMine))
+                            .background(MaterialTheme.colorScheme.surfaceVariant.copy(alpha = 0.5f))
+                            .padding(horizontal = 12.dp, vertical = 9.dp)
+                            .widthIn(max = 280.dp),
+                ) {
+                    Text(
+                        text = "This message was deleted",
+                        style = MaterialTheme.typography.bodyMedium,
+                        fontStyle = FontStyle.Italic,
+                        color = MaterialTheme.colorScheme.onSurfaceVariant,
+                    )
+                }
+            }
+        } else if (hypernote != null) {
             HypernoteRenderer(
                 messageId = message.id,
                 hypernote = hypernote,
@@ -1850,6 +2089,22 @@ private fun MessageBubble(
                                                 showMenu = false
                                             },
                                         )
+                                        if (isMine) {
+                                            DropdownMenuItem(
+                                                text = { Text("Edit") },
+                                                onClick = {
+                                                    onEdit(message)
+                                                    showMenu = false
+                                                },
+                                            )
+                                            DropdownMenuItem(
+                                                text = { Text("Delete", color = MaterialTheme.colorScheme.error) },
+                                                onClick = {
+                                                    onDelete(message.id)
+                                                    showMenu = false
+                                                },
+                                            )
+                                        }
                                         if (message.delivery is MessageDeliveryState.Failed) {
                                             DropdownMenuItem(
                                                 text = { Text("Retry") },
@@ -1911,6 +2166,7 @@ private fun MessageBubble(
                     Spacer(Modifier.width(4.dp))
                     DeliveryStateIcon(
                         delivery = message.delivery,
+                        seenBy = message.seenBy,
                         tint = MaterialTheme.colorScheme.onSurfaceVariant,
                         onClick =
                             if (message.delivery is MessageDeliveryState.Failed) {
@@ -1919,6 +2175,16 @@ private fun MessageBubble(
                                 null
                             },
                     )
+                    seenByText(message.seenBy)?.let { seen ->
+                        Spacer(Modifier.width(4.dp))
+                        Text(
+                            text = seen,
+                            style = MaterialTheme.typography.labelSmall,
+                            color = MaterialTheme.colorScheme.onSurfaceVariant,
+                            maxLines = 1,
+                            overflow = TextOverflow.Ellipsis,
+                        )
+                    }
                 }
             }
         }
@@ -2020,6 +2286,7 @@ private fun ReplyReferencePreview(
 private fun ReplyComposerPreview(
     message: ChatMessage,
     onClear: () -> Unit,
+    title: String? = null,
 ) {
     val sender =
         when {
@@ -2047,7 +2314,7 @@ private fun ReplyComposerPreview(
         )
         Column(modifier = Modifier.weight(1f), verticalArrangement = Arrangement.spacedBy(2.dp)) {
             Text(
-                text = "Replying to $sender",
+                text = title ?: "Replying to $sender",
                 style = MaterialTheme.typography.labelSmall,
                 color = MaterialTheme.colorScheme.onSurfaceVariant,
                 maxLines = 1,
@@ -1,5 +1,6 @@
 package com.pika.app.ui.screens
 
+import androidx.compose.foundation.Image
 import androidx.compose.foundation.clickable
 import androidx.compose.foundation.layout.Arrangement
 import androidx.compose.foundation.layout.Box
@@ -11,13 +12,19 @@ import androidx.compose.foundation.layout.fillMaxSize
 import androidx.compose.foundation.layout.fillMaxWidth
 import androidx.compose.foundation.layout.height
 import androidx.compose.foundation.layout.padding
+import androidx.compose.foundation.layout.size
 import androidx.compose.foundation.layout.width
 import androidx.compose.foundation.lazy.LazyColumn
 import androidx.compose.foundation.lazy.items
 import androidx.compose.material.icons.Icons
+import androidx.compose.material.icons.filled.AdminPanelSettings
 import androidx.compose.material.icons.filled.ArrowBack
+import androidx.compose.material.icons.filled.Check
+import androidx.compose.material.icons.filled.Close
 import androidx.compose.material.icons.filled.Edit
+import androidx.compose.material.icons.filled.Link
 import androidx.compose.material.icons.filled.PersonRemove
+import androidx.compose.material.icons.filled.RemoveModerator
 import androidx.compose.material3.AlertDialog
 import androidx.compose.material3.Button
 import androidx.compose.material3.ButtonDefaults
@@ -27,6 +34,7 @@ import androidx.compose.material3.Icon
 import androidx.compose.material3.IconButton
 import androidx.compose.material3.MaterialTheme
 import androidx.compose.material3.OutlinedTextField
+import androidx.compose.material3.Switch
 import androidx.compose.material3.Scaffold
 import androidx.compose.material3.Text
 import androidx.compose.material3.TextButton
@@ -38,17 +46,25 @@ import androidx.compose.runtime.remember
 import androidx.compose.runtime.setValue
 import androidx.compose.ui.Alignment
 import androidx.compose.ui.Modifier
+import androidx.compose.ui.draw.clip
+import androidx.compose.ui.graphics.asImageBitmap
+import androidx.compose.ui.platform.LocalClipboardManager
 import androidx.compose.ui.platform.testTag
+import androidx.compose.ui.text.AnnotatedString
 import androidx.compose.ui.text.style.TextOverflow
 import androidx.compose.ui.unit.dp
 import com.pika.app.AppManager
 import com.pika.app.rust.AppAction
 import com.pika.app.rust.AuthState
+import com.pika.app.rust.GroupInviteLinkInfo
+import com.pika.app.rust.GroupJoinRequest
 import com.pika.app.rust.MemberInfo
 import com.pika.app.rust.MyProfileState
+import com.pika.app.rust.groupInviteLinkUrl
 import com.pika.app.rust.isValidPeerKey
 import com.pika.app.rust.normalizePeerKey
 import com.pika.app.ui.Avatar
+import com.pika.app.ui.QrCode
 import com.pika.app.ui.TestTags
 
 @Composable
@@ -71,6 +87,8 @@ fun GroupInfoScreen(manager: AppManager, chatId: String, padding: PaddingValues)
     var showLeaveDialog by remember { mutableStateOf(false) }
     var showGroupProfileEditor by remember { mutableStateOf(false) }
     var memberToRemove by remember { mutableStateOf<MemberInfo?>(null) }
+    var inviteRequiresApproval by remember { mutableStateOf(false) }
+    var shownInviteLinkUrl by remember { mutableStateOf<String?>(null) }
 
     val (myPubkey, myNpub) = when (val a = manager.state.auth) {
         is AuthState.LoggedIn -> a.pubkey to a.npub
@@ -209,6 +227,15 @@ fun GroupInfoScreen(manager: AppManager, chatId: String, padding: PaddingValues)
                     member = member,
                     isAdmin = isAdmin,
                     onRemove = { memberToRemove = member },
+                    onToggleAdmin = {
+                        val action =
+                            if (member.isAdmin) {
+                                AppAction.DemoteAdmin(chatId, member.pubkey)
+                            } else {
+                                AppAction.PromoteAdmin(chatId, member.pubkey)
+                            }
+                        manager.dispatch(action)
+                    },
                     onTap = { manager.dispatch(AppAction.OpenPeerProfile(member.pubkey)) },
                 )
             }
@@ -251,6 +278,76 @@ fun GroupInfoScreen(manager: AppManager, chatId: String, padding: PaddingValues)
                         }
                     }
                 }
+
+                // Join requests waiting for approval
+                if (chat.joinRequests.isNotEmpty()) {
+                    item {
+                        HorizontalDivider()
+                        Spacer(Modifier.height(4.dp))
+                        Text(
+                            "Join Requests (${chat.joinRequests.size})",
+                            style = MaterialTheme.typography.titleSmall,
+                        )
+                    }
+                    items(chat.joinRequests, key = { "join-${it.pubkey}" }) { request ->
+                        JoinRequestRow(
+                            request = request,
+                            onApprove = { manager.dispatch(AppAction.ApproveJoinRequest(chatId, request.pubkey)) },
+                            onDecline = { manager.dispatch(AppAction.DeclineJoinRequest(chatId, request.pubkey)) },
+                        )
+                    }
+                }
+
+                // Invite links
+                item {
+                    HorizontalDivider()
+                    Spacer(Modifier.height(4.dp))
+                    Text(
+                        "Invite Links",
+                        style = MaterialTheme.typography.titleSmall,
+                    )
+                    Row(
+                        modifier = Modifier.fillMaxWidth(),
+                        verticalAlignment = Alignment.CenterVertically,
+                    ) {
+                        Text(
+                            "Require approval",
+                            style = MaterialTheme.typography.bodyMedium,
+                            modifier = Modifier.weight(1f),
+                        )
+                        Switch(
+                            checked = inviteRequiresApproval,
+                            onCheckedChange = { inviteRequiresApproval = it },
+                        )
+                    }
+                    Row(horizontalArrangement = Arrangement.spacedBy(12.dp)) {
+                        Button(
+                            onClick = {
+                                manager.dispatch(
+                                    AppAction.CreateGroupInviteLink(chatId, true, inviteRequiresApproval),
+                                )
+                            },
+                        ) {
+                            Text("New link")
+                        }
+                        TextButton(
+                            onClick = {
+                                manager.dispatch(
+                                    AppAction.CreateGroupInviteLink(chatId, false, inviteRequiresApproval),
+                                )
+                            },
+                        ) {
+                            Text("One-time link")
+                        }
+                    }
+                }
+                items(chat.inviteLinks, key = { "link-${it.token}" }) { link ->
+                    InviteLinkRow(
+                        link = link,
+                        onShow = { shownInviteLinkUrl = inviteLinkUrl(link) },
+                        onRevoke = { manager.dispatch(AppAction.RevokeGroupInviteLink(chatId, link.token)) },
+                    )
+                }
             }
 
             // Leave group
@@ -304,6 +401,11 @@ fun GroupInfoScreen(manager: AppManager, chatId: String, padding: PaddingValues)
         )
     }
 
+    // Invite link QR dialog
+    shownInviteLinkUrl?.let { url ->
+        InviteLinkDialog(url = url, onDismiss = { shownInviteLinkUrl = null })
+    }
+
     // Remove member confirmation dialog
     memberToRemove?.let { member ->
         AlertDialog(
@@ -332,6 +434,7 @@ private fun MemberRow(
     member: MemberInfo,
     isAdmin: Boolean,
     onRemove: () -> Unit,
+    onToggleAdmin: () -> Unit,
     onTap: () -> Unit,
 ) {
     Row(
@@ -372,6 +475,12 @@ private fun MemberRow(
             )
         }
         if (isAdmin) {
+            IconButton(onClick = onToggleAdmin) {
+                Icon(
+                    if (member.isAdmin) Icons.Default.RemoveModerator else Icons.Default.AdminPanelSettings,
+                    contentDescription = if (member.isAdmin) "Remove admin" else "Make admin",
+                )
+            }
             IconButton(onClick = onRemove) {
                 Icon(
                     Icons.Default.PersonRemove,
@@ -383,6 +492,134 @@ private fun MemberRow(
     }
 }
 
+private fun inviteLinkUrl(link: GroupInviteLinkInfo): String? =
+    groupInviteLinkUrl(AppManager.NOSTR_CONNECT_CALLBACK_SCHEME, link.adminNpub, link.token)
+
+@Composable
+private fun InviteLinkRow(
+    link: GroupInviteLinkInfo,
+    onShow: () -> Unit,
+    onRevoke: () -> Unit,
+) {
+    val details =
+        buildList {
+            add(if (link.multiUse) "Used ${link.useCount} times" else "One-time")
+            if (link.requiresApproval) add("requires approval")
+        }.joinToString(" · ")
+    Row(
+        modifier = Modifier
+            .fillMaxWidth()
+            .clickable { onShow() }
+            .padding(vertical = 8.dp),
+        horizontalArrangement = Arrangement.spacedBy(12.dp),
+        verticalAlignment = Alignment.CenterVertically,
+    ) {
+        Icon(Icons.Default.Link, contentDescription = null)
+        Column(modifier = Modifier.weight(1f)) {
+            Text(
+                if (link.multiUse) "Invite link" else "One-time link",
+                style = MaterialTheme.typography.bodyLarge,
+            )
+            Text(
+                details,
+                style = MaterialTheme.typography.bodySmall,
+                color = MaterialTheme.colorScheme.onSurfaceVariant,
+            )
+        }
+        IconButton(onClick = onRevoke) {
+            Icon(
+                Icons.Default.Close,
+                contentDescription = "Revoke link",
+                tint = MaterialTheme.colorScheme.error,
+            )
+        }
+    }
+}
+
+@Composable
+private fun JoinRequestRow(
+    request: GroupJoinRequest,
+    onApprove: () -> Unit,
+    onDecline: () -> Unit,
+) {
+    Row(
+        modifier = Modifier.fillMaxWidth().padding(vertical = 8.dp),
+        horizontalArrangement = Arrangement.spacedBy(12.dp),
+        verticalAlignment = Alignment.CenterVertically,
+    ) {
+        Avatar(
+            name = request.name,
+            npub = request.npub,
+            pictureUrl = request.pictureUrl,
+            size = 40.dp,
+        )
+        Text(
+            request.name ?: truncatedNpub(request.npub),
+            style = MaterialTheme.typography.bodyLarge,
+            maxLines = 1,
+            overflow = TextOverflow.Ellipsis,
+            modifier = Modifier.weight(1f),
+        )
+        IconButton(onClick = onDecline) {
+            Icon(
+                Icons.Default.Close,
+                contentDescription = "Decline",
+                tint = MaterialTheme.colorScheme.error,
+            )
+        }
+        IconButton(onClick = onApprove) {
+            Icon(
+                Icons.Default.Check,
+                contentDescription = "Approve",
+                tint = MaterialTheme.colorScheme.primary,
+            )
+        }
+    }
+}
+
+@Composable
+private fun InviteLinkDialog(url: String, onDismiss: () -> Unit) {
+    val clipboard = LocalClipboardManager.current
+    val qr = remember(url) { QrCode.encode(url, 512).asImageBitmap() }
+    AlertDialog(
+        onDismissRequest = onDismiss,
+        title = { Text("Invite Link") },
+        text = {
+            Column(
+                modifier = Modifier.fillMaxWidth(),
+                horizontalAlignment = Alignment.CenterHorizontally,
+                verticalArrangement = Arrangement.spacedBy(12.dp),
+            ) {
+                Image(
+                    bitmap = qr,
+                    contentDescription = "Invite link QR",
+                    modifier = Modifier.size(200.dp).clip(MaterialTheme.shapes.medium),
+                )
+                Text(
+                    url,
+                    style = MaterialTheme.typography.bodySmall,
+                    color = MaterialTheme.colorScheme.onSurfaceVariant,
+                    maxLines = 2,
+                    overflow = TextOverflow.Ellipsis,
+                )
+            }
+        },
+        confirmButton = {
+            TextButton(onClick = {
+                clipboard.setText(AnnotatedString(url))
+                onDismiss()
+            }) {
+                Text("Copy")
+            }
+        },
+        dismissButton = {
+            TextButton(onClick = onDismiss) {
+                Text("Close")
+            }
+        },
+    )
+}
+
 @Composable
 private fun GroupProfileEditorDialog(
     currentProfile: MyProfileState?,
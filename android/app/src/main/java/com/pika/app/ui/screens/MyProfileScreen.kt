@@ -34,6 +34,7 @@ import androidx.compose.material3.MaterialTheme
 import androidx.compose.material3.ModalBottomSheet
 import androidx.compose.material3.OutlinedButton
 import androidx.compose.material3.OutlinedTextField
+import androidx.compose.material3.Switch
 import androidx.compose.material3.Text
 import androidx.compose.material3.TextButton
 import androidx.compose.material3.rememberModalBottomSheetState
@@ -43,6 +44,7 @@ import androidx.compose.runtime.LaunchedEffect
 import androidx.compose.runtime.getValue
 import androidx.compose.runtime.mutableStateOf
 import androidx.compose.runtime.remember
+import androidx.compose.runtime.rememberCoroutineScope
 import androidx.compose.runtime.setValue
 import androidx.compose.ui.Alignment
 import androidx.compose.ui.Modifier
@@ -53,6 +55,7 @@ import androidx.compose.ui.platform.LocalContext
 import androidx.compose.ui.platform.testTag
 import androidx.compose.ui.text.AnnotatedString
 import androidx.compose.ui.text.input.KeyboardType
+import androidx.compose.ui.text.input.PasswordVisualTransformation
 import androidx.compose.ui.text.style.TextOverflow
 import androidx.compose.ui.unit.dp
 import androidx.compose.material.icons.Icons
@@ -61,11 +64,17 @@ import androidx.compose.material.icons.filled.Visibility
 import androidx.compose.material.icons.filled.VisibilityOff
 import androidx.core.content.pm.PackageInfoCompat
 import com.pika.app.AppManager
+import com.pika.app.rust.AccountSummary
 import com.pika.app.rust.AppAction
 import com.pika.app.ui.Avatar
 import com.pika.app.ui.QrCode
 import com.pika.app.ui.TestTags
 import java.io.ByteArrayOutputStream
+import java.io.File
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.launch
+import kotlinx.coroutines.withContext
 
 @OptIn(ExperimentalMaterial3Api::class)
 @Composable
@@ -88,6 +97,11 @@ fun MyProfileSheet(
     var isLoadingPhoto by remember { mutableStateOf(false) }
     var buildNumberTapCount by remember { mutableStateOf(0) }
     val developerModeEnabled = manager.state.developerMode
+    val readReceiptsEnabled = manager.state.readReceiptsEnabled
+    var backupPassphrase by remember { mutableStateOf("") }
+    var newAccountNsec by remember { mutableStateOf("") }
+    var accountPendingRemoval by remember { mutableStateOf<AccountSummary?>(null) }
+    val coroutineScope = rememberCoroutineScope()
 
     val nsec = remember { manager.getNsec() }
 
@@ -119,6 +133,57 @@ fun MyProfileSheet(
         }
     }
 
+    // Rust reads/writes plain paths, so backups go through a cache file and are
+    // copied to/from the document the user picked.
+    val exportBackupLauncher = rememberLauncherForActivityResult(
+        ActivityResultContracts.CreateDocument("application/octet-stream"),
+    ) { uri: Uri? ->
+        if (uri == null) return@rememberLauncherForActivityResult
+        val passphrase = backupPassphrase
+        backupPassphrase = ""
+        val staged = File(ctx.cacheDir, "pika-backup.pikabackup")
+        staged.delete()
+        manager.dispatch(AppAction.ExportBackup(staged.absolutePath, passphrase))
+        coroutineScope.launch {
+            // The archive is renamed into place once complete; failures surface as a toast.
+            repeat(120) {
+                if (staged.exists()) {
+                    withContext(Dispatchers.IO) {
+                        ctx.contentResolver.openOutputStream(uri)?.use { out ->
+                            staged.inputStream().use { it.copyTo(out) }
+                        }
+                        staged.delete()
+                    }
+                    return@launch
+                }
+                delay(250)
+            }
+        }
+    }
+
+    val importBackupLauncher = rememberLauncherForActivityResult(
+        ActivityResultContracts.OpenDocument(),
+    ) { uri: Uri? ->
+        if (uri == null) return@rememberLauncherForActivityResult
+        val passphrase = backupPassphrase
+        backupPassphrase = ""
+        coroutineScope.launch {
+            val staged = File(ctx.cacheDir, "pika-restore.pikabackup")
+            val copied = withContext(Dispatchers.IO) {
+                runCatching {
+                    ctx.contentResolver.openInputStream(uri)?.use { input ->
+                        staged.outputStream().use { input.copyTo(it) }
+                    } != null
+                }.getOrDefault(false)
+            }
+            if (copied) {
+                manager.dispatch(AppAction.ImportBackup(staged.absolutePath, passphrase))
+            } else {
+                Toast.makeText(ctx, "Could not read that backup", Toast.LENGTH_SHORT).show()
+            }
+        }
+    }
+
     val photoLauncher = rememberLauncherForActivityResult(
         ActivityResultContracts.GetContent(),
     ) { uri: Uri? ->
@@ -273,6 +338,132 @@ fun MyProfileSheet(
                 }
             }
 
+            item {
+                ProfileSectionCard(title = "Privacy") {
+                    Row(verticalAlignment = Alignment.CenterVertically) {
+                        Column(modifier = Modifier.weight(1f)) {
+                            Text("Send read receipts", style = MaterialTheme.typography.bodyMedium)
+                            Text(
+                                "Let others in a chat see when you've read their messages.",
+                                style = MaterialTheme.typography.bodySmall,
+                                color = MaterialTheme.colorScheme.onSurfaceVariant,
+                            )
+                        }
+                        Switch(
+                            checked = readReceiptsEnabled,
+                            onCheckedChange = { enabled ->
+                                manager.dispatch(AppAction.SetReadReceiptsEnabled(enabled))
+                            },
+                        )
+                    }
+                }
+            }
+
+            item {
+                ProfileSectionCard(title = "Accounts") {
+                    manager.state.accounts.forEach { account ->
+                        val label = account.name?.takeIf { it.isNotBlank() } ?: account.npub
+                        Row(
+                            verticalAlignment = Alignment.CenterVertically,
+                            horizontalArrangement = Arrangement.spacedBy(12.dp),
+                        ) {
+                            Avatar(
+                                name = account.name,
+                                npub = account.npub,
+                                pictureUrl = account.pictureUrl,
+                                size = 32.dp,
+                            )
+                            Column(modifier = Modifier.weight(1f)) {
+                                Text(
+                                    label,
+                                    style = MaterialTheme.typography.bodyMedium,
+                                    maxLines = 1,
+                                    overflow = TextOverflow.Ellipsis,
+                                )
+                                val status =
+                                    when {
+                                        account.isActive -> "Active"
+                                        account.unreadCount > 0u -> "${account.unreadCount} unread"
+                                        else -> null
+                                    }
+                                if (status != null) {
+                                    Text(
+                                        status,
+                                        style = MaterialTheme.typography.bodySmall,
+                                        color = MaterialTheme.colorScheme.onSurfaceVariant,
+                                    )
+                                }
+                            }
+                            if (!account.isActive) {
+                                TextButton(
+                                    onClick = {
+                                        manager.switchAccount(account.pubkey)
+                                        onDismiss()
+                                    },
+                                ) {
+                                    Text("Switch")
+                                }
+                                TextButton(onClick = { accountPendingRemoval = account }) {
+                                    Text("Remove", color = MaterialTheme.colorScheme.error)
+                                }
+                            }
+                        }
+                    }
+                    OutlinedTextField(
+                        value = newAccountNsec,
+                        onValueChange = { newAccountNsec = it },
+                        label = { Text("nsec of another account") },
+                        singleLine = true,
+                        visualTransformation = PasswordVisualTransformation(),
+                        keyboardOptions = KeyboardOptions(keyboardType = KeyboardType.Password),
+                        modifier = Modifier.fillMaxWidth(),
+                    )
+                    OutlinedButton(
+                        onClick = {
+                            manager.addAccount(newAccountNsec)
+                            newAccountNsec = ""
+                            onDismiss()
+                        },
+                        enabled = newAccountNsec.isNotBlank(),
+                    ) {
+                        Text("Add account")
+                    }
+                }
+            }
+
+            item {
+                ProfileSectionCard(title = "Backup") {
+                    Text(
+                        "Export your chats and encryption state, protected by a passphrase. Restoring replaces this device's data for the same account.",
+                        style = MaterialTheme.typography.bodySmall,
+                        color = MaterialTheme.colorScheme.onSurfaceVariant,
+                    )
+                    OutlinedTextField(
+                        value = backupPassphrase,
+                        onValueChange = { backupPassphrase = it },
+                        label = { Text("Backup passphrase") },
+                        singleLine = true,
+                        visualTransformation = PasswordVisualTransformation(),
+                        keyboardOptions = KeyboardOptions(keyboardType = KeyboardType.Password),
+                        modifier = Modifier.fillMaxWidth(),
+                    )
+                    Row(horizontalArrangement = Arrangement.spacedBy(8.dp)) {
+                        OutlinedButton(
+                            onClick = { exportBackupLauncher.launch("pika-backup.pikabackup") },
+                            enabled = backupPassphrase.isNotEmpty(),
+                        ) {
+                            Text("Export backup")
+                        }
+                        OutlinedButton(
+                            onClick = { importBackupLauncher.launch(arrayOf("*/*")) },
+                            enabled = backupPassphrase.isNotEmpty(),
+                        ) {
+                            Text("Restore backup")
+                        }
+                    }
+                }
+            }
+
             // App version / build
             item {
                 ProfileSectionCard(title = "App Version") {
@@ -404,6 +595,29 @@ fun MyProfileSheet(
         )
     }
 
+    accountPendingRemoval?.let { account ->
+        AlertDialog(
+            onDismissRequest = { accountPendingRemoval = null },
+            title = { Text("Remove account?") },
+            text = { Text("Its chats and keys will be deleted from this device.") },
+            confirmButton = {
+                TextButton(
+                    onClick = {
+                        manager.removeAccount(account.pubkey)
+                        accountPendingRemoval = null
+                    },
+                ) {
+                    Text("Remove", color = MaterialTheme.colorScheme.error)
+                }
+            },
+            dismissButton = {
+                TextButton(onClick = { accountPendingRemoval = null }) {
+                    Text("Cancel")
+                }
+            },
+        )
+    }
+
     if (showWipeConfirm) {
         AlertDialog(
             onDismissRequest = { showWipeConfirm = false },
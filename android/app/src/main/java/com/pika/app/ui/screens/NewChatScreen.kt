@@ -44,6 +44,7 @@ import com.pika.app.AppManager
 import com.pika.app.rust.AppAction
 import com.pika.app.rust.AuthState
 import com.pika.app.rust.FollowListEntry
+import com.pika.app.rust.isGroupInviteLink
 import com.pika.app.rust.isValidPeerKey
 import com.pika.app.rust.normalizePeerKey
 import com.pika.app.ui.Avatar
@@ -58,6 +59,7 @@ fun NewChatScreen(manager: AppManager, padding: PaddingValues) {
     var searchText by remember { mutableStateOf("") }
     val peer = normalizePeerKey(npub)
     val isValidPeer = isValidPeerKey(peer)
+    val isInviteLink = isGroupInviteLink(npub.trim())
     val isLoading = manager.state.busy.creatingChat
     val isFetchingFollows = manager.state.busy.fetchingFollowList
     val followList = manager.state.followList
@@ -113,7 +115,7 @@ fun NewChatScreen(manager: AppManager, padding: PaddingValues) {
                     label = { Text("Peer npub") },
                     singleLine = true,
                     enabled = !isLoading,
-                    isError = peer.isNotEmpty() && !isValidPeer,
+                    isError = peer.isNotEmpty() && !isValidPeer && !isInviteLink,
                     modifier = Modifier.fillMaxWidth().testTag(TestTags.NEWCHAT_PEER_NPUB),
                 )
             }
@@ -129,7 +131,7 @@ fun NewChatScreen(manager: AppManager, padding: PaddingValues) {
                     TextButton(
                         onClick = {
                             val raw = clipboard.getText()?.text.orEmpty()
-                            npub = normalizePeerKey(raw)
+                            npub = if (isGroupInviteLink(raw.trim())) raw.trim() else normalizePeerKey(raw)
                         },
                         enabled = !isLoading,
                         modifier = Modifier.testTag(TestTags.NEWCHAT_PASTE),
@@ -137,17 +139,24 @@ fun NewChatScreen(manager: AppManager, padding: PaddingValues) {
                         Text("Paste")
                     }
                 }
-                if (peer.isNotEmpty() && !isValidPeer) {
+                if (peer.isNotEmpty() && !isValidPeer && !isInviteLink) {
                     Text(
-                        "Enter a valid npub1… or 64-char hex pubkey.",
+                        "Enter a valid npub1…, 64-char hex pubkey or group invite link.",
                         color = MaterialTheme.colorScheme.error,
                     )
                 }
             }
             item {
                 Button(
-                    onClick = { manager.dispatch(AppAction.CreateChat(peer)) },
-                    enabled = isValidPeer && !isLoading,
+                    onClick = {
+                        if (isInviteLink) {
+                            manager.dispatch(AppAction.JoinGroupViaInviteLink(npub.trim()))
+                            npub = ""
+                        } else {
+                            manager.dispatch(AppAction.CreateChat(peer))
+                        }
+                    },
+                    enabled = (isValidPeer || isInviteLink) && !isLoading,
                     modifier = Modifier.fillMaxWidth().testTag(TestTags.NEWCHAT_START),
                 ) {
                     if (isLoading) {
@@ -160,7 +169,7 @@ fun NewChatScreen(manager: AppManager, padding: PaddingValues) {
                             Text("Creating…")
                         }
                     } else {
-                        Text("Start chat")
+                        Text(if (isInviteLink) "Join group" else "Start chat")
                     }
                 }
             }
@@ -222,7 +231,11 @@ fun NewChatScreen(manager: AppManager, padding: PaddingValues) {
         QrScannerDialog(
             onDismiss = { showScanner = false },
             onScanned = { scanned ->
-                npub = scanned
+                if (isGroupInviteLink(scanned.trim())) {
+                    manager.dispatch(AppAction.JoinGroupViaInviteLink(scanned.trim()))
+                } else {
+                    npub = scanned
+                }
                 showScanner = false
             },
         )
@@ -24,6 +24,7 @@ import androidx.compose.material3.Icon
 import androidx.compose.material3.IconButton
 import androidx.compose.material3.MaterialTheme
 import androidx.compose.material3.ModalBottomSheet
+import androidx.compose.material3.OutlinedButton
 import androidx.compose.material3.Text
 import androidx.compose.material3.rememberModalBottomSheetState
 import androidx.compose.material3.surfaceColorAtElevation
@@ -164,6 +165,30 @@ fun PeerProfileSheet(
                     }
                 }
             }
+
+            // Block / Unblock
+            item {
+                ProfileSectionCard {
+                    if (profile.isBlocked) {
+                        OutlinedButton(
+                            onClick = { manager.dispatch(AppAction.UnblockUser(profile.pubkey)) },
+                            modifier = Modifier.fillMaxWidth(),
+                        ) {
+                            Text("Unblock")
+                        }
+                    } else {
+                        OutlinedButton(
+                            onClick = { manager.dispatch(AppAction.BlockUser(profile.pubkey)) },
+                            colors = ButtonDefaults.outlinedButtonColors(
+                                contentColor = MaterialTheme.colorScheme.error,
+                            ),
+                            modifier = Modifier.fillMaxWidth(),
+                        ) {
+                            Text("Block")
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -12,6 +12,8 @@ import android.speech.RecognitionListener
 import android.speech.RecognizerIntent
 import android.speech.SpeechRecognizer
 import androidx.compose.foundation.clickable
+import androidx.compose.foundation.gestures.detectHorizontalDragGestures
+import androidx.compose.foundation.gestures.detectTapGestures
 import androidx.compose.foundation.background
 import androidx.compose.foundation.layout.Arrangement
 import androidx.compose.foundation.layout.Box
@@ -51,6 +53,7 @@ import androidx.compose.ui.Modifier
 import androidx.compose.ui.draw.clip
 import androidx.compose.ui.graphics.Color
 import androidx.compose.ui.graphics.vector.ImageVector
+import androidx.compose.ui.input.pointer.pointerInput
 import androidx.compose.ui.text.font.FontFamily
 import androidx.compose.ui.text.style.TextOverflow
 import androidx.compose.ui.unit.dp
@@ -68,7 +71,6 @@ import kotlinx.coroutines.withContext
 import java.io.File
 import kotlin.math.log10
 import kotlin.math.max
-import kotlin.random.Random
 
 private const val RECORDER_BAR_WIDTH_DP = 3
 private const val RECORDER_BAR_SPACING_DP = 2
@@ -457,23 +459,39 @@ internal class VoiceAttachmentPlayer {
         scope.cancel()
     }
 
+    /** Jump to [fraction] of the clip without changing whether it is playing. */
+    fun seekTo(
+        path: String,
+        fraction: Float,
+    ) {
+        val local = preparePlayer(path) ?: return
+        val clamped = fraction.coerceIn(0f, 1f)
+        val targetMs = (clamped * local.duration).toInt()
+        runCatching { local.seekTo(targetMs) }
+        currentSeconds = targetMs / 1_000f
+        progress = clamped
+    }
+
     private fun play(path: String) {
-        if (currentPath != path) {
-            stopTicker()
-            releasePlayer()
-            progress = 0f
-            currentSeconds = 0f
-            durationSeconds = readDurationSeconds(path)
-        }
+        val local = preparePlayer(path) ?: return
+        runCatching { local.start() }
+        isPlaying = true
+        startTicker()
+    }
 
+    private fun preparePlayer(path: String): MediaPlayer? {
         val existing = player
         if (existing != null && currentPath == path) {
-            runCatching { existing.start() }
-            isPlaying = true
-            startTicker()
-            return
+            return existing
         }
 
+        stopTicker()
+        releasePlayer()
+        isPlaying = false
+        progress = 0f
+        currentSeconds = 0f
+        durationSeconds = readDurationSeconds(path)
+
         val created =
             runCatching {
                 MediaPlayer().apply {
@@ -487,14 +505,12 @@ internal class VoiceAttachmentPlayer {
                         runCatching { seekTo(0) }
                     }
                 }
-            }.getOrNull() ?: return
+            }.getOrNull() ?: return null
 
         player = created
         currentPath = path
         durationSeconds = max(durationSeconds, created.duration / 1_000f)
-        runCatching { created.start() }
-        isPlaying = true
-        startTicker()
+        return created
     }
 
     private fun pause() {
@@ -672,11 +688,14 @@ private fun RecordingControlIcon(
 @Composable
 internal fun VoiceAttachmentPlayerRow(
     localPath: String,
+    waveform: List<Float>,
+    durationMs: ULong?,
     isMine: Boolean,
     modifier: Modifier = Modifier,
 ) {
     val player = remember(localPath) { VoiceAttachmentPlayer() }
-    val waveform = remember(localPath) { generateWaveform(localPath) }
+    val bars = remember(waveform) { downsampleWaveform(waveform, VOICE_ATTACHMENT_BAR_COUNT) }
+    val fallbackSeconds = (durationMs?.toLong() ?: 0L) / 1_000f
 
     DisposableEffect(localPath) {
         player.ensureMetadata(localPath)
@@ -707,8 +726,10 @@ internal fun VoiceAttachmentPlayerRow(
         }
 
         VoiceWaveformBars(
-            levels = waveform,
+            levels = bars,
             modifier = Modifier.weight(1f),
+            alignment = Alignment.CenterStart,
+            onSeek = { fraction -> player.seekTo(localPath, fraction) },
             playedProgress = player.progress,
             playedColor =
                 if (isMine) {
@@ -725,7 +746,14 @@ internal fun VoiceAttachmentPlayerRow(
         )
 
         Text(
-            text = formatVoiceDuration(if (player.isPlaying) player.currentSeconds else player.durationSeconds),
+            text =
+                formatVoiceDuration(
+                    when {
+                        player.isPlaying || player.currentSeconds > 0f -> player.currentSeconds
+                        player.durationSeconds > 0f -> player.durationSeconds
+                        else -> fallbackSeconds
+                    },
+                ),
             style =
                 MaterialTheme.typography.labelSmall.copy(
                     fontFamily = FontFamily.Monospace,
@@ -749,6 +777,8 @@ private fun VoiceWaveformBars(
     playedProgress: Float,
     playedColor: Color,
     unplayedColor: Color,
+    alignment: Alignment = Alignment.CenterEnd,
+    onSeek: ((Float) -> Unit)? = null,
 ) {
     val normalized = levels.map { it.coerceIn(0f, 1f) }
     val visible =
@@ -759,8 +789,22 @@ private fun VoiceWaveformBars(
         }
     val safePlayedProgress = playedProgress.coerceIn(0f, 1f)
 
-    Box(modifier = modifier.height(28.dp), contentAlignment = Alignment.CenterEnd) {
+    Box(modifier = modifier.height(28.dp), contentAlignment = alignment) {
         Row(
+            modifier =
+                if (onSeek == null) {
+                    Modifier
+                } else {
+                    Modifier
+                        .height(28.dp)
+                        .pointerInput(onSeek) {
+                            detectTapGestures { offset -> onSeek(offset.x / size.width) }
+                        }.pointerInput(onSeek) {
+                            detectHorizontalDragGestures { change, _ ->
+                                onSeek(change.position.x / size.width)
+                            }
+                        }
+                },
             horizontalArrangement = Arrangement.spacedBy(RECORDER_BAR_SPACING_DP.dp),
             verticalAlignment = Alignment.CenterVertically,
         ) {
@@ -794,11 +838,15 @@ private fun formatVoiceDuration(seconds: Float): String {
     return "%d:%02d".format(minutes, secs)
 }
 
-private fun generateWaveform(path: String): List<Float> {
-    val file = File(path)
-    val seed = path.hashCode().toLong() xor file.length()
-    val rnd = Random(seed)
-    return List(VOICE_ATTACHMENT_BAR_COUNT) {
-        0.16f + (rnd.nextFloat() * 0.84f)
+/** Keep the peak of each group so long waveforms still fit in a chat bubble. */
+internal fun downsampleWaveform(
+    levels: List<Float>,
+    bars: Int,
+): List<Float> {
+    if (levels.size <= bars) return levels
+    return List(bars) { bar ->
+        val start = bar * levels.size / bars
+        val end = max(start + 1, (bar + 1) * levels.size / bars)
+        levels.subList(start, end).max()
     }
 }
@@ -17,12 +17,17 @@ use nostr_sdk::prelude::*;
 use pika_agent_control_plane::{
     AgentProvisionRequest, MicrovmAgentBackend, MicrovmProvisionParams,
 };
+use pika_marmot_runtime::invite_link::{
+    GroupInviteLink, JOIN_REQUEST_KIND, JOIN_REQUEST_TTL_SECS, JoinRequest,
+};
 use pika_marmot_runtime::key_package::normalize_peer_key_package_event_for_mdk;
 use pika_marmot_runtime::outbound::{OutboundConversationAction, PreparedConversationAction};
 use pika_marmot_runtime::runtime::MarmotRuntime;
+use pika_media::{quality, quality_store};
 use pika_relay_profiles::{
     default_key_package_relays, default_message_relays, default_primary_blossom_server,
 };
+use pikachat_sidecar::call_tts::{TtsBackendKind, TtsConfig};
 use serde::Deserialize;
 use serde_json::json;
 
@@ -126,8 +131,10 @@ enum Command {
   pikachat qr
   pikachat qr --channel test
   pikachat qr --channel dev --scheme customscheme
+  pikachat qr --link pika://join/npub1.../<token>
 
-Prints a QR code to the terminal. When scanned, it opens Pika and starts a 1:1 chat.")]
+Prints a QR code to the terminal. When scanned, it opens Pika and starts a 1:1 chat.
+With --link, the QR code opens a group invite link instead.")]
     Qr {
         /// Channel id used to resolve deep-link scheme from config/channels.json.
         #[arg(long, value_enum, default_value_t = QrChannel::Prod, env = "PIKA_CHANNEL")]
@@ -136,6 +143,22 @@ Prints a QR code to the terminal. When scanned, it opens Pika and starts a 1:1 c
         /// Explicit URL scheme override (bypasses channel mapping).
         #[arg(long)]
         scheme: Option<String>,
+
+        /// Group invite link to render, re-targeted at the channel's scheme
+        #[arg(long)]
+        link: Option<String>,
+    },
+
+    /// Ask a group admin to add you, using a group invite link
+    #[command(after_help = "Example:
+  pikachat join pika://join/npub1.../<token>
+
+Gift-wraps a join request to the admin who made the link. Once they add you,
+the invite shows up in 'pikachat welcomes'. The admin needs your key package,
+which 'pikachat init' publishes.")]
+    Join {
+        /// Invite link (<scheme>://join/<admin npub>/<token>)
+        link: String,
     },
 
     /// Publish a key package (kind 443) so peers can invite you
@@ -263,6 +286,40 @@ A .hnmd file can include a JSON frontmatter block with title and state:
         state: Option<String>,
     },
 
+    /// Edit one of your own messages in a group
+    #[command(after_help = "Example:
+  pikachat edit --group <hex-group-id> --message-id <hex-message-id> --content 'fixed typo'
+
+The message ID is shown in `pikachat messages` output.")]
+    Edit {
+        /// Nostr group ID (hex)
+        #[arg(long)]
+        group: String,
+
+        /// Message ID (hex) of the message to edit
+        #[arg(long)]
+        message_id: String,
+
+        /// Replacement message content
+        #[arg(long)]
+        content: String,
+    },
+
+    /// Delete one of your own messages in a group
+    #[command(after_help = "Example:
+  pikachat delete --group <hex-group-id> --message-id <hex-message-id>
+
+Other members see the message replaced by a tombstone.")]
+    Delete {
+        /// Nostr group ID (hex)
+        #[arg(long)]
+        group: String,
+
+        /// Message ID (hex) of the message to delete
+        #[arg(long)]
+        message_id: String,
+    },
+
     /// Print the canonical hypernote component/action catalog
     HypernoteCatalog {
         /// Compact JSON output (single line)
@@ -353,6 +410,23 @@ If --output is omitted, the original filename from the sender is used.")]
         lookback: u64,
     },
 
+    /// Dump a call's quality history (RTT, jitter, loss, bitrate, video fps) as JSON
+    #[command(after_help = "Examples:
+  pikachat call-report                                   # list recorded calls
+  pikachat call-report <call-id>
+  pikachat call-report <call-id> --db ~/app-data/accounts/<pubkey>/call_quality.sqlite3
+
+The app keeps call_quality.sqlite3 in each account's data dir, next to
+call_timeline.json. Without --db the file in --state-dir is read.")]
+    CallReport {
+        /// Call ID to report on; omit to list the calls that have samples
+        call_id: Option<String>,
+
+        /// Path to a call_quality.sqlite3 file (defaults to <state_dir>/call_quality.sqlite3)
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+
     /// Long-running JSONL sidecar daemon intended to be embedded/invoked by OpenClaw
     Daemon {
         /// Giftwrap lookback window (NIP-59 backdates timestamps; use hours/days, not seconds)
@@ -383,6 +457,19 @@ If --output is omitted, the original filename from the sender is used.")]
         /// Working directory passed to ACP `session/new` (defaults to <state_dir>/acp).
         #[arg(long)]
         acp_cwd: Option<PathBuf>,
+
+        /// Default TTS backend for call replies: `openai`, `local` (Piper) or `fixture`.
+        /// `send_audio_response` can still pick another one per reply.
+        #[arg(long, env = "PIKACHAT_TTS_BACKEND")]
+        tts_backend: Option<TtsBackendKind>,
+
+        /// Piper voice model (`.onnx`) for the offline `local` TTS backend.
+        #[arg(long, env = "PIKACHAT_TTS_LOCAL_MODEL")]
+        tts_local_model: Option<PathBuf>,
+
+        /// Piper executable for the `local` TTS backend (defaults to `piper` on PATH).
+        #[arg(long, env = "PIKACHAT_TTS_LOCAL_BIN")]
+        tts_local_bin: Option<PathBuf>,
     },
 
     /// Manage AI agents (HTTP control plane)
@@ -590,6 +677,25 @@ async fn handle_remote(cli: &Cli) -> anyhow::Result<()> {
                 "state": state.as_deref().or(file_state.as_deref()),
             })
         }
+        Command::Edit {
+            group,
+            message_id,
+            content,
+        } => {
+            serde_json::json!({
+                "cmd": "edit_message",
+                "nostr_group_id": group,
+                "event_id": message_id,
+                "content": content,
+            })
+        }
+        Command::Delete { group, message_id } => {
+            serde_json::json!({
+                "cmd": "delete_message",
+                "nostr_group_id": group,
+                "event_id": message_id,
+            })
+        }
         Command::Invite { peer, name } => {
             serde_json::json!({
                 "cmd": "init_group",
@@ -602,7 +708,7 @@ async fn handle_remote(cli: &Cli) -> anyhow::Result<()> {
         }
         _ => {
             anyhow::bail!(
-                "command not supported in --remote mode; supported: groups, welcomes, accept-welcome, messages, send, send-hypernote, invite, publish-kp"
+                "command not supported in --remote mode; supported: groups, welcomes, accept-welcome, messages, send, send-hypernote, edit, delete, invite, publish-kp"
             );
         }
     };
@@ -662,7 +768,12 @@ async fn main() -> anyhow::Result<()> {
         }
         Command::Init { nsec } => cmd_init(&cli, nsec.as_deref()).await,
         Command::Identity => cmd_identity(&cli),
-        Command::Qr { channel, scheme } => cmd_qr(&cli, *channel, scheme.as_deref()),
+        Command::Qr {
+            channel,
+            scheme,
+            link,
+        } => cmd_qr(&cli, *channel, scheme.as_deref(), link.as_deref()),
+        Command::Join { link } => cmd_join(&cli, link).await,
         Command::PublishKp => cmd_publish_kp(&cli).await,
         Command::Invite { peer, name } => cmd_invite(&cli, peer, name).await,
         Command::Welcomes => cmd_welcomes(&cli),
@@ -713,6 +824,12 @@ async fn main() -> anyhow::Result<()> {
             )
             .await
         }
+        Command::Edit {
+            group,
+            message_id,
+            content,
+        } => cmd_edit_message(&cli, group, message_id, content).await,
+        Command::Delete { group, message_id } => cmd_delete_message(&cli, group, message_id).await,
         Command::HypernoteCatalog { compact } => cmd_hypernote_catalog(*compact),
         Command::DownloadMedia { message_id, output } => {
             cmd_download_media(&cli, message_id, output.as_deref()).await
@@ -726,6 +843,9 @@ async fn main() -> anyhow::Result<()> {
             cmd_update_group_profile(&cli, group, name.as_deref(), about.as_deref()).await
         }
         Command::Listen { timeout, lookback } => cmd_listen(&cli, *timeout, *lookback).await,
+        Command::CallReport { call_id, db } => {
+            cmd_call_report(&cli, call_id.as_deref(), db.as_deref())
+        }
         Command::Daemon {
             giftwrap_lookback_sec,
             allow_pubkey,
@@ -733,7 +853,15 @@ async fn main() -> anyhow::Result<()> {
             exec,
             acp_exec,
             acp_cwd,
+            tts_backend,
+            tts_local_model,
+            tts_local_bin,
         } => {
+            let tts = TtsConfig {
+                default_backend: *tts_backend,
+                local_bin: tts_local_bin.clone(),
+                local_model: tts_local_model.clone(),
+            };
             cmd_daemon(
                 &cli,
                 *giftwrap_lookback_sec,
@@ -742,6 +870,7 @@ async fn main() -> anyhow::Result<()> {
                 exec.as_deref(),
                 acp_exec.as_deref(),
                 acp_cwd.as_deref(),
+                tts,
             )
             .await
         }
@@ -1030,10 +1159,29 @@ fn cmd_identity(cli: &Cli) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn cmd_qr(cli: &Cli, channel: QrChannel, scheme_override: Option<&str>) -> anyhow::Result<()> {
+fn cmd_qr(
+    cli: &Cli,
+    channel: QrChannel,
+    scheme_override: Option<&str>,
+    invite_link: Option<&str>,
+) -> anyhow::Result<()> {
+    let scheme = resolve_qr_scheme(channel, scheme_override)?;
+    if let Some(raw) = invite_link {
+        let link = GroupInviteLink::parse(raw)
+            .with_context(|| format!("not a group invite link: {raw}"))?;
+        let deep_link = link.to_url(&scheme);
+        qr2term::print_qr(&deep_link).context("render QR code")?;
+        eprintln!();
+        eprintln!(
+            "  admin: {}",
+            link.admin.to_bech32().context("encode npub")?
+        );
+        eprintln!("  link: {deep_link}");
+        return Ok(());
+    }
+
     let keys = mdk_util::load_or_create_keys(&cli.state_dir.join("identity.json"))?;
     let npub = keys.public_key().to_bech32().context("encode npub")?;
-    let scheme = resolve_qr_scheme(channel, scheme_override)?;
     let deep_link = format!("{scheme}://chat/{npub}");
 
     qr2term::print_qr(&deep_link).context("render QR code")?;
@@ -1064,6 +1212,35 @@ fn resolve_qr_scheme(channel: QrChannel, scheme_override: Option<&str>) -> anyho
     Ok(scheme.to_string())
 }
 
+async fn cmd_join(cli: &Cli, raw_link: &str) -> anyhow::Result<()> {
+    let link = GroupInviteLink::parse(raw_link)
+        .with_context(|| format!("not a group invite link: {raw_link}"))?;
+    let keys = mdk_util::load_or_create_keys(&cli.state_dir.join("identity.json"))?;
+    if link.admin == keys.public_key() {
+        anyhow::bail!("that invite link is your own");
+    }
+    let client = client_all(cli, &keys).await?;
+    let relays = relay_util::parse_relay_urls(&resolve_relays(cli))?;
+
+    let rumor = EventBuilder::new(
+        JOIN_REQUEST_KIND,
+        JoinRequest::new(&link.token).to_content(),
+    )
+    .build(keys.public_key());
+    let expires = Timestamp::from_secs(Timestamp::now().as_secs() + JOIN_REQUEST_TTL_SECS);
+    let giftwrap = EventBuilder::gift_wrap(&keys, &link.admin, rumor, [Tag::expiration(expires)])
+        .await
+        .context("gift-wrap join request")?;
+    relay_util::publish_and_confirm(&client, &relays, &giftwrap, "join request").await?;
+    client.shutdown().await;
+
+    print(json!({
+        "admin_pubkey": link.admin.to_hex(),
+        "event_id": giftwrap.id.to_hex(),
+    }));
+    Ok(())
+}
+
 async fn cmd_publish_kp(cli: &Cli) -> anyhow::Result<()> {
     let (keys, mdk) = open(cli)?;
     let kp_relays_str = resolve_kp_relays(cli);
@@ -1673,6 +1850,92 @@ async fn cmd_send_hypernote(
     Ok(())
 }
 
+async fn cmd_edit_message(
+    cli: &Cli,
+    group_hex: &str,
+    message_id_hex: &str,
+    content: &str,
+) -> anyhow::Result<()> {
+    let content = content.trim();
+    if content.is_empty() {
+        anyhow::bail!("--content is required");
+    }
+    let target = EventId::from_hex(message_id_hex.trim()).context("parse message id")?;
+    revise_own_message(
+        cli,
+        group_hex,
+        target,
+        OutboundConversationAction::Edit {
+            target_event_id: target,
+            content: content.to_string(),
+            created_at: Timestamp::now(),
+        },
+        "edit_message",
+    )
+    .await
+}
+
+async fn cmd_delete_message(
+    cli: &Cli,
+    group_hex: &str,
+    message_id_hex: &str,
+) -> anyhow::Result<()> {
+    let target = EventId::from_hex(message_id_hex.trim()).context("parse message id")?;
+    revise_own_message(
+        cli,
+        group_hex,
+        target,
+        OutboundConversationAction::Delete {
+            target_event_id: target,
+            created_at: Timestamp::now(),
+        },
+        "delete_message",
+    )
+    .await
+}
+
+/// Publish an edit or deletion for a message we authored. Receivers ignore
+/// revisions from anyone but the original sender, so refuse early instead.
+async fn revise_own_message(
+    cli: &Cli,
+    group_hex: &str,
+    target: EventId,
+    action: OutboundConversationAction,
+    label: &str,
+) -> anyhow::Result<()> {
+    let (keys, mdk) = open(cli)?;
+    let mut seen_mls_event_ids = mdk_util::load_processed_mls_event_ids(&cli.state_dir);
+    let relays = relay_util::parse_relay_urls(&resolve_relays(cli))?;
+    let group = find_group(&mdk, group_hex)?;
+    let client = client(cli, &keys).await?;
+
+    let ngid = hex::encode(group.nostr_group_id);
+    ingest_group_backlog(&mdk, &client, &relays, &ngid, &mut seen_mls_event_ids).await?;
+
+    let original = mdk
+        .get_message(&group.mls_group_id, &target)
+        .context("get message")?
+        .ok_or_else(|| anyhow!("message {} not found in group", target.to_hex()))?;
+    if original.pubkey != keys.public_key() {
+        anyhow::bail!("you can only edit or delete your own messages");
+    }
+
+    let prepared = prepare_cli_outbound_action(&mdk, keys.public_key(), group, action)
+        .context("create message")?;
+    MarmotRuntime::with_client(&mdk, &client)
+        .publish_prepared_action(&relays, &prepared, label)
+        .await?;
+    client.shutdown().await;
+    mdk_util::persist_processed_mls_event_ids(&cli.state_dir, &seen_mls_event_ids)?;
+
+    print(json!({
+        "event_id": prepared.rumor_id.to_hex(),
+        "target_event_id": target.to_hex(),
+        "nostr_group_id": ngid,
+    }));
+    Ok(())
+}
+
 fn cmd_hypernote_catalog(compact: bool) -> anyhow::Result<()> {
     if compact {
         print(hn::hypernote_catalog_value());
@@ -1682,6 +1945,84 @@ fn cmd_hypernote_catalog(compact: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn cmd_call_report(cli: &Cli, call_id: Option<&str>, db: Option<&Path>) -> anyhow::Result<()> {
+    print(call_report_json(&cli.state_dir, call_id, db)?);
+    Ok(())
+}
+
+fn call_report_json(
+    state_dir: &Path,
+    call_id: Option<&str>,
+    db: Option<&Path>,
+) -> anyhow::Result<serde_json::Value> {
+    let path = db
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| state_dir.join(quality_store::QUALITY_DB_FILE));
+    if !path.exists() {
+        anyhow::bail!("no call quality history at {}", path.display());
+    }
+    let conn = quality_store::open_quality_db(&path)
+        .with_context(|| format!("open {}", path.display()))?;
+
+    let Some(call_id) = call_id else {
+        let calls = quality_store::list_quality_calls(&conn).context("list calls")?;
+        let calls: Vec<serde_json::Value> = calls
+            .into_iter()
+            .map(|c| {
+                json!({
+                    "call_id": c.call_id,
+                    "chat_id": c.chat_id,
+                    "first_at_ms": c.first_at_ms,
+                    "last_at_ms": c.last_at_ms,
+                    "sample_count": c.sample_count,
+                })
+            })
+            .collect();
+        return Ok(json!({ "calls": calls }));
+    };
+
+    let samples = quality_store::load_quality_samples(&conn, call_id).context("load samples")?;
+    if samples.is_empty() {
+        anyhow::bail!("no quality samples for call {call_id}");
+    }
+    let summary = quality::summarize(&samples).map(|s| {
+        json!({
+            "score": s.score,
+            "sample_count": s.sample_count,
+            "duration_ms": s.duration_ms,
+            "avg_rtt_ms": s.avg_rtt_ms,
+            "avg_jitter_ms": s.avg_jitter_ms,
+            "max_jitter_ms": s.max_jitter_ms,
+            "avg_loss_pct": s.avg_loss_pct,
+            "max_loss_pct": s.max_loss_pct,
+            "concealed_frames": s.concealed_frames,
+            "avg_tx_bitrate_bps": s.avg_tx_bitrate_bps,
+            "avg_rx_bitrate_bps": s.avg_rx_bitrate_bps,
+            "avg_video_fps": s.avg_video_fps,
+        })
+    });
+    let samples: Vec<serde_json::Value> = samples
+        .iter()
+        .map(|s| {
+            json!({
+                "at_ms": s.at_ms,
+                "rtt_ms": s.rtt_ms,
+                "jitter_ms": s.jitter_ms,
+                "loss_pct": s.loss_pct,
+                "concealed_frames": s.concealed_frames,
+                "tx_bitrate_bps": s.tx_bitrate_bps,
+                "rx_bitrate_bps": s.rx_bitrate_bps,
+                "video_fps": s.video_fps,
+            })
+        })
+        .collect();
+    Ok(json!({
+        "call_id": call_id,
+        "summary": summary,
+        "samples": samples,
+    }))
+}
+
 async fn cmd_download_media(
     cli: &Cli,
     message_id_hex: &str,
@@ -2552,6 +2893,7 @@ async fn cmd_listen(cli: &Cli, timeout_sec: u64, lookback_sec: u64) -> anyhow::R
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn cmd_daemon(
     cli: &Cli,
     giftwrap_lookback_sec: u64,
@@ -2560,6 +2902,7 @@ async fn cmd_daemon(
     exec_cmd: Option<&str>,
     acp_exec: Option<&str>,
     acp_cwd: Option<&Path>,
+    tts: TtsConfig,
 ) -> anyhow::Result<()> {
     let relay_urls = resolve_relays(cli);
     let acp_backend = acp_exec.map(|exec_cmd| {
@@ -2576,6 +2919,7 @@ async fn cmd_daemon(
         auto_accept_welcomes,
         exec_cmd,
         acp_backend,
+        tts,
     )
     .await
     .context("pikachat daemon failed")
@@ -2949,6 +3293,58 @@ mod tests {
         assert!(!help.contains("--wrapper-event-id <"));
     }
 
+    #[test]
+    fn join_and_qr_accept_group_invite_links() {
+        let link = "pika://join/npub1xyz/0123456789abcdef0123456789abcdef";
+        let cli = Cli::try_parse_from(["pikachat", "join", link]).expect("parse join");
+        assert!(matches!(cli.cmd, Command::Join { link: ref l } if l == link));
+
+        let cli = Cli::try_parse_from(["pikachat", "qr", "--channel", "dev", "--link", link])
+            .expect("parse qr --link");
+        match cli.cmd {
+            Command::Qr {
+                channel, link: l, ..
+            } => {
+                assert_eq!(channel, QrChannel::Dev);
+                assert_eq!(l.as_deref(), Some(link));
+            }
+            _ => panic!("expected qr command"),
+        }
+        assert_eq!(resolve_qr_scheme(QrChannel::Dev, None).unwrap(), "pikadev");
+    }
+
+    #[test]
+    fn edit_and_delete_parse_group_and_message_id() {
+        let cli = Cli::try_parse_from([
+            "pikachat",
+            "edit",
+            "--group",
+            "aa",
+            "--message-id",
+            "bb",
+            "--content",
+            "fixed",
+        ])
+        .expect("parse edit");
+        match cli.cmd {
+            Command::Edit {
+                group,
+                message_id,
+                content,
+            } => {
+                assert_eq!(group, "aa");
+                assert_eq!(message_id, "bb");
+                assert_eq!(content, "fixed");
+            }
+            _ => panic!("expected edit command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["pikachat", "delete", "--group", "aa", "--message-id", "bb"])
+                .expect("parse delete");
+        assert!(matches!(cli.cmd, Command::Delete { .. }));
+    }
+
     #[test]
     fn accept_welcome_parses_primary_and_compat_flag_names() {
         let cli = Cli::try_parse_from([
@@ -3009,6 +3405,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn daemon_command_parses_tts_flags() {
+        let cli = Cli::try_parse_from([
+            "pikachat",
+            "daemon",
+            "--tts-backend",
+            "local",
+            "--tts-local-model",
+            "/models/en_US-amy-medium.onnx",
+        ])
+        .expect("parse daemon TTS flags");
+
+        match cli.cmd {
+            Command::Daemon {
+                tts_backend,
+                tts_local_model,
+                tts_local_bin,
+                ..
+            } => {
+                assert_eq!(tts_backend, Some(TtsBackendKind::Local));
+                assert_eq!(
+                    tts_local_model.as_deref(),
+                    Some(Path::new("/models/en_US-amy-medium.onnx"))
+                );
+                assert!(tts_local_bin.is_none());
+            }
+            _ => panic!("expected daemon command"),
+        }
+    }
+
     #[test]
     fn cli_message_media_refs_uses_shared_runtime_service() {
         let inviter_dir = tempfile::tempdir().expect("inviter tempdir");
@@ -3195,4 +3621,41 @@ mod tests {
             published.lock().expect("published lock")[0].id
         );
     }
+
+    #[test]
+    fn call_report_lists_calls_and_dumps_samples() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let missing = call_report_json(dir.path(), None, None).unwrap_err();
+        assert!(missing.to_string().contains("no call quality history"));
+
+        let conn = quality_store::open_quality_db(&dir.path().join(quality_store::QUALITY_DB_FILE))
+            .expect("open db");
+        for at_ms in [1_000, 3_000] {
+            let sample = quality::QualitySample {
+                at_ms,
+                rtt_ms: None,
+                jitter_ms: 6,
+                loss_pct: 0.0,
+                concealed_frames: 0,
+                tx_bitrate_bps: 32_000,
+                rx_bitrate_bps: 30_000,
+                video_fps: 0.0,
+            };
+            quality_store::insert_quality_sample(&conn, "call-1", "chat-1", &sample)
+                .expect("insert sample");
+        }
+        drop(conn);
+
+        let list = call_report_json(dir.path(), None, None).expect("list calls");
+        assert_eq!(list["calls"][0]["call_id"], "call-1");
+        assert_eq!(list["calls"][0]["sample_count"], 2);
+
+        let report = call_report_json(dir.path(), Some("call-1"), None).expect("report");
+        assert_eq!(report["samples"].as_array().map(Vec::len), Some(2));
+        assert_eq!(report["summary"]["duration_ms"], 2_000);
+        assert_eq!(report["summary"]["avg_rtt_ms"], serde_json::Value::Null);
+        assert!(report["summary"]["score"].as_f64().unwrap() > 4.0);
+
+        assert!(call_report_json(dir.path(), Some("other"), None).is_err());
+    }
 }
@@ -51,10 +51,13 @@ impl ManagerModel {
         };
 
         // Side-effect updates must not be dropped, even if stale.
-        if let AppUpdate::AccountCreated { nsec, .. } = &update {
+        if let AppUpdate::AccountCreated { nsec, pubkey, .. } = &update {
             if nsec_store.get_nsec().unwrap_or_default().is_empty() && !nsec.is_empty() {
                 nsec_store.set_nsec(nsec);
             }
+            if !pubkey.is_empty() {
+                nsec_store.for_account(pubkey).set_nsec(nsec);
+            }
         }
 
         if update_rev <= self.last_rev_applied {
@@ -64,9 +67,16 @@ impl ManagerModel {
         self.last_rev_applied = update_rev;
         match update {
             AppUpdate::FullState(state) => {
-                if matches!(state.auth, AuthState::LoggedIn { .. }) {
+                if let AuthState::LoggedIn { pubkey, .. } = &state.auth {
                     if let Some(nsec) = self.pending_login_nsec.take() {
                         nsec_store.set_nsec(&nsec);
+                        nsec_store.for_account(pubkey).set_nsec(&nsec);
+                    }
+                    // Relaunch restores whichever account was active last.
+                    if let Some(nsec) = nsec_store.for_account(pubkey).get_nsec() {
+                        if nsec_store.get_nsec().as_deref() != Some(nsec.as_str()) {
+                            nsec_store.set_nsec(&nsec);
+                        }
                     }
                 } else if state.toast.as_deref().is_some_and(|msg| {
                     msg.starts_with("Invalid nsec:")
@@ -134,6 +144,14 @@ impl AppManager {
 
         if let Some(nsec) = inner.nsec_store.get_nsec() {
             if !nsec.is_empty() {
+                // Sessions saved before multi-account support only have the
+                // active nsec; file it under its account too.
+                if let Some(pubkey) = pika_core::pubkey_for_nsec(&nsec) {
+                    let account = inner.nsec_store.for_account(&pubkey);
+                    if account.get_nsec().is_none() {
+                        account.set_nsec(&nsec);
+                    }
+                }
                 {
                     let mut model = write_model(&inner.model);
                     model.is_restoring_session = true;
@@ -175,8 +193,67 @@ impl AppManager {
     }
 
     pub fn logout(&self) {
+        let active = match &self.state().auth {
+            AuthState::LoggedIn { pubkey, .. } => Some(pubkey.clone()),
+            AuthState::LoggedOut => None,
+        };
+        if let Some(pubkey) = &active {
+            self.inner.nsec_store.for_account(pubkey).clear();
+        }
         self.inner.nsec_store.clear();
         self.inner.core.dispatch(AppAction::Logout);
+
+        // The core falls back to another account it still holds a signer for;
+        // after a relaunch it only knows the saved nsecs, so sign one back in.
+        let fallback = self.state().accounts.into_iter().find_map(|account| {
+            (Some(&account.pubkey) != active.as_ref())
+                .then(|| {
+                    self.inner
+                        .nsec_store
+                        .for_account(&account.pubkey)
+                        .get_nsec()
+                })
+                .flatten()
+        });
+        if let Some(nsec) = fallback {
+            self.inner.core.dispatch(AppAction::AddAccount { nsec });
+        }
+    }
+
+    /// Sign in another account alongside the current one and switch to it.
+    pub fn add_account(&self, nsec: String) {
+        let nsec = nsec.trim().to_string();
+        if let Some(pubkey) = pika_core::pubkey_for_nsec(&nsec) {
+            self.inner.nsec_store.for_account(&pubkey).set_nsec(&nsec);
+        }
+        self.inner.core.dispatch(AppAction::AddAccount { nsec });
+    }
+
+    pub fn switch_account(&self, pubkey: String) {
+        // A saved nsec lets us switch even if the core hasn't seen this
+        // account's signer since launch.
+        match self.inner.nsec_store.for_account(&pubkey).get_nsec() {
+            Some(nsec) => self.inner.core.dispatch(AppAction::AddAccount { nsec }),
+            None => self
+                .inner
+                .core
+                .dispatch(AppAction::SwitchAccount { pubkey }),
+        }
+    }
+
+    pub fn remove_account(&self, pubkey: String) {
+        let is_active = matches!(
+            &self.state().auth,
+            AuthState::LoggedIn { pubkey: active, .. } if *active == pubkey
+        );
+        if is_active {
+            self.logout();
+            return;
+        }
+        self.inner.nsec_store.for_account(&pubkey).clear();
+        self.inner
+            .core
+            .dispatch(AppAction::RemoveAccount { pubkey });
     }
 
     pub fn clear_local_session_for_recovery(&self) {
@@ -201,6 +278,10 @@ impl AppManager {
         self.inner.core.send_video_frame(payload);
     }
 
+    pub fn send_screen_frame(&self, payload: Vec<u8>) {
+        self.inner.core.send_screen_frame(payload);
+    }
+
     pub fn reset_relay_config_to_defaults(&self) {
         let path = self.inner.data_dir.join("pika_config.json");
         let existing = std::fs::read_to_string(&path).ok();
@@ -255,6 +336,17 @@ impl FileNsecStore {
         Self { path }
     }
 
+    /// Per-account copy of an nsec, kept beside the active one so accounts
+    /// can be switched back to after a restart.
+    fn for_account(&self, pubkey_hex: &str) -> FileNsecStore {
+        let dir = self
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        FileNsecStore::new(dir.join("desktop_accounts").join(pubkey_hex))
+    }
+
     fn get_nsec(&self) -> Option<String> {
         let bytes = std::fs::read(&self.path).ok()?;
         let raw = String::from_utf8(bytes).ok()?;
@@ -448,6 +540,38 @@ mod tests {
         assert!(model.pending_login_nsec.is_none());
     }
 
+    #[test]
+    fn login_files_nsec_under_its_account() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = FileNsecStore::new(tmp.path().join("nsec.txt"));
+        let mut model = ManagerModel::new(state_with(0, false));
+        model.pending_login_nsec = Some("nsec1pending".to_string());
+
+        model.apply_update(AppUpdate::FullState(state_with(1, true)), &store);
+
+        assert_eq!(
+            store.for_account("pubkey").get_nsec().as_deref(),
+            Some("nsec1pending")
+        );
+    }
+
+    #[test]
+    fn switched_account_becomes_restore_target() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = FileNsecStore::new(tmp.path().join("nsec.txt"));
+        store.set_nsec("nsec1first");
+        store.for_account("pubkey").set_nsec("nsec1second");
+        let mut model = ManagerModel::new(state_with(0, false));
+
+        model.apply_update(AppUpdate::FullState(state_with(1, true)), &store);
+
+        assert_eq!(store.get_nsec().as_deref(), Some("nsec1second"));
+        assert_eq!(
+            store.for_account("pubkey").get_nsec().as_deref(),
+            Some("nsec1second")
+        );
+    }
+
     #[cfg(unix)]
     #[test]
     fn nsec_store_uses_owner_only_permissions() {
@@ -369,3 +369,8 @@ pub fn call_banner_button_style(_theme: &Theme, status: button::Status) -> butto
 pub fn call_error_color() -> Color {
     current().danger.base.scale_alpha(0.8)
 }
+
+/// Ring colour around a group call participant who is currently speaking.
+pub fn call_speaking_color() -> Color {
+    current().success.base
+}
@@ -51,11 +51,17 @@ pub const ELLIPSIS: &str = "\u{e0b6}";
 pub const CHECK: &str = "\u{e06c}";
 pub const CHECK_CHECK: &str = "\u{e38e}";
 pub const CLOCK: &str = "\u{e087}";
+pub const BELL: &str = "\u{e059}";
+pub const BELL_OFF: &str = "\u{e05a}";
 pub const COPY: &str = "\u{e09e}";
 pub const CHEVRON_LEFT: &str = "\u{e06e}";
 pub const LOG_OUT: &str = "\u{e10e}";
 pub const PEN: &str = "\u{e12f}";
+pub const TRASH: &str = "\u{e18d}";
 pub const KEY: &str = "\u{e0fd}";
+pub const LINK: &str = "\u{e102}";
+pub const SHIELD: &str = "\u{e158}";
+pub const SHIELD_OFF: &str = "\u{e15a}";
 pub const INFO: &str = "\u{e0f9}";
 #[allow(dead_code)]
 pub const AT_SIGN: &str = "\u{e04e}";
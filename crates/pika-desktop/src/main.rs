@@ -120,6 +120,13 @@ fn active_theme(state: &DesktopApp) -> Theme {
     }
 }
 
+fn auth_pubkey(auth: &AuthState) -> Option<&str> {
+    match auth {
+        AuthState::LoggedIn { pubkey, .. } => Some(pubkey),
+        AuthState::LoggedOut => None,
+    }
+}
+
 fn manager_update_stream(manager: &AppManager) -> impl iced::futures::Stream<Item = ()> {
     let rx = manager.subscribe_updates();
     iced::futures::stream::unfold(rx, |rx| async move {
@@ -272,7 +279,7 @@ impl DesktopApp {
                 ..
             } => match message {
                 Message::CoreUpdated => {
-                    self.sync_from_manager();
+                    return self.sync_from_manager();
                 }
                 Message::Home(message) => {
                     if let Screen::Home(ref mut home_state) = screen {
@@ -288,6 +295,15 @@ impl DesktopApp {
                                     avatar_cache.borrow_mut().clear();
                                     *screen = Screen::Login(screen::login::State::new());
                                 }
+                                screen::home::Event::AddAccount { nsec } => {
+                                    manager.add_account(nsec);
+                                }
+                                screen::home::Event::SwitchAccount { pubkey } => {
+                                    manager.switch_account(pubkey);
+                                }
+                                screen::home::Event::RemoveAccount { pubkey } => {
+                                    manager.remove_account(pubkey);
+                                }
                                 screen::home::Event::Task(task) => {
                                     return task.map(Message::Home);
                                 }
@@ -425,9 +441,9 @@ impl DesktopApp {
 
     // ── Core state synchronisation ──────────────────────────────────────────
 
-    fn sync_from_manager(&mut self) {
+    fn sync_from_manager(&mut self) -> Task<Message> {
         match self {
-            DesktopApp::BootError { .. } => {}
+            DesktopApp::BootError { .. } => Task::none(),
             DesktopApp::Loaded {
                 avatar_cache,
                 cached_profiles,
@@ -439,7 +455,7 @@ impl DesktopApp {
                 let latest = manager.state();
                 if latest.rev == state.rev {
                     self.retry_follow_list_if_needed();
-                    return;
+                    return Task::none();
                 }
 
                 // Detect auth transitions for screen changes.
@@ -447,6 +463,9 @@ impl DesktopApp {
                 let now_logged_in = matches!(latest.auth, AuthState::LoggedIn { .. });
                 let was_logged_in = matches!(state.auth, AuthState::LoggedIn { .. });
                 let now_logged_out = matches!(latest.auth, AuthState::LoggedOut);
+                let account_changed = was_logged_in
+                    && now_logged_in
+                    && auth_pubkey(&state.auth) != auth_pubkey(&latest.auth);
 
                 if was_logged_out && now_logged_in {
                     // Login succeeded → transition to Main screen.
@@ -454,6 +473,10 @@ impl DesktopApp {
                     if !matches!(screen, Screen::Home(_)) {
                         *screen = Screen::Home(Box::new(screen::home::State::new(&latest)));
                     }
+                } else if account_changed {
+                    // Switched accounts (or logout fell back to another one):
+                    // start the home screen over for the new account.
+                    *screen = Screen::Home(Box::new(screen::home::State::new(&latest)));
                 } else if was_logged_in && now_logged_out {
                     // Logged out externally (e.g. session expired) → show Login.
                     avatar_cache.borrow_mut().clear();
@@ -463,12 +486,16 @@ impl DesktopApp {
                 }
 
                 // Delegate screen-specific sync.
-                if let Screen::Home(ref mut home) = screen {
-                    home.sync_from_update(state, &latest, manager, cached_profiles);
-                }
+                let task = match screen {
+                    Screen::Home(ref mut home) => home
+                        .sync_from_update(state, &latest, manager, cached_profiles)
+                        .map(Message::Home),
+                    Screen::Login(_) => Task::none(),
+                };
 
                 *state = latest;
                 self.retry_follow_list_if_needed();
+                task
             }
         }
     }
@@ -623,6 +650,18 @@ impl DesktopApp {
                 // reach here from keyboard shortcuts, but handle gracefully.
                 Task::none()
             }
+            screen::home::Event::AddAccount { nsec } => {
+                manager.add_account(nsec);
+                Task::none()
+            }
+            screen::home::Event::SwitchAccount { pubkey } => {
+                manager.switch_account(pubkey);
+                Task::none()
+            }
+            screen::home::Event::RemoveAccount { pubkey } => {
+                manager.remove_account(pubkey);
+                Task::none()
+            }
             screen::home::Event::Task(task) => task.map(Message::Home),
             screen::home::Event::ThemeChanged { index } => {
                 *active_theme_index = index;
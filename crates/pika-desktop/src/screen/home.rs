@@ -39,6 +39,18 @@ pub struct State {
     theme_picker: Option<views::theme_picker::State>,
     /// Index into `ALL_THEMES` of the theme being previewed (during picker navigation).
     pub preview_theme_index: Option<usize>,
+    /// Search result the user picked that isn't loaded in the conversation yet.
+    pending_jump: Option<PendingJump>,
+}
+
+/// Older history is paged in with `LoadOlderMessages` until the target
+/// message shows up (or there is nothing older left), then we scroll to it.
+#[derive(Debug)]
+struct PendingJump {
+    chat_id: String,
+    message_id: String,
+    /// Oldest message id we last paged before, so each page is requested once.
+    requested_before: Option<String>,
 }
 
 // ── Messages ────────────────────────────────────────────────────────────────
@@ -75,6 +87,12 @@ pub enum Event {
     AppAction(AppAction),
     /// Destroy the session
     Logout,
+    /// Sign in another account and switch to it
+    AddAccount { nsec: String },
+    /// Switch to a signed-in account
+    SwitchAccount { pubkey: String },
+    /// Forget an account and its local data
+    RemoveAccount { pubkey: String },
     /// Perform an Iced task
     Task(Task<Message>),
     /// The user selected a theme from the picker.
@@ -124,6 +142,7 @@ impl State {
             command_palette: None,
             theme_picker: None,
             preview_theme_index: None,
+            pending_jump: None,
         }
     }
 
@@ -144,7 +163,7 @@ impl State {
         new_state: &AppState,
         manager: &AppManager,
         cached_profiles: &[FollowListEntry],
-    ) {
+    ) -> Task<Message> {
         // Close new-chat form once creating_chat finishes.
         if old_state.busy.creating_chat && !new_state.busy.creating_chat {
             if matches!(self.pane, Pane::NewChat(_)) {
@@ -236,6 +255,58 @@ impl State {
         {
             manager.dispatch(AppAction::RefreshFollowList);
         }
+
+        // Feed message index results into the open palette.
+        if let Some(ref mut palette) = self.command_palette {
+            if old_state.message_search != new_state.message_search {
+                let context = new_state
+                    .current_chat
+                    .as_ref()
+                    .map(|chat| views::command_palette::ChatContext { chat });
+                palette.sync_message_search(
+                    new_state.message_search.as_ref(),
+                    &new_state.chat_list,
+                    context,
+                );
+            }
+        }
+
+        self.advance_pending_jump(new_state, manager)
+            .unwrap_or_else(Task::none)
+    }
+
+    /// Scroll to the pending search result once it's loaded, paging older
+    /// history in the meantime.
+    fn advance_pending_jump(
+        &mut self,
+        state: &AppState,
+        manager: &AppManager,
+    ) -> Option<Task<Message>> {
+        let jump = self.pending_jump.as_mut()?;
+        let chat = state
+            .current_chat
+            .as_ref()
+            .filter(|chat| chat.chat_id == jump.chat_id)?;
+        if let Some(task) = views::conversation::jump_to_message_task(chat, &jump.message_id) {
+            self.pending_jump = None;
+            return Some(task.map(Message::Conversation));
+        }
+        let oldest = chat.messages.first().map(|m| m.id.clone());
+        match oldest {
+            Some(oldest) if chat.can_load_older => {
+                if jump.requested_before.as_ref() != Some(&oldest) {
+                    jump.requested_before = Some(oldest.clone());
+                    manager.dispatch(AppAction::LoadOlderMessages {
+                        chat_id: chat.chat_id.clone(),
+                        before_message_id: oldest,
+                        limit: 50,
+                    });
+                }
+            }
+            // Nothing older to load; the message is gone (deleted or expired).
+            _ => self.pending_jump = None,
+        }
+        None
     }
 
     pub fn update(
@@ -313,6 +384,38 @@ impl State {
                                     cached_profiles,
                                 );
                             }
+                            views::command_palette::Event::SearchMessages { query } => {
+                                manager.dispatch(AppAction::SearchMessages {
+                                    query,
+                                    chat_id: None,
+                                });
+                            }
+                            views::command_palette::Event::OpenMessage {
+                                chat_id,
+                                message_id,
+                            } => {
+                                self.command_palette = None;
+                                let already_open = state
+                                    .current_chat
+                                    .as_ref()
+                                    .is_some_and(|chat| chat.chat_id == chat_id);
+                                if !already_open {
+                                    self.optimistic_selected_chat_id = Some(chat_id.clone());
+                                    self.conversation.emoji_picker_message_id = None;
+                                    self.clear_pane();
+                                    manager.dispatch(AppAction::OpenChat {
+                                        chat_id: chat_id.clone(),
+                                    });
+                                }
+                                self.pending_jump = Some(PendingJump {
+                                    chat_id,
+                                    message_id,
+                                    requested_before: None,
+                                });
+                                if let Some(task) = self.advance_pending_jump(state, manager) {
+                                    return Some(Event::Task(task));
+                                }
+                            }
                             views::command_palette::Event::JumpToMessage { message_id } => {
                                 self.command_palette = None;
                                 if let Some(chat) = &state.current_chat {
@@ -460,6 +563,25 @@ impl State {
                 views::call_screen::Message::ToggleCamera => {
                     manager.dispatch(AppAction::ToggleCamera);
                 }
+                views::call_screen::Message::ToggleAudioProcessing => {
+                    manager.dispatch(AppAction::ToggleAudioProcessing);
+                }
+                views::call_screen::Message::ToggleRecording => {
+                    manager.dispatch(AppAction::ToggleCallRecording);
+                }
+                views::call_screen::Message::SaveCaptions => {
+                    if let Some(call) = &state.active_call {
+                        manager.dispatch(AppAction::SaveCallCaptions {
+                            call_id: call.call_id.clone(),
+                        });
+                    }
+                }
+                views::call_screen::Message::StartScreenShare => {
+                    manager.dispatch(AppAction::StartScreenShare);
+                }
+                views::call_screen::Message::StopScreenShare => {
+                    manager.dispatch(AppAction::StopScreenShare);
+                }
                 views::call_screen::Message::DismissCallScreen => {
                     self.show_call_screen = false;
                 }
@@ -473,6 +595,12 @@ impl State {
                     self.clear_pane();
                     manager.dispatch(AppAction::OpenChat { chat_id });
                 }
+                views::chat_rail::Message::AcceptInvite(invite_id) => {
+                    manager.dispatch(AppAction::AcceptInvite { invite_id });
+                }
+                views::chat_rail::Message::DeclineInvite(invite_id) => {
+                    manager.dispatch(AppAction::DeclineInvite { invite_id });
+                }
                 views::chat_rail::Message::ClickNewChat => {
                     let opening = !matches!(self.pane, Pane::NewChat(_));
                     self.clear_pane();
@@ -573,6 +701,42 @@ impl State {
                                 });
                             }
                         }
+                        views::conversation::Event::EditMessage {
+                            message_id,
+                            content,
+                        } => {
+                            if let Some(chat) = &state.current_chat {
+                                manager.dispatch(AppAction::EditMessage {
+                                    chat_id: chat.chat_id.clone(),
+                                    message_id,
+                                    content,
+                                });
+                            }
+                        }
+                        views::conversation::Event::DeleteMessage { message_id } => {
+                            if let Some(chat) = &state.current_chat {
+                                manager.dispatch(AppAction::DeleteMessage {
+                                    chat_id: chat.chat_id.clone(),
+                                    message_id,
+                                });
+                            }
+                        }
+                        views::conversation::Event::SetDisappearingTimer(expire_after_secs) => {
+                            if let Some(chat) = &state.current_chat {
+                                manager.dispatch(AppAction::SetDisappearingTimer {
+                                    chat_id: chat.chat_id.clone(),
+                                    expire_after_secs,
+                                });
+                            }
+                        }
+                        views::conversation::Event::SetNotificationMode(mode) => {
+                            if let Some(chat) = &state.current_chat {
+                                manager.dispatch(AppAction::SetChatNotificationMode {
+                                    chat_id: chat.chat_id.clone(),
+                                    mode,
+                                });
+                            }
+                        }
                         views::conversation::Event::ShowGroupInfo => {
                             self.clear_pane();
                             if let Some(chat) = &state.current_chat {
@@ -647,6 +811,57 @@ impl State {
                                     });
                                 }
                             }
+                            views::group_info::Event::SetAdmin { pubkey, admin } => {
+                                if let Some(chat) = &state.current_chat {
+                                    let chat_id = chat.chat_id.clone();
+                                    manager.dispatch(if admin {
+                                        AppAction::PromoteAdmin { chat_id, pubkey }
+                                    } else {
+                                        AppAction::DemoteAdmin { chat_id, pubkey }
+                                    });
+                                }
+                            }
+                            views::group_info::Event::CreateInviteLink {
+                                multi_use,
+                                requires_approval,
+                            } => {
+                                if let Some(chat) = &state.current_chat {
+                                    manager.dispatch(AppAction::CreateGroupInviteLink {
+                                        chat_id: chat.chat_id.clone(),
+                                        multi_use,
+                                        requires_approval,
+                                    });
+                                }
+                            }
+                            views::group_info::Event::CopyInviteLink { url } => {
+                                return Some(Event::Task(
+                                    iced::clipboard::write(url).map(|_: ()| Message::CallTimerTick),
+                                ));
+                            }
+                            views::group_info::Event::RevokeInviteLink { token } => {
+                                if let Some(chat) = &state.current_chat {
+                                    manager.dispatch(AppAction::RevokeGroupInviteLink {
+                                        chat_id: chat.chat_id.clone(),
+                                        token,
+                                    });
+                                }
+                            }
+                            views::group_info::Event::ApproveJoinRequest { pubkey } => {
+                                if let Some(chat) = &state.current_chat {
+                                    manager.dispatch(AppAction::ApproveJoinRequest {
+                                        chat_id: chat.chat_id.clone(),
+                                        pubkey,
+                                    });
+                                }
+                            }
+                            views::group_info::Event::DeclineJoinRequest { pubkey } => {
+                                if let Some(chat) = &state.current_chat {
+                                    manager.dispatch(AppAction::DeclineJoinRequest {
+                                        chat_id: chat.chat_id.clone(),
+                                        pubkey,
+                                    });
+                                }
+                            }
                             views::group_info::Event::LeaveGroup => {
                                 if let Some(chat) = &state.current_chat {
                                     manager.dispatch(AppAction::LeaveGroup {
@@ -728,6 +943,9 @@ impl State {
                             views::new_chat::Event::CreateChat { peer_npub } => {
                                 manager.dispatch(AppAction::CreateChat { peer_npub });
                             }
+                            views::new_chat::Event::JoinGroup { link } => {
+                                manager.dispatch(AppAction::JoinGroupViaInviteLink { link });
+                            }
                         }
                     }
                 }
@@ -805,6 +1023,15 @@ impl State {
                             views::my_profile::Event::Logout => {
                                 return Some(Event::Logout);
                             }
+                            views::my_profile::Event::AddAccount { nsec } => {
+                                return Some(Event::AddAccount { nsec });
+                            }
+                            views::my_profile::Event::SwitchAccount { pubkey } => {
+                                return Some(Event::SwitchAccount { pubkey });
+                            }
+                            views::my_profile::Event::RemoveAccount { pubkey } => {
+                                return Some(Event::RemoveAccount { pubkey });
+                            }
                         }
                     }
                 }
@@ -840,6 +1067,20 @@ impl State {
                                 });
                             }
                         }
+                        views::peer_profile::Event::Block => {
+                            if let Some(profile) = &state.peer_profile {
+                                manager.dispatch(AppAction::BlockUser {
+                                    pubkey: profile.pubkey.clone(),
+                                });
+                            }
+                        }
+                        views::peer_profile::Event::Unblock => {
+                            if let Some(profile) = &state.peer_profile {
+                                manager.dispatch(AppAction::UnblockUser {
+                                    pubkey: profile.pubkey.clone(),
+                                });
+                            }
+                        }
                         views::peer_profile::Event::StartChat { peer_npub } => {
                             // If a 1:1 chat already exists with this peer, open it
                             // instead of creating a duplicate.
@@ -918,13 +1159,15 @@ impl State {
         // ── Incoming call banner ────────────────────────────────────
         if let Some(call) = &state.active_call {
             if matches!(call.status, CallStatus::Ringing) {
-                let peer_name = state
-                    .chat_list
-                    .iter()
-                    .find(|c| c.chat_id == call.chat_id)
-                    .and_then(|c| c.members.first())
-                    .and_then(|m| m.name.as_deref())
-                    .unwrap_or("Unknown");
+                let chat = state.chat_list.iter().find(|c| c.chat_id == call.chat_id);
+                let peer_name = if call.is_group_call {
+                    chat.and_then(|c| c.group_name.as_deref())
+                        .unwrap_or("Group")
+                } else {
+                    chat.and_then(|c| c.members.first())
+                        .and_then(|m| m.name.as_deref())
+                        .unwrap_or("Unknown")
+                };
                 main_column = main_column.push(
                     views::call_banner::view(peer_name, call.is_video_call)
                         .map(Message::CallBanner),
@@ -943,6 +1186,7 @@ impl State {
         );
         let rail = views::chat_rail::view(
             &state.chat_list,
+            &state.invitations,
             selected_chat_id,
             matches!(self.pane, Pane::NewChat(_)),
             matches!(self.pane, Pane::NewGroup(_)),
@@ -959,6 +1203,11 @@ impl State {
                     &self.my_npub,
                     app_version_display,
                     state.my_profile.picture_url.as_deref(),
+                    state.read_receipts_enabled,
+                    state.auto_accept_followed_invites,
+                    state.block_list_sync_enabled,
+                    &state.blocked_users,
+                    &state.accounts,
                     cache,
                 )
                 .map(Message::MyProfile)
@@ -998,13 +1247,22 @@ impl State {
                 )
                 .map(Message::NewChat)
         } else if let Some(call) = state.active_call.as_ref().filter(|_| self.show_call_screen) {
-            let peer = state.current_chat.as_ref().and_then(|c| c.members.first());
-            let peer_name = peer.and_then(|m| m.name.as_deref()).unwrap_or("Unknown");
-            let peer_picture_url = peer.and_then(|m| m.picture_url.as_deref());
+            let chat = state.current_chat.as_ref();
+            let peer = chat.and_then(|c| c.members.first());
+            let peer_name = if call.is_group_call {
+                chat.and_then(|c| c.group_name.as_deref())
+                    .unwrap_or("Group")
+            } else {
+                peer.and_then(|m| m.name.as_deref()).unwrap_or("Unknown")
+            };
+            let peer_picture_url = peer
+                .filter(|_| !call.is_group_call)
+                .and_then(|m| m.picture_url.as_deref());
             views::call_screen::call_screen_view(
                 call,
                 peer_name,
                 peer_picture_url,
+                chat.map(|c| c.members.as_slice()).unwrap_or(&[]),
                 &self.video_pipeline,
                 cache,
             )
@@ -1,18 +1,53 @@
+use std::cell::RefCell;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::thread::LocalKey;
+use std::time::{Duration, Instant};
 
-use pika_core::VideoFrameReceiver;
-use pika_media::tracks::video_params;
+use openh264::decoder::Decoder;
+use pika_core::{CallVideoProfile, VideoFrameReceiver};
+use pika_media::tracks::{screen_params, video_params};
 
 use crate::app_manager::AppManager;
-use crate::video_shader::VideoShaderProgram;
+use crate::video_shader::{RemoteCamera, RemoteScreen, VideoShaderProgram, VideoStream};
+
+/// Encoder requests from the core's rate control, picked up by the capture thread.
+#[derive(Default)]
+struct EncoderControl {
+    profile: Mutex<Option<CallVideoProfile>>,
+    keyframe: AtomicBool,
+}
+
+/// Latest decoded frame of one remote stream, shared between the receiver
+/// callback and the shader widget drawing it.
+#[derive(Clone, Default)]
+struct FrameSlot {
+    frame: Arc<Mutex<Option<DecodedFrame>>>,
+    generation: Arc<AtomicU64>,
+}
+
+impl FrameSlot {
+    fn store(&self, frame: DecodedFrame) {
+        if let Ok(mut slot) = self.frame.lock() {
+            *slot = Some(frame);
+            self.generation.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(mut slot) = self.frame.lock() {
+            *slot = None;
+        }
+    }
+}
 
 /// Receives decrypted H.264 NALUs from Rust core via the `VideoFrameReceiver` callback,
 /// decodes them with openh264, and stores the latest RGBA frame for iced rendering.
 struct DesktopVideoReceiver {
-    latest_frame: Arc<Mutex<Option<DecodedFrame>>>,
-    generation: Arc<AtomicU64>,
+    remote: FrameSlot,
+    screen: FrameSlot,
+    encoder_control: Arc<EncoderControl>,
 }
 
 #[derive(Clone)]
@@ -23,35 +58,53 @@ pub struct DecodedFrame {
 }
 
 impl DesktopVideoReceiver {
-    fn new(latest_frame: Arc<Mutex<Option<DecodedFrame>>>, generation: Arc<AtomicU64>) -> Self {
+    fn new(remote: FrameSlot, screen: FrameSlot, encoder_control: Arc<EncoderControl>) -> Self {
         Self {
-            latest_frame,
-            generation,
+            remote,
+            screen,
+            encoder_control,
         }
     }
 }
 
+thread_local! {
+    static CAMERA_DECODER: RefCell<Option<Decoder>> = RefCell::new(Decoder::new().ok());
+    static SCREEN_DECODER: RefCell<Option<Decoder>> = RefCell::new(Decoder::new().ok());
+}
+
 impl VideoFrameReceiver for DesktopVideoReceiver {
     fn on_video_frame(&self, _call_id: String, payload: Vec<u8>) {
-        let decoded = decode_h264_to_rgba(&payload);
-        if let Some(frame) = decoded {
-            if let Ok(mut slot) = self.latest_frame.lock() {
-                *slot = Some(frame);
-                self.generation.fetch_add(1, Ordering::Relaxed);
-            }
+        if let Some(frame) = decode_h264_to_rgba(&CAMERA_DECODER, &payload) {
+            self.remote.store(frame);
         }
     }
-}
 
-fn decode_h264_to_rgba(annexb: &[u8]) -> Option<DecodedFrame> {
-    use openh264::formats::YUVSource;
+    fn on_video_profile(&self, _call_id: String, profile: CallVideoProfile) {
+        if let Ok(mut slot) = self.encoder_control.profile.lock() {
+            *slot = Some(profile);
+        }
+    }
 
-    thread_local! {
-        static DECODER: std::cell::RefCell<Option<openh264::decoder::Decoder>> =
-            std::cell::RefCell::new(openh264::decoder::Decoder::new().ok());
+    fn on_keyframe_request(&self, _call_id: String) {
+        self.encoder_control.keyframe.store(true, Ordering::Relaxed);
     }
 
-    DECODER.with(|cell| {
+    fn on_screen_frame(&self, _call_id: String, payload: Vec<u8>) {
+        if let Some(frame) = decode_h264_to_rgba(&SCREEN_DECODER, &payload) {
+            self.screen.store(frame);
+        }
+    }
+}
+
+/// Decodes with the given thread's decoder; each stream needs its own, since
+/// a decoder only follows one H.264 sequence.
+fn decode_h264_to_rgba(
+    decoder: &'static LocalKey<RefCell<Option<Decoder>>>,
+    annexb: &[u8],
+) -> Option<DecodedFrame> {
+    use openh264::formats::YUVSource;
+
+    decoder.with(|cell| {
         let mut decoder_opt = cell.borrow_mut();
         let decoder = decoder_opt.as_mut()?;
         let yuv = decoder.decode(annexb).ok()??;
@@ -68,19 +121,38 @@ fn decode_h264_to_rgba(annexb: &[u8]) -> Option<DecodedFrame> {
     })
 }
 
-/// Captures from the system camera, encodes to H.264, and pushes to Rust core.
+/// Captures from the system camera or screen, encodes to H.264, and pushes to
+/// Rust core.
 struct CaptureThread {
     stop: Arc<AtomicBool>,
     handle: Option<thread::JoinHandle<()>>,
 }
 
 impl CaptureThread {
-    fn start(manager: AppManager, camera_error: Arc<Mutex<Option<String>>>) -> Self {
+    fn start(
+        manager: AppManager,
+        camera_error: Arc<Mutex<Option<String>>>,
+        encoder_control: Arc<EncoderControl>,
+    ) -> Self {
         let stop = Arc::new(AtomicBool::new(false));
         let stop_flag = stop.clone();
 
         let handle = thread::spawn(move || {
-            Self::capture_loop(manager, stop_flag, camera_error);
+            Self::capture_loop(manager, stop_flag, camera_error, encoder_control);
+        });
+
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    fn start_screen(manager: AppManager, screen_error: Arc<Mutex<Option<String>>>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+
+        let handle = thread::spawn(move || {
+            Self::screen_loop(manager, stop_flag, screen_error);
         });
 
         Self {
@@ -100,6 +172,7 @@ impl CaptureThread {
         manager: AppManager,
         stop: Arc<AtomicBool>,
         camera_error: Arc<Mutex<Option<String>>>,
+        encoder_control: Arc<EncoderControl>,
     ) {
         use nokhwa::pixel_format::RgbFormat;
         use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType, Resolution};
@@ -130,7 +203,15 @@ impl CaptureThread {
             return;
         }
 
-        let mut encoder: openh264::encoder::Encoder = match openh264::encoder::Encoder::new() {
+        // Starts at the top profile; the core steps it down when receivers
+        // report congestion.
+        let mut profile = CallVideoProfile {
+            width: video_params::WIDTH,
+            height: video_params::HEIGHT,
+            fps: video_params::FPS,
+            bitrate_bps: video_params::BITRATE_BPS,
+        };
+        let mut encoder = match new_encoder(&profile) {
             Ok(e) => e,
             Err(e) => {
                 set_error(format!("H.264 encoder init failed: {e}"));
@@ -139,6 +220,7 @@ impl CaptureThread {
         };
 
         let mut frame_count = 0u64;
+        let mut last_encoded: Option<Instant> = None;
 
         while !stop.load(Ordering::Relaxed) {
             // camera.frame() blocks until the next frame is available,
@@ -148,22 +230,55 @@ impl CaptureThread {
                 Err(_) => continue,
             };
 
+            let requested = encoder_control
+                .profile
+                .lock()
+                .ok()
+                .and_then(|slot| *slot)
+                .filter(|requested| *requested != profile);
+            if let Some(requested) = requested {
+                match new_encoder(&requested) {
+                    Ok(e) => {
+                        encoder = e;
+                        profile = requested;
+                        // A fresh encoder starts with an IDR anyway.
+                        frame_count = 0;
+                    }
+                    Err(e) => eprintln!("[video] H.264 encoder reconfigure failed: {e}"),
+                }
+            }
+
+            // Drop camera frames beyond the profile's frame rate.
+            let frame_interval = Duration::from_secs(1) / profile.fps.max(1);
+            if last_encoded.is_some_and(|t| t.elapsed() < frame_interval.mul_f32(0.9)) {
+                continue;
+            }
+            last_encoded = Some(Instant::now());
+
             // Decode camera buffer to RGB
             let rgb_image = match buf.decode_image::<RgbFormat>() {
                 Ok(img) => img,
                 Err(_) => continue,
             };
 
-            let width = rgb_image.width() as usize;
-            let height = rgb_image.height() as usize;
-            let rgb_bytes = rgb_image.as_raw();
+            let (width, height, rgb_bytes) = fit_rgb(
+                rgb_image.as_raw(),
+                rgb_image.width() as usize,
+                rgb_image.height() as usize,
+                profile.width as usize,
+                profile.height as usize,
+            );
 
             // Convert RGB to YUV420 for openh264
-            let yuv = rgb_to_yuv420(rgb_bytes, width, height);
+            let yuv = rgb_to_yuv420(&rgb_bytes, width, height);
             let yuv_buf = YUVBuffer::from_vec(yuv, width, height);
 
-            // Force IDR periodically so late joiners get SPS/PPS
-            if frame_count.is_multiple_of(video_params::KEYFRAME_INTERVAL as u64) {
+            // Force IDR periodically so late joiners get SPS/PPS, and whenever a
+            // receiver lost sync.
+            let requested_keyframe = encoder_control.keyframe.swap(false, Ordering::Relaxed);
+            if requested_keyframe
+                || frame_count.is_multiple_of(video_params::KEYFRAME_INTERVAL as u64)
+            {
                 encoder.force_intra_frame();
             }
             frame_count += 1;
@@ -184,6 +299,143 @@ impl CaptureThread {
 
         let _ = camera.stop_stream();
     }
+
+    /// Captures the first monitor at `screen_params::FPS`. Screen share has no
+    /// rate adaptation, so the encoder stays at `screen_params` throughout.
+    fn screen_loop(
+        manager: AppManager,
+        stop: Arc<AtomicBool>,
+        screen_error: Arc<Mutex<Option<String>>>,
+    ) {
+        use openh264::formats::YUVBuffer;
+
+        let set_error = |msg: String| {
+            if let Ok(mut slot) = screen_error.lock() {
+                *slot = Some(msg);
+            }
+        };
+
+        let monitor = match xcap::Monitor::all() {
+            Ok(monitors) => monitors.into_iter().next(),
+            Err(e) => {
+                set_error(format!("Screen capture unavailable: {e}"));
+                return;
+            }
+        };
+        let Some(monitor) = monitor else {
+            set_error("No screen to share".to_string());
+            return;
+        };
+
+        let profile = CallVideoProfile {
+            width: screen_params::WIDTH,
+            height: screen_params::HEIGHT,
+            fps: screen_params::FPS,
+            bitrate_bps: screen_params::BITRATE_BPS,
+        };
+        let mut encoder = match new_encoder(&profile) {
+            Ok(e) => e,
+            Err(e) => {
+                set_error(format!("H.264 encoder init failed: {e}"));
+                return;
+            }
+        };
+
+        let frame_interval = Duration::from_secs(1) / screen_params::FPS;
+        let mut next_frame = Instant::now();
+        let mut frame_count = 0u64;
+
+        while !stop.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if next_frame > now {
+                thread::sleep(next_frame - now);
+                continue;
+            }
+            next_frame = now + frame_interval;
+
+            let image = match monitor.capture_image() {
+                Ok(image) => image,
+                Err(e) => {
+                    set_error(format!("Screen capture failed: {e}"));
+                    continue;
+                }
+            };
+            let rgb: Vec<u8> = image
+                .as_raw()
+                .chunks_exact(4)
+                .flat_map(|px| [px[0], px[1], px[2]])
+                .collect();
+            let src_width = image.width() as usize;
+            let src_height = image.height() as usize;
+            // YUV420 needs even dimensions; odd-sized screens get scaled a pixel.
+            let (width, height, rgb_bytes) = fit_rgb(
+                &rgb,
+                src_width,
+                src_height,
+                (profile.width as usize).min(src_width & !1),
+                (profile.height as usize).min(src_height & !1),
+            );
+
+            let yuv = rgb_to_yuv420(&rgb_bytes, width, height);
+            let yuv_buf = YUVBuffer::from_vec(yuv, width, height);
+
+            // Nobody requests screen keyframes, so send them on a fixed interval
+            // for viewers who join or lose frames mid-share.
+            if frame_count.is_multiple_of(screen_params::KEYFRAME_INTERVAL as u64) {
+                encoder.force_intra_frame();
+            }
+            frame_count += 1;
+
+            let encoded: openh264::encoder::EncodedBitStream<'_> = match encoder.encode(&yuv_buf) {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+            let annexb = encoded.to_vec();
+            if !annexb.is_empty() {
+                manager.send_screen_frame(annexb);
+            }
+        }
+    }
+}
+
+fn new_encoder(profile: &CallVideoProfile) -> Result<openh264::encoder::Encoder, openh264::Error> {
+    use openh264::encoder::{BitRate, Encoder, EncoderConfig, FrameRate};
+    use openh264::OpenH264API;
+
+    let config = EncoderConfig::new()
+        .bitrate(BitRate::from_bps(profile.bitrate_bps))
+        .max_frame_rate(FrameRate::from_hz(profile.fps as f32));
+    Encoder::with_api_config(OpenH264API::from_source(), config)
+}
+
+/// Nearest-neighbour downscale of an RGB frame to fit within the profile size,
+/// keeping the aspect ratio and even dimensions for YUV420.
+fn fit_rgb(
+    rgb: &[u8],
+    width: usize,
+    height: usize,
+    max_width: usize,
+    max_height: usize,
+) -> (usize, usize, std::borrow::Cow<'_, [u8]>) {
+    if width <= max_width && height <= max_height {
+        return (width, height, std::borrow::Cow::Borrowed(rgb));
+    }
+    let scale = (max_width as f64 / width as f64).min(max_height as f64 / height as f64);
+    let out_width = (((width as f64 * scale) as usize) & !1).max(2);
+    let out_height = (((height as f64 * scale) as usize) & !1).max(2);
+    let mut out = vec![0u8; out_width * out_height * 3];
+    for row in 0..out_height {
+        let src_row = row * height / out_height;
+        for col in 0..out_width {
+            let src_col = col * width / out_width;
+            let src = (src_row * width + src_col) * 3;
+            let dst = (row * out_width + col) * 3;
+            if src + 2 < rgb.len() {
+                out[dst..dst + 3].copy_from_slice(&rgb[src..src + 3]);
+            }
+        }
+    }
+    (out_width, out_height, std::borrow::Cow::Owned(out))
 }
 
 fn rgb_to_yuv420(rgb: &[u8], width: usize, height: usize) -> Vec<u8> {
@@ -220,29 +472,78 @@ fn rgb_to_yuv420(rgb: &[u8], width: usize, height: usize) -> Vec<u8> {
     yuv
 }
 
+/// One remote stream as the call screen sees it: the frame slot plus
+/// staleness tracking.
+struct RemoteFeed {
+    slot: FrameSlot,
+    /// The stream counts as stopped once no new frame arrived for this long.
+    stale_after: Duration,
+    /// Track the last generation we saw a new frame, for staleness detection.
+    last_seen_generation: u64,
+    /// Monotonic instant of the last new remote frame.
+    last_frame_instant: Option<Instant>,
+}
+
+impl RemoteFeed {
+    fn new(stale_after: Duration) -> Self {
+        Self {
+            slot: FrameSlot::default(),
+            stale_after,
+            last_seen_generation: 0,
+            last_frame_instant: None,
+        }
+    }
+
+    fn is_live(&self) -> bool {
+        self.slot.generation.load(Ordering::Relaxed) > 0
+            && self
+                .last_frame_instant
+                .is_some_and(|t| t.elapsed() < self.stale_after)
+    }
+
+    fn check_staleness(&mut self) {
+        let gen = self.slot.generation.load(Ordering::Relaxed);
+        if gen != self.last_seen_generation {
+            self.last_seen_generation = gen;
+            self.last_frame_instant = Some(Instant::now());
+        } else if let Some(t) = self.last_frame_instant {
+            if t.elapsed() > self.stale_after {
+                // Remote stopped sending — clear the frame
+                self.slot.clear();
+                self.last_frame_instant = None;
+            }
+        }
+    }
+
+    fn program<S: VideoStream>(&self) -> VideoShaderProgram<S> {
+        VideoShaderProgram::new(self.slot.frame.clone(), self.slot.generation.clone())
+    }
+}
+
 /// Manages the full desktop video pipeline lifecycle.
 pub struct DesktopVideoPipeline {
-    latest_frame: Arc<Mutex<Option<DecodedFrame>>>,
-    generation: Arc<AtomicU64>,
+    remote: RemoteFeed,
+    screen: RemoteFeed,
     camera_error: Arc<Mutex<Option<String>>>,
+    screen_error: Arc<Mutex<Option<String>>>,
+    encoder_control: Arc<EncoderControl>,
     capture_thread: Option<CaptureThread>,
+    screen_thread: Option<CaptureThread>,
     is_active: bool,
-    /// Track the last generation we saw a new frame, for staleness detection.
-    last_seen_generation: u64,
-    /// Monotonic instant of the last new remote frame.
-    last_frame_instant: Option<std::time::Instant>,
 }
 
 impl DesktopVideoPipeline {
     pub fn new() -> Self {
         Self {
-            latest_frame: Arc::new(Mutex::new(None)),
-            generation: Arc::new(AtomicU64::new(0)),
+            remote: RemoteFeed::new(Duration::from_secs(1)),
+            // Screen shares run at a few frames per second.
+            screen: RemoteFeed::new(Duration::from_secs(2)),
             camera_error: Arc::new(Mutex::new(None)),
+            screen_error: Arc::new(Mutex::new(None)),
+            encoder_control: Arc::new(EncoderControl::default()),
             capture_thread: None,
+            screen_thread: None,
             is_active: false,
-            last_seen_generation: 0,
-            last_frame_instant: None,
         }
     }
 
@@ -250,24 +551,38 @@ impl DesktopVideoPipeline {
         self.camera_error.lock().ok()?.clone()
     }
 
+    pub fn screen_error(&self) -> Option<String> {
+        self.screen_error.lock().ok()?.clone()
+    }
+
     pub fn start(&mut self, manager: &AppManager) {
         if self.is_active {
             return;
         }
         self.is_active = true;
 
-        // Clear any previous camera error
-        if let Ok(mut slot) = self.camera_error.lock() {
-            *slot = None;
+        // Clear any previous capture errors
+        for error in [&self.camera_error, &self.screen_error] {
+            if let Ok(mut slot) = error.lock() {
+                *slot = None;
+            }
         }
 
-        let receiver =
-            DesktopVideoReceiver::new(self.latest_frame.clone(), self.generation.clone());
+        // Each call starts back at the top profile.
+        if let Ok(mut slot) = self.encoder_control.profile.lock() {
+            *slot = None;
+        }
+        let receiver = DesktopVideoReceiver::new(
+            self.remote.slot.clone(),
+            self.screen.slot.clone(),
+            self.encoder_control.clone(),
+        );
         manager.set_video_frame_receiver(Box::new(receiver));
 
         self.capture_thread = Some(CaptureThread::start(
             manager.clone(),
             self.camera_error.clone(),
+            self.encoder_control.clone(),
         ));
     }
 
@@ -280,9 +595,9 @@ impl DesktopVideoPipeline {
         if let Some(mut ct) = self.capture_thread.take() {
             ct.stop();
         }
-        if let Ok(mut slot) = self.latest_frame.lock() {
-            *slot = None;
-        }
+        self.stop_screen_capture();
+        self.remote.slot.clear();
+        self.screen.slot.clear();
     }
 
     /// Stop only the camera capture thread (when camera is toggled off) but keep
@@ -300,38 +615,54 @@ impl DesktopVideoPipeline {
         self.capture_thread = Some(CaptureThread::start(
             manager.clone(),
             self.camera_error.clone(),
+            self.encoder_control.clone(),
         ));
     }
 
+    fn start_screen_capture(&mut self, manager: &AppManager) {
+        if self.screen_thread.is_some() {
+            return;
+        }
+        if let Ok(mut slot) = self.screen_error.lock() {
+            *slot = None;
+        }
+        self.screen_thread = Some(CaptureThread::start_screen(
+            manager.clone(),
+            self.screen_error.clone(),
+        ));
+    }
+
+    fn stop_screen_capture(&mut self) {
+        if let Some(mut st) = self.screen_thread.take() {
+            st.stop();
+        }
+    }
+
     /// Whether at least one video frame has been decoded and is not stale.
     pub fn has_video(&self) -> bool {
-        self.generation.load(Ordering::Relaxed) > 0
-            && self
-                .last_frame_instant
-                .is_some_and(|t| t.elapsed().as_secs_f64() < 1.0)
+        self.remote.is_live()
+    }
+
+    /// Whether a remote participant is sharing their screen.
+    pub fn has_screen_share(&self) -> bool {
+        self.screen.is_live()
     }
 
     /// Call periodically (e.g. on each video tick) to update staleness tracking
-    /// and clear the decoded frame if no new frames arrive for 1 second.
+    /// and clear decoded frames once their stream stops.
     pub fn check_staleness(&mut self) {
-        let gen = self.generation.load(Ordering::Relaxed);
-        if gen != self.last_seen_generation {
-            self.last_seen_generation = gen;
-            self.last_frame_instant = Some(std::time::Instant::now());
-        } else if let Some(t) = self.last_frame_instant {
-            if t.elapsed().as_secs_f64() > 1.0 {
-                // Remote stopped sending — clear the frame
-                if let Ok(mut slot) = self.latest_frame.lock() {
-                    *slot = None;
-                }
-                self.last_frame_instant = None;
-            }
-        }
+        self.remote.check_staleness();
+        self.screen.check_staleness();
     }
 
     /// Create a shader program for rendering the video via a persistent GPU texture.
-    pub fn shader_program(&self) -> VideoShaderProgram {
-        VideoShaderProgram::new(self.latest_frame.clone(), self.generation.clone())
+    pub fn shader_program(&self) -> VideoShaderProgram<RemoteCamera> {
+        self.remote.program()
+    }
+
+    /// Shader program for the remote screen share.
+    pub fn screen_program(&self) -> VideoShaderProgram<RemoteScreen> {
+        self.screen.program()
     }
 
     pub fn sync_with_call(&mut self, call: Option<&pika_core::CallState>, manager: &AppManager) {
@@ -346,6 +677,11 @@ impl DesktopVideoPipeline {
                 } else {
                     self.stop_capture();
                 }
+                if call.is_screen_sharing {
+                    self.start_screen_capture(manager);
+                } else {
+                    self.stop_screen_capture();
+                }
             }
             _ => {
                 self.stop();
@@ -1,3 +1,4 @@
+use std::marker::PhantomData;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
@@ -8,28 +9,44 @@ use iced::Rectangle;
 
 use crate::video::DecodedFrame;
 
+/// Which remote stream a [`VideoShaderProgram`] shows. iced keeps one pipeline,
+/// and so one texture, per primitive type; streams on screen at the same time
+/// need distinct markers.
+pub trait VideoStream: Send + Sync + 'static {}
+
+/// The remote participant's camera.
+pub struct RemoteCamera;
+
+/// The remote participant's screen share.
+pub struct RemoteScreen;
+
+impl VideoStream for RemoteCamera {}
+impl VideoStream for RemoteScreen {}
+
 /// Shader-based video renderer that maintains a persistent GPU texture.
 ///
 /// Unlike `iced::widget::image` which recreates GPU textures on every
 /// `Handle::from_rgba()` call (causing flicker), this renders by updating
 /// the texture in-place via `queue.write_texture()`.
-pub struct VideoShaderProgram {
+pub struct VideoShaderProgram<S: VideoStream> {
     frame_data: Arc<Mutex<Option<DecodedFrame>>>,
     generation: Arc<AtomicU64>,
+    stream: PhantomData<fn() -> S>,
 }
 
-impl VideoShaderProgram {
+impl<S: VideoStream> VideoShaderProgram<S> {
     pub fn new(frame_data: Arc<Mutex<Option<DecodedFrame>>>, generation: Arc<AtomicU64>) -> Self {
         Self {
             frame_data,
             generation,
+            stream: PhantomData,
         }
     }
 }
 
-impl<Message: Send + 'static> shader::Program<Message> for VideoShaderProgram {
+impl<Message: Send + 'static, S: VideoStream> shader::Program<Message> for VideoShaderProgram<S> {
     type State = ();
-    type Primitive = VideoFramePrimitive;
+    type Primitive = VideoFramePrimitive<S>;
 
     fn draw(
         &self,
@@ -50,19 +67,21 @@ impl<Message: Send + 'static> shader::Program<Message> for VideoShaderProgram {
             generation: gen,
             video_width: width,
             video_height: height,
+            stream: PhantomData,
         }
     }
 }
 
 /// Carries per-frame data for the GPU upload.
-pub struct VideoFramePrimitive {
+pub struct VideoFramePrimitive<S: VideoStream> {
     frame_data: Arc<Mutex<Option<DecodedFrame>>>,
     generation: u64,
     video_width: u32,
     video_height: u32,
+    stream: PhantomData<fn() -> S>,
 }
 
-impl std::fmt::Debug for VideoFramePrimitive {
+impl<S: VideoStream> std::fmt::Debug for VideoFramePrimitive<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("VideoFramePrimitive")
             .field("generation", &self.generation)
@@ -72,12 +91,12 @@ impl std::fmt::Debug for VideoFramePrimitive {
     }
 }
 
-impl shader::Primitive for VideoFramePrimitive {
-    type Pipeline = VideoFramePipeline;
+impl<S: VideoStream> shader::Primitive for VideoFramePrimitive<S> {
+    type Pipeline = VideoFramePipeline<S>;
 
     fn prepare(
         &self,
-        pipeline: &mut VideoFramePipeline,
+        pipeline: &mut VideoFramePipeline<S>,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         bounds: &Rectangle,
@@ -133,7 +152,7 @@ impl shader::Primitive for VideoFramePrimitive {
 
     fn render(
         &self,
-        pipeline: &VideoFramePipeline,
+        pipeline: &VideoFramePipeline<S>,
         encoder: &mut wgpu::CommandEncoder,
         target: &wgpu::TextureView,
         clip_bounds: &Rectangle<u32>,
@@ -169,7 +188,7 @@ impl shader::Primitive for VideoFramePrimitive {
 }
 
 /// Persistent GPU resources for video rendering — created once by iced.
-pub struct VideoFramePipeline {
+pub struct VideoFramePipeline<S: VideoStream> {
     render_pipeline: wgpu::RenderPipeline,
     texture: wgpu::Texture,
     sampler: wgpu::Sampler,
@@ -179,9 +198,10 @@ pub struct VideoFramePipeline {
     texture_width: u32,
     texture_height: u32,
     last_generation: u64,
+    stream: PhantomData<fn() -> S>,
 }
 
-impl VideoFramePipeline {
+impl<S: VideoStream> VideoFramePipeline<S> {
     fn recreate_texture(&mut self, device: &wgpu::Device, width: u32, height: u32) {
         self.texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("video_frame_texture"),
@@ -252,7 +272,7 @@ impl VideoFramePipeline {
     }
 }
 
-impl shader::Pipeline for VideoFramePipeline {
+impl<S: VideoStream> shader::Pipeline for VideoFramePipeline<S> {
     fn new(device: &wgpu::Device, _queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
         // Initial 1x1 texture (will be resized on first frame).
         let texture = device.create_texture(&wgpu::TextureDescriptor {
@@ -387,6 +407,7 @@ impl shader::Pipeline for VideoFramePipeline {
             texture_width: 1,
             texture_height: 1,
             last_generation: 0,
+            stream: PhantomData,
         }
     }
 }
@@ -1,6 +1,6 @@
 use iced::widget::{button, center, column, container, row, shader, stack, text, Space};
-use iced::{Alignment, Element, Fill, Theme};
-use pika_core::{CallState, CallStatus};
+use iced::{Alignment, Element, Fill, FillPortion, Theme};
+use pika_core::{CallState, CallStatus, MemberInfo};
 
 use super::avatar::{avatar_circle, AvatarCache};
 use crate::video::DesktopVideoPipeline;
@@ -16,6 +16,11 @@ pub enum Message {
     EndCall,
     ToggleMute,
     ToggleCamera,
+    ToggleAudioProcessing,
+    StartScreenShare,
+    StopScreenShare,
+    ToggleRecording,
+    SaveCaptions,
 }
 
 /// Full-screen call overlay (matches the iOS CallScreenView layout).
@@ -23,6 +28,7 @@ pub fn call_screen_view<'a>(
     call: &'a CallState,
     peer_name: &'a str,
     peer_picture_url: Option<&str>,
+    members: &'a [MemberInfo],
     video_pipeline: &DesktopVideoPipeline,
     cache: &mut AvatarCache,
 ) -> Element<'a, Message, Theme> {
@@ -36,27 +42,25 @@ pub fn call_screen_view<'a>(
 
     // For video calls, use a stacked layout (video background + controls overlay)
     if call.is_video_call {
-        let has_video = video_pipeline.has_video();
-        let camera_err = video_pipeline.camera_error();
-        let program = video_pipeline.shader_program();
-        return build_video_call_layout(
-            call,
-            peer_name,
-            status_text,
-            has_video,
-            program,
-            camera_err,
-        );
+        return build_video_call_layout(call, peer_name, members, status_text, video_pipeline);
     }
 
     // Audio call (or video call without a frame yet): standard layout
-    build_audio_call_layout(call, peer_name, peer_picture_url, status_text, cache)
+    build_audio_call_layout(
+        call,
+        peer_name,
+        peer_picture_url,
+        members,
+        status_text,
+        cache,
+    )
 }
 
 fn build_audio_call_layout<'a>(
     call: &'a CallState,
     peer_name: &'a str,
     peer_picture_url: Option<&str>,
+    members: &'a [MemberInfo],
     status_text: &'a str,
     cache: &mut AvatarCache,
 ) -> Element<'a, Message, Theme> {
@@ -81,6 +85,7 @@ fn build_audio_call_layout<'a>(
     .spacing(8)
     .width(Fill);
 
+    content = push_participants(content, call, members);
     content = push_duration_and_debug(content, call);
     content = content.push(Space::new().height(Fill));
     content = content.push(center(build_controls(call)).width(Fill));
@@ -97,15 +102,17 @@ fn build_audio_call_layout<'a>(
 fn build_video_call_layout<'a>(
     call: &'a CallState,
     peer_name: &'a str,
+    members: &'a [MemberInfo],
     status_text: &'a str,
-    has_video: bool,
-    program: crate::video_shader::VideoShaderProgram,
-    camera_error: Option<String>,
+    video_pipeline: &DesktopVideoPipeline,
 ) -> Element<'a, Message, Theme> {
     // Video background: shader widget renders directly to a persistent GPU texture
     // (no flicker from Handle::from_rgba texture recreation).
-    let video_bg: Element<'a, Message, Theme> = if has_video {
-        shader(program).width(Fill).into()
+    let camera_view: Element<'a, Message, Theme> = if video_pipeline.has_video() {
+        shader(video_pipeline.shader_program())
+            .width(Fill)
+            .height(Fill)
+            .into()
     } else {
         // No remote frame yet — show waiting message on black background
         container(
@@ -134,6 +141,27 @@ fn build_video_call_layout<'a>(
         })
         .into()
     };
+    // A remote screen share takes most of the width, with the camera beside it.
+    let video_bg: Element<'a, Message, Theme> = if video_pipeline.has_screen_share() {
+        row![
+            shader(video_pipeline.screen_program())
+                .width(FillPortion(3))
+                .height(Fill),
+            container(camera_view)
+                .width(FillPortion(1))
+                .height(Fill)
+                .center_y(Fill),
+        ]
+        .spacing(4)
+        .into()
+    } else {
+        camera_view
+    };
+    let status_line = if call.is_screen_sharing {
+        format!("{peer_name} \u{2022} {status_text} \u{2022} Sharing your screen")
+    } else {
+        format!("{peer_name} \u{2022} {status_text}")
+    };
 
     // Controls overlay on top of video
     let mut overlay = column![].width(Fill).height(Fill);
@@ -146,26 +174,25 @@ fn build_video_call_layout<'a>(
                 .padding([4, 12])
                 .style(theme::call_control_button_style),
             Space::new().width(Fill),
-            container(
-                text(format!("{peer_name} \u{2022} {status_text}"))
-                    .size(14)
-                    .color(iced::Color::WHITE),
-            )
-            .padding([4, 10])
-            .style(|_: &Theme| container::Style {
-                background: Some(iced::Background::Color(iced::Color::from_rgba(
-                    0.0, 0.0, 0.0, 0.5,
-                ))),
-                border: iced::border::rounded(6),
-                ..Default::default()
-            }),
+            container(text(status_line).size(14).color(iced::Color::WHITE))
+                .padding([4, 10])
+                .style(|_: &Theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.5,
+                    ))),
+                    border: iced::border::rounded(6),
+                    ..Default::default()
+                }),
         ]
         .padding([12, 16])
         .align_y(Alignment::Center),
     );
 
-    // Camera error banner
-    if let Some(err) = camera_error {
+    // Camera / screen capture error banner
+    if let Some(err) = video_pipeline
+        .camera_error()
+        .or_else(|| video_pipeline.screen_error())
+    {
         overlay = overlay.push(
             container(text(err).size(12).color(design::call_error_color()))
                 .padding([4, 12])
@@ -180,6 +207,8 @@ fn build_video_call_layout<'a>(
         );
     }
 
+    overlay = push_participants(overlay, call, members);
+
     // Duration and debug stats
     overlay = push_duration_and_debug(overlay, call);
 
@@ -213,6 +242,60 @@ fn dismiss_button_row<'a>() -> Element<'a, Message, Theme> {
     .into()
 }
 
+/// Group calls: one chip per remote participant, ringed while speaking.
+fn push_participants<'a>(
+    content: iced::widget::Column<'a, Message, Theme>,
+    call: &'a CallState,
+    members: &'a [MemberInfo],
+) -> iced::widget::Column<'a, Message, Theme> {
+    if !call.is_group_call || call.participants.is_empty() {
+        return content;
+    }
+    let chips = call.participants.iter().map(|participant| {
+        let name = members
+            .iter()
+            .find(|m| m.npub == participant.npub)
+            .and_then(|m| m.name.clone())
+            .unwrap_or_else(|| short_npub(&participant.npub));
+        let label = if participant.is_muted {
+            format!("{name} \u{1F507}")
+        } else {
+            name
+        };
+        let speaking = participant.is_speaking;
+        container(text(label).size(13).color(iced::Color::WHITE))
+            .padding([4, 10])
+            .style(move |_: &Theme| container::Style {
+                background: Some(iced::Background::Color(iced::Color::from_rgba(
+                    1.0, 1.0, 1.0, 0.12,
+                ))),
+                border: iced::Border {
+                    color: if speaking {
+                        design::call_speaking_color()
+                    } else {
+                        iced::Color::TRANSPARENT
+                    },
+                    width: 2.0,
+                    radius: 12.0.into(),
+                },
+                ..Default::default()
+            })
+            .into()
+    });
+    content.push(
+        container(row(chips).spacing(8))
+            .center_x(Fill)
+            .padding([4, 0]),
+    )
+}
+
+fn short_npub(npub: &str) -> String {
+    if npub.len() <= 16 {
+        return npub.to_string();
+    }
+    format!("{}\u{2026}{}", &npub[..8], &npub[npub.len() - 4..])
+}
+
 fn push_duration_and_debug<'a>(
     mut content: iced::widget::Column<'a, Message, Theme>,
     call: &'a CallState,
@@ -229,22 +312,68 @@ fn push_duration_and_debug<'a>(
         }
     }
 
+    // Everyone on the call sees this while anyone, including us, is recording.
+    if call.is_live && (call.is_recording || call.participants.iter().any(|p| p.is_recording)) {
+        content = content.push(
+            text("\u{25cf} Recording")
+                .size(14)
+                .color(design::call_error_color())
+                .center()
+                .width(Fill),
+        );
+    }
+
+    for caption in &call.captions {
+        let speaker = if caption.is_local {
+            "You"
+        } else {
+            caption.speaker_name.as_str()
+        };
+        content = content.push(
+            text(format!("{speaker}: {}", caption.text))
+                .size(15)
+                .color(iced::Color::from_rgba(1.0, 1.0, 1.0, 0.85))
+                .center()
+                .width(Fill),
+        );
+    }
+
     if let Some(debug) = &call.debug {
         let video_stats = if debug.video_tx > 0 || debug.video_rx > 0 {
             let mut s = format!(" vtx:{} vrx:{}", debug.video_tx, debug.video_rx);
             if debug.video_rx_decrypt_fail > 0 {
                 s += &format!(" vfail:{}", debug.video_rx_decrypt_fail);
             }
+            if let Some(profile) = &debug.video_profile {
+                s += &format!(
+                    " {}p{}@{}k",
+                    profile.height,
+                    profile.fps,
+                    profile.bitrate_bps / 1_000
+                );
+            }
             s
         } else {
             String::new()
         };
+        let quality_stats = if debug.rx_concealed > 0 || debug.rx_reordered > 0 || debug.rx_late > 0
+        {
+            format!(
+                " plc:{} reord:{} late:{}",
+                debug.rx_concealed, debug.rx_reordered, debug.rx_late
+            )
+        } else {
+            String::new()
+        };
         let stats = format!(
-            "TX:{} RX:{} drop:{} jitter:{}ms{}{}",
+            "TX:{} RX:{} drop:{} jitter:{}/{}ms (net {}ms){}{}{}",
             debug.tx_frames,
             debug.rx_frames,
             debug.rx_dropped,
             debug.jitter_buffer_ms,
+            debug.jitter_target_ms,
+            debug.rx_jitter_ms,
+            quality_stats,
             debug
                 .last_rtt_ms
                 .map(|rtt| format!(" rtt:{rtt}ms"))
@@ -287,14 +416,34 @@ fn build_controls<'a>(call: &'a CallState) -> Element<'a, Message, Theme> {
             } else {
                 theme::call_control_button_style
             };
-            let mut controls =
-                row![
-                    button(text(mute_label).size(14).color(iced::Color::WHITE).center())
-                        .on_press(Message::ToggleMute)
-                        .padding([12, 24])
-                        .style(mute_style),
-                ]
-                .align_y(Alignment::Center);
+            let processing_label = if call.is_audio_processing_enabled {
+                "Clean Audio"
+            } else {
+                "Raw Audio"
+            };
+            let processing_style: fn(&Theme, button::Status) -> button::Style =
+                if call.is_audio_processing_enabled {
+                    theme::call_control_button_style
+                } else {
+                    theme::call_muted_button_style
+                };
+            let mut controls = row![
+                button(text(mute_label).size(14).color(iced::Color::WHITE).center())
+                    .on_press(Message::ToggleMute)
+                    .padding([12, 24])
+                    .style(mute_style),
+                Space::new().width(24),
+                button(
+                    text(processing_label)
+                        .size(14)
+                        .color(iced::Color::WHITE)
+                        .center()
+                )
+                .on_press(Message::ToggleAudioProcessing)
+                .padding([12, 24])
+                .style(processing_style),
+            ]
+            .align_y(Alignment::Center);
 
             if call.is_video_call {
                 let cam_label = if call.is_camera_enabled {
@@ -314,6 +463,47 @@ fn build_controls<'a>(call: &'a CallState) -> Element<'a, Message, Theme> {
                         .padding([12, 24])
                         .style(cam_style),
                 );
+
+                if matches!(call.status, CallStatus::Connecting | CallStatus::Active) {
+                    let (share_label, share_message) = if call.is_screen_sharing {
+                        ("Stop Sharing", Message::StopScreenShare)
+                    } else {
+                        ("Share Screen", Message::StartScreenShare)
+                    };
+                    controls = controls.push(Space::new().width(24)).push(
+                        button(
+                            text(share_label)
+                                .size(14)
+                                .color(iced::Color::WHITE)
+                                .center(),
+                        )
+                        .on_press(share_message)
+                        .padding([12, 24])
+                        .style(theme::call_control_button_style),
+                    );
+                }
+            }
+
+            if matches!(call.status, CallStatus::Connecting | CallStatus::Active) {
+                let (record_label, record_style): (
+                    &str,
+                    fn(&Theme, button::Status) -> button::Style,
+                ) = if call.is_recording {
+                    ("Stop Recording", theme::call_muted_button_style)
+                } else {
+                    ("Record", theme::call_control_button_style)
+                };
+                controls = controls.push(Space::new().width(24)).push(
+                    button(
+                        text(record_label)
+                            .size(14)
+                            .color(iced::Color::WHITE)
+                            .center(),
+                    )
+                    .on_press(Message::ToggleRecording)
+                    .padding([12, 24])
+                    .style(record_style),
+                );
             }
 
             controls = controls.push(Space::new().width(48)).push(
@@ -330,33 +520,47 @@ fn build_controls<'a>(call: &'a CallState) -> Element<'a, Message, Theme> {
             } else {
                 reason.clone()
             };
+            let mut actions = row![
+                button(text("Done").size(14).color(iced::Color::WHITE).center())
+                    .on_press(Message::DismissCallScreen)
+                    .padding([10, 20])
+                    .style(theme::secondary_button_style),
+                Space::new().width(24),
+                button(
+                    text("Start Again")
+                        .size(14)
+                        .color(iced::Color::WHITE)
+                        .center()
+                )
+                .on_press(if call.is_video_call {
+                    Message::StartVideoCall
+                } else {
+                    Message::StartCall
+                })
+                .padding([10, 20])
+                .style(theme::call_accept_button_style),
+            ]
+            .align_y(Alignment::Center);
+            if !call.captions.is_empty() {
+                actions = actions.push(Space::new().width(24)).push(
+                    button(
+                        text("Save Captions")
+                            .size(14)
+                            .color(iced::Color::WHITE)
+                            .center(),
+                    )
+                    .on_press(Message::SaveCaptions)
+                    .padding([10, 20])
+                    .style(theme::secondary_button_style),
+                );
+            }
             column![
                 text(reason_text)
                     .size(14)
                     .color(iced::Color::from_rgba(1.0, 1.0, 1.0, 0.7))
                     .center()
                     .width(Fill),
-                row![
-                    button(text("Done").size(14).color(iced::Color::WHITE).center())
-                        .on_press(Message::DismissCallScreen)
-                        .padding([10, 20])
-                        .style(theme::secondary_button_style),
-                    Space::new().width(24),
-                    button(
-                        text("Start Again")
-                            .size(14)
-                            .color(iced::Color::WHITE)
-                            .center()
-                    )
-                    .on_press(if call.is_video_call {
-                        Message::StartVideoCall
-                    } else {
-                        Message::StartCall
-                    })
-                    .padding([10, 20])
-                    .style(theme::call_accept_button_style),
-                ]
-                .align_y(Alignment::Center),
+                actions,
             ]
             .spacing(12)
             .align_x(Alignment::Center)
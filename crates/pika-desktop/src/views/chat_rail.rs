@@ -1,6 +1,6 @@
 use iced::widget::{button, column, container, row, scrollable, text, Space};
 use iced::{Alignment, Element, Fill, Length, Padding, Theme};
-use pika_core::ChatSummary;
+use pika_core::{ChatSummary, InvitationSummary};
 
 use crate::icons;
 use crate::theme;
@@ -8,15 +8,19 @@ use crate::views::avatar::avatar_circle;
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    AcceptInvite(String),
     ClickMyProfile,
     ClickNewChat,
     ClickNewGroup,
+    DeclineInvite(String),
     OpenChat(String),
 }
 
 /// Left sidebar containing the chat list and action buttons.
+#[allow(clippy::too_many_arguments)]
 pub fn view<'a>(
     chat_list: &[ChatSummary],
+    invitations: &[InvitationSummary],
     selected_id: Option<&str>,
     show_new_chat_form: bool,
     show_new_group_form: bool,
@@ -56,8 +60,23 @@ pub fn view<'a>(
     .align_y(Alignment::Center)
     .padding([0, 4]);
 
-    // ── Chat list ───────────────────────────────────────────────────
-    let chat_items = chat_list.iter().fold(column![].spacing(4), |col, chat| {
+    // ── Invitations + chat list ─────────────────────────────────────
+    let mut chat_items = column![].spacing(4);
+    if !invitations.is_empty() {
+        chat_items = chat_items.push(
+            container(
+                text("Invitations")
+                    .size(13)
+                    .font(icons::BOLD)
+                    .color(theme::text_faded()),
+            )
+            .padding([4, 12]),
+        );
+        for invite in invitations {
+            chat_items = chat_items.push(invite_item(invite, avatar_cache));
+        }
+    }
+    let chat_items = chat_list.iter().fold(chat_items, |col, chat| {
         col.push(chat_item(chat, selected_id, avatar_cache))
     });
 
@@ -126,16 +145,22 @@ fn chat_item<'a>(
     let avatar: Element<'a, Message, Theme> =
         avatar_circle(Some(&name), picture_url, 48.0, avatar_cache);
 
-    // Name + timestamp row
-    let top_row = row![
-        text(theme::truncate(&name, 20))
-            .size(15)
-            .font(icons::BOLD)
-            .color(theme::text_primary()),
-        Space::new().width(Fill),
-        timestamp_text,
-    ]
+    // Name + muted indicator + timestamp row
+    let mut top_row = row![text(theme::truncate(&name, 20))
+        .size(15)
+        .font(icons::BOLD)
+        .color(theme::text_primary())]
+    .spacing(6)
     .align_y(Alignment::Center);
+    if chat.is_muted {
+        top_row = top_row.push(
+            text(icons::BELL_OFF)
+                .font(icons::LUCIDE_FONT)
+                .size(13)
+                .color(theme::text_faded()),
+        );
+    }
+    let top_row = top_row.push(Space::new().width(Fill)).push(timestamp_text);
 
     // Preview + optional badge
     let mut bottom_row = row![text(theme::truncate(preview, 30))
@@ -173,6 +198,57 @@ fn chat_item<'a>(
         .into()
 }
 
+/// A pending invitation with accept/decline buttons.
+fn invite_item<'a>(
+    invite: &InvitationSummary,
+    avatar_cache: &mut super::avatar::AvatarCache,
+) -> Element<'a, Message, Theme> {
+    let inviter = invite
+        .inviter_name
+        .clone()
+        .unwrap_or_else(|| short_npub(&invite.inviter_npub));
+    let title = invite.group_name.clone().unwrap_or_else(|| inviter.clone());
+    let subtitle = format!("From {inviter} \u{00b7} {} members", invite.member_count);
+
+    let avatar: Element<'a, Message, Theme> = avatar_circle(
+        Some(&inviter),
+        invite.inviter_picture_url.as_deref(),
+        48.0,
+        avatar_cache,
+    );
+
+    let buttons = row![
+        button(text("Accept").size(13).center())
+            .on_press(Message::AcceptInvite(invite.invite_id.clone()))
+            .padding([4, 12])
+            .style(theme::primary_button_style),
+        button(text("Decline").size(13).center())
+            .on_press(Message::DeclineInvite(invite.invite_id.clone()))
+            .padding([4, 12])
+            .style(theme::secondary_button_style),
+    ]
+    .spacing(8);
+
+    let details = column![
+        text(theme::truncate(&title, 24))
+            .size(15)
+            .font(icons::BOLD)
+            .color(theme::text_primary()),
+        text(theme::truncate(&subtitle, 32))
+            .size(13)
+            .color(theme::text_secondary())
+            .wrapping(text::Wrapping::None),
+        buttons,
+    ]
+    .spacing(4)
+    .width(Fill);
+
+    container(row![avatar, details].spacing(12).align_y(Alignment::Center))
+        .width(Fill)
+        .padding([10, 12])
+        .into()
+}
+
 /// Derive a display name for a chat.
 fn chat_display_name(chat: &ChatSummary) -> String {
     if chat.is_group {
@@ -9,13 +9,15 @@
 //! The palette adjusts suggestions based on the calling context:
 //! - When in a group chat, "Add member to group" appears at the top
 //! - When in any chat, "Jump to date" is available
-//! - Message content within the current conversation is searchable
+//! - Message content across all chats is searchable through the core's
+//!   local message index; loaded messages in the current conversation are
+//!   used until index results arrive
 
 use iced::widget::{
     button, column, container, mouse_area, operation, row, scrollable, text, text_input, Id, Space,
 };
 use iced::{Alignment, Element, Fill, Length, Padding, Task, Theme};
-use pika_core::{ChatMessage, ChatSummary, ChatViewState};
+use pika_core::{ChatMessage, ChatSummary, ChatViewState, MessageSearchState};
 
 use crate::theme;
 use crate::{design, icons};
@@ -41,6 +43,8 @@ pub enum PaletteAction {
     OpenThemePicker,
     /// Jump to a specific message in the current conversation.
     JumpToMessage { message_id: String },
+    /// Open a chat and jump to a message found by the message index.
+    OpenMessage { chat_id: String, message_id: String },
 }
 
 // ── Palette item ────────────────────────────────────────────────────────────
@@ -69,6 +73,8 @@ pub struct State {
     pub query: String,
     pub selected_index: usize,
     pub results: Vec<PaletteItem>,
+    /// Index hits for the current query, converted to palette items.
+    message_results: Vec<PaletteItem>,
     pub input_id: Id,
 }
 
@@ -87,12 +93,24 @@ pub enum Message {
 // ── Events (bubbled up to home screen) ──────────────────────────────────────
 
 pub enum Event {
-    OpenChat { chat_id: String },
+    OpenChat {
+        chat_id: String,
+    },
     StartNewChat,
     StartNewGroup,
     OpenMyProfile,
     OpenThemePicker,
-    JumpToMessage { message_id: String },
+    JumpToMessage {
+        message_id: String,
+    },
+    OpenMessage {
+        chat_id: String,
+        message_id: String,
+    },
+    /// The query changed; the parent should ask the core to search messages.
+    SearchMessages {
+        query: String,
+    },
     Dismissed,
 }
 
@@ -110,6 +128,7 @@ impl State {
             query: String::new(),
             selected_index: 0,
             results,
+            message_results: Vec::new(),
             input_id,
         }
     }
@@ -127,14 +146,14 @@ impl State {
         match message {
             Message::QueryChanged(value) => {
                 self.query = value;
-                self.results = if self.query.trim().is_empty() {
-                    build_default_results(chat_list, context.as_ref())
-                } else {
-                    build_filtered_results(chat_list, &self.query, context.as_ref())
-                };
+                self.message_results.clear();
+                self.rebuild_results(chat_list, context.as_ref());
                 // Reset selection but clamp to results length.
                 self.selected_index = 0;
-                (None, scroll_to_index(0, self.results.len()))
+                let event = (!self.query.trim().is_empty()).then(|| Event::SearchMessages {
+                    query: self.query.clone(),
+                });
+                (event, scroll_to_index(0, self.results.len()))
             }
             Message::ArrowUp => {
                 if !self.results.is_empty() {
@@ -176,6 +195,47 @@ impl State {
         }
     }
 
+    /// Pick up message index results from core state. Results for an older
+    /// query (the user kept typing) are ignored.
+    pub fn sync_message_search(
+        &mut self,
+        search: Option<&MessageSearchState>,
+        chat_list: &[ChatSummary],
+        context: Option<ChatContext<'_>>,
+    ) {
+        let Some(search) = search.filter(|s| s.chat_id.is_none() && s.query == self.query) else {
+            return;
+        };
+        self.message_results = search
+            .results
+            .iter()
+            .map(|hit| {
+                let sender = hit.sender_name.as_deref().unwrap_or("Someone");
+                PaletteItem {
+                    title: format!("{sender} in {}", hit.chat_name),
+                    subtitle: hit.snippet.clone(),
+                    shortcut: None,
+                    action: PaletteAction::OpenMessage {
+                        chat_id: hit.chat_id.clone(),
+                        message_id: hit.message_id.clone(),
+                    },
+                }
+            })
+            .collect();
+        self.rebuild_results(chat_list, context.as_ref());
+        self.selected_index = self
+            .selected_index
+            .min(self.results.len().saturating_sub(1));
+    }
+
+    fn rebuild_results(&mut self, chat_list: &[ChatSummary], context: Option<&ChatContext<'_>>) {
+        self.results = if self.query.trim().is_empty() {
+            build_default_results(chat_list, context)
+        } else {
+            build_filtered_results(chat_list, &self.query, context, &self.message_results)
+        };
+    }
+
     /// Render the command palette overlay.
     pub fn view(&self) -> Element<'_, Message, Theme> {
         // ── Backdrop (click to dismiss) ─────────────────────────────
@@ -366,6 +426,7 @@ fn build_filtered_results(
     chat_list: &[ChatSummary],
     query: &str,
     context: Option<&ChatContext<'_>>,
+    message_results: &[PaletteItem],
 ) -> Vec<PaletteItem> {
     let q = query.to_lowercase();
     let mut items = Vec::new();
@@ -445,8 +506,11 @@ fn build_filtered_results(
         }
     }
 
-    // 4. Message search within current conversation (Phase 12).
-    if let Some(ctx) = context {
+    // 4. Message search: index hits across all chats, or the loaded messages
+    //    of the current conversation until those arrive.
+    if !message_results.is_empty() {
+        items.extend(message_results.iter().cloned());
+    } else if let Some(ctx) = context {
         let message_matches = search_messages(&ctx.chat.messages, &q);
         for (msg, snippet) in message_matches {
             let chat_name = ctx.chat.group_name.as_deref().unwrap_or("this chat");
@@ -600,6 +664,13 @@ fn event_for_action(action: &PaletteAction) -> Event {
         PaletteAction::JumpToMessage { message_id } => Event::JumpToMessage {
             message_id: message_id.clone(),
         },
+        PaletteAction::OpenMessage {
+            chat_id,
+            message_id,
+        } => Event::OpenMessage {
+            chat_id: chat_id.clone(),
+            message_id: message_id.clone(),
+        },
     }
 }
 
@@ -3,7 +3,7 @@ use iced::widget::{
     button, column, container, operation, row, scrollable, text, text_input, Space, Stack,
 };
 use iced::{Alignment, Element, Fill, Task, Theme};
-use pika_core::{CallState, CallStatus, ChatMessage, ChatViewState};
+use pika_core::{CallState, CallStatus, ChatMessage, ChatNotificationMode, ChatViewState};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -20,6 +20,8 @@ const CONVERSATION_SCROLL_ID: &str = "conversation-scroll";
 pub struct State {
     pub message_input: String,
     pub reply_to_message_id: Option<String>,
+    /// Own message currently being edited in the composer, if any.
+    pub editing_message_id: Option<String>,
     pub emoji_picker_message_id: Option<String>,
     pub hovered_message_id: Option<String>,
     /// True while a file is being dragged over the conversation area.
@@ -35,6 +37,12 @@ pub enum Message {
     SendMessage,
     SetReplyTarget(String),
     CancelReplyTarget,
+    StartEditMessage {
+        message_id: String,
+        content: String,
+    },
+    CancelEditMessage,
+    DeleteMessage(String),
     JumpToMessage(String),
     ReactToMessage {
         message_id: String,
@@ -60,6 +68,8 @@ pub enum Message {
     StartVideoCall,
     OpenCallScreen,
     OpenPeerProfile(String),
+    SetDisappearingTimer(Option<u64>),
+    SetNotificationMode(ChatNotificationMode),
 }
 
 // ── Events ──────────────────────────────────────────────────────────────────
@@ -87,6 +97,10 @@ pub enum Event {
     JumpToMessage(String),
     /// A reaction was sent
     ReactToMessage { message_id: String, emoji: String },
+    /// The user saved an edit to one of their own messages
+    EditMessage { message_id: String, content: String },
+    /// The user deleted one of their own messages
+    DeleteMessage { message_id: String },
     /// The conversation header's group-info button was pressed
     ShowGroupInfo,
     /// The conversation header's call button was pressed
@@ -97,6 +111,10 @@ pub enum Event {
     OpenCallScreen,
     /// The user clicked a peer's name/avatar to view their profile
     OpenPeerProfile(String),
+    /// The header's disappearing-messages button picked a new timer (`None` = off)
+    SetDisappearingTimer(Option<u64>),
+    /// The header's notifications button picked a new mode
+    SetNotificationMode(ChatNotificationMode),
 }
 
 // ── Implementation ──────────────────────────────────────────────────────────
@@ -106,6 +124,7 @@ impl State {
         Self {
             message_input: String::new(),
             reply_to_message_id: None,
+            editing_message_id: None,
             emoji_picker_message_id: None,
             hovered_message_id: None,
             file_hover: false,
@@ -135,6 +154,16 @@ impl State {
                 if content.is_empty() {
                     return (None, None);
                 }
+                if let Some(message_id) = self.editing_message_id.take() {
+                    self.message_input.clear();
+                    return (
+                        Some(Event::EditMessage {
+                            message_id,
+                            content,
+                        }),
+                        None,
+                    );
+                }
                 let reply_to = self.reply_to_message_id.take();
                 self.message_input.clear();
                 self.emoji_picker_message_id = None;
@@ -154,6 +183,28 @@ impl State {
                 self.reply_to_message_id = None;
                 (None, None)
             }
+            Message::StartEditMessage {
+                message_id,
+                content,
+            } => {
+                self.reply_to_message_id = None;
+                self.emoji_picker_message_id = None;
+                self.editing_message_id = Some(message_id);
+                self.message_input = content;
+                (None, None)
+            }
+            Message::CancelEditMessage => {
+                self.editing_message_id = None;
+                self.message_input.clear();
+                (None, None)
+            }
+            Message::DeleteMessage(message_id) => {
+                if self.editing_message_id.as_deref() == Some(&message_id) {
+                    self.editing_message_id = None;
+                    self.message_input.clear();
+                }
+                (Some(Event::DeleteMessage { message_id }), None)
+            }
             Message::JumpToMessage(message_id) => (Some(Event::JumpToMessage(message_id)), None),
             Message::ReactToMessage { message_id, emoji } => {
                 self.emoji_picker_message_id = None;
@@ -227,10 +278,13 @@ impl State {
             Message::StartVideoCall => (Some(Event::StartVideoCall), None),
             Message::OpenCallScreen => (Some(Event::OpenCallScreen), None),
             Message::OpenPeerProfile(pubkey) => (Some(Event::OpenPeerProfile(pubkey)), None),
+            Message::SetDisappearingTimer(secs) => (Some(Event::SetDisappearingTimer(secs)), None),
+            Message::SetNotificationMode(mode) => (Some(Event::SetNotificationMode(mode)), None),
         }
     }
 
-    /// Clean up reply target if the referenced message disappeared.
+    /// Clean up reply and edit targets if the referenced message disappeared
+    /// (or, for edits, was deleted).
     pub fn clean_reply_target(&mut self, chat: Option<&ChatViewState>) {
         if let Some(reply_id) = self.reply_to_message_id.as_ref() {
             let still_present = chat
@@ -240,6 +294,19 @@ impl State {
                 self.reply_to_message_id = None;
             }
         }
+        if let Some(edit_id) = self.editing_message_id.as_ref() {
+            let still_editable = chat
+                .map(|c| {
+                    c.messages
+                        .iter()
+                        .any(|msg| &msg.id == edit_id && !msg.deleted)
+                })
+                .unwrap_or(false);
+            if !still_editable {
+                self.editing_message_id = None;
+                self.message_input.clear();
+            }
+        }
     }
 
     /// Center pane: conversation header + message list + input bar.
@@ -265,6 +332,17 @@ impl State {
         if !subtitle.is_empty() {
             header_info = header_info.push(text(subtitle).size(13).color(theme::text_secondary()));
         }
+        if let Some(secs) = chat.disappearing_timer_secs {
+            header_info = header_info.push(
+                text(format!("Disappearing · {}", disappearing_timer_label(secs)))
+                    .size(12)
+                    .color(theme::text_secondary()),
+            );
+        }
+        let now = unix_now();
+        if let Some(label) = notification_mode_label(chat.notification_mode, now) {
+            header_info = header_info.push(text(label).size(12).color(theme::text_secondary()));
+        }
 
         let picture_url = if chat.is_group {
             None
@@ -272,12 +350,12 @@ impl State {
             chat.members.first().and_then(|m| m.picture_url.as_deref())
         };
 
-        // Call buttons for 1:1 chats
+        // Call buttons (1:1 and group calls)
         #[allow(clippy::type_complexity)]
         let (call_button, video_call_button): (
             Option<Element<'a, Message, Theme>>,
             Option<Element<'a, Message, Theme>>,
-        ) = if !chat.is_group {
+        ) = {
             let has_live_call_for_chat = active_call
                 .as_ref()
                 .map(|c| c.chat_id == chat.chat_id && !matches!(c.status, CallStatus::Ended { .. }))
@@ -332,8 +410,6 @@ impl State {
             };
 
             (audio_btn, video_btn)
-        } else {
-            (None, None)
         };
 
         // Profile-clickable area (avatar + name) — hover only on this part
@@ -361,9 +437,57 @@ impl State {
             container(profile_content).padding([8, 12]).into()
         };
 
-        // Full header row: [profile area] [spacer] [call buttons]
-        let mut header_row =
-            row![profile_area, Space::new().width(Fill)].align_y(Alignment::Center);
+        // Clicking cycles through the preset timers, wrapping back to off.
+        let timer_button: Element<'a, Message, Theme> = button(
+            text(icons::CLOCK)
+                .font(icons::LUCIDE_FONT)
+                .size(20)
+                .color(if chat.disappearing_timer_secs.is_some() {
+                    theme::accent_blue()
+                } else {
+                    theme::text_primary()
+                })
+                .center(),
+        )
+        .on_press(Message::SetDisappearingTimer(next_disappearing_timer(
+            chat.disappearing_timer_secs,
+        )))
+        .padding([8, 10])
+        .style(theme::icon_button_style(false))
+        .into();
+
+        // Cycles all → mentions only → snoozed → muted → all.
+        let notifications_button: Element<'a, Message, Theme> = button(
+            text(if chat.notification_mode == ChatNotificationMode::All {
+                icons::BELL
+            } else {
+                icons::BELL_OFF
+            })
+            .font(icons::LUCIDE_FONT)
+            .size(20)
+            .color(if chat.notification_mode == ChatNotificationMode::All {
+                theme::text_primary()
+            } else {
+                theme::accent_blue()
+            })
+            .center(),
+        )
+        .on_press(Message::SetNotificationMode(next_notification_mode(
+            chat.notification_mode,
+            now,
+        )))
+        .padding([8, 10])
+        .style(theme::icon_button_style(false))
+        .into();
+
+        // Full header row: [profile area] [spacer] [notifications] [timer] [call buttons]
+        let mut header_row = row![
+            profile_area,
+            Space::new().width(Fill),
+            notifications_button,
+            timer_button
+        ]
+        .align_y(Alignment::Center);
 
         if let Some(btn) = video_call_button {
             header_row = header_row.push(btn);
@@ -524,6 +648,25 @@ impl State {
             .padding([10, 16]);
 
         let mut input_column = column![].spacing(6);
+        if self.editing_message_id.is_some() {
+            let edit_row = row![
+                text(icons::PEN)
+                    .font(icons::LUCIDE_FONT)
+                    .size(14)
+                    .color(theme::text_secondary()),
+                text("Editing message")
+                    .size(13)
+                    .color(theme::text_secondary())
+                    .width(Fill),
+                button(text("Cancel").size(12))
+                    .on_press(Message::CancelEditMessage)
+                    .style(theme::secondary_button_style),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .padding([6, 16]);
+            input_column = input_column.push(edit_row);
+        }
         let replying_to = self
             .reply_to_message_id
             .as_ref()
@@ -690,6 +833,73 @@ fn read_and_send_first_file(paths: &[PathBuf]) -> Option<Event> {
     })
 }
 
+const DISAPPEARING_TIMER_PRESETS: [u64; 4] = [300, 3_600, 86_400, 604_800];
+
+fn next_disappearing_timer(current: Option<u64>) -> Option<u64> {
+    match current {
+        None => Some(DISAPPEARING_TIMER_PRESETS[0]),
+        Some(secs) => DISAPPEARING_TIMER_PRESETS
+            .iter()
+            .copied()
+            .find(|preset| *preset > secs),
+    }
+}
+
+fn disappearing_timer_label(secs: u64) -> String {
+    let (count, unit) = [
+        (604_800, "week"),
+        (86_400, "day"),
+        (3_600, "hour"),
+        (60, "minute"),
+    ]
+    .into_iter()
+    .find(|(size, _)| secs >= *size && secs % size == 0)
+    .map(|(size, unit)| (secs / size, unit))
+    .unwrap_or((secs, "second"));
+    if count == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{count} {unit}s")
+    }
+}
+
+const NOTIFICATION_SNOOZE_SECS: i64 = 8 * 3_600;
+
+fn next_notification_mode(current: ChatNotificationMode, now: i64) -> ChatNotificationMode {
+    match current {
+        ChatNotificationMode::All => ChatNotificationMode::MentionsOnly,
+        ChatNotificationMode::MentionsOnly => ChatNotificationMode::MutedUntil {
+            until: now + NOTIFICATION_SNOOZE_SECS,
+        },
+        ChatNotificationMode::MutedUntil { .. } => ChatNotificationMode::Muted,
+        ChatNotificationMode::Muted => ChatNotificationMode::All,
+    }
+}
+
+fn notification_mode_label(mode: ChatNotificationMode, now: i64) -> Option<String> {
+    match mode {
+        ChatNotificationMode::All => None,
+        ChatNotificationMode::MentionsOnly => Some("Notifications · Mentions only".to_string()),
+        ChatNotificationMode::MutedUntil { until } => {
+            let left = (until - now).max(60);
+            let left = if left >= 3_600 {
+                format!("{}h", left / 3_600)
+            } else {
+                format!("{}m", left / 60)
+            };
+            Some(format!("Muted · {left} left"))
+        }
+        ChatNotificationMode::Muted => Some("Muted".to_string()),
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
 fn chat_title(chat: &ChatViewState) -> String {
     if let Some(name) = &chat.group_name {
         if !name.trim().is_empty() {
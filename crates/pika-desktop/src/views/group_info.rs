@@ -1,17 +1,27 @@
 use iced::widget::{button, column, container, row, rule, scrollable, text, text_input, Space};
-use iced::{Alignment, Element, Fill, Theme};
-use pika_core::ChatViewState;
+use iced::{Alignment, Element, Fill, Length, Theme};
+use pika_core::{ChatViewState, GroupInviteLinkInfo, GroupJoinRequest};
 
 use crate::icons;
 use crate::theme;
 use crate::views::avatar::avatar_circle;
 
+/// Desktop builds don't register a URL scheme; links open the release app.
+const INVITE_LINK_SCHEME: &str = "pika";
+
+/// The shareable URL for one of our invite links.
+pub fn invite_link_url(link: &GroupInviteLinkInfo) -> String {
+    pika_core::group_invite_link_url(INVITE_LINK_SCHEME, &link.admin_npub, &link.token)
+        .unwrap_or_default()
+}
+
 // ── State ───────────────────────────────────────────────────────────────────
 
 #[derive(Debug)]
 pub struct State {
     pub name_draft: String,
     pub npub_input: String,
+    pub invite_requires_approval: bool,
 }
 
 // ── Messages ────────────────────────────────────────────────────────────────
@@ -23,6 +33,13 @@ pub enum Message {
     NpubChanged(String),
     AddMember,
     RemoveMember(String),
+    SetAdmin(String, bool),
+    ToggleInviteApproval,
+    CreateInviteLink { multi_use: bool },
+    CopyInviteLink(String),
+    RevokeInviteLink(String),
+    ApproveJoinRequest(String),
+    DeclineJoinRequest(String),
     LeaveGroup,
     Close,
     OpenPeerProfile(String),
@@ -32,12 +49,40 @@ pub enum Message {
 // ── Events ──────────────────────────────────────────────────────────────────
 
 pub enum Event {
-    RenameGroup { name: String },
-    AddMember { npub: String },
-    RemoveMember { pubkey: String },
+    RenameGroup {
+        name: String,
+    },
+    AddMember {
+        npub: String,
+    },
+    RemoveMember {
+        pubkey: String,
+    },
+    SetAdmin {
+        pubkey: String,
+        admin: bool,
+    },
+    CreateInviteLink {
+        multi_use: bool,
+        requires_approval: bool,
+    },
+    CopyInviteLink {
+        url: String,
+    },
+    RevokeInviteLink {
+        token: String,
+    },
+    ApproveJoinRequest {
+        pubkey: String,
+    },
+    DeclineJoinRequest {
+        pubkey: String,
+    },
     LeaveGroup,
     Close,
-    OpenPeerProfile { pubkey: String },
+    OpenPeerProfile {
+        pubkey: String,
+    },
     EditGroupProfile,
 }
 
@@ -48,6 +93,7 @@ impl State {
         Self {
             name_draft: group_name.unwrap_or_default().to_string(),
             npub_input: String::new(),
+            invite_requires_approval: false,
         }
     }
 
@@ -73,6 +119,19 @@ impl State {
                 Some(Event::AddMember { npub })
             }
             Message::RemoveMember(pubkey) => Some(Event::RemoveMember { pubkey }),
+            Message::SetAdmin(pubkey, admin) => Some(Event::SetAdmin { pubkey, admin }),
+            Message::ToggleInviteApproval => {
+                self.invite_requires_approval = !self.invite_requires_approval;
+                None
+            }
+            Message::CreateInviteLink { multi_use } => Some(Event::CreateInviteLink {
+                multi_use,
+                requires_approval: self.invite_requires_approval,
+            }),
+            Message::CopyInviteLink(url) => Some(Event::CopyInviteLink { url }),
+            Message::RevokeInviteLink(token) => Some(Event::RevokeInviteLink { token }),
+            Message::ApproveJoinRequest(pubkey) => Some(Event::ApproveJoinRequest { pubkey }),
+            Message::DeclineJoinRequest(pubkey) => Some(Event::DeclineJoinRequest { pubkey }),
             Message::LeaveGroup => Some(Event::LeaveGroup),
             Message::Close => Some(Event::Close),
             Message::OpenPeerProfile(pubkey) => Some(Event::OpenPeerProfile { pubkey }),
@@ -110,26 +169,45 @@ impl State {
             .padding([12, 16]),
         );
 
-        // ── Group name edit ──────────────────────────────────────────
-        let name_row = row![
-            text_input("Group name\u{2026}", &self.name_draft)
-                .on_input(Message::NameChanged)
-                .on_submit(Message::RenameGroup)
-                .padding(10)
-                .width(Fill)
-                .style(theme::dark_input_style),
-            button(text("Rename").size(14).font(icons::MEDIUM).center())
-                .on_press(Message::RenameGroup)
-                .padding([10, 20])
-                .style(theme::primary_button_style),
-        ]
-        .spacing(8)
-        .align_y(Alignment::Center);
-
-        content = content.push(container(name_row).padding([8, 24]));
+        // ── Group name (editable by admins) ──────────────────────────
+        if chat.is_admin {
+            let name_row = row![
+                text_input("Group name\u{2026}", &self.name_draft)
+                    .on_input(Message::NameChanged)
+                    .on_submit(Message::RenameGroup)
+                    .padding(10)
+                    .width(Fill)
+                    .style(theme::dark_input_style),
+                button(text("Rename").size(14).font(icons::MEDIUM).center())
+                    .on_press(Message::RenameGroup)
+                    .padding([10, 20])
+                    .style(theme::primary_button_style),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            content = content.push(container(name_row).padding([8, 24]));
+        } else {
+            let name = chat.group_name.as_deref().unwrap_or("Group");
+            content = content.push(
+                container(
+                    text(name)
+                        .size(16)
+                        .font(icons::BOLD)
+                        .color(theme::text_primary()),
+                )
+                .padding([8, 24]),
+            );
+        }
 
         content = content.push(container(rule::horizontal(1)).padding([8, 24]));
 
+        // ── Invite links and join requests (admins only) ─────────────
+        if chat.is_admin {
+            content = content.push(self.invite_links_section(chat, avatar_cache));
+            content = content.push(container(rule::horizontal(1)).padding([8, 24]));
+        }
+
         // ── Members section ──────────────────────────────────────────
         content = content.push(
             container(
@@ -210,8 +288,181 @@ impl State {
     }
 }
 
+impl State {
+    fn invite_links_section<'a>(
+        &'a self,
+        chat: &'a ChatViewState,
+        avatar_cache: &mut super::avatar::AvatarCache,
+    ) -> Element<'a, Message, Theme> {
+        let approval_indicator = container(
+            text(icons::CHECK)
+                .font(icons::LUCIDE_FONT)
+                .size(12)
+                .center(),
+        )
+        .width(Length::Fixed(20.0))
+        .height(Length::Fixed(20.0))
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center)
+        .style(theme::checkbox_style(self.invite_requires_approval));
+
+        let controls = row![
+            button(
+                row![
+                    approval_indicator,
+                    text("Require approval")
+                        .size(13)
+                        .color(theme::text_secondary()),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            )
+            .on_press(Message::ToggleInviteApproval)
+            .padding([6, 8])
+            .style(theme::icon_button_style(false)),
+            Space::new().width(Fill),
+            button(text("One-time link").size(13).font(icons::MEDIUM).center())
+                .on_press(Message::CreateInviteLink { multi_use: false })
+                .padding([6, 12])
+                .style(theme::icon_button_style(false)),
+            button(text("New link").size(13).font(icons::MEDIUM).center())
+                .on_press(Message::CreateInviteLink { multi_use: true })
+                .padding([6, 12])
+                .style(theme::primary_button_style),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let mut section = column![
+            text("Invite links")
+                .size(14)
+                .font(icons::BOLD)
+                .color(theme::text_primary()),
+            controls,
+        ]
+        .spacing(8);
+
+        for link in &chat.invite_links {
+            section = section.push(invite_link_row(link));
+        }
+
+        if !chat.join_requests.is_empty() {
+            section = section.push(
+                text(format!("Join requests ({})", chat.join_requests.len()))
+                    .size(14)
+                    .font(icons::BOLD)
+                    .color(theme::text_primary()),
+            );
+            for request in &chat.join_requests {
+                section = section.push(join_request_row(request, avatar_cache));
+            }
+        }
+
+        container(section).padding([8, 24]).into()
+    }
+}
+
 // ── Private helpers ─────────────────────────────────────────────────────────
 
+/// Small borderless icon button used on list rows.
+fn row_icon_button<'a>(
+    icon_cp: &'a str,
+    color: iced::Color,
+    on_press: Message,
+) -> Element<'a, Message, Theme> {
+    button(text(icon_cp).font(icons::LUCIDE_FONT).size(14).color(color))
+        .on_press(on_press)
+        .padding([4, 6])
+        .style(move |_: &Theme, status: button::Status| {
+            let bg = match status {
+                button::Status::Hovered => theme::hover_bg(),
+                _ => iced::Color::TRANSPARENT,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(bg)),
+                text_color: color,
+                border: iced::border::rounded(6),
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+fn invite_link_row(link: &GroupInviteLinkInfo) -> Element<'_, Message, Theme> {
+    let url = invite_link_url(link);
+    let kind = match (link.multi_use, link.requires_approval) {
+        (true, false) => format!("Used {} times", link.use_count),
+        (true, true) => format!("Used {} times \u{00b7} approval", link.use_count),
+        (false, false) => "One-time".to_string(),
+        (false, true) => "One-time \u{00b7} approval".to_string(),
+    };
+    row![
+        text(icons::LINK)
+            .font(icons::LUCIDE_FONT)
+            .size(16)
+            .color(theme::text_secondary()),
+        column![
+            text(theme::truncated_npub_long(&url))
+                .size(12)
+                .font(icons::MONO)
+                .color(theme::text_primary()),
+            text(kind).size(11).color(theme::text_faded()),
+        ]
+        .spacing(2)
+        .width(Fill),
+        row_icon_button(
+            icons::COPY,
+            theme::text_secondary(),
+            Message::CopyInviteLink(url),
+        ),
+        row_icon_button(
+            icons::X,
+            theme::danger(),
+            Message::RevokeInviteLink(link.token.clone()),
+        ),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn join_request_row<'a>(
+    request: &'a GroupJoinRequest,
+    avatar_cache: &mut super::avatar::AvatarCache,
+) -> Element<'a, Message, Theme> {
+    let display_name = request
+        .name
+        .clone()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| theme::truncated_npub(&request.npub));
+    let avatar: Element<'_, Message, Theme> = avatar_circle(
+        Some(&display_name),
+        request.picture_url.as_deref(),
+        28.0,
+        avatar_cache,
+    );
+    row![
+        avatar,
+        text(display_name)
+            .size(14)
+            .color(theme::text_primary())
+            .width(Fill),
+        row_icon_button(
+            icons::CHECK,
+            theme::accent_blue(),
+            Message::ApproveJoinRequest(request.pubkey.clone()),
+        ),
+        row_icon_button(
+            icons::X,
+            theme::danger(),
+            Message::DeclineJoinRequest(request.pubkey.clone()),
+        ),
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .into()
+}
+
 /// Signal-style full-width action row: [icon] [label] — hover shows bg.
 fn danger_action_row<'a>(
     icon_cp: &'a str,
@@ -317,6 +568,34 @@ fn member_row<'a>(
     }
 
     if is_admin {
+        let (role_icon, promote) = if member.is_admin {
+            (icons::SHIELD_OFF, false)
+        } else {
+            (icons::SHIELD, true)
+        };
+        row_content = row_content.push(
+            button(
+                text(role_icon)
+                    .font(icons::LUCIDE_FONT)
+                    .size(14)
+                    .color(theme::text_secondary()),
+            )
+            .on_press(Message::SetAdmin(member.pubkey.clone(), promote))
+            .padding([4, 6])
+            .style(|_: &Theme, status: button::Status| {
+                let bg = match status {
+                    button::Status::Hovered => theme::hover_bg(),
+                    _ => iced::Color::TRANSPARENT,
+                };
+                button::Style {
+                    background: Some(iced::Background::Color(bg)),
+                    text_color: theme::text_secondary(),
+                    border: iced::border::rounded(6),
+                    ..Default::default()
+                }
+            }),
+        );
+
         let pubkey = member.pubkey.clone();
         row_content = row_content.push(
             button(
@@ -1,6 +1,8 @@
 use iced::widget::{button, column, container, image, mouse_area, row, text, Space};
 use iced::{border, Alignment, Background, Color, Element, Fill, Font, Length, Theme};
-use pika_core::{ChatMediaAttachment, ChatMediaKind, ChatMessage, MessageDeliveryState};
+use pika_core::{
+    ChatMediaAttachment, ChatMediaKind, ChatMessage, MessageDeliveryState, MessageReader,
+};
 
 use super::avatar::{avatar_circle, AvatarCache};
 use super::conversation::Message;
@@ -17,6 +19,9 @@ const EMOJI_FONT: Font = Font::with_name("Noto Color Emoji");
 /// 2 × 32px buttons + 4px spacing = 68px.
 const ACTION_ICONS_WIDTH: f32 = 68.0;
 
+/// Own messages also get edit + delete buttons: 4 × 32px + 3 × 4px = 140px.
+const OWN_ACTION_ICONS_WIDTH: f32 = 140.0;
+
 /// Common emoji choices for the quick picker.
 const EMOJI_CHOICES: &[&str] = &[
     "\u{2764}\u{FE0F}", // ❤️
@@ -52,8 +57,9 @@ pub fn message_bubble<'a>(
     let timestamp = theme::relative_time(msg.timestamp);
     let msg_id = msg.id.clone();
 
-    // Show action icons when hovered or picker is open
-    let show_icons = hovered || emoji_picker_open;
+    // Show action icons when hovered or picker is open. Tombstones of deleted
+    // messages have nothing left to reply to, react to, or edit.
+    let show_icons = (hovered || emoji_picker_open) && !msg.deleted;
 
     // ── Reaction chips below the bubble ─────────────────────────
     let chips_row = reaction_chips_row(msg, &msg_id);
@@ -119,11 +125,19 @@ pub fn message_bubble<'a>(
         for attachment in &msg.media {
             bubble_content = bubble_content.push(media_attachment_view(attachment, &msg_id, true));
         }
-        if !msg.display_content.is_empty() {
+        if msg.deleted {
+            bubble_content = bubble_content.push(tombstone_text(Color::WHITE.scale_alpha(0.7)));
+        } else if !msg.display_content.is_empty() {
             bubble_content =
                 bubble_content.push(text(&msg.display_content).size(15).color(Color::WHITE));
         }
-        bubble_content = bubble_content.push(timestamp_row(timestamp, &msg.delivery, true));
+        bubble_content = bubble_content.push(timestamp_row(
+            timestamp,
+            msg.edited_at.is_some(),
+            &msg.delivery,
+            &msg.seen_by,
+            true,
+        ));
         let bubble = container(bubble_content)
             .padding([10, 14])
             .max_width(500)
@@ -136,9 +150,9 @@ pub fn message_bubble<'a>(
 
         // Always reserve space for action icons to prevent layout jumps on hover.
         if show_icons {
-            bubble_row = bubble_row.push(message_action_icons(&msg_id, emoji_picker_open));
+            bubble_row = bubble_row.push(message_action_icons(msg, emoji_picker_open));
         } else {
-            bubble_row = bubble_row.push(Space::new().width(ACTION_ICONS_WIDTH));
+            bubble_row = bubble_row.push(Space::new().width(OWN_ACTION_ICONS_WIDTH));
         }
         bubble_row = bubble_row.push(bubble);
 
@@ -185,14 +199,22 @@ pub fn message_bubble<'a>(
         for attachment in &msg.media {
             bubble_content = bubble_content.push(media_attachment_view(attachment, &msg_id, false));
         }
-        if !msg.display_content.is_empty() {
+        if msg.deleted {
+            bubble_content = bubble_content.push(tombstone_text(theme::text_faded()));
+        } else if !msg.display_content.is_empty() {
             bubble_content = bubble_content.push(
                 text(&msg.display_content)
                     .size(15)
                     .color(theme::text_primary()),
             );
         }
-        bubble_content = bubble_content.push(timestamp_row(timestamp, &msg.delivery, false));
+        bubble_content = bubble_content.push(timestamp_row(
+            timestamp,
+            msg.edited_at.is_some(),
+            &msg.delivery,
+            &msg.seen_by,
+            false,
+        ));
 
         let bubble = container(bubble_content)
             .padding([10, 14])
@@ -225,7 +247,7 @@ pub fn message_bubble<'a>(
 
         // Always reserve space for action icons to prevent layout jumps on hover.
         if show_icons {
-            bubble_row = bubble_row.push(message_action_icons(&msg_id, emoji_picker_open));
+            bubble_row = bubble_row.push(message_action_icons(msg, emoji_picker_open));
         } else {
             bubble_row = bubble_row.push(Space::new().width(ACTION_ICONS_WIDTH));
         }
@@ -374,10 +396,11 @@ fn media_attachment_view<'a>(
     .into()
 }
 
-/// Small action icons beside the bubble (Signal-style).
-fn message_action_icons<'a>(msg_id: &str, picker_open: bool) -> Element<'a, Message, Theme> {
-    let mid = msg_id.to_string();
-    let reply_mid = msg_id.to_string();
+/// Small action icons beside the bubble (Signal-style). Own messages also get
+/// edit and delete buttons.
+fn message_action_icons<'a>(msg: &ChatMessage, picker_open: bool) -> Element<'a, Message, Theme> {
+    let mid = msg.id.clone();
+    let reply_mid = msg.id.clone();
 
     // React icon: ✕ when picker is open, smiley-plus otherwise
     let react_icon = if picker_open {
@@ -386,34 +409,37 @@ fn message_action_icons<'a>(msg_id: &str, picker_open: bool) -> Element<'a, Mess
         icons::SMILE_PLUS
     };
 
-    let reply_btn = button(
-        text(icons::REPLY)
-            .font(icons::LUCIDE_FONT)
-            .size(18)
-            .center(),
-    )
-    .padding([6, 6])
-    .width(32.0)
-    .height(32.0)
-    .on_press(Message::SetReplyTarget(reply_mid))
-    .style(|_theme: &Theme, status: button::Status| {
-        let (bg, text_color) = match status {
-            button::Status::Hovered => (theme::hover_bg(), theme::text_primary()),
-            _ => (Color::TRANSPARENT, theme::text_faded()),
-        };
-        button::Style {
-            background: Some(Background::Color(bg)),
-            text_color,
-            border: border::rounded(8),
-            ..Default::default()
-        }
-    });
+    let mut icons_row = row![
+        action_icon_button(icons::REPLY, Message::SetReplyTarget(reply_mid)),
+        action_icon_button(react_icon, Message::ToggleEmojiPicker(mid)),
+    ]
+    .spacing(4)
+    .align_y(iced::Alignment::Center);
+
+    if msg.is_mine {
+        icons_row = icons_row
+            .push(action_icon_button(
+                icons::PEN,
+                Message::StartEditMessage {
+                    message_id: msg.id.clone(),
+                    content: msg.content.clone(),
+                },
+            ))
+            .push(action_icon_button(
+                icons::TRASH,
+                Message::DeleteMessage(msg.id.clone()),
+            ));
+    }
+
+    icons_row.into()
+}
 
-    let react_btn = button(text(react_icon).font(icons::LUCIDE_FONT).size(18).center())
+fn action_icon_button<'a>(icon: &'a str, on_press: Message) -> Element<'a, Message, Theme> {
+    button(text(icon).font(icons::LUCIDE_FONT).size(18).center())
         .padding([6, 6])
         .width(32.0)
         .height(32.0)
-        .on_press(Message::ToggleEmojiPicker(mid))
+        .on_press(on_press)
         .style(|_theme: &Theme, status: button::Status| {
             let (bg, text_color) = match status {
                 button::Status::Hovered => (theme::hover_bg(), theme::text_primary()),
@@ -425,11 +451,19 @@ fn message_action_icons<'a>(msg_id: &str, picker_open: bool) -> Element<'a, Mess
                 border: border::rounded(8),
                 ..Default::default()
             }
-        });
+        })
+        .into()
+}
 
-    row![reply_btn, react_btn]
-        .spacing(4)
-        .align_y(iced::Alignment::Center)
+/// Placeholder body for a message its author deleted.
+fn tombstone_text<'a>(color: Color) -> Element<'a, Message, Theme> {
+    text("This message was deleted")
+        .size(14)
+        .font(Font {
+            style: iced::font::Style::Italic,
+            ..Font::DEFAULT
+        })
+        .color(color)
         .into()
 }
 
@@ -513,9 +547,13 @@ fn emoji_picker_bar<'a>(msg_id: &str) -> Element<'a, Message, Theme> {
 
 /// Timestamp + delivery state row for a message bubble.
 /// Sent messages get a Lucide checkmark icon; received just show the timestamp.
+/// Edited messages are marked "edited" after the timestamp, and sent messages
+/// with read receipts list who has seen them.
 fn timestamp_row<'a>(
     timestamp: String,
+    edited: bool,
     delivery: &MessageDeliveryState,
+    seen_by: &[MessageReader],
     is_mine: bool,
 ) -> Element<'a, Message, Theme> {
     let text_color = if is_mine {
@@ -523,6 +561,11 @@ fn timestamp_row<'a>(
     } else {
         theme::text_faded()
     };
+    let timestamp = if edited {
+        format!("{timestamp} \u{00B7} edited")
+    } else {
+        timestamp
+    };
 
     if !is_mine {
         return text(timestamp).size(11).color(text_color).into();
@@ -534,7 +577,7 @@ fn timestamp_row<'a>(
         MessageDeliveryState::Failed { .. } => (icons::X, theme::danger()),
     };
 
-    row![
+    let mut status = row![
         text(timestamp).size(11).color(text_color),
         text(icon_cp)
             .font(icons::LUCIDE_FONT)
@@ -542,8 +585,27 @@ fn timestamp_row<'a>(
             .color(icon_color),
     ]
     .spacing(3)
-    .align_y(Alignment::Center)
-    .into()
+    .align_y(Alignment::Center);
+    if matches!(delivery, MessageDeliveryState::Sent) {
+        if let Some(seen) = seen_by_label(seen_by) {
+            status = status.push(text(seen).size(11).color(text_color));
+        }
+    }
+    status.into()
+}
+
+fn seen_by_label(readers: &[MessageReader]) -> Option<String> {
+    let first = readers.first()?;
+    let Some(first_name) = first.name.as_deref() else {
+        return Some("Seen".to_string());
+    };
+    Some(match readers {
+        [_] => format!("Seen by {first_name}"),
+        [_, MessageReader {
+            name: Some(second), ..
+        }] => format!("Seen by {first_name}, {second}"),
+        _ => format!("Seen by {first_name} and {} others", readers.len() - 1),
+    })
 }
 
 fn reaction_chip_style(
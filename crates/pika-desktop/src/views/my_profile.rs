@@ -2,8 +2,8 @@ use std::path::PathBuf;
 
 use base64::Engine as _;
 use iced::widget::{button, column, container, row, rule, text, text_input, Space};
-use iced::{Alignment, Element, Fill, Task, Theme};
-use pika_core::{AppAction, MyProfileState};
+use iced::{Alignment, Element, Fill, Length, Task, Theme};
+use pika_core::{AccountSummary, AppAction, BlockedUser, MyProfileState};
 
 use crate::icons;
 use crate::theme;
@@ -22,6 +22,10 @@ pub struct State {
     upload_after_save: bool,
     /// True while the Blossom upload + kind-0 publish is in flight.
     uploading: bool,
+    /// Passphrase for exporting or restoring an account backup.
+    backup_passphrase: String,
+    /// nsec typed into the "Add account" field.
+    new_account_nsec: String,
 }
 
 #[derive(Debug, Clone)]
@@ -33,15 +37,28 @@ struct PendingImage {
 #[derive(Debug, Clone)]
 pub enum Message {
     AboutChanged(String),
+    AddAccount,
+    AddAccountNsecChanged(String),
+    AutoAcceptInvitesToggled(bool),
+    BackupPassphraseChanged(String),
+    BlockListSyncToggled(bool),
     CopyAppVersion,
     CopyNpub,
+    ExportBackup,
+    ExportBackupPathPicked(Option<PathBuf>),
+    ImportBackup,
+    ImportBackupPathPicked(Option<PathBuf>),
     LogoutClicked,
     LogoutConfirmed,
     LogoutCancelled,
     NameChanged(String),
     PickProfileImage,
     ProfileImagePicked(Vec<PathBuf>),
+    ReadReceiptsToggled(bool),
+    RemoveAccount(String),
     Save,
+    SwitchAccount(String),
+    UnblockUser(String),
 }
 
 pub enum Event {
@@ -49,6 +66,9 @@ pub enum Event {
     CopyNpub,
     CopyAppVersion,
     Logout,
+    AddAccount { nsec: String },
+    SwitchAccount { pubkey: String },
+    RemoveAccount { pubkey: String },
 }
 
 impl State {
@@ -61,6 +81,8 @@ impl State {
             pending_image: None,
             upload_after_save: false,
             uploading: false,
+            backup_passphrase: String::new(),
+            new_account_nsec: String::new(),
         }
     }
 
@@ -69,8 +91,69 @@ impl State {
             Message::AboutChanged(about) => {
                 self.about = about;
             }
+            Message::AddAccount => {
+                let nsec = std::mem::take(&mut self.new_account_nsec);
+                return (Some(Event::AddAccount { nsec }), None);
+            }
+            Message::AddAccountNsecChanged(nsec) => {
+                self.new_account_nsec = nsec;
+            }
+            Message::BackupPassphraseChanged(passphrase) => {
+                self.backup_passphrase = passphrase;
+            }
             Message::CopyAppVersion => return (Some(Event::CopyAppVersion), None),
             Message::CopyNpub => return (Some(Event::CopyNpub), None),
+            Message::ExportBackup => {
+                let task = Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Save account backup")
+                            .set_file_name("pika-backup.pikabackup")
+                            .save_file()
+                            .await
+                            .map(|h| h.path().to_path_buf())
+                    },
+                    Message::ExportBackupPathPicked,
+                );
+                return (None, Some(task));
+            }
+            Message::ExportBackupPathPicked(path) => {
+                if let Some(path) = path {
+                    let passphrase = std::mem::take(&mut self.backup_passphrase);
+                    return (
+                        Some(Event::AppAction(AppAction::ExportBackup {
+                            path: path.to_string_lossy().into_owned(),
+                            passphrase,
+                        })),
+                        None,
+                    );
+                }
+            }
+            Message::ImportBackup => {
+                let task = Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Restore account backup")
+                            .pick_file()
+                            .await
+                            .map(|h| h.path().to_path_buf())
+                    },
+                    Message::ImportBackupPathPicked,
+                );
+                return (None, Some(task));
+            }
+            Message::ImportBackupPathPicked(path) => {
+                if let Some(path) = path {
+                    let passphrase = std::mem::take(&mut self.backup_passphrase);
+                    return (
+                        Some(Event::AppAction(AppAction::ImportBackup {
+                            path: path.to_string_lossy().into_owned(),
+                            passphrase,
+                        })),
+                        None,
+                    );
+                }
+            }
             Message::LogoutClicked => {
                 self.confirm_logout = true;
             }
@@ -108,6 +191,42 @@ impl State {
                     self.pending_image = Some(img);
                 }
             }
+            Message::AutoAcceptInvitesToggled(enabled) => {
+                return (
+                    Some(Event::AppAction(AppAction::SetAutoAcceptFollowedInvites {
+                        enabled,
+                    })),
+                    None,
+                );
+            }
+            Message::BlockListSyncToggled(enabled) => {
+                return (
+                    Some(Event::AppAction(AppAction::SetBlockListSyncEnabled {
+                        enabled,
+                    })),
+                    None,
+                );
+            }
+            Message::UnblockUser(pubkey) => {
+                return (
+                    Some(Event::AppAction(AppAction::UnblockUser { pubkey })),
+                    None,
+                );
+            }
+            Message::ReadReceiptsToggled(enabled) => {
+                return (
+                    Some(Event::AppAction(AppAction::SetReadReceiptsEnabled {
+                        enabled,
+                    })),
+                    None,
+                );
+            }
+            Message::RemoveAccount(pubkey) => {
+                return (Some(Event::RemoveAccount { pubkey }), None);
+            }
+            Message::SwitchAccount(pubkey) => {
+                return (Some(Event::SwitchAccount { pubkey }), None);
+            }
             Message::Save => {
                 if self.pending_image.is_some() {
                     // Save name/about first; the deferred upload fires
@@ -155,11 +274,17 @@ impl State {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn view<'a>(
         &'a self,
         npub: &'a str,
         app_version: &'a str,
         picture_url: Option<&'a str>,
+        read_receipts_enabled: bool,
+        auto_accept_followed_invites: bool,
+        block_list_sync_enabled: bool,
+        blocked_users: &'a [BlockedUser],
+        accounts: &'a [AccountSummary],
         avatar_cache: &mut super::avatar::AvatarCache,
     ) -> Element<'a, Message, Theme> {
         let mut content = column![].spacing(4).width(Fill);
@@ -326,6 +451,178 @@ impl State {
 
         content = content.push(container(rule::horizontal(1)).padding([8, 24]));
 
+        // ── Privacy toggles ──────────────────────────────────────────
+        content = content.push(toggle_row(
+            icons::CHECK_CHECK,
+            "Send read receipts",
+            read_receipts_enabled,
+            Message::ReadReceiptsToggled(!read_receipts_enabled),
+        ));
+        content = content.push(toggle_row(
+            icons::USERS,
+            "Auto-join invites from people you follow",
+            auto_accept_followed_invites,
+            Message::AutoAcceptInvitesToggled(!auto_accept_followed_invites),
+        ));
+        content = content.push(toggle_row(
+            icons::CIRCLE_X,
+            "Sync blocked people across devices",
+            block_list_sync_enabled,
+            Message::BlockListSyncToggled(!block_list_sync_enabled),
+        ));
+
+        // ── Blocked people ───────────────────────────────────────────
+        for user in blocked_users {
+            let label = user
+                .name
+                .as_deref()
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| theme::truncated_npub(&user.npub));
+            let blocked_row = row![
+                avatar_circle(
+                    Some(label.as_str()),
+                    user.picture_url.as_deref(),
+                    28.0,
+                    avatar_cache,
+                ),
+                text(label).size(14).color(theme::text_secondary()),
+                Space::new().width(Fill),
+                button(text("Unblock").size(13).center())
+                    .on_press(Message::UnblockUser(user.pubkey.clone()))
+                    .padding([6, 12])
+                    .style(theme::secondary_button_style),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center);
+            content = content.push(container(blocked_row).padding([4, 24]));
+        }
+
+        content = content.push(container(rule::horizontal(1)).padding([8, 24]));
+
+        // ── Accounts (switch / remove / add) ─────────────────────────
+        for account in accounts {
+            let label = account
+                .name
+                .as_deref()
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| theme::truncated_npub(&account.npub));
+            let mut account_row = row![
+                avatar_circle(
+                    Some(label.as_str()),
+                    account.picture_url.as_deref(),
+                    28.0,
+                    avatar_cache,
+                ),
+                text(label).size(14).color(theme::text_secondary()),
+                Space::new().width(Fill),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center);
+            if account.unread_count > 0 {
+                account_row = account_row.push(
+                    container(
+                        text(account.unread_count.to_string())
+                            .size(11)
+                            .color(iced::Color::WHITE)
+                            .center(),
+                    )
+                    .width(Length::Fixed(20.0))
+                    .height(Length::Fixed(20.0))
+                    .align_x(Alignment::Center)
+                    .align_y(Alignment::Center)
+                    .style(theme::badge_container_style),
+                );
+            }
+            if account.is_active {
+                account_row = account_row.push(
+                    text(icons::CHECK)
+                        .font(icons::LUCIDE_FONT)
+                        .size(16)
+                        .color(theme::accent_blue()),
+                );
+            } else {
+                account_row = account_row
+                    .push(
+                        button(text("Switch").size(13).center())
+                            .on_press(Message::SwitchAccount(account.pubkey.clone()))
+                            .padding([6, 12])
+                            .style(theme::secondary_button_style),
+                    )
+                    .push(
+                        button(text("Remove").size(13).center())
+                            .on_press(Message::RemoveAccount(account.pubkey.clone()))
+                            .padding([6, 12])
+                            .style(theme::secondary_button_style),
+                    );
+            }
+            content = content.push(container(account_row).padding([4, 24]));
+        }
+        let add_account_button = button(text("Add account").size(13).center())
+            .padding([8, 16])
+            .style(theme::secondary_button_style)
+            .on_press_maybe(
+                (!self.new_account_nsec.trim().is_empty()).then_some(Message::AddAccount),
+            );
+        content = content.push(
+            container(
+                row![
+                    text(icons::USER)
+                        .font(icons::LUCIDE_FONT)
+                        .size(18)
+                        .color(theme::text_secondary()),
+                    text_input("nsec of another account\u{2026}", &self.new_account_nsec)
+                        .on_input(Message::AddAccountNsecChanged)
+                        .on_submit(Message::AddAccount)
+                        .secure(true)
+                        .padding(10)
+                        .width(Fill)
+                        .style(theme::dark_input_style),
+                    add_account_button,
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+            )
+            .padding([4, 24]),
+        );
+
+        content = content.push(container(rule::horizontal(1)).padding([8, 24]));
+
+        // ── Backup (passphrase + export/restore) ─────────────────────
+        content = content.push(
+            container(
+                row![
+                    text(icons::KEY)
+                        .font(icons::LUCIDE_FONT)
+                        .size(18)
+                        .color(theme::text_secondary()),
+                    text_input("Backup passphrase\u{2026}", &self.backup_passphrase)
+                        .on_input(Message::BackupPassphraseChanged)
+                        .secure(true)
+                        .padding(10)
+                        .width(Fill)
+                        .style(theme::dark_input_style),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+            )
+            .padding([4, 24]),
+        );
+        let has_passphrase = !self.backup_passphrase.is_empty();
+        let export_button = button(text("Export backup").size(13).center())
+            .padding([8, 16])
+            .style(theme::secondary_button_style)
+            .on_press_maybe(has_passphrase.then_some(Message::ExportBackup));
+        let import_button = button(text("Restore backup").size(13).center())
+            .padding([8, 16])
+            .style(theme::secondary_button_style)
+            .on_press_maybe(has_passphrase.then_some(Message::ImportBackup));
+        content =
+            content.push(container(row![export_button, import_button].spacing(8)).padding([4, 24]));
+
+        content = content.push(container(rule::horizontal(1)).padding([8, 24]));
+
         // ── Logout ────────────────────────────────────────────────────
         content = content.push(Space::new().height(Fill));
 
@@ -400,6 +697,47 @@ impl State {
 
 // ── Helpers ─────────────────────────────────────────────────────────────────
 
+/// A full-width settings row with a checkbox indicator on the right.
+fn toggle_row<'a>(
+    icon: &'a str,
+    label: &'a str,
+    enabled: bool,
+    on_press: Message,
+) -> Element<'a, Message, Theme> {
+    let indicator = container(
+        text(icons::CHECK)
+            .font(icons::LUCIDE_FONT)
+            .size(12)
+            .center(),
+    )
+    .width(Length::Fixed(20.0))
+    .height(Length::Fixed(20.0))
+    .align_x(Alignment::Center)
+    .align_y(Alignment::Center)
+    .style(theme::checkbox_style(enabled));
+    container(
+        button(
+            row![
+                text(icon)
+                    .font(icons::LUCIDE_FONT)
+                    .size(18)
+                    .color(theme::text_secondary()),
+                text(label).size(14).color(theme::text_secondary()),
+                Space::new().width(Fill),
+                indicator,
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center),
+        )
+        .on_press(on_press)
+        .width(Fill)
+        .padding([12, 24])
+        .style(ghost_row_style),
+    )
+    .width(Fill)
+    .into()
+}
+
 fn ghost_row_style(_theme: &Theme, status: button::Status) -> button::Style {
     let bg = match status {
         button::Status::Hovered => theme::hover_bg(),
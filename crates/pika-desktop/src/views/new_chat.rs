@@ -29,6 +29,7 @@ pub enum Message {
 
 pub enum Event {
     CreateChat { peer_npub: String },
+    JoinGroup { link: String },
 }
 
 // ── Implementation ──────────────────────────────────────────────────────────
@@ -58,6 +59,10 @@ impl State {
                 if peer_npub.is_empty() {
                     return None;
                 }
+                if pika_core::is_group_invite_link(&peer_npub) {
+                    self.input.clear();
+                    return Some(Event::JoinGroup { link: peer_npub });
+                }
                 Some(Event::CreateChat { peer_npub })
             }
             Message::StartChatWith(peer_npub) => Some(Event::CreateChat { peer_npub }),
@@ -83,7 +88,7 @@ impl State {
 
         // ── Manual entry ────────────────────────────────────────────────
         let input_row = row![
-            text_input("npub1\u{2026} or hex pubkey", &self.input)
+            text_input("npub1\u{2026}, hex pubkey or invite link", &self.input)
                 .on_input(Message::InputChanged)
                 .on_submit(Message::StartChat)
                 .padding(10)
@@ -12,6 +12,8 @@ pub enum Message {
     CopyNpub,
     Follow,
     Unfollow,
+    Block,
+    Unblock,
     StartChat(String),
 }
 
@@ -20,6 +22,8 @@ pub enum Event {
     CopyNpub,
     Follow,
     Unfollow,
+    Block,
+    Unblock,
     StartChat { peer_npub: String },
 }
 
@@ -29,6 +33,8 @@ pub fn update(message: Message) -> Option<Event> {
         Message::CopyNpub => Some(Event::CopyNpub),
         Message::Follow => Some(Event::Follow),
         Message::Unfollow => Some(Event::Unfollow),
+        Message::Block => Some(Event::Block),
+        Message::Unblock => Some(Event::Unblock),
         Message::StartChat(npub) => Some(Event::StartChat { peer_npub: npub }),
     }
 }
@@ -152,6 +158,22 @@ pub fn peer_profile_view<'a>(
         .center_x(Fill),
     );
 
+    let block_btn = if profile.is_blocked {
+        button(text("Unblock").size(14).font(icons::MEDIUM).center()).on_press(Message::Unblock)
+    } else {
+        button(text("Block").size(14).font(icons::MEDIUM).center()).on_press(Message::Block)
+    };
+    content = content.push(
+        container(
+            block_btn
+                .width(Length::Fixed(160.0))
+                .padding([10, 24])
+                .style(theme::icon_button_style(false)),
+        )
+        .width(Fill)
+        .center_x(Fill),
+    );
+
     container(content)
         .width(Fill)
         .height(Fill)
@@ -4,11 +4,14 @@ use mdk_core::prelude::GroupId;
 use mdk_sqlite_storage::MdkSqliteStorage;
 use nostr_sdk::hashes::{Hash as _, sha256};
 use pika_media::crypto::{FrameKeyMaterial, opaque_participant_label};
+use pika_media::directory::{DirectoryEntry, DirectoryMessage};
+use pika_media::tracks::TrackSpec;
 use serde::{Deserialize, Serialize};
 
 pub const DEFAULT_CALL_BROADCAST_PREFIX: &str = "pika/calls";
 const CALL_NS: &str = "pika.call";
 const CALL_PROTOCOL_VERSION: u8 = 1;
+const CALL_DIRECTORY_VERSION: u8 = 1;
 const RELAY_AUTH_CAP_PREFIX: &str = "capv1_";
 const RELAY_AUTH_HEX_LEN: usize = 64;
 
@@ -41,6 +44,54 @@ impl CallTrackSpec {
             frame_ms: 33,
         }
     }
+
+    /// Screen share, tuned for legible text rather than motion. Frames only
+    /// flow while the sender is sharing.
+    pub fn screen0_h264_default() -> Self {
+        Self {
+            name: "screen0".to_string(),
+            codec: "h264".to_string(),
+            sample_rate: 90_000,
+            channels: 0,
+            frame_ms: 200,
+        }
+    }
+
+    /// Call data track. Video calls carry receiver feedback for the sender's
+    /// rate control on it.
+    pub fn data0_default() -> Self {
+        Self {
+            name: "data0".to_string(),
+            codec: "data".to_string(),
+            sample_rate: 0,
+            channels: 0,
+            frame_ms: 0,
+        }
+    }
+}
+
+impl From<&CallTrackSpec> for TrackSpec {
+    fn from(track: &CallTrackSpec) -> Self {
+        Self {
+            name: track.name.clone(),
+            codec: track.codec.clone(),
+            sample_rate: track.sample_rate,
+            channels: track.channels,
+            frame_ms: track.frame_ms,
+        }
+    }
+}
+
+impl From<&TrackSpec> for CallTrackSpec {
+    fn from(track: &TrackSpec) -> Self {
+        Self {
+            name: track.name.clone(),
+            codec: track.codec.clone(),
+            sample_rate: track.sample_rate,
+            channels: track.channels,
+            frame_ms: track.frame_ms,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -49,6 +100,10 @@ pub struct CallSessionParams {
     pub broadcast_base: String,
     pub relay_auth: String,
     pub tracks: Vec<CallTrackSpec>,
+    /// Multi-party call in an MLS group chat. Media keys and relay auth are
+    /// derived from a group-wide seed instead of the 1:1 pubkey pair.
+    #[serde(default)]
+    pub group_call: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,6 +124,22 @@ pub enum ParsedCallSignal {
         call_id: String,
         reason: String,
     },
+    /// Group call roster: every participant currently publishing and their tracks.
+    Directory {
+        call_id: String,
+        directory: DirectoryMessage,
+    },
+    /// Sender's own mute/camera state in a group call.
+    MediaState {
+        call_id: String,
+        muted: bool,
+        camera_enabled: bool,
+    },
+    /// Sender started or stopped recording the call on their device.
+    Recording {
+        call_id: String,
+        recording: bool,
+    },
 }
 
 pub enum OutgoingCallSignal<'a> {
@@ -76,6 +147,9 @@ pub enum OutgoingCallSignal<'a> {
     Accept(&'a CallSessionParams),
     Reject { reason: &'a str },
     End { reason: &'a str },
+    Directory(&'a DirectoryMessage),
+    MediaState { muted: bool, camera_enabled: bool },
+    Recording { recording: bool },
 }
 
 #[derive(Debug, Clone)]
@@ -84,6 +158,10 @@ pub struct CallMediaCryptoContext {
     pub rx_keys: FrameKeyMaterial,
     pub video_tx_keys: Option<FrameKeyMaterial>,
     pub video_rx_keys: Option<FrameKeyMaterial>,
+    pub data_tx_keys: Option<FrameKeyMaterial>,
+    pub data_rx_keys: Option<FrameKeyMaterial>,
+    pub screen_tx_keys: Option<FrameKeyMaterial>,
+    pub screen_rx_keys: Option<FrameKeyMaterial>,
     pub local_participant_label: String,
     pub peer_participant_label: String,
 }
@@ -116,6 +194,61 @@ struct CallReasonBody {
     reason: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CallDirectoryEntryBody {
+    pubkey: String,
+    tracks: Vec<CallTrackSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CallDirectoryBody {
+    version: u8,
+    participants: Vec<CallDirectoryEntryBody>,
+}
+
+impl From<&DirectoryMessage> for CallDirectoryBody {
+    fn from(directory: &DirectoryMessage) -> Self {
+        Self {
+            version: directory.version,
+            participants: directory
+                .entries
+                .iter()
+                .map(|entry| CallDirectoryEntryBody {
+                    pubkey: entry.participant_pubkey_hex.clone(),
+                    tracks: entry.tracks.iter().map(CallTrackSpec::from).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<CallDirectoryBody> for DirectoryMessage {
+    fn from(body: CallDirectoryBody) -> Self {
+        Self {
+            version: body.version,
+            entries: body
+                .participants
+                .into_iter()
+                .map(|entry| DirectoryEntry {
+                    participant_pubkey_hex: entry.pubkey,
+                    tracks: entry.tracks.iter().map(TrackSpec::from).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CallMediaStateBody {
+    muted: bool,
+    camera_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CallRecordingBody {
+    recording: bool,
+}
+
 fn now_millis() -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now()
@@ -145,6 +278,12 @@ pub fn call_shared_seed(
     local_pubkey_hex: &str,
     peer_pubkey_hex: &str,
 ) -> String {
+    if session.group_call {
+        return format!(
+            "pika-call-media-v1|{call_id}|{}|{}|group",
+            session.moq_url, session.broadcast_base
+        );
+    }
     let (left, right) = if local_pubkey_hex <= peer_pubkey_hex {
         (local_pubkey_hex, peer_pubkey_hex)
     } else {
@@ -199,10 +338,50 @@ pub fn parse_call_signal(content: &str) -> Option<ParsedCallSignal> {
                 reason: body.reason,
             })
         }
+        "call.directory" => {
+            let body: CallDirectoryBody = serde_json::from_value(env.body).ok()?;
+            if body.version != CALL_DIRECTORY_VERSION {
+                return None;
+            }
+            Some(ParsedCallSignal::Directory {
+                call_id: env.call_id,
+                directory: body.into(),
+            })
+        }
+        "call.media_state" => {
+            let body: CallMediaStateBody = serde_json::from_value(env.body).ok()?;
+            Some(ParsedCallSignal::MediaState {
+                call_id: env.call_id,
+                muted: body.muted,
+                camera_enabled: body.camera_enabled,
+            })
+        }
+        "call.recording" => {
+            let body: CallRecordingBody = serde_json::from_value(env.body).ok()?;
+            Some(ParsedCallSignal::Recording {
+                call_id: env.call_id,
+                recording: body.recording,
+            })
+        }
         _ => None,
     }
 }
 
+/// Builds the roster a group call participant publishes so late joiners can
+/// subscribe to everyone already in the call.
+pub fn call_directory(participants: &[(String, Vec<CallTrackSpec>)]) -> DirectoryMessage {
+    DirectoryMessage {
+        version: CALL_DIRECTORY_VERSION,
+        entries: participants
+            .iter()
+            .map(|(pubkey_hex, tracks)| DirectoryEntry {
+                participant_pubkey_hex: pubkey_hex.clone(),
+                tracks: tracks.iter().map(TrackSpec::from).collect(),
+            })
+            .collect(),
+    }
+}
+
 pub fn build_call_signal_json(
     call_id: &str,
     outgoing: OutgoingCallSignal<'_>,
@@ -222,6 +401,24 @@ pub fn build_call_signal_json(
                 reason: reason.to_string(),
             })?,
         ),
+        OutgoingCallSignal::Directory(directory) => (
+            "call.directory",
+            serde_json::to_value(CallDirectoryBody::from(directory))?,
+        ),
+        OutgoingCallSignal::MediaState {
+            muted,
+            camera_enabled,
+        } => (
+            "call.media_state",
+            serde_json::to_value(CallMediaStateBody {
+                muted,
+                camera_enabled,
+            })?,
+        ),
+        OutgoingCallSignal::Recording { recording } => (
+            "call.recording",
+            serde_json::to_value(CallRecordingBody { recording })?,
+        ),
     };
 
     let env = CallEnvelope {
@@ -246,7 +443,7 @@ fn derive_track_keys(
         ctx.local_pubkey_hex,
         ctx.peer_pubkey_hex,
     );
-    let generation = 0u8;
+    let generation = media_key_generation(ctx.group_epoch);
 
     let tx_hash = context_hash(&[
         b"pika.call.media.base.v1",
@@ -320,10 +517,22 @@ fn derive_track_keys(
     Ok((tx_keys, rx_keys, group_root))
 }
 
+/// Frame key generation used for a given MLS epoch. Tying it to the epoch lets
+/// every participant (including ones who join mid-call) agree on it without
+/// coordinating; it wraps, which is fine since receivers only keep two.
+pub fn media_key_generation(group_epoch: u64) -> u8 {
+    (group_epoch & 0xff) as u8
+}
+
+/// Derives frame keys from the group's current MLS epoch. The base keys come from
+/// the epoch's exporter secret, so members removed by a commit can't follow a
+/// call past it.
 pub fn derive_call_media_crypto_context(
     ctx: &CallCryptoDeriveContext<'_>,
     primary_track: &str,
     video_track: Option<&str>,
+    data_track: Option<&str>,
+    screen_track: Option<&str>,
 ) -> Result<CallMediaCryptoContext, String> {
     let (tx_keys, rx_keys, group_root) = derive_track_keys(ctx, primary_track)?;
 
@@ -333,12 +542,28 @@ pub fn derive_call_media_crypto_context(
     } else {
         (None, None)
     };
+    let (data_tx_keys, data_rx_keys) = if let Some(track) = data_track {
+        let (dtx, drx, _) = derive_track_keys(ctx, track)?;
+        (Some(dtx), Some(drx))
+    } else {
+        (None, None)
+    };
+    let (screen_tx_keys, screen_rx_keys) = if let Some(track) = screen_track {
+        let (stx, srx, _) = derive_track_keys(ctx, track)?;
+        (Some(stx), Some(srx))
+    } else {
+        (None, None)
+    };
 
     Ok(CallMediaCryptoContext {
         tx_keys,
         rx_keys,
         video_tx_keys,
         video_rx_keys,
+        data_tx_keys,
+        data_rx_keys,
+        screen_tx_keys,
+        screen_rx_keys,
         local_participant_label: opaque_participant_label(
             &group_root,
             ctx.local_pubkey_hex.as_bytes(),
@@ -404,6 +629,7 @@ mod tests {
             broadcast_base: format!("pika/calls/{call_id}"),
             relay_auth: "capv1_test_token".to_string(),
             tracks: vec![CallTrackSpec::audio0_opus_default()],
+            group_call: false,
         };
         let json = build_call_signal_json(call_id, OutgoingCallSignal::Invite(&session)).unwrap();
         let parsed = parse_call_signal(&json);
@@ -440,6 +666,7 @@ mod tests {
             broadcast_base: "pika/calls/test".to_string(),
             relay_auth: String::new(),
             tracks: vec![CallTrackSpec::audio0_opus_default()],
+            group_call: false,
         };
         let a = call_shared_seed(
             "call-123",
@@ -455,4 +682,92 @@ mod tests {
         );
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn directory_signal_round_trip() {
+        let directory = call_directory(&[
+            (
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                vec![CallTrackSpec::audio0_opus_default()],
+            ),
+            (
+                "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+                vec![
+                    CallTrackSpec::audio0_opus_default(),
+                    CallTrackSpec::video0_h264_default(),
+                ],
+            ),
+        ]);
+        let json =
+            build_call_signal_json("call-123", OutgoingCallSignal::Directory(&directory)).unwrap();
+        match parse_call_signal(&json) {
+            Some(ParsedCallSignal::Directory {
+                call_id,
+                directory: got,
+            }) => {
+                assert_eq!(call_id, "call-123");
+                assert_eq!(got, directory);
+                assert_eq!(got.entries[1].tracks[1].name, "video0");
+            }
+            _ => panic!("expected directory"),
+        }
+    }
+
+    #[test]
+    fn media_state_signal_round_trip() {
+        let json = build_call_signal_json(
+            "call-123",
+            OutgoingCallSignal::MediaState {
+                muted: true,
+                camera_enabled: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            parse_call_signal(&json),
+            Some(ParsedCallSignal::MediaState {
+                call_id: "call-123".to_string(),
+                muted: true,
+                camera_enabled: false,
+            })
+        );
+    }
+
+    #[test]
+    fn recording_signal_round_trip() {
+        let json = build_call_signal_json(
+            "call-123",
+            OutgoingCallSignal::Recording { recording: true },
+        )
+        .unwrap();
+        assert!(json.contains("\"call.recording\""));
+        assert_eq!(
+            parse_call_signal(&json),
+            Some(ParsedCallSignal::Recording {
+                call_id: "call-123".to_string(),
+                recording: true,
+            })
+        );
+    }
+
+    #[test]
+    fn group_shared_seed_ignores_pubkey_pair() {
+        let session = CallSessionParams {
+            moq_url: "https://moq.example.com/anon".to_string(),
+            broadcast_base: "pika/calls/test".to_string(),
+            relay_auth: String::new(),
+            tracks: vec![CallTrackSpec::audio0_opus_default()],
+            group_call: true,
+        };
+        let a = call_shared_seed("call-123", &session, "aa", "bb");
+        let b = call_shared_seed("call-123", &session, "cc", "dd");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn session_without_group_flag_parses_as_one_to_one() {
+        let json = r#"{"moq_url":"https://moq.example.com/anon","broadcast_base":"pika/calls/x","relay_auth":"","tracks":[]}"#;
+        let session: CallSessionParams = serde_json::from_str(json).unwrap();
+        assert!(!session.group_call);
+    }
 }
@@ -5,6 +5,7 @@ use crate::call::{
     validate_relay_auth_token,
 };
 use mdk_core::prelude::GroupId;
+use pika_media::directory::DirectoryMessage;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PendingIncomingCall {
@@ -19,6 +20,7 @@ pub struct PendingIncomingCall {
 pub struct PendingOutgoingCall {
     pub call_id: String,
     pub target_id: String,
+    /// Empty for group calls, where any member of the target may accept.
     pub peer_pubkey_hex: String,
     pub session: CallSessionParams,
     pub is_video_call: bool,
@@ -77,6 +79,45 @@ pub struct RemoteCallTermination {
     pub reason: String,
 }
 
+/// A group call the local member is connected to, with the remote participants
+/// it already has media keys for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveGroupCall {
+    pub call_id: String,
+    pub target_id: String,
+    pub session: CallSessionParams,
+    pub participant_pubkeys_hex: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JoinedCallParticipant {
+    pub call_id: String,
+    pub pubkey_hex: String,
+    pub media_crypto: CallMediaCryptoContext,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallParticipantLeft {
+    pub call_id: String,
+    pub pubkey_hex: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallParticipantMediaState {
+    pub call_id: String,
+    pub pubkey_hex: String,
+    pub muted: bool,
+    pub camera_enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallParticipantRecording {
+    pub call_id: String,
+    pub pubkey_hex: String,
+    pub recording: bool,
+}
+
 #[derive(Debug, Clone)]
 pub enum InboundCallSignalOutcome {
     Ignore,
@@ -85,6 +126,10 @@ pub enum InboundCallSignalOutcome {
     OutgoingAccepted(Box<AcceptedOutgoingCall>),
     IncomingAcceptFailed(IncomingAcceptFailure),
     RemoteTermination(RemoteCallTermination),
+    ParticipantsJoined(Vec<JoinedCallParticipant>),
+    ParticipantLeft(CallParticipantLeft),
+    ParticipantMediaState(CallParticipantMediaState),
+    ParticipantRecording(CallParticipantRecording),
 }
 
 pub struct CallWorkflowRuntime<'a> {
@@ -104,6 +149,7 @@ pub struct InboundSignalContext<'a> {
     pub policy: InboundCallPolicy,
     pub has_live_call: bool,
     pub pending_outgoing: Option<&'a PendingOutgoingCall>,
+    pub live_group_call: Option<&'a LiveGroupCall>,
 }
 
 impl<'a> CallWorkflowRuntime<'a> {
@@ -173,6 +219,63 @@ impl<'a> CallWorkflowRuntime<'a> {
         self.prepare_signal(call_id, OutgoingCallSignal::End { reason })
     }
 
+    pub fn prepare_directory_signal(
+        &self,
+        call_id: &str,
+        directory: &DirectoryMessage,
+    ) -> Result<PreparedCallSignal, String> {
+        self.prepare_signal(call_id, OutgoingCallSignal::Directory(directory))
+    }
+
+    pub fn prepare_media_state_signal(
+        &self,
+        call_id: &str,
+        muted: bool,
+        camera_enabled: bool,
+    ) -> Result<PreparedCallSignal, String> {
+        self.prepare_signal(
+            call_id,
+            OutgoingCallSignal::MediaState {
+                muted,
+                camera_enabled,
+            },
+        )
+    }
+
+    pub fn prepare_recording_signal(
+        &self,
+        call_id: &str,
+        recording: bool,
+    ) -> Result<PreparedCallSignal, String> {
+        self.prepare_signal(call_id, OutgoingCallSignal::Recording { recording })
+    }
+
+    /// Re-derives a live call's media keys for one remote participant after the
+    /// group moved to a new MLS epoch. Returns `None` while `current` still
+    /// matches the group's epoch. Broadcast labels are carried over so existing
+    /// publish/subscribe paths stay valid across the rotation.
+    pub fn rotate_media_crypto(
+        &self,
+        group: GroupCallContext<'_>,
+        call_id: &str,
+        session: &CallSessionParams,
+        peer_pubkey_hex: &str,
+        current: &CallMediaCryptoContext,
+    ) -> Result<Option<CallMediaCryptoContext>, String> {
+        let group_epoch = self.group_epoch(group.mls_group_id)?;
+        if group_epoch == current.tx_keys.epoch {
+            return Ok(None);
+        }
+        let mut rotated = self.derive_media_crypto(group, call_id, session, peer_pubkey_hex)?;
+        rotated
+            .local_participant_label
+            .clone_from(&current.local_participant_label);
+        rotated
+            .peer_participant_label
+            .clone_from(&current.peer_participant_label);
+        Ok(Some(rotated))
+    }
+
     pub fn handle_inbound_signal(
         &self,
         ctx: InboundSignalContext<'_>,
@@ -203,12 +306,16 @@ impl<'a> CallWorkflowRuntime<'a> {
                 }
             }
             ParsedCallSignal::Accept { call_id, session } => {
+                if let Some(live) = live_group_call_for(&ctx, &call_id) {
+                    return self.group_participant_accepted(&ctx, live, call_id, session);
+                }
                 let Some(pending) = ctx.pending_outgoing else {
                     return InboundCallSignalOutcome::Ignore;
                 };
                 if pending.call_id != call_id
                     || pending.target_id != ctx.target_id
-                    || pending.peer_pubkey_hex != ctx.sender_pubkey_hex
+                    || (!pending.session.group_call
+                        && pending.peer_pubkey_hex != ctx.sender_pubkey_hex)
                 {
                     return InboundCallSignalOutcome::Ignore;
                 }
@@ -248,11 +355,116 @@ impl<'a> CallWorkflowRuntime<'a> {
             }
             ParsedCallSignal::Reject { call_id, reason }
             | ParsedCallSignal::End { call_id, reason } => {
+                let pending_group_call = ctx.pending_outgoing.is_some_and(|pending| {
+                    pending.session.group_call
+                        && pending.call_id == call_id
+                        && pending.target_id == ctx.target_id
+                });
+                if pending_group_call || live_group_call_for(&ctx, &call_id).is_some() {
+                    // One member leaving or declining doesn't end a group call for the rest.
+                    return InboundCallSignalOutcome::ParticipantLeft(CallParticipantLeft {
+                        call_id,
+                        pubkey_hex: ctx.sender_pubkey_hex.to_string(),
+                        reason,
+                    });
+                }
                 InboundCallSignalOutcome::RemoteTermination(RemoteCallTermination {
                     call_id,
                     reason,
                 })
             }
+            ParsedCallSignal::Directory { call_id, directory } => {
+                let Some(live) = live_group_call_for(&ctx, &call_id) else {
+                    return InboundCallSignalOutcome::Ignore;
+                };
+                let joined: Vec<JoinedCallParticipant> = directory
+                    .entries
+                    .iter()
+                    .map(|entry| entry.participant_pubkey_hex.as_str())
+                    .filter(|pubkey_hex| is_new_participant(&ctx, live, pubkey_hex))
+                    .filter_map(|pubkey_hex| {
+                        self.derive_joined_participant(&ctx, live, &call_id, pubkey_hex)
+                    })
+                    .collect();
+                if joined.is_empty() {
+                    return InboundCallSignalOutcome::Ignore;
+                }
+                InboundCallSignalOutcome::ParticipantsJoined(joined)
+            }
+            ParsedCallSignal::MediaState {
+                call_id,
+                muted,
+                camera_enabled,
+            } => {
+                if live_group_call_for(&ctx, &call_id).is_none() {
+                    return InboundCallSignalOutcome::Ignore;
+                }
+                InboundCallSignalOutcome::ParticipantMediaState(CallParticipantMediaState {
+                    call_id,
+                    pubkey_hex: ctx.sender_pubkey_hex.to_string(),
+                    muted,
+                    camera_enabled,
+                })
+            }
+            ParsedCallSignal::Recording { call_id, recording } => {
+                // 1:1 calls have no roster here; the caller matches the call id.
+                if live_group_call_for(&ctx, &call_id).is_none() && !ctx.has_live_call {
+                    return InboundCallSignalOutcome::Ignore;
+                }
+                InboundCallSignalOutcome::ParticipantRecording(CallParticipantRecording {
+                    call_id,
+                    pubkey_hex: ctx.sender_pubkey_hex.to_string(),
+                    recording,
+                })
+            }
+        }
+    }
+
+    fn group_participant_accepted(
+        &self,
+        ctx: &InboundSignalContext<'_>,
+        live: &LiveGroupCall,
+        call_id: String,
+        session: CallSessionParams,
+    ) -> InboundCallSignalOutcome {
+        if !is_new_participant(ctx, live, ctx.sender_pubkey_hex) {
+            return InboundCallSignalOutcome::Ignore;
+        }
+        if live.session.relay_auth != session.relay_auth {
+            tracing::warn!(
+                call_id,
+                sender = ctx.sender_pubkey_hex,
+                "group call accept relay auth mismatch; ignoring"
+            );
+            return InboundCallSignalOutcome::Ignore;
+        }
+        match self.derive_joined_participant(ctx, live, &call_id, ctx.sender_pubkey_hex) {
+            Some(joined) => InboundCallSignalOutcome::ParticipantsJoined(vec![joined]),
+            None => InboundCallSignalOutcome::Ignore,
+        }
+    }
+
+    fn derive_joined_participant(
+        &self,
+        ctx: &InboundSignalContext<'_>,
+        live: &LiveGroupCall,
+        call_id: &str,
+        pubkey_hex: &str,
+    ) -> Option<JoinedCallParticipant> {
+        match self.derive_media_crypto(ctx.group, call_id, &live.session, pubkey_hex) {
+            Ok(media_crypto) => Some(JoinedCallParticipant {
+                call_id: call_id.to_string(),
+                pubkey_hex: pubkey_hex.to_string(),
+                media_crypto,
+            }),
+            Err(error) => {
+                tracing::warn!(
+                    call_id,
+                    participant = pubkey_hex,
+                    "group call participant media key setup failed: {error}"
+                );
+                None
+            }
         }
     }
 
@@ -316,12 +528,7 @@ impl<'a> CallWorkflowRuntime<'a> {
         session: &CallSessionParams,
         peer_pubkey_hex: &str,
     ) -> Result<CallMediaCryptoContext, String> {
-        let group_epoch = self
-            .mdk
-            .get_group(group.mls_group_id)
-            .map_err(|e| format!("load mls group failed: {e}"))?
-            .ok_or_else(|| "mls group not found".to_string())?
-            .epoch;
+        let group_epoch = self.group_epoch(group.mls_group_id)?;
         let derive_ctx = CallCryptoDeriveContext {
             mdk: self.mdk,
             mls_group_id: group.mls_group_id,
@@ -332,12 +539,53 @@ impl<'a> CallWorkflowRuntime<'a> {
             peer_pubkey_hex,
         };
         let video_track = has_video_track(session).then_some("video0");
-        derive_call_media_crypto_context(&derive_ctx, "audio0", video_track)
+        let data_track = has_track(session, "data0").then_some("data0");
+        let screen_track = has_track(session, "screen0").then_some("screen0");
+        derive_call_media_crypto_context(
+            &derive_ctx,
+            "audio0",
+            video_track,
+            data_track,
+            screen_track,
+        )
+    }
+
+    fn group_epoch(&self, mls_group_id: &GroupId) -> Result<u64, String> {
+        Ok(self
+            .mdk
+            .get_group(mls_group_id)
+            .map_err(|e| format!("load mls group failed: {e}"))?
+            .ok_or_else(|| "mls group not found".to_string())?
+            .epoch)
     }
 }
 
 fn has_video_track(session: &CallSessionParams) -> bool {
-    session.tracks.iter().any(|track| track.name == "video0")
+    has_track(session, "video0")
+}
+
+fn has_track(session: &CallSessionParams, name: &str) -> bool {
+    session.tracks.iter().any(|track| track.name == name)
+}
+
+fn live_group_call_for<'a>(
+    ctx: &InboundSignalContext<'a>,
+    call_id: &str,
+) -> Option<&'a LiveGroupCall> {
+    ctx.live_group_call
+        .filter(|live| live.call_id == call_id && live.target_id == ctx.target_id)
+}
+
+fn is_new_participant(
+    ctx: &InboundSignalContext<'_>,
+    live: &LiveGroupCall,
+    pubkey_hex: &str,
+) -> bool {
+    pubkey_hex != ctx.group.local_pubkey_hex
+        && !live
+            .participant_pubkeys_hex
+            .iter()
+            .any(|known| known == pubkey_hex)
 }
 
 #[cfg(test)]
@@ -435,6 +683,7 @@ mod tests {
             broadcast_base: format!("pika/calls/{call_id}"),
             relay_auth: String::new(),
             tracks: vec![CallTrackSpec::audio0_opus_default()],
+            group_call: false,
         };
         let relay_auth = derive_relay_auth_token(&CallCryptoDeriveContext {
             mdk: &inviter_mdk,
@@ -539,6 +788,7 @@ mod tests {
                 },
                 has_live_call: false,
                 pending_outgoing: None,
+                live_group_call: None,
             },
             ParsedCallSignal::Invite {
                 call_id: "call-runtime-test".to_string(),
@@ -580,6 +830,7 @@ mod tests {
                 },
                 has_live_call: false,
                 pending_outgoing: Some(&pending),
+                live_group_call: None,
             },
             ParsedCallSignal::Accept {
                 call_id: "call-runtime-test".to_string(),
@@ -594,4 +845,590 @@ mod tests {
             _ => panic!("expected outgoing accept"),
         }
     }
+
+    #[test]
+    fn handle_inbound_signal_tracks_group_call_joins_and_leaves() {
+        let (_inviter_mdk, _mdk, group_id, inviter_keys, invitee_keys, mut session, runtime) =
+            make_group();
+        session.group_call = true;
+        let local_pubkey_hex = invitee_keys.public_key().to_hex();
+        let inviter_pubkey_hex = inviter_keys.public_key().to_hex();
+        let mut live = LiveGroupCall {
+            call_id: "call-runtime-test".to_string(),
+            target_id: "chat1".to_string(),
+            session: session.clone(),
+            participant_pubkeys_hex: Vec::new(),
+        };
+        let inbound = |live: &LiveGroupCall, signal: ParsedCallSignal| {
+            runtime.handle_inbound_signal(
+                InboundSignalContext {
+                    target_id: "chat1",
+                    sender_pubkey_hex: &inviter_pubkey_hex,
+                    group: GroupCallContext {
+                        mls_group_id: &group_id,
+                        local_pubkey_hex: &local_pubkey_hex,
+                    },
+                    policy: InboundCallPolicy {
+                        allow_group_calls: true,
+                        allow_video_calls: true,
+                    },
+                    has_live_call: true,
+                    pending_outgoing: None,
+                    live_group_call: Some(live),
+                },
+                signal,
+            )
+        };
+
+        match inbound(
+            &live,
+            ParsedCallSignal::Accept {
+                call_id: "call-runtime-test".to_string(),
+                session: session.clone(),
+            },
+        ) {
+            InboundCallSignalOutcome::ParticipantsJoined(joined) => {
+                assert_eq!(joined.len(), 1);
+                assert_eq!(joined[0].pubkey_hex, inviter_pubkey_hex);
+            }
+            _ => panic!("expected participant join"),
+        }
+
+        live.participant_pubkeys_hex
+            .push(inviter_pubkey_hex.clone());
+        let directory = crate::call::call_directory(&[
+            (local_pubkey_hex.clone(), session.tracks.clone()),
+            (inviter_pubkey_hex.clone(), session.tracks.clone()),
+        ]);
+        assert!(matches!(
+            inbound(
+                &live,
+                ParsedCallSignal::Directory {
+                    call_id: "call-runtime-test".to_string(),
+                    directory,
+                },
+            ),
+            InboundCallSignalOutcome::Ignore
+        ));
+
+        match inbound(
+            &live,
+            ParsedCallSignal::MediaState {
+                call_id: "call-runtime-test".to_string(),
+                muted: true,
+                camera_enabled: false,
+            },
+        ) {
+            InboundCallSignalOutcome::ParticipantMediaState(state) => {
+                assert_eq!(state.pubkey_hex, inviter_pubkey_hex);
+                assert!(state.muted);
+            }
+            _ => panic!("expected media state"),
+        }
+
+        match inbound(
+            &live,
+            ParsedCallSignal::Recording {
+                call_id: "call-runtime-test".to_string(),
+                recording: true,
+            },
+        ) {
+            InboundCallSignalOutcome::ParticipantRecording(state) => {
+                assert_eq!(state.pubkey_hex, inviter_pubkey_hex);
+                assert!(state.recording);
+            }
+            _ => panic!("expected recording state"),
+        }
+
+        match inbound(
+            &live,
+            ParsedCallSignal::End {
+                call_id: "call-runtime-test".to_string(),
+                reason: "user_hangup".to_string(),
+            },
+        ) {
+            InboundCallSignalOutcome::ParticipantLeft(left) => {
+                assert_eq!(left.pubkey_hex, inviter_pubkey_hex);
+                assert_eq!(left.reason, "user_hangup");
+            }
+            _ => panic!("expected participant left"),
+        }
+    }
+
+    #[test]
+    fn group_call_participants_share_sender_keys() {
+        let (inviter_mdk, invitee_mdk, group_id, inviter_keys, invitee_keys, mut session, _) =
+            make_group();
+        session.group_call = true;
+        let inviter_hex = inviter_keys.public_key().to_hex();
+        let invitee_hex = invitee_keys.public_key().to_hex();
+        let inviter_view = CallWorkflowRuntime::new(inviter_mdk)
+            .derive_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &inviter_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &invitee_hex,
+            )
+            .expect("inviter media crypto");
+        let invitee_view = CallWorkflowRuntime::new(invitee_mdk)
+            .derive_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &invitee_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &inviter_hex,
+            )
+            .expect("invitee media crypto");
+
+        assert_eq!(inviter_view.tx_keys.key_id, invitee_view.rx_keys.key_id);
+        assert_eq!(inviter_view.rx_keys.key_id, invitee_view.tx_keys.key_id);
+        assert_eq!(
+            inviter_view.local_participant_label,
+            invitee_view.peer_participant_label
+        );
+        assert_eq!(
+            inviter_view.tx_keys.key_id,
+            crate::call::key_id_for_sender(inviter_hex.as_bytes())
+        );
+
+        let info = pika_media::crypto::FrameInfo {
+            counter: 0,
+            group_seq: 0,
+            frame_idx: 0,
+            keyframe: true,
+        };
+        let encrypted = pika_media::crypto::encrypt_frame(b"hello", &inviter_view.tx_keys, info)
+            .expect("encrypt");
+        let decrypted = pika_media::crypto::decrypt_frame(&encrypted, &invitee_view.rx_keys)
+            .expect("decrypt with group sender key");
+        assert_eq!(decrypted.payload, b"hello");
+    }
+
+    #[test]
+    fn data_and_screen_tracks_get_their_own_keys() {
+        let (inviter_mdk, invitee_mdk, group_id, inviter_keys, invitee_keys, mut session, _) =
+            make_group();
+        let inviter_hex = inviter_keys.public_key().to_hex();
+        let invitee_hex = invitee_keys.public_key().to_hex();
+        let audio_only =
+            media_crypto_for(inviter_mdk, &group_id, &inviter_hex, &invitee_hex, &session);
+        assert!(audio_only.data_tx_keys.is_none());
+        assert!(audio_only.screen_tx_keys.is_none());
+
+        session.tracks.push(CallTrackSpec::video0_h264_default());
+        session.tracks.push(CallTrackSpec::data0_default());
+        session.tracks.push(CallTrackSpec::screen0_h264_default());
+        let inviter_crypto =
+            media_crypto_for(inviter_mdk, &group_id, &inviter_hex, &invitee_hex, &session);
+        let invitee_crypto =
+            media_crypto_for(invitee_mdk, &group_id, &invitee_hex, &inviter_hex, &session);
+        let data_tx = inviter_crypto.data_tx_keys.expect("data tx keys");
+        let data_rx = invitee_crypto.data_rx_keys.expect("data rx keys");
+        let screen_tx = inviter_crypto.screen_tx_keys.expect("screen tx keys");
+        let screen_rx = invitee_crypto.screen_rx_keys.expect("screen rx keys");
+        let video_tx = inviter_crypto.video_tx_keys.expect("video tx keys");
+
+        // Each track counts frames independently, so sharing a key would reuse
+        // nonces.
+        let info = pika_media::crypto::FrameInfo {
+            counter: 0,
+            group_seq: 0,
+            frame_idx: 0,
+            keyframe: true,
+        };
+        let encrypted =
+            pika_media::crypto::encrypt_frame(b"report", &data_tx, info).expect("encrypt");
+        let decrypted =
+            pika_media::crypto::decrypt_frame(&encrypted, &data_rx).expect("decrypt data frame");
+        assert_eq!(decrypted.payload, b"report");
+        let screen_encrypted =
+            pika_media::crypto::encrypt_frame(b"report", &screen_tx, info).expect("encrypt");
+        let decrypted = pika_media::crypto::decrypt_frame(&screen_encrypted, &screen_rx)
+            .expect("decrypt screen frame");
+        assert_eq!(decrypted.payload, b"report");
+        let video_encrypted =
+            pika_media::crypto::encrypt_frame(b"report", &video_tx, info).expect("encrypt");
+        assert_ne!(encrypted, video_encrypted);
+        assert_ne!(screen_encrypted, video_encrypted);
+        assert!(pika_media::crypto::decrypt_frame(&video_encrypted, &data_rx).is_err());
+        assert!(pika_media::crypto::decrypt_frame(&video_encrypted, &screen_rx).is_err());
+        assert!(pika_media::crypto::decrypt_frame(&encrypted, &screen_rx).is_err());
+    }
+
+    fn relay_session(
+        relay: &pika_media::session::InMemoryRelay,
+        session: &CallSessionParams,
+    ) -> pika_media::session::MediaSession {
+        let mut media = pika_media::session::MediaSession::with_relay(
+            pika_media::session::SessionConfig {
+                moq_url: session.moq_url.clone(),
+                relay_auth: session.relay_auth.clone(),
+            },
+            relay.clone(),
+        );
+        media.connect().expect("connect relay");
+        media
+    }
+
+    fn audio_frame(
+        keys: &pika_media::crypto::FrameKeyMaterial,
+        seq: u64,
+        payload: &[u8],
+    ) -> pika_media::session::MediaFrame {
+        let info = pika_media::crypto::FrameInfo {
+            counter: seq as u32,
+            group_seq: seq,
+            frame_idx: 0,
+            keyframe: true,
+        };
+        pika_media::session::MediaFrame {
+            seq,
+            timestamp_us: seq * 20_000,
+            keyframe: true,
+            payload: pika_media::crypto::encrypt_frame(payload, keys, info).expect("encrypt"),
+        }
+    }
+
+    fn media_crypto_for(
+        mdk: &PikaMdk,
+        group_id: &GroupId,
+        local_pubkey_hex: &str,
+        peer_pubkey_hex: &str,
+        session: &CallSessionParams,
+    ) -> CallMediaCryptoContext {
+        CallWorkflowRuntime::new(mdk)
+            .derive_media_crypto(
+                GroupCallContext {
+                    mls_group_id: group_id,
+                    local_pubkey_hex,
+                },
+                "call-runtime-test",
+                session,
+                peer_pubkey_hex,
+            )
+            .expect("derive media crypto")
+    }
+
+    #[test]
+    fn media_keys_rotate_on_commit_without_dropping_in_flight_frames() {
+        use pika_media::crypto::{FrameKeyRing, KEY_ROTATION_RX_WINDOW};
+        use pika_media::tracks::TrackAddress;
+
+        let (inviter_mdk, invitee_mdk, group_id, inviter_keys, invitee_keys, session, _) =
+            make_group();
+        let inviter_hex = inviter_keys.public_key().to_hex();
+        let invitee_hex = invitee_keys.public_key().to_hex();
+        let inviter_crypto =
+            media_crypto_for(inviter_mdk, &group_id, &inviter_hex, &invitee_hex, &session);
+        let invitee_crypto =
+            media_crypto_for(invitee_mdk, &group_id, &invitee_hex, &inviter_hex, &session);
+
+        let relay = pika_media::session::InMemoryRelay::new();
+        let publisher = relay_session(&relay, &session);
+        let subscriber = relay_session(&relay, &session);
+        let track = TrackAddress {
+            broadcast_path: pika_media::tracks::broadcast_path(
+                &session.broadcast_base,
+                &inviter_crypto.local_participant_label,
+            )
+            .expect("broadcast path"),
+            track_name: "audio0".to_string(),
+        };
+        let rx = subscriber.subscribe(&track).expect("subscribe");
+        let mut ring = FrameKeyRing::new(invitee_crypto.rx_keys.clone());
+
+        // Sent before the commit and still queued when the receiver rekeys.
+        publisher
+            .publish(&track, audio_frame(&inviter_crypto.tx_keys, 0, b"before"))
+            .expect("publish old generation");
+
+        // A new member is added mid-call; both participants process the commit.
+        let peer_dir = tempfile::tempdir().expect("peer tempdir");
+        let peer_keys = Keys::generate();
+        let peer_mdk = open_mdk(peer_dir.path()).expect("open peer mdk");
+        let evolution = MembershipRuntime::new(inviter_mdk)
+            .prepare_add_members(&group_id, &[make_key_package_event(&peer_mdk, &peer_keys)])
+            .expect("prepare add member");
+        inviter_mdk
+            .merge_pending_commit(&group_id)
+            .expect("merge pending commit");
+        invitee_mdk
+            .process_message(&evolution.evolution_event)
+            .expect("process evolution");
+
+        let inviter_rotated = CallWorkflowRuntime::new(inviter_mdk)
+            .rotate_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &inviter_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &invitee_hex,
+                &inviter_crypto,
+            )
+            .expect("inviter rotate")
+            .expect("inviter epoch advanced");
+        let invitee_rotated = CallWorkflowRuntime::new(invitee_mdk)
+            .rotate_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &invitee_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &inviter_hex,
+                &invitee_crypto,
+            )
+            .expect("invitee rotate")
+            .expect("invitee epoch advanced");
+
+        assert_eq!(
+            inviter_rotated.tx_keys.epoch,
+            inviter_crypto.tx_keys.epoch + 1
+        );
+        assert_ne!(
+            inviter_rotated.tx_keys.generation,
+            inviter_crypto.tx_keys.generation
+        );
+        assert_eq!(
+            inviter_rotated.local_participant_label, inviter_crypto.local_participant_label,
+            "rotation must keep the broadcast path"
+        );
+        assert!(
+            CallWorkflowRuntime::new(invitee_mdk)
+                .rotate_media_crypto(
+                    GroupCallContext {
+                        mls_group_id: &group_id,
+                        local_pubkey_hex: &invitee_hex,
+                    },
+                    "call-runtime-test",
+                    &session,
+                    &inviter_hex,
+                    &invitee_rotated,
+                )
+                .expect("rotate again")
+                .is_none(),
+            "no rotation while the epoch is unchanged"
+        );
+
+        ring.rotate(invitee_rotated.rx_keys.clone(), KEY_ROTATION_RX_WINDOW);
+        publisher
+            .publish(&track, audio_frame(&inviter_rotated.tx_keys, 1, b"after"))
+            .expect("publish new generation");
+
+        let before = rx
+            .recv_timeout(std::time::Duration::from_secs(1))
+            .expect("old frame");
+        let after = rx
+            .recv_timeout(std::time::Duration::from_secs(1))
+            .expect("new frame");
+        assert_eq!(
+            ring.decrypt(&before.payload).expect("decrypt old").payload,
+            b"before"
+        );
+        assert_eq!(
+            ring.decrypt(&after.payload).expect("decrypt new").payload,
+            b"after"
+        );
+    }
+
+    #[test]
+    fn audio_reaches_receivers_that_have_not_processed_the_commit() {
+        use pika_media::crypto::{
+            FrameKeyRing, FrameKeySchedule, KEY_ROTATION_OVERLAP, KEY_ROTATION_RX_WINDOW,
+        };
+        use pika_media::tracks::TrackAddress;
+        use std::time::{Duration, Instant};
+
+        let (inviter_mdk, invitee_mdk, group_id, inviter_keys, invitee_keys, session, _) =
+            make_group();
+        let inviter_hex = inviter_keys.public_key().to_hex();
+        let invitee_hex = invitee_keys.public_key().to_hex();
+        let inviter_crypto =
+            media_crypto_for(inviter_mdk, &group_id, &inviter_hex, &invitee_hex, &session);
+        let invitee_crypto =
+            media_crypto_for(invitee_mdk, &group_id, &invitee_hex, &inviter_hex, &session);
+
+        let relay = pika_media::session::InMemoryRelay::new();
+        let publisher = relay_session(&relay, &session);
+        let subscriber = relay_session(&relay, &session);
+        let track = TrackAddress {
+            broadcast_path: pika_media::tracks::broadcast_path(
+                &session.broadcast_base,
+                &inviter_crypto.local_participant_label,
+            )
+            .expect("broadcast path"),
+            track_name: "audio0".to_string(),
+        };
+        let rx = subscriber.subscribe(&track).expect("subscribe");
+        let mut ring = FrameKeyRing::new(invitee_crypto.rx_keys.clone());
+        let mut tx_keys = FrameKeySchedule::new(inviter_crypto.tx_keys.clone());
+
+        // Only the sender has processed the commit so far.
+        let peer_dir = tempfile::tempdir().expect("peer tempdir");
+        let peer_keys = Keys::generate();
+        let peer_mdk = open_mdk(peer_dir.path()).expect("open peer mdk");
+        let evolution = MembershipRuntime::new(inviter_mdk)
+            .prepare_add_members(&group_id, &[make_key_package_event(&peer_mdk, &peer_keys)])
+            .expect("prepare add member");
+        inviter_mdk
+            .merge_pending_commit(&group_id)
+            .expect("merge pending commit");
+        let inviter_rotated = CallWorkflowRuntime::new(inviter_mdk)
+            .rotate_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &inviter_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &invitee_hex,
+                &inviter_crypto,
+            )
+            .expect("inviter rotate")
+            .expect("inviter epoch advanced");
+        let rotated_at = Instant::now();
+        tx_keys.rotate_at(
+            inviter_rotated.tx_keys.clone(),
+            KEY_ROTATION_OVERLAP,
+            rotated_at,
+        );
+
+        publisher
+            .publish(
+                &track,
+                audio_frame(
+                    tx_keys.current_at(rotated_at + Duration::from_millis(20)),
+                    0,
+                    b"mid-commit",
+                ),
+            )
+            .expect("publish during overlap");
+        let frame = rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("frame during overlap");
+        assert_eq!(
+            ring.decrypt(&frame.payload)
+                .expect("lagging receiver still hears the sender")
+                .payload,
+            b"mid-commit"
+        );
+
+        // The receiver catches up inside the overlap; the sender switches after it.
+        invitee_mdk
+            .process_message(&evolution.evolution_event)
+            .expect("process evolution");
+        let invitee_rotated = CallWorkflowRuntime::new(invitee_mdk)
+            .rotate_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &invitee_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &inviter_hex,
+                &invitee_crypto,
+            )
+            .expect("invitee rotate")
+            .expect("invitee epoch advanced");
+        ring.rotate(invitee_rotated.rx_keys.clone(), KEY_ROTATION_RX_WINDOW);
+
+        let late = audio_frame(tx_keys.current_at(rotated_at), 1, b"still-old");
+        let switched = audio_frame(
+            tx_keys.current_at(rotated_at + KEY_ROTATION_OVERLAP),
+            2,
+            b"new",
+        );
+        publisher.publish(&track, late).expect("publish old");
+        publisher.publish(&track, switched).expect("publish new");
+        for expected in [b"still-old".as_slice(), b"new".as_slice()] {
+            let frame = rx.recv_timeout(Duration::from_secs(1)).expect("frame");
+            assert_eq!(
+                ring.decrypt(&frame.payload).expect("decrypt").payload,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn removed_member_cannot_decrypt_frames_after_rekey() {
+        use pika_media::crypto::{FrameKeyRing, FrameKeySchedule, tx_rotation_delay};
+        use pika_media::tracks::TrackAddress;
+
+        let (inviter_mdk, invitee_mdk, group_id, inviter_keys, invitee_keys, session, _) =
+            make_group();
+        let inviter_hex = inviter_keys.public_key().to_hex();
+        let invitee_hex = invitee_keys.public_key().to_hex();
+        let inviter_crypto =
+            media_crypto_for(inviter_mdk, &group_id, &inviter_hex, &invitee_hex, &session);
+        let invitee_crypto =
+            media_crypto_for(invitee_mdk, &group_id, &invitee_hex, &inviter_hex, &session);
+
+        let relay = pika_media::session::InMemoryRelay::new();
+        let publisher = relay_session(&relay, &session);
+        let eavesdropper = relay_session(&relay, &session);
+        let track = TrackAddress {
+            broadcast_path: pika_media::tracks::broadcast_path(
+                &session.broadcast_base,
+                &inviter_crypto.local_participant_label,
+            )
+            .expect("broadcast path"),
+            track_name: "audio0".to_string(),
+        };
+        let rx = eavesdropper.subscribe(&track).expect("subscribe");
+        let mut ring = FrameKeyRing::new(invitee_crypto.rx_keys.clone());
+        let mut tx_keys = FrameKeySchedule::new(inviter_crypto.tx_keys.clone());
+
+        inviter_mdk
+            .remove_members(&group_id, &[invitee_keys.public_key()])
+            .expect("remove member");
+        inviter_mdk
+            .merge_pending_commit(&group_id)
+            .expect("merge removal");
+        let rotated = CallWorkflowRuntime::new(inviter_mdk)
+            .rotate_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group_id,
+                    local_pubkey_hex: &inviter_hex,
+                },
+                "call-runtime-test",
+                &session,
+                &invitee_hex,
+                &inviter_crypto,
+            )
+            .expect("rotate")
+            .expect("epoch advanced");
+        tx_keys.rotate(rotated.tx_keys.clone(), tx_rotation_delay(true));
+        publisher
+            .publish(&track, audio_frame(tx_keys.current(), 0, b"secret"))
+            .expect("publish");
+
+        // The removed member still reads the relay but never gets the new epoch,
+        // and the very first frame after the commit is already out of reach.
+        let frame = rx
+            .recv_timeout(std::time::Duration::from_secs(1))
+            .expect("frame relayed");
+        assert!(ring.decrypt(&frame.payload).is_err());
+        assert!(
+            CallWorkflowRuntime::new(invitee_mdk)
+                .rotate_media_crypto(
+                    GroupCallContext {
+                        mls_group_id: &group_id,
+                        local_pubkey_hex: &invitee_hex,
+                    },
+                    "call-runtime-test",
+                    &session,
+                    &inviter_hex,
+                    &invitee_crypto,
+                )
+                .expect("removed member rotate")
+                .is_none()
+        );
+    }
 }
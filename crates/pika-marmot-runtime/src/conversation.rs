@@ -5,10 +5,13 @@ use anyhow::{Context, Result, anyhow};
 use mdk_core::prelude::{GroupId, MessageProcessingResult};
 use mdk_storage_traits::{
     groups::{Pagination, types::Group},
-    messages::types::Message,
+    messages::{
+        MessageStorage,
+        types::{Message, MessageState},
+    },
 };
 use nostr_sdk::prelude::{
-    Alphabet, Client, Event, EventId, Filter, Kind, RelayUrl, SingleLetterTag,
+    Alphabet, Client, Event, EventId, Filter, Kind, RelayUrl, SingleLetterTag, Tags,
 };
 
 use crate::PikaMdk;
@@ -43,7 +46,15 @@ pub struct RuntimeGroupUpdate {
 pub enum ConversationEvent {
     Application(Box<RuntimeApplicationMessage>),
     GroupUpdate(RuntimeGroupUpdate),
-    UnresolvedGroup { mls_group_id: GroupId },
+    UnresolvedGroup {
+        mls_group_id: GroupId,
+    },
+    /// MDK refused a commit because its sender isn't an admin; the group
+    /// state is unchanged.
+    RejectedCommit {
+        nostr_group_id_hex: String,
+        reason: String,
+    },
     PreviouslyFailed,
 }
 
@@ -69,10 +80,17 @@ impl<'a> ConversationRuntime<'a> {
         if event.kind != Kind::MlsGroupMessage {
             return Ok(None);
         }
-        let result = self
-            .mdk
-            .process_message(event)
-            .context("process group message")?;
+        let result = match self.mdk.process_message(event) {
+            Ok(result) => result,
+            // MIP-03: only admins may commit membership or group-data changes.
+            Err(err @ mdk_core::Error::CommitFromNonAdmin) => {
+                return Ok(Some(ConversationEvent::RejectedCommit {
+                    nostr_group_id_hex: group_tag(event).unwrap_or_default(),
+                    reason: err.to_string(),
+                }));
+            }
+            Err(err) => return Err(anyhow::Error::new(err).context("process group message")),
+        };
         Ok(self.interpret_processing_result(result))
     }
 
@@ -167,6 +185,22 @@ impl<'a> ConversationRuntime<'a> {
             .context("get messages")
     }
 
+    /// Scrub a stored application message. The row stays so the wrapper is
+    /// still recognised as processed, but its content and tags are dropped and
+    /// it is marked deleted so hosts stop rendering it.
+    pub fn expire_message(&self, message: &Message) -> Result<()> {
+        let mut scrubbed = message.clone();
+        scrubbed.content.clear();
+        scrubbed.tags = Tags::new();
+        scrubbed.event.content.clear();
+        scrubbed.event.tags = Tags::new();
+        scrubbed.state = MessageState::Deleted;
+        self.mdk
+            .storage()
+            .save_message(scrubbed)
+            .context("expire message")
+    }
+
     pub async fn ingest_backlog_messages(
         &self,
         client: &Client,
@@ -220,6 +254,15 @@ impl<'a> ConversationRuntime<'a> {
     }
 }
 
+fn group_tag(event: &Event) -> Option<String> {
+    event
+        .tags
+        .iter()
+        .find(|tag| tag.single_letter_tag() == Some(SingleLetterTag::lowercase(Alphabet::H)))
+        .and_then(|tag| tag.content())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,4 +407,193 @@ mod tests {
                 .is_empty()
         );
     }
+
+    #[test]
+    fn expire_message_scrubs_stored_content() {
+        let inviter_dir = tempfile::tempdir().expect("inviter tempdir");
+        let invitee_dir = tempfile::tempdir().expect("invitee tempdir");
+        let inviter_keys = Keys::generate();
+        let invitee_keys = Keys::generate();
+        let inviter_mdk = open_test_mdk(&inviter_dir);
+        let invitee_mdk = open_test_mdk(&invitee_dir);
+        let invitee_kp = make_key_package_event(&invitee_mdk, &invitee_keys);
+        let config = NostrGroupConfigData::new(
+            "expire test".to_string(),
+            String::new(),
+            None,
+            None,
+            None,
+            vec![RelayUrl::parse("wss://test.relay").expect("relay url")],
+            vec![inviter_keys.public_key(), invitee_keys.public_key()],
+        );
+        let created = inviter_mdk
+            .create_group(&inviter_keys.public_key(), vec![invitee_kp], config)
+            .expect("create group");
+        let rumor = nostr_sdk::prelude::UnsignedEvent::new(
+            inviter_keys.public_key(),
+            Timestamp::from(123_u64),
+            Kind::ChatMessage,
+            [],
+            "secret".to_string(),
+        );
+        inviter_mdk
+            .create_message(&created.group.mls_group_id, rumor)
+            .expect("create message");
+
+        let runtime = ConversationRuntime::new(&inviter_mdk);
+        let nostr_group_id_hex = hex::encode(created.group.nostr_group_id);
+        let stored = runtime
+            .get_messages(&nostr_group_id_hex, None)
+            .expect("get messages");
+        assert_eq!(stored.len(), 1);
+
+        runtime.expire_message(&stored[0]).expect("expire message");
+
+        let after = runtime
+            .get_messages(&nostr_group_id_hex, None)
+            .expect("get messages");
+        assert!(after.iter().all(|m| m.content.is_empty()));
+        assert!(after.iter().all(|m| m.state == MessageState::Deleted));
+    }
+
+    /// A group where only the inviter is an admin and the invitee has joined.
+    fn make_joined_group(
+        inviter_dir: &tempfile::TempDir,
+        invitee_dir: &tempfile::TempDir,
+    ) -> (PikaMdk, PikaMdk, GroupId, Keys, Keys) {
+        let inviter_keys = Keys::generate();
+        let invitee_keys = Keys::generate();
+        let inviter_mdk = open_test_mdk(inviter_dir);
+        let invitee_mdk = open_test_mdk(invitee_dir);
+        let invitee_kp = make_key_package_event(&invitee_mdk, &invitee_keys);
+        let config = NostrGroupConfigData::new(
+            "admins only".to_string(),
+            String::new(),
+            None,
+            None,
+            None,
+            vec![RelayUrl::parse("wss://test.relay").expect("relay url")],
+            vec![inviter_keys.public_key()],
+        );
+        let created = inviter_mdk
+            .create_group(&inviter_keys.public_key(), vec![invitee_kp], config)
+            .expect("create group");
+        inviter_mdk
+            .merge_pending_commit(&created.group.mls_group_id)
+            .expect("merge initial commit");
+        let welcome_rumor = created
+            .welcome_rumors
+            .into_iter()
+            .next()
+            .expect("welcome rumor");
+        tokio::runtime::Runtime::new()
+            .expect("tokio runtime")
+            .block_on(async {
+                let wrapper = nostr_sdk::prelude::EventBuilder::gift_wrap(
+                    &inviter_keys,
+                    &invitee_keys.public_key(),
+                    welcome_rumor,
+                    [],
+                )
+                .await
+                .expect("build giftwrap");
+                crate::welcome::ingest_welcome_from_giftwrap(
+                    &invitee_mdk,
+                    &invitee_keys,
+                    &wrapper,
+                    |_| true,
+                )
+                .await
+                .expect("ingest welcome")
+                .expect("welcome should ingest");
+            });
+        let pending = invitee_mdk
+            .get_pending_welcomes(None)
+            .expect("get pending welcomes");
+        invitee_mdk
+            .accept_welcome(pending.first().expect("pending welcome"))
+            .expect("accept welcome");
+        (
+            inviter_mdk,
+            invitee_mdk,
+            created.group.mls_group_id,
+            inviter_keys,
+            invitee_keys,
+        )
+    }
+
+    #[test]
+    fn commits_from_non_admin_members_are_rejected() {
+        use mdk_core::prelude::NostrGroupDataUpdate;
+        use mdk_storage_traits::groups::GroupStorage;
+
+        for attempt in ["add", "remove", "rename"] {
+            let inviter_dir = tempfile::tempdir().expect("inviter tempdir");
+            let invitee_dir = tempfile::tempdir().expect("invitee tempdir");
+            let (inviter_mdk, invitee_mdk, group_id, inviter_keys, invitee_keys) =
+                make_joined_group(&inviter_dir, &invitee_dir);
+            let members_before = inviter_mdk.get_members(&group_id).expect("members");
+            let group_before = inviter_mdk
+                .get_group(&group_id)
+                .expect("get group")
+                .expect("group exists");
+
+            // MDK refuses to build admin-only commits for a non-admin, so the
+            // invitee's cached admin list claims otherwise. The MLS group
+            // context, which the inviter checks against, still only names the
+            // inviter.
+            let mut forged = invitee_mdk
+                .get_group(&group_id)
+                .expect("get invitee group")
+                .expect("invitee group exists");
+            forged.admin_pubkeys.insert(invitee_keys.public_key());
+            invitee_mdk
+                .storage()
+                .save_group(forged)
+                .expect("forge local admin list");
+
+            let commit = match attempt {
+                "add" => {
+                    let peer_dir = tempfile::tempdir().expect("peer tempdir");
+                    let peer_mdk = open_test_mdk(&peer_dir);
+                    let peer_kp = make_key_package_event(&peer_mdk, &Keys::generate());
+                    invitee_mdk
+                        .add_members(&group_id, &[peer_kp])
+                        .map(|result| result.evolution_event)
+                }
+                "remove" => invitee_mdk
+                    .remove_members(&group_id, &[inviter_keys.public_key()])
+                    .map(|result| result.evolution_event),
+                _ => invitee_mdk
+                    .update_group_data(
+                        &group_id,
+                        NostrGroupDataUpdate::new().name("hijacked".to_string()),
+                    )
+                    .map(|result| result.evolution_event),
+            };
+
+            let commit =
+                commit.unwrap_or_else(|e| panic!("{attempt}: build non-admin commit: {e}"));
+            let outcome = ConversationRuntime::new(&inviter_mdk)
+                .process_event(&commit)
+                .expect("process non-admin commit");
+            assert!(
+                matches!(outcome, Some(ConversationEvent::RejectedCommit { .. })),
+                "{attempt}: expected a rejected commit, got {outcome:?}"
+            );
+
+            let group_after = inviter_mdk
+                .get_group(&group_id)
+                .expect("get group")
+                .expect("group exists");
+            assert_eq!(
+                inviter_mdk.get_members(&group_id).expect("members"),
+                members_before,
+                "{attempt}: membership changed"
+            );
+            assert_eq!(group_after.name, group_before.name, "{attempt}");
+            assert_eq!(group_after.epoch, group_before.epoch, "{attempt}");
+            assert_eq!(group_after.admin_pubkeys, group_before.admin_pubkeys);
+        }
+    }
 }
@@ -0,0 +1,143 @@
+//! Shareable group invite links.
+//!
+//! A link names the admin who minted it and an opaque token:
+//! `<scheme>://join/<admin npub>/<token>`. Opening one gift-wraps a
+//! [`JOIN_REQUEST_KIND`] rumor carrying the token to the admin, whose client
+//! looks the token up and adds the requester to the group it was minted for.
+//! The group itself never appears in the link, so revoking the token is
+//! enough to make it useless.
+
+use nostr_sdk::prelude::{Kind, PublicKey, ToBech32};
+
+/// Gift-wrapped request to join the group behind an invite token.
+pub const JOIN_REQUEST_KIND_NUM: u16 = 1_012;
+pub const JOIN_REQUEST_KIND: Kind = Kind::Custom(JOIN_REQUEST_KIND_NUM);
+/// Join requests expire from relays, and are ignored by admins, after a week.
+pub const JOIN_REQUEST_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Invite tokens are 16 random bytes, hex encoded.
+pub const INVITE_TOKEN_LEN: usize = 32;
+
+const JOIN_HOST: &str = "join";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInviteLink {
+    pub admin: PublicKey,
+    pub token: String,
+}
+
+impl GroupInviteLink {
+    pub fn new(admin: PublicKey, token: impl Into<String>) -> Self {
+        Self {
+            admin,
+            token: token.into(),
+        }
+    }
+
+    pub fn to_url(&self, scheme: &str) -> String {
+        let npub = self
+            .admin
+            .to_bech32()
+            .unwrap_or_else(|_| self.admin.to_hex());
+        format!("{scheme}://{JOIN_HOST}/{npub}/{}", self.token)
+    }
+
+    /// Parses `<scheme>://join/<npub or hex>/<token>` for any scheme, so links
+    /// minted by dev and release builds are interchangeable.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (scheme, rest) = input.trim().split_once("://")?;
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return None;
+        }
+        let mut parts = rest.trim_end_matches('/').split('/');
+        if !parts.next()?.eq_ignore_ascii_case(JOIN_HOST) {
+            return None;
+        }
+        let admin = PublicKey::parse(parts.next()?).ok()?;
+        let token = parts.next()?.to_ascii_lowercase();
+        if parts.next().is_some() || !is_valid_invite_token(&token) {
+            return None;
+        }
+        Some(Self { admin, token })
+    }
+}
+
+pub fn is_valid_invite_token(token: &str) -> bool {
+    token.len() == INVITE_TOKEN_LEN && token.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Payload of a [`JOIN_REQUEST_KIND`] rumor.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct JoinRequest {
+    pub token: String,
+}
+
+impl JoinRequest {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+
+    pub fn to_content(&self) -> String {
+        serde_json::to_string(self).expect("serialize join request")
+    }
+
+    pub fn parse(content: &str) -> Option<Self> {
+        let request: Self = serde_json::from_str(content).ok()?;
+        is_valid_invite_token(&request.token).then_some(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nostr_sdk::prelude::Keys;
+
+    const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn invite_link_round_trips_across_schemes() {
+        let admin = Keys::generate().public_key();
+        let link = GroupInviteLink::new(admin, TOKEN);
+        let url = link.to_url("pika");
+        assert!(url.starts_with("pika://join/npub1"));
+        assert_eq!(GroupInviteLink::parse(&url), Some(link.clone()));
+        assert_eq!(
+            GroupInviteLink::parse(&format!("{}/", link.to_url("pikadev"))),
+            Some(link.clone())
+        );
+        assert_eq!(
+            GroupInviteLink::parse(&format!("pika://join/{}/{TOKEN}", admin.to_hex())),
+            Some(link)
+        );
+    }
+
+    #[test]
+    fn invite_link_rejects_malformed_input() {
+        let npub = Keys::generate().public_key().to_bech32().unwrap();
+        for input in [
+            format!("pika://chat/{npub}"),
+            format!("pika://join/{npub}"),
+            format!("pika://join/{npub}/abc"),
+            format!("pika://join/{npub}/{TOKEN}/extra"),
+            format!("pika://join/not-a-key/{TOKEN}"),
+            format!("join/{npub}/{TOKEN}"),
+        ] {
+            assert_eq!(GroupInviteLink::parse(&input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn join_request_requires_a_valid_token() {
+        let content = JoinRequest::new(TOKEN).to_content();
+        assert_eq!(JoinRequest::parse(&content), Some(JoinRequest::new(TOKEN)));
+        assert_eq!(JoinRequest::parse(r#"{"token":"nope"}"#), None);
+        assert_eq!(JoinRequest::parse("hello"), None);
+    }
+}
@@ -2,6 +2,7 @@ pub mod call;
 pub mod call_runtime;
 pub mod conversation;
 pub mod group;
+pub mod invite_link;
 pub mod key_package;
 pub mod media;
 pub mod membership;
@@ -9,6 +10,7 @@ pub mod message;
 pub mod outbound;
 pub mod relay;
 pub mod runtime;
+pub mod transfer;
 pub mod welcome;
 
 use std::collections::HashSet;
@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use base64::Engine;
 use mdk_core::encrypted_media::types::{
     EncryptedMediaUpload, MediaProcessingOptions, MediaReference,
 };
@@ -13,6 +14,18 @@ use crate::PikaMdk;
 
 pub const MAX_CHAT_MEDIA_BYTES: usize = 32 * 1024 * 1024;
 
+/// URL carried by an imeta tag built before its blob has been uploaded.
+/// [`MediaRuntime::finish_pending_upload`] swaps in the real Blossom URL.
+pub const PENDING_UPLOAD_URL: &str = "pending:upload";
+
+/// Largest poster JPEG carried inline in an imeta tag. The tag travels inside the
+/// MLS-encrypted message, so the poster needs no upload of its own but must stay small.
+pub const MAX_INLINE_THUMBNAIL_BYTES: usize = 24 * 1024;
+
+const INLINE_THUMBNAIL_PREFIX: &str = "data:image/jpeg;base64,";
+/// Voice-note waveform bars are amplitudes in `0..=MAX_WAVEFORM_LEVEL` (NIP-A0).
+pub const MAX_WAVEFORM_LEVEL: u8 = 100;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeMediaAttachment {
     pub url: String,
@@ -59,6 +72,29 @@ pub struct RuntimeDownloadedMedia {
     pub decrypted_data: Vec<u8>,
 }
 
+/// Preview metadata carried in an imeta tag next to the MDK-managed fields, so
+/// receivers can render a placeholder before downloading the blob.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaPreview {
+    pub blurhash: Option<String>,
+    /// Poster JPEG, at most [`MAX_INLINE_THUMBNAIL_BYTES`].
+    pub thumbnail_jpeg: Option<Vec<u8>>,
+    pub duration_ms: Option<u64>,
+    pub dimensions: Option<(u32, u32)>,
+    /// Voice-note amplitudes, each at most [`MAX_WAVEFORM_LEVEL`].
+    pub waveform: Option<Vec<u8>>,
+}
+
+impl MediaPreview {
+    pub fn is_empty(&self) -> bool {
+        self.blurhash.is_none()
+            && self.thumbnail_jpeg.is_none()
+            && self.duration_ms.is_none()
+            && self.dimensions.is_none()
+            && self.waveform.is_none()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolvedUploadMetadata {
     pub filename: String,
@@ -136,6 +172,41 @@ impl<'a> MediaRuntime<'a> {
         }
     }
 
+    /// Build the imeta tag for `upload` ahead of the Blossom upload.
+    ///
+    /// Unlike [`EncryptedMediaUpload`], the tag is plain data and can be journaled so an
+    /// interrupted upload can still be published after the app restarts.
+    pub fn pending_imeta_tag(&self, mls_group_id: &GroupId, upload: &EncryptedMediaUpload) -> Tag {
+        let manager = self.mdk.media_manager(mls_group_id.clone());
+        manager.create_imeta_tag(upload, PENDING_UPLOAD_URL)
+    }
+
+    /// Like [`Self::finish_upload`], but starting from a tag built by
+    /// [`Self::pending_imeta_tag`].
+    pub fn finish_pending_upload(
+        &self,
+        mls_group_id: &GroupId,
+        pending_imeta_tag: &Tag,
+        uploaded_blob: UploadedBlob,
+    ) -> Result<RuntimeMediaUploadResult> {
+        let imeta_tag = imeta_tag_with_url(pending_imeta_tag, &uploaded_blob.uploaded_url)?;
+        let manager = self.mdk.media_manager(mls_group_id.clone());
+        let reference = manager
+            .parse_imeta_tag(&imeta_tag)
+            .context("parse uploaded imeta tag")?;
+        let attachment = attachment_from_reference(
+            &reference,
+            Some(uploaded_blob.descriptor_sha256_hex.to_ascii_lowercase()),
+        );
+
+        Ok(RuntimeMediaUploadResult {
+            attachment,
+            reference,
+            imeta_tag,
+            uploaded_blob,
+        })
+    }
+
     pub async fn upload_media<T>(
         &self,
         signer: &T,
@@ -261,44 +332,18 @@ where
 
     let mut last_error: Option<String> = None;
     for server in blossom_servers {
-        let base_url = match Url::parse(server) {
-            Ok(url) => url,
-            Err(err) => {
-                last_error = Some(format!("{server}: {err}"));
-                continue;
-            }
-        };
-
-        let blossom = BlossomClient::new(base_url);
-        let descriptor = match blossom
-            .upload_blob(
-                encrypted_data.clone(),
-                Some(mime_type.to_string()),
-                None,
-                Some(signer),
-            )
-            .await
+        match put_blob_to_server(
+            signer,
+            encrypted_data.clone(),
+            mime_type,
+            expected_hash_hex,
+            server,
+        )
+        .await
         {
-            Ok(descriptor) => descriptor,
-            Err(err) => {
-                last_error = Some(format!("{server}: {err}"));
-                continue;
-            }
-        };
-
-        let descriptor_sha256_hex = descriptor.sha256.to_string();
-        if !descriptor_sha256_hex.eq_ignore_ascii_case(expected_hash_hex) {
-            last_error = Some(format!(
-                "{server}: uploaded hash mismatch (expected {expected_hash_hex}, got {descriptor_sha256_hex})"
-            ));
-            continue;
+            Ok(uploaded) => return Ok(uploaded),
+            Err(err) => last_error = Some(err),
         }
-
-        return Ok(UploadedBlob {
-            blossom_server: server.clone(),
-            uploaded_url: descriptor.url.to_string(),
-            descriptor_sha256_hex,
-        });
     }
 
     anyhow::bail!(
@@ -307,6 +352,171 @@ where
     )
 }
 
+/// Whole-blob BUD-02 `PUT /upload` to a single server. Errors are prefixed with the server.
+pub(crate) async fn put_blob_to_server<T>(
+    signer: &T,
+    encrypted_data: Vec<u8>,
+    mime_type: &str,
+    expected_hash_hex: &str,
+    server: &str,
+) -> std::result::Result<UploadedBlob, String>
+where
+    T: NostrSigner,
+{
+    let base_url = Url::parse(server).map_err(|err| format!("{server}: {err}"))?;
+    let blossom = BlossomClient::new(base_url);
+    let descriptor = blossom
+        .upload_blob(
+            encrypted_data,
+            Some(mime_type.to_string()),
+            None,
+            Some(signer),
+        )
+        .await
+        .map_err(|err| format!("{server}: {err}"))?;
+
+    let descriptor_sha256_hex = descriptor.sha256.to_string();
+    if !descriptor_sha256_hex.eq_ignore_ascii_case(expected_hash_hex) {
+        return Err(format!(
+            "{server}: uploaded hash mismatch (expected {expected_hash_hex}, got {descriptor_sha256_hex})"
+        ));
+    }
+
+    Ok(UploadedBlob {
+        blossom_server: server.to_string(),
+        uploaded_url: descriptor.url.to_string(),
+        descriptor_sha256_hex,
+    })
+}
+
+/// Replace the `url` entry of an imeta tag.
+pub fn imeta_tag_with_url(tag: &Tag, url: &str) -> Result<Tag> {
+    if !is_imeta_tag(tag) {
+        anyhow::bail!("not an imeta tag");
+    }
+    let mut replaced = false;
+    let values: Vec<String> = tag
+        .as_slice()
+        .iter()
+        .map(|value| {
+            if !replaced && value.starts_with("url ") {
+                replaced = true;
+                format!("url {url}")
+            } else {
+                value.clone()
+            }
+        })
+        .collect();
+    if !replaced {
+        anyhow::bail!("imeta tag has no url entry");
+    }
+    Tag::parse(values).context("rebuild imeta tag")
+}
+
+/// Add `blurhash`, `thumb`, `duration`, `dim` and `waveform` entries to an imeta tag, replacing
+/// any the tag already has for the fields `preview` sets.
+///
+/// The poster is inlined as a `data:` URL; one larger than
+/// [`MAX_INLINE_THUMBNAIL_BYTES`] is left out.
+pub fn imeta_tag_with_preview(tag: &Tag, preview: &MediaPreview) -> Result<Tag> {
+    if !is_imeta_tag(tag) {
+        anyhow::bail!("not an imeta tag");
+    }
+    let thumbnail = preview
+        .thumbnail_jpeg
+        .as_deref()
+        .filter(|jpeg| !jpeg.is_empty() && jpeg.len() <= MAX_INLINE_THUMBNAIL_BYTES);
+
+    let mut added = Vec::new();
+    if let Some(blurhash) = &preview.blurhash {
+        added.push(format!("blurhash {blurhash}"));
+    }
+    if let Some(jpeg) = thumbnail {
+        added.push(format!(
+            "thumb {INLINE_THUMBNAIL_PREFIX}{}",
+            base64::engine::general_purpose::STANDARD.encode(jpeg)
+        ));
+    }
+    if let Some(duration_ms) = preview.duration_ms {
+        added.push(format!("duration {:.3}", duration_ms as f64 / 1000.0));
+    }
+    if let Some((width, height)) = preview.dimensions {
+        added.push(format!("dim {width}x{height}"));
+    }
+    if let Some(waveform) = preview.waveform.as_deref().filter(|w| !w.is_empty()) {
+        let levels: Vec<String> = waveform
+            .iter()
+            .map(|level| (*level).min(MAX_WAVEFORM_LEVEL).to_string())
+            .collect();
+        added.push(format!("waveform {}", levels.join(" ")));
+    }
+    let replaced: Vec<&str> = added
+        .iter()
+        .filter_map(|entry| entry.split_once(' ').map(|(key, _)| key))
+        .collect();
+
+    let values: Vec<String> = tag
+        .as_slice()
+        .iter()
+        .enumerate()
+        .filter(|(i, value)| {
+            *i == 0
+                || !value
+                    .split_once(' ')
+                    .is_some_and(|(key, _)| replaced.contains(&key))
+        })
+        .map(|(_, value)| value.clone())
+        .chain(added.iter().cloned())
+        .collect();
+    Tag::parse(values).context("rebuild imeta tag")
+}
+
+/// Read back the preview fields written by [`imeta_tag_with_preview`]. Malformed
+/// entries are skipped, and `thumb` values other than inline JPEGs are ignored.
+pub fn imeta_preview(tag: &Tag) -> MediaPreview {
+    let mut preview = MediaPreview::default();
+    for value in tag.as_slice().iter().skip(1) {
+        let Some((key, rest)) = value.split_once(' ') else {
+            continue;
+        };
+        match key {
+            "blurhash" if !rest.is_empty() => preview.blurhash = Some(rest.to_string()),
+            "thumb" => {
+                preview.thumbnail_jpeg = rest
+                    .strip_prefix(INLINE_THUMBNAIL_PREFIX)
+                    .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+                    .filter(|jpeg| !jpeg.is_empty() && jpeg.len() <= MAX_INLINE_THUMBNAIL_BYTES);
+            }
+            "duration" => {
+                preview.duration_ms = rest
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                    .map(|secs| (secs * 1000.0).round() as u64);
+            }
+            "dim" => {
+                preview.dimensions = rest
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)));
+            }
+            "waveform" => {
+                preview.waveform = rest
+                    .split_whitespace()
+                    .map(|level| {
+                        level
+                            .parse::<u8>()
+                            .ok()
+                            .filter(|l| *l <= MAX_WAVEFORM_LEVEL)
+                    })
+                    .collect::<Option<Vec<u8>>>()
+                    .filter(|levels| !levels.is_empty());
+            }
+            _ => {}
+        }
+    }
+    preview
+}
+
 pub fn is_imeta_tag(tag: &Tag) -> bool {
     matches!(tag.kind(), TagKind::Custom(kind) if kind.as_ref() == "imeta")
 }
@@ -533,6 +743,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn imeta_tag_with_url_replaces_only_the_url_entry() {
+        let tag = Tag::parse([
+            "imeta",
+            "url pending:upload",
+            "m image/jpeg",
+            "filename photo.jpg",
+        ])
+        .unwrap();
+        let patched = imeta_tag_with_url(&tag, "https://blossom.example/abc").unwrap();
+        assert_eq!(
+            patched.as_slice(),
+            [
+                "imeta",
+                "url https://blossom.example/abc",
+                "m image/jpeg",
+                "filename photo.jpg",
+            ]
+        );
+
+        let not_imeta = Tag::parse(["p", "deadbeef"]).unwrap();
+        assert!(imeta_tag_with_url(&not_imeta, "https://blossom.example/abc").is_err());
+    }
+
+    #[test]
+    fn imeta_preview_round_trips_through_the_tag() {
+        let tag = Tag::parse([
+            "imeta",
+            "url https://blossom.example/abc",
+            "m video/mp4",
+            "dim 10x10",
+        ])
+        .unwrap();
+        let preview = MediaPreview {
+            blurhash: Some("LEHV6nWB2yk8pyo0adR*.7kCMdnj".to_string()),
+            thumbnail_jpeg: Some(vec![0xff, 0xd8, 0xff, 0xe0]),
+            duration_ms: Some(12_345),
+            dimensions: Some((1280, 720)),
+            waveform: None,
+        };
+        let with_preview = imeta_tag_with_preview(&tag, &preview).unwrap();
+
+        assert_eq!(
+            with_preview
+                .as_slice()
+                .iter()
+                .filter(|v| v.starts_with("dim "))
+                .count(),
+            1
+        );
+        assert_eq!(
+            with_preview.as_slice()[1],
+            "url https://blossom.example/abc"
+        );
+        assert_eq!(imeta_preview(&with_preview), preview);
+        assert!(imeta_preview(&tag).blurhash.is_none());
+    }
+
+    #[test]
+    fn imeta_preview_reads_voice_note_waveform() {
+        let tag = Tag::parse(["imeta", "url https://blossom.example/abc", "m audio/mp4"]).unwrap();
+        let preview = MediaPreview {
+            duration_ms: Some(4_200),
+            waveform: Some(vec![0, 12, 100, 57]),
+            ..MediaPreview::default()
+        };
+        let with_preview = imeta_tag_with_preview(&tag, &preview).unwrap();
+        assert!(
+            with_preview
+                .as_slice()
+                .contains(&"waveform 0 12 100 57".to_string())
+        );
+        assert_eq!(imeta_preview(&with_preview), preview);
+
+        let malformed =
+            Tag::parse(["imeta", "url https://blossom.example/abc", "waveform 3 101"]).unwrap();
+        assert!(imeta_preview(&malformed).waveform.is_none());
+    }
+
+    #[test]
+    fn imeta_tag_with_preview_drops_oversized_thumbnails() {
+        let tag = Tag::parse(["imeta", "url https://blossom.example/abc"]).unwrap();
+        let preview = MediaPreview {
+            thumbnail_jpeg: Some(vec![0; MAX_INLINE_THUMBNAIL_BYTES + 1]),
+            ..MediaPreview::default()
+        };
+        let with_preview = imeta_tag_with_preview(&tag, &preview).unwrap();
+        assert_eq!(with_preview, tag);
+    }
+
+    #[test]
+    fn pending_imeta_tag_finishes_like_a_direct_upload() {
+        let inviter_dir = tempfile::tempdir().expect("inviter tempdir");
+        let invitee_dir = tempfile::tempdir().expect("invitee tempdir");
+        let inviter_keys = Keys::generate();
+        let invitee_keys = Keys::generate();
+        let inviter_mdk = open_mdk(inviter_dir.path()).expect("open inviter mdk");
+        let invitee_mdk = open_mdk(invitee_dir.path()).expect("open invitee mdk");
+        let invitee_kp = make_key_package_event(&invitee_mdk, &invitee_keys);
+        let config = mdk_core::prelude::NostrGroupConfigData::new(
+            "pending imeta".to_string(),
+            String::new(),
+            None,
+            None,
+            None,
+            vec![RelayUrl::parse("wss://test.relay").expect("relay url")],
+            vec![inviter_keys.public_key(), invitee_keys.public_key()],
+        );
+        let created = inviter_mdk
+            .create_group(&inviter_keys.public_key(), vec![invitee_kp], config)
+            .expect("create group");
+        let mls_group_id = created.group.mls_group_id;
+
+        let runtime = MediaRuntime::new(&inviter_mdk);
+        let prepared = runtime
+            .prepare_upload(
+                &mls_group_id,
+                b"resumable media",
+                Some("text/plain"),
+                Some("resume.txt"),
+            )
+            .expect("prepare");
+        let blob = UploadedBlob {
+            blossom_server: "https://example.com".to_string(),
+            uploaded_url: "https://example.com/blob".to_string(),
+            descriptor_sha256_hex: hex::encode(prepared.upload.encrypted_hash),
+        };
+        let direct = runtime.finish_upload(&mls_group_id, &prepared.upload, blob.clone());
+
+        let pending_tag = runtime.pending_imeta_tag(&mls_group_id, &prepared.upload);
+        let finished = runtime
+            .finish_pending_upload(&mls_group_id, &pending_tag, blob)
+            .expect("finish pending upload");
+
+        assert_eq!(finished.imeta_tag, direct.imeta_tag);
+        assert_eq!(finished.attachment, direct.attachment);
+    }
+
     #[test]
     fn decrypt_downloaded_media_rejects_ciphertext_hash_mismatch() {
         let inviter_dir = tempfile::tempdir().expect("inviter tempdir");
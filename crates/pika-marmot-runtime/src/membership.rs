@@ -1,6 +1,8 @@
+use std::collections::BTreeSet;
 use std::future::Future;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, bail};
+use mdk_core::prelude::NostrGroupDataUpdate;
 use mdk_storage_traits::GroupId;
 use nostr_sdk::prelude::{Event, PublicKey, UnsignedEvent};
 
@@ -31,6 +33,12 @@ pub struct MembershipUpdateResult {
     pub welcome_delivery: Option<WelcomeDeliveryPlan>,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AdminChange {
+    Promote,
+    Demote,
+}
+
 #[derive(Debug, Clone)]
 pub enum EvolutionPublishStatus {
     Published,
@@ -74,6 +82,67 @@ impl<'a> MembershipRuntime<'a> {
         )
     }
 
+    /// Fails unless `pubkey` is in the group's admin set. Hosts call this
+    /// before building any commit that changes membership or group data.
+    pub fn ensure_admin(&self, mls_group_id: &GroupId, pubkey: &PublicKey) -> Result<()> {
+        if self.admins(mls_group_id)?.contains(pubkey) {
+            Ok(())
+        } else {
+            bail!("only group admins can do that")
+        }
+    }
+
+    /// Rewrite the admin set in the group-context extension. `actor` must
+    /// already be an admin, and a group never ends up with no admins.
+    pub fn prepare_admin_change(
+        &self,
+        mls_group_id: &GroupId,
+        actor: &PublicKey,
+        target: PublicKey,
+        change: AdminChange,
+    ) -> Result<PreparedMembershipEvolution> {
+        self.ensure_admin(mls_group_id, actor)?;
+        let mut admins = self.admins(mls_group_id)?;
+        match change {
+            AdminChange::Promote => {
+                let members = self
+                    .mdk
+                    .get_members(mls_group_id)
+                    .context("get members for admin change")?;
+                if !members.contains(&target) {
+                    bail!("not a member of this group");
+                }
+                if !admins.insert(target) {
+                    bail!("already an admin");
+                }
+            }
+            AdminChange::Demote => {
+                if !admins.remove(&target) {
+                    bail!("not an admin");
+                }
+                if admins.is_empty() {
+                    bail!("a group needs at least one admin");
+                }
+            }
+        }
+
+        let update = NostrGroupDataUpdate::new().admins(admins.into_iter().collect());
+        let result = self
+            .mdk
+            .update_group_data(mls_group_id, update)
+            .context("update group admins")?;
+        self.prepare_evolution(mls_group_id.clone(), result.evolution_event, None, vec![])
+    }
+
+    fn admins(&self, mls_group_id: &GroupId) -> Result<BTreeSet<PublicKey>> {
+        let group = self
+            .mdk
+            .get_group(mls_group_id)
+            .context("get group for admin check")?
+            .ok_or_else(|| anyhow!("group not found"))?;
+        Ok(group.admin_pubkeys.into_iter().collect())
+    }
+
     pub fn prepare_evolution(
         &self,
         mls_group_id: GroupId,
@@ -253,6 +322,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn admin_changes_require_an_admin_and_keep_one_admin() {
+        let (_inviter_dir, _invitee_dir, inviter_mdk, group_id, inviter_keys) = create_base_group();
+        let runtime = MembershipRuntime::new(&inviter_mdk);
+        let me = inviter_keys.public_key();
+        let invitee = inviter_mdk
+            .get_members(&group_id)
+            .expect("members")
+            .into_iter()
+            .find(|pubkey| *pubkey != me)
+            .expect("invitee");
+        let outsider = Keys::generate().public_key();
+
+        runtime
+            .ensure_admin(&group_id, &me)
+            .expect("inviter is admin");
+        assert!(runtime.ensure_admin(&group_id, &outsider).is_err());
+        assert!(
+            runtime
+                .prepare_admin_change(&group_id, &outsider, me, AdminChange::Demote)
+                .is_err()
+        );
+        assert!(
+            runtime
+                .prepare_admin_change(&group_id, &me, outsider, AdminChange::Promote)
+                .is_err()
+        );
+
+        let prepared = runtime
+            .prepare_admin_change(&group_id, &me, invitee, AdminChange::Demote)
+            .expect("demote invitee");
+        assert_eq!(prepared.evolution_event.kind, Kind::MlsGroupMessage);
+        assert!(prepared.added_pubkeys.is_empty());
+        let finalized = runtime.finalize_published_evolution(prepared);
+        assert!(finalized.merge_error.is_none());
+        assert!(runtime.ensure_admin(&group_id, &invitee).is_err());
+
+        // The last admin can't step down.
+        assert!(
+            runtime
+                .prepare_admin_change(&group_id, &me, me, AdminChange::Demote)
+                .is_err()
+        );
+
+        let prepared = runtime
+            .prepare_admin_change(&group_id, &me, invitee, AdminChange::Promote)
+            .expect("promote invitee");
+        runtime.finalize_published_evolution(prepared);
+        runtime
+            .ensure_admin(&group_id, &invitee)
+            .expect("invitee is admin again");
+    }
+
     #[tokio::test]
     async fn prepared_evolution_publish_status_tracks_shared_publish_outcome() {
         let (_inviter_dir, _invitee_dir, inviter_mdk, group_id, _keys) = create_base_group();
@@ -3,20 +3,62 @@ use nostr_sdk::prelude::{Kind, Tag, TagKind};
 
 pub const TYPING_INDICATOR_KIND_NUM: u16 = 20_067;
 pub const TYPING_INDICATOR_KIND: Kind = Kind::Custom(TYPING_INDICATOR_KIND_NUM);
+/// Ephemeral "read up to here" marker; the `e` tag names the newest rumor the sender has seen.
+pub const READ_RECEIPT_KIND_NUM: u16 = 20_068;
+pub const READ_RECEIPT_KIND: Kind = Kind::Custom(READ_RECEIPT_KIND_NUM);
 pub const CALL_SIGNAL_KIND_NUM: u16 = 10;
 pub const CALL_SIGNAL_KIND: Kind = Kind::Custom(CALL_SIGNAL_KIND_NUM);
 pub const HYPERNOTE_KIND: Kind = Kind::Custom(hn::HYPERNOTE_KIND);
 pub const HYPERNOTE_ACTION_RESPONSE_KIND: Kind = Kind::Custom(hn::HYPERNOTE_ACTION_RESPONSE_KIND);
+/// Replacement content for an earlier message; the `e` tag names the original rumor.
+pub const MESSAGE_EDIT_KIND_NUM: u16 = 1_010;
+pub const MESSAGE_EDIT_KIND: Kind = Kind::Custom(MESSAGE_EDIT_KIND_NUM);
+/// Deletions reuse NIP-09 event deletion, with an `e` tag naming the original rumor.
+pub const MESSAGE_DELETION_KIND: Kind = Kind::EventDeletion;
+/// Group-wide disappearing-message setting; the newest one in the group wins.
+pub const DISAPPEARING_TIMER_KIND_NUM: u16 = 1_011;
+pub const DISAPPEARING_TIMER_KIND: Kind = Kind::Custom(DISAPPEARING_TIMER_KIND_NUM);
+
+/// Payload of a [`DISAPPEARING_TIMER_KIND`] rumor. `expire_after_secs == 0`
+/// turns disappearing messages off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DisappearingTimerSetting {
+    pub expire_after_secs: u64,
+}
+
+impl DisappearingTimerSetting {
+    pub fn new(expire_after_secs: Option<u64>) -> Self {
+        Self {
+            expire_after_secs: expire_after_secs.unwrap_or(0),
+        }
+    }
+
+    pub fn expire_after_secs(self) -> Option<u64> {
+        (self.expire_after_secs > 0).then_some(self.expire_after_secs)
+    }
+
+    pub fn to_content(self) -> String {
+        serde_json::to_string(&self).expect("serialize disappearing timer")
+    }
+
+    pub fn parse(content: &str) -> Option<Self> {
+        serde_json::from_str(content).ok()
+    }
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum MessageClassification {
     TypingIndicator,
+    ReadReceipt,
     CallSignal,
     Chat,
     Reaction,
     Hypernote,
     HypernoteResponse,
     GroupProfile,
+    Edit,
+    Deletion,
+    DisappearingTimer,
 }
 
 impl MessageClassification {
@@ -25,29 +67,44 @@ impl MessageClassification {
     }
 
     pub fn increments_loaded(self) -> bool {
-        matches!(self, Self::Chat | Self::Reaction | Self::Hypernote)
+        matches!(
+            self,
+            Self::Chat | Self::Reaction | Self::Hypernote | Self::Edit | Self::Deletion
+        )
     }
 
     pub fn is_chat_visible(self) -> bool {
         matches!(
             self,
-            Self::Chat | Self::Reaction | Self::Hypernote | Self::HypernoteResponse
+            Self::Chat
+                | Self::Reaction
+                | Self::Hypernote
+                | Self::HypernoteResponse
+                | Self::Edit
+                | Self::Deletion
         )
     }
 }
 
+fn has_pika_marker<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> bool {
+    tags.into_iter().any(|tag| {
+        tag.kind() == TagKind::d()
+            && tag
+                .content()
+                .map(|content| content == "pika")
+                .unwrap_or(false)
+    })
+}
+
 pub fn is_pika_typing_indicator<'a>(
     content: &str,
     tags: impl IntoIterator<Item = &'a Tag>,
 ) -> bool {
-    content == "typing"
-        && tags.into_iter().any(|tag| {
-            tag.kind() == TagKind::d()
-                && tag
-                    .content()
-                    .map(|content| content == "pika")
-                    .unwrap_or(false)
-        })
+    content == "typing" && has_pika_marker(tags)
+}
+
+pub fn is_pika_read_receipt<'a>(content: &str, tags: impl IntoIterator<Item = &'a Tag>) -> bool {
+    content == "read" && has_pika_marker(tags)
 }
 
 pub fn classify_message<'a>(
@@ -60,12 +117,18 @@ pub fn classify_message<'a>(
         Kind::Reaction => Some(MessageClassification::Reaction),
         Kind::Custom(TYPING_INDICATOR_KIND_NUM) => is_pika_typing_indicator(content, tags)
             .then_some(MessageClassification::TypingIndicator),
+        Kind::Custom(READ_RECEIPT_KIND_NUM) => {
+            is_pika_read_receipt(content, tags).then_some(MessageClassification::ReadReceipt)
+        }
         Kind::Custom(CALL_SIGNAL_KIND_NUM) => Some(MessageClassification::CallSignal),
         Kind::Custom(hn::HYPERNOTE_KIND) => Some(MessageClassification::Hypernote),
         Kind::Custom(hn::HYPERNOTE_ACTION_RESPONSE_KIND) => {
             Some(MessageClassification::HypernoteResponse)
         }
         Kind::Metadata => Some(MessageClassification::GroupProfile),
+        Kind::Custom(MESSAGE_EDIT_KIND_NUM) => Some(MessageClassification::Edit),
+        Kind::EventDeletion => Some(MessageClassification::Deletion),
+        Kind::Custom(DISAPPEARING_TIMER_KIND_NUM) => Some(MessageClassification::DisappearingTimer),
         _ => None,
     }
 }
@@ -89,6 +152,13 @@ mod tests {
         assert!(!is_pika_typing_indicator("hello", pika_tags().iter()));
     }
 
+    #[test]
+    fn read_receipt_requires_pika_marker() {
+        assert!(is_pika_read_receipt("read", pika_tags().iter()));
+        assert!(!is_pika_read_receipt("read", Tags::new().iter()));
+        assert!(!is_pika_read_receipt("typing", pika_tags().iter()));
+    }
+
     #[test]
     fn classify_message_maps_shared_kinds() {
         assert_eq!(
@@ -119,6 +189,35 @@ mod tests {
             classify_message(TYPING_INDICATOR_KIND, "typing", pika_tags().iter()),
             Some(MessageClassification::TypingIndicator)
         );
+        assert_eq!(
+            classify_message(READ_RECEIPT_KIND, "read", pika_tags().iter()),
+            Some(MessageClassification::ReadReceipt)
+        );
+        assert_eq!(
+            classify_message(MESSAGE_EDIT_KIND, "fixed", Tags::new().iter()),
+            Some(MessageClassification::Edit)
+        );
+        assert_eq!(
+            classify_message(MESSAGE_DELETION_KIND, "", Tags::new().iter()),
+            Some(MessageClassification::Deletion)
+        );
+        assert_eq!(
+            classify_message(DISAPPEARING_TIMER_KIND, "{}", Tags::new().iter()),
+            Some(MessageClassification::DisappearingTimer)
+        );
+    }
+
+    #[test]
+    fn disappearing_timer_setting_round_trips_and_zero_means_off() {
+        let on = DisappearingTimerSetting::new(Some(3_600));
+        assert_eq!(DisappearingTimerSetting::parse(&on.to_content()), Some(on));
+        assert_eq!(on.expire_after_secs(), Some(3_600));
+
+        let off = DisappearingTimerSetting::new(None);
+        assert_eq!(off.to_content(), r#"{"expire_after_secs":0}"#);
+        assert_eq!(off.expire_after_secs(), None);
+
+        assert_eq!(DisappearingTimerSetting::parse("not json"), None);
     }
 
     #[test]
@@ -131,6 +230,10 @@ mod tests {
             classify_message(TYPING_INDICATOR_KIND, "typing", Tags::new().iter()),
             None
         );
+        assert_eq!(
+            classify_message(READ_RECEIPT_KIND, "read", Tags::new().iter()),
+            None
+        );
     }
 
     #[test]
@@ -139,6 +242,8 @@ mod tests {
         assert!(MessageClassification::Hypernote.increments_unread());
         assert!(!MessageClassification::Reaction.increments_unread());
         assert!(!MessageClassification::GroupProfile.increments_unread());
+        assert!(!MessageClassification::Edit.increments_unread());
+        assert!(!MessageClassification::Deletion.increments_unread());
 
         assert!(MessageClassification::Chat.increments_loaded());
         assert!(MessageClassification::Reaction.increments_loaded());
@@ -150,8 +255,15 @@ mod tests {
         assert!(MessageClassification::Reaction.is_chat_visible());
         assert!(MessageClassification::Hypernote.is_chat_visible());
         assert!(MessageClassification::HypernoteResponse.is_chat_visible());
+        assert!(MessageClassification::Edit.is_chat_visible());
+        assert!(MessageClassification::Deletion.is_chat_visible());
         assert!(!MessageClassification::TypingIndicator.is_chat_visible());
+        assert!(!MessageClassification::ReadReceipt.is_chat_visible());
+        assert!(!MessageClassification::ReadReceipt.increments_unread());
+        assert!(!MessageClassification::ReadReceipt.increments_loaded());
         assert!(!MessageClassification::CallSignal.is_chat_visible());
         assert!(!MessageClassification::GroupProfile.is_chat_visible());
+        assert!(!MessageClassification::DisappearingTimer.is_chat_visible());
+        assert!(!MessageClassification::DisappearingTimer.increments_unread());
     }
 }
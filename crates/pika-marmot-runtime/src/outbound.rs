@@ -48,6 +48,23 @@ pub enum OutboundConversationAction {
         created_at: Timestamp,
         expires_at: Timestamp,
     },
+    ReadReceipt {
+        target_event_id: EventId,
+        created_at: Timestamp,
+    },
+    Edit {
+        target_event_id: EventId,
+        content: String,
+        created_at: Timestamp,
+    },
+    Delete {
+        target_event_id: EventId,
+        created_at: Timestamp,
+    },
+    DisappearingTimer {
+        expire_after_secs: Option<u64>,
+        created_at: Timestamp,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -225,6 +242,62 @@ fn build_unsigned_action(
                 "typing",
             ),
         ),
+        OutboundConversationAction::ReadReceipt {
+            target_event_id,
+            created_at,
+        } => (
+            crate::message::READ_RECEIPT_KIND,
+            UnsignedEvent::new(
+                sender,
+                created_at,
+                crate::message::READ_RECEIPT_KIND,
+                [
+                    Tag::custom(TagKind::d(), ["pika"]),
+                    Tag::event(target_event_id),
+                ],
+                "read",
+            ),
+        ),
+        OutboundConversationAction::Edit {
+            target_event_id,
+            content,
+            created_at,
+        } => (
+            crate::message::MESSAGE_EDIT_KIND,
+            UnsignedEvent::new(
+                sender,
+                created_at,
+                crate::message::MESSAGE_EDIT_KIND,
+                [Tag::event(target_event_id)],
+                content,
+            ),
+        ),
+        OutboundConversationAction::Delete {
+            target_event_id,
+            created_at,
+        } => (
+            crate::message::MESSAGE_DELETION_KIND,
+            UnsignedEvent::new(
+                sender,
+                created_at,
+                crate::message::MESSAGE_DELETION_KIND,
+                [Tag::event(target_event_id)],
+                "",
+            ),
+        ),
+        OutboundConversationAction::DisappearingTimer {
+            expire_after_secs,
+            created_at,
+        } => (
+            crate::message::DISAPPEARING_TIMER_KIND,
+            UnsignedEvent::new(
+                sender,
+                created_at,
+                crate::message::DISAPPEARING_TIMER_KIND,
+                [],
+                crate::message::DisappearingTimerSetting::new(expire_after_secs).to_content(),
+            ),
+        ),
     }
 }
 
@@ -353,4 +426,58 @@ mod tests {
             .expect("prepare typing");
         assert_eq!(typing.kind, crate::message::TYPING_INDICATOR_KIND);
     }
+
+    #[test]
+    fn edit_and_delete_actions_reference_the_original_rumor() {
+        let target_event_id = EventId::all_zeros();
+        let (kind, edit) = build_unsigned_action(
+            Keys::generate().public_key(),
+            OutboundConversationAction::Edit {
+                target_event_id,
+                content: "fixed typo".to_string(),
+                created_at: Timestamp::from(126_u64),
+            },
+        );
+        assert_eq!(kind, crate::message::MESSAGE_EDIT_KIND);
+        assert_eq!(edit.content, "fixed typo");
+        assert_eq!(
+            edit.tags.iter().find(|t| t.kind() == TagKind::e()),
+            Some(&Tag::event(target_event_id))
+        );
+
+        let (kind, delete) = build_unsigned_action(
+            Keys::generate().public_key(),
+            OutboundConversationAction::Delete {
+                target_event_id,
+                created_at: Timestamp::from(127_u64),
+            },
+        );
+        assert_eq!(kind, crate::message::MESSAGE_DELETION_KIND);
+        assert!(delete.content.is_empty());
+        assert_eq!(
+            delete.tags.iter().find(|t| t.kind() == TagKind::e()),
+            Some(&Tag::event(target_event_id))
+        );
+    }
+
+    #[test]
+    fn read_receipt_action_is_a_marked_pointer_to_the_read_rumor() {
+        let target_event_id = EventId::all_zeros();
+        let (kind, receipt) = build_unsigned_action(
+            Keys::generate().public_key(),
+            OutboundConversationAction::ReadReceipt {
+                target_event_id,
+                created_at: Timestamp::from(128_u64),
+            },
+        );
+        assert_eq!(kind, crate::message::READ_RECEIPT_KIND);
+        assert!(crate::message::is_pika_read_receipt(
+            &receipt.content,
+            receipt.tags.iter()
+        ));
+        assert_eq!(
+            receipt.tags.iter().find(|t| t.kind() == TagKind::e()),
+            Some(&Tag::event(target_event_id))
+        );
+    }
 }
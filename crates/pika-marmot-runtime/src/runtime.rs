@@ -9,7 +9,7 @@ use mdk_storage_traits::messages::types::Message;
 use nostr_sdk::prelude::*;
 
 use crate::PikaMdk;
-use crate::call::{CallSessionParams, ParsedCallSignal};
+use crate::call::{CallMediaCryptoContext, CallSessionParams, ParsedCallSignal};
 use crate::call_runtime::{
     CallWorkflowRuntime, GroupCallContext, InboundCallSignalOutcome, InboundSignalContext,
     PendingIncomingCall, PreparedAcceptedCall, PreparedCallSignal,
@@ -19,7 +19,9 @@ use crate::media::{
     MediaRuntime, ParsedMediaAttachment, PreparedMediaUpload, RuntimeDownloadedMedia,
     RuntimeMediaUploadResult,
 };
-use crate::membership::{MembershipRuntime, MembershipUpdateResult, PreparedMembershipEvolution};
+use crate::membership::{
+    AdminChange, MembershipRuntime, MembershipUpdateResult, PreparedMembershipEvolution,
+};
 use crate::outbound::{
     OutboundConversationAction, OutboundConversationRuntime, PreparedConversationAction,
     PublishedConversationAction, ResolvedConversationTarget,
@@ -173,6 +175,10 @@ impl<'a> MarmotRuntime<'a> {
             .get_messages(nostr_group_id_hex, pagination)
     }
 
+    pub fn expire_message(&self, message: &Message) -> Result<()> {
+        self.conversation().expire_message(message)
+    }
+
     pub async fn ingest_group_backlog(
         &self,
         relay_urls: &[RelayUrl],
@@ -275,6 +281,24 @@ impl<'a> MarmotRuntime<'a> {
             .finish_upload(mls_group_id, upload, uploaded_blob)
     }
 
+    pub fn pending_imeta_tag(
+        &self,
+        mls_group_id: &GroupId,
+        upload: &mdk_core::encrypted_media::types::EncryptedMediaUpload,
+    ) -> Tag {
+        self.media().pending_imeta_tag(mls_group_id, upload)
+    }
+
+    pub fn finish_pending_upload(
+        &self,
+        mls_group_id: &GroupId,
+        pending_imeta_tag: &Tag,
+        uploaded_blob: crate::media::UploadedBlob,
+    ) -> Result<RuntimeMediaUploadResult> {
+        self.media()
+            .finish_pending_upload(mls_group_id, pending_imeta_tag, uploaded_blob)
+    }
+
     pub fn parse_message_attachments(&self, message: &Message) -> Vec<ParsedMediaAttachment> {
         self.media().parse_message_attachments(message)
     }
@@ -314,6 +338,21 @@ impl<'a> MarmotRuntime<'a> {
             .prepare_add_members(mls_group_id, key_package_events)
     }
 
+    pub fn ensure_group_admin(&self, mls_group_id: &GroupId, pubkey: &PublicKey) -> Result<()> {
+        self.membership().ensure_admin(mls_group_id, pubkey)
+    }
+
+    pub fn prepare_admin_change(
+        &self,
+        mls_group_id: &GroupId,
+        actor: &PublicKey,
+        target: PublicKey,
+        change: AdminChange,
+    ) -> Result<PreparedMembershipEvolution> {
+        self.membership()
+            .prepare_admin_change(mls_group_id, actor, target, change)
+    }
+
     pub fn prepare_evolution(
         &self,
         mls_group_id: GroupId,
@@ -377,6 +416,44 @@ impl<'a> MarmotRuntime<'a> {
         self.calls().prepare_end_signal(call_id, reason)
     }
 
+    pub fn prepare_call_directory_signal(
+        &self,
+        call_id: &str,
+        directory: &pika_media::directory::DirectoryMessage,
+    ) -> Result<PreparedCallSignal, String> {
+        self.calls().prepare_directory_signal(call_id, directory)
+    }
+
+    pub fn prepare_call_media_state_signal(
+        &self,
+        call_id: &str,
+        muted: bool,
+        camera_enabled: bool,
+    ) -> Result<PreparedCallSignal, String> {
+        self.calls()
+            .prepare_media_state_signal(call_id, muted, camera_enabled)
+    }
+
+    pub fn prepare_call_recording_signal(
+        &self,
+        call_id: &str,
+        recording: bool,
+    ) -> Result<PreparedCallSignal, String> {
+        self.calls().prepare_recording_signal(call_id, recording)
+    }
+
+    pub fn rotate_call_media_crypto(
+        &self,
+        group: GroupCallContext<'_>,
+        call_id: &str,
+        session: &CallSessionParams,
+        peer_pubkey_hex: &str,
+        current: &CallMediaCryptoContext,
+    ) -> Result<Option<CallMediaCryptoContext>, String> {
+        self.calls()
+            .rotate_media_crypto(group, call_id, session, peer_pubkey_hex, current)
+    }
+
     pub fn handle_inbound_call_signal(
         &self,
         ctx: InboundSignalContext<'_>,
@@ -0,0 +1,554 @@
+//! Chunked, resumable Blossom transfers.
+//!
+//! Downloads use HTTP range requests and append to a `.part` file, so an interrupted
+//! fetch picks up from the bytes already on disk — including after a restart.
+//!
+//! Uploads use a small offset-based extension of BUD-02:
+//!
+//! - `HEAD /upload/<sha256>` reports how many bytes the server holds in `Upload-Offset`
+//!   (404 when no upload is in progress).
+//! - `PATCH /upload/<sha256>` appends one chunk at `Upload-Offset` (with the full size in
+//!   `Upload-Length`). The server answers `204` with the new offset, or `200` with the
+//!   blob descriptor once the last byte has landed. A `409` carries the server's offset
+//!   so the client can resync.
+//!
+//! Servers without the extension answer `PATCH` with 404/405/501 and get a whole-blob
+//! `PUT /upload` instead.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use nostr_sdk::prelude::{EventBuilder, JsonUtil, Kind, NostrSigner, Tag, Timestamp};
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use serde::Deserialize;
+
+use crate::media::{UploadedBlob, put_blob_to_server};
+
+/// Bytes sent per `PATCH` or requested per ranged `GET`.
+pub const TRANSFER_CHUNK_BYTES: usize = 256 * 1024;
+
+pub const UPLOAD_OFFSET_HEADER: &str = "Upload-Offset";
+pub const UPLOAD_LENGTH_HEADER: &str = "Upload-Length";
+
+/// Consecutive attempts without forward progress before a transfer gives up.
+const MAX_STALLED_ATTEMPTS: u32 = 4;
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+const BLOSSOM_AUTH_KIND: u16 = 24242;
+const BLOSSOM_AUTH_TTL_SECS: u64 = 300;
+/// A cached upload authorization is re-signed once it is this close to expiring.
+const BLOSSOM_AUTH_REFRESH_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub transferred_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct DescriptorBody {
+    url: String,
+    sha256: String,
+}
+
+/// Signed `Authorization` header for one blob, shared by all of its chunks so
+/// remote signers are not asked to approve every `PATCH`.
+struct UploadAuthorization {
+    header: String,
+    expires_at: u64,
+}
+
+impl UploadAuthorization {
+    fn is_fresh(&self, now: u64) -> bool {
+        now + BLOSSOM_AUTH_REFRESH_SECS < self.expires_at
+    }
+}
+
+enum ChunkedUpload {
+    Done(UploadedBlob),
+    Unsupported,
+}
+
+/// Upload `encrypted_data` to the first server that accepts it, in
+/// [`TRANSFER_CHUNK_BYTES`] chunks, resuming from whatever the server already holds.
+pub async fn upload_blob_resumable<T, F>(
+    client: &reqwest::Client,
+    signer: &T,
+    encrypted_data: &[u8],
+    mime_type: &str,
+    expected_hash_hex: &str,
+    blossom_servers: &[String],
+    mut on_progress: F,
+) -> Result<UploadedBlob>
+where
+    T: NostrSigner,
+    F: FnMut(TransferProgress),
+{
+    if blossom_servers.is_empty() {
+        anyhow::bail!("no valid Blossom servers configured");
+    }
+
+    let total_bytes = encrypted_data.len() as u64;
+    let mut last_error: Option<String> = None;
+    for server in blossom_servers {
+        let chunked = upload_chunks(
+            client,
+            signer,
+            encrypted_data,
+            mime_type,
+            expected_hash_hex,
+            server,
+            &mut on_progress,
+        )
+        .await;
+        match chunked {
+            Ok(ChunkedUpload::Done(uploaded)) => return Ok(uploaded),
+            Ok(ChunkedUpload::Unsupported) => {
+                tracing::debug!(%server, "chunked upload unsupported, sending whole blob");
+                match put_blob_to_server(
+                    signer,
+                    encrypted_data.to_vec(),
+                    mime_type,
+                    expected_hash_hex,
+                    server,
+                )
+                .await
+                {
+                    Ok(uploaded) => {
+                        on_progress(TransferProgress {
+                            transferred_bytes: total_bytes,
+                            total_bytes,
+                        });
+                        return Ok(uploaded);
+                    }
+                    Err(err) => last_error = Some(err),
+                }
+            }
+            Err(err) => last_error = Some(format!("{server}: {err:#}")),
+        }
+    }
+
+    anyhow::bail!(
+        "blossom upload failed: {}",
+        last_error.unwrap_or_else(|| "unknown error".to_string())
+    )
+}
+
+async fn upload_chunks<T, F>(
+    client: &reqwest::Client,
+    signer: &T,
+    data: &[u8],
+    mime_type: &str,
+    expected_hash_hex: &str,
+    server: &str,
+    on_progress: &mut F,
+) -> Result<ChunkedUpload>
+where
+    T: NostrSigner,
+    F: FnMut(TransferProgress),
+{
+    let base = server.trim_end_matches('/');
+    let hash = expected_hash_hex.to_ascii_lowercase();
+    let total_bytes = data.len() as u64;
+
+    // The blob may already be there, e.g. the final chunk landed but its response was lost.
+    if let Ok(response) = client.head(format!("{base}/{hash}")).send().await
+        && response.status() == StatusCode::OK
+    {
+        on_progress(TransferProgress {
+            transferred_bytes: total_bytes,
+            total_bytes,
+        });
+        return Ok(ChunkedUpload::Done(UploadedBlob {
+            blossom_server: server.to_string(),
+            uploaded_url: format!("{base}/{hash}"),
+            descriptor_sha256_hex: hash,
+        }));
+    }
+
+    let session_url = format!("{base}/upload/{hash}");
+    let mut offset = upload_offset(client, &session_url)
+        .await
+        .unwrap_or(0)
+        .min(total_bytes);
+    let mut stalled = 0u32;
+    let mut authorization: Option<UploadAuthorization> = None;
+    loop {
+        on_progress(TransferProgress {
+            transferred_bytes: offset,
+            total_bytes,
+        });
+
+        let start = offset as usize;
+        let end = (start + TRANSFER_CHUNK_BYTES).min(data.len());
+        let now = Timestamp::now().as_u64();
+        let auth = match authorization.take().filter(|auth| auth.is_fresh(now)) {
+            Some(auth) => auth,
+            None => upload_authorization(signer, &hash).await?,
+        };
+        let sent = client
+            .patch(&session_url)
+            .header(AUTHORIZATION, authorization.insert(auth).header.clone())
+            .header(CONTENT_TYPE, mime_type)
+            .header(UPLOAD_OFFSET_HEADER, offset.to_string())
+            .header(UPLOAD_LENGTH_HEADER, total_bytes.to_string())
+            .body(data[start..end].to_vec())
+            .send()
+            .await;
+
+        let response = match sent {
+            Ok(response) => response,
+            Err(err) => {
+                stalled += 1;
+                if stalled >= MAX_STALLED_ATTEMPTS {
+                    return Err(err).context("send upload chunk");
+                }
+                tracing::debug!(%err, offset, "upload chunk interrupted, resyncing");
+                tokio::time::sleep(RETRY_BACKOFF * stalled).await;
+                if let Some(server_offset) = upload_offset(client, &session_url).await {
+                    if server_offset > offset {
+                        stalled = 0;
+                    }
+                    offset = server_offset.min(total_bytes);
+                }
+                continue;
+            }
+        };
+
+        match response.status() {
+            StatusCode::OK | StatusCode::CREATED => {
+                let descriptor: DescriptorBody =
+                    response.json().await.context("parse blob descriptor")?;
+                if !descriptor.sha256.eq_ignore_ascii_case(&hash) {
+                    anyhow::bail!(
+                        "uploaded hash mismatch (expected {hash}, got {})",
+                        descriptor.sha256
+                    );
+                }
+                on_progress(TransferProgress {
+                    transferred_bytes: total_bytes,
+                    total_bytes,
+                });
+                return Ok(ChunkedUpload::Done(UploadedBlob {
+                    blossom_server: server.to_string(),
+                    uploaded_url: descriptor.url,
+                    descriptor_sha256_hex: descriptor.sha256,
+                }));
+            }
+            StatusCode::NO_CONTENT => {
+                let next = header_u64(&response, UPLOAD_OFFSET_HEADER).unwrap_or(end as u64);
+                if next > offset {
+                    stalled = 0;
+                } else {
+                    stalled += 1;
+                    if stalled >= MAX_STALLED_ATTEMPTS {
+                        anyhow::bail!("upload stalled at byte {offset}");
+                    }
+                }
+                offset = next.min(total_bytes);
+            }
+            StatusCode::CONFLICT => {
+                stalled += 1;
+                if stalled >= MAX_STALLED_ATTEMPTS {
+                    anyhow::bail!("upload offset kept conflicting at byte {offset}");
+                }
+                offset = header_u64(&response, UPLOAD_OFFSET_HEADER)
+                    .context("409 without Upload-Offset")?
+                    .min(total_bytes);
+            }
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => {
+                return Ok(ChunkedUpload::Unsupported);
+            }
+            status if status.is_server_error() => {
+                stalled += 1;
+                if stalled >= MAX_STALLED_ATTEMPTS {
+                    anyhow::bail!("upload chunk failed: HTTP {status}");
+                }
+                tokio::time::sleep(RETRY_BACKOFF * stalled).await;
+                if let Some(server_offset) = upload_offset(client, &session_url).await {
+                    offset = server_offset.min(total_bytes);
+                }
+            }
+            status => anyhow::bail!("upload chunk rejected: HTTP {status}"),
+        }
+    }
+}
+
+/// Bytes the server holds for an in-progress upload, or `None` when it can't say.
+async fn upload_offset(client: &reqwest::Client, session_url: &str) -> Option<u64> {
+    let response = client.head(session_url).send().await.ok()?;
+    match response.status() {
+        StatusCode::NOT_FOUND => Some(0),
+        status if status.is_success() => Some(header_u64(&response, UPLOAD_OFFSET_HEADER)?),
+        _ => None,
+    }
+}
+
+/// BUD-02 upload authorization (kind 24242) for the blob with `hash_hex`.
+async fn upload_authorization<T>(signer: &T, hash_hex: &str) -> Result<UploadAuthorization>
+where
+    T: NostrSigner,
+{
+    let expires_at = Timestamp::now().as_u64() + BLOSSOM_AUTH_TTL_SECS;
+    let expiration = Timestamp::from_secs(expires_at);
+    let event = EventBuilder::new(Kind::from(BLOSSOM_AUTH_KIND), "Upload blob")
+        .tags([
+            Tag::hashtag("upload"),
+            Tag::parse(["x", hash_hex]).context("build x tag")?,
+            Tag::expiration(expiration),
+        ])
+        .sign(signer)
+        .await
+        .context("sign blossom authorization")?;
+    Ok(UploadAuthorization {
+        header: format!(
+            "Nostr {}",
+            base64::engine::general_purpose::STANDARD.encode(event.as_json())
+        ),
+        expires_at,
+    })
+}
+
+enum RangeOutcome {
+    Appended,
+    Complete,
+}
+
+/// Download `url` into `part_path` with ranged requests and return the full body.
+///
+/// Bytes already in `part_path` are kept, so calling this again after an interruption
+/// (or an app restart) resumes where the last attempt stopped. The caller owns
+/// `part_path` and should delete it once the data has been verified.
+pub async fn download_blob_resumable<F>(
+    client: &reqwest::Client,
+    url: &str,
+    part_path: &Path,
+    mut on_progress: F,
+) -> Result<Vec<u8>>
+where
+    F: FnMut(TransferProgress),
+{
+    if let Some(parent) = part_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create dir {}", parent.display()))?;
+    }
+
+    let mut total_bytes: Option<u64> = None;
+    let mut stalled = 0u32;
+    loop {
+        let offset = file_len(part_path);
+        if let Some(total) = total_bytes {
+            if offset == total {
+                break;
+            }
+            if offset > total {
+                truncate(part_path)?;
+                continue;
+            }
+        }
+
+        match fetch_range(
+            client,
+            url,
+            part_path,
+            offset,
+            &mut total_bytes,
+            &mut on_progress,
+        )
+        .await
+        {
+            Ok(RangeOutcome::Complete) => break,
+            Ok(RangeOutcome::Appended) => stalled = 0,
+            Err(err) => {
+                if file_len(part_path) > offset {
+                    stalled = 0;
+                } else {
+                    stalled += 1;
+                }
+                if stalled >= MAX_STALLED_ATTEMPTS {
+                    return Err(err);
+                }
+                tracing::debug!(%err, offset, "download interrupted, resuming");
+                tokio::time::sleep(RETRY_BACKOFF * stalled.max(1)).await;
+            }
+        }
+    }
+
+    std::fs::read(part_path).with_context(|| format!("read {}", part_path.display()))
+}
+
+async fn fetch_range<F>(
+    client: &reqwest::Client,
+    url: &str,
+    part_path: &Path,
+    offset: u64,
+    total_bytes: &mut Option<u64>,
+    on_progress: &mut F,
+) -> Result<RangeOutcome>
+where
+    F: FnMut(TransferProgress),
+{
+    let last = offset + TRANSFER_CHUNK_BYTES as u64 - 1;
+    let mut response = client
+        .get(url)
+        .header(RANGE, format!("bytes={offset}-{last}"))
+        .send()
+        .await
+        .with_context(|| format!("download {url}"))?;
+
+    match response.status() {
+        StatusCode::PARTIAL_CONTENT => {
+            let (start, total) = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_content_range)
+                .context("206 without a usable Content-Range")?;
+            if start != offset {
+                anyhow::bail!("server sent range starting at {start}, asked for {offset}");
+            }
+            *total_bytes = Some(total);
+            append_body(&mut response, part_path, offset, total, on_progress).await?;
+            Ok(RangeOutcome::Appended)
+        }
+        StatusCode::OK => {
+            // The server ignored the range; take the whole body from the start.
+            truncate(part_path)?;
+            let total = response.content_length().unwrap_or(0);
+            let written = append_body(&mut response, part_path, 0, total, on_progress).await?;
+            *total_bytes = Some(written);
+            Ok(RangeOutcome::Complete)
+        }
+        StatusCode::RANGE_NOT_SATISFIABLE => {
+            let total = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_unsatisfied_range);
+            if offset > 0 && total == Some(offset) {
+                *total_bytes = total;
+                return Ok(RangeOutcome::Complete);
+            }
+            // Whatever is on disk doesn't match the blob; start over.
+            truncate(part_path)?;
+            anyhow::bail!("download failed: HTTP {}", response.status())
+        }
+        status => anyhow::bail!("download failed: HTTP {status}"),
+    }
+}
+
+/// Stream the response body onto the end of `part_path`, reporting progress per chunk.
+/// Bytes are flushed as they arrive so an interrupted body still counts.
+async fn append_body<F>(
+    response: &mut reqwest::Response,
+    part_path: &Path,
+    offset: u64,
+    total: u64,
+    on_progress: &mut F,
+) -> Result<u64>
+where
+    F: FnMut(TransferProgress),
+{
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(part_path)
+        .with_context(|| format!("open {}", part_path.display()))?;
+    let mut written = offset;
+    while let Some(chunk) = response.chunk().await.context("read media response body")? {
+        file.write_all(&chunk)
+            .with_context(|| format!("write {}", part_path.display()))?;
+        file.flush()
+            .with_context(|| format!("flush {}", part_path.display()))?;
+        written += chunk.len() as u64;
+        on_progress(TransferProgress {
+            transferred_bytes: written,
+            total_bytes: total.max(written),
+        });
+    }
+    Ok(written)
+}
+
+fn header_u64(response: &reqwest::Response, name: &str) -> Option<u64> {
+    response
+        .headers()
+        .get(name)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn truncate(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("remove {}", path.display())),
+    }
+}
+
+/// Parse `bytes <start>-<end>/<total>` into `(start, total)`.
+fn parse_content_range(value: &str) -> Option<(u64, u64)> {
+    let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    let start: u64 = start.trim().parse().ok()?;
+    let end: u64 = end.trim().parse().ok()?;
+    let total: u64 = total.trim().parse().ok()?;
+    if start > end || end >= total {
+        return None;
+    }
+    Some((start, total))
+}
+
+/// Parse the `bytes */<total>` form sent with a 416.
+fn parse_unsatisfied_range(value: &str) -> Option<u64> {
+    value.trim().strip_prefix("bytes */")?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_range_parses_start_and_total() {
+        assert_eq!(parse_content_range("bytes 0-99/1000"), Some((0, 1000)));
+        assert_eq!(parse_content_range("bytes 900-999/1000"), Some((900, 1000)));
+    }
+
+    #[test]
+    fn content_range_rejects_malformed_values() {
+        assert_eq!(parse_content_range("bytes 10-5/1000"), None);
+        assert_eq!(parse_content_range("bytes 0-1000/1000"), None);
+        assert_eq!(parse_content_range("bytes */1000"), None);
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn upload_authorization_is_reused_until_close_to_expiry() {
+        let auth = UploadAuthorization {
+            header: "Nostr e30=".to_string(),
+            expires_at: 1_000 + BLOSSOM_AUTH_TTL_SECS,
+        };
+        assert!(auth.is_fresh(1_000));
+        assert!(auth.is_fresh(1_000 + BLOSSOM_AUTH_TTL_SECS - BLOSSOM_AUTH_REFRESH_SECS - 1));
+        assert!(!auth.is_fresh(1_000 + BLOSSOM_AUTH_TTL_SECS - BLOSSOM_AUTH_REFRESH_SECS));
+        assert!(!auth.is_fresh(1_000 + BLOSSOM_AUTH_TTL_SECS));
+    }
+
+    #[test]
+    fn unsatisfied_range_parses_total() {
+        assert_eq!(parse_unsatisfied_range("bytes */4096"), Some(4096));
+        assert_eq!(parse_unsatisfied_range("bytes 0-1/2"), None);
+    }
+}
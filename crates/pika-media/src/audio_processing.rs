@@ -0,0 +1,917 @@
+//! Capture-side voice processing for calls.
+//!
+//! Sits between the microphone and the Opus encoder and works on 48 kHz mono
+//! PCM: acoustic echo cancellation against the audio we just played, voice
+//! activity detection, noise suppression and automatic gain control.
+
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+/// Envelope block used by the echo delay estimator (1 ms at 48 kHz).
+const BLOCK_SAMPLES: usize = 48;
+/// Longest speaker-to-microphone delay the echo canceller searches for.
+const MAX_ECHO_DELAY_BLOCKS: usize = 500;
+/// Capture history correlated against the playback reference.
+const DELAY_WINDOW_BLOCKS: usize = 250;
+const DELAY_ESTIMATE_INTERVAL_BLOCKS: usize = 100;
+const DELAY_MIN_CORRELATION: f32 = 0.6;
+const DELAY_CONFIRMATIONS: u32 = 2;
+/// How much better another lag has to match before a locked delay moves.
+const DELAY_SWITCH_MARGIN: f32 = 0.1;
+/// Adaptive filter length (~10.7 ms), starting slightly ahead of the estimated
+/// delay so the direct path isn't cut off when the estimate rounds late.
+const ECHO_FILTER_TAPS: usize = 512;
+const ECHO_FILTER_LEAD: usize = 64;
+const ECHO_STEP_SIZE: f32 = 0.3;
+/// Error energy ratio (3 dB) at which one filter replaces the other.
+const ECHO_FILTER_COPY_RATIO: f32 = 0.5;
+/// Frames in a row the background filter has to win before it's trusted; a
+/// single frame can win by luck while it adapts through near-end speech.
+const ECHO_FILTER_COPY_FRAMES: u32 = 3;
+/// Below this reference power (summed over the filter taps) there is nothing to
+/// learn from, so the filter holds still.
+const ECHO_MIN_REFERENCE_POWER: f32 = ECHO_FILTER_TAPS as f32 * 100.0;
+const FAR_HISTORY_SAMPLES: usize =
+    (MAX_ECHO_DELAY_BLOCKS + DELAY_WINDOW_BLOCKS) * BLOCK_SAMPLES + ECHO_FILTER_TAPS;
+
+const NS_FFT_SIZE: usize = 1024;
+const NS_WINDOW: usize = 960;
+const NS_HOP: usize = NS_WINDOW / 2;
+const NS_BINS: usize = NS_FFT_SIZE / 2 + 1;
+/// Hops averaged into the initial noise estimate.
+const NS_INIT_HOPS: u32 = 10;
+const NS_NOISE_SMOOTHING: f32 = 0.9;
+/// Decision-directed a priori SNR smoothing.
+const NS_DD_ALPHA: f32 = 0.98;
+/// Strongest per-bin attenuation (~-16 dB); lower floors turn residual noise musical.
+const NS_MIN_GAIN: f32 = 0.15;
+
+/// Frame energy over the noise floor needed to call it speech (~9 dB).
+const VAD_SNR: f32 = 8.0;
+/// Quietest mean-square energy that can count as speech (RMS 60).
+const VAD_MIN_ENERGY: f32 = 3_600.0;
+const VAD_FLOOR_RISE: f32 = 1.02;
+const VAD_FLOOR_FALL: f32 = 0.2;
+/// Frames a voice decision is held so word gaps don't flap the detector.
+const VAD_HANGOVER_FRAMES: u32 = 10;
+
+/// Speech level the gain control steers toward (RMS, ~-21 dBFS).
+const AGC_TARGET_RMS: f32 = 3_000.0;
+const AGC_MIN_GAIN: f32 = 0.25;
+const AGC_MAX_GAIN: f32 = 8.0;
+const AGC_ATTACK: f32 = 0.2;
+const AGC_RELEASE: f32 = 0.02;
+const AGC_PEAK_LIMIT: f32 = 32_000.0;
+
+/// Which capture stages run. Voice activity detection always runs since the
+/// other stages lean on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioProcessingConfig {
+    pub echo_cancellation: bool,
+    pub noise_suppression: bool,
+    pub gain_control: bool,
+}
+
+impl Default for AudioProcessingConfig {
+    fn default() -> Self {
+        Self {
+            echo_cancellation: true,
+            noise_suppression: true,
+            gain_control: true,
+        }
+    }
+}
+
+pub struct AudioProcessor {
+    config: AudioProcessingConfig,
+    echo: EchoCanceller,
+    vad: VoiceActivityDetector,
+    noise: NoiseSuppressor,
+    gain: GainControl,
+    scratch: Vec<f32>,
+}
+
+impl std::fmt::Debug for AudioProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioProcessor")
+            .field("config", &self.config)
+            .field("echo_delay_samples", &self.echo.delay_samples)
+            .field("gain", &self.gain.gain)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for AudioProcessor {
+    fn default() -> Self {
+        Self::new(AudioProcessingConfig::default())
+    }
+}
+
+impl AudioProcessor {
+    pub fn new(config: AudioProcessingConfig) -> Self {
+        Self {
+            config,
+            echo: EchoCanceller::new(),
+            vad: VoiceActivityDetector::default(),
+            noise: NoiseSuppressor::new(),
+            gain: GainControl::default(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Records audio handed to the speaker as the echo reference. Call it once
+    /// per playout tick, with silence when nothing was played, so the reference
+    /// advances at the same rate as capture.
+    pub fn push_render_frame(&mut self, pcm: &[i16]) {
+        if self.config.echo_cancellation {
+            self.echo.push_render(pcm);
+        }
+    }
+
+    /// Processes one captured frame in place and returns whether it carries
+    /// voice. Noise suppression delays the signal by 10 ms.
+    pub fn process_capture(&mut self, pcm: &mut [i16]) -> bool {
+        let mut samples = std::mem::take(&mut self.scratch);
+        samples.clear();
+        samples.extend(pcm.iter().map(|s| *s as f32));
+
+        if self.config.echo_cancellation {
+            self.echo.process(&mut samples);
+        }
+        let voice = self.vad.update(&samples);
+        if self.config.noise_suppression {
+            self.noise.process(&mut samples, voice);
+        }
+        if self.config.gain_control {
+            // Hangover frames are word gaps; only adapt on frames that carry speech.
+            self.gain.process(&mut samples, self.vad.speech);
+        }
+
+        for (out, sample) in pcm.iter_mut().zip(&samples) {
+            *out = sample.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+        self.scratch = samples;
+        voice
+    }
+
+    /// Speaker-to-microphone delay the echo canceller has locked onto.
+    pub fn echo_delay_samples(&self) -> Option<usize> {
+        self.echo.delay_samples
+    }
+}
+
+/// Sums 48-sample blocks into mean absolute amplitudes.
+#[derive(Debug, Default)]
+struct BlockEnvelope {
+    blocks: VecDeque<f32>,
+    /// Absolute index one past the newest block.
+    end: u64,
+    sum: f32,
+    count: usize,
+}
+
+impl BlockEnvelope {
+    fn push(&mut self, sample: f32, capacity: usize) -> bool {
+        self.sum += sample.abs();
+        self.count += 1;
+        if self.count < BLOCK_SAMPLES {
+            return false;
+        }
+        self.blocks.push_back(self.sum / BLOCK_SAMPLES as f32);
+        while self.blocks.len() > capacity {
+            self.blocks.pop_front();
+        }
+        self.end += 1;
+        self.sum = 0.0;
+        self.count = 0;
+        true
+    }
+
+    fn start(&self) -> u64 {
+        self.end - self.blocks.len() as u64
+    }
+}
+
+/// Linear echo canceller: a delay estimate from envelope cross-correlation
+/// followed by an NLMS filter over the taps around that delay.
+///
+/// Two filters run side by side. The background one adapts on every sample and
+/// is copied into the foreground (which produces the output) only when it
+/// cancels more, so double talk can't wreck the converged echo path.
+struct EchoCanceller {
+    far: VecDeque<f32>,
+    /// Absolute index one past the newest reference sample.
+    far_end: u64,
+    /// Absolute index one past the newest capture sample.
+    near_end: u64,
+    far_envelope: BlockEnvelope,
+    near_envelope: BlockEnvelope,
+    blocks_since_estimate: usize,
+    delay_samples: Option<usize>,
+    candidate: Option<(usize, u32)>,
+    /// Taps stored oldest-first to line up with the reference slice.
+    background: Vec<f32>,
+    foreground: Vec<f32>,
+    background_wins: u32,
+    reference: Vec<f32>,
+}
+
+impl EchoCanceller {
+    fn new() -> Self {
+        Self {
+            far: VecDeque::with_capacity(FAR_HISTORY_SAMPLES),
+            far_end: 0,
+            near_end: 0,
+            far_envelope: BlockEnvelope::default(),
+            near_envelope: BlockEnvelope::default(),
+            blocks_since_estimate: 0,
+            delay_samples: None,
+            candidate: None,
+            background: vec![0.0; ECHO_FILTER_TAPS],
+            foreground: vec![0.0; ECHO_FILTER_TAPS],
+            background_wins: 0,
+            reference: Vec::new(),
+        }
+    }
+
+    fn push_render(&mut self, pcm: &[i16]) {
+        for sample in pcm {
+            let sample = *sample as f32;
+            self.far.push_back(sample);
+            if self.far.len() > FAR_HISTORY_SAMPLES {
+                self.far.pop_front();
+            }
+            self.far_end += 1;
+            self.far_envelope
+                .push(sample, MAX_ECHO_DELAY_BLOCKS + DELAY_WINDOW_BLOCKS);
+        }
+    }
+
+    fn far_sample(&self, index: i64) -> f32 {
+        let start = (self.far_end - self.far.len() as u64) as i64;
+        if index < start || index >= self.far_end as i64 {
+            return 0.0;
+        }
+        self.far[(index - start) as usize]
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        let frame_start = self.near_end;
+        for sample in samples.iter() {
+            if self.near_envelope.push(*sample, DELAY_WINDOW_BLOCKS) {
+                self.blocks_since_estimate += 1;
+            }
+        }
+        self.near_end += samples.len() as u64;
+        if self.blocks_since_estimate >= DELAY_ESTIMATE_INTERVAL_BLOCKS {
+            self.blocks_since_estimate = 0;
+            self.update_delay();
+        }
+
+        let Some(delay) = self.delay_samples else {
+            return;
+        };
+        let offset = delay.saturating_sub(ECHO_FILTER_LEAD) as i64;
+        // reference[n..n + TAPS] holds the far samples that can echo into capture sample n.
+        let first = frame_start as i64 - offset - (ECHO_FILTER_TAPS as i64 - 1);
+        let mut reference = std::mem::take(&mut self.reference);
+        reference.clear();
+        reference.extend(
+            (0..(samples.len() + ECHO_FILTER_TAPS - 1) as i64).map(|i| self.far_sample(first + i)),
+        );
+
+        let mut power: f32 = reference[..ECHO_FILTER_TAPS].iter().map(|x| x * x).sum();
+        let mut background_energy = 0.0f32;
+        let mut foreground_energy = 0.0f32;
+        for (n, sample) in samples.iter_mut().enumerate() {
+            let x = &reference[n..n + ECHO_FILTER_TAPS];
+            let near = *sample;
+            let background_error = near - dot(&self.background, x);
+            let foreground_error = near - dot(&self.foreground, x);
+            background_energy += background_error * background_error;
+            foreground_energy += foreground_error * foreground_error;
+
+            if power > ECHO_MIN_REFERENCE_POWER {
+                let step = ECHO_STEP_SIZE * background_error / (power + ECHO_MIN_REFERENCE_POWER);
+                for (w, x) in self.background.iter_mut().zip(x) {
+                    *w += step * x;
+                }
+            }
+            *sample = foreground_error;
+
+            if let Some(next) = reference.get(n + ECHO_FILTER_TAPS) {
+                power = (power + next * next - x[0] * x[0]).max(0.0);
+            }
+        }
+        self.reference = reference;
+
+        if background_energy < foreground_energy * ECHO_FILTER_COPY_RATIO {
+            self.background_wins += 1;
+            if self.background_wins >= ECHO_FILTER_COPY_FRAMES {
+                self.foreground.copy_from_slice(&self.background);
+                self.background_wins = 0;
+            }
+        } else {
+            self.background_wins = 0;
+            if background_energy * ECHO_FILTER_COPY_RATIO > foreground_energy {
+                // Diverged, most likely adapting through double talk.
+                self.background.copy_from_slice(&self.foreground);
+            }
+        }
+    }
+
+    fn update_delay(&mut self) {
+        let correlations = self.delay_correlations();
+        let Some(&(lag, correlation)) = correlations.iter().max_by(|a, b| a.1.total_cmp(&b.1))
+        else {
+            return;
+        };
+        if correlation < DELAY_MIN_CORRELATION {
+            return;
+        }
+        if let Some(current) = self.delay_samples {
+            // Near-end speech muddies the envelopes; only move off a delay that
+            // still explains the capture about as well as anything else.
+            let current_lag = current / BLOCK_SAMPLES;
+            let current_correlation = correlations
+                .iter()
+                .filter(|(l, _)| l.abs_diff(current_lag) <= 1)
+                .map(|(_, c)| *c)
+                .fold(f32::MIN, f32::max);
+            if correlation < current_correlation + DELAY_SWITCH_MARGIN {
+                self.candidate = None;
+                return;
+            }
+        }
+        let confirmations = match self.candidate {
+            Some((previous, count)) if previous.abs_diff(lag) <= 1 => count + 1,
+            _ => 1,
+        };
+        self.candidate = Some((lag, confirmations));
+        if confirmations < DELAY_CONFIRMATIONS {
+            return;
+        }
+        let delay = lag * BLOCK_SAMPLES;
+        let moved = self
+            .delay_samples
+            .is_none_or(|current| current.abs_diff(delay) > 2 * BLOCK_SAMPLES);
+        if moved {
+            self.delay_samples = Some(delay);
+            self.background.fill(0.0);
+            self.foreground.fill(0.0);
+            self.background_wins = 0;
+        }
+    }
+
+    /// Normalized correlation between the capture envelope and the playback
+    /// envelope at each lag (in blocks) the history covers.
+    fn delay_correlations(&self) -> Vec<(usize, f32)> {
+        let near = &self.near_envelope;
+        if near.blocks.len() < DELAY_WINDOW_BLOCKS {
+            return Vec::new();
+        }
+        let near_mean = near.blocks.iter().sum::<f32>() / DELAY_WINDOW_BLOCKS as f32;
+        let near_centered: Vec<f32> = near.blocks.iter().map(|v| v - near_mean).collect();
+        let near_var: f32 = near_centered.iter().map(|v| v * v).sum();
+        if near_var <= f32::EPSILON {
+            return Vec::new();
+        }
+
+        let far = &self.far_envelope;
+        let window_start = near.end - DELAY_WINDOW_BLOCKS as u64;
+        let mut correlations = Vec::new();
+        for lag in 0..=MAX_ECHO_DELAY_BLOCKS as u64 {
+            let Some(start) = window_start.checked_sub(lag) else {
+                break;
+            };
+            if start < far.start() {
+                break;
+            }
+            if start + DELAY_WINDOW_BLOCKS as u64 > far.end {
+                continue;
+            }
+            let offset = (start - far.start()) as usize;
+            let window = far.blocks.range(offset..offset + DELAY_WINDOW_BLOCKS);
+            let far_mean = window.clone().sum::<f32>() / DELAY_WINDOW_BLOCKS as f32;
+            let mut cross = 0.0f32;
+            let mut far_var = 0.0f32;
+            for (n, f) in near_centered.iter().zip(window) {
+                let f = f - far_mean;
+                cross += n * f;
+                far_var += f * f;
+            }
+            if far_var > f32::EPSILON {
+                correlations.push((lag as usize, cross / (near_var * far_var).sqrt()));
+            }
+        }
+        correlations
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    // Independent accumulators let the compiler vectorize the sum.
+    let mut acc = [0.0f32; 8];
+    for (a, b) in a.chunks_exact(8).zip(b.chunks_exact(8)) {
+        for ((acc, a), b) in acc.iter_mut().zip(a).zip(b) {
+            *acc += a * b;
+        }
+    }
+    acc.iter().sum()
+}
+
+/// Energy detector against a tracked noise floor, with hangover.
+#[derive(Debug, Default)]
+struct VoiceActivityDetector {
+    noise_floor: Option<f32>,
+    hangover: u32,
+    /// Whether the last frame itself was above the speech threshold.
+    speech: bool,
+}
+
+impl VoiceActivityDetector {
+    fn update(&mut self, samples: &[f32]) -> bool {
+        self.speech = false;
+        if samples.is_empty() {
+            return self.hangover > 0;
+        }
+        let energy = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+        let floor = match self.noise_floor {
+            None if energy > 0.0 => energy,
+            None => return false,
+            Some(floor) if energy < floor => floor + VAD_FLOOR_FALL * (energy - floor),
+            Some(floor) => (floor * VAD_FLOOR_RISE).min(energy),
+        };
+        self.noise_floor = Some(floor.max(1.0));
+
+        if energy > floor * VAD_SNR && energy > VAD_MIN_ENERGY {
+            self.speech = true;
+            self.hangover = VAD_HANGOVER_FRAMES;
+            true
+        } else if self.hangover > 0 {
+            self.hangover -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Short-time spectral Wiener filter with a decision-directed SNR estimate.
+/// Runs 960-sample sqrt-Hann windows at 50% overlap.
+struct NoiseSuppressor {
+    fft: Fft,
+    window: Vec<f32>,
+    analysis: Vec<f32>,
+    pending: Vec<f32>,
+    overlap: Vec<f32>,
+    output: VecDeque<f32>,
+    noise: Vec<f32>,
+    previous_clean: Vec<f32>,
+    hops: u32,
+    re: Vec<f32>,
+    im: Vec<f32>,
+}
+
+impl NoiseSuppressor {
+    fn new() -> Self {
+        Self {
+            fft: Fft::new(NS_FFT_SIZE),
+            window: (0..NS_WINDOW)
+                .map(|n| (PI * n as f32 / NS_WINDOW as f32).sin())
+                .collect(),
+            analysis: vec![0.0; NS_WINDOW],
+            pending: Vec::with_capacity(NS_HOP),
+            overlap: vec![0.0; NS_WINDOW - NS_HOP],
+            output: VecDeque::new(),
+            noise: vec![0.0; NS_BINS],
+            previous_clean: vec![0.0; NS_BINS],
+            hops: 0,
+            re: vec![0.0; NS_FFT_SIZE],
+            im: vec![0.0; NS_FFT_SIZE],
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32], voice: bool) {
+        for sample in samples.iter() {
+            self.pending.push(*sample);
+            if self.pending.len() == NS_HOP {
+                self.analysis.drain(..NS_HOP);
+                self.analysis.append(&mut self.pending);
+                self.process_hop(voice);
+            }
+        }
+        for sample in samples.iter_mut() {
+            *sample = self.output.pop_front().unwrap_or(0.0);
+        }
+    }
+
+    fn process_hop(&mut self, voice: bool) {
+        for (i, (re, im)) in self.re.iter_mut().zip(self.im.iter_mut()).enumerate() {
+            *re = match (self.analysis.get(i), self.window.get(i)) {
+                (Some(s), Some(w)) => s * w,
+                _ => 0.0,
+            };
+            *im = 0.0;
+        }
+        self.fft.transform(&mut self.re, &mut self.im, false);
+
+        let initializing = self.hops < NS_INIT_HOPS;
+        for k in 0..NS_BINS {
+            let power = self.re[k] * self.re[k] + self.im[k] * self.im[k];
+            let noise = &mut self.noise[k];
+            if initializing {
+                *noise += (power - *noise) / (self.hops + 1) as f32;
+            } else if !voice || power < *noise {
+                *noise = NS_NOISE_SMOOTHING * *noise + (1.0 - NS_NOISE_SMOOTHING) * power;
+            }
+            let noise = noise.max(1.0);
+            let posterior = power / noise;
+            let prior = NS_DD_ALPHA * self.previous_clean[k] / noise
+                + (1.0 - NS_DD_ALPHA) * (posterior - 1.0).max(0.0);
+            let gain = (prior / (1.0 + prior)).max(NS_MIN_GAIN);
+            self.previous_clean[k] = gain * gain * power;
+
+            self.re[k] *= gain;
+            self.im[k] *= gain;
+            if k > 0 && k < NS_FFT_SIZE / 2 {
+                self.re[NS_FFT_SIZE - k] *= gain;
+                self.im[NS_FFT_SIZE - k] *= gain;
+            }
+        }
+        self.hops = self.hops.saturating_add(1);
+
+        self.fft.transform(&mut self.re, &mut self.im, true);
+        let scale = 1.0 / NS_FFT_SIZE as f32;
+        for i in 0..NS_HOP {
+            let y = self.re[i] * scale * self.window[i];
+            self.output.push_back(self.overlap[i] + y);
+        }
+        for i in 0..NS_WINDOW - NS_HOP {
+            self.overlap[i] = self.re[NS_HOP + i] * scale * self.window[NS_HOP + i];
+        }
+    }
+}
+
+/// Radix-2 complex FFT with precomputed twiddles.
+struct Fft {
+    cos: Vec<f32>,
+    sin: Vec<f32>,
+    bit_reverse: Vec<usize>,
+}
+
+impl Fft {
+    fn new(size: usize) -> Self {
+        debug_assert!(size.is_power_of_two());
+        let bits = size.trailing_zeros();
+        Self {
+            cos: (0..size / 2)
+                .map(|k| (2.0 * PI * k as f32 / size as f32).cos())
+                .collect(),
+            sin: (0..size / 2)
+                .map(|k| -(2.0 * PI * k as f32 / size as f32).sin())
+                .collect(),
+            bit_reverse: (0..size)
+                .map(|i| i.reverse_bits() >> (usize::BITS - bits))
+                .collect(),
+        }
+    }
+
+    /// In-place transform; the inverse is left unscaled.
+    fn transform(&self, re: &mut [f32], im: &mut [f32], inverse: bool) {
+        let size = self.bit_reverse.len();
+        for (i, &j) in self.bit_reverse.iter().enumerate() {
+            if i < j {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+        let mut len = 2;
+        while len <= size {
+            let half = len / 2;
+            let stride = size / len;
+            for start in (0..size).step_by(len) {
+                for k in 0..half {
+                    let wr = self.cos[k * stride];
+                    let wi = if inverse {
+                        -self.sin[k * stride]
+                    } else {
+                        self.sin[k * stride]
+                    };
+                    let (a, b) = (start + k, start + k + half);
+                    let tr = re[b] * wr - im[b] * wi;
+                    let ti = re[b] * wi + im[b] * wr;
+                    re[b] = re[a] - tr;
+                    im[b] = im[a] - ti;
+                    re[a] += tr;
+                    im[a] += ti;
+                }
+            }
+            len *= 2;
+        }
+    }
+}
+
+/// Steers speech toward a fixed level. Gain only moves while voice is present,
+/// so pauses don't pump the background noise up.
+#[derive(Debug)]
+struct GainControl {
+    gain: f32,
+}
+
+impl Default for GainControl {
+    fn default() -> Self {
+        Self { gain: 1.0 }
+    }
+}
+
+impl GainControl {
+    fn process(&mut self, samples: &mut [f32], speech: bool) {
+        if samples.is_empty() {
+            return;
+        }
+        let previous = self.gain;
+        if speech {
+            let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+            if rms > 1.0 {
+                let desired = (AGC_TARGET_RMS / rms).clamp(AGC_MIN_GAIN, AGC_MAX_GAIN);
+                let rate = if desired < self.gain {
+                    AGC_ATTACK
+                } else {
+                    AGC_RELEASE
+                };
+                self.gain += rate * (desired - self.gain);
+            }
+        }
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let limit = if peak > 0.0 {
+            AGC_PEAK_LIMIT / peak
+        } else {
+            f32::MAX
+        };
+        self.gain = self.gain.min(limit);
+
+        // Ramp across the frame so gain changes don't click.
+        let start = previous.min(limit);
+        let step = (self.gain - start) / samples.len() as f32;
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample *= start + step * (i + 1) as f32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: usize = 960;
+
+    /// Deterministic white noise in [-amplitude, amplitude].
+    struct Noise(u64);
+
+    impl Noise {
+        fn next(&mut self, amplitude: f32) -> i16 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let unit = ((self.0 >> 33) as f32 / (1u64 << 31) as f32) * 2.0 - 1.0;
+            (unit * amplitude) as i16
+        }
+
+        fn frame(&mut self, amplitude: f32) -> Vec<i16> {
+            (0..FRAME).map(|_| self.next(amplitude)).collect()
+        }
+    }
+
+    fn tone(frame_index: usize, freq: f32, amplitude: f32) -> Vec<i16> {
+        (0..FRAME)
+            .map(|i| {
+                let t = (frame_index * FRAME + i) as f32 / 48_000.0;
+                ((2.0 * PI * freq * t).sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    /// Speech-like loudness contour for the far end. Irregular so the delay
+    /// estimator can't lock onto a repeat of it.
+    fn syllable_level(frame: usize) -> f32 {
+        [8_000.0, 1_500.0, 6_000.0, 500.0, 3_000.0, 7_000.0, 1_000.0][(frame / 9) % 7]
+    }
+
+    fn rms(pcm: &[i16]) -> f32 {
+        (pcm.iter().map(|s| (*s as f32).powi(2)).sum::<f32>() / pcm.len() as f32).sqrt()
+    }
+
+    fn only(config: AudioProcessingConfig) -> AudioProcessor {
+        AudioProcessor::new(config)
+    }
+
+    #[test]
+    fn fft_round_trips() {
+        let fft = Fft::new(16);
+        let input: Vec<f32> = (0..16).map(|i| (i as f32 * 0.7).sin() * 100.0).collect();
+        let mut re = input.clone();
+        let mut im = vec![0.0; 16];
+        fft.transform(&mut re, &mut im, false);
+        fft.transform(&mut re, &mut im, true);
+        for (out, original) in re.iter().zip(&input) {
+            assert!((out / 16.0 - original).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn echo_canceller_finds_delay_and_removes_echo() {
+        let mut processor = only(AudioProcessingConfig {
+            echo_cancellation: true,
+            noise_suppression: false,
+            gain_control: false,
+        });
+        let delay = 3_000;
+        let mut noise = Noise(7);
+        let mut played: Vec<i16> = Vec::new();
+        let mut input_energy = 0.0;
+        let mut output_energy = 0.0;
+        for frame in 0..250 {
+            let far = noise.frame(syllable_level(frame));
+            let mut near: Vec<i16> = (0..FRAME)
+                .map(|i| {
+                    let idx = (frame * FRAME + i) as isize - delay as isize;
+                    if idx < 0 {
+                        0
+                    } else {
+                        played[idx as usize] / 2
+                    }
+                })
+                .collect();
+            let before = rms(&near);
+            processor.process_capture(&mut near);
+            if frame >= 200 {
+                input_energy += before * before;
+                output_energy += rms(&near).powi(2);
+            }
+            processor.push_render_frame(&far);
+            played.extend_from_slice(&far);
+        }
+
+        let found = processor.echo_delay_samples().expect("delay locked");
+        assert!(found.abs_diff(delay) <= BLOCK_SAMPLES, "found {found}");
+        let erle_db = 10.0 * (input_energy / output_energy).log10();
+        assert!(erle_db > 20.0, "echo only reduced by {erle_db:.1} dB");
+    }
+
+    #[test]
+    fn echo_canceller_keeps_near_end_speech_during_double_talk() {
+        let mut processor = only(AudioProcessingConfig {
+            echo_cancellation: true,
+            noise_suppression: false,
+            gain_control: false,
+        });
+        let delay = 2_400;
+        let mut noise = Noise(11);
+        let mut played: Vec<i16> = Vec::new();
+        let mut speech_energy = 0.0;
+        let mut output_energy = 0.0;
+        for frame in 0..300 {
+            let far = noise.frame(syllable_level(frame));
+            let speech = if frame >= 200 {
+                tone(frame, 300.0, 4_000.0)
+            } else {
+                vec![0; FRAME]
+            };
+            let mut near: Vec<i16> = (0..FRAME)
+                .map(|i| {
+                    let idx = (frame * FRAME + i) as isize - delay as isize;
+                    let echo = if idx < 0 { 0 } else { played[idx as usize] / 2 };
+                    echo.saturating_add(speech[i])
+                })
+                .collect();
+            processor.process_capture(&mut near);
+            if frame >= 250 {
+                speech_energy += rms(&speech).powi(2);
+                output_energy += rms(&near).powi(2);
+            }
+            processor.push_render_frame(&far);
+            played.extend_from_slice(&far);
+        }
+
+        // Output is near-end speech plus residual echo, not cancelled speech.
+        let ratio_db = 10.0 * (output_energy / speech_energy).log10();
+        assert!(
+            ratio_db.abs() < 1.5,
+            "speech level moved by {ratio_db:.1} dB"
+        );
+    }
+
+    #[test]
+    fn noise_suppressor_attenuates_stationary_noise() {
+        let mut processor = only(AudioProcessingConfig {
+            echo_cancellation: false,
+            noise_suppression: true,
+            gain_control: false,
+        });
+        let mut noise = Noise(3);
+        let mut input_energy = 0.0;
+        let mut output_energy = 0.0;
+        for frame in 0..150 {
+            let mut pcm = noise.frame(600.0);
+            let before = rms(&pcm);
+            let voice = processor.process_capture(&mut pcm);
+            if frame >= 50 {
+                assert!(!voice, "noise flagged as voice in frame {frame}");
+                input_energy += before * before;
+                output_energy += rms(&pcm).powi(2);
+            }
+        }
+        let reduction_db = 10.0 * (input_energy / output_energy).log10();
+        assert!(
+            reduction_db > 10.0,
+            "noise only reduced by {reduction_db:.1} dB"
+        );
+    }
+
+    #[test]
+    fn noise_suppressor_passes_speech_over_noise() {
+        let mut processor = only(AudioProcessingConfig {
+            echo_cancellation: false,
+            noise_suppression: true,
+            gain_control: false,
+        });
+        let mut noise = Noise(5);
+        let mut tone_energy = 0.0;
+        let mut output_energy = 0.0;
+        for frame in 0..150 {
+            let speech = if frame >= 50 {
+                tone(frame, 440.0, 6_000.0)
+            } else {
+                vec![0; FRAME]
+            };
+            let mut pcm: Vec<i16> = noise
+                .frame(300.0)
+                .iter()
+                .zip(&speech)
+                .map(|(n, s)| n.saturating_add(*s))
+                .collect();
+            let voice = processor.process_capture(&mut pcm);
+            if frame >= 60 {
+                assert!(voice, "speech missed in frame {frame}");
+                tone_energy += rms(&speech).powi(2);
+                output_energy += rms(&pcm).powi(2);
+            }
+        }
+        let ratio_db = 10.0 * (output_energy / tone_energy).log10();
+        assert!(
+            ratio_db.abs() < 1.0,
+            "speech level moved by {ratio_db:.1} dB"
+        );
+    }
+
+    #[test]
+    fn gain_control_raises_quiet_speech_and_holds_in_silence() {
+        let mut processor = only(AudioProcessingConfig {
+            echo_cancellation: false,
+            noise_suppression: false,
+            gain_control: true,
+        });
+        let mut noise = Noise(9);
+        let mut last = Vec::new();
+        for frame in 0..300 {
+            // Bursts with gaps, like speech, after a moment of room noise.
+            let talking = frame >= 20 && ((frame - 20) / 10) % 2 == 0;
+            let mut pcm: Vec<i16> = noise
+                .frame(50.0)
+                .iter()
+                .zip(tone(frame, 200.0, if talking { 700.0 } else { 0.0 }))
+                .map(|(n, s)| n.saturating_add(s))
+                .collect();
+            processor.process_capture(&mut pcm);
+            if talking {
+                last = pcm;
+            }
+        }
+        let level = rms(&last);
+        assert!(
+            (level - AGC_TARGET_RMS).abs() < AGC_TARGET_RMS * 0.1,
+            "level {level}"
+        );
+
+        let gain = processor.gain.gain;
+        for _ in 0..50 {
+            processor.process_capture(&mut vec![0; FRAME]);
+        }
+        assert_eq!(processor.gain.gain, gain);
+    }
+
+    #[test]
+    fn gain_control_never_clips_loud_peaks() {
+        let mut processor = only(AudioProcessingConfig {
+            echo_cancellation: false,
+            noise_suppression: false,
+            gain_control: true,
+        });
+        processor.gain.gain = AGC_MAX_GAIN;
+        let mut loud = tone(100, 200.0, 30_000.0);
+        processor.process_capture(&mut loud);
+        let peak = loud.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+        assert!(peak as f32 <= AGC_PEAK_LIMIT + 1.0, "peak {peak}");
+    }
+}
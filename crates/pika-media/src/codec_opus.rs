@@ -1,44 +1,437 @@
-// TODO: Replace with real Opus encoding/decoding (e.g. via the `opus` or `audiopus` crate).
-// Currently this is a pass-through that ships raw PCM as little-endian i16 bytes.
-// At 48 kHz mono, each 20 ms frame is 960 samples × 2 bytes = 1920 bytes (~768 kbps),
-// which works for local/synthetic testing but wastes bandwidth over real MOQ relays
-// where Opus would compress to ~16–32 kbps.
+use std::sync::Mutex;
+
+use audiopus::coder::{Decoder, Encoder};
+use audiopus::{Application, Bitrate, Channels, SampleRate, Signal};
+
+use crate::tracks::TrackSpec;
+
+/// Largest packet libopus will ever emit for a single frame.
+const MAX_PACKET_BYTES: usize = 1275;
+/// Consecutive lost frames synthesized by Opus PLC before switching to comfort noise.
+/// PLC extrapolates the last pitch period and fades out; past ~100 ms it starts to
+/// sound robotic, so longer gaps get low-level noise instead of hard silence.
+const PLC_MAX_FRAMES: u32 = 5;
+/// Peak amplitude (i16 scale) of the comfort noise used for long gaps.
+const COMFORT_NOISE_AMPLITUDE: i32 = 48;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OpusPacket(pub Vec<u8>);
 
-#[derive(Debug, Clone, Default)]
-pub struct OpusCodec;
+/// Encoder settings for a single Opus stream.
+///
+/// Defaults target 48 kHz mono voice in 20 ms frames at 32 kbps, which matches
+/// `default_audio_track()` and keeps a call at or under ~32 kbps on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpusConfig {
+    pub sample_rate_hz: u32,
+    pub channels: u8,
+    pub frame_ms: u16,
+    pub bitrate_bps: u32,
+    /// 0 (fastest) through 10 (best quality).
+    pub complexity: u8,
+    /// Discontinuous transmission: near-empty packets during silence.
+    pub dtx: bool,
+    /// In-band forward error correction so a lost frame can be rebuilt from the next one.
+    pub inband_fec: bool,
+    /// Expected packet loss, used by the encoder to size the FEC payload.
+    pub expected_loss_percent: u8,
+}
+
+impl Default for OpusConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate_hz: 48_000,
+            channels: 1,
+            frame_ms: 20,
+            bitrate_bps: 32_000,
+            complexity: 5,
+            dtx: false,
+            inband_fec: true,
+            expected_loss_percent: 10,
+        }
+    }
+}
+
+impl OpusConfig {
+    pub fn for_track(track: &TrackSpec) -> Self {
+        Self {
+            sample_rate_hz: track.sample_rate,
+            channels: track.channels,
+            frame_ms: track.frame_ms,
+            ..Self::default()
+        }
+    }
+
+    /// Interleaved samples in one frame.
+    pub fn frame_samples(&self) -> usize {
+        (self.sample_rate_hz as usize * self.frame_ms as usize / 1000) * self.channels as usize
+    }
+
+    fn validate(&self) -> Result<(SampleRate, Channels), OpusCodecError> {
+        let sample_rate = match self.sample_rate_hz {
+            8_000 => SampleRate::Hz8000,
+            12_000 => SampleRate::Hz12000,
+            16_000 => SampleRate::Hz16000,
+            24_000 => SampleRate::Hz24000,
+            48_000 => SampleRate::Hz48000,
+            other => return Err(OpusCodecError::UnsupportedSampleRate(other)),
+        };
+        let channels = match self.channels {
+            1 => Channels::Mono,
+            2 => Channels::Stereo,
+            other => return Err(OpusCodecError::UnsupportedChannels(other)),
+        };
+        if !matches!(self.frame_ms, 10 | 20 | 40 | 60) {
+            return Err(OpusCodecError::UnsupportedFrameDuration(self.frame_ms));
+        }
+        if !(6_000..=510_000).contains(&self.bitrate_bps) {
+            return Err(OpusCodecError::InvalidBitrate(self.bitrate_bps));
+        }
+        if self.complexity > 10 {
+            return Err(OpusCodecError::InvalidComplexity(self.complexity));
+        }
+        Ok((sample_rate, channels))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpusCodecError {
+    UnsupportedSampleRate(u32),
+    UnsupportedChannels(u8),
+    UnsupportedFrameDuration(u16),
+    InvalidBitrate(u32),
+    InvalidComplexity(u8),
+    Opus(String),
+}
+
+impl std::fmt::Display for OpusCodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedSampleRate(hz) => write!(f, "unsupported opus sample rate: {hz}"),
+            Self::UnsupportedChannels(n) => write!(f, "unsupported opus channel count: {n}"),
+            Self::UnsupportedFrameDuration(ms) => {
+                write!(f, "unsupported opus frame duration: {ms}ms")
+            }
+            Self::InvalidBitrate(bps) => write!(f, "invalid opus bitrate: {bps}bps"),
+            Self::InvalidComplexity(c) => write!(f, "invalid opus complexity: {c}"),
+            Self::Opus(msg) => write!(f, "opus error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for OpusCodecError {}
+
+impl From<audiopus::Error> for OpusCodecError {
+    fn from(err: audiopus::Error) -> Self {
+        Self::Opus(err.to_string())
+    }
+}
+
+/// Stateful Opus encoder/decoder pair for one direction of a call.
+///
+/// Both halves keep inter-frame state, so a codec should be used for a single
+/// outgoing stream and a single incoming stream. The methods take `&self` so a
+/// worker can share one codec between its capture and playback paths.
+#[derive(Debug)]
+pub struct OpusCodec {
+    config: OpusConfig,
+    encoder: Mutex<Encoder>,
+    decoder: Mutex<Decoder>,
+}
+
+impl Default for OpusCodec {
+    fn default() -> Self {
+        Self::new(OpusConfig::default()).expect("default opus config is valid")
+    }
+}
 
 impl OpusCodec {
+    pub fn new(config: OpusConfig) -> Result<Self, OpusCodecError> {
+        let (sample_rate, channels) = config.validate()?;
+        let mut encoder = Encoder::new(sample_rate, channels, Application::Voip)?;
+        encoder.set_bitrate(Bitrate::BitsPerSecond(config.bitrate_bps as i32))?;
+        encoder.set_complexity(config.complexity)?;
+        encoder.set_signal(Signal::Voice)?;
+        encoder.set_dtx(config.dtx)?;
+        encoder.set_inband_fec(config.inband_fec)?;
+        encoder.set_packet_loss_perc(config.expected_loss_percent.min(100))?;
+        let decoder = Decoder::new(sample_rate, channels)?;
+        Ok(Self {
+            config,
+            encoder: Mutex::new(encoder),
+            decoder: Mutex::new(decoder),
+        })
+    }
+
+    pub fn for_track(track: &TrackSpec) -> Result<Self, OpusCodecError> {
+        Self::new(OpusConfig::for_track(track))
+    }
+
+    pub fn config(&self) -> &OpusConfig {
+        &self.config
+    }
+
+    /// Encode exactly one frame of interleaved PCM.
+    ///
+    /// Short input is padded with silence and long input is truncated to
+    /// `config().frame_samples()`, since Opus only accepts fixed frame sizes.
+    /// An encoder failure yields an empty packet, which the decoder conceals.
     pub fn encode_pcm_i16(&self, pcm: &[i16]) -> OpusPacket {
-        // Raw PCM pass-through — not actual Opus. See module-level TODO.
-        OpusPacket(
-            pcm.iter()
-                .flat_map(|sample| sample.to_le_bytes())
-                .collect::<Vec<u8>>(),
-        )
+        let frame_samples = self.config.frame_samples();
+        let mut frame = Vec::with_capacity(frame_samples);
+        frame.extend_from_slice(&pcm[..pcm.len().min(frame_samples)]);
+        frame.resize(frame_samples, 0);
+
+        let mut out = vec![0u8; MAX_PACKET_BYTES];
+        let encoder = self.encoder.lock().expect("opus encoder poisoned");
+        match encoder.encode(&frame, &mut out) {
+            Ok(len) => {
+                out.truncate(len);
+                OpusPacket(out)
+            }
+            Err(_) => OpusPacket(Vec::new()),
+        }
     }
 
+    /// Decode one packet into a full frame of interleaved PCM.
+    ///
+    /// Empty or corrupt packets are concealed by the decoder, so the output is
+    /// always one frame long and playout timing is preserved.
     pub fn decode_to_pcm_i16(&self, packet: &OpusPacket) -> Vec<i16> {
-        packet
-            .0
-            .chunks_exact(2)
-            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
-            .collect()
+        let frame_samples = self.config.frame_samples();
+        let mut pcm = vec![0i16; frame_samples];
+        let mut decoder = self.decoder.lock().expect("opus decoder poisoned");
+        let decoded = if packet.0.is_empty() {
+            None
+        } else {
+            decode_into(&mut decoder, Some(&packet.0), &mut pcm, false)
+        };
+        if decoded
+            .or_else(|| decode_into(&mut decoder, None, &mut pcm, false))
+            .is_none()
+        {
+            pcm.fill(0);
+        }
+        pcm
+    }
+
+    /// Rebuild a lost frame from the in-band FEC data carried by the packet
+    /// that follows it. Falls back to PLC when the packet has no FEC payload.
+    pub fn recover_from_next_pcm_i16(&self, next: &OpusPacket) -> Vec<i16> {
+        let mut pcm = vec![0i16; self.config.frame_samples()];
+        let mut decoder = self.decoder.lock().expect("opus decoder poisoned");
+        let recovered = if next.0.is_empty() {
+            None
+        } else {
+            decode_into(&mut decoder, Some(&next.0), &mut pcm, true)
+        };
+        if recovered
+            .or_else(|| decode_into(&mut decoder, None, &mut pcm, false))
+            .is_none()
+        {
+            pcm.fill(0);
+        }
+        pcm
+    }
+
+    /// Synthesize a frame for a gap with nothing to decode.
+    ///
+    /// `run` is the number of consecutive concealed frames including this one.
+    /// Short gaps use Opus packet-loss concealment; longer gaps get comfort
+    /// noise so the listener does not hear dead air.
+    pub fn conceal_pcm_i16(&self, run: u32) -> Vec<i16> {
+        let frame_samples = self.config.frame_samples();
+        if run <= PLC_MAX_FRAMES {
+            let mut pcm = vec![0i16; frame_samples];
+            let mut decoder = self.decoder.lock().expect("opus decoder poisoned");
+            if decode_into(&mut decoder, None, &mut pcm, false).is_some() {
+                return pcm;
+            }
+        }
+        comfort_noise(frame_samples, run)
     }
 }
 
+/// Deterministic low-level white noise; seeded by `run` so repeated calls vary.
+fn comfort_noise(samples: usize, run: u32) -> Vec<i16> {
+    let mut state = 0x9E37_79B9u32 ^ run.wrapping_mul(0x85EB_CA6B);
+    (0..samples)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let span = (COMFORT_NOISE_AMPLITUDE * 2 + 1) as u32;
+            ((state % span) as i32 - COMFORT_NOISE_AMPLITUDE) as i16
+        })
+        .collect()
+}
+
+fn decode_into(
+    decoder: &mut Decoder,
+    packet: Option<&[u8]>,
+    pcm: &mut [i16],
+    fec: bool,
+) -> Option<usize> {
+    let packet = match packet {
+        Some(bytes) => Some(bytes.try_into().ok()?),
+        None => None,
+    };
+    let output = pcm.try_into().ok()?;
+    decoder.decode(packet, output, fec).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tracks::default_audio_track;
+
+    fn sine_frame(codec: &OpusCodec, frame_idx: usize) -> Vec<i16> {
+        let n = codec.config().frame_samples();
+        (0..n)
+            .map(|i| {
+                let t = (frame_idx * n + i) as f32 / codec.config().sample_rate_hz as f32;
+                ((t * 440.0 * std::f32::consts::TAU).sin() * 8_000.0) as i16
+            })
+            .collect()
+    }
+
+    fn rms(pcm: &[i16]) -> f64 {
+        let sum: f64 = pcm.iter().map(|s| (*s as f64) * (*s as f64)).sum();
+        (sum / pcm.len().max(1) as f64).sqrt()
+    }
+
+    #[test]
+    fn encodes_well_below_raw_pcm_size() {
+        let codec = OpusCodec::default();
+        let mut total = 0usize;
+        for i in 0..50 {
+            let packet = codec.encode_pcm_i16(&sine_frame(&codec, i));
+            assert!(!packet.0.is_empty());
+            total += packet.0.len();
+        }
+        // 50 frames = 1 second; raw PCM would be 96_000 bytes.
+        let kbps = total * 8 / 1000;
+        assert!(kbps <= 48, "expected <=48kbps, got {kbps}kbps");
+    }
+
+    #[test]
+    fn roundtrip_preserves_signal_energy() {
+        let codec = OpusCodec::default();
+        let mut input_rms = 0.0;
+        let mut output_rms = 0.0;
+        for i in 0..50 {
+            let pcm = sine_frame(&codec, i);
+            let decoded = codec.decode_to_pcm_i16(&codec.encode_pcm_i16(&pcm));
+            assert_eq!(decoded.len(), codec.config().frame_samples());
+            // Skip warm-up frames while the encoder converges.
+            if i >= 10 {
+                input_rms += rms(&pcm);
+                output_rms += rms(&decoded);
+            }
+        }
+        let ratio = output_rms / input_rms;
+        assert!((0.7..1.3).contains(&ratio), "energy ratio {ratio}");
+    }
+
+    #[test]
+    fn short_input_is_padded_to_a_full_frame() {
+        let codec = OpusCodec::default();
+        let packet = codec.encode_pcm_i16(&[1, -1]);
+        assert!(!packet.0.is_empty());
+        assert_eq!(
+            codec.decode_to_pcm_i16(&packet).len(),
+            codec.config().frame_samples()
+        );
+    }
+
+    #[test]
+    fn empty_or_corrupt_packet_decodes_to_one_frame() {
+        let codec = OpusCodec::default();
+        let frame_samples = codec.config().frame_samples();
+        assert_eq!(
+            codec.decode_to_pcm_i16(&OpusPacket(Vec::new())).len(),
+            frame_samples
+        );
+        assert_eq!(
+            codec.decode_to_pcm_i16(&OpusPacket(vec![0xff; 3])).len(),
+            frame_samples
+        );
+    }
+
+    #[test]
+    fn config_follows_track_spec_and_rejects_invalid_values() {
+        let config = OpusConfig::for_track(&default_audio_track());
+        assert_eq!(config.frame_samples(), 960);
+        assert!(OpusCodec::new(config).is_ok());
+
+        let bad_rate = OpusConfig {
+            sample_rate_hz: 44_100,
+            ..config
+        };
+        assert_eq!(
+            OpusCodec::new(bad_rate).unwrap_err(),
+            OpusCodecError::UnsupportedSampleRate(44_100)
+        );
+        let bad_complexity = OpusConfig {
+            complexity: 11,
+            ..config
+        };
+        assert!(OpusCodec::new(bad_complexity).is_err());
+    }
+
+    #[test]
+    fn dtx_shrinks_silent_frames() {
+        let codec = OpusCodec::new(OpusConfig {
+            dtx: true,
+            ..OpusConfig::default()
+        })
+        .expect("codec");
+        let silence = vec![0i16; codec.config().frame_samples()];
+        let mut last = 0usize;
+        for _ in 0..30 {
+            last = codec.encode_pcm_i16(&silence).0.len();
+        }
+        assert!(last <= 3, "expected DTX packet, got {last} bytes");
+    }
+
+    #[test]
+    fn conceals_short_gaps_with_plc_and_long_gaps_with_comfort_noise() {
+        let codec = OpusCodec::default();
+        for i in 0..20 {
+            let _ = codec.decode_to_pcm_i16(&codec.encode_pcm_i16(&sine_frame(&codec, i)));
+        }
+        let plc = codec.conceal_pcm_i16(1);
+        assert_eq!(plc.len(), codec.config().frame_samples());
+        assert!(
+            rms(&plc) > 100.0,
+            "PLC should extend the tone, rms {}",
+            rms(&plc)
+        );
+
+        let noise = codec.conceal_pcm_i16(PLC_MAX_FRAMES + 1);
+        assert_eq!(noise.len(), codec.config().frame_samples());
+        assert!(noise
+            .iter()
+            .all(|s| s.unsigned_abs() as i32 <= COMFORT_NOISE_AMPLITUDE));
+        assert!(noise.iter().any(|s| *s != 0));
+    }
 
     #[test]
-    fn pcm_roundtrip_preserves_samples() {
-        let codec = OpusCodec;
-        let pcm: Vec<i16> = vec![-32768, -1024, -1, 0, 1, 1024, 32767];
-        let packet = codec.encode_pcm_i16(&pcm);
-        let decoded = codec.decode_to_pcm_i16(&packet);
-        assert_eq!(decoded, pcm);
+    fn fec_recovers_lost_frame_from_next_packet() {
+        let sender = OpusCodec::default();
+        let receiver = OpusCodec::default();
+        let mut packets = Vec::new();
+        for i in 0..30 {
+            packets.push(sender.encode_pcm_i16(&sine_frame(&sender, i)));
+        }
+        for packet in &packets[..20] {
+            let _ = receiver.decode_to_pcm_i16(packet);
+        }
+        // Frame 20 is lost; rebuild it from frame 21's FEC payload.
+        let recovered = receiver.recover_from_next_pcm_i16(&packets[21]);
+        assert_eq!(recovered.len(), receiver.config().frame_samples());
+        assert!(rms(&recovered) > 1_000.0, "rms {}", rms(&recovered));
+        let next = receiver.decode_to_pcm_i16(&packets[21]);
+        assert!(rms(&next) > 1_000.0);
     }
 }
@@ -2,11 +2,32 @@ use aes_gcm::aead::{Aead, Payload};
 use aes_gcm::{Aes128Gcm, KeyInit, Nonce};
 use hkdf::Hkdf;
 use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
 
 const FRAME_VERSION: u8 = 1;
 const FLAGS_KEYFRAME: u8 = 0x01;
 const HEADER_LEN: usize = 35;
 
+/// How long a sender keeps sealing frames under the previous key generation
+/// after a rotation, so peers that have not processed the commit yet keep
+/// hearing us.
+pub const KEY_ROTATION_OVERLAP: Duration = Duration::from_secs(2);
+
+/// How long a receiver keeps accepting the previous key generation after a
+/// rotation. Covers the sender's [`KEY_ROTATION_OVERLAP`] plus the same again
+/// for senders that process the commit after we do.
+pub const KEY_ROTATION_RX_WINDOW: Duration = Duration::from_secs(4);
+
+/// Sender delay for a rotation. A commit that removed a member switches right
+/// away, since the overlap would let them keep decrypting our frames.
+pub fn tx_rotation_delay(member_removed: bool) -> Duration {
+    if member_removed {
+        Duration::ZERO
+    } else {
+        KEY_ROTATION_OVERLAP
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FrameInfo {
     pub counter: u32,
@@ -222,6 +243,114 @@ pub fn decrypt_frame(
     })
 }
 
+/// Receive keys for one sender track across key rotations.
+///
+/// After [`FrameKeyRing::rotate`] the new generation is used for every frame
+/// that carries it, while frames still sealed under the previous generation are
+/// accepted until the overlap window closes. Anything older is rejected.
+#[derive(Debug, Clone)]
+pub struct FrameKeyRing {
+    current: FrameKeyMaterial,
+    previous: Option<(FrameKeyMaterial, Instant)>,
+}
+
+impl FrameKeyRing {
+    pub fn new(keys: FrameKeyMaterial) -> Self {
+        Self {
+            current: keys,
+            previous: None,
+        }
+    }
+
+    pub fn current(&self) -> &FrameKeyMaterial {
+        &self.current
+    }
+
+    pub fn rotate(&mut self, next: FrameKeyMaterial, overlap: Duration) {
+        self.rotate_at(next, overlap, Instant::now());
+    }
+
+    pub fn rotate_at(&mut self, next: FrameKeyMaterial, overlap: Duration, now: Instant) {
+        let previous = std::mem::replace(&mut self.current, next);
+        self.previous = Some((previous, now + overlap));
+    }
+
+    pub fn decrypt(&mut self, payload: &[u8]) -> Result<DecryptedFrame, FrameCryptoError> {
+        self.decrypt_at(payload, Instant::now())
+    }
+
+    pub fn decrypt_at(
+        &mut self,
+        payload: &[u8],
+        now: Instant,
+    ) -> Result<DecryptedFrame, FrameCryptoError> {
+        if self
+            .previous
+            .as_ref()
+            .is_some_and(|(_, expires_at)| now >= *expires_at)
+        {
+            self.previous = None;
+        }
+        match decrypt_frame(payload, &self.current) {
+            Err(
+                err @ (FrameCryptoError::GenerationMismatch { .. }
+                | FrameCryptoError::EpochMismatch { .. }),
+            ) => match &self.previous {
+                Some((previous, _)) => decrypt_frame(payload, previous).map_err(|_| err),
+                None => Err(err),
+            },
+            result => result,
+        }
+    }
+}
+
+/// Send keys for one local track across key rotations.
+///
+/// [`FrameKeySchedule::rotate`] does not switch right away: peers that have not
+/// processed the commit yet can only open the current generation, so the next
+/// one takes over once the delay has passed.
+#[derive(Debug, Clone)]
+pub struct FrameKeySchedule {
+    current: FrameKeyMaterial,
+    next: Option<(FrameKeyMaterial, Instant)>,
+}
+
+impl FrameKeySchedule {
+    pub fn new(keys: FrameKeyMaterial) -> Self {
+        Self {
+            current: keys,
+            next: None,
+        }
+    }
+
+    pub fn rotate(&mut self, next: FrameKeyMaterial, delay: Duration) {
+        self.rotate_at(next, delay, Instant::now());
+    }
+
+    pub fn rotate_at(&mut self, next: FrameKeyMaterial, delay: Duration, now: Instant) {
+        // Receivers that saw the pending rotation only keep one generation
+        // behind, so it takes over before the new one is scheduled.
+        if let Some((pending, _)) = self.next.take() {
+            self.current = pending;
+        }
+        self.next = Some((next, now + delay));
+    }
+
+    /// Keys to seal the next frame with.
+    pub fn current(&mut self) -> &FrameKeyMaterial {
+        self.current_at(Instant::now())
+    }
+
+    pub fn current_at(&mut self, now: Instant) -> &FrameKeyMaterial {
+        if self.next.as_ref().is_some_and(|(_, at)| now >= *at) {
+            if let Some((next, _)) = self.next.take() {
+                self.current = next;
+            }
+        }
+        &self.current
+    }
+}
+
 pub fn opaque_participant_label(shared_seed: &[u8], participant_id: &[u8]) -> String {
     let digest = hash32(
         [
@@ -358,4 +487,112 @@ mod tests {
         let high = build_nonce(salt, 0x0100_0001);
         assert_ne!(low, high, "high counter bits must affect nonce");
     }
+
+    fn rotated_keys(epoch: u64, generation: u8) -> FrameKeyMaterial {
+        let seed = format!("call-shared-seed-epoch-{epoch}");
+        FrameKeyMaterial::from_fallback_context(
+            seed.as_bytes(),
+            b"sender-a",
+            epoch,
+            generation,
+            "audio0",
+        )
+    }
+
+    fn info(seq: u64) -> FrameInfo {
+        FrameInfo {
+            counter: seq as u32,
+            group_seq: seq,
+            frame_idx: 0,
+            keyframe: true,
+        }
+    }
+
+    #[test]
+    fn key_ring_accepts_both_generations_during_overlap() {
+        let old = rotated_keys(1, 0);
+        let new = rotated_keys(2, 1);
+        let start = Instant::now();
+        let mut ring = FrameKeyRing::new(old.clone());
+
+        let in_flight = encrypt_frame(b"before", &old, info(1)).expect("encrypt old");
+        ring.rotate_at(new.clone(), KEY_ROTATION_OVERLAP, start);
+        let fresh = encrypt_frame(b"after", &new, info(2)).expect("encrypt new");
+
+        let opened = ring.decrypt_at(&fresh, start).expect("new generation");
+        assert_eq!(opened.payload, b"after");
+        let opened = ring
+            .decrypt_at(&in_flight, start + Duration::from_millis(500))
+            .expect("previous generation inside overlap");
+        assert_eq!(opened.payload, b"before");
+        assert_eq!(ring.current().generation, 1);
+    }
+
+    #[test]
+    fn key_ring_drops_previous_generation_after_overlap() {
+        let old = rotated_keys(1, 0);
+        let new = rotated_keys(2, 1);
+        let start = Instant::now();
+        let mut ring = FrameKeyRing::new(old.clone());
+        ring.rotate_at(new.clone(), KEY_ROTATION_OVERLAP, start);
+
+        let stale = encrypt_frame(b"late", &old, info(3)).expect("encrypt old");
+        let err = ring
+            .decrypt_at(&stale, start + KEY_ROTATION_OVERLAP)
+            .expect_err("overlap closed");
+        assert!(matches!(err, FrameCryptoError::GenerationMismatch { .. }));
+
+        let fresh = encrypt_frame(b"now", &new, info(4)).expect("encrypt new");
+        assert!(ring
+            .decrypt_at(&fresh, start + KEY_ROTATION_OVERLAP)
+            .is_ok());
+    }
+
+    #[test]
+    fn key_schedule_keeps_sending_previous_generation_during_overlap() {
+        let old = rotated_keys(1, 0);
+        let new = rotated_keys(2, 1);
+        let start = Instant::now();
+        let mut schedule = FrameKeySchedule::new(old.clone());
+        schedule.rotate_at(new.clone(), KEY_ROTATION_OVERLAP, start);
+
+        // A receiver that has not processed the commit yet.
+        let mut lagging = FrameKeyRing::new(old.clone());
+        let sealed = encrypt_frame(
+            b"during",
+            schedule.current_at(start + Duration::from_millis(500)),
+            info(1),
+        )
+        .expect("encrypt");
+        assert_eq!(
+            lagging.decrypt_at(&sealed, start).expect("old").payload,
+            b"during"
+        );
+
+        assert_eq!(
+            schedule.current_at(start + KEY_ROTATION_OVERLAP).generation,
+            new.generation
+        );
+    }
+
+    #[test]
+    fn key_schedule_promotes_pending_keys_on_back_to_back_rotations() {
+        let start = Instant::now();
+        let mut schedule = FrameKeySchedule::new(rotated_keys(1, 0));
+        schedule.rotate_at(rotated_keys(2, 1), KEY_ROTATION_OVERLAP, start);
+        schedule.rotate_at(rotated_keys(3, 2), KEY_ROTATION_OVERLAP, start);
+        assert_eq!(schedule.current_at(start).generation, 1);
+        assert_eq!(
+            schedule.current_at(start + KEY_ROTATION_OVERLAP).generation,
+            2
+        );
+    }
+
+    #[test]
+    fn key_ring_rejects_frames_from_unknown_generation() {
+        let mut ring = FrameKeyRing::new(rotated_keys(1, 0));
+        ring.rotate(rotated_keys(2, 1), KEY_ROTATION_OVERLAP);
+        let future = encrypt_frame(b"ahead", &rotated_keys(3, 2), info(5)).expect("encrypt");
+        assert!(ring.decrypt(&future).is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 #[derive(Debug, Clone)]
 pub struct JitterBuffer<T> {
@@ -82,6 +82,214 @@ impl<T> JitterBuffer<T> {
     }
 }
 
+/// Consecutive missing frames concealed before playout pauses to re-prefill.
+const MAX_CONCEALED_RUN: u32 = 10;
+/// In-order frames required before the target depth is allowed to shrink by one.
+const SHRINK_AFTER_FRAMES: u32 = 250;
+/// Transit-time jumps larger than this are a stream discontinuity (sender muted
+/// or paused), not network jitter, and restart the jitter estimate baseline.
+const MAX_TRANSIT_DELTA_US: f64 = 500_000.0;
+
+/// What the playout clock should render for the next frame slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Playout<T> {
+    /// The expected frame arrived in time.
+    Frame(T),
+    /// The expected frame is missing and must be concealed. `next` is the
+    /// following frame when already buffered, so a codec with in-band FEC can
+    /// rebuild the lost one from it. `run` counts consecutive concealed slots.
+    Conceal { seq: u64, next: Option<T>, run: u32 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JitterStats {
+    pub dropped: u64,
+    pub underflows: u64,
+    pub reordered: u64,
+    pub late: u64,
+    pub concealed: u64,
+    /// Smoothed inter-arrival jitter (RFC 3550 estimator) in microseconds.
+    pub jitter_us: u64,
+}
+
+/// Sequence-aware jitter buffer for a single media track.
+///
+/// Frames are keyed by `seq`, so out-of-order arrivals are reordered before
+/// playout and frames arriving after their slot was played are counted as late.
+/// The prefill target tracks measured inter-arrival jitter: it grows as soon as
+/// jitter or an underflow calls for it and shrinks slowly once the stream is
+/// stable. Gaps surface as [`Playout::Conceal`] instead of silence.
+#[derive(Debug, Clone)]
+pub struct AdaptiveJitterBuffer<T> {
+    frame_duration_us: u64,
+    min_target: usize,
+    max_target: usize,
+    max_frames: usize,
+    target_frames: usize,
+    frames: BTreeMap<u64, T>,
+    next_seq: Option<u64>,
+    playout_started: bool,
+    concealed_run: u32,
+    stable_frames: u32,
+    last_arrival: Option<(u64, u64)>,
+    jitter_us: f64,
+    stats: JitterStats,
+}
+
+impl<T: Clone> AdaptiveJitterBuffer<T> {
+    pub fn new(
+        frame_duration_us: u64,
+        min_target: usize,
+        max_target: usize,
+        max_frames: usize,
+    ) -> Self {
+        let max_frames = max_frames.max(1);
+        let max_target = max_target.clamp(1, max_frames);
+        let min_target = min_target.clamp(1, max_target);
+        Self {
+            frame_duration_us: frame_duration_us.max(1),
+            min_target,
+            max_target,
+            max_frames,
+            target_frames: min_target,
+            frames: BTreeMap::new(),
+            next_seq: None,
+            playout_started: false,
+            concealed_run: 0,
+            stable_frames: 0,
+            last_arrival: None,
+            jitter_us: 0.0,
+            stats: JitterStats::default(),
+        }
+    }
+
+    /// Buffer a frame. `arrival_us` is the local receive clock in microseconds.
+    ///
+    /// Returns `false` when the frame was discarded as late or duplicate.
+    pub fn push(&mut self, seq: u64, timestamp_us: u64, arrival_us: u64, frame: T) -> bool {
+        if self.next_seq.is_some_and(|next| seq < next) {
+            self.stats.late = self.stats.late.saturating_add(1);
+            return false;
+        }
+        if self.frames.contains_key(&seq) {
+            return false;
+        }
+        if self
+            .frames
+            .last_key_value()
+            .is_some_and(|(&newest, _)| seq < newest)
+        {
+            self.stats.reordered = self.stats.reordered.saturating_add(1);
+        } else {
+            self.observe_arrival(timestamp_us, arrival_us);
+        }
+
+        self.frames.insert(seq, frame);
+        while self.frames.len() > self.max_frames {
+            if let Some((oldest, _)) = self.frames.pop_first() {
+                self.stats.dropped = self.stats.dropped.saturating_add(1);
+                self.next_seq = Some(oldest.saturating_add(1));
+            }
+        }
+        true
+    }
+
+    pub fn pop_for_playout(&mut self) -> Option<Playout<T>> {
+        if !self.playout_started {
+            if self.frames.len() < self.target_frames {
+                return None;
+            }
+            self.playout_started = true;
+            self.concealed_run = 0;
+            let oldest = *self.frames.keys().next()?;
+            self.next_seq = Some(self.next_seq.map_or(oldest, |next| next.max(oldest)));
+        }
+
+        // Catch up when a burst left us well above target (e.g. after a stall).
+        while self.frames.len() > self.target_frames.saturating_add(2) {
+            let Some((oldest, _)) = self.frames.pop_first() else {
+                break;
+            };
+            self.stats.dropped = self.stats.dropped.saturating_add(1);
+            self.next_seq = Some(oldest.saturating_add(1));
+        }
+
+        let seq = self.next_seq?;
+        if let Some(frame) = self.frames.remove(&seq) {
+            self.next_seq = Some(seq.saturating_add(1));
+            self.concealed_run = 0;
+            self.stable_frames = self.stable_frames.saturating_add(1);
+            if self.stable_frames >= SHRINK_AFTER_FRAMES {
+                self.stable_frames = 0;
+                self.target_frames = self
+                    .target_frames
+                    .saturating_sub(1)
+                    .max(self.jitter_target())
+                    .max(self.min_target);
+            }
+            return Some(Playout::Frame(frame));
+        }
+
+        self.stable_frames = 0;
+        if self.frames.is_empty() && self.concealed_run >= MAX_CONCEALED_RUN {
+            self.playout_started = false;
+            self.stats.underflows = self.stats.underflows.saturating_add(1);
+            self.target_frames = (self.target_frames + 1).min(self.max_target);
+            return None;
+        }
+
+        self.concealed_run = self.concealed_run.saturating_add(1);
+        self.stats.concealed = self.stats.concealed.saturating_add(1);
+        self.next_seq = Some(seq.saturating_add(1));
+        Some(Playout::Conceal {
+            seq,
+            next: self.frames.get(&seq.saturating_add(1)).cloned(),
+            run: self.concealed_run,
+        })
+    }
+
+    fn observe_arrival(&mut self, timestamp_us: u64, arrival_us: u64) {
+        if let Some((last_ts, last_arrival)) = self.last_arrival {
+            let transit_delta =
+                (arrival_us as f64 - last_arrival as f64) - (timestamp_us as f64 - last_ts as f64);
+            if transit_delta.abs() > MAX_TRANSIT_DELTA_US {
+                self.last_arrival = Some((timestamp_us, arrival_us));
+                return;
+            }
+            self.jitter_us += (transit_delta.abs() - self.jitter_us) / 16.0;
+            self.stats.jitter_us = self.jitter_us as u64;
+            let wanted = self.jitter_target();
+            if wanted > self.target_frames {
+                self.target_frames = wanted;
+                self.stable_frames = 0;
+            }
+        }
+        self.last_arrival = Some((timestamp_us, arrival_us));
+    }
+
+    /// Depth needed to absorb roughly three times the measured jitter.
+    fn jitter_target(&self) -> usize {
+        let frames = (self.jitter_us * 3.0 / self.frame_duration_us as f64).ceil() as usize;
+        (frames + 1).clamp(self.min_target, self.max_target)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn target_frames(&self) -> usize {
+        self.target_frames
+    }
+
+    pub fn stats(&self) -> JitterStats {
+        self.stats
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +315,24 @@ mod tests {
         assert_eq!(jb.pop_for_playout(), Some(11));
     }
 
+    #[test]
+    fn buffers_encoded_opus_packets() {
+        use crate::codec_opus::OpusCodec;
+
+        let codec = OpusCodec::default();
+        let frame = vec![0i16; codec.config().frame_samples()];
+        let mut jb = JitterBuffer::with_target(4, 2);
+        for _ in 0..3 {
+            assert!(!jb.push(codec.encode_pcm_i16(&frame)));
+        }
+        let packet = jb.pop_for_playout().expect("prefilled");
+        assert_eq!(
+            codec.decode_to_pcm_i16(&packet).len(),
+            codec.config().frame_samples()
+        );
+        assert_eq!(jb.len(), 2);
+    }
+
     #[test]
     fn underflow_resets_playout_until_refilled() {
         let mut jb = JitterBuffer::with_target(4, 2);
@@ -122,4 +348,105 @@ mod tests {
         assert!(!jb.push(4));
         assert_eq!(jb.pop_for_playout(), Some(3));
     }
+
+    fn adaptive(min: usize, max: usize) -> AdaptiveJitterBuffer<u64> {
+        AdaptiveJitterBuffer::new(20_000, min, max, 16)
+    }
+
+    fn push_at(jb: &mut AdaptiveJitterBuffer<u64>, seq: u64, arrival_us: u64) -> bool {
+        jb.push(seq, seq * 20_000, arrival_us, seq)
+    }
+
+    #[test]
+    fn adaptive_reorders_out_of_order_frames() {
+        let mut jb = adaptive(3, 8);
+        push_at(&mut jb, 0, 0);
+        push_at(&mut jb, 2, 40_000);
+        push_at(&mut jb, 1, 41_000);
+        assert_eq!(jb.stats().reordered, 1);
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(0)));
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(1)));
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(2)));
+    }
+
+    #[test]
+    fn adaptive_conceals_gaps_and_counts_late_frames() {
+        let mut jb = adaptive(2, 8);
+        push_at(&mut jb, 0, 0);
+        push_at(&mut jb, 2, 40_000);
+        push_at(&mut jb, 3, 60_000);
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(0)));
+        assert_eq!(
+            jb.pop_for_playout(),
+            Some(Playout::Conceal {
+                seq: 1,
+                next: Some(2),
+                run: 1
+            })
+        );
+        assert!(!push_at(&mut jb, 1, 80_000), "frame 1 already concealed");
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(2)));
+
+        let stats = jb.stats();
+        assert_eq!(stats.concealed, 1);
+        assert_eq!(stats.late, 1);
+    }
+
+    #[test]
+    fn adaptive_target_grows_with_jitter() {
+        let mut jb = adaptive(1, 8);
+        for seq in 0..40u64 {
+            // Alternate early/late arrivals by +/-15ms around the 20ms cadence.
+            let wobble = if seq % 2 == 0 { 0 } else { 30_000 };
+            push_at(&mut jb, seq, seq * 20_000 + wobble);
+            let _ = jb.pop_for_playout();
+        }
+        assert!(jb.stats().jitter_us > 10_000, "{:?}", jb.stats());
+        assert!(jb.target_frames() >= 3, "target {}", jb.target_frames());
+    }
+
+    #[test]
+    fn adaptive_steady_stream_keeps_minimum_target() {
+        let mut jb = adaptive(2, 8);
+        for seq in 0..100u64 {
+            push_at(&mut jb, seq, seq * 20_000);
+            let _ = jb.pop_for_playout();
+        }
+        assert_eq!(jb.target_frames(), 2);
+        assert_eq!(jb.stats().concealed, 0);
+    }
+
+    #[test]
+    fn adaptive_ignores_sender_pause_when_estimating_jitter() {
+        let mut jb = adaptive(2, 8);
+        for seq in 0..10u64 {
+            push_at(&mut jb, seq, seq * 20_000);
+        }
+        // Sender muted for 5s; timestamps continue from where they left off.
+        for seq in 10..20u64 {
+            push_at(&mut jb, seq, 5_000_000 + seq * 20_000);
+        }
+        assert_eq!(jb.stats().jitter_us, 0);
+        assert_eq!(jb.target_frames(), 2);
+    }
+
+    #[test]
+    fn adaptive_long_outage_underflows_and_grows_target() {
+        let mut jb = adaptive(1, 8);
+        push_at(&mut jb, 0, 0);
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(0)));
+        let mut concealed = 0;
+        while let Some(Playout::Conceal { .. }) = jb.pop_for_playout() {
+            concealed += 1;
+        }
+        assert_eq!(concealed, MAX_CONCEALED_RUN);
+        assert_eq!(jb.stats().underflows, 1);
+        assert_eq!(jb.target_frames(), 2);
+
+        // Playout resumes at the next buffered frame once refilled.
+        push_at(&mut jb, 20, 400_000);
+        assert_eq!(jb.pop_for_playout(), None);
+        push_at(&mut jb, 21, 420_000);
+        assert_eq!(jb.pop_for_playout(), Some(Playout::Frame(20)));
+    }
 }
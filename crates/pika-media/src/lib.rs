@@ -1,9 +1,16 @@
+pub mod audio_processing;
 pub mod codec_opus;
 pub mod crypto;
 pub mod directory;
 pub mod jitter;
 #[cfg(feature = "network")]
 pub mod network;
+pub mod quality;
+#[cfg(feature = "sqlite")]
+pub mod quality_store;
+pub mod recording;
 pub mod session;
+pub mod stt;
 pub mod subscription;
 pub mod tracks;
+pub mod video_adapt;
@@ -0,0 +1,256 @@
+//! Call quality history.
+//!
+//! The call runtime reports cumulative counters several times a second.
+//! [`QualitySampler`] turns them into one [`QualitySample`] per interval:
+//! round-trip time, jitter, loss, concealment, bitrate and video frame rate.
+//! [`summarize`] condenses a call's samples into a single score for reports.
+
+/// One interval of a call's quality history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualitySample {
+    /// Unix time in milliseconds at the end of the interval.
+    pub at_ms: i64,
+    pub rtt_ms: Option<u32>,
+    /// Smoothed inter-arrival jitter of the remote audio.
+    pub jitter_ms: u32,
+    /// Share of audio playout slots that had to be concealed, 0 to 100.
+    pub loss_pct: f32,
+    pub concealed_frames: u64,
+    pub tx_bitrate_bps: u64,
+    pub rx_bitrate_bps: u64,
+    /// Remote video frames received per second; 0 on audio-only calls.
+    pub video_fps: f32,
+}
+
+/// Cumulative counters from the call runtime. They only ever grow while a
+/// call is running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QualityCounters {
+    pub rx_frames: u64,
+    pub rx_concealed: u64,
+    pub tx_bytes: u64,
+    pub rx_bytes: u64,
+    pub video_rx_frames: u64,
+}
+
+/// Emits a [`QualitySample`] each time at least `interval_ms` has passed
+/// since the previous one.
+#[derive(Debug)]
+pub struct QualitySampler {
+    interval_ms: i64,
+    last: Option<(i64, QualityCounters)>,
+}
+
+impl QualitySampler {
+    pub fn new(interval_ms: i64) -> Self {
+        Self {
+            interval_ms: interval_ms.max(1),
+            last: None,
+        }
+    }
+
+    /// The first observation only sets the baseline.
+    pub fn observe(
+        &mut self,
+        at_ms: i64,
+        counters: QualityCounters,
+        rtt_ms: Option<u32>,
+        jitter_ms: u32,
+    ) -> Option<QualitySample> {
+        let Some((last_at_ms, last)) = self.last else {
+            self.last = Some((at_ms, counters));
+            return None;
+        };
+        let elapsed_ms = at_ms - last_at_ms;
+        if elapsed_ms < self.interval_ms {
+            return None;
+        }
+        self.last = Some((at_ms, counters));
+
+        let received = counters.rx_frames.saturating_sub(last.rx_frames);
+        let concealed = counters.rx_concealed.saturating_sub(last.rx_concealed);
+        let slots = received.saturating_add(concealed);
+        let loss_pct = if slots == 0 {
+            0.0
+        } else {
+            concealed as f32 * 100.0 / slots as f32
+        };
+        let bitrate = |now: u64, before: u64| {
+            now.saturating_sub(before).saturating_mul(8 * 1000) / elapsed_ms as u64
+        };
+        let video_frames = counters
+            .video_rx_frames
+            .saturating_sub(last.video_rx_frames);
+        Some(QualitySample {
+            at_ms,
+            rtt_ms,
+            jitter_ms,
+            loss_pct,
+            concealed_frames: concealed,
+            tx_bitrate_bps: bitrate(counters.tx_bytes, last.tx_bytes),
+            rx_bitrate_bps: bitrate(counters.rx_bytes, last.rx_bytes),
+            video_fps: video_frames as f32 * 1000.0 / elapsed_ms as f32,
+        })
+    }
+}
+
+/// Estimated mean opinion score, from 1.0 (unusable) to 4.5 (excellent),
+/// using a simplified ITU-T G.107 E-model. Without an RTT measurement only
+/// jitter counts towards delay.
+pub fn estimate_mos(rtt_ms: Option<u32>, jitter_ms: u32, loss_pct: f32) -> f32 {
+    let one_way_ms = rtt_ms.unwrap_or(0) as f32 / 2.0 + jitter_ms as f32 * 2.0 + 10.0;
+    let delay_penalty = if one_way_ms < 160.0 {
+        one_way_ms / 40.0
+    } else {
+        (one_way_ms - 120.0) / 10.0
+    };
+    let r = (93.2 - delay_penalty - loss_pct.max(0.0) * 2.5).clamp(0.0, 100.0);
+    let mos = 1.0 + 0.035 * r + 0.000_007 * r * (r - 60.0) * (100.0 - r);
+    mos.clamp(1.0, 4.5)
+}
+
+/// A call's quality condensed for reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualitySummary {
+    /// Mean of each sample's [`estimate_mos`].
+    pub score: f32,
+    pub sample_count: u32,
+    /// From the first sample to the last.
+    pub duration_ms: i64,
+    /// `None` when no sample had an RTT measurement.
+    pub avg_rtt_ms: Option<u32>,
+    pub avg_jitter_ms: u32,
+    pub max_jitter_ms: u32,
+    pub avg_loss_pct: f32,
+    pub max_loss_pct: f32,
+    pub concealed_frames: u64,
+    pub avg_tx_bitrate_bps: u64,
+    pub avg_rx_bitrate_bps: u64,
+    /// Averaged over the samples that received any video.
+    pub avg_video_fps: f32,
+}
+
+/// `None` when there are no samples.
+pub fn summarize(samples: &[QualitySample]) -> Option<QualitySummary> {
+    let (first, last) = (samples.first()?, samples.last()?);
+    let count = samples.len();
+    let mean_u64 = |f: fn(&QualitySample) -> u64| samples.iter().map(f).sum::<u64>() / count as u64;
+    let mean_f32 = |values: &[f32]| {
+        if values.is_empty() {
+            0.0
+        } else {
+            values.iter().sum::<f32>() / values.len() as f32
+        }
+    };
+
+    let rtts: Vec<u64> = samples
+        .iter()
+        .filter_map(|s| s.rtt_ms.map(u64::from))
+        .collect();
+    let avg_rtt_ms =
+        (!rtts.is_empty()).then(|| (rtts.iter().sum::<u64>() / rtts.len() as u64) as u32);
+    let scores: Vec<f32> = samples
+        .iter()
+        .map(|s| estimate_mos(s.rtt_ms, s.jitter_ms, s.loss_pct))
+        .collect();
+    let losses: Vec<f32> = samples.iter().map(|s| s.loss_pct).collect();
+    let video_fps: Vec<f32> = samples
+        .iter()
+        .map(|s| s.video_fps)
+        .filter(|fps| *fps > 0.0)
+        .collect();
+
+    Some(QualitySummary {
+        score: mean_f32(&scores),
+        sample_count: count as u32,
+        duration_ms: last.at_ms.saturating_sub(first.at_ms),
+        avg_rtt_ms,
+        avg_jitter_ms: mean_u64(|s| s.jitter_ms as u64) as u32,
+        max_jitter_ms: samples.iter().map(|s| s.jitter_ms).max().unwrap_or(0),
+        avg_loss_pct: mean_f32(&losses),
+        max_loss_pct: losses.iter().copied().fold(0.0, f32::max),
+        concealed_frames: samples.iter().map(|s| s.concealed_frames).sum(),
+        avg_tx_bitrate_bps: mean_u64(|s| s.tx_bitrate_bps),
+        avg_rx_bitrate_bps: mean_u64(|s| s.rx_bitrate_bps),
+        avg_video_fps: mean_f32(&video_fps),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters(rx_frames: u64, rx_concealed: u64, bytes: u64, video: u64) -> QualityCounters {
+        QualityCounters {
+            rx_frames,
+            rx_concealed,
+            tx_bytes: bytes,
+            rx_bytes: bytes,
+            video_rx_frames: video,
+        }
+    }
+
+    #[test]
+    fn sampler_turns_counters_into_interval_rates() {
+        let mut sampler = QualitySampler::new(2_000);
+        assert!(sampler
+            .observe(10_000, counters(0, 0, 0, 0), None, 0)
+            .is_none());
+        assert!(sampler
+            .observe(11_000, counters(50, 0, 4_000, 30), None, 5)
+            .is_none());
+
+        let sample = sampler
+            .observe(12_000, counters(90, 10, 16_000, 60), Some(80), 12)
+            .expect("sample after interval");
+        assert_eq!(sample.at_ms, 12_000);
+        assert_eq!(sample.rtt_ms, Some(80));
+        assert_eq!(sample.jitter_ms, 12);
+        assert_eq!(sample.concealed_frames, 10);
+        assert!((sample.loss_pct - 10.0).abs() < 0.01);
+        assert_eq!(sample.tx_bitrate_bps, 64_000);
+        assert_eq!(sample.rx_bitrate_bps, 64_000);
+        assert!((sample.video_fps - 30.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn mos_drops_with_loss_and_delay() {
+        let clean = estimate_mos(Some(40), 5, 0.0);
+        assert!(clean > 4.3, "clean call scored {clean}");
+        assert!(estimate_mos(Some(40), 5, 10.0) < clean);
+        assert!(estimate_mos(Some(600), 5, 0.0) < clean);
+        assert_eq!(estimate_mos(Some(2_000), 500, 100.0), 1.0);
+    }
+
+    #[test]
+    fn summary_averages_samples() {
+        let sample = |at_ms, rtt_ms, loss_pct, video_fps| QualitySample {
+            at_ms,
+            rtt_ms,
+            jitter_ms: 10,
+            loss_pct,
+            concealed_frames: 2,
+            tx_bitrate_bps: 30_000,
+            rx_bitrate_bps: 40_000,
+            video_fps,
+        };
+        assert!(summarize(&[]).is_none());
+
+        let summary = summarize(&[
+            sample(1_000, Some(100), 0.0, 0.0),
+            sample(3_000, None, 4.0, 24.0),
+            sample(5_000, Some(50), 2.0, 30.0),
+        ])
+        .expect("summary");
+        assert_eq!(summary.sample_count, 3);
+        assert_eq!(summary.duration_ms, 4_000);
+        assert_eq!(summary.avg_rtt_ms, Some(75));
+        assert_eq!(summary.avg_jitter_ms, 10);
+        assert!((summary.avg_loss_pct - 2.0).abs() < 0.01);
+        assert_eq!(summary.max_loss_pct, 4.0);
+        assert_eq!(summary.concealed_frames, 6);
+        assert_eq!(summary.avg_rx_bitrate_bps, 40_000);
+        assert!((summary.avg_video_fps - 27.0).abs() < 0.01);
+        assert!(summary.score > 1.0 && summary.score <= 4.5);
+    }
+}
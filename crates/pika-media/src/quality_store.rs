@@ -0,0 +1,203 @@
+//! SQLite persistence for [`QualitySample`]s, shared by the app and the
+//! `pikachat` CLI so either can read a device's call reports.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::quality::QualitySample;
+
+pub const QUALITY_DB_FILE: &str = "call_quality.sqlite3";
+
+/// A call with recorded samples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityCallEntry {
+    pub call_id: String,
+    pub chat_id: String,
+    pub first_at_ms: i64,
+    pub last_at_ms: i64,
+    pub sample_count: u32,
+}
+
+pub fn open_quality_db(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    create_tables(&conn)?;
+    Ok(conn)
+}
+
+fn create_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        r#"
+        PRAGMA journal_mode=WAL;
+
+        CREATE TABLE IF NOT EXISTS call_quality_samples (
+            call_id TEXT NOT NULL,
+            chat_id TEXT NOT NULL,
+            at_ms INTEGER NOT NULL,
+            rtt_ms INTEGER,
+            jitter_ms INTEGER NOT NULL,
+            loss_pct REAL NOT NULL,
+            concealed_frames INTEGER NOT NULL,
+            tx_bitrate_bps INTEGER NOT NULL,
+            rx_bitrate_bps INTEGER NOT NULL,
+            video_fps REAL NOT NULL,
+            PRIMARY KEY (call_id, at_ms)
+        );
+        "#,
+    )
+}
+
+pub fn insert_quality_sample(
+    conn: &Connection,
+    call_id: &str,
+    chat_id: &str,
+    sample: &QualitySample,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"
+        INSERT OR REPLACE INTO call_quality_samples (
+            call_id,
+            chat_id,
+            at_ms,
+            rtt_ms,
+            jitter_ms,
+            loss_pct,
+            concealed_frames,
+            tx_bitrate_bps,
+            rx_bitrate_bps,
+            video_fps
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        "#,
+        params![
+            call_id,
+            chat_id,
+            sample.at_ms,
+            sample.rtt_ms.map(i64::from),
+            i64::from(sample.jitter_ms),
+            f64::from(sample.loss_pct),
+            sample.concealed_frames as i64,
+            sample.tx_bitrate_bps as i64,
+            sample.rx_bitrate_bps as i64,
+            f64::from(sample.video_fps),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Oldest first.
+pub fn load_quality_samples(
+    conn: &Connection,
+    call_id: &str,
+) -> rusqlite::Result<Vec<QualitySample>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT at_ms, rtt_ms, jitter_ms, loss_pct, concealed_frames,
+               tx_bitrate_bps, rx_bitrate_bps, video_fps
+        FROM call_quality_samples
+        WHERE call_id = ?1
+        ORDER BY at_ms ASC
+        "#,
+    )?;
+    let rows = stmt.query_map(params![call_id], |row| {
+        Ok(QualitySample {
+            at_ms: row.get(0)?,
+            rtt_ms: row.get::<_, Option<i64>>(1)?.map(|v| v as u32),
+            jitter_ms: row.get::<_, i64>(2)? as u32,
+            loss_pct: row.get::<_, f64>(3)? as f32,
+            concealed_frames: row.get::<_, i64>(4)? as u64,
+            tx_bitrate_bps: row.get::<_, i64>(5)? as u64,
+            rx_bitrate_bps: row.get::<_, i64>(6)? as u64,
+            video_fps: row.get::<_, f64>(7)? as f32,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Most recent call first.
+pub fn list_quality_calls(conn: &Connection) -> rusqlite::Result<Vec<QualityCallEntry>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT call_id, chat_id, MIN(at_ms), MAX(at_ms), COUNT(*)
+        FROM call_quality_samples
+        GROUP BY call_id
+        ORDER BY MAX(at_ms) DESC
+        "#,
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(QualityCallEntry {
+            call_id: row.get(0)?,
+            chat_id: row.get(1)?,
+            first_at_ms: row.get(2)?,
+            last_at_ms: row.get(3)?,
+            sample_count: row.get::<_, i64>(4)? as u32,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Drops every call but the `keep_calls` most recent ones.
+pub fn prune_quality_calls(conn: &Connection, keep_calls: usize) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"
+        DELETE FROM call_quality_samples
+        WHERE call_id NOT IN (
+            SELECT call_id FROM call_quality_samples
+            GROUP BY call_id
+            ORDER BY MAX(at_ms) DESC
+            LIMIT ?1
+        )
+        "#,
+        params![keep_calls as i64],
+    )?;
+    Ok(())
+}
+
+pub fn clear_quality_samples(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM call_quality_samples", [])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(at_ms: i64, loss_pct: f32) -> QualitySample {
+        QualitySample {
+            at_ms,
+            rtt_ms: (at_ms % 2 == 0).then_some(60),
+            jitter_ms: 8,
+            loss_pct,
+            concealed_frames: 3,
+            tx_bitrate_bps: 32_000,
+            rx_bitrate_bps: 31_000,
+            video_fps: 0.0,
+        }
+    }
+
+    #[test]
+    fn samples_round_trip_and_old_calls_are_pruned() {
+        let conn = Connection::open_in_memory().expect("open db");
+        create_tables(&conn).expect("create tables");
+
+        insert_quality_sample(&conn, "call-a", "chat-1", &sample(3_000, 1.5)).unwrap();
+        insert_quality_sample(&conn, "call-a", "chat-1", &sample(1_000, 0.0)).unwrap();
+        insert_quality_sample(&conn, "call-b", "chat-2", &sample(9_001, 0.0)).unwrap();
+
+        let loaded = load_quality_samples(&conn, "call-a").unwrap();
+        assert_eq!(loaded, vec![sample(1_000, 0.0), sample(3_000, 1.5)]);
+
+        let calls = list_quality_calls(&conn).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].call_id, "call-b");
+        assert_eq!(calls[1].first_at_ms, 1_000);
+        assert_eq!(calls[1].last_at_ms, 3_000);
+        assert_eq!(calls[1].sample_count, 2);
+
+        prune_quality_calls(&conn, 1).unwrap();
+        assert!(load_quality_samples(&conn, "call-a").unwrap().is_empty());
+        assert_eq!(load_quality_samples(&conn, "call-b").unwrap().len(), 1);
+
+        clear_quality_samples(&conn).unwrap();
+        assert!(list_quality_calls(&conn).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+//! Ogg Opus muxing (RFC 7845) for local call recordings.
+//!
+//! Packets are buffered into pages of about a second each; the whole file
+//! lives in memory until [`OggOpusWriter::finish`], since it is encrypted as
+//! one blob before it touches disk.
+
+/// Samples at 48 kHz the decoder should discard from the start of the stream:
+/// libopus' encoder lookahead at 48 kHz.
+pub const OPUS_PRE_SKIP: u16 = 312;
+/// Flush a page once it holds this many packets (1 s of 20 ms frames).
+const PACKETS_PER_PAGE: usize = 50;
+/// Segment table entries available on a single page.
+const MAX_SEGMENTS: usize = 255;
+
+const HEADER_BOS: u8 = 0x02;
+const HEADER_EOS: u8 = 0x04;
+const VENDOR: &str = "pika";
+
+#[derive(Debug)]
+pub struct OggOpusWriter {
+    out: Vec<u8>,
+    serial: u32,
+    page_seq: u32,
+    /// 48 kHz samples through the end of the last packet pushed.
+    granule: u64,
+    pending: Vec<Vec<u8>>,
+    pending_segments: usize,
+}
+
+impl OggOpusWriter {
+    /// Starts a stream with the identification and comment header pages.
+    /// `serial` only needs to be unique among streams multiplexed in one file.
+    pub fn new(serial: u32, channels: u8, input_sample_rate: u32) -> Self {
+        let mut writer = Self {
+            out: Vec::new(),
+            serial,
+            page_seq: 0,
+            granule: 0,
+            pending: Vec::new(),
+            pending_segments: 0,
+        };
+
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1); // version
+        head.push(channels);
+        head.extend_from_slice(&OPUS_PRE_SKIP.to_le_bytes());
+        head.extend_from_slice(&input_sample_rate.to_le_bytes());
+        head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+        head.push(0); // mapping family: mono/stereo
+        writer.write_page(&[head], HEADER_BOS, 0);
+
+        let mut tags = Vec::new();
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(VENDOR.len() as u32).to_le_bytes());
+        tags.extend_from_slice(VENDOR.as_bytes());
+        tags.extend_from_slice(&0u32.to_le_bytes()); // user comments
+        writer.write_page(&[tags], 0, 0);
+
+        writer
+    }
+
+    /// Appends one Opus packet covering `samples_48k` samples at 48 kHz.
+    pub fn push_packet(&mut self, packet: &[u8], samples_48k: u32) {
+        let segments = packet.len() / 255 + 1;
+        if self.pending_segments + segments > MAX_SEGMENTS {
+            self.flush(0);
+        }
+        self.pending.push(packet.to_vec());
+        self.pending_segments += segments;
+        self.granule = self.granule.saturating_add(samples_48k as u64);
+        if self.pending.len() >= PACKETS_PER_PAGE {
+            self.flush(0);
+        }
+    }
+
+    /// Recorded length, including the encoder's pre-skip.
+    pub fn duration_ms(&self) -> u64 {
+        self.granule / 48
+    }
+
+    /// Closes the stream and returns the file bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.flush(HEADER_EOS);
+        self.out
+    }
+
+    fn flush(&mut self, header_type: u8) {
+        if self.pending.is_empty() && header_type & HEADER_EOS == 0 {
+            return;
+        }
+        let packets = std::mem::take(&mut self.pending);
+        self.pending_segments = 0;
+        self.write_page(&packets, header_type, self.granule);
+    }
+
+    fn write_page(&mut self, packets: &[Vec<u8>], header_type: u8, granule: u64) {
+        let mut lacing = Vec::new();
+        for packet in packets {
+            lacing.extend(std::iter::repeat_n(255u8, packet.len() / 255));
+            lacing.push((packet.len() % 255) as u8);
+        }
+
+        let start = self.out.len();
+        self.out.extend_from_slice(b"OggS");
+        self.out.push(0); // stream structure version
+        self.out.push(header_type);
+        self.out.extend_from_slice(&granule.to_le_bytes());
+        self.out.extend_from_slice(&self.serial.to_le_bytes());
+        self.out.extend_from_slice(&self.page_seq.to_le_bytes());
+        let crc_at = self.out.len();
+        self.out.extend_from_slice(&[0; 4]);
+        self.out.push(lacing.len() as u8);
+        self.out.extend_from_slice(&lacing);
+        for packet in packets {
+            self.out.extend_from_slice(packet);
+        }
+
+        let crc = ogg_crc(&self.out[start..]);
+        self.out[crc_at..crc_at + 4].copy_from_slice(&crc.to_le_bytes());
+        self.page_seq = self.page_seq.wrapping_add(1);
+    }
+}
+
+/// CRC-32 with polynomial 0x04c11db7, no reflection and zero init, as Ogg uses.
+fn ogg_crc(data: &[u8]) -> u32 {
+    let mut crc = 0u32;
+    for &byte in data {
+        crc ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Page {
+        header_type: u8,
+        granule: u64,
+        seq: u32,
+        packets: Vec<Vec<u8>>,
+    }
+
+    fn parse_pages(mut data: &[u8]) -> Vec<Page> {
+        let mut pages = Vec::new();
+        while !data.is_empty() {
+            assert_eq!(&data[0..4], b"OggS");
+            let n_segments = data[26] as usize;
+            let lacing = &data[27..27 + n_segments];
+            let body_len: usize = lacing.iter().map(|&l| l as usize).sum();
+            let page_len = 27 + n_segments + body_len;
+
+            let mut zeroed = data[..page_len].to_vec();
+            zeroed[22..26].fill(0);
+            let crc = u32::from_le_bytes(data[22..26].try_into().unwrap());
+            assert_eq!(ogg_crc(&zeroed), crc, "page crc");
+
+            let mut packets = Vec::new();
+            let mut current = Vec::new();
+            let mut offset = 27 + n_segments;
+            for &len in lacing {
+                current.extend_from_slice(&data[offset..offset + len as usize]);
+                offset += len as usize;
+                if len < 255 {
+                    packets.push(std::mem::take(&mut current));
+                }
+            }
+            pages.push(Page {
+                header_type: data[5],
+                granule: u64::from_le_bytes(data[6..14].try_into().unwrap()),
+                seq: u32::from_le_bytes(data[18..22].try_into().unwrap()),
+                packets,
+            });
+            data = &data[page_len..];
+        }
+        pages
+    }
+
+    #[test]
+    fn crc_matches_reference_vector() {
+        // CRC-32/CKSUM check value without its final inversion.
+        assert_eq!(ogg_crc(b"123456789"), 0x89a1_897f);
+    }
+
+    #[test]
+    fn writes_headers_then_audio_pages_ending_in_eos() {
+        let mut writer = OggOpusWriter::new(7, 1, 48_000);
+        for i in 0..120u32 {
+            writer.push_packet(&[i as u8; 40], 960);
+        }
+        assert_eq!(writer.duration_ms(), 2_400);
+        let pages = parse_pages(&writer.finish());
+
+        assert_eq!(pages[0].header_type, HEADER_BOS);
+        assert!(pages[0].packets[0].starts_with(b"OpusHead"));
+        assert_eq!(pages[0].packets[0][9], 1);
+        assert!(pages[1].packets[0].starts_with(b"OpusTags"));
+
+        let audio = &pages[2..];
+        assert_eq!(audio.len(), 3);
+        assert_eq!(audio[0].granule, 50 * 960);
+        assert_eq!(audio[2].granule, 120 * 960);
+        assert_eq!(audio[2].header_type, HEADER_EOS);
+        let packets: Vec<_> = audio.iter().flat_map(|p| p.packets.clone()).collect();
+        assert_eq!(packets.len(), 120);
+        assert_eq!(packets[119], vec![119u8; 40]);
+        for (i, page) in pages.iter().enumerate() {
+            assert_eq!(page.seq, i as u32);
+        }
+    }
+
+    #[test]
+    fn large_packets_are_laced_and_split_across_pages() {
+        let mut writer = OggOpusWriter::new(1, 1, 48_000);
+        for _ in 0..3 {
+            writer.push_packet(&[9u8; 1_000], 960);
+        }
+        writer.push_packet(&[1u8; 255], 960);
+        let pages = parse_pages(&writer.finish());
+        let packets: Vec<_> = pages[2..].iter().flat_map(|p| p.packets.clone()).collect();
+        assert_eq!(packets.len(), 4);
+        assert_eq!(packets[0].len(), 1_000);
+        assert_eq!(packets[3].len(), 255);
+    }
+}
@@ -272,6 +272,55 @@ mod tests {
         assert_eq!(got, (0u64..50).collect::<Vec<u64>>());
     }
 
+    #[test]
+    fn relays_real_opus_packets_end_to_end() {
+        use crate::codec_opus::{OpusCodec, OpusPacket};
+
+        let relay = InMemoryRelay::new();
+        let config = SessionConfig {
+            moq_url: "https://moq.example.com/anon".to_string(),
+            relay_auth: "capv1_1111111111111111111111111111111111111111111111111111111111111111"
+                .to_string(),
+        };
+        let mut publisher = MediaSession::with_relay(config.clone(), relay.clone());
+        let mut subscriber = MediaSession::with_relay(config, relay);
+        publisher.connect().expect("publisher connect");
+        subscriber.connect().expect("subscriber connect");
+
+        let track = TrackAddress {
+            broadcast_path: "pika/calls/cid/pk".to_string(),
+            track_name: "audio0".to_string(),
+        };
+        let rx = subscriber.subscribe(&track).expect("subscribe");
+
+        let sender = OpusCodec::default();
+        let receiver = OpusCodec::default();
+        let frame_samples = sender.config().frame_samples();
+        for i in 0u64..25 {
+            let pcm: Vec<i16> = (0..frame_samples)
+                .map(|n| (((i as usize * frame_samples + n) % 96) as i16 - 48) * 100)
+                .collect();
+            let packet = sender.encode_pcm_i16(&pcm);
+            assert!(packet.0.len() < frame_samples * 2);
+            let frame = MediaFrame {
+                seq: i,
+                timestamp_us: i * 20_000,
+                keyframe: true,
+                payload: packet.0,
+            };
+            assert_eq!(publisher.publish(&track, frame).expect("publish"), 1);
+        }
+
+        for i in 0u64..25 {
+            let frame = rx
+                .recv_timeout(Duration::from_secs(1))
+                .expect("expected frame");
+            assert_eq!(frame.seq, i);
+            let pcm = receiver.decode_to_pcm_i16(&OpusPacket(frame.payload));
+            assert_eq!(pcm.len(), frame_samples);
+        }
+    }
+
     #[test]
     fn multi_subscribe_delivers_to_all_subscribers() {
         let relay = InMemoryRelay::new();
@@ -0,0 +1,492 @@
+//! Speech-to-text for call captions.
+//!
+//! [`SpeechSegmenter`] cuts decoded call audio at pauses in speech and hands
+//! out [`SpeechSegment`]s; an [`SttBackend`] turns each one into text. Engines
+//! can take longer than real time, so hosts run them off the media thread.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// RMS threshold (i16 scale) below which a frame is considered silence.
+/// 500 filters typical laptop/headset background noise while still catching
+/// normal speech (which is usually RMS 1000–10000+ on i16 scale).
+pub const DEFAULT_SILENCE_RMS_THRESHOLD: f64 = 500.0;
+
+/// Consecutive silence duration (ms) required to trigger a segment boundary.
+const SILENCE_DURATION_MS: u64 = 700;
+
+/// Safety cap: force-emit a segment even without silence after this duration.
+const MAX_SEGMENT_MS: u64 = 20_000;
+
+/// Minimum speech duration: don't emit very short fragments.
+const MIN_SEGMENT_MS: u64 = 500;
+
+/// Duration of a single Opus frame.
+const FRAME_MS: u64 = 20;
+
+/// Sample rate whisper.cpp expects its input WAV in.
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// A stretch of speech bounded by pauses, as interleaved PCM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechSegment {
+    pub sample_rate_hz: u32,
+    pub channels: u8,
+    pub pcm: Vec<i16>,
+}
+
+impl SpeechSegment {
+    pub fn duration_ms(&self) -> u64 {
+        let per_second = self.sample_rate_hz as u64 * self.channels.max(1) as u64;
+        if per_second == 0 {
+            return 0;
+        }
+        self.pcm.len() as u64 * 1000 / per_second
+    }
+}
+
+/// A speech-to-text engine. Returns an empty string for segments without words.
+pub trait SttBackend: Send {
+    fn transcribe(&mut self, segment: &SpeechSegment) -> Result<String, SttError>;
+}
+
+#[derive(Debug)]
+pub enum SttError {
+    Io(std::io::Error),
+    Engine(String),
+}
+
+impl std::fmt::Display for SttError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "stt io error: {err}"),
+            Self::Engine(msg) => write!(f, "stt engine error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SttError {}
+
+impl From<std::io::Error> for SttError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Returns canned lines in turn, one per segment. For tests and demos.
+#[derive(Debug, Clone)]
+pub struct FixtureStt {
+    lines: Vec<String>,
+    next: usize,
+}
+
+impl FixtureStt {
+    pub fn new(lines: Vec<String>) -> Self {
+        Self { lines, next: 0 }
+    }
+}
+
+impl Default for FixtureStt {
+    fn default() -> Self {
+        Self::new(vec!["fixture caption".to_string()])
+    }
+}
+
+impl SttBackend for FixtureStt {
+    fn transcribe(&mut self, _segment: &SpeechSegment) -> Result<String, SttError> {
+        if self.lines.is_empty() {
+            return Ok(String::new());
+        }
+        let line = self.lines[self.next % self.lines.len()].clone();
+        self.next = self.next.wrapping_add(1);
+        Ok(line)
+    }
+}
+
+/// Runs a local whisper.cpp binary (`whisper-cli`) on each segment. Nothing
+/// leaves the device; the model file has to be downloaded up front.
+#[derive(Debug, Clone)]
+pub struct WhisperCliStt {
+    binary: PathBuf,
+    model: PathBuf,
+    language: Option<String>,
+    scratch_dir: PathBuf,
+    seq: u64,
+}
+
+impl WhisperCliStt {
+    pub fn new(binary: impl Into<PathBuf>, model: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
+            model: model.into(),
+            language: None,
+            scratch_dir: std::env::temp_dir(),
+            seq: 0,
+        }
+    }
+
+    /// Pins the spoken language instead of letting whisper detect it per segment.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+}
+
+impl SttBackend for WhisperCliStt {
+    fn transcribe(&mut self, segment: &SpeechSegment) -> Result<String, SttError> {
+        let mono = to_mono(&segment.pcm, segment.channels);
+        let pcm = resample_linear(&mono, segment.sample_rate_hz, WHISPER_SAMPLE_RATE);
+        let wav_path =
+            self.scratch_dir
+                .join(format!("pika-stt-{}-{}.wav", std::process::id(), self.seq));
+        self.seq = self.seq.wrapping_add(1);
+        std::fs::write(&wav_path, wav_bytes(WHISPER_SAMPLE_RATE, 1, &pcm))?;
+
+        let output = Command::new(&self.binary)
+            .arg("-m")
+            .arg(&self.model)
+            .arg("-f")
+            .arg(&wav_path)
+            .args(["-nt", "-np", "-l"])
+            .arg(self.language.as_deref().unwrap_or("auto"))
+            .output();
+        let _ = std::fs::remove_file(&wav_path);
+        let output = output?;
+        if !output.status.success() {
+            return Err(SttError::Engine(format!(
+                "{} exited with {}: {}",
+                self.binary.display(),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(clean_whisper_output(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+}
+
+/// Joins whisper's output lines, dropping non-speech markers like `[BLANK_AUDIO]`.
+fn clean_whisper_output(stdout: &str) -> String {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| !(line.starts_with('[') && line.ends_with(']')))
+        .filter(|line| !(line.starts_with('(') && line.ends_with(')')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn to_mono(pcm: &[i16], channels: u8) -> Vec<i16> {
+    let channels = channels.max(1) as usize;
+    if channels == 1 {
+        return pcm.to_vec();
+    }
+    pcm.chunks_exact(channels)
+        .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / channels as i32) as i16)
+        .collect()
+}
+
+fn resample_linear(pcm: &[i16], from_hz: u32, to_hz: u32) -> Vec<i16> {
+    if from_hz == to_hz || from_hz == 0 || pcm.is_empty() {
+        return pcm.to_vec();
+    }
+    let out_len = (pcm.len() as u64 * to_hz as u64 / from_hz as u64) as usize;
+    let step = from_hz as f64 / to_hz as f64;
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * step;
+            let idx = pos as usize;
+            let frac = pos - idx as f64;
+            let a = pcm[idx.min(pcm.len() - 1)] as f64;
+            let b = pcm[(idx + 1).min(pcm.len() - 1)] as f64;
+            (a + (b - a) * frac).round() as i16
+        })
+        .collect()
+}
+
+/// 16-bit PCM WAV file bytes.
+pub fn wav_bytes(sample_rate_hz: u32, channels: u8, pcm: &[i16]) -> Vec<u8> {
+    let data_len = (pcm.len() * 2) as u32;
+    let block_align = channels as u16 * 2;
+    let mut out = Vec::with_capacity(44 + pcm.len() * 2);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&(channels as u16).to_le_bytes());
+    out.extend_from_slice(&sample_rate_hz.to_le_bytes());
+    out.extend_from_slice(&(sample_rate_hz * block_align as u32).to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in pcm {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    out
+}
+
+/// Silence-based audio segmenter.
+///
+/// Buffers PCM frames and emits segments at natural speech boundaries
+/// (detected by consecutive silence frames exceeding a threshold).
+#[derive(Debug)]
+pub struct SpeechSegmenter {
+    sample_rate_hz: u32,
+    channels: u8,
+    pcm: Vec<i16>,
+    /// Number of consecutive silence frames seen so far.
+    consecutive_silence_frames: u64,
+    /// Total frames ingested since the last segment was emitted.
+    frames_since_emit: u64,
+    /// Number of speech (non-silence) frames in the current segment.
+    speech_frames: u64,
+    /// Whether we have seen any speech frames in the current segment.
+    has_speech: bool,
+    /// Samples per frame (sample_rate * channels * FRAME_MS / 1000).
+    samples_per_frame: usize,
+    /// RMS threshold for silence classification.
+    rms_threshold: f64,
+    /// Number of silence frames required to trigger a segment boundary.
+    silence_frame_threshold: u64,
+    /// Maximum frames before force-emit.
+    max_frames: u64,
+    /// Minimum speech frames before we allow emit.
+    min_frames: u64,
+}
+
+impl SpeechSegmenter {
+    pub fn new(sample_rate_hz: u32, channels: u8) -> Self {
+        let samples_per_frame =
+            (sample_rate_hz as u64 * channels as u64 * FRAME_MS / 1000) as usize;
+        Self {
+            sample_rate_hz,
+            channels,
+            pcm: Vec::new(),
+            consecutive_silence_frames: 0,
+            frames_since_emit: 0,
+            speech_frames: 0,
+            has_speech: false,
+            samples_per_frame: samples_per_frame.max(1),
+            rms_threshold: DEFAULT_SILENCE_RMS_THRESHOLD,
+            silence_frame_threshold: SILENCE_DURATION_MS / FRAME_MS,
+            max_frames: MAX_SEGMENT_MS / FRAME_MS,
+            min_frames: MIN_SEGMENT_MS / FRAME_MS,
+        }
+    }
+
+    pub fn with_rms_threshold(mut self, rms_threshold: f64) -> Self {
+        self.rms_threshold = rms_threshold;
+        self
+    }
+
+    pub fn push(&mut self, pcm: &[i16]) {
+        self.pcm.extend_from_slice(pcm);
+        // Process in frame-sized chunks for RMS classification.
+        let frames_available = pcm.len() / self.samples_per_frame;
+        // We only need to classify the newly pushed frames.
+        // The frames start at offset (total_pcm - newly_pushed).
+        let start_sample = self.pcm.len() - pcm.len();
+        for i in 0..frames_available {
+            let frame_start = start_sample + i * self.samples_per_frame;
+            let frame_end = frame_start + self.samples_per_frame;
+            let frame = &self.pcm[frame_start..frame_end];
+            let rms = compute_rms(frame);
+            self.frames_since_emit += 1;
+
+            if rms < self.rms_threshold {
+                self.consecutive_silence_frames += 1;
+            } else {
+                self.consecutive_silence_frames = 0;
+                self.has_speech = true;
+                self.speech_frames += 1;
+            }
+        }
+    }
+
+    /// Returns a segment if a boundary was detected.
+    pub fn pop_segment(&mut self) -> Option<SpeechSegment> {
+        // Safety cap: force-emit if we've buffered too long, but only if
+        // there's actual speech. Pure background noise gets discarded.
+        if self.frames_since_emit >= self.max_frames && !self.pcm.is_empty() {
+            if self.has_speech {
+                let pcm = self.drain_segment();
+                return Some(self.segment(pcm));
+            }
+            // Discard noise-only buffer to prevent unbounded growth.
+            self.drain_segment();
+            return None;
+        }
+
+        // Silence-based boundary: emit if we have enough speech and enough silence.
+        if self.has_speech
+            && self.consecutive_silence_frames >= self.silence_frame_threshold
+            && self.speech_frames >= self.min_frames
+        {
+            // Emit everything up to the start of the silence gap.
+            let silence_samples = self.consecutive_silence_frames as usize * self.samples_per_frame;
+            let speech_end = self.pcm.len().saturating_sub(silence_samples);
+            if speech_end == 0 {
+                let pcm = self.drain_segment();
+                return Some(self.segment(pcm));
+            }
+            let pcm: Vec<i16> = self.pcm.drain(..speech_end).collect();
+            // Reset state but keep the trailing silence in the buffer for the next segment.
+            self.consecutive_silence_frames = 0;
+            self.frames_since_emit = 0;
+            self.speech_frames = 0;
+            self.has_speech = false;
+            return Some(self.segment(pcm));
+        }
+
+        None
+    }
+
+    /// Emits whatever speech is still buffered, e.g. when the call ends.
+    pub fn flush(&mut self) -> Option<SpeechSegment> {
+        if self.pcm.is_empty() || !self.has_speech {
+            self.drain_segment();
+            return None;
+        }
+        let pcm = self.drain_segment();
+        Some(self.segment(pcm))
+    }
+
+    fn drain_segment(&mut self) -> Vec<i16> {
+        self.consecutive_silence_frames = 0;
+        self.frames_since_emit = 0;
+        self.speech_frames = 0;
+        self.has_speech = false;
+        self.pcm.drain(..).collect()
+    }
+
+    fn segment(&self, pcm: Vec<i16>) -> SpeechSegment {
+        SpeechSegment {
+            sample_rate_hz: self.sample_rate_hz,
+            channels: self.channels,
+            pcm,
+        }
+    }
+}
+
+fn compute_rms(samples: &[i16]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum_sq / samples.len() as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES_PER_FRAME: usize = 960;
+
+    /// Generate synthetic speech-like PCM (high amplitude).
+    fn synthetic_speech(samples: usize) -> Vec<i16> {
+        (0..samples)
+            .map(|i| {
+                // ~1kHz sine wave at ~half amplitude
+                let t = i as f64 / 48_000.0;
+                (f64::sin(t * 2.0 * std::f64::consts::PI * 1000.0) * 10_000.0) as i16
+            })
+            .collect()
+    }
+
+    fn synthetic_silence(samples: usize) -> Vec<i16> {
+        vec![0i16; samples]
+    }
+
+    #[test]
+    fn segmenter_emits_on_silence_gap() {
+        let mut segmenter = SpeechSegmenter::new(48_000, 1);
+
+        // 1 second of speech, then 800ms of silence (> 700ms threshold).
+        segmenter.push(&synthetic_speech(SAMPLES_PER_FRAME * 50));
+        assert!(segmenter.pop_segment().is_none(), "should not emit yet");
+        segmenter.push(&synthetic_silence(SAMPLES_PER_FRAME * 40));
+
+        let segment = segmenter.pop_segment().expect("emit after silence gap");
+        assert_eq!(segment.sample_rate_hz, 48_000);
+        assert_eq!(segment.duration_ms(), 1_000);
+    }
+
+    #[test]
+    fn segmenter_force_emits_at_max() {
+        let mut segmenter = SpeechSegmenter::new(48_000, 1);
+        let max_frames = (MAX_SEGMENT_MS / FRAME_MS) as usize;
+        segmenter.push(&synthetic_speech(SAMPLES_PER_FRAME * (max_frames + 1)));
+        assert!(
+            segmenter.pop_segment().is_some(),
+            "should force-emit at max segment duration"
+        );
+    }
+
+    #[test]
+    fn segmenter_min_segment_respected() {
+        let mut segmenter = SpeechSegmenter::new(48_000, 1);
+
+        // 40ms of speech is below MIN_SEGMENT_MS, however long the pause after it.
+        segmenter.push(&synthetic_speech(SAMPLES_PER_FRAME * 2));
+        segmenter.push(&synthetic_silence(SAMPLES_PER_FRAME * 40));
+        assert!(
+            segmenter.pop_segment().is_none(),
+            "should not emit below min segment duration"
+        );
+    }
+
+    #[test]
+    fn flush_returns_remaining_speech_only() {
+        let mut segmenter = SpeechSegmenter::new(48_000, 1);
+        segmenter.push(&synthetic_speech(SAMPLES_PER_FRAME * 30));
+        assert!(segmenter.pop_segment().is_none());
+        assert!(segmenter.flush().is_some(), "flush should return speech");
+
+        segmenter.push(&synthetic_silence(SAMPLES_PER_FRAME * 30));
+        assert!(
+            segmenter.flush().is_none(),
+            "flush should return None for silence-only"
+        );
+    }
+
+    #[test]
+    fn fixture_backend_cycles_lines() {
+        let mut stt = FixtureStt::new(vec!["one".into(), "two".into()]);
+        let segment = SpeechSegment {
+            sample_rate_hz: 48_000,
+            channels: 1,
+            pcm: synthetic_speech(SAMPLES_PER_FRAME),
+        };
+        let lines: Vec<String> = (0..3).map(|_| stt.transcribe(&segment).unwrap()).collect();
+        assert_eq!(lines, ["one", "two", "one"]);
+    }
+
+    #[test]
+    fn whisper_input_is_16k_mono_wav() {
+        let stereo: Vec<i16> = (0..4_800).flat_map(|i| [i as i16, -(i as i16)]).collect();
+        let mono = to_mono(&stereo, 2);
+        assert_eq!(mono.len(), 4_800);
+        assert!(mono.iter().all(|&s| s == 0));
+
+        let resampled = resample_linear(&synthetic_speech(48_000), 48_000, WHISPER_SAMPLE_RATE);
+        assert_eq!(resampled.len(), 16_000);
+
+        let wav = wav_bytes(WHISPER_SAMPLE_RATE, 1, &resampled);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(wav.len(), 44 + 32_000);
+    }
+
+    #[test]
+    fn whisper_output_drops_non_speech_markers() {
+        let stdout = "\n [BLANK_AUDIO]\n Hello there.\n (wind blowing)\n How are you?\n";
+        assert_eq!(clean_whisper_output(stdout), "Hello there. How are you?");
+        assert_eq!(clean_whisper_output(" [BLANK_AUDIO]\n"), "");
+    }
+}
@@ -2,6 +2,8 @@ const PARTICIPANT_LABEL_HEX_LEN: usize = 64;
 
 /// Shared video encoding parameters used by all platforms.
 /// Platform capture/encode code should reference these rather than hardcoding values.
+/// They are the starting (best) profile; during a call the sender steps down from
+/// here as [`crate::video_adapt`] sees congestion.
 pub mod video_params {
     /// Target frame rate for video capture and encoding (frames per second).
     pub const FPS: u32 = 30;
@@ -15,6 +17,23 @@ pub mod video_params {
     pub const HEIGHT: u32 = 720;
 }
 
+/// Screen share encoding parameters. Screens are mostly static text, so they
+/// keep a high resolution and spend the bitrate on few, sharp frames instead of
+/// motion. Screen share has no rate adaptation.
+pub mod screen_params {
+    /// Target frame rate for screen capture and encoding (frames per second).
+    pub const FPS: u32 = 5;
+    /// Target encoded bitrate in bits per second (1.2 Mbps).
+    pub const BITRATE_BPS: u32 = 1_200_000;
+    /// Keyframe interval in frames (every 2 seconds at 5fps). Nobody asks for
+    /// screen keyframes, so this bounds how long a viewer waits after joining.
+    pub const KEYFRAME_INTERVAL: u32 = 10;
+    /// Maximum capture width; larger screens are downscaled to fit.
+    pub const WIDTH: u32 = 1920;
+    /// Maximum capture height.
+    pub const HEIGHT: u32 = 1080;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TrackSpec {
     pub name: String,
@@ -44,6 +63,16 @@ pub fn default_video_track() -> TrackSpec {
     }
 }
 
+pub fn default_screen_track() -> TrackSpec {
+    TrackSpec {
+        name: "screen0".to_string(),
+        codec: "h264".to_string(),
+        sample_rate: 90_000,
+        channels: 0,
+        frame_ms: 200,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TrackAddress {
     pub broadcast_path: String,
@@ -0,0 +1,811 @@
+//! Congestion-aware video sending.
+//!
+//! Receivers measure loss, late frames and jitter on each sender's video track
+//! and report them back over the call's data track about once a second.
+//! Senders walk a ladder of encoder profiles: down while reports show
+//! congestion, back up after a run of clean ones, with keyframes requested
+//! whenever the decoder on the other end is likely out of sync.
+
+use std::collections::VecDeque;
+
+use crate::tracks::video_params;
+
+/// Encoder settings the platform capture pipeline should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoProfile {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub bitrate_bps: u32,
+}
+
+/// Sender profiles from best to most conservative. The first entry matches
+/// [`video_params`], which is where every call starts.
+pub const VIDEO_PROFILES: [VideoProfile; 5] = [
+    VideoProfile {
+        width: video_params::WIDTH,
+        height: video_params::HEIGHT,
+        fps: video_params::FPS,
+        bitrate_bps: video_params::BITRATE_BPS,
+    },
+    VideoProfile {
+        width: 960,
+        height: 540,
+        fps: 30,
+        bitrate_bps: 900_000,
+    },
+    VideoProfile {
+        width: 640,
+        height: 360,
+        fps: 24,
+        bitrate_bps: 500_000,
+    },
+    VideoProfile {
+        width: 480,
+        height: 270,
+        fps: 15,
+        bitrate_bps: 250_000,
+    },
+    VideoProfile {
+        width: 320,
+        height: 180,
+        fps: 15,
+        bitrate_bps: 120_000,
+    },
+];
+
+/// How often receivers send a [`ReceiverReport`] for each sender.
+pub const REPORT_INTERVAL_US: u64 = 1_000_000;
+/// Receivers don't ask the same sender for keyframes more often than this.
+pub const KEYFRAME_REQUEST_INTERVAL_US: u64 = 1_000_000;
+
+/// Frames arriving this much slower than the fastest recent transit are late.
+const LATE_THRESHOLD_US: i64 = 150_000;
+/// Report intervals the fastest-transit baseline is taken over, so a route or
+/// clock change doesn't leave it stuck low forever.
+const TRANSIT_BASELINE_INTERVALS: usize = 10;
+/// Transit-time jumps larger than this are a discontinuity (camera paused),
+/// not jitter.
+const MAX_TRANSIT_DELTA_US: i64 = 500_000;
+
+const MAX_LOSS_RATIO: f32 = 0.05;
+const MAX_LATE_RATIO: f32 = 0.10;
+const MAX_JITTER_US: u32 = 60_000;
+/// Clean reports needed before the first probe up the ladder.
+const PROBE_AFTER_REPORTS: u32 = 5;
+/// Each failed probe doubles the wait before the next one, up to this.
+const MAX_PROBE_AFTER_REPORTS: u32 = 60;
+/// A probe that survives this many reports is the new normal.
+const PROBE_CONFIRM_REPORTS: u32 = 3;
+/// Reports ignored after stepping down; they still cover the old profile
+/// and the queue it built up.
+const STEP_DOWN_HOLD_REPORTS: u32 = 1;
+const MIN_KEYFRAME_INTERVAL_US: u64 = 500_000;
+
+const FEEDBACK_VERSION: u8 = 1;
+const FEEDBACK_KIND_REPORT: u8 = 1;
+const FEEDBACK_KIND_KEYFRAME_REQUEST: u8 = 2;
+const REPORT_LEN: usize = 16;
+
+/// What a receiver saw of one sender's video since its previous report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiverReport {
+    /// Frames the sequence numbers say should have arrived.
+    pub expected: u32,
+    pub received: u32,
+    pub late: u32,
+    /// Smoothed transit jitter (RFC 3550 estimator).
+    pub jitter_us: u32,
+}
+
+impl ReceiverReport {
+    pub fn lost(&self) -> u32 {
+        self.expected.saturating_sub(self.received)
+    }
+
+    pub fn loss_ratio(&self) -> f32 {
+        ratio(self.lost(), self.expected)
+    }
+
+    pub fn late_ratio(&self) -> f32 {
+        ratio(self.late, self.received)
+    }
+
+    pub fn is_congested(&self) -> bool {
+        self.loss_ratio() > MAX_LOSS_RATIO
+            || self.late_ratio() > MAX_LATE_RATIO
+            || self.jitter_us > MAX_JITTER_US
+    }
+}
+
+fn ratio(part: u32, whole: u32) -> f32 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f32 / whole as f32
+    }
+}
+
+/// Receive-side accounting for one sender's video track.
+#[derive(Debug, Clone, Default)]
+pub struct ReceiverStats {
+    interval_start: Option<u64>,
+    highest_seq: Option<u64>,
+    received: u32,
+    late: u32,
+    interval_min_transit: Option<i64>,
+    min_transits: VecDeque<i64>,
+    last_transit: Option<i64>,
+    jitter_us: f64,
+}
+
+impl ReceiverStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a frame that decrypted. `timestamp_us` is the sender's capture
+    /// clock and `arrival_us` ours; only their differences matter. Returns true
+    /// when frames before this one went missing.
+    pub fn on_frame(&mut self, seq: u64, timestamp_us: u64, arrival_us: u64) -> bool {
+        if self.interval_start.is_none() {
+            self.interval_start = Some(seq);
+        }
+        let gap = self
+            .highest_seq
+            .is_some_and(|highest| seq > highest.saturating_add(1));
+        if self.highest_seq.is_none_or(|highest| seq > highest) {
+            self.highest_seq = Some(seq);
+        }
+        self.received = self.received.saturating_add(1);
+
+        let transit = arrival_us as i64 - timestamp_us as i64;
+        if let Some(last) = self.last_transit {
+            let delta = (transit - last).abs();
+            if delta > MAX_TRANSIT_DELTA_US {
+                self.min_transits.clear();
+                self.interval_min_transit = None;
+            } else {
+                self.jitter_us += (delta as f64 - self.jitter_us) / 16.0;
+            }
+        }
+        self.last_transit = Some(transit);
+        self.interval_min_transit = Some(
+            self.interval_min_transit
+                .map_or(transit, |min| min.min(transit)),
+        );
+        let baseline = self
+            .min_transits
+            .iter()
+            .copied()
+            .chain(self.interval_min_transit)
+            .min()
+            .unwrap_or(transit);
+        if transit - baseline > LATE_THRESHOLD_US {
+            self.late = self.late.saturating_add(1);
+        }
+        gap
+    }
+
+    /// Closes the current interval. `None` until the first frame arrives.
+    pub fn take_report(&mut self) -> Option<ReceiverReport> {
+        let start = self.interval_start?;
+        let highest = self.highest_seq?;
+        let next = highest.saturating_add(1);
+        let report = ReceiverReport {
+            expected: u32::try_from(next.saturating_sub(start)).unwrap_or(u32::MAX),
+            received: self.received,
+            late: self.late,
+            jitter_us: self.jitter_us as u32,
+        };
+        self.interval_start = Some(next);
+        self.received = 0;
+        self.late = 0;
+        if let Some(min) = self.interval_min_transit.take() {
+            self.min_transits.push_back(min);
+            while self.min_transits.len() > TRANSIT_BASELINE_INTERVALS {
+                self.min_transits.pop_front();
+            }
+        }
+        Some(report)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFeedback {
+    Report(ReceiverReport),
+    KeyframeRequest,
+}
+
+/// Receiver-to-sender message carried on the call data track. `sender` names
+/// whose video it is about, since every member hears every data track in a
+/// group call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackMessage {
+    pub sender: String,
+    pub feedback: VideoFeedback,
+}
+
+impl FeedbackMessage {
+    pub fn encode(&self) -> Vec<u8> {
+        let sender = self.sender.as_bytes();
+        let sender = &sender[..sender.len().min(u8::MAX as usize)];
+        let mut out = Vec::with_capacity(3 + sender.len() + REPORT_LEN);
+        out.push(FEEDBACK_VERSION);
+        match self.feedback {
+            VideoFeedback::Report(_) => out.push(FEEDBACK_KIND_REPORT),
+            VideoFeedback::KeyframeRequest => out.push(FEEDBACK_KIND_KEYFRAME_REQUEST),
+        }
+        out.push(sender.len() as u8);
+        out.extend_from_slice(sender);
+        if let VideoFeedback::Report(report) = self.feedback {
+            for value in [
+                report.expected,
+                report.received,
+                report.late,
+                report.jitter_us,
+            ] {
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let [version, kind, sender_len, rest @ ..] = bytes else {
+            return Err("feedback message too short".to_string());
+        };
+        if *version != FEEDBACK_VERSION {
+            return Err(format!("unsupported feedback version {version}"));
+        }
+        let sender_len = *sender_len as usize;
+        if rest.len() < sender_len {
+            return Err("feedback sender truncated".to_string());
+        }
+        let (sender, body) = rest.split_at(sender_len);
+        let sender = std::str::from_utf8(sender)
+            .map_err(|_| "feedback sender is not utf-8".to_string())?
+            .to_string();
+        let feedback = match *kind {
+            FEEDBACK_KIND_REPORT => {
+                if body.len() < REPORT_LEN {
+                    return Err("feedback report truncated".to_string());
+                }
+                let field = |i: usize| {
+                    u32::from_be_bytes([
+                        body[i * 4],
+                        body[i * 4 + 1],
+                        body[i * 4 + 2],
+                        body[i * 4 + 3],
+                    ])
+                };
+                VideoFeedback::Report(ReceiverReport {
+                    expected: field(0),
+                    received: field(1),
+                    late: field(2),
+                    jitter_us: field(3),
+                })
+            }
+            FEEDBACK_KIND_KEYFRAME_REQUEST => VideoFeedback::KeyframeRequest,
+            other => return Err(format!("unknown feedback kind {other}")),
+        };
+        Ok(Self { sender, feedback })
+    }
+}
+
+/// Sender-side profile selection driven by receiver reports.
+///
+/// Any congested report steps one profile down. After enough clean reports it
+/// probes one step up; a probe that fails right away doubles the wait before
+/// the next, so a link that sits between two profiles settles on the lower one
+/// instead of oscillating.
+#[derive(Debug, Clone)]
+pub struct VideoRateController {
+    level: usize,
+    clean_reports: u32,
+    probe_after: u32,
+    /// Reports seen since the last step up, while it is still on trial.
+    probing: Option<u32>,
+    hold: u32,
+    impaired: bool,
+    keyframe_pending: bool,
+    last_keyframe_us: Option<u64>,
+}
+
+impl Default for VideoRateController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VideoRateController {
+    pub fn new() -> Self {
+        Self {
+            level: 0,
+            clean_reports: 0,
+            probe_after: PROBE_AFTER_REPORTS,
+            probing: None,
+            hold: 0,
+            impaired: false,
+            keyframe_pending: false,
+            last_keyframe_us: None,
+        }
+    }
+
+    pub fn profile(&self) -> VideoProfile {
+        VIDEO_PROFILES[self.level]
+    }
+
+    /// Index into [`VIDEO_PROFILES`]; 0 is the best.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Applies a receiver report. Returns true when the profile changed.
+    pub fn on_report(&mut self, report: &ReceiverReport) -> bool {
+        if report.expected == 0 && report.received == 0 {
+            // Camera off or nothing sent yet; says nothing about the link.
+            return false;
+        }
+        if self.hold > 0 {
+            self.hold -= 1;
+            return false;
+        }
+
+        if report.is_congested() {
+            self.clean_reports = 0;
+            self.impaired = true;
+            if let Some(reports) = self.probing.take() {
+                if reports < PROBE_CONFIRM_REPORTS {
+                    self.probe_after = (self.probe_after * 2).min(MAX_PROBE_AFTER_REPORTS);
+                }
+            }
+            if self.level + 1 < VIDEO_PROFILES.len() {
+                self.level += 1;
+                self.hold = STEP_DOWN_HOLD_REPORTS;
+                self.keyframe_pending = true;
+                return true;
+            }
+            return false;
+        }
+
+        if report.lost() > 0 {
+            self.impaired = true;
+        } else if self.impaired {
+            // Whatever was lost left the remote decoder patching over holes.
+            self.impaired = false;
+            self.keyframe_pending = true;
+        }
+        if let Some(reports) = self.probing.as_mut() {
+            *reports += 1;
+            if *reports >= PROBE_CONFIRM_REPORTS {
+                self.probing = None;
+                self.probe_after = PROBE_AFTER_REPORTS;
+            }
+        }
+        self.clean_reports += 1;
+        if self.level > 0 && self.clean_reports >= self.probe_after {
+            self.level -= 1;
+            self.clean_reports = 0;
+            self.probing = Some(0);
+            self.keyframe_pending = true;
+            return true;
+        }
+        false
+    }
+
+    pub fn on_keyframe_request(&mut self) {
+        self.keyframe_pending = true;
+    }
+
+    /// Whether the encoder should emit a keyframe now. Rate limited so a burst
+    /// of requests from several receivers costs one keyframe.
+    pub fn take_keyframe_request(&mut self, now_us: u64) -> bool {
+        if !self.keyframe_pending {
+            return false;
+        }
+        if self
+            .last_keyframe_us
+            .is_some_and(|last| now_us.saturating_sub(last) < MIN_KEYFRAME_INTERVAL_US)
+        {
+            return false;
+        }
+        self.keyframe_pending = false;
+        self.last_keyframe_us = Some(now_us);
+        true
+    }
+}
+
+/// Whether an H.264 Annex B access unit contains an IDR slice.
+pub fn h264_is_keyframe(annexb: &[u8]) -> bool {
+    let mut zeros = 0usize;
+    let mut i = 0usize;
+    while i < annexb.len() {
+        let byte = annexb[i];
+        if byte == 1 && zeros >= 2 {
+            if let Some(header) = annexb.get(i + 1) {
+                if header & 0x1f == 5 {
+                    return true;
+                }
+            }
+            zeros = 0;
+        } else if byte == 0 {
+            zeros += 1;
+        } else {
+            zeros = 0;
+        }
+        i += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{InMemoryRelay, MediaFrame};
+
+    const RELAY_AUTH: &str =
+        "capv1_1111111111111111111111111111111111111111111111111111111111111111";
+    const SENDER: &str = "aa11";
+
+    fn report(expected: u32, received: u32) -> ReceiverReport {
+        ReceiverReport {
+            expected,
+            received,
+            late: 0,
+            jitter_us: 2_000,
+        }
+    }
+
+    #[test]
+    fn feedback_roundtrips() {
+        for feedback in [
+            VideoFeedback::Report(ReceiverReport {
+                expected: 30,
+                received: 27,
+                late: 2,
+                jitter_us: 14_500,
+            }),
+            VideoFeedback::KeyframeRequest,
+        ] {
+            let msg = FeedbackMessage {
+                sender: SENDER.to_string(),
+                feedback,
+            };
+            assert_eq!(FeedbackMessage::decode(&msg.encode()), Ok(msg));
+        }
+        assert!(FeedbackMessage::decode(&[]).is_err());
+        assert!(FeedbackMessage::decode(&[9, 1, 0]).is_err());
+        assert!(FeedbackMessage::decode(&[FEEDBACK_VERSION, 1, 0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn receiver_stats_count_loss_and_late_frames() {
+        let mut stats = ReceiverStats::new();
+        assert_eq!(stats.take_report(), None);
+        let mut gaps = 0;
+        for seq in 0..30u64 {
+            if seq % 10 == 5 {
+                continue;
+            }
+            // Three frames stuck behind a 200 ms queue.
+            let queued = if (20..23).contains(&seq) { 200_000 } else { 0 };
+            let ts = seq * 33_000;
+            if stats.on_frame(seq, ts, ts + 40_000 + queued) {
+                gaps += 1;
+            }
+        }
+        assert_eq!(gaps, 3);
+        let report = stats.take_report().expect("report");
+        assert_eq!(report.expected, 30);
+        assert_eq!(report.received, 27);
+        assert_eq!(report.lost(), 3);
+        assert_eq!(report.late, 3);
+        assert!(report.is_congested());
+
+        for seq in 30..60u64 {
+            let ts = seq * 33_000;
+            stats.on_frame(seq, ts, ts + 40_000);
+        }
+        let report = stats.take_report().expect("report");
+        assert_eq!((report.expected, report.received, report.late), (30, 30, 0));
+    }
+
+    #[test]
+    fn controller_steps_down_and_backs_off_failed_probes() {
+        let mut rate = VideoRateController::new();
+        assert_eq!(rate.profile(), VIDEO_PROFILES[0]);
+
+        assert!(rate.on_report(&report(30, 20)));
+        assert_eq!(rate.level(), 1);
+        assert!(rate.take_keyframe_request(0));
+        // The next report still describes the old profile.
+        assert!(!rate.on_report(&report(30, 20)));
+        assert!(rate.on_report(&report(30, 20)));
+        assert_eq!(rate.level(), 2);
+        assert!(!rate.on_report(&report(30, 30)));
+        assert!(rate.take_keyframe_request(1_000_000));
+
+        // Recovery forces a keyframe even without a profile change.
+        assert!(!rate.on_report(&report(24, 24)));
+        assert!(rate.take_keyframe_request(2_000_000));
+        for _ in 0..PROBE_AFTER_REPORTS - 2 {
+            assert!(!rate.on_report(&report(24, 24)));
+        }
+        assert!(rate.on_report(&report(24, 24)));
+        assert_eq!(rate.level(), 1);
+
+        // The probe fails immediately, so the next one waits twice as long.
+        assert!(rate.on_report(&report(30, 25)));
+        assert_eq!(rate.level(), 2);
+        assert!(!rate.on_report(&report(30, 25)));
+        for _ in 0..PROBE_AFTER_REPORTS * 2 - 1 {
+            assert!(!rate.on_report(&report(24, 24)));
+        }
+        assert!(rate.on_report(&report(24, 24)));
+        assert_eq!(rate.level(), 1);
+    }
+
+    #[test]
+    fn controller_ignores_idle_reports_and_rate_limits_keyframes() {
+        let mut rate = VideoRateController::new();
+        assert!(!rate.on_report(&ReceiverReport::default()));
+        assert_eq!(rate.level(), 0);
+        assert!(!rate.take_keyframe_request(0));
+
+        rate.on_keyframe_request();
+        assert!(rate.take_keyframe_request(0));
+        rate.on_keyframe_request();
+        assert!(!rate.take_keyframe_request(100_000));
+        assert!(rate.take_keyframe_request(MIN_KEYFRAME_INTERVAL_US));
+    }
+
+    #[test]
+    fn detects_idr_in_annexb() {
+        let sps_pps_idr = [
+            0, 0, 0, 1, 0x67, 0x42, 0, 0, 1, 0x68, 0xce, 0, 0, 1, 0x65, 0x88,
+        ];
+        let delta = [0, 0, 0, 1, 0x41, 0x9a, 0x00, 0x00, 0x05];
+        assert!(h264_is_keyframe(&sps_pps_idr));
+        assert!(!h264_is_keyframe(&delta));
+        assert!(!h264_is_keyframe(&[]));
+    }
+
+    /// Bottleneck link: frames queue behind each other at `capacity_bps` and
+    /// are tail-dropped once the queue holds more than `max_queue_us`.
+    struct ImpairedLink {
+        capacity_bps: u64,
+        propagation_us: u64,
+        max_queue_us: u64,
+        busy_until_us: u64,
+    }
+
+    impl ImpairedLink {
+        fn new(capacity_bps: u64) -> Self {
+            Self {
+                capacity_bps,
+                propagation_us: 40_000,
+                max_queue_us: 250_000,
+                busy_until_us: 0,
+            }
+        }
+
+        /// Arrival time of a frame handed to the link at `now_us`, or `None` if dropped.
+        fn send(&mut self, now_us: u64, bytes: usize) -> Option<u64> {
+            let start = self.busy_until_us.max(now_us);
+            if start - now_us > self.max_queue_us {
+                return None;
+            }
+            self.busy_until_us = start + bytes as u64 * 8 * 1_000_000 / self.capacity_bps;
+            Some(self.busy_until_us + self.propagation_us)
+        }
+    }
+
+    /// One sender and one receiver talking through an [`InMemoryRelay`]: video
+    /// crosses the impaired link, feedback comes back on the data track.
+    struct Harness {
+        relay: InMemoryRelay,
+        video: crate::subscription::MediaFrameSubscription,
+        data: crate::subscription::MediaFrameSubscription,
+        link: ImpairedLink,
+        in_flight: VecDeque<(u64, MediaFrame)>,
+        rate: VideoRateController,
+        stats: ReceiverStats,
+        now_us: u64,
+        next_frame_us: u64,
+        next_report_us: u64,
+        last_keyframe_request_us: Option<u64>,
+        seq: u64,
+        data_seq: u64,
+        keyframes: u64,
+        levels: Vec<usize>,
+    }
+
+    impl Harness {
+        fn new(capacity_bps: u64) -> Self {
+            let relay = InMemoryRelay::new();
+            let video = relay.subscribe("call/sender/video0", RELAY_AUTH).unwrap();
+            let data = relay.subscribe("call/receiver/data0", RELAY_AUTH).unwrap();
+            Self {
+                relay,
+                video,
+                data,
+                link: ImpairedLink::new(capacity_bps),
+                in_flight: VecDeque::new(),
+                rate: VideoRateController::new(),
+                stats: ReceiverStats::new(),
+                now_us: 0,
+                next_frame_us: 0,
+                next_report_us: REPORT_INTERVAL_US,
+                last_keyframe_request_us: None,
+                seq: 0,
+                data_seq: 0,
+                keyframes: 0,
+                levels: Vec::new(),
+            }
+        }
+
+        fn send_feedback(&mut self, feedback: VideoFeedback) {
+            let payload = FeedbackMessage {
+                sender: SENDER.to_string(),
+                feedback,
+            }
+            .encode();
+            let frame = MediaFrame {
+                seq: self.data_seq,
+                timestamp_us: self.now_us,
+                keyframe: true,
+                payload,
+            };
+            self.data_seq += 1;
+            self.relay
+                .publish("call/receiver/data0", RELAY_AUTH, frame)
+                .unwrap();
+        }
+
+        /// Advances the simulation in 1 ms steps; records the sender's level
+        /// once per simulated second.
+        fn run_secs(&mut self, secs: u64) {
+            let end = self.now_us + secs * 1_000_000;
+            while self.now_us < end {
+                // Sender: encode at the current profile, keyframes 4x larger.
+                if self.now_us >= self.next_frame_us {
+                    let profile = self.rate.profile();
+                    let keyframe = self.rate.take_keyframe_request(self.now_us);
+                    if keyframe {
+                        self.keyframes += 1;
+                    }
+                    let mut bytes = (profile.bitrate_bps / profile.fps / 8) as usize;
+                    if keyframe {
+                        bytes *= 4;
+                    }
+                    let frame = MediaFrame {
+                        seq: self.seq,
+                        timestamp_us: self.now_us,
+                        keyframe,
+                        payload: vec![0u8; bytes],
+                    };
+                    self.seq += 1;
+                    if let Some(arrival) = self.link.send(self.now_us, bytes) {
+                        self.in_flight.push_back((arrival, frame));
+                    }
+                    self.next_frame_us += 1_000_000 / profile.fps as u64;
+                }
+                while self
+                    .in_flight
+                    .front()
+                    .is_some_and(|(at, _)| *at <= self.now_us)
+                {
+                    let (_, frame) = self.in_flight.pop_front().unwrap();
+                    self.relay
+                        .publish("call/sender/video0", RELAY_AUTH, frame)
+                        .unwrap();
+                }
+
+                // Receiver.
+                let mut request_keyframe = false;
+                while let Ok(frame) = self.video.try_recv() {
+                    if self
+                        .stats
+                        .on_frame(frame.seq, frame.timestamp_us, self.now_us)
+                    {
+                        request_keyframe = true;
+                    }
+                }
+                if request_keyframe
+                    && self
+                        .last_keyframe_request_us
+                        .is_none_or(|last| self.now_us - last >= KEYFRAME_REQUEST_INTERVAL_US)
+                {
+                    self.last_keyframe_request_us = Some(self.now_us);
+                    self.send_feedback(VideoFeedback::KeyframeRequest);
+                }
+                if self.now_us >= self.next_report_us {
+                    self.next_report_us += REPORT_INTERVAL_US;
+                    if let Some(report) = self.stats.take_report() {
+                        self.send_feedback(VideoFeedback::Report(report));
+                    }
+                }
+
+                // Sender reads feedback.
+                while let Ok(frame) = self.data.try_recv() {
+                    let msg = FeedbackMessage::decode(&frame.payload).unwrap();
+                    assert_eq!(msg.sender, SENDER);
+                    match msg.feedback {
+                        VideoFeedback::Report(report) => {
+                            self.rate.on_report(&report);
+                        }
+                        VideoFeedback::KeyframeRequest => self.rate.on_keyframe_request(),
+                    }
+                }
+
+                self.now_us += 1_000;
+                if self.now_us.is_multiple_of(1_000_000) {
+                    self.levels.push(self.rate.level());
+                }
+            }
+        }
+
+        fn last_levels(&self, secs: usize) -> &[usize] {
+            &self.levels[self.levels.len() - secs..]
+        }
+    }
+
+    /// Highest profile whose bitrate fits the link.
+    fn fitting_level(capacity_bps: u64) -> usize {
+        VIDEO_PROFILES
+            .iter()
+            .position(|p| (p.bitrate_bps as u64) < capacity_bps)
+            .unwrap_or(VIDEO_PROFILES.len() - 1)
+    }
+
+    #[test]
+    fn clean_link_stays_at_top_profile() {
+        let mut harness = Harness::new(5_000_000);
+        harness.run_secs(30);
+        assert!(harness.levels.iter().all(|&level| level == 0));
+        assert_eq!(harness.keyframes, 0);
+    }
+
+    #[test]
+    fn impaired_link_converges_to_fitting_profile() {
+        let capacity = 700_000;
+        let target = fitting_level(capacity);
+        assert_eq!(target, 2);
+        let mut harness = Harness::new(capacity);
+        harness.run_secs(90);
+
+        let tail = harness.last_levels(40);
+        let settled = tail.iter().filter(|&&level| level == target).count();
+        assert!(
+            settled >= 36,
+            "expected to settle on level {target}, got {:?}",
+            harness.levels
+        );
+        assert!(
+            tail.iter()
+                .all(|&level| level + 1 >= target && level <= target + 1),
+            "strayed from level {target}: {:?}",
+            harness.levels
+        );
+    }
+
+    #[test]
+    fn recovers_top_profile_with_keyframe_when_link_clears() {
+        let mut harness = Harness::new(400_000);
+        harness.run_secs(20);
+        assert_eq!(
+            harness.rate.level(),
+            fitting_level(400_000),
+            "{:?}",
+            harness.levels
+        );
+
+        let keyframes = harness.keyframes;
+        harness.link.capacity_bps = 5_000_000;
+        harness.run_secs(40);
+        assert_eq!(harness.rate.level(), 0, "levels: {:?}", harness.levels);
+        assert!(harness.last_levels(10).iter().all(|&level| level == 0));
+        assert!(harness.keyframes > keyframes);
+    }
+}
@@ -1,6 +1,8 @@
 mod mdk_support;
 
+use base64::Engine;
 use mdk_core::prelude::MessageProcessingResult;
+use nostr::nips::nip19::ToBech32;
 use nostr::{Event, Kind, TagKind};
 
 uniffi::setup_scaffolding!();
@@ -109,6 +111,10 @@ pub fn decrypt_push_notification(
     if msg.pubkey == pubkey {
         return None;
     }
+    // Nor for anyone the user blocked.
+    if sender_is_blocked(&data_dir, &pubkey.to_hex(), &msg.pubkey.to_hex()) {
+        return None;
+    }
 
     // Helper: fetch group only in branches that need it.
     let get_group = || match mdk.get_group(&msg.mls_group_id) {
@@ -127,6 +133,16 @@ pub fn decrypt_push_notification(
             };
             let chat_id = hex::encode(group.nostr_group_id);
 
+            match chat_notify_mode(&data_dir, &pubkey.to_hex(), &chat_id) {
+                ChatNotifyMode::Muted => return None,
+                ChatNotifyMode::MentionsOnly
+                    if !mentions_account(&msg.content, &msg.tags, &pubkey) =>
+                {
+                    return None;
+                }
+                _ => {}
+            }
+
             let media = match msg.kind {
                 Kind::ChatMessage => notif_media(&msg.tags),
                 _ => None,
@@ -157,10 +173,13 @@ pub fn decrypt_push_notification(
                 _ => unreachable!(),
             };
 
-            // Try to download and decrypt the image for rich notification thumbnails.
-            let image_data = media
-                .filter(|m| m.kind == NotifMediaKind::Image)
-                .and_then(|m| download_and_decrypt_image(&mdk, &msg.mls_group_id, m.tag));
+            // Rich notification thumbnails: download and decrypt images; videos carry
+            // their poster inline in the imeta tag.
+            let image_data = media.and_then(|m| match m.kind {
+                NotifMediaKind::Image => download_and_decrypt_image(&mdk, &msg.mls_group_id, m.tag),
+                NotifMediaKind::Video => inline_thumbnail(m.tag),
+                _ => None,
+            });
 
             let group_name = if group.name != "DM" && !group.name.is_empty() {
                 Some(group.name.clone())
@@ -174,7 +193,7 @@ pub fn decrypt_push_notification(
 
             let sender_hex = msg.pubkey.to_hex();
             let (sender_name, sender_picture_url) =
-                resolve_sender_profile(&data_dir, &sender_hex, Some(&chat_id));
+                resolve_sender_profile(&data_dir, &pubkey.to_hex(), &sender_hex, Some(&chat_id));
 
             Some(PushNotificationResult::Content {
                 content: PushNotificationContent {
@@ -208,6 +227,10 @@ pub fn decrypt_push_notification(
                 }
             };
             let chat_id = hex::encode(group.nostr_group_id);
+            // Mentions-only chats still ring; a call is addressed to us.
+            if chat_notify_mode(&data_dir, &pubkey.to_hex(), &chat_id) == ChatNotifyMode::Muted {
+                return None;
+            }
             let is_video = probe
                 .body
                 .as_ref()
@@ -215,7 +238,7 @@ pub fn decrypt_push_notification(
                 .unwrap_or(false);
             let sender_hex = msg.pubkey.to_hex();
             let (caller_name, caller_picture_url) =
-                resolve_sender_profile(&data_dir, &sender_hex, Some(&chat_id));
+                resolve_sender_profile(&data_dir, &pubkey.to_hex(), &sender_hex, Some(&chat_id));
             Some(PushNotificationResult::CallInvite {
                 chat_id,
                 call_id: probe.call_id,
@@ -289,6 +312,19 @@ fn notif_media(tags: &nostr::Tags) -> Option<NotifMedia<'_>> {
     None
 }
 
+/// Decode the poster JPEG a sender inlined in the imeta `thumb` entry.
+fn inline_thumbnail(imeta_tag: &nostr::Tag) -> Option<Vec<u8>> {
+    let encoded = imeta_tag
+        .as_slice()
+        .iter()
+        .skip(1)
+        .find_map(|e| e.strip_prefix("thumb data:image/jpeg;base64,"))?;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+        .filter(|jpeg| !jpeg.is_empty())
+}
+
 /// Max encrypted download size for NSE image thumbnails (10 MB).
 const MAX_NSE_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
 
@@ -329,23 +365,105 @@ fn download_and_decrypt_image(
     manager.decrypt_from_download(&encrypted, &reference).ok()
 }
 
-/// Look up display name and picture URL from the SQLite profile cache.
-/// If `chat_id` is provided, checks for a group-specific profile first,
-/// falling back to the global profile.
+/// Opens the account's profile cache read-only. Each account keeps it under
+/// accounts/<pubkey>; older installs still have it in the data dir root.
+fn open_profile_db(data_dir: &str, account_hex: &str) -> Option<rusqlite::Connection> {
+    let account_db = std::path::Path::new(data_dir)
+        .join("accounts")
+        .join(account_hex)
+        .join("profiles.sqlite3");
+    let db_path = if account_db.exists() {
+        account_db
+    } else {
+        std::path::Path::new(data_dir).join("profiles.sqlite3")
+    };
+    rusqlite::Connection::open_with_flags(
+        &db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .ok()
+}
+
+/// Whether `pubkey_hex` is on the account's block list. Older caches have no
+/// `blocks` table, which reads as not blocked.
+fn sender_is_blocked(data_dir: &str, account_hex: &str, pubkey_hex: &str) -> bool {
+    open_profile_db(data_dir, account_hex).is_some_and(|conn| {
+        conn.query_row(
+            "SELECT 1 FROM blocks WHERE pubkey = ?1",
+            [pubkey_hex],
+            |_| Ok(()),
+        )
+        .is_ok()
+    })
+}
+
+/// A chat's notification mode as the app stored it in `chat_notification_modes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChatNotifyMode {
+    All,
+    MentionsOnly,
+    /// Muted for good or snoozed right now.
+    Muted,
+}
+
+impl ChatNotifyMode {
+    /// `mode` is "mentions" or "muted"; a muted row with `muted_until` is a
+    /// snooze, which is over once `now` reaches it.
+    fn from_row(mode: &str, muted_until: Option<i64>, now: i64) -> Self {
+        match (mode, muted_until) {
+            ("mentions", _) => Self::MentionsOnly,
+            ("muted", None) => Self::Muted,
+            ("muted", Some(until)) if until > now => Self::Muted,
+            _ => Self::All,
+        }
+    }
+}
+
+/// No row (or an older cache without the table) means every notification.
+fn chat_notify_mode(data_dir: &str, account_hex: &str, chat_id: &str) -> ChatNotifyMode {
+    let row = open_profile_db(data_dir, account_hex).and_then(|conn| {
+        conn.query_row(
+            "SELECT mode, muted_until FROM chat_notification_modes WHERE chat_id = ?1",
+            [chat_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?)),
+        )
+        .ok()
+    });
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    row.map_or(ChatNotifyMode::All, |(mode, muted_until)| {
+        ChatNotifyMode::from_row(&mode, muted_until, now)
+    })
+}
+
+/// Whether a message is addressed to `account`: a `nostr:npub…` mention in
+/// the text, or a `p` tag, which replies carry for the author they answer.
+fn mentions_account(content: &str, tags: &nostr::Tags, account: &nostr::PublicKey) -> bool {
+    let account_hex = account.to_hex();
+    let tagged = tags
+        .iter()
+        .any(|t| t.kind() == TagKind::p() && t.content() == Some(account_hex.as_str()));
+    tagged
+        || account
+            .to_bech32()
+            .is_ok_and(|npub| content.contains(&format!("nostr:{npub}")))
+}
+
+/// Look up display name and picture URL from the receiving account's SQLite
+/// profile cache. If `chat_id` is provided, checks for a group-specific profile
+/// first, falling back to the global profile.
 fn resolve_sender_profile(
     data_dir: &str,
+    account_hex: &str,
     pubkey_hex: &str,
     chat_id: Option<&str>,
 ) -> (String, Option<String>) {
     let fallback = (format!("{}...", &pubkey_hex[..8]), None);
 
-    let db_path = std::path::Path::new(data_dir).join("profiles.sqlite3");
-    let conn = match rusqlite::Connection::open_with_flags(
-        &db_path,
-        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
-    ) {
-        Ok(c) => c,
-        Err(_) => return fallback,
+    let Some(conn) = open_profile_db(data_dir, account_hex) else {
+        return fallback;
     };
 
     // Try group profile first, then fall back to global profile.
@@ -460,6 +578,19 @@ mod tests {
         assert_eq!(m.kind.label(), "Sent a file");
     }
 
+    #[test]
+    fn video_poster_comes_from_the_inline_thumb() {
+        let tag = Tag::parse(vec![
+            "imeta",
+            "url https://example.com/file",
+            "m video/mp4",
+            "thumb data:image/jpeg;base64,/9j/4A==",
+        ])
+        .unwrap();
+        assert_eq!(inline_thumbnail(&tag), Some(vec![0xff, 0xd8, 0xff, 0xe0]));
+        assert_eq!(inline_thumbnail(&imeta_tag("video/mp4")), None);
+    }
+
     #[test]
     fn media_kind_no_imeta_tags() {
         let tag = Tag::parse(vec!["e", "abc123"]).unwrap();
@@ -467,6 +598,55 @@ mod tests {
         assert!(notif_media(&tags).is_none());
     }
 
+    #[test]
+    fn notify_mode_from_stored_row() {
+        assert_eq!(
+            ChatNotifyMode::from_row("mentions", None, 100),
+            ChatNotifyMode::MentionsOnly
+        );
+        assert_eq!(
+            ChatNotifyMode::from_row("muted", None, 100),
+            ChatNotifyMode::Muted
+        );
+        assert_eq!(
+            ChatNotifyMode::from_row("muted", Some(200), 100),
+            ChatNotifyMode::Muted
+        );
+        assert_eq!(
+            ChatNotifyMode::from_row("muted", Some(100), 100),
+            ChatNotifyMode::All
+        );
+        assert_eq!(
+            ChatNotifyMode::from_row("bogus", None, 100),
+            ChatNotifyMode::All
+        );
+    }
+
+    #[test]
+    fn mentions_by_npub_or_p_tag() {
+        let me = nostr::Keys::generate().public_key();
+        let other = nostr::Keys::generate().public_key();
+        let npub = me.to_bech32().unwrap();
+
+        assert!(mentions_account(
+            &format!("hey nostr:{npub}!"),
+            &tags_from(vec![]),
+            &me
+        ));
+        assert!(!mentions_account("hey everyone", &tags_from(vec![]), &me));
+
+        let reply_to = |pk: &nostr::PublicKey| {
+            tags_from(vec![Tag::parse(vec![
+                "p".to_string(),
+                pk.to_hex(),
+                String::new(),
+            ])
+            .unwrap()])
+        };
+        assert!(mentions_account("sure", &reply_to(&me), &me));
+        assert!(!mentions_account("sure", &reply_to(&other), &me));
+    }
+
     #[test]
     fn media_kind_empty_tags() {
         let tags = tags_from(vec![]);
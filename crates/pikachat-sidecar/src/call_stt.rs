@@ -0,0 +1,116 @@
+use std::sync::mpsc as std_mpsc;
+
+use pika_media::stt::{FixtureStt, SpeechSegment, SttBackend, WhisperCliStt};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::protocol::OutMsg;
+
+/// Picks the speech-to-text engine from the environment; `None` leaves calls
+/// untranscribed.
+///
+/// - `PIKACHAT_STT_FIXTURE_TEXT` answers every segment with that text;
+///   `PIKACHAT_STT_FIXTURE=1` does the same with a default line.
+/// - `PIKACHAT_STT_WHISPER_MODEL` runs whisper.cpp locally with that model, via
+///   `PIKACHAT_STT_WHISPER_BIN` (default `whisper-cli`). `PIKACHAT_STT_LANGUAGE`
+///   pins the language instead of detecting it.
+pub fn stt_backend_from_env() -> Option<Box<dyn SttBackend>> {
+    if let Some(text) = std::env::var("PIKACHAT_STT_FIXTURE_TEXT")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    {
+        return Some(Box::new(FixtureStt::new(vec![text])));
+    }
+    if std::env::var("PIKACHAT_STT_FIXTURE")
+        .ok()
+        .as_deref()
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+    {
+        return Some(Box::new(FixtureStt::default()));
+    }
+
+    let model = std::env::var("PIKACHAT_STT_WHISPER_MODEL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())?;
+    let binary = std::env::var("PIKACHAT_STT_WHISPER_BIN")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| "whisper-cli".to_string());
+    let mut whisper = WhisperCliStt::new(binary, model);
+    if let Some(language) = std::env::var("PIKACHAT_STT_LANGUAGE")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    {
+        whisper = whisper.with_language(language);
+    }
+    Some(Box::new(whisper))
+}
+
+/// Transcribes a call's speech segments on its own thread and reports each
+/// line as [`OutMsg::CallTranscript`]. The thread exits once this is dropped
+/// and the queued segments are done.
+pub struct CallTranscriber {
+    segments: std_mpsc::Sender<SpeechSegment>,
+}
+
+impl CallTranscriber {
+    pub fn spawn(
+        call_id: &str,
+        mut backend: Box<dyn SttBackend>,
+        out_tx: mpsc::UnboundedSender<OutMsg>,
+    ) -> Self {
+        let (segments, rx) = std_mpsc::channel::<SpeechSegment>();
+        let call_id = call_id.to_string();
+        std::thread::spawn(move || {
+            for segment in rx {
+                match backend.transcribe(&segment) {
+                    Ok(text) if text.trim().is_empty() => {}
+                    Ok(text) => {
+                        let _ = out_tx.send(OutMsg::CallTranscript {
+                            call_id: call_id.clone(),
+                            text: text.trim().to_string(),
+                        });
+                    }
+                    Err(err) => {
+                        warn!("[pikachat] stt failed call_id={call_id} err={err}");
+                    }
+                }
+            }
+        });
+        Self { segments }
+    }
+
+    pub fn transcribe(&self, segment: SpeechSegment) {
+        let _ = self.segments.send(segment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcriber_reports_fixture_lines_and_skips_empty_text() {
+        let (out_tx, mut out_rx) = mpsc::unbounded_channel();
+        let backend = FixtureStt::new(vec!["hello there".into(), " ".into()]);
+        let transcriber = CallTranscriber::spawn("call-1", Box::new(backend), out_tx);
+        let segment = SpeechSegment {
+            sample_rate_hz: 48_000,
+            channels: 1,
+            pcm: vec![1_000; 960],
+        };
+        transcriber.transcribe(segment.clone());
+        transcriber.transcribe(segment);
+        drop(transcriber);
+
+        match out_rx.blocking_recv() {
+            Some(OutMsg::CallTranscript { call_id, text }) => {
+                assert_eq!(call_id, "call-1");
+                assert_eq!(text, "hello there");
+            }
+            other => panic!("expected call_transcript, got {other:?}"),
+        }
+        assert!(out_rx.blocking_recv().is_none());
+    }
+}
@@ -1,16 +1,164 @@
+use std::collections::{HashMap, VecDeque};
 use std::f32::consts::TAU;
-use std::io::Cursor;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+/// Cached phrase audio is capped at roughly five minutes of 24 kHz speech.
+const PHRASE_CACHE_MAX_SAMPLES: usize = 24_000 * 60 * 5;
+/// Streaming engines hand audio over in ~200 ms pieces.
+const STREAM_CHUNK_MS: usize = 200;
+const DEFAULT_LOCAL_TTS_BIN: &str = "piper";
+const DEFAULT_LOCAL_TTS_SAMPLE_RATE_HZ: u32 = 22_050;
+/// Give up looking for a WAV `data` chunk after this many header bytes.
+const MAX_WAV_HEADER_BYTES: usize = 64 * 1024;
+
+#[derive(Clone)]
 pub struct TtsPcm {
     pub sample_rate_hz: u32,
     pub channels: u16,
     pub pcm_i16: Vec<i16>,
 }
 
+/// A text-to-speech engine. Audio goes to `sink` as soon as each piece is
+/// rendered, so callers can start playback before the whole reply is done.
+pub trait TtsBackend: Send + Sync {
+    fn synthesize(
+        &self,
+        text: &str,
+        sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TtsBackendKind {
+    /// OpenAI-compatible `/audio/speech` endpoint.
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// Piper running on this machine; works offline.
+    #[serde(alias = "piper")]
+    Local,
+    /// Deterministic tone for tests and dev.
+    Fixture,
+}
+
+impl TtsBackendKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Local => "local",
+            Self::Fixture => "fixture",
+        }
+    }
+}
+
+impl FromStr for TtsBackendKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAi),
+            "local" | "piper" => Ok(Self::Local),
+            "fixture" => Ok(Self::Fixture),
+            other => Err(anyhow!("unknown tts backend: {other}")),
+        }
+    }
+}
+
+/// Daemon-wide TTS settings. A `send_audio_response` may still name another
+/// backend for a single reply.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TtsConfig {
+    /// Backend for replies that don't pick one. When unset, fixture mode wins
+    /// if `PIKACHAT_TTS_FIXTURE=1`, then the local engine if it has a model,
+    /// then OpenAI.
+    pub default_backend: Option<TtsBackendKind>,
+    /// Piper executable (default `piper`).
+    pub local_bin: Option<PathBuf>,
+    /// Piper `.onnx` voice model; required by the local backend.
+    pub local_model: Option<PathBuf>,
+}
+
+impl TtsConfig {
+    fn resolved_default(&self) -> TtsBackendKind {
+        if let Some(kind) = self.default_backend {
+            return kind;
+        }
+        if env_flag("PIKACHAT_TTS_FIXTURE") {
+            return TtsBackendKind::Fixture;
+        }
+        if self.local_model.is_some() {
+            return TtsBackendKind::Local;
+        }
+        TtsBackendKind::OpenAi
+    }
+}
+
+/// The daemon's TTS engines, built on first use and shared across calls so
+/// each keeps its phrase cache.
+pub struct TtsEngines {
+    config: TtsConfig,
+    default_kind: TtsBackendKind,
+    engines: Mutex<HashMap<TtsBackendKind, Arc<dyn TtsBackend>>>,
+}
+
+impl TtsEngines {
+    pub fn new(config: TtsConfig) -> Self {
+        let default_kind = config.resolved_default();
+        Self {
+            config,
+            default_kind,
+            engines: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn default_kind(&self) -> TtsBackendKind {
+        self.default_kind
+    }
+
+    /// Returns the engine for `kind`, or the daemon default when `None`.
+    pub fn engine(&self, kind: Option<TtsBackendKind>) -> anyhow::Result<Arc<dyn TtsBackend>> {
+        let kind = kind.unwrap_or(self.default_kind);
+        let mut engines = self.engines.lock().expect("tts engines lock poisoned");
+        if let Some(engine) = engines.get(&kind) {
+            return Ok(engine.clone());
+        }
+        let backend: Box<dyn TtsBackend> = match kind {
+            TtsBackendKind::OpenAi => Box::new(OpenAiTts::from_env()?),
+            TtsBackendKind::Local => {
+                let model = self.config.local_model.clone().context(
+                    "local tts not configured: pass --tts-local-model or set PIKACHAT_TTS_LOCAL_MODEL",
+                )?;
+                let binary = self
+                    .config
+                    .local_bin
+                    .clone()
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_LOCAL_TTS_BIN));
+                Box::new(PiperTts::new(binary, model))
+            }
+            TtsBackendKind::Fixture => Box::new(FixtureTts),
+        };
+        let engine: Arc<dyn TtsBackend> = Arc::new(CachedTts::new(backend));
+        engines.insert(kind, engine.clone());
+        Ok(engine)
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var(name)
+        .ok()
+        .as_deref()
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
 #[derive(Debug, Serialize)]
 struct OpenAiSpeechRequest {
     model: String,
@@ -19,115 +167,353 @@ struct OpenAiSpeechRequest {
     response_format: String,
 }
 
-pub fn synthesize_tts_pcm(text: &str) -> anyhow::Result<TtsPcm> {
-    let input = text.trim();
-    if input.is_empty() {
-        return Err(anyhow!("tts input is empty"));
+/// Any OpenAI-compatible `/audio/speech` endpoint, read as the server streams
+/// the WAV body.
+pub struct OpenAiTts {
+    base_url: String,
+    api_key: String,
+    model: String,
+    voice: String,
+}
+
+impl OpenAiTts {
+    /// Reads `OPENAI_API_KEY`, `OPENAI_BASE_URL`, `OPENAI_TTS_MODEL` and
+    /// `OPENAI_TTS_VOICE`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .context("tts not configured: set OPENAI_API_KEY or pick another tts backend")?;
+        let base_url = std::env::var("OPENAI_BASE_URL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+        let model = std::env::var("OPENAI_TTS_MODEL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "gpt-4o-mini-tts".to_string());
+        let voice = std::env::var("OPENAI_TTS_VOICE")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "alloy".to_string());
+        Ok(Self {
+            base_url,
+            api_key,
+            model,
+            voice,
+        })
     }
-    if std::env::var("PIKACHAT_TTS_FIXTURE")
-        .ok()
-        .as_deref()
-        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-        .unwrap_or(false)
-    {
-        return Ok(fixture_tone_pcm());
+}
+
+impl TtsBackend for OpenAiTts {
+    fn synthesize(
+        &self,
+        text: &str,
+        sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let input = text.trim();
+        if input.is_empty() {
+            return Err(anyhow!("tts input is empty"));
+        }
+        // reqwest::blocking panics when built inside a tokio runtime, so the
+        // client is made here on the synthesis thread rather than up front.
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .context("build openai tts client")?;
+
+        let url = format!("{}/audio/speech", self.base_url.trim_end_matches('/'));
+        let body = OpenAiSpeechRequest {
+            model: self.model.clone(),
+            voice: self.voice.clone(),
+            input: input.to_string(),
+            response_format: "wav".to_string(),
+        };
+        let resp = client
+            .post(url)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .context("openai speech request failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().unwrap_or_default();
+            return Err(anyhow!("openai tts failed status={status} body={body}"));
+        }
+        stream_wav_pcm(resp, sink)
     }
+}
 
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .context("tts not configured: set OPENAI_API_KEY or PIKACHAT_TTS_FIXTURE=1")?;
-    let base_url = std::env::var("OPENAI_BASE_URL")
-        .ok()
-        .filter(|v| !v.trim().is_empty())
-        .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
-    let model = std::env::var("OPENAI_TTS_MODEL")
-        .ok()
-        .filter(|v| !v.trim().is_empty())
-        .unwrap_or_else(|| "gpt-4o-mini-tts".to_string());
-    let voice = std::env::var("OPENAI_TTS_VOICE")
+/// Offline synthesis through a local [Piper](https://github.com/rhasspy/piper)
+/// binary, streamed from its raw PCM output.
+pub struct PiperTts {
+    binary: PathBuf,
+    model: PathBuf,
+    sample_rate_hz: u32,
+}
+
+impl PiperTts {
+    pub fn new(binary: impl Into<PathBuf>, model: impl Into<PathBuf>) -> Self {
+        let model = model.into();
+        let sample_rate_hz = piper_model_sample_rate(&model);
+        Self {
+            binary: binary.into(),
+            model,
+            sample_rate_hz,
+        }
+    }
+}
+
+/// Piper keeps the voice's sample rate in `<model>.json` next to the model.
+fn piper_model_sample_rate(model: &Path) -> u32 {
+    let mut config_path = model.as_os_str().to_owned();
+    config_path.push(".json");
+    std::fs::read(&config_path)
         .ok()
-        .filter(|v| !v.trim().is_empty())
-        .unwrap_or_else(|| "alloy".to_string());
-
-    let client = reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(60))
-        .build()
-        .context("build openai tts client")?;
-
-    let url = format!("{}/audio/speech", base_url.trim_end_matches('/'));
-    let body = OpenAiSpeechRequest {
-        model,
-        voice,
-        input: input.to_string(),
-        response_format: "wav".to_string(),
-    };
-    let resp = client
-        .post(url)
-        .bearer_auth(api_key)
-        .json(&body)
-        .send()
-        .context("openai speech request failed")?;
-    let status = resp.status();
-    let bytes = resp.bytes().context("read openai speech response body")?;
-    if !status.is_success() {
-        return Err(anyhow!(
-            "openai tts failed status={} body={}",
-            status,
-            String::from_utf8_lossy(&bytes)
-        ));
-    }
-    decode_wav_pcm(&bytes)
-}
-
-fn decode_wav_pcm(bytes: &[u8]) -> anyhow::Result<TtsPcm> {
-    // Try hound first; fall back to manual parsing for streaming WAVs
-    // (OpenAI TTS sets data_chunk_size to 0xFFFFFFFF).
-    match hound::WavReader::new(Cursor::new(bytes.to_vec())) {
-        Ok(mut reader) => {
-            let spec = reader.spec();
-            if spec.channels == 0 {
-                return Err(anyhow!("tts wav has zero channels"));
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .and_then(|config| config["audio"]["sample_rate"].as_u64())
+        .and_then(|rate| u32::try_from(rate).ok())
+        .filter(|rate| *rate > 0)
+        .unwrap_or(DEFAULT_LOCAL_TTS_SAMPLE_RATE_HZ)
+}
+
+impl TtsBackend for PiperTts {
+    fn synthesize(
+        &self,
+        text: &str,
+        sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let input = text.trim();
+        if input.is_empty() {
+            return Err(anyhow!("tts input is empty"));
+        }
+        let mut child = Command::new(&self.binary)
+            .arg("--model")
+            .arg(&self.model)
+            .arg("--output-raw")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("spawn local tts {}", self.binary.display()))?;
+        {
+            let mut stdin = child.stdin.take().context("local tts stdin")?;
+            // Piper reads one utterance per line.
+            let line = input.replace(['\r', '\n'], " ");
+            stdin
+                .write_all(line.as_bytes())
+                .and_then(|()| stdin.write_all(b"\n"))
+                .context("write local tts input")?;
+        }
+        let stdout = child.stdout.take().context("local tts stdout")?;
+        let streamed = stream_pcm_s16le(stdout, self.sample_rate_hz, 1, sink);
+        if streamed.is_err() {
+            let _ = child.kill();
+        }
+        let status = child.wait().context("wait for local tts")?;
+        streamed?;
+        if !status.success() {
+            return Err(anyhow!("local tts exited with {status}"));
+        }
+        Ok(())
+    }
+}
+
+/// Answers every phrase with a short 440 Hz tone.
+pub struct FixtureTts;
+
+impl TtsBackend for FixtureTts {
+    fn synthesize(
+        &self,
+        text: &str,
+        sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        if text.trim().is_empty() {
+            return Err(anyhow!("tts input is empty"));
+        }
+        sink(fixture_tone_pcm())
+    }
+}
+
+/// Splits replies into sentences, so the first one can play while the rest
+/// render, and reuses audio for sentences it has already spoken.
+pub struct CachedTts {
+    inner: Box<dyn TtsBackend>,
+    cache: Mutex<PhraseCache>,
+}
+
+impl CachedTts {
+    pub fn new(inner: Box<dyn TtsBackend>) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(PhraseCache::new(PHRASE_CACHE_MAX_SAMPLES)),
+        }
+    }
+}
+
+impl TtsBackend for CachedTts {
+    fn synthesize(
+        &self,
+        text: &str,
+        sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let phrases = split_phrases(text);
+        if phrases.is_empty() {
+            return Err(anyhow!("tts input is empty"));
+        }
+        for phrase in phrases {
+            let cached = self
+                .cache
+                .lock()
+                .expect("tts phrase cache lock poisoned")
+                .get(&phrase);
+            if let Some(pcm) = cached {
+                sink(pcm)?;
+                continue;
             }
-            let pcm_i16 = match (spec.sample_format, spec.bits_per_sample) {
-                (hound::SampleFormat::Int, 16) => reader
-                    .samples::<i16>()
-                    .collect::<Result<Vec<_>, _>>()
-                    .context("read 16-bit wav samples")?,
-                (hound::SampleFormat::Float, 32) => {
-                    let mut out = Vec::new();
-                    for sample in reader.samples::<f32>() {
-                        let s = sample.context("read 32-bit float wav sample")?;
-                        out.push((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16);
+
+            let mut rendered: Option<TtsPcm> = None;
+            let mut cacheable = true;
+            self.inner.synthesize(&phrase, &mut |chunk| {
+                match rendered.as_mut() {
+                    None => rendered = Some(chunk.clone()),
+                    Some(acc)
+                        if acc.sample_rate_hz == chunk.sample_rate_hz
+                            && acc.channels == chunk.channels =>
+                    {
+                        acc.pcm_i16.extend_from_slice(&chunk.pcm_i16);
                     }
-                    out
-                }
-                (fmt, bits) => {
-                    return Err(anyhow!(
-                        "unsupported tts wav format sample_format={fmt:?} bits_per_sample={bits}"
-                    ));
+                    Some(_) => cacheable = false,
                 }
+                sink(chunk)
+            })?;
+            if let Some(pcm) = rendered.filter(|_| cacheable) {
+                self.cache
+                    .lock()
+                    .expect("tts phrase cache lock poisoned")
+                    .insert(phrase, pcm);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Least-recently-used phrase audio, bounded by total sample count.
+struct PhraseCache {
+    max_samples: usize,
+    samples: usize,
+    entries: HashMap<String, TtsPcm>,
+    order: VecDeque<String>,
+}
+
+impl PhraseCache {
+    fn new(max_samples: usize) -> Self {
+        Self {
+            max_samples,
+            samples: 0,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, phrase: &str) -> Option<TtsPcm> {
+        let pcm = self.entries.get(phrase)?.clone();
+        self.touch(phrase);
+        Some(pcm)
+    }
+
+    fn insert(&mut self, phrase: String, pcm: TtsPcm) {
+        let len = pcm.pcm_i16.len();
+        if len > self.max_samples {
+            return;
+        }
+        if let Some(old) = self.entries.insert(phrase.clone(), pcm) {
+            self.samples -= old.pcm_i16.len();
+        }
+        self.samples += len;
+        self.touch(&phrase);
+        while self.samples > self.max_samples {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
             };
-            Ok(TtsPcm {
-                sample_rate_hz: spec.sample_rate,
-                channels: spec.channels,
-                pcm_i16,
-            })
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.samples -= evicted.pcm_i16.len();
+            }
         }
-        Err(_) => decode_wav_pcm_manual(bytes),
     }
+
+    fn touch(&mut self, phrase: &str) {
+        self.order.retain(|p| p != phrase);
+        self.order.push_back(phrase.to_string());
+    }
+}
+
+/// Breaks text at sentence ends and line breaks, collapsing whitespace so
+/// repeated phrases share a cache key.
+fn split_phrases(text: &str) -> Vec<String> {
+    let mut phrases = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' || c == '\r' {
+            push_phrase(&mut phrases, &mut current);
+            continue;
+        }
+        current.push(c);
+        let sentence_end = matches!(c, '.' | '!' | '?' | '…')
+            && chars.peek().is_none_or(|next| next.is_whitespace());
+        if sentence_end {
+            push_phrase(&mut phrases, &mut current);
+        }
+    }
+    push_phrase(&mut phrases, &mut current);
+    phrases
 }
 
-/// Manual WAV parser for streaming WAVs with invalid chunk sizes.
-fn decode_wav_pcm_manual(bytes: &[u8]) -> anyhow::Result<TtsPcm> {
-    if bytes.len() < 44 {
-        return Err(anyhow!("wav too short ({} bytes)", bytes.len()));
+fn push_phrase(phrases: &mut Vec<String>, current: &mut String) {
+    let phrase = current.split_whitespace().collect::<Vec<_>>().join(" ");
+    current.clear();
+    if !phrase.is_empty() {
+        phrases.push(phrase);
     }
-    let fmt_pos = bytes
-        .windows(4)
-        .position(|w| w == b"fmt ")
-        .context("no fmt chunk")?;
+}
+
+/// Streams a 16-bit WAV body. Chunk sizes in the header are ignored, since
+/// streaming servers (OpenAI among them) write `0xFFFFFFFF` there.
+fn stream_wav_pcm(
+    mut reader: impl Read,
+    sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut header = Vec::new();
+    let mut buf = [0u8; 1024];
+    let (sample_rate_hz, channels, data_start) = loop {
+        if let Some(found) = parse_wav_header(&header)? {
+            break found;
+        }
+        if header.len() > MAX_WAV_HEADER_BYTES {
+            return Err(anyhow!("tts wav has no data chunk"));
+        }
+        let n = match reader.read(&mut buf) {
+            Ok(n) => n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err).context("read tts wav header"),
+        };
+        if n == 0 {
+            return Err(anyhow!("wav too short ({} bytes)", header.len()));
+        }
+        header.extend_from_slice(&buf[..n]);
+    };
+    let already_read = Cursor::new(header.split_off(data_start));
+    stream_pcm_s16le(already_read.chain(reader), sample_rate_hz, channels, sink)
+}
+
+/// Returns `(sample_rate_hz, channels, data_offset)` once `bytes` holds the
+/// whole header, `None` while more is needed.
+fn parse_wav_header(bytes: &[u8]) -> anyhow::Result<Option<(u32, u16, usize)>> {
+    let Some(fmt_pos) = bytes.windows(4).position(|w| w == b"fmt ") else {
+        return Ok(None);
+    };
     let h = fmt_pos + 8;
     if bytes.len() < h + 16 {
-        return Err(anyhow!("fmt chunk too short"));
+        return Ok(None);
     }
     let channels = u16::from_le_bytes([bytes[h + 2], bytes[h + 3]]);
     let sample_rate = u32::from_le_bytes(bytes[h + 4..h + 8].try_into()?);
@@ -135,21 +521,65 @@ fn decode_wav_pcm_manual(bytes: &[u8]) -> anyhow::Result<TtsPcm> {
     if bits != 16 || channels == 0 {
         return Err(anyhow!("unsupported wav: bits={bits} channels={channels}"));
     }
-    let data_pos = bytes
-        .windows(4)
-        .position(|w| w == b"data")
-        .context("no data chunk")?;
-    let pcm_start = data_pos + 8;
-    let pcm_bytes = &bytes[pcm_start..];
-    let pcm_i16: Vec<i16> = pcm_bytes
-        .chunks_exact(2)
-        .map(|c| i16::from_le_bytes([c[0], c[1]]))
-        .collect();
-    Ok(TtsPcm {
-        sample_rate_hz: sample_rate,
-        channels,
-        pcm_i16,
-    })
+    let Some(data_pos) = bytes[h..].windows(4).position(|w| w == b"data") else {
+        return Ok(None);
+    };
+    let data_start = h + data_pos + 8;
+    if bytes.len() < data_start {
+        return Ok(None);
+    }
+    Ok(Some((sample_rate, channels, data_start)))
+}
+
+/// Forwards interleaved 16-bit little-endian PCM from `reader` in
+/// [`STREAM_CHUNK_MS`] pieces.
+fn stream_pcm_s16le(
+    mut reader: impl Read,
+    sample_rate_hz: u32,
+    channels: u16,
+    sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let frame_bytes = 2 * channels.max(1) as usize;
+    let chunk_frames = (sample_rate_hz as usize * STREAM_CHUNK_MS / 1000).max(1);
+    let mut buf = vec![0u8; chunk_frames * frame_bytes];
+    let mut filled = 0usize;
+    let mut emitted = false;
+    let mut emit = |bytes: &[u8]| {
+        let pcm_i16 = bytes
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        sink(TtsPcm {
+            sample_rate_hz,
+            channels,
+            pcm_i16,
+        })
+    };
+    loop {
+        let n = match reader.read(&mut buf[filled..]) {
+            Ok(n) => n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err).context("read tts audio stream"),
+        };
+        if n == 0 {
+            break;
+        }
+        filled += n;
+        if filled == buf.len() {
+            emit(&buf)?;
+            emitted = true;
+            filled = 0;
+        }
+    }
+    let tail = filled - filled % frame_bytes;
+    if tail > 0 {
+        emit(&buf[..tail])?;
+        emitted = true;
+    }
+    if !emitted {
+        return Err(anyhow!("tts synthesis produced no audio"));
+    }
+    Ok(())
 }
 
 fn fixture_tone_pcm() -> TtsPcm {
@@ -176,8 +606,37 @@ fn fixture_tone_pcm() -> TtsPcm {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use super::*;
 
+    fn collect(backend: &dyn TtsBackend, text: &str) -> Vec<TtsPcm> {
+        let mut chunks = Vec::new();
+        backend
+            .synthesize(text, &mut |chunk| {
+                chunks.push(chunk);
+                Ok(())
+            })
+            .expect("synthesize");
+        chunks
+    }
+
+    /// Reads at most `step` bytes per call, like a slow network body.
+    struct Trickle {
+        bytes: Vec<u8>,
+        pos: usize,
+        step: usize,
+    }
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.bytes.len() - self.pos);
+            buf[..n].copy_from_slice(&self.bytes[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
     #[test]
     fn fixture_tone_has_audio_samples() {
         let pcm = fixture_tone_pcm();
@@ -185,4 +644,123 @@ mod tests {
         assert_eq!(pcm.sample_rate_hz, 24_000);
         assert!(!pcm.pcm_i16.is_empty());
     }
+
+    #[test]
+    fn split_phrases_breaks_at_sentence_ends_and_lines() {
+        assert_eq!(
+            split_phrases("Hello  there. It costs 3.50 today!\nSee you?  "),
+            vec!["Hello there.", "It costs 3.50 today!", "See you?"]
+        );
+        assert!(split_phrases(" \n ").is_empty());
+    }
+
+    #[test]
+    fn cached_tts_streams_each_phrase_and_reuses_repeats() {
+        struct Counting(Arc<AtomicUsize>);
+        impl TtsBackend for Counting {
+            fn synthesize(
+                &self,
+                text: &str,
+                sink: &mut dyn FnMut(TtsPcm) -> anyhow::Result<()>,
+            ) -> anyhow::Result<()> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                for _ in 0..2 {
+                    sink(TtsPcm {
+                        sample_rate_hz: 16_000,
+                        channels: 1,
+                        pcm_i16: vec![text.len() as i16; 160],
+                    })?;
+                }
+                Ok(())
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tts = CachedTts::new(Box::new(Counting(calls.clone())));
+
+        let first = collect(&tts, "Got it. One moment.");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(first.len(), 4);
+
+        let second = collect(&tts, "Got it.  Done.");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(second.len(), 3);
+        assert_eq!(second[0].pcm_i16.len(), 320);
+        assert_eq!(second[0].pcm_i16[0], "Got it.".len() as i16);
+    }
+
+    #[test]
+    fn phrase_cache_evicts_least_recently_used() {
+        let pcm = |n: usize| TtsPcm {
+            sample_rate_hz: 16_000,
+            channels: 1,
+            pcm_i16: vec![0; n],
+        };
+        let mut cache = PhraseCache::new(100);
+        cache.insert("a".into(), pcm(40));
+        cache.insert("b".into(), pcm(40));
+        assert!(cache.get("a").is_some());
+        cache.insert("c".into(), pcm(40));
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+        cache.insert("huge".into(), pcm(101));
+        assert!(cache.get("huge").is_none());
+    }
+
+    #[test]
+    fn wav_stream_ignores_bogus_sizes_and_chunks_audio() {
+        let samples: Vec<i16> = (0..10_000).map(|i| (i % 1000) as i16).collect();
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&u32::MAX.to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&24_000u32.to_le_bytes());
+        wav.extend_from_slice(&48_000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&u32::MAX.to_le_bytes());
+        for s in &samples {
+            wav.extend_from_slice(&s.to_le_bytes());
+        }
+
+        let mut chunks = Vec::new();
+        let reader = Trickle {
+            bytes: wav,
+            pos: 0,
+            step: 7,
+        };
+        stream_wav_pcm(reader, &mut |chunk| {
+            chunks.push(chunk);
+            Ok(())
+        })
+        .expect("stream wav");
+
+        // 200 ms at 24 kHz per chunk, with the remainder in the last one.
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.sample_rate_hz == 24_000));
+        assert_eq!(chunks[0].pcm_i16.len(), 4_800);
+        let joined: Vec<i16> = chunks.into_iter().flat_map(|c| c.pcm_i16).collect();
+        assert_eq!(joined, samples);
+    }
+
+    #[test]
+    fn engines_default_to_the_configured_backend() {
+        let engines = TtsEngines::new(TtsConfig {
+            default_backend: Some(TtsBackendKind::Fixture),
+            ..TtsConfig::default()
+        });
+        assert_eq!(engines.default_kind(), TtsBackendKind::Fixture);
+        let engine = engines.engine(None).expect("fixture engine");
+        assert_eq!(collect(engine.as_ref(), "Hi.").len(), 1);
+        assert!(engines.engine(Some(TtsBackendKind::Local)).is_err());
+        assert_eq!(
+            "piper".parse::<TtsBackendKind>().unwrap(),
+            TtsBackendKind::Local
+        );
+    }
 }
@@ -23,6 +23,7 @@ use pika_marmot_runtime::call_runtime::{
 };
 use pika_marmot_runtime::conversation::ConversationEvent;
 use pika_marmot_runtime::group::{CreatedGroup, create_group_and_publish_welcomes};
+use pika_marmot_runtime::membership::{AdminChange, PreparedMembershipEvolution};
 use pika_marmot_runtime::message::{
     CALL_SIGNAL_KIND, MessageClassification, classify_message as classify_shared_message,
 };
@@ -34,12 +35,13 @@ use pika_marmot_runtime::runtime::{
 use pika_marmot_runtime::welcome::{
     AcceptedWelcome, accept_welcome_and_catch_up, take_pending_welcome,
 };
-use pika_media::codec_opus::{OpusCodec, OpusPacket};
-use pika_media::crypto::{FrameInfo, decrypt_frame, encrypt_frame};
+use pika_media::codec_opus::{OpusCodec, OpusConfig, OpusPacket};
+use pika_media::crypto::{FrameInfo, FrameKeyMaterial, decrypt_frame, encrypt_frame};
 use pika_media::network::NetworkRelay;
 use pika_media::session::{
     InMemoryRelay, MediaFrame, MediaSession, MediaSessionError, SessionConfig,
 };
+use pika_media::stt::SpeechSegment;
 use pika_media::tracks::{TrackAddress, broadcast_path};
 
 use serde::Deserialize;
@@ -50,8 +52,9 @@ use tokio::task::JoinHandle;
 use tracing::warn;
 
 use crate::acp::{AcpBackendConfig, AcpBackendManager, AcpTurnCompletion};
-use crate::call_audio::OpusToAudioPipeline;
-use crate::call_tts::synthesize_tts_pcm;
+use crate::call_audio::{OpusToAudioPipeline, pcm_to_wav};
+use crate::call_stt::{CallTranscriber, stt_backend_from_env};
+use crate::call_tts::{TtsBackend, TtsConfig, TtsEngines};
 use crate::protocol::{DaemonCmd, InCmd, MediaAttachmentOut, OutMsg, out_error, out_ok};
 use host_context::{DaemonHostContext, DaemonPrepareError};
 
@@ -60,7 +63,7 @@ use pika_marmot_runtime::call::key_id_for_sender;
 #[cfg(test)]
 use pika_marmot_runtime::welcome::find_pending_welcome_index;
 #[cfg(test)]
-use pika_media::crypto::{FrameKeyMaterial, opaque_participant_label};
+use pika_media::crypto::opaque_participant_label;
 
 const PROTOCOL_VERSION: u32 = 1;
 const ACCEPT_WELCOME_BACKLOG_LIMIT: usize = 200;
@@ -369,6 +372,7 @@ fn default_audio_call_session(call_id: &str) -> CallSessionParams {
         relay_auth: "capv1_aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
             .to_string(),
         tracks: vec![CallTrackSpec::audio0_opus_default()],
+        group_call: false,
     }
 }
 
@@ -412,6 +416,8 @@ struct CompatCallSessionParams {
     #[serde(default)]
     relay_auth: String,
     tracks: Vec<CallTrackSpec>,
+    #[serde(default)]
+    group_call: bool,
 }
 
 fn parse_call_signal(content: &str) -> Option<ParsedCallSignal> {
@@ -426,6 +432,7 @@ fn parse_call_signal(content: &str) -> Option<ParsedCallSignal> {
                 broadcast_base: session.broadcast_base,
                 relay_auth: session.relay_auth,
                 tracks: session.tracks,
+                group_call: session.group_call,
             }),
             Err(e) => {
                 warn!("[pikachat] {msg_type} body parse failed call_id={call_id} err={e:#}",);
@@ -642,91 +649,122 @@ struct VoicePublishStats {
     frames_published: u64,
 }
 
-fn publish_tts_audio_response_with_relay(
-    session: &CallSessionParams,
-    relay: InMemoryRelay,
-    media_crypto: &CallMediaCryptoContext,
-    start_seq: u64,
-    tts_text: &str,
-) -> anyhow::Result<VoicePublishStats> {
-    let text = tts_text.to_string();
-    let tts_pcm = std::thread::spawn(move || synthesize_tts_pcm(&text))
-        .join()
-        .map_err(|_| anyhow!("tts synthesis thread panicked"))?
-        .context("synthesize call tts")?;
-    publish_pcm_audio_response_with_relay(session, relay, media_crypto, start_seq, tts_pcm)
+/// Encodes, encrypts and publishes mono PCM on the local audio track as it
+/// arrives, so spoken replies start playing before synthesis finishes.
+struct VoicePublisher {
+    transport: CallMediaTransport,
+    publish_track: TrackAddress,
+    tx_keys: FrameKeyMaterial,
+    codec: OpusCodec,
+    sample_rate: u32,
+    frame_ms: u16,
+    frame_samples: usize,
+    /// Sleep one frame between frames so the receiver isn't handed a burst it
+    /// can't buffer. In-memory relays skip this.
+    paced: bool,
+    pending: Vec<i16>,
+    seq: u64,
+    frames: u64,
 }
 
-fn publish_pcm_audio_response_with_relay(
-    session: &CallSessionParams,
-    relay: InMemoryRelay,
-    media_crypto: &CallMediaCryptoContext,
-    start_seq: u64,
-    tts_pcm: crate::call_tts::TtsPcm,
-) -> anyhow::Result<VoicePublishStats> {
-    let Some(track) = call_audio_track_spec(session) else {
-        return Err(anyhow!("call session missing opus audio track"));
-    };
-    if track.channels != 1 {
-        return Err(anyhow!(
-            "tts publish only supports mono track for now (got channels={})",
-            track.channels
-        ));
-    }
+impl VoicePublisher {
+    fn new(
+        session: &CallSessionParams,
+        transport: CallMediaTransport,
+        paced: bool,
+        media_crypto: &CallMediaCryptoContext,
+        start_seq: u64,
+    ) -> anyhow::Result<Self> {
+        let Some(track) = call_audio_track_spec(session) else {
+            return Err(anyhow!("call session missing opus audio track"));
+        };
+        if track.channels != 1 {
+            return Err(anyhow!(
+                "tts publish only supports mono (got channels={})",
+                track.channels
+            ));
+        }
+        let publish_track = TrackAddress {
+            broadcast_path: broadcast_path(
+                &session.broadcast_base,
+                &media_crypto.local_participant_label,
+            )
+            .map_err(|e| anyhow!("invalid local broadcast path: {e}"))?,
+            track_name: track.name.clone(),
+        };
+        tracing::info!(
+            "[tts] publish init ({}) broadcast_base={} local_label={} peer_label={} publish_path={} track={} start_seq={}",
+            if paced { "transport" } else { "relay" },
+            session.broadcast_base,
+            media_crypto.local_participant_label,
+            media_crypto.peer_participant_label,
+            publish_track.broadcast_path,
+            publish_track.track_name,
+            start_seq,
+        );
 
-    let mut media = MediaSession::with_relay(
-        SessionConfig {
-            moq_url: session.moq_url.clone(),
-            relay_auth: session.relay_auth.clone(),
-        },
-        relay,
-    );
-    media.connect().map_err(|e| anyhow::anyhow!("{e}"))?;
-    let publish_track = TrackAddress {
-        broadcast_path: broadcast_path(
-            &session.broadcast_base,
-            &media_crypto.local_participant_label,
-        )
-        .map_err(|e| anyhow!("invalid local broadcast path: {e}"))?,
-        track_name: track.name.clone(),
-    };
-    tracing::info!(
-        "[tts] publish init (relay) broadcast_base={} local_label={} peer_label={} publish_path={} track={} start_seq={}",
-        session.broadcast_base,
-        media_crypto.local_participant_label,
-        media_crypto.peer_participant_label,
-        publish_track.broadcast_path,
-        publish_track.track_name,
-        start_seq
-    );
+        let frame_samples = ((track.sample_rate as usize) * (track.frame_ms as usize) / 1000)
+            .saturating_mul(track.channels as usize);
+        if frame_samples == 0 {
+            return Err(anyhow!("invalid frame size from track spec"));
+        }
+        let codec = OpusCodec::new(OpusConfig {
+            sample_rate_hz: track.sample_rate,
+            channels: track.channels,
+            frame_ms: track.frame_ms,
+            ..OpusConfig::default()
+        })
+        .map_err(|e| anyhow!("init opus encoder failed: {e}"))?;
+        Ok(Self {
+            transport,
+            publish_track,
+            tx_keys: media_crypto.tx_keys.clone(),
+            codec,
+            sample_rate: track.sample_rate,
+            frame_ms: track.frame_ms,
+            frame_samples,
+            paced,
+            pending: Vec::new(),
+            seq: start_seq,
+            frames: 0,
+        })
+    }
 
-    let mono_pcm = downmix_to_mono(&tts_pcm.pcm_i16, tts_pcm.channels);
-    let pcm = resample_mono_linear(&mono_pcm, tts_pcm.sample_rate_hz, track.sample_rate);
-    if pcm.is_empty() {
-        return Err(anyhow!("tts synthesis produced no pcm samples"));
+    fn push(&mut self, tts_pcm: crate::call_tts::TtsPcm) -> anyhow::Result<()> {
+        let mono_pcm = downmix_to_mono(&tts_pcm.pcm_i16, tts_pcm.channels);
+        let pcm = resample_mono_linear(&mono_pcm, tts_pcm.sample_rate_hz, self.sample_rate);
+        self.pending.extend_from_slice(&pcm);
+        while self.pending.len() >= self.frame_samples {
+            let frame_pcm: Vec<i16> = self.pending.drain(..self.frame_samples).collect();
+            self.publish_frame(&frame_pcm)?;
+        }
+        Ok(())
     }
 
-    let frame_samples = ((track.sample_rate as usize) * (track.frame_ms as usize) / 1000)
-        .saturating_mul(track.channels as usize);
-    if frame_samples == 0 {
-        return Err(anyhow!("invalid frame size from track spec"));
+    /// Pads out and sends the last partial frame.
+    fn finish(mut self) -> anyhow::Result<VoicePublishStats> {
+        if !self.pending.is_empty() {
+            let mut frame_pcm = std::mem::take(&mut self.pending);
+            frame_pcm.resize(self.frame_samples, 0);
+            self.publish_frame(&frame_pcm)?;
+        }
+        if self.frames == 0 {
+            return Err(anyhow!("tts synthesis produced no pcm samples"));
+        }
+        Ok(VoicePublishStats {
+            next_seq: self.seq,
+            frames_published: self.frames,
+        })
     }
 
-    let codec = OpusCodec;
-    let mut seq = start_seq;
-    let mut frames = 0u64;
-    for chunk in pcm.chunks(frame_samples) {
+    fn publish_frame(&mut self, frame_pcm: &[i16]) -> anyhow::Result<()> {
+        let seq = self.seq;
         let frame_counter =
             u32::try_from(seq).map_err(|_| anyhow!("call media tx counter exhausted"))?;
-        let mut frame_pcm = Vec::with_capacity(frame_samples);
-        frame_pcm.extend_from_slice(chunk);
-        if frame_pcm.len() < frame_samples {
-            frame_pcm.resize(frame_samples, 0);
-        }
-        let packet = codec.encode_pcm_i16(&frame_pcm);
+        let packet = self.codec.encode_pcm_i16(frame_pcm);
         let encrypted = encrypt_frame(
             &packet.0,
-            &media_crypto.tx_keys,
+            &self.tx_keys,
             FrameInfo {
                 counter: frame_counter,
                 group_seq: seq,
@@ -737,21 +775,88 @@ fn publish_pcm_audio_response_with_relay(
         .map_err(|e| anyhow!("encrypt tts frame failed: {e}"))?;
         let frame = MediaFrame {
             seq,
-            timestamp_us: seq.saturating_mul((track.frame_ms as u64) * 1_000),
+            timestamp_us: seq.saturating_mul((self.frame_ms as u64) * 1_000),
             keyframe: true,
             payload: encrypted,
         };
-        media
-            .publish(&publish_track, frame)
+        self.transport
+            .publish(&self.publish_track, frame)
             .context("publish tts frame")?;
-        seq = seq.saturating_add(1);
-        frames = frames.saturating_add(1);
+        self.seq = seq.saturating_add(1);
+        self.frames = self.frames.saturating_add(1);
+        if self.paced {
+            std::thread::sleep(Duration::from_millis(self.frame_ms as u64));
+        }
+        Ok(())
     }
+}
 
-    Ok(VoicePublishStats {
-        next_seq: seq,
-        frames_published: frames,
-    })
+fn in_memory_voice_transport(
+    session: &CallSessionParams,
+    relay: InMemoryRelay,
+) -> anyhow::Result<CallMediaTransport> {
+    let mut media = MediaSession::with_relay(
+        SessionConfig {
+            moq_url: session.moq_url.clone(),
+            relay_auth: session.relay_auth.clone(),
+        },
+        relay,
+    );
+    media.connect().map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(CallMediaTransport::InMemory { session: media })
+}
+
+/// Publishes audio from `engine` chunk by chunk as it is synthesized.
+fn stream_tts_audio_response(
+    mut publisher: VoicePublisher,
+    engine: Arc<dyn TtsBackend>,
+    tts_text: &str,
+) -> anyhow::Result<VoicePublishStats> {
+    // Engines may use reqwest::blocking::Client, which panics if created
+    // inside a tokio runtime. Run synthesis on a dedicated thread.
+    let (chunk_tx, chunk_rx) = std::sync::mpsc::channel();
+    let text = tts_text.to_string();
+    let synth = std::thread::spawn(move || {
+        engine.synthesize(&text, &mut |chunk| {
+            chunk_tx
+                .send(chunk)
+                .map_err(|_| anyhow!("tts publisher stopped"))
+        })
+    });
+    for chunk in chunk_rx {
+        publisher.push(chunk)?;
+    }
+    synth
+        .join()
+        .map_err(|_| anyhow!("tts synthesis thread panicked"))?
+        .context("synthesize call tts")?;
+    publisher.finish()
+}
+
+fn publish_tts_audio_response_with_relay(
+    session: &CallSessionParams,
+    relay: InMemoryRelay,
+    media_crypto: &CallMediaCryptoContext,
+    start_seq: u64,
+    engine: Arc<dyn TtsBackend>,
+    tts_text: &str,
+) -> anyhow::Result<VoicePublishStats> {
+    let transport = in_memory_voice_transport(session, relay)?;
+    let publisher = VoicePublisher::new(session, transport, false, media_crypto, start_seq)?;
+    stream_tts_audio_response(publisher, engine, tts_text)
+}
+
+fn publish_pcm_audio_response_with_relay(
+    session: &CallSessionParams,
+    relay: InMemoryRelay,
+    media_crypto: &CallMediaCryptoContext,
+    start_seq: u64,
+    tts_pcm: crate::call_tts::TtsPcm,
+) -> anyhow::Result<VoicePublishStats> {
+    let transport = in_memory_voice_transport(session, relay)?;
+    let mut publisher = VoicePublisher::new(session, transport, false, media_crypto, start_seq)?;
+    publisher.push(tts_pcm)?;
+    publisher.finish()
 }
 
 fn publish_pcm_audio_response_with_transport(
@@ -761,89 +866,9 @@ fn publish_pcm_audio_response_with_transport(
     start_seq: u64,
     tts_pcm: crate::call_tts::TtsPcm,
 ) -> anyhow::Result<VoicePublishStats> {
-    let Some(track) = call_audio_track_spec(session) else {
-        return Err(anyhow!("call session missing opus audio track"));
-    };
-    if track.channels != 1 {
-        return Err(anyhow!(
-            "tts publish only supports mono (got channels={})",
-            track.channels
-        ));
-    }
-
-    let publish_track = TrackAddress {
-        broadcast_path: broadcast_path(
-            &session.broadcast_base,
-            &media_crypto.local_participant_label,
-        )
-        .map_err(|e| anyhow!("invalid local broadcast path: {e}"))?,
-        track_name: track.name.clone(),
-    };
-    tracing::info!(
-        "[tts] publish init (transport) broadcast_base={} local_label={} peer_label={} publish_path={} track={} start_seq={}",
-        session.broadcast_base,
-        media_crypto.local_participant_label,
-        media_crypto.peer_participant_label,
-        publish_track.broadcast_path,
-        publish_track.track_name,
-        start_seq,
-    );
-
-    let mono_pcm = downmix_to_mono(&tts_pcm.pcm_i16, tts_pcm.channels);
-    let pcm = resample_mono_linear(&mono_pcm, tts_pcm.sample_rate_hz, track.sample_rate);
-    if pcm.is_empty() {
-        return Err(anyhow!("tts synthesis produced no pcm samples"));
-    }
-
-    let frame_samples = ((track.sample_rate as usize) * (track.frame_ms as usize) / 1000)
-        .saturating_mul(track.channels as usize);
-    if frame_samples == 0 {
-        return Err(anyhow!("invalid frame size from track spec"));
-    }
-
-    let codec = OpusCodec;
-    let mut seq = start_seq;
-    let mut frames = 0u64;
-    for chunk in pcm.chunks(frame_samples) {
-        let frame_counter =
-            u32::try_from(seq).map_err(|_| anyhow!("call media tx counter exhausted"))?;
-        let mut frame_pcm = Vec::with_capacity(frame_samples);
-        frame_pcm.extend_from_slice(chunk);
-        if frame_pcm.len() < frame_samples {
-            frame_pcm.resize(frame_samples, 0);
-        }
-        let packet = codec.encode_pcm_i16(&frame_pcm);
-        let encrypted = encrypt_frame(
-            &packet.0,
-            &media_crypto.tx_keys,
-            FrameInfo {
-                counter: frame_counter,
-                group_seq: seq,
-                frame_idx: 0,
-                keyframe: true,
-            },
-        )
-        .map_err(|e| anyhow!("encrypt tts frame failed: {e}"))?;
-        let frame = MediaFrame {
-            seq,
-            timestamp_us: seq.saturating_mul((track.frame_ms as u64) * 1_000),
-            keyframe: true,
-            payload: encrypted,
-        };
-        transport
-            .publish(&publish_track, frame)
-            .context("publish tts frame")?;
-        seq = seq.saturating_add(1);
-        frames = frames.saturating_add(1);
-        // Pace frame delivery at ~real-time so the receiver doesn't get a
-        // burst of frames it can't buffer properly.
-        std::thread::sleep(Duration::from_millis(track.frame_ms as u64));
-    }
-
-    Ok(VoicePublishStats {
-        next_seq: seq,
-        frames_published: frames,
-    })
+    let mut publisher = VoicePublisher::new(session, transport, true, media_crypto, start_seq)?;
+    publisher.push(tts_pcm)?;
+    publisher.finish()
 }
 
 fn publish_tts_audio_response_with_transport(
@@ -851,16 +876,11 @@ fn publish_tts_audio_response_with_transport(
     transport: CallMediaTransport,
     media_crypto: &CallMediaCryptoContext,
     start_seq: u64,
+    engine: Arc<dyn TtsBackend>,
     tts_text: &str,
 ) -> anyhow::Result<VoicePublishStats> {
-    // synthesize_tts_pcm uses reqwest::blocking::Client which panics if created
-    // inside a tokio runtime. Run it on a dedicated thread.
-    let text = tts_text.to_string();
-    let tts_pcm = std::thread::spawn(move || synthesize_tts_pcm(&text))
-        .join()
-        .map_err(|_| anyhow!("tts synthesis thread panicked"))?
-        .context("synthesize call tts")?;
-    publish_pcm_audio_response_with_transport(session, transport, media_crypto, start_seq, tts_pcm)
+    let publisher = VoicePublisher::new(session, transport, true, media_crypto, start_seq)?;
+    stream_tts_audio_response(publisher, engine, tts_text)
 }
 
 fn publish_pcm_audio_response(
@@ -888,6 +908,7 @@ fn publish_tts_audio_response(
     session: &CallSessionParams,
     media_crypto: &CallMediaCryptoContext,
     start_seq: u64,
+    engine: Arc<dyn TtsBackend>,
     tts_text: &str,
 ) -> anyhow::Result<VoicePublishStats> {
     if is_real_moq_url(&session.moq_url) {
@@ -897,11 +918,19 @@ fn publish_tts_audio_response(
             transport,
             media_crypto,
             start_seq,
+            engine,
             tts_text,
         )
     } else {
         let relay = shared_call_relay(session);
-        publish_tts_audio_response_with_relay(session, relay, media_crypto, start_seq, tts_text)
+        publish_tts_audio_response_with_relay(
+            session,
+            relay,
+            media_crypto,
+            start_seq,
+            engine,
+            tts_text,
+        )
     }
 }
 
@@ -980,23 +1009,36 @@ fn start_stt_worker_with_relay(
         let mut rx_decrypt_dropped = 0u64;
         let mut ticks = 0u64;
 
-        let emit_chunk = |wav: Vec<u8>,
+        let transcriber = stt_backend_from_env()
+            .map(|backend| CallTranscriber::spawn(&call_id, backend, out_tx.clone()));
+
+        let emit_chunk = |segment: SpeechSegment,
                           seq: &mut u64,
                           call_id: &str,
                           call_evt_tx: &mpsc::UnboundedSender<CallWorkerEvent>,
                           tmp_dir: &std::path::Path| {
-            let wav_path = tmp_dir.join(format!("chunk_{seq}.wav"));
-            if let Err(err) = std::fs::write(&wav_path, &wav) {
-                warn!("[pikachat] write audio chunk failed call_id={call_id} err={err}");
-                return;
+            match pcm_to_wav(sample_rate, channels, &segment.pcm) {
+                Ok(wav) => {
+                    let wav_path = tmp_dir.join(format!("chunk_{seq}.wav"));
+                    if let Err(err) = std::fs::write(&wav_path, &wav) {
+                        warn!("[pikachat] write audio chunk failed call_id={call_id} err={err}");
+                    } else {
+                        *seq += 1;
+                        let _ = call_evt_tx.send(CallWorkerEvent::AudioChunk {
+                            call_id: call_id.to_string(),
+                            audio_path: wav_path.to_string_lossy().to_string(),
+                            sample_rate,
+                            channels,
+                        });
+                    }
+                }
+                Err(err) => {
+                    warn!("[pikachat] encode audio chunk failed call_id={call_id} err={err:#}");
+                }
+            }
+            if let Some(transcriber) = &transcriber {
+                transcriber.transcribe(segment);
             }
-            *seq += 1;
-            let _ = call_evt_tx.send(CallWorkerEvent::AudioChunk {
-                call_id: call_id.to_string(),
-                audio_path: wav_path.to_string_lossy().to_string(),
-                sample_rate,
-                channels,
-            });
         };
 
         while !stop_for_task.load(Ordering::Relaxed) {
@@ -1013,8 +1055,8 @@ fn start_stt_worker_with_relay(
                     }
                 };
                 rx_frames = rx_frames.saturating_add(1);
-                if let Some(wav) = pipeline.ingest_packet(OpusPacket(decrypted.payload)) {
-                    emit_chunk(wav, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
+                if let Some(segment) = pipeline.ingest_packet(OpusPacket(decrypted.payload)) {
+                    emit_chunk(segment, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
                 }
             }
 
@@ -1030,8 +1072,8 @@ fn start_stt_worker_with_relay(
             std::thread::sleep(Duration::from_millis(20));
         }
 
-        if let Some(wav) = pipeline.flush() {
-            emit_chunk(wav, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
+        if let Some(segment) = pipeline.flush() {
+            emit_chunk(segment, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
         }
     });
 
@@ -1084,23 +1126,36 @@ fn start_stt_worker_with_transport(
         let _ = std::fs::create_dir_all(&tmp_dir);
         let mut chunk_seq = 0u64;
 
-        let emit_chunk = |wav: Vec<u8>,
+        let transcriber = stt_backend_from_env()
+            .map(|backend| CallTranscriber::spawn(&call_id, backend, out_tx.clone()));
+
+        let emit_chunk = |segment: SpeechSegment,
                           seq: &mut u64,
                           call_id: &str,
                           call_evt_tx: &mpsc::UnboundedSender<CallWorkerEvent>,
                           tmp_dir: &std::path::Path| {
-            let wav_path = tmp_dir.join(format!("chunk_{seq}.wav"));
-            if let Err(err) = std::fs::write(&wav_path, &wav) {
-                warn!("[pikachat] write audio chunk failed call_id={call_id} err={err}");
-                return;
+            match pcm_to_wav(sample_rate, channels, &segment.pcm) {
+                Ok(wav) => {
+                    let wav_path = tmp_dir.join(format!("chunk_{seq}.wav"));
+                    if let Err(err) = std::fs::write(&wav_path, &wav) {
+                        warn!("[pikachat] write audio chunk failed call_id={call_id} err={err}");
+                    } else {
+                        *seq += 1;
+                        let _ = call_evt_tx.send(CallWorkerEvent::AudioChunk {
+                            call_id: call_id.to_string(),
+                            audio_path: wav_path.to_string_lossy().to_string(),
+                            sample_rate,
+                            channels,
+                        });
+                    }
+                }
+                Err(err) => {
+                    warn!("[pikachat] encode audio chunk failed call_id={call_id} err={err:#}");
+                }
+            }
+            if let Some(transcriber) = &transcriber {
+                transcriber.transcribe(segment);
             }
-            *seq += 1;
-            let _ = call_evt_tx.send(CallWorkerEvent::AudioChunk {
-                call_id: call_id.to_string(),
-                audio_path: wav_path.to_string_lossy().to_string(),
-                sample_rate,
-                channels,
-            });
         };
 
         let mut rx_frames = 0u64;
@@ -1120,8 +1175,8 @@ fn start_stt_worker_with_transport(
                     }
                 };
                 rx_frames = rx_frames.saturating_add(1);
-                if let Some(wav) = pipeline.ingest_packet(OpusPacket(decrypted.payload)) {
-                    emit_chunk(wav, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
+                if let Some(segment) = pipeline.ingest_packet(OpusPacket(decrypted.payload)) {
+                    emit_chunk(segment, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
                 }
             }
 
@@ -1137,8 +1192,8 @@ fn start_stt_worker_with_transport(
             std::thread::sleep(Duration::from_millis(20));
         }
 
-        if let Some(wav) = pipeline.flush() {
-            emit_chunk(wav, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
+        if let Some(segment) = pipeline.flush() {
+            emit_chunk(segment, &mut chunk_seq, &call_id, &call_evt_tx, &tmp_dir);
         }
     });
 
@@ -1180,7 +1235,7 @@ fn start_echo_worker_with_relay(
     let stop = Arc::new(AtomicBool::new(false));
     let stop_for_task = stop.clone();
     let task = tokio::spawn(async move {
-        let codec = OpusCodec;
+        let codec = OpusCodec::default();
         let mut seq = 0u64;
         let mut tx_frames = 0u64;
         let mut rx_frames = 0u64;
@@ -1259,7 +1314,7 @@ fn start_echo_worker_with_transport(
     let stop = Arc::new(AtomicBool::new(false));
     let stop_for_task = stop.clone();
     let task = tokio::task::spawn_blocking(move || {
-        let codec = OpusCodec;
+        let codec = OpusCodec::default();
         let mut seq = 0u64;
         let mut tx_frames = 0u64;
         let mut rx_frames = 0u64;
@@ -1492,7 +1547,7 @@ pub async fn run_audio_echo_smoke(frame_count: u64) -> anyhow::Result<AudioEchoS
     )
     .context("start echo worker")?;
 
-    let codec = OpusCodec;
+    let codec = OpusCodec::default();
     let mut sent_frames = 0u64;
     for i in 0..frame_count {
         let pcm = vec![i as i16, (i as i16).saturating_mul(-1)];
@@ -1533,6 +1588,123 @@ pub async fn run_audio_echo_smoke(frame_count: u64) -> anyhow::Result<AudioEchoS
     })
 }
 
+async fn change_group_admin(
+    host: &DaemonHostContext<'_>,
+    reply_tx: &mpsc::UnboundedSender<OutMsg>,
+    request_id: Option<String>,
+    nostr_group_id: &str,
+    pubkey: &str,
+    change: AdminChange,
+) {
+    let target = match PublicKey::parse(pubkey.trim()) {
+        Ok(pk) => pk,
+        Err(e) => {
+            reply_tx
+                .send(out_error(
+                    request_id,
+                    "bad_pubkey",
+                    format!("invalid pubkey: {e}"),
+                ))
+                .ok();
+            return;
+        }
+    };
+    let prepared = match host.prepare_admin_change(nostr_group_id, target, change) {
+        Ok(prepared) => prepared,
+        Err(DaemonPrepareError::BadGroup(e)) => {
+            reply_tx
+                .send(out_error(request_id, "bad_group_id", format!("{e:#}")))
+                .ok();
+            return;
+        }
+        Err(DaemonPrepareError::Prepare(e)) => {
+            reply_tx
+                .send(out_error(
+                    request_id,
+                    "admin_change_failed",
+                    format!("{e:#}"),
+                ))
+                .ok();
+            return;
+        }
+    };
+    match host
+        .publish_evolution(prepared, "daemon_admin_change")
+        .await
+    {
+        Ok(()) => {
+            reply_tx
+                .send(out_ok(
+                    request_id,
+                    Some(json!({
+                        "nostr_group_id": nostr_group_id,
+                        "pubkey": target.to_hex(),
+                    })),
+                ))
+                .ok();
+        }
+        Err(e) => {
+            reply_tx
+                .send(out_error(request_id, "publish_failed", format!("{e:#}")))
+                .ok();
+        }
+    }
+}
+
+/// Publish an edit or delete of one of our own messages and reply with the
+/// inner event id of the revision.
+async fn revise_message(
+    host: &DaemonHostContext<'_>,
+    reply_tx: &mpsc::UnboundedSender<OutMsg>,
+    request_id: Option<String>,
+    nostr_group_id: &str,
+    event_id: &str,
+    revision: impl FnOnce(EventId) -> OutboundConversationAction,
+    label: &str,
+) {
+    let target = match EventId::from_hex(event_id.trim()) {
+        Ok(id) => id,
+        Err(_) => {
+            reply_tx
+                .send(out_error(
+                    request_id,
+                    "bad_event_id",
+                    "event_id must be hex",
+                ))
+                .ok();
+            return;
+        }
+    };
+    let prepared = match host.prepare_outbound_action(nostr_group_id, revision(target)) {
+        Ok(prepared) => prepared,
+        Err(DaemonPrepareError::BadGroup(e)) => {
+            reply_tx
+                .send(out_error(request_id, "bad_group_id", format!("{e:#}")))
+                .ok();
+            return;
+        }
+        Err(DaemonPrepareError::Prepare(e)) => {
+            reply_tx
+                .send(out_error(request_id, "publish_failed", format!("{e:#}")))
+                .ok();
+            return;
+        }
+    };
+    let inner_id = prepared.rumor_id.to_hex();
+    match host.publish_prepared(&prepared, label).await {
+        Ok(_) => {
+            reply_tx
+                .send(out_ok(request_id, Some(json!({"event_id": inner_id}))))
+                .ok();
+        }
+        Err(e) => {
+            reply_tx
+                .send(out_error(request_id, "publish_failed", format!("{e:#}")))
+                .ok();
+        }
+    }
+}
+
 async fn publish_and_confirm_multi(
     client: &Client,
     relays: &[RelayUrl],
@@ -1662,6 +1834,7 @@ fn build_acp_prompt(nostr_group_id: &str, sender_hex: &str, content: &str) -> St
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn daemon_main(
     relays_arg: &[String],
     state_dir: &Path,
@@ -1670,6 +1843,7 @@ pub async fn daemon_main(
     auto_accept_welcomes: bool,
     exec_cmd: Option<&str>,
     acp_backend: Option<AcpBackendConfig>,
+    tts: TtsConfig,
 ) -> anyhow::Result<()> {
     crate::ensure_dir(state_dir).context("create state dir")?;
 
@@ -1768,6 +1942,11 @@ pub async fn daemon_main(
         }
         None => (None, None),
     };
+    let tts_engines = TtsEngines::new(tts);
+    tracing::info!(
+        "[pikachat] tts default backend={}",
+        tts_engines.default_kind().as_str()
+    );
     let bootstrapped = bootstrap_runtime_for_daemon(state_dir, &keys)?;
     let client = bootstrapped.session.client.clone();
     let mdk = bootstrapped.session.mdk;
@@ -2146,9 +2325,13 @@ pub async fn daemon_main(
                                             if !sender_allowed(&msg.pubkey.to_hex()) {
                                                 continue;
                                             }
-                                            if classify_daemon_message(&msg)
-                                                == Some(MessageClassification::TypingIndicator)
-                                            {
+                                            if matches!(
+                                                classify_daemon_message(&msg),
+                                                Some(
+                                                    MessageClassification::TypingIndicator
+                                                        | MessageClassification::ReadReceipt
+                                                )
+                                            ) {
                                                 continue;
                                             }
                                             let media: Vec<MediaAttachmentOut> = {
@@ -2375,6 +2558,55 @@ pub async fn daemon_main(
                             }
                         }
                     }
+                    InCmd::EditMessage {
+                        request_id,
+                        nostr_group_id,
+                        event_id,
+                        content,
+                    } => {
+                        let content = content.trim();
+                        if content.is_empty() {
+                            out_tx
+                                .send(out_error(request_id, "bad_content", "content is required"))
+                                .ok();
+                            continue;
+                        }
+                        let host = DaemonHostContext::new(&client, &relay_urls, &mdk, &keys, &pubkey_hex);
+                        revise_message(
+                            &host,
+                            reply_tx,
+                            request_id,
+                            &nostr_group_id,
+                            &event_id,
+                            |target_event_id| OutboundConversationAction::Edit {
+                                target_event_id,
+                                content: content.to_string(),
+                                created_at: Timestamp::now(),
+                            },
+                            "daemon_edit_message",
+                        )
+                        .await;
+                    }
+                    InCmd::DeleteMessage {
+                        request_id,
+                        nostr_group_id,
+                        event_id,
+                    } => {
+                        let host = DaemonHostContext::new(&client, &relay_urls, &mdk, &keys, &pubkey_hex);
+                        revise_message(
+                            &host,
+                            reply_tx,
+                            request_id,
+                            &nostr_group_id,
+                            &event_id,
+                            |target_event_id| OutboundConversationAction::Delete {
+                                target_event_id,
+                                created_at: Timestamp::now(),
+                            },
+                            "daemon_delete_message",
+                        )
+                        .await;
+                    }
                     InCmd::SubmitHypernoteAction {
                         request_id,
                         nostr_group_id,
@@ -2767,6 +2999,7 @@ pub async fn daemon_main(
                                 channels: 1,
                                 frame_ms: 1,
                             }],
+                            group_call: false,
                         };
                         if session.relay_auth.trim().is_empty() {
                             match host
@@ -3034,6 +3267,7 @@ pub async fn daemon_main(
                         request_id,
                         call_id,
                         tts_text,
+                        tts_backend,
                     } => {
                         let Some(current) = active_call.as_mut() else {
                             let _ = reply_tx.send(out_error(request_id, "not_found", "active call not found"));
@@ -3055,15 +3289,28 @@ pub async fn daemon_main(
                             let _ = reply_tx.send(out_error(request_id, "bad_request", "tts_text must not be empty"));
                             continue;
                         }
+                        let engine = match tts_engines.engine(tts_backend) {
+                            Ok(engine) => engine,
+                            Err(err) => {
+                                let _ = reply_tx.send(out_error(
+                                    request_id,
+                                    "bad_request",
+                                    format!("tts backend unavailable: {err:#}"),
+                                ));
+                                continue;
+                            }
+                        };
                         tracing::info!(
-                            "[pikachat] send_audio_response start call_id={} text_len={}",
+                            "[pikachat] send_audio_response start call_id={} text_len={} tts_backend={}",
                             call_id,
-                            tts_text.len()
+                            tts_text.len(),
+                            tts_backend.unwrap_or(tts_engines.default_kind()).as_str()
                         );
                         match publish_tts_audio_response(
                             &current.session,
                             &current.media_crypto,
                             current.next_voice_seq,
+                            engine,
                             &tts_text,
                         ) {
                             Ok(stats) => {
@@ -3362,6 +3609,28 @@ pub async fn daemon_main(
                             member_count,
                         }).ok();
                     }
+                    InCmd::PromoteAdmin { request_id, nostr_group_id, pubkey } => {
+                        change_group_admin(
+                            &DaemonHostContext::new(&client, &relay_urls, &mdk, &keys, &pubkey_hex),
+                            reply_tx,
+                            request_id,
+                            &nostr_group_id,
+                            &pubkey,
+                            AdminChange::Promote,
+                        )
+                        .await;
+                    }
+                    InCmd::DemoteAdmin { request_id, nostr_group_id, pubkey } => {
+                        change_group_admin(
+                            &DaemonHostContext::new(&client, &relay_urls, &mdk, &keys, &pubkey_hex),
+                            reply_tx,
+                            request_id,
+                            &nostr_group_id,
+                            &pubkey,
+                            AdminChange::Demote,
+                        )
+                        .await;
+                    }
                     InCmd::Shutdown { request_id } => {
                         reply_tx.send(out_ok(request_id, None)).ok();
                         shutdown = true;
@@ -3608,6 +3877,12 @@ pub async fn daemon_main(
                                     }
                                     _ => None,
                                 };
+                                // The daemon's call worker bridges a single peer; decline
+                                // multi-party group calls instead of joining half of one.
+                                let is_group_call_invite = matches!(
+                                    &signal,
+                                    ParsedCallSignal::Invite { session, .. } if session.group_call
+                                );
                                 match host.handle_inbound_call_signal(
                                     pika_marmot_runtime::call_runtime::InboundSignalContext {
                                         target_id: &nostr_group_id,
@@ -3617,11 +3892,12 @@ pub async fn daemon_main(
                                             local_pubkey_hex: &pubkey_hex,
                                         },
                                         policy: InboundCallPolicy {
-                                            allow_group_calls: true,
+                                            allow_group_calls: !is_group_call_invite,
                                             allow_video_calls: false,
                                         },
                                         has_live_call: active_call.is_some(),
                                         pending_outgoing,
+                                        live_group_call: None,
                                     },
                                     signal,
                                 ) {
@@ -3761,10 +4037,18 @@ pub async fn daemon_main(
                                                 .ok();
                                         }
                                     }
+                                    InboundCallSignalOutcome::ParticipantsJoined(_)
+                                    | InboundCallSignalOutcome::ParticipantLeft(_)
+                                    | InboundCallSignalOutcome::ParticipantMediaState(_)
+                                    | InboundCallSignalOutcome::ParticipantRecording(_) => {}
                                 }
                                 continue;
                             }
-                            if classification == MessageClassification::TypingIndicator {
+                            if matches!(
+                                classification,
+                                MessageClassification::TypingIndicator
+                                    | MessageClassification::ReadReceipt
+                            ) {
                                 continue;
                             }
                             let mut media: Vec<MediaAttachmentOut> = Vec::new();
@@ -3820,6 +4104,18 @@ pub async fn daemon_main(
                                 }
                             }
                         }
+                        Ok(Some(ConversationEvent::RejectedCommit { nostr_group_id_hex, reason })) => {
+                            warn!(
+                                "[pikachat] rejected non-admin commit group={} id={} reason={}",
+                                nostr_group_id_hex,
+                                event.id.to_hex(),
+                                reason
+                            );
+                            out_tx.send(OutMsg::GroupCommitRejected {
+                                nostr_group_id: nostr_group_id_hex,
+                                reason,
+                            }).ok();
+                        }
                         Ok(Some(_)) => {}
                         Ok(None) => {}
                         Err(e) => {
@@ -4516,6 +4812,10 @@ mod tests {
             ),
             video_tx_keys: None,
             video_rx_keys: None,
+            data_tx_keys: None,
+            data_rx_keys: None,
+            screen_tx_keys: None,
+            screen_rx_keys: None,
         };
 
         let mut observer = MediaSession::with_relay(
@@ -4545,7 +4845,7 @@ mod tests {
 
         let stats = publish_pcm_audio_response_with_relay(
             &session,
-            relay,
+            relay.clone(),
             &media_crypto,
             0,
             crate::call_tts::TtsPcm {
@@ -4557,18 +4857,38 @@ mod tests {
         .expect("publish tts pcm");
         assert_eq!(stats.frames_published, total_frames as u64);
 
+        // Two fixture sentences: 2 x 650 ms of tone, streamed after the pcm.
+        let engines = TtsEngines::new(TtsConfig {
+            default_backend: Some(crate::call_tts::TtsBackendKind::Fixture),
+            ..TtsConfig::default()
+        });
+        let reply = publish_tts_audio_response_with_relay(
+            &session,
+            relay,
+            &media_crypto,
+            stats.next_seq,
+            engines.engine(None).expect("fixture engine"),
+            "Hello. Goodbye.",
+        )
+        .expect("publish tts reply");
+        assert_eq!(reply.frames_published, 65);
+        assert_eq!(reply.next_seq, stats.next_seq + 65);
+        let expected_frames = stats.frames_published + reply.frames_published;
+
+        let codec = OpusCodec::default();
         let mut echoed_frames = 0u64;
         let deadline = std::time::Instant::now() + Duration::from_secs(2);
-        while echoed_frames < stats.frames_published && std::time::Instant::now() < deadline {
+        while echoed_frames < expected_frames && std::time::Instant::now() < deadline {
             while let Ok(frame) = echoed_rx.try_recv() {
                 let opened =
                     decrypt_frame(&frame.payload, &media_crypto.tx_keys).expect("decrypt frame");
-                let _ = OpusCodec.decode_to_pcm_i16(&OpusPacket(opened.payload));
+                let pcm = codec.decode_to_pcm_i16(&OpusPacket(opened.payload));
+                assert_eq!(pcm.len(), frame_samples);
                 echoed_frames = echoed_frames.saturating_add(1);
             }
             std::thread::sleep(Duration::from_millis(20));
         }
-        assert_eq!(echoed_frames, stats.frames_published);
+        assert_eq!(echoed_frames, expected_frames);
     }
 
     // ── Media helper tests ─────────────────────────────────────────────
@@ -158,6 +158,44 @@ impl<'a> DaemonHostContext<'a> {
             .map_err(DaemonPrepareError::Prepare)
     }
 
+    /// Checks that we're a group admin before building the commit.
+    pub(super) fn prepare_admin_change(
+        &self,
+        nostr_group_id: &str,
+        target: PublicKey,
+        change: AdminChange,
+    ) -> Result<PreparedMembershipEvolution, DaemonPrepareError> {
+        let mls_group_id = self
+            .resolve_group(nostr_group_id)
+            .map_err(DaemonPrepareError::BadGroup)?;
+        self.runtime()
+            .prepare_admin_change(&mls_group_id, &self.keys.public_key(), target, change)
+            .map_err(DaemonPrepareError::Prepare)
+    }
+
+    /// Publish a commit and merge it only once a relay has confirmed it.
+    pub(super) async fn publish_evolution(
+        &self,
+        prepared: PreparedMembershipEvolution,
+        label: &str,
+    ) -> anyhow::Result<()> {
+        if self.relay_urls.is_empty() {
+            anyhow::bail!("no relays configured");
+        }
+        publish_and_confirm_multi(
+            self.client,
+            self.relay_urls,
+            &prepared.evolution_event,
+            label,
+        )
+        .await?;
+        let finalized = self.runtime().finalize_published_evolution(prepared);
+        match finalized.merge_error {
+            Some(err) => Err(anyhow!("merge pending commit: {err}")),
+            None => Ok(()),
+        }
+    }
+
     pub(super) fn derive_relay_auth_token(
         &self,
         nostr_group_id: &str,
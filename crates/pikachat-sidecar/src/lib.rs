@@ -9,7 +9,8 @@ use anyhow::Context;
 
 pub mod acp;
 mod call_audio;
-mod call_tts;
+mod call_stt;
+pub mod call_tts;
 pub mod daemon;
 pub mod protocol;
 mod relay;
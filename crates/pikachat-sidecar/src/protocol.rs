@@ -5,6 +5,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
+use crate::call_tts::TtsBackendKind;
+
 /// Versioned request surface for the daemon's native JSONL/socket protocol.
 #[derive(Debug, Deserialize)]
 #[serde(tag = "cmd", rename_all = "snake_case")]
@@ -60,6 +62,19 @@ pub enum InCmd {
         event_id: String,
         emoji: String,
     },
+    EditMessage {
+        #[serde(default)]
+        request_id: Option<String>,
+        nostr_group_id: String,
+        event_id: String,
+        content: String,
+    },
+    DeleteMessage {
+        #[serde(default)]
+        request_id: Option<String>,
+        nostr_group_id: String,
+        event_id: String,
+    },
     SubmitHypernoteAction {
         #[serde(default)]
         request_id: Option<String>,
@@ -139,6 +154,9 @@ pub enum InCmd {
         request_id: Option<String>,
         call_id: String,
         tts_text: String,
+        /// Overrides the daemon's default TTS backend for this reply.
+        #[serde(default)]
+        tts_backend: Option<TtsBackendKind>,
     },
     SendAudioFile {
         #[serde(default)]
@@ -164,6 +182,20 @@ pub enum InCmd {
         #[serde(default = "default_group_name")]
         group_name: String,
     },
+    /// Admin-only: add `pubkey` (a current member) to the group's admin set.
+    PromoteAdmin {
+        #[serde(default)]
+        request_id: Option<String>,
+        nostr_group_id: String,
+        pubkey: String,
+    },
+    /// Admin-only: drop `pubkey` from the group's admin set.
+    DemoteAdmin {
+        #[serde(default)]
+        request_id: Option<String>,
+        nostr_group_id: String,
+        pubkey: String,
+    },
     GetMessages {
         #[serde(default)]
         request_id: Option<String>,
@@ -250,6 +282,11 @@ pub enum OutMsg {
         sample_rate: u32,
         channels: u8,
     },
+    /// Text of one speech segment from the peer, when an STT engine is configured.
+    CallTranscript {
+        call_id: String,
+        text: String,
+    },
     CallData {
         call_id: String,
         payload_hex: String,
@@ -261,6 +298,11 @@ pub enum OutMsg {
         peer_pubkey: String,
         member_count: u32,
     },
+    /// An inbound commit was refused because its sender isn't a group admin.
+    GroupCommitRejected {
+        nostr_group_id: String,
+        reason: String,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -384,6 +426,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_admin_change_cmds() {
+        let json =
+            r#"{"cmd":"promote_admin","request_id":"r3","nostr_group_id":"aa","pubkey":"npub1x"}"#;
+        let cmd: InCmd = serde_json::from_str(json).expect("deserialize");
+        match cmd {
+            InCmd::PromoteAdmin {
+                request_id,
+                nostr_group_id,
+                pubkey,
+            } => {
+                assert_eq!(request_id.as_deref(), Some("r3"));
+                assert_eq!(nostr_group_id, "aa");
+                assert_eq!(pubkey, "npub1x");
+            }
+            other => panic!("expected PromoteAdmin, got {other:?}"),
+        }
+
+        let json = r#"{"cmd":"demote_admin","nostr_group_id":"bb","pubkey":"ff"}"#;
+        let cmd: InCmd = serde_json::from_str(json).expect("deserialize");
+        assert!(matches!(
+            cmd,
+            InCmd::DemoteAdmin {
+                request_id: None,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn deserialize_hypernote_catalog_cmd() {
         let json = r#"{"cmd":"hypernote_catalog","request_id":"r2"}"#;
@@ -425,6 +496,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_edit_and_delete_message_cmds() {
+        let json = r#"{
+            "cmd": "edit_message",
+            "nostr_group_id": "aa",
+            "event_id": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+            "content": "fixed typo"
+        }"#;
+        let cmd: InCmd = serde_json::from_str(json).expect("deserialize");
+        match cmd {
+            InCmd::EditMessage {
+                request_id,
+                nostr_group_id,
+                content,
+                ..
+            } => {
+                assert!(request_id.is_none());
+                assert_eq!(nostr_group_id, "aa");
+                assert_eq!(content, "fixed typo");
+            }
+            other => panic!("expected EditMessage, got {other:?}"),
+        }
+
+        let json = r#"{
+            "cmd": "delete_message",
+            "request_id": "d1",
+            "nostr_group_id": "aa",
+            "event_id": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+        }"#;
+        let cmd: InCmd = serde_json::from_str(json).expect("deserialize");
+        match cmd {
+            InCmd::DeleteMessage {
+                request_id,
+                event_id,
+                ..
+            } => {
+                assert_eq!(request_id.as_deref(), Some("d1"));
+                assert_eq!(
+                    event_id,
+                    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+                );
+            }
+            other => panic!("expected DeleteMessage, got {other:?}"),
+        }
+    }
+
     #[test]
     fn deserialize_submit_hypernote_action_cmd() {
         let json = r#"{
@@ -471,10 +588,29 @@ mod tests {
                 request_id,
                 call_id,
                 tts_text,
+                tts_backend,
             } => {
                 assert_eq!(request_id.as_deref(), Some("r5"));
                 assert_eq!(call_id, "550e8400-e29b-41d4-a716-446655440010");
                 assert_eq!(tts_text, "hello from sidecar");
+                assert_eq!(tts_backend, None);
+            }
+            other => panic!("expected SendAudioResponse, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn deserialize_send_audio_response_cmd_with_tts_backend() {
+        let json = r#"{
+            "cmd": "send_audio_response",
+            "call_id": "550e8400-e29b-41d4-a716-446655440010",
+            "tts_text": "hello",
+            "tts_backend": "local"
+        }"#;
+        let cmd: InCmd = serde_json::from_str(json).expect("deserialize send_audio_response");
+        match cmd {
+            InCmd::SendAudioResponse { tts_backend, .. } => {
+                assert_eq!(tts_backend, Some(TtsBackendKind::Local));
             }
             other => panic!("expected SendAudioResponse, got {other:?}"),
         }
@@ -0,0 +1,810 @@
+//! Local stand-in Blossom server for exercising chunked, resumable media transfers.
+//!
+//! Serves the subset of BUD-01/BUD-02 the apps use (`GET`/`HEAD /<sha256>` with range
+//! support, whole-blob `PUT /upload`) plus the offset-based `HEAD`/`PATCH /upload/<sha256>`
+//! extension spoken by `pika_marmot_runtime::transfer`. Blobs live in memory.
+//!
+//! [`StandInBlossom::interrupt_transfers`] cuts upload and download bodies off mid-stream
+//! so tests can interrupt transfers deterministically.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use clap::Args;
+use nostr_sdk::prelude::{Event, JsonUtil};
+use pika_marmot_runtime::transfer::{UPLOAD_LENGTH_HEADER, UPLOAD_OFFSET_HEADER};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const BLOSSOM_AUTH_KIND: u16 = 24242;
+
+#[derive(Debug, Args)]
+pub struct BlossomServeArgs {
+    /// Port to listen on (0 picks a free port)
+    #[arg(long, default_value_t = 0)]
+    port: u16,
+
+    /// Reject chunked uploads so clients fall back to whole-blob PUT
+    #[arg(long)]
+    no_chunked_uploads: bool,
+
+    /// Cut every upload/download body off after this many bytes
+    #[arg(long)]
+    interrupt_after_bytes: Option<usize>,
+}
+
+pub async fn run(args: BlossomServeArgs) -> Result<()> {
+    let server = StandInBlossom::start_on(SocketAddr::from(([127, 0, 0, 1], args.port))).await?;
+    server.set_chunked_uploads(!args.no_chunked_uploads);
+    if let Some(after_bytes) = args.interrupt_after_bytes {
+        server.interrupt_transfers(usize::MAX, after_bytes);
+    }
+    println!("{}", server.url());
+    tokio::signal::ctrl_c().await.context("wait for ctrl-c")?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct StoredBlob {
+    data: Vec<u8>,
+    mime_type: String,
+}
+
+#[derive(Debug)]
+struct ServerState {
+    blobs: HashMap<String, StoredBlob>,
+    uploads: HashMap<String, Vec<u8>>,
+    chunked_uploads: bool,
+    interruptions_left: usize,
+    interrupt_after_bytes: usize,
+    upload_body_bytes: u64,
+    download_range_starts: Vec<u64>,
+}
+
+impl ServerState {
+    /// Byte budget for the next transfer body, if it should be cut short.
+    fn take_interruption(&mut self) -> Option<usize> {
+        if self.interruptions_left == 0 {
+            return None;
+        }
+        self.interruptions_left -= 1;
+        Some(self.interrupt_after_bytes)
+    }
+}
+
+/// In-process Blossom server bound to localhost. Stops when dropped.
+pub struct StandInBlossom {
+    url: String,
+    state: Arc<Mutex<ServerState>>,
+    task: JoinHandle<()>,
+}
+
+impl StandInBlossom {
+    pub async fn start() -> Result<Self> {
+        Self::start_on(SocketAddr::from(([127, 0, 0, 1], 0))).await
+    }
+
+    pub async fn start_on(addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("bind stand-in blossom on {addr}"))?;
+        let url = format!(
+            "http://{}",
+            listener
+                .local_addr()
+                .context("read stand-in blossom addr")?
+        );
+        let state = Arc::new(Mutex::new(ServerState {
+            blobs: HashMap::new(),
+            uploads: HashMap::new(),
+            chunked_uploads: true,
+            interruptions_left: 0,
+            interrupt_after_bytes: 0,
+            upload_body_bytes: 0,
+            download_range_starts: Vec::new(),
+        }));
+
+        let task = {
+            let state = state.clone();
+            let url = url.clone();
+            tokio::spawn(async move {
+                loop {
+                    let Ok((stream, _)) = listener.accept().await else {
+                        continue;
+                    };
+                    let state = state.clone();
+                    let url = url.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = serve_connection(stream, &url, &state).await {
+                            tracing::debug!(%err, "stand-in blossom connection ended");
+                        }
+                    });
+                }
+            })
+        };
+
+        Ok(Self { url, state, task })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Enable or disable the chunked upload extension.
+    pub fn set_chunked_uploads(&self, enabled: bool) {
+        self.lock().chunked_uploads = enabled;
+    }
+
+    /// Cut the next `count` upload or download bodies off after `after_bytes` bytes.
+    pub fn interrupt_transfers(&self, count: usize, after_bytes: usize) {
+        let mut state = self.lock();
+        state.interruptions_left = count;
+        state.interrupt_after_bytes = after_bytes;
+    }
+
+    /// Store a blob directly and return its sha256.
+    pub fn insert_blob(&self, data: Vec<u8>, mime_type: &str) -> String {
+        let hash = hex::encode(Sha256::digest(&data));
+        self.lock().blobs.insert(
+            hash.clone(),
+            StoredBlob {
+                data,
+                mime_type: mime_type.to_string(),
+            },
+        );
+        hash
+    }
+
+    /// Seed an in-progress chunked upload, as if an earlier session had sent `data`.
+    pub fn seed_partial_upload(&self, hash: &str, data: Vec<u8>) {
+        self.lock().uploads.insert(hash.to_ascii_lowercase(), data);
+    }
+
+    pub fn blob(&self, hash: &str) -> Option<Vec<u8>> {
+        self.lock()
+            .blobs
+            .get(&hash.to_ascii_lowercase())
+            .map(|blob| blob.data.clone())
+    }
+
+    /// Upload body bytes received so far, across `PATCH` and `PUT`.
+    pub fn upload_body_bytes(&self) -> u64 {
+        self.lock().upload_body_bytes
+    }
+
+    /// Start offsets of every blob `GET`, in request order.
+    pub fn download_range_starts(&self) -> Vec<u64> {
+        self.lock().download_range_starts.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ServerState> {
+        self.state.lock().expect("stand-in blossom state poisoned")
+    }
+}
+
+impl Drop for StandInBlossom {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body_prefix: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    fn header_u64(&self, name: &str) -> Option<u64> {
+        self.header(name)?.trim().parse().ok()
+    }
+}
+
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn header(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    fn json(self, value: serde_json::Value) -> Self {
+        let mut response = self.header("Content-Type", "application/json");
+        response.body = value.to_string().into_bytes();
+        response
+    }
+
+    fn reason(self, reason: &str) -> Self {
+        self.header("X-Reason", reason)
+    }
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    base_url: &str,
+    state: &Mutex<ServerState>,
+) -> Result<()> {
+    let request = read_request_head(&mut stream).await?;
+    let path = request
+        .path
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let response = match (request.method.as_str(), path.as_str()) {
+        ("PUT", "/upload") => {
+            let Some(body) = read_body(&mut stream, &request, state).await? else {
+                return Ok(());
+            };
+            if request.header("authorization").is_none() {
+                Response::new("401 Unauthorized").reason("missing authorization")
+            } else {
+                let hash = hex::encode(Sha256::digest(&body));
+                let mime_type = request
+                    .header("content-type")
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let descriptor = store_blob(state, base_url, &hash, body, mime_type);
+                Response::new("200 OK").json(descriptor)
+            }
+        }
+        ("HEAD", path) if path.starts_with("/upload/") => {
+            let hash = path.trim_start_matches("/upload/").to_ascii_lowercase();
+            let state = lock(state);
+            match state.uploads.get(&hash) {
+                Some(received) if state.chunked_uploads => {
+                    Response::new("200 OK").header(UPLOAD_OFFSET_HEADER, received.len())
+                }
+                _ => Response::new("404 Not Found"),
+            }
+        }
+        ("PATCH", path) if path.starts_with("/upload/") => {
+            let hash = path.trim_start_matches("/upload/").to_ascii_lowercase();
+            match patch_upload(&mut stream, &request, state, base_url, &hash).await? {
+                Some(response) => response,
+                None => return Ok(()),
+            }
+        }
+        ("GET" | "HEAD", path) => {
+            let hash = path
+                .trim_start_matches('/')
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let blob = lock(state).blobs.get(&hash).cloned();
+            match blob {
+                Some(blob) => {
+                    return serve_blob(&mut stream, &request, state, blob).await;
+                }
+                None => Response::new("404 Not Found"),
+            }
+        }
+        _ => Response::new("404 Not Found"),
+    };
+
+    write_response(&mut stream, &request.method, response).await
+}
+
+/// Apply one chunk of a chunked upload. `None` means the connection was cut on purpose.
+async fn patch_upload(
+    stream: &mut TcpStream,
+    request: &Request,
+    state: &Mutex<ServerState>,
+    base_url: &str,
+    hash: &str,
+) -> Result<Option<Response>> {
+    if !lock(state).chunked_uploads {
+        return Ok(Some(Response::new("405 Method Not Allowed")));
+    }
+    if let Err(err) = verify_upload_authorization(request, hash) {
+        return Ok(Some(
+            Response::new("401 Unauthorized").reason(&err.to_string()),
+        ));
+    }
+    let (Some(offset), Some(length)) = (
+        request.header_u64(UPLOAD_OFFSET_HEADER),
+        request.header_u64(UPLOAD_LENGTH_HEADER),
+    ) else {
+        return Ok(Some(
+            Response::new("400 Bad Request").reason("missing Upload-Offset or Upload-Length"),
+        ));
+    };
+
+    let held = lock(state)
+        .uploads
+        .get(hash)
+        .map(|received| received.len() as u64)
+        .unwrap_or(0);
+    if offset != held {
+        return Ok(Some(
+            Response::new("409 Conflict").header(UPLOAD_OFFSET_HEADER, held),
+        ));
+    }
+
+    let interrupted = lock(state).take_interruption();
+    let body = match interrupted {
+        Some(after_bytes) => read_body_prefix(stream, request, after_bytes).await?,
+        None => read_exact_body(stream, request).await?,
+    };
+
+    let mut guard = lock(state);
+    guard.upload_body_bytes += body.len() as u64;
+    let received = guard.uploads.entry(hash.to_string()).or_default();
+    received.extend_from_slice(&body);
+    let received_len = received.len() as u64;
+    if interrupted.is_some() {
+        // Keep what arrived and drop the connection without answering.
+        return Ok(None);
+    }
+    if received_len > length {
+        guard.uploads.remove(hash);
+        return Ok(Some(
+            Response::new("400 Bad Request").reason("upload longer than Upload-Length"),
+        ));
+    }
+    if received_len < length {
+        return Ok(Some(
+            Response::new("204 No Content").header(UPLOAD_OFFSET_HEADER, received_len),
+        ));
+    }
+
+    let data = guard.uploads.remove(hash).unwrap_or_default();
+    drop(guard);
+    if hex::encode(Sha256::digest(&data)) != hash {
+        return Ok(Some(
+            Response::new("400 Bad Request").reason("uploaded bytes do not match sha256"),
+        ));
+    }
+    let mime_type = request
+        .header("content-type")
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    let descriptor = store_blob(state, base_url, hash, data, mime_type);
+    Ok(Some(Response::new("200 OK").json(descriptor)))
+}
+
+async fn serve_blob(
+    stream: &mut TcpStream,
+    request: &Request,
+    state: &Mutex<ServerState>,
+    blob: StoredBlob,
+) -> Result<()> {
+    let total = blob.data.len() as u64;
+    let range = request.header("range").and_then(parse_range);
+    let (status, start, end) = match range {
+        Some((start, end)) if start >= total || end.is_some_and(|end| end < start) => {
+            let response = Response::new("416 Range Not Satisfiable")
+                .header("Content-Range", format!("bytes */{total}"));
+            return write_response(stream, &request.method, response).await;
+        }
+        Some((start, end)) => {
+            let end = end.unwrap_or(total - 1).min(total - 1);
+            ("206 Partial Content", start, end)
+        }
+        None => ("200 OK", 0, total.saturating_sub(1)),
+    };
+    let body = if total == 0 {
+        Vec::new()
+    } else {
+        blob.data[start as usize..=end as usize].to_vec()
+    };
+
+    let mut response = Response::new(status)
+        .header("Content-Type", &blob.mime_type)
+        .header("Accept-Ranges", "bytes");
+    if status.starts_with("206") {
+        response = response.header("Content-Range", format!("bytes {start}-{end}/{total}"));
+    }
+    if request.method == "HEAD" {
+        response
+            .headers
+            .push(("Content-Length", body.len().to_string()));
+        return write_response(stream, &request.method, response).await;
+    }
+
+    let interrupted = {
+        let mut state = lock(state);
+        state.download_range_starts.push(start);
+        state.take_interruption()
+    };
+    let Some(after_bytes) = interrupted else {
+        response.body = body;
+        return write_response(stream, &request.method, response).await;
+    };
+
+    // Promise the whole range, send only part of it, then hang up.
+    response
+        .headers
+        .push(("Content-Length", body.len().to_string()));
+    write_head(stream, &response).await?;
+    stream
+        .write_all(&body[..after_bytes.min(body.len())])
+        .await
+        .context("write partial body")?;
+    stream.flush().await.context("flush partial body")?;
+    Ok(())
+}
+
+fn store_blob(
+    state: &Mutex<ServerState>,
+    base_url: &str,
+    hash: &str,
+    data: Vec<u8>,
+    mime_type: String,
+) -> serde_json::Value {
+    let size = data.len();
+    let descriptor = serde_json::json!({
+        "url": format!("{base_url}/{hash}"),
+        "sha256": hash,
+        "size": size,
+        "type": mime_type,
+        "uploaded": chrono::Utc::now().timestamp(),
+    });
+    lock(state)
+        .blobs
+        .insert(hash.to_string(), StoredBlob { data, mime_type });
+    descriptor
+}
+
+fn verify_upload_authorization(request: &Request, hash: &str) -> Result<()> {
+    let header = request
+        .header("authorization")
+        .ok_or_else(|| anyhow!("missing authorization"))?;
+    let encoded = header
+        .strip_prefix("Nostr ")
+        .ok_or_else(|| anyhow!("authorization is not a Nostr event"))?;
+    let json = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .context("decode authorization")?;
+    let event = Event::from_json(json).context("parse authorization event")?;
+    event.verify().context("verify authorization event")?;
+    if event.kind.as_u16() != BLOSSOM_AUTH_KIND {
+        bail!("authorization has kind {}", event.kind);
+    }
+    let has_tag = |name: &str, value: &str| {
+        event
+            .tags
+            .iter()
+            .any(|tag| tag.as_slice().get(..2) == Some(&[name.to_string(), value.to_string()][..]))
+    };
+    if !has_tag("t", "upload") {
+        bail!("authorization is not for uploads");
+    }
+    if !has_tag("x", hash) {
+        bail!("authorization is for a different blob");
+    }
+    Ok(())
+}
+
+/// Parse `bytes=<start>-[<end>]`.
+fn parse_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (start, end) = value.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let start = start.trim().parse().ok()?;
+    let end = match end.trim() {
+        "" => None,
+        end => Some(end.parse().ok()?),
+    };
+    Some((start, end))
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> Result<Request> {
+    let mut buf = Vec::new();
+    let header_end = loop {
+        if let Some(idx) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break idx + 4;
+        }
+        if buf.len() > MAX_HEADER_BYTES {
+            bail!("request headers too large");
+        }
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await.context("read request head")?;
+        if n == 0 {
+            bail!("connection closed before request head");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body_prefix: buf[header_end..].to_vec(),
+    })
+}
+
+/// Read a full body, or `None` if this transfer was cut short on purpose.
+async fn read_body(
+    stream: &mut TcpStream,
+    request: &Request,
+    state: &Mutex<ServerState>,
+) -> Result<Option<Vec<u8>>> {
+    let interrupted = lock(state).take_interruption();
+    match interrupted {
+        Some(after_bytes) => {
+            let body = read_body_prefix(stream, request, after_bytes).await?;
+            lock(state).upload_body_bytes += body.len() as u64;
+            Ok(None)
+        }
+        None => {
+            let body = read_exact_body(stream, request).await?;
+            lock(state).upload_body_bytes += body.len() as u64;
+            Ok(Some(body))
+        }
+    }
+}
+
+async fn read_exact_body(stream: &mut TcpStream, request: &Request) -> Result<Vec<u8>> {
+    let length = request.header_u64("content-length").unwrap_or(0) as usize;
+    read_body_prefix(stream, request, length).await
+}
+
+/// Read up to `limit` body bytes (never past `Content-Length`).
+async fn read_body_prefix(
+    stream: &mut TcpStream,
+    request: &Request,
+    limit: usize,
+) -> Result<Vec<u8>> {
+    let length = request.header_u64("content-length").unwrap_or(0) as usize;
+    let want = limit.min(length);
+    let mut body = request.body_prefix.clone();
+    body.truncate(want);
+    while body.len() < want {
+        let mut chunk = vec![0u8; (want - body.len()).min(64 * 1024)];
+        let n = stream.read(&mut chunk).await.context("read request body")?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    Ok(body)
+}
+
+async fn write_head(stream: &mut TcpStream, response: &Response) -> Result<()> {
+    let mut head = format!("HTTP/1.1 {}\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("Connection: close\r\n\r\n");
+    stream
+        .write_all(head.as_bytes())
+        .await
+        .context("write response head")
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    method: &str,
+    mut response: Response,
+) -> Result<()> {
+    if !response
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+    {
+        response
+            .headers
+            .push(("Content-Length", response.body.len().to_string()));
+    }
+    write_head(stream, &response).await?;
+    if method != "HEAD" {
+        stream
+            .write_all(&response.body)
+            .await
+            .context("write response body")?;
+    }
+    stream.flush().await.context("flush response")?;
+    Ok(())
+}
+
+fn lock(state: &Mutex<ServerState>) -> std::sync::MutexGuard<'_, ServerState> {
+    state.lock().expect("stand-in blossom state poisoned")
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr_sdk::Keys;
+    use pika_marmot_runtime::transfer::{
+        TRANSFER_CHUNK_BYTES, TransferProgress, download_blob_resumable, upload_blob_resumable,
+    };
+
+    use super::*;
+
+    fn sample_blob(len: usize) -> (Vec<u8>, String) {
+        let data: Vec<u8> = (0..len).map(|i| (i * 31 % 251) as u8).collect();
+        let hash = hex::encode(Sha256::digest(&data));
+        (data, hash)
+    }
+
+    fn assert_monotonic(progress: &[TransferProgress], total: u64) {
+        assert!(!progress.is_empty(), "expected progress callbacks");
+        for pair in progress.windows(2) {
+            assert!(pair[0].transferred_bytes <= pair[1].transferred_bytes);
+        }
+        let last = progress.last().unwrap();
+        assert_eq!(last.transferred_bytes, total);
+        assert_eq!(last.total_bytes, total);
+    }
+
+    #[tokio::test]
+    async fn chunked_upload_survives_interrupted_chunks() {
+        let server = StandInBlossom::start().await.unwrap();
+        let (data, hash) = sample_blob(3 * TRANSFER_CHUNK_BYTES + 1234);
+        server.interrupt_transfers(2, 10_000);
+
+        let mut progress = Vec::new();
+        let uploaded = upload_blob_resumable(
+            &reqwest::Client::new(),
+            &Keys::generate(),
+            &data,
+            "video/mp4",
+            &hash,
+            &[server.url().to_string()],
+            |p| progress.push(p),
+        )
+        .await
+        .expect("upload");
+
+        assert_eq!(uploaded.descriptor_sha256_hex, hash);
+        assert_eq!(uploaded.uploaded_url, format!("{}/{hash}", server.url()));
+        assert_eq!(server.blob(&hash).as_deref(), Some(&data[..]));
+        // Interrupted chunks only resend what the server had not kept.
+        assert_eq!(server.upload_body_bytes(), data.len() as u64);
+        assert_monotonic(&progress, data.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn chunked_upload_resumes_from_server_offset() {
+        let server = StandInBlossom::start().await.unwrap();
+        let (data, hash) = sample_blob(2 * TRANSFER_CHUNK_BYTES);
+        let already_sent = TRANSFER_CHUNK_BYTES + 77;
+        server.seed_partial_upload(&hash, data[..already_sent].to_vec());
+
+        let mut progress = Vec::new();
+        upload_blob_resumable(
+            &reqwest::Client::new(),
+            &Keys::generate(),
+            &data,
+            "application/octet-stream",
+            &hash,
+            &[server.url().to_string()],
+            |p| progress.push(p),
+        )
+        .await
+        .expect("upload");
+
+        assert_eq!(server.blob(&hash).as_deref(), Some(&data[..]));
+        assert_eq!(
+            server.upload_body_bytes(),
+            (data.len() - already_sent) as u64
+        );
+        assert_eq!(progress[0].transferred_bytes, already_sent as u64);
+    }
+
+    #[tokio::test]
+    async fn upload_falls_back_to_whole_blob_put() {
+        let server = StandInBlossom::start().await.unwrap();
+        server.set_chunked_uploads(false);
+        let (data, hash) = sample_blob(TRANSFER_CHUNK_BYTES + 5);
+
+        let uploaded = upload_blob_resumable(
+            &reqwest::Client::new(),
+            &Keys::generate(),
+            &data,
+            "application/octet-stream",
+            &hash,
+            &[server.url().to_string()],
+            |_| {},
+        )
+        .await
+        .expect("upload");
+
+        assert_eq!(uploaded.descriptor_sha256_hex, hash);
+        assert_eq!(server.blob(&hash).as_deref(), Some(&data[..]));
+    }
+
+    #[tokio::test]
+    async fn download_survives_interrupted_bodies() {
+        let server = StandInBlossom::start().await.unwrap();
+        let (data, _) = sample_blob(2 * TRANSFER_CHUNK_BYTES + 999);
+        let hash = server.insert_blob(data.clone(), "video/mp4");
+        server.interrupt_transfers(2, 50_000);
+        let dir = tempfile::tempdir().unwrap();
+        let part_path = dir.path().join("blob.part");
+
+        let mut progress = Vec::new();
+        let downloaded = download_blob_resumable(
+            &reqwest::Client::new(),
+            &format!("{}/{hash}", server.url()),
+            &part_path,
+            |p| progress.push(p),
+        )
+        .await
+        .expect("download");
+
+        assert_eq!(downloaded, data);
+        let starts = server.download_range_starts();
+        assert_eq!(&starts[..3], &[0, 50_000, 100_000]);
+        assert_monotonic(&progress, data.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn download_resumes_from_partial_file_after_restart() {
+        let server = StandInBlossom::start().await.unwrap();
+        let (data, _) = sample_blob(TRANSFER_CHUNK_BYTES + 4321);
+        let hash = server.insert_blob(data.clone(), "application/octet-stream");
+        let dir = tempfile::tempdir().unwrap();
+        let part_path = dir.path().join("blob.part");
+        let already_fetched = 200_000;
+        std::fs::write(&part_path, &data[..already_fetched]).unwrap();
+
+        let downloaded = download_blob_resumable(
+            &reqwest::Client::new(),
+            &format!("{}/{hash}", server.url()),
+            &part_path,
+            |_| {},
+        )
+        .await
+        .expect("download");
+
+        assert_eq!(downloaded, data);
+        assert_eq!(server.download_range_starts()[0], already_fetched as u64);
+    }
+
+    #[tokio::test]
+    async fn download_of_complete_part_file_is_a_no_op_fetch() {
+        let server = StandInBlossom::start().await.unwrap();
+        let (data, _) = sample_blob(1000);
+        let hash = server.insert_blob(data.clone(), "application/octet-stream");
+        let dir = tempfile::tempdir().unwrap();
+        let part_path = dir.path().join("blob.part");
+        std::fs::write(&part_path, &data).unwrap();
+
+        let downloaded = download_blob_resumable(
+            &reqwest::Client::new(),
+            &format!("{}/{hash}", server.url()),
+            &part_path,
+            |_| {},
+        )
+        .await
+        .expect("download");
+
+        assert_eq!(downloaded, data);
+    }
+}
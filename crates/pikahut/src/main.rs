@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use pikahut::{blossom_upload, config, fixture, test_harness};
+use pikahut::{blossom_server, blossom_upload, config, fixture, test_harness};
 use tracing_subscriber::EnvFilter;
 
 #[derive(Debug, Parser)]
@@ -101,6 +101,8 @@ enum Command {
     },
     /// Upload files to blossom servers and print URLs
     BlossomUpload(blossom_upload::BlossomUploadArgs),
+    /// Serve a local stand-in Blossom server with chunked, resumable transfers
+    BlossomServe(blossom_server::BlossomServeArgs),
 }
 
 #[tokio::main]
@@ -188,5 +190,6 @@ async fn main() -> Result<()> {
         }
         Command::Test { command } => test_harness::run(command).await,
         Command::BlossomUpload(args) => blossom_upload::run(args).await,
+        Command::BlossomServe(args) => blossom_server::run(args).await,
     }
 }
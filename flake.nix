@@ -414,6 +414,7 @@
             pkgs.diesel-cli
             pkgs.openssl
             pkgs.pkg-config
+            pkgs.cmake
             pkgs.cargo-watch
           ] ++ pkgs.lib.optionals pkgs.stdenv.isDarwin [
             pkgs.xcodegen
@@ -25,11 +25,6 @@ class NotificationService: UNNotificationServiceExtension {
             return
         }
 
-        guard let nsec = SharedKeychainHelper.getNsec() else {
-            contentHandler(request.content)
-            return
-        }
-
         let appGroup = Bundle.main.infoDictionary?["PikaAppGroup"] as? String ?? "group.org.pikachat.pika"
         let keychainGroup = Bundle.main.infoDictionary?["PikaKeychainGroup"] as? String ?? ""
 
@@ -37,7 +32,22 @@ class NotificationService: UNNotificationServiceExtension {
             .containerURL(forSecurityApplicationGroupIdentifier: appGroup)!
             .appendingPathComponent("Library/Application Support").path
 
-        switch decryptPushNotification(dataDir: dataDir, nsec: nsec, eventJson: eventJson, keychainGroup: keychainGroup) {
+        let nsecs = SharedKeychainHelper.accountNsecs(dataDir: dataDir)
+        guard !nsecs.isEmpty else {
+            contentHandler(request.content)
+            return
+        }
+
+        // The push doesn't say which account it's for; the first account that
+        // can process the event owns it.
+        var decrypted: PushNotificationResult?
+        for nsec in nsecs {
+            decrypted = decryptPushNotification(dataDir: dataDir, nsec: nsec, eventJson: eventJson, keychainGroup: keychainGroup)
+            if case .error = decrypted { continue }
+            break
+        }
+
+        switch decrypted {
         case .content(let msg):
             content.body = msg.content
             content.userInfo["chat_id"] = msg.chatId
@@ -215,12 +225,28 @@ class NotificationService: UNNotificationServiceExtension {
     }
 }
 
-/// Reads the nsec from the shared keychain access group.
+/// Reads account nsecs from the shared keychain access group.
 enum SharedKeychainHelper {
     private static let service = "com.pika.app"
-    private static let account = "nsec"
 
-    static func getNsec() -> String? {
+    /// The active account's nsec first, then every other signed-in account
+    /// (one directory per account under `accounts/`).
+    static func accountNsecs(dataDir: String) -> [String] {
+        var nsecs: [String] = []
+        if let active = getNsec(account: "nsec") {
+            nsecs.append(active)
+        }
+        let accountsDir = (dataDir as NSString).appendingPathComponent("accounts")
+        let pubkeys = (try? FileManager.default.contentsOfDirectory(atPath: accountsDir)) ?? []
+        for pubkey in pubkeys.sorted() {
+            if let nsec = getNsec(account: "nsec.\(pubkey)"), !nsecs.contains(nsec) {
+                nsecs.append(nsec)
+            }
+        }
+        return nsecs
+    }
+
+    static func getNsec(account: String) -> String? {
         let accessGroup = Bundle.main.infoDictionary?["PikaKeychainGroup"] as? String ?? ""
         var query: [String: Any] = [
             kSecClass as String: kSecClassGenericPassword,
@@ -29,16 +29,22 @@ protocol AuthStore: AnyObject {
     func saveBunker(bunkerUri: String, bunkerClientNsec: String)
     func clear()
     func getNsec() -> String?
+    /// Per-account nsecs, kept so signed-in accounts can be switched back to after a relaunch.
+    func saveAccountNsec(_ nsec: String, pubkey: String)
+    func accountNsec(pubkey: String) -> String?
+    func removeAccountNsec(pubkey: String)
 }
 
 final class KeychainAuthStore: AuthStore {
     private let localNsecStore: KeychainNsecStore
     private let bunkerClientNsecStore: KeychainNsecStore
+    private let keychainGroup: String?
     private let defaults = UserDefaults.standard
     private let modeKey = "pika.auth.mode"
     private let bunkerUriKey = "pika.auth.bunker_uri"
 
     init(keychainGroup: String? = nil) {
+        self.keychainGroup = keychainGroup
         localNsecStore = KeychainNsecStore(account: "nsec", keychainGroup: keychainGroup)
         bunkerClientNsecStore = KeychainNsecStore(account: "bunker_client_nsec", keychainGroup: keychainGroup)
     }
@@ -100,6 +106,29 @@ final class KeychainAuthStore: AuthStore {
         guard let stored = load(), stored.mode == .localNsec else { return nil }
         return stored.nsec
     }
+
+    // Keychain account "nsec.<pubkey>"; the notification extension reads the same items.
+    private func accountStore(pubkey: String) -> KeychainNsecStore {
+        KeychainNsecStore(account: "nsec.\(pubkey)", keychainGroup: keychainGroup)
+    }
+
+    func saveAccountNsec(_ nsec: String, pubkey: String) {
+        let trimmed = nsec.trimmingCharacters(in: .whitespacesAndNewlines)
+        guard !trimmed.isEmpty, !pubkey.isEmpty else { return }
+        accountStore(pubkey: pubkey).setNsec(trimmed)
+    }
+
+    func accountNsec(pubkey: String) -> String? {
+        guard !pubkey.isEmpty, let nsec = accountStore(pubkey: pubkey).getNsec(), !nsec.isEmpty else {
+            return nil
+        }
+        return nsec
+    }
+
+    func removeAccountNsec(pubkey: String) {
+        guard !pubkey.isEmpty else { return }
+        accountStore(pubkey: pubkey).clearNsec()
+    }
 }
 
 @MainActor
@@ -206,6 +235,7 @@ final class AppManager: AppReconciler {
         let appVersion = Bundle.main.infoDictionary?["CFBundleShortVersionString"] as? String ?? "0.0.0"
         let core = FfiApp(dataDir: dataDir, keychainGroup: keychainGroup, appVersion: appVersion)
         core.setExternalSignerBridge(bridge: IOSExternalSignerBridge())
+        core.setMediaProcessor(processor: IOSMediaProcessor())
         self.init(core: core, authStore: authStore)
     }
 
@@ -220,11 +250,12 @@ final class AppManager: AppReconciler {
 
         // Side-effect updates must not be lost: `AccountCreated` carries an `nsec` that isn't in
         // AppState snapshots (by design). Store it even if the update is stale w.r.t. rev.
-        if case .accountCreated(_, let nsec, _, _) = update {
+        if case .accountCreated(_, let nsec, let pubkey, _) = update {
             let existing = authStore.load()?.nsec ?? ""
             if existing.isEmpty && !nsec.isEmpty {
                 authStore.saveLocalNsec(nsec)
             }
+            authStore.saveAccountNsec(nsec, pubkey: pubkey)
         } else if case .bunkerSessionDescriptor(_, let bunkerUri, let clientNsec) = update {
             if !bunkerUri.isEmpty, !clientNsec.isEmpty {
                 authStore.saveBunker(bunkerUri: bunkerUri, bunkerClientNsec: clientNsec)
@@ -290,9 +321,56 @@ final class AppManager: AppReconciler {
     }
 
     func logout() {
+        let active = activeAccountPubkey
+        if let active {
+            authStore.removeAccountNsec(pubkey: active)
+        }
         authStore.clear()
         clearShareExtensionState()
         dispatch(.logout)
+
+        // Rust falls back to another account it still holds a signer for; after a
+        // relaunch it only has what we stored, so sign the next one back in.
+        let fallback = state.accounts
+            .filter { $0.pubkey != active }
+            .lazy
+            .compactMap { self.authStore.accountNsec(pubkey: $0.pubkey) }
+            .first
+        if let fallback {
+            dispatch(.addAccount(nsec: fallback))
+        }
+    }
+
+    /// Signs in another account alongside the current one and switches to it.
+    func addAccount(nsec: String) {
+        let trimmed = nsec.trimmingCharacters(in: .whitespacesAndNewlines)
+        if let pubkey = pubkeyForNsec(nsec: trimmed) {
+            authStore.saveAccountNsec(trimmed, pubkey: pubkey)
+        }
+        dispatch(.addAccount(nsec: trimmed))
+    }
+
+    func switchAccount(pubkey: String) {
+        // A stored nsec works even if Rust hasn't seen this account's signer since launch.
+        if let nsec = authStore.accountNsec(pubkey: pubkey) {
+            dispatch(.addAccount(nsec: nsec))
+        } else {
+            dispatch(.switchAccount(pubkey: pubkey))
+        }
+    }
+
+    func removeAccount(pubkey: String) {
+        if pubkey == activeAccountPubkey {
+            logout()
+            return
+        }
+        authStore.removeAccountNsec(pubkey: pubkey)
+        dispatch(.removeAccount(pubkey: pubkey))
+    }
+
+    private var activeAccountPubkey: String? {
+        guard case .loggedIn(_, let pubkey, _) = state.auth else { return nil }
+        return pubkey
     }
 
     var isDeveloperModeEnabled: Bool {
@@ -303,6 +381,42 @@ final class AppManager: AppReconciler {
         dispatch(.enableDeveloperMode)
     }
 
+    var isReadReceiptsEnabled: Bool {
+        state.readReceiptsEnabled
+    }
+
+    func setReadReceiptsEnabled(_ enabled: Bool) {
+        dispatch(.setReadReceiptsEnabled(enabled: enabled))
+    }
+
+    /// Writes the backup to a temp file for the share sheet; Rust reports the result as a toast.
+    func exportBackup(passphrase: String) -> URL {
+        let url = FileManager.default.temporaryDirectory
+            .appendingPathComponent("pika-backup.pikabackup")
+        dispatch(.exportBackup(path: url.path, passphrase: passphrase))
+        return url
+    }
+
+    func importBackup(from url: URL, passphrase: String) {
+        // Files picked via fileImporter are security-scoped; copy before handing the path to Rust.
+        let didStartAccess = url.startAccessingSecurityScopedResource()
+        defer {
+            if didStartAccess {
+                url.stopAccessingSecurityScopedResource()
+            }
+        }
+        let copy = FileManager.default.temporaryDirectory
+            .appendingPathComponent("pika-restore.pikabackup")
+        do {
+            try? FileManager.default.removeItem(at: copy)
+            try FileManager.default.copyItem(at: url, to: copy)
+        } catch {
+            NSLog("[PikaAppManager] importBackup copy failed: \(error.localizedDescription)")
+            return
+        }
+        dispatch(.importBackup(path: copy.path, passphrase: passphrase))
+    }
+
     func wipeProfileCacheForDeveloperTools() {
         dispatch(.wipeProfileCache)
     }
@@ -327,6 +441,12 @@ final class AppManager: AppReconciler {
             return
         }
 
+        if isGroupInviteLink(input: url.absoluteString) {
+            NSLog("[PikaAppManager] onOpenURL dispatching JoinGroupViaInviteLink")
+            dispatch(.joinGroupViaInviteLink(link: url.absoluteString))
+            return
+        }
+
         if Self.isShareDispatchDeepLink(url) {
             NSLog("[PikaAppManager] onOpenURL processing pending share queue")
             processPendingShareQueue(openFirstChat: true)
@@ -429,13 +549,22 @@ final class AppManager: AppReconciler {
     }
 
     private func syncAuthStoreWithAuthState() {
-        guard case .loggedIn(_, _, let mode) = state.auth else { return }
+        guard case .loggedIn(_, let pubkey, let mode) = state.auth else { return }
 
         switch mode {
         case .localNsec:
             if authStore.load()?.mode != .localNsec {
                 authStore.clear()
             }
+            // File the nsec we logged in with under its account, and make the active
+            // account's nsec the one restored on next launch.
+            if let nsec = authStore.accountNsec(pubkey: pubkey) {
+                if authStore.getNsec() != nsec {
+                    authStore.saveLocalNsec(nsec)
+                }
+            } else if let nsec = authStore.getNsec() {
+                authStore.saveAccountNsec(nsec, pubkey: pubkey)
+            }
         case .bunkerSigner(let bunkerUri):
             let clientNsec = authStore.load()?.bunkerClientNsec ?? ""
             if !clientNsec.isEmpty {
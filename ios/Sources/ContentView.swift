@@ -188,6 +188,15 @@ struct ContentView: View {
                 onToggleCamera: {
                     manager.dispatch(.toggleCamera)
                 },
+                onToggleAudioProcessing: {
+                    manager.dispatch(.toggleAudioProcessing)
+                },
+                onToggleRecording: {
+                    manager.dispatch(.toggleCallRecording)
+                },
+                onSaveCaptions: {
+                    manager.dispatch(.saveCallCaptions(callId: call.callId))
+                },
                 onFlipCamera: {
                     videoPipeline.switchCamera()
                 },
@@ -203,7 +212,8 @@ struct ContentView: View {
                     isCallScreenPresented = false
                 },
                 remotePixelBuffer: videoPipeline.remotePixelBuffer,
-                localCaptureSession: videoPipeline.localCaptureSession
+                localCaptureSession: videoPipeline.localCaptureSession,
+                participantNames: callParticipantNames(for: call, in: state)
             )
         }
     }
@@ -253,6 +263,8 @@ private func screenView(
             onLogout: { manager.logout() },
             onOpenChat: { manager.dispatch(.openChat(chatId: $0)) },
             onArchiveChat: { manager.dispatch(.archiveChat(chatId: $0)) },
+            onAcceptInvite: { manager.dispatch(.acceptInvite(inviteId: $0)) },
+            onDeclineInvite: { manager.dispatch(.declineInvite(inviteId: $0)) },
             onNewChat: { manager.dispatch(.pushScreen(screen: .newChat)) },
             onNewGroupChat: { manager.dispatch(.pushScreen(screen: .newGroupChat)) },
             onEnsureAgent: { manager.ensureAgent() },
@@ -265,15 +277,23 @@ private func screenView(
             },
             isDeveloperModeEnabledProvider: { manager.isDeveloperModeEnabled },
             onEnableDeveloperMode: { manager.enableDeveloperMode() },
+            isReadReceiptsEnabledProvider: { manager.isReadReceiptsEnabled },
+            onSetReadReceiptsEnabled: { manager.setReadReceiptsEnabled($0) },
+            onExportBackup: { manager.exportBackup(passphrase: $0) },
+            onImportBackup: { manager.importBackup(from: $0, passphrase: $1) },
             onWipeProfileCache: { manager.wipeProfileCacheForDeveloperTools() },
             onWipeMediaCache: { manager.dispatch(.wipeMediaCache) },
             onWipeLocalData: { manager.wipeLocalDataForDeveloperTools() },
+            onAddAccount: { manager.addAccount(nsec: $0) },
+            onSwitchAccount: { manager.switchAccount(pubkey: $0) },
+            onRemoveAccount: { manager.removeAccount(pubkey: $0) },
             nsecProvider: { manager.getNsec() }
         )
     case .newChat:
         NewChatView(
             state: newChatState(from: state),
             onCreateChat: { manager.dispatch(.createChat(peerNpub: $0)) },
+            onJoinGroup: { manager.dispatch(.joinGroupViaInviteLink(link: $0)) },
             onRefreshFollowList: { manager.dispatch(.refreshFollowList) }
         )
     case .newGroupChat:
@@ -371,6 +391,18 @@ private func screenView(
             },
             onRetryMessage: { chatId, messageId in
                 manager.dispatch(.retryMessage(chatId: chatId, messageId: messageId))
+            },
+            onEditMessage: { messageId, content in
+                manager.dispatch(.editMessage(chatId: chatId, messageId: messageId, content: content))
+            },
+            onDeleteMessage: { messageId in
+                manager.dispatch(.deleteMessage(chatId: chatId, messageId: messageId))
+            },
+            onSetDisappearingTimer: { secs in
+                manager.dispatch(.setDisappearingTimer(chatId: chatId, expireAfterSecs: secs))
+            },
+            onSetNotificationMode: { mode in
+                manager.dispatch(.setChatNotificationMode(chatId: chatId, mode: mode))
             }
         )
         .onAppear {
@@ -418,6 +450,8 @@ private func screenView(
                     },
                     onFollow: { manager.dispatch(.followUser(pubkey: profile.pubkey)) },
                     onUnfollow: { manager.dispatch(.unfollowUser(pubkey: profile.pubkey)) },
+                    onBlock: { manager.dispatch(.blockUser(pubkey: profile.pubkey)) },
+                    onUnblock: { manager.dispatch(.unblockUser(pubkey: profile.pubkey)) },
                     onOpenMediaGallery: {
                         manager.dispatch(.pushScreen(screen: .chatMedia(chatId: chatId)))
                     },
@@ -434,6 +468,13 @@ private func screenView(
             onRemoveMember: { pubkey in
                 manager.dispatch(.removeGroupMembers(chatId: chatId, memberPubkeys: [pubkey]))
             },
+            onSetAdmin: { pubkey, admin in
+                if admin {
+                    manager.dispatch(.promoteAdmin(chatId: chatId, pubkey: pubkey))
+                } else {
+                    manager.dispatch(.demoteAdmin(chatId: chatId, pubkey: pubkey))
+                }
+            },
             onLeaveGroup: {
                 manager.dispatch(.leaveGroup(chatId: chatId))
             },
@@ -455,6 +496,22 @@ private func screenView(
             },
             onOpenMediaGallery: {
                 manager.dispatch(.pushScreen(screen: .chatMedia(chatId: chatId)))
+            },
+            onCreateInviteLink: { multiUse, requiresApproval in
+                manager.dispatch(.createGroupInviteLink(
+                    chatId: chatId,
+                    multiUse: multiUse,
+                    requiresApproval: requiresApproval
+                ))
+            },
+            onRevokeInviteLink: { token in
+                manager.dispatch(.revokeGroupInviteLink(chatId: chatId, token: token))
+            },
+            onApproveJoinRequest: { pubkey in
+                manager.dispatch(.approveJoinRequest(chatId: chatId, pubkey: pubkey))
+            },
+            onDeclineJoinRequest: { pubkey in
+                manager.dispatch(.declineJoinRequest(chatId: chatId, pubkey: pubkey))
             }
         )
         .sheet(isPresented: Binding(
@@ -496,6 +553,8 @@ private func screenView(
                     },
                     onFollow: { manager.dispatch(.followUser(pubkey: profile.pubkey)) },
                     onUnfollow: { manager.dispatch(.unfollowUser(pubkey: profile.pubkey)) },
+                    onBlock: { manager.dispatch(.blockUser(pubkey: profile.pubkey)) },
+                    onUnblock: { manager.dispatch(.unblockUser(pubkey: profile.pubkey)) },
                     onOpenMediaGallery: nil,
                     onClose: { manager.dispatch(.closePeerProfile) }
                 )
@@ -504,7 +563,10 @@ private func screenView(
     case .chatMedia(let chatId):
         ChatMediaGalleryView(
             chatId: chatId,
-            items: state.mediaGallery?.items ?? []
+            items: state.mediaGallery?.items ?? [],
+            onOpenRecording: { hash in
+                manager.dispatch(.openCallRecording(chatId: chatId, originalHashHex: hash))
+            }
         )
         .onAppear {
             manager.dispatch(.loadMediaGallery(chatId: chatId))
@@ -533,9 +595,11 @@ private func chatListState(from state: AppState, manager: AppManager) -> ChatLis
     let myNpub = myNpub(from: state)
     return ChatListViewState(
         chats: state.chatList,
+        invitations: state.invitations,
         myNpub: myNpub,
         myProfile: state.myProfile,
-        agentButton: state.agentButton
+        agentButton: state.agentButton,
+        accounts: state.accounts
     )
 }
 
@@ -634,9 +698,25 @@ private func callPeerMember(for call: CallState, in state: AppState) -> MemberIn
     return nil
 }
 
+@MainActor
+private func callParticipantNames(for call: CallState, in state: AppState) -> [String: String] {
+    guard call.isGroupCall else { return [:] }
+    let members: [MemberInfo]
+    if let currentChat = state.currentChat, currentChat.chatId == call.chatId {
+        members = currentChat.members
+    } else {
+        members = state.chatList.first(where: { $0.chatId == call.chatId })?.members ?? []
+    }
+    var names: [String: String] = [:]
+    for member in members {
+        names[member.npub] = member.name ?? shortenedNpub(member.npub)
+    }
+    return names
+}
+
 @MainActor
 private func callPeerDisplayName(for call: CallState, in state: AppState) -> String {
-    // Group calls: show group name (currently rejected at protocol level, but handle gracefully).
+    // Group calls: show the group name; participants are listed separately.
     if let currentChat = state.currentChat, currentChat.chatId == call.chatId, currentChat.isGroup {
         return currentChat.groupName ?? "Group"
     }
@@ -0,0 +1,101 @@
+import AVFoundation
+import Accelerate
+import Foundation
+import UIKit
+
+/// AVFoundation-backed poster extraction and transcoding for outgoing videos, and
+/// waveform decoding for voice notes. Rust calls this from a background thread, so
+/// blocking here is fine.
+final class IOSMediaProcessor: MediaProcessor, @unchecked Sendable {
+    /// Tried in order until an export fits under the requested size.
+    private static let exportPresets = [
+        AVAssetExportPreset1280x720,
+        AVAssetExportPreset960x540,
+        AVAssetExportPreset640x480,
+    ]
+
+    func extractPoster(path: String) -> VideoPoster? {
+        let asset = AVURLAsset(url: URL(fileURLWithPath: path))
+        let generator = AVAssetImageGenerator(asset: asset)
+        generator.appliesPreferredTrackTransform = true
+        generator.maximumSize = CGSize(width: 1280, height: 1280)
+
+        guard
+            let cgImage = try? generator.copyCGImage(at: .zero, actualTime: nil),
+            let jpeg = UIImage(cgImage: cgImage).jpegData(compressionQuality: 0.8)
+        else {
+            return nil
+        }
+
+        let seconds = asset.duration.seconds
+        let durationMs = seconds.isFinite && seconds > 0 ? UInt64(seconds * 1000) : 0
+        return VideoPoster(
+            imageData: jpeg,
+            width: UInt32(cgImage.width),
+            height: UInt32(cgImage.height),
+            durationMs: durationMs
+        )
+    }
+
+    func transcode(inputPath: String, outputPath: String, maxBytes: UInt64) -> Bool {
+        let asset = AVURLAsset(url: URL(fileURLWithPath: inputPath))
+        let outputURL = URL(fileURLWithPath: outputPath)
+
+        for preset in Self.exportPresets {
+            try? FileManager.default.removeItem(at: outputURL)
+            guard let session = AVAssetExportSession(asset: asset, presetName: preset) else {
+                continue
+            }
+            session.outputURL = outputURL
+            session.outputFileType = .mp4
+            session.shouldOptimizeForNetworkUse = true
+
+            let done = DispatchSemaphore(value: 0)
+            session.exportAsynchronously { done.signal() }
+            done.wait()
+
+            guard session.status == .completed else {
+                NSLog("[PikaMediaProcessor] export failed: \(session.error?.localizedDescription ?? "unknown")")
+                continue
+            }
+            let attributes = try? FileManager.default.attributesOfItem(atPath: outputPath)
+            if let size = (attributes?[.size] as? NSNumber)?.uint64Value, size <= maxBytes {
+                return true
+            }
+        }
+        try? FileManager.default.removeItem(at: outputURL)
+        return false
+    }
+
+    func audioWaveform(path: String, bars: UInt32) -> AudioWaveform? {
+        guard bars > 0, let audioFile = try? AVAudioFile(forReading: URL(fileURLWithPath: path)) else {
+            return nil
+        }
+        let format = audioFile.processingFormat
+        let frameCount = AVAudioFrameCount(audioFile.length)
+        guard frameCount > 0,
+              let buffer = AVAudioPCMBuffer(pcmFormat: format, frameCapacity: frameCount),
+              (try? audioFile.read(into: buffer)) != nil,
+              let channelData = buffer.floatChannelData?[0]
+        else {
+            return nil
+        }
+
+        let totalFrames = Int(buffer.frameLength)
+        let barCount = min(Int(bars), totalFrames)
+        var levels: [Float] = []
+        levels.reserveCapacity(barCount)
+        for bar in 0..<barCount {
+            let start = bar * totalFrames / barCount
+            let end = max(start + 1, (bar + 1) * totalFrames / barCount)
+            var rms: Float = 0
+            vDSP_rmsqv(channelData.advanced(by: start), 1, &rms, vDSP_Length(end - start))
+            // Same dB mapping as the recorder's live levels.
+            let db = 20 * log10f(max(rms, 1e-6))
+            levels.append(max(0, min(1, (db + 50) / 50)))
+        }
+
+        let durationMs = UInt64(max(0, Double(audioFile.length) / format.sampleRate * 1000))
+        return AudioWaveform(levels: levels, durationMs: durationMs)
+    }
+}
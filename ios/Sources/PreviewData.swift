@@ -237,7 +237,11 @@ enum PreviewAppState {
                 firstUnreadMessageId: nil,
                 canLoadOlder: false,
                 typingMembers: [],
-                myGroupProfile: nil
+                myGroupProfile: nil,
+                disappearingTimerSecs: nil,
+                notificationMode: .all,
+                inviteLinks: [],
+                joinRequests: []
             )
         )
     }
@@ -315,18 +319,26 @@ enum PreviewAppState {
             myProfile: myProfile,
             busy: busy,
             chatList: chatList,
+            invitations: [],
             currentChat: currentChat,
             followList: followList,
+            blockedUsers: [],
             peerProfile: nil,
             activeCall: activeCall,
             callTimeline: callTimeline,
+            callReport: nil,
             toast: toast,
             developerMode: false,
+            readReceiptsEnabled: false,
+            autoAcceptFollowedInvites: true,
+            blockListSyncEnabled: false,
             updateRequired: false,
             agentButton: nil,
             agentProvisioning: nil,
             voiceRecording: nil,
-            mediaGallery: nil
+            mediaGallery: nil,
+            messageSearch: nil,
+            accounts: []
         )
     }
 
@@ -341,7 +353,10 @@ enum PreviewAppState {
             displayName: name ?? samplePeerNpub,
             subtitle: name == nil ? nil : samplePeerNpub,
             lastMessagePreview: lastMessage,
-            unreadCount: unread
+            unreadCount: unread,
+            disappearingTimerSecs: nil,
+            notificationMode: .all,
+            isMuted: false
         )
     }
 
@@ -413,7 +428,11 @@ enum PreviewAppState {
             firstUnreadMessageId: nil,
             canLoadOlder: true,
             typingMembers: [],
-            myGroupProfile: nil
+            myGroupProfile: nil,
+            disappearingTimerSecs: nil,
+            notificationMode: .all,
+            inviteLinks: [],
+            joinRequests: []
         )
     }
 
@@ -452,7 +471,11 @@ enum PreviewAppState {
             firstUnreadMessageId: nil,
             canLoadOlder: true,
             typingMembers: [],
-            myGroupProfile: nil
+            myGroupProfile: nil,
+            disappearingTimerSecs: nil,
+            notificationMode: .all,
+            inviteLinks: [],
+            joinRequests: []
         )
     }
 
@@ -611,7 +634,11 @@ enum PreviewAppState {
             firstUnreadMessageId: nil,
             canLoadOlder: true,
             typingMembers: [],
-            myGroupProfile: nil
+            myGroupProfile: nil,
+            disappearingTimerSecs: nil,
+            notificationMode: .all,
+            inviteLinks: [],
+            joinRequests: []
         )
     }
 
@@ -644,7 +671,12 @@ enum PreviewAppState {
                         schemeVersion: "v1",
                         localPath: nil,
                         uploadProgress: nil,
-                        blurhash: nil
+                        transferredBytes: nil,
+                        totalBytes: nil,
+                        blurhash: nil,
+                        thumbnailPath: nil,
+                        durationMs: nil,
+                        waveform: nil
                     ),
                 ],
                 pollTally: [],
@@ -679,7 +711,12 @@ enum PreviewAppState {
                         schemeVersion: "v1",
                         localPath: nil,
                         uploadProgress: nil,
-                        blurhash: nil
+                        transferredBytes: nil,
+                        totalBytes: nil,
+                        blurhash: nil,
+                        thumbnailPath: nil,
+                        durationMs: nil,
+                        waveform: nil
                     ),
                 ],
                 pollTally: [],
@@ -714,7 +751,12 @@ enum PreviewAppState {
                         schemeVersion: "v1",
                         localPath: nil,
                         uploadProgress: nil,
-                        blurhash: nil
+                        transferredBytes: nil,
+                        totalBytes: nil,
+                        blurhash: nil,
+                        thumbnailPath: nil,
+                        durationMs: nil,
+                        waveform: nil
                     ),
                 ],
                 pollTally: [],
@@ -734,7 +776,11 @@ enum PreviewAppState {
             firstUnreadMessageId: nil,
             canLoadOlder: false,
             typingMembers: [],
-            myGroupProfile: nil
+            myGroupProfile: nil,
+            disappearingTimerSecs: nil,
+            notificationMode: .all,
+            inviteLinks: [],
+            joinRequests: []
         )
     }
 
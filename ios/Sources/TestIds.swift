@@ -50,9 +50,13 @@ enum TestIds {
     static let chatSend = "chat_send"
     static let chatMediaGrid = "chat_media_grid"
     static let chatGroupInfo = "chat_group_info"
+    static let chatDisappearingTimer = "chat_disappearing_timer"
+    static let chatNotificationMode = "chat_notification_mode"
     static let chatReactionBar = "chat_reaction_bar"
     static let chatActionCard = "chat_action_card"
     static let chatActionCopy = "chat_action_copy"
+    static let chatActionEdit = "chat_action_edit"
+    static let chatActionDelete = "chat_action_delete"
 
     // Group info
     static let groupInfoAddNpub = "groupinfo_add_npub"
@@ -65,6 +69,9 @@ enum TestIds {
     static let chatCallEnd = "chat_call_end"
     static let chatCallMute = "chat_call_mute"
     static let chatCallSpeaker = "chat_call_speaker"
+    static let chatCallAudioProcessing = "chat_call_audio_processing"
+    static let chatCallRecord = "chat_call_record"
+    static let chatCallSaveCaptions = "chat_call_save_captions"
     static let callScreenDismiss = "call_screen_dismiss"
     static let callReturnToCall = "call_return_to_call"
     static let callTimelineEvent = "call_timeline_event"
@@ -7,9 +7,11 @@ struct LoginViewState: Equatable {
 
 struct ChatListViewState: Equatable {
     let chats: [ChatSummary]
+    let invitations: [InvitationSummary]
     let myNpub: String?
     let myProfile: MyProfileState
     let agentButton: AgentButtonState?
+    let accounts: [AccountSummary]
 }
 
 typealias AgentButtonState = AgentMenuItemState
@@ -65,11 +65,18 @@ func callDurationText(startedAt: Int64?, nowSeconds: Int64 = Int64(Date().timeIn
 
 func formattedCallDebugStats(_ debug: CallDebugStats) -> String {
     var s = "tx \(debug.txFrames)  rx \(debug.rxFrames)  drop \(debug.rxDropped)"
+    s += "  jb \(debug.jitterBufferMs)/\(debug.jitterTargetMs)ms"
+    if debug.rxConcealed > 0 || debug.rxReordered > 0 || debug.rxLate > 0 {
+        s += "  plc \(debug.rxConcealed)  reord \(debug.rxReordered)  late \(debug.rxLate)"
+    }
     if debug.videoTx > 0 || debug.videoRx > 0 {
         s += "  vtx \(debug.videoTx)  vrx \(debug.videoRx)"
         if debug.videoRxDecryptFail > 0 {
             s += "  vfail \(debug.videoRxDecryptFail)"
         }
+        if let profile = debug.videoProfile {
+            s += "  \(profile.height)p\(profile.fps) \(profile.bitrateBps / 1000)k"
+        }
     }
     return s
 }
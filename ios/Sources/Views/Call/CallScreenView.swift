@@ -14,6 +14,9 @@ struct CallScreenView: View {
     let onEndCall: @MainActor () -> Void
     let onToggleMute: @MainActor () -> Void
     let onToggleCamera: @MainActor () -> Void
+    let onToggleAudioProcessing: @MainActor () -> Void
+    let onToggleRecording: @MainActor () -> Void
+    let onSaveCaptions: @MainActor () -> Void
     let onFlipCamera: @MainActor () -> Void
     let onStartAgain: @MainActor () -> Void
     let onDismiss: @MainActor () -> Void
@@ -22,6 +25,8 @@ struct CallScreenView: View {
     var remotePixelBuffer: CVPixelBuffer?
     /// Local camera preview session (zero-copy preview layer).
     var localCaptureSession: AVCaptureSession?
+    /// Display names for group call participants, keyed by npub.
+    var participantNames: [String: String] = [:]
 
     @State private var showMicDeniedAlert = false
     @State private var isSpeakerOn = false
@@ -66,6 +71,33 @@ struct CallScreenView: View {
         }
     }
 
+    // MARK: - Group Call Participants
+
+    private var participantStrip: some View {
+        ScrollView(.horizontal, showsIndicators: false) {
+            HStack(spacing: 8) {
+                ForEach(call.participants, id: \.npub) { participant in
+                    HStack(spacing: 4) {
+                        Text(participantNames[participant.npub] ?? String(participant.npub.prefix(12)))
+                            .lineLimit(1)
+                        if participant.isMuted {
+                            Image(systemName: "mic.slash.fill")
+                        }
+                    }
+                    .font(.footnote.weight(.medium))
+                    .foregroundStyle(.white)
+                    .padding(.horizontal, 10)
+                    .padding(.vertical, 5)
+                    .background(Color.white.opacity(0.12), in: Capsule())
+                    .overlay(
+                        Capsule().stroke(participant.isSpeaking ? Color.green : Color.clear, lineWidth: 2)
+                    )
+                }
+            }
+            .padding(.horizontal, 16)
+        }
+    }
+
     // MARK: - Audio Call Layout (existing)
 
     private var audioCallBody: some View {
@@ -100,12 +132,20 @@ struct CallScreenView: View {
                         .foregroundStyle(.white.opacity(0.86))
                 }
 
+                if call.isGroupCall && !call.participants.isEmpty {
+                    participantStrip
+                }
+
                 if let duration = call.durationDisplay, call.isLive {
                     Text(duration)
                         .font(.title3.monospacedDigit().weight(.medium))
                         .foregroundStyle(.white.opacity(0.9))
                 }
 
+                recordingBadge
+
+                captionsView
+
                 if let debug = call.debug {
                     Text(formattedCallDebugStats(debug))
                         .font(.caption.monospacedDigit())
@@ -194,6 +234,7 @@ struct CallScreenView: View {
                                 .padding(.vertical, 4)
                                 .background(Color.black.opacity(0.5), in: Capsule())
                         }
+                        recordingBadge
                         if let debug = call.debug {
                             Text(formattedCallDebugStats(debug))
                                 .font(.caption2.monospacedDigit())
@@ -209,6 +250,9 @@ struct CallScreenView: View {
 
                 Spacer()
 
+                captionsView
+                    .padding(.horizontal, 16)
+
                 // Bottom controls
                 videoControlRow
                     .padding(.horizontal, 16)
@@ -253,7 +297,7 @@ struct CallScreenView: View {
                 .accessibilityIdentifier(TestIds.chatCallAccept)
             }
         case .offering, .connecting, .active:
-            HStack(spacing: 28) {
+            HStack(spacing: 20) {
                 CallControlButton(
                     title: call.isMuted ? "Unmute" : "Mute",
                     systemImage: call.isMuted ? "mic.slash.fill" : "mic.fill",
@@ -263,6 +307,10 @@ struct CallScreenView: View {
                 }
                 .accessibilityIdentifier(TestIds.chatCallMute)
 
+                audioProcessingButton
+
+                recordingButton
+
                 CallControlButton(
                     title: call.isCameraEnabled ? "Cam Off" : "Cam On",
                     systemImage: call.isCameraEnabled ? "video.fill" : "video.slash.fill",
@@ -310,12 +358,75 @@ struct CallScreenView: View {
                     .tint(.green)
                     .accessibilityIdentifier(TestIds.chatCallStart)
                 }
+
+                if !call.captions.isEmpty {
+                    Button("Save Captions to Chat") {
+                        onSaveCaptions()
+                    }
+                    .buttonStyle(.bordered)
+                    .tint(.white)
+                    .accessibilityIdentifier(TestIds.chatCallSaveCaptions)
+                }
             }
         }
     }
 
     // MARK: - Shared Components
 
+    private var audioProcessingButton: some View {
+        CallControlButton(
+            title: call.isAudioProcessingEnabled ? "Clean" : "Raw",
+            systemImage: call.isAudioProcessingEnabled ? "waveform" : "waveform.slash",
+            tint: call.isAudioProcessingEnabled ? .white.opacity(0.25) : .orange
+        ) {
+            onToggleAudioProcessing()
+        }
+        .accessibilityIdentifier(TestIds.chatCallAudioProcessing)
+    }
+
+    private var recordingButton: some View {
+        CallControlButton(
+            title: call.isRecording ? "Stop Rec" : "Record",
+            systemImage: call.isRecording ? "stop.circle.fill" : "record.circle",
+            tint: call.isRecording ? .red : .white.opacity(0.25)
+        ) {
+            onToggleRecording()
+        }
+        .accessibilityIdentifier(TestIds.chatCallRecord)
+    }
+
+    /// Shown to everyone on the call while anyone, including us, is recording it.
+    @ViewBuilder
+    private var recordingBadge: some View {
+        if call.isLive && (call.isRecording || call.participants.contains(where: { $0.isRecording })) {
+            Label("Recording", systemImage: "record.circle.fill")
+                .font(.caption.weight(.semibold))
+                .foregroundStyle(.white)
+                .padding(.horizontal, 10)
+                .padding(.vertical, 4)
+                .background(Color.red.opacity(0.8), in: Capsule())
+        }
+    }
+
+    /// Latest live captions; our own lines are labelled "You".
+    @ViewBuilder
+    private var captionsView: some View {
+        if !call.captions.isEmpty {
+            VStack(alignment: .leading, spacing: 4) {
+                ForEach(Array(call.captions.enumerated()), id: \.offset) { _, caption in
+                    (Text(caption.isLocal ? "You" : caption.speakerName).bold()
+                        + Text(": \(caption.text)"))
+                        .font(.callout)
+                        .foregroundStyle(.white)
+                        .frame(maxWidth: .infinity, alignment: .leading)
+                }
+            }
+            .padding(.horizontal, 12)
+            .padding(.vertical, 8)
+            .background(Color.black.opacity(0.5), in: RoundedRectangle(cornerRadius: 12))
+        }
+    }
+
     private var header: some View {
         HStack {
             Button {
@@ -360,7 +471,7 @@ struct CallScreenView: View {
                 .accessibilityIdentifier(TestIds.chatCallAccept)
             }
         case .offering, .connecting, .active:
-            HStack(spacing: 36) {
+            HStack(spacing: 28) {
                 CallControlButton(
                     title: call.isMuted ? "Unmute" : "Mute",
                     systemImage: call.isMuted ? "mic.slash.fill" : "mic.fill",
@@ -370,6 +481,10 @@ struct CallScreenView: View {
                 }
                 .accessibilityIdentifier(TestIds.chatCallMute)
 
+                audioProcessingButton
+
+                recordingButton
+
                 CallControlButton(
                     title: isSpeakerOn ? "Speaker" : "Speaker",
                     systemImage: isSpeakerOn ? "speaker.wave.2.fill" : "speaker.fill",
@@ -410,6 +525,15 @@ struct CallScreenView: View {
                     .tint(.green)
                     .accessibilityIdentifier(TestIds.chatCallStart)
                 }
+
+                if !call.captions.isEmpty {
+                    Button("Save Captions to Chat") {
+                        onSaveCaptions()
+                    }
+                    .buttonStyle(.bordered)
+                    .tint(.white)
+                    .accessibilityIdentifier(TestIds.chatCallSaveCaptions)
+                }
             }
         }
     }
@@ -504,16 +628,28 @@ private struct CallControlButton: View {
             isMuted: false,
             isVideoCall: false,
             isCameraEnabled: false,
+            isAudioProcessingEnabled: true,
+            isScreenSharing: false,
+            isRecording: false,
             debug: CallDebugStats(
                 txFrames: 1023,
                 rxFrames: 1001,
                 rxDropped: 4,
-                jitterBufferMs: 25,
+                jitterBufferMs: 40,
+                jitterTargetMs: 60,
+                rxJitterMs: 12,
+                rxReordered: 3,
+                rxLate: 1,
+                rxConcealed: 7,
                 lastRttMs: 32,
                 videoTx: 0,
                 videoRx: 0,
-                videoRxDecryptFail: 0
-            )
+                videoRxDecryptFail: 0,
+                videoProfile: nil
+            ),
+            isGroupCall: false,
+            participants: [],
+            captions: []
         ),
         peerName: "Waffle",
         peerPictureUrl: nil,
@@ -522,6 +658,9 @@ private struct CallControlButton: View {
         onEndCall: {},
         onToggleMute: {},
         onToggleCamera: {},
+        onToggleAudioProcessing: {},
+        onToggleRecording: {},
+        onSaveCaptions: {},
         onFlipCamera: {},
         onStartAgain: {},
         onDismiss: {}
@@ -543,7 +682,13 @@ private struct CallControlButton: View {
             isMuted: false,
             isVideoCall: true,
             isCameraEnabled: true,
-            debug: nil
+            isAudioProcessingEnabled: true,
+            isScreenSharing: false,
+            isRecording: false,
+            debug: nil,
+            isGroupCall: false,
+            participants: [],
+            captions: []
         ),
         peerName: "Waffle",
         peerPictureUrl: nil,
@@ -552,6 +697,9 @@ private struct CallControlButton: View {
         onEndCall: {},
         onToggleMute: {},
         onToggleCamera: {},
+        onToggleAudioProcessing: {},
+        onToggleRecording: {},
+        onSaveCaptions: {},
         onFlipCamera: {},
         onStartAgain: {},
         onDismiss: {}
@@ -7,6 +7,11 @@ extension CallTimelineEvent {
     var date: Date {
         Date(timeIntervalSince1970: TimeInterval(timestamp))
     }
+
+    /// Disappearing-timer changes share the call timeline; keys start with `disappearing:`.
+    var systemImage: String {
+        id.hasPrefix("disappearing:") ? "timer" : "phone.badge.clock"
+    }
 }
 
 struct CallTimelineEventRow: View {
@@ -15,7 +20,7 @@ struct CallTimelineEventRow: View {
     var body: some View {
         HStack {
             Spacer()
-            Label(event.text, systemImage: "phone.badge.clock")
+            Label(event.text, systemImage: event.systemImage)
                 .font(.caption.weight(.semibold))
                 .foregroundStyle(.secondary)
                 .padding(.horizontal, 10)
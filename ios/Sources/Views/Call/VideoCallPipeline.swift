@@ -15,6 +15,8 @@ final class VideoCallPipeline {
     private var isActive = false
     private var lastRemoteFrameTime: CFAbsoluteTime = 0
     private var stalenessTimer: Timer?
+    /// Latest encoder profile from Rust's rate control; nil means the top profile.
+    private var videoProfile: CallVideoProfile?
 
     var localCaptureSession: AVCaptureSession? {
         captureManager?.captureSession
@@ -41,6 +43,14 @@ final class VideoCallPipeline {
             self.lastRemoteFrameTime = CFAbsoluteTimeGetCurrent()
             self.remotePixelBuffer = pixelBuffer
         }
+        dec.onVideoProfile = { [weak self] profile in
+            guard let self else { return }
+            self.videoProfile = profile
+            self.captureManager?.applyProfile(profile)
+        }
+        dec.onKeyframeRequest = { [weak self] in
+            self?.captureManager?.requestKeyframe()
+        }
         decoder = dec
 
         // Register decoder as the video frame receiver with Rust core
@@ -65,6 +75,7 @@ final class VideoCallPipeline {
         captureManager = nil
         decoder = nil
         remotePixelBuffer = nil
+        videoProfile = nil
     }
 
     func switchCamera() {
@@ -88,7 +99,7 @@ final class VideoCallPipeline {
     private func syncCapture(enabled: Bool) {
         if enabled {
             if captureManager == nil, let core {
-                let cap = VideoCaptureManager(core: core)
+                let cap = VideoCaptureManager(core: core, profile: videoProfile)
                 cap.startCapture()
                 captureManager = cap
             }
@@ -6,6 +6,7 @@ import VideoToolbox
 private final class SharedCaptureState: @unchecked Sendable {
     private let lock = NSLock()
     private var _compressionSession: VTCompressionSession?
+    private var _forceKeyframe = false
     private let _core: (any AppCore)?
 
     init(core: (any AppCore)?) {
@@ -19,6 +20,19 @@ private final class SharedCaptureState: @unchecked Sendable {
 
     var core: (any AppCore)? { _core }
 
+    func requestKeyframe() {
+        lock.withLock { _forceKeyframe = true }
+    }
+
+    /// Atomically read and clear a pending keyframe request.
+    func takeKeyframeRequest() -> Bool {
+        lock.withLock {
+            let requested = _forceKeyframe
+            _forceKeyframe = false
+            return requested
+        }
+    }
+
     /// Atomically read and clear the compression session.
     func takeCompressionSession() -> VTCompressionSession? {
         lock.withLock {
@@ -31,7 +45,8 @@ private final class SharedCaptureState: @unchecked Sendable {
 
 /// Manages camera capture and H.264 encoding for video calls.
 /// Captures from the front camera at 720p 30fps, encodes to H.264 Annex B NALUs,
-/// and pushes them to Rust core via `ffiApp.sendVideoFrame()`.
+/// and pushes them to Rust core via `ffiApp.sendVideoFrame()`. Rust's rate control
+/// can step the encoder down to a smaller profile via `applyProfile(_:)`.
 @MainActor
 final class VideoCaptureManager: NSObject {
     let captureSession = AVCaptureSession()
@@ -39,13 +54,18 @@ final class VideoCaptureManager: NSObject {
     private let processingQueue = DispatchQueue(label: "pika.video.capture", qos: .userInteractive)
     private var currentCameraPosition: AVCaptureDevice.Position = .front
     private var isRunning = false
+    private var profile: CallVideoProfile
 
     private let shared: SharedCaptureState
 
     private nonisolated(unsafe) static let log = Logger(subsystem: "chat.pika", category: "VideoCaptureManager")
 
-    init(core: (any AppCore)?) {
+    /// Top of the rate-control ladder; matches `pika_media::tracks::video_params`.
+    static let defaultProfile = CallVideoProfile(width: 1280, height: 720, fps: 30, bitrateBps: 1_500_000)
+
+    init(core: (any AppCore)?, profile: CallVideoProfile? = nil) {
         self.shared = SharedCaptureState(core: core)
+        self.profile = profile ?? Self.defaultProfile
         super.init()
     }
 
@@ -74,6 +94,23 @@ final class VideoCaptureManager: NSObject {
         }
     }
 
+    /// Switch to a new encoder profile. The new session starts with a keyframe.
+    func applyProfile(_ profile: CallVideoProfile) {
+        guard profile != self.profile else { return }
+        self.profile = profile
+        guard isRunning else { return }
+        setupEncoder()
+        let fps = Int32(profile.fps)
+        processingQueue.async { [weak self] in
+            guard let input = self?.captureSession.inputs.first as? AVCaptureDeviceInput else { return }
+            Self.setFrameRate(fps, on: input.device)
+        }
+    }
+
+    func requestKeyframe() {
+        shared.requestKeyframe()
+    }
+
     func switchCamera() {
         let newPosition: AVCaptureDevice.Position = currentCameraPosition == .front ? .back : .front
         currentCameraPosition = newPosition
@@ -129,15 +166,7 @@ final class VideoCaptureManager: NSObject {
             captureSession.addInput(input)
         }
 
-        // Configure frame rate
-        do {
-            try device.lockForConfiguration()
-            device.activeVideoMinFrameDuration = CMTime(value: 1, timescale: 30)
-            device.activeVideoMaxFrameDuration = CMTime(value: 1, timescale: 30)
-            device.unlockForConfiguration()
-        } catch {
-            Self.log.error("failed to set frame rate: \(error.localizedDescription)")
-        }
+        Self.setFrameRate(Int32(profile.fps), on: device)
 
         // Video output
         videoOutput.videoSettings = [
@@ -169,9 +198,10 @@ final class VideoCaptureManager: NSObject {
         }
 
         // Capture connection rotates pixels to portrait (videoOrientation = .portrait),
-        // so the pixel buffers are 720x1280 — encoder dimensions must match.
-        let width: Int32 = 720
-        let height: Int32 = 1280
+        // so the encoder is portrait too; VideoToolbox scales the 720x1280 buffers
+        // down for smaller profiles.
+        let width = Int32(profile.height)
+        let height = Int32(profile.width)
 
         var session: VTCompressionSession?
         let status = VTCompressionSessionCreate(
@@ -194,9 +224,11 @@ final class VideoCaptureManager: NSObject {
         VTSessionSetProperty(session, key: kVTCompressionPropertyKey_RealTime, value: kCFBooleanTrue)
         VTSessionSetProperty(session, key: kVTCompressionPropertyKey_ProfileLevel,
                              value: kVTProfileLevel_H264_Main_AutoLevel)
-        let bitrate = 1_500_000 as CFNumber // 1.5 Mbps
+        let bitrate = NSNumber(value: profile.bitrateBps)
         VTSessionSetProperty(session, key: kVTCompressionPropertyKey_AverageBitRate, value: bitrate)
-        let keyframeInterval = 60 as CFNumber // every 2s at 30fps
+        let frameRate = NSNumber(value: profile.fps)
+        VTSessionSetProperty(session, key: kVTCompressionPropertyKey_ExpectedFrameRate, value: frameRate)
+        let keyframeInterval = NSNumber(value: profile.fps * 2) // every 2s
         VTSessionSetProperty(session, key: kVTCompressionPropertyKey_MaxKeyFrameInterval, value: keyframeInterval)
         VTSessionSetProperty(session, key: kVTCompressionPropertyKey_AllowFrameReordering, value: kCFBooleanFalse)
 
@@ -205,6 +237,17 @@ final class VideoCaptureManager: NSObject {
         shared.compressionSession = session
     }
 
+    private nonisolated static func setFrameRate(_ fps: Int32, on device: AVCaptureDevice) {
+        do {
+            try device.lockForConfiguration()
+            device.activeVideoMinFrameDuration = CMTime(value: 1, timescale: fps)
+            device.activeVideoMaxFrameDuration = CMTime(value: 1, timescale: fps)
+            device.unlockForConfiguration()
+        } catch {
+            log.error("failed to set frame rate: \(error.localizedDescription)")
+        }
+    }
+
     private func camera(for position: AVCaptureDevice.Position) -> AVCaptureDevice? {
         AVCaptureDevice.default(.builtInWideAngleCamera, for: .video, position: position)
     }
@@ -213,12 +256,15 @@ final class VideoCaptureManager: NSObject {
         guard let session = shared.compressionSession else { return }
 
         var flags: VTEncodeInfoFlags = []
+        let frameProperties: CFDictionary? = shared.takeKeyframeRequest()
+            ? [kVTEncodeFrameOptionKey_ForceKeyFrame: kCFBooleanTrue] as CFDictionary
+            : nil
         let status = VTCompressionSessionEncodeFrame(
             session,
             imageBuffer: pixelBuffer,
             presentationTimeStamp: presentationTime,
             duration: CMTime(value: 1, timescale: 30),
-            frameProperties: nil,
+            frameProperties: frameProperties,
             infoFlagsOut: &flags
         ) { [weak self] status, _, sampleBuffer in
             guard status == noErr, let sampleBuffer else { return }
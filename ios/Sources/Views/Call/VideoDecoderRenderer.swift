@@ -15,11 +15,32 @@ final class VideoDecoderRenderer: VideoFrameReceiver {
 
     /// Called on the main thread when a new decoded frame is available.
     var onDecodedFrame: ((CVPixelBuffer) -> Void)?
+    /// Called on the main thread when Rust's rate control picks a new encoder profile.
+    var onVideoProfile: ((CallVideoProfile) -> Void)?
+    /// Called on the main thread when a remote decoder needs a keyframe from us.
+    var onKeyframeRequest: (() -> Void)?
 
     func onVideoFrame(callId: String, payload: Data) {
         processAnnexBPayload(payload)
     }
 
+    func onVideoProfile(callId: String, profile: CallVideoProfile) {
+        DispatchQueue.main.async { [weak self] in
+            self?.onVideoProfile?(profile)
+        }
+    }
+
+    func onKeyframeRequest(callId: String) {
+        DispatchQueue.main.async { [weak self] in
+            self?.onKeyframeRequest?()
+        }
+    }
+
+    func onScreenFrame(callId: String, payload: Data) {
+        // Remote screen shares aren't shown on iOS yet. They come from a
+        // separate encoder, so they must not reach this decoder session.
+    }
+
     // MARK: - Annex B Parsing
 
     private func processAnnexBPayload(_ data: Data) {
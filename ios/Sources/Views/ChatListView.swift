@@ -6,6 +6,8 @@ struct ChatListView: View {
     let onLogout: @MainActor () -> Void
     let onOpenChat: @MainActor (String) -> Void
     let onArchiveChat: @MainActor (String) -> Void
+    let onAcceptInvite: @MainActor (String) -> Void
+    let onDeclineInvite: @MainActor (String) -> Void
     let onNewChat: @MainActor () -> Void
     let onNewGroupChat: @MainActor () -> Void
     let onEnsureAgent: @MainActor () -> Void
@@ -14,62 +16,93 @@ struct ChatListView: View {
     let onUploadProfilePhoto: @MainActor (_ data: Data, _ mimeType: String) -> Void
     let isDeveloperModeEnabledProvider: @MainActor () -> Bool
     let onEnableDeveloperMode: @MainActor () -> Void
+    let isReadReceiptsEnabledProvider: @MainActor () -> Bool
+    let onSetReadReceiptsEnabled: @MainActor (Bool) -> Void
+    let onExportBackup: @MainActor (_ passphrase: String) -> URL
+    let onImportBackup: @MainActor (_ fileURL: URL, _ passphrase: String) -> Void
     let onWipeProfileCache: @MainActor () -> Void
     let onWipeMediaCache: @MainActor () -> Void
     let onWipeLocalData: @MainActor () -> Void
+    let onAddAccount: @MainActor (_ nsec: String) -> Void
+    let onSwitchAccount: @MainActor (_ pubkey: String) -> Void
+    let onRemoveAccount: @MainActor (_ pubkey: String) -> Void
     let nsecProvider: @MainActor () -> String?
     @State private var showMyNpub = false
 
     var body: some View {
-        List(state.chats, id: \.chatId) { chat in
-            let row = HStack(spacing: 12) {
-                if chat.isGroup {
-                    groupAvatar(chat)
-                } else {
-                    AvatarView(
-                        name: chat.members.first?.name,
-                        npub: chat.members.first?.npub ?? "",
-                        pictureUrl: chat.members.first?.pictureUrl
-                    )
+        List {
+            if !state.invitations.isEmpty {
+                Section("Invitations") {
+                    ForEach(state.invitations, id: \.inviteId) { invite in
+                        inviteRow(invite)
+                    }
                 }
+            }
 
-                VStack(alignment: .leading, spacing: 2) {
-                    Text(chat.displayName)
-                        .font(.headline)
-                        .lineLimit(1)
-                    if let subtitle = chat.subtitle {
-                        Text(subtitle)
-                            .font(.caption)
-                            .foregroundStyle(.tertiary)
-                            .lineLimit(1)
+            ForEach(state.chats, id: \.chatId) { chat in
+                let row = HStack(spacing: 12) {
+                    if chat.isGroup {
+                        groupAvatar(chat)
+                    } else {
+                        AvatarView(
+                            name: chat.members.first?.name,
+                            npub: chat.members.first?.npub ?? "",
+                            pictureUrl: chat.members.first?.pictureUrl
+                        )
                     }
-                    Text(chat.lastMessagePreview)
-                        .font(.subheadline)
-                        .foregroundStyle(.secondary)
-                        .lineLimit(1)
-                }
 
-                Spacer(minLength: 0)
-            }
-            .contentShape(Rectangle())
+                    VStack(alignment: .leading, spacing: 2) {
+                        HStack(spacing: 4) {
+                            Text(chat.displayName)
+                                .font(.headline)
+                                .lineLimit(1)
+                            if chat.disappearingTimerSecs != nil {
+                                Image(systemName: "timer")
+                                    .font(.caption)
+                                    .foregroundStyle(.secondary)
+                                    .accessibilityLabel("Disappearing messages on")
+                            }
+                            if chat.isMuted {
+                                Image(systemName: "bell.slash")
+                                    .font(.caption)
+                                    .foregroundStyle(.secondary)
+                                    .accessibilityLabel("Muted")
+                            }
+                        }
+                        if let subtitle = chat.subtitle {
+                            Text(subtitle)
+                                .font(.caption)
+                                .foregroundStyle(.tertiary)
+                                .lineLimit(1)
+                        }
+                        Text(chat.lastMessagePreview)
+                            .font(.subheadline)
+                            .foregroundStyle(.secondary)
+                            .lineLimit(1)
+                    }
 
-            Button {
-                onOpenChat(chat.chatId)
-            } label: {
-                if chat.unreadCount > 0 {
-                    row.badge(Int(chat.unreadCount))
-                } else {
-                    row
+                    Spacer(minLength: 0)
                 }
-            }
-            .tint(.primary)
-            .swipeActions(edge: .trailing, allowsFullSwipe: true) {
-                Button(role: .destructive) {
-                    onArchiveChat(chat.chatId)
+                .contentShape(Rectangle())
+
+                Button {
+                    onOpenChat(chat.chatId)
                 } label: {
-                    Label("Archive", systemImage: "archivebox")
+                    if chat.unreadCount > 0 {
+                        row.badge(Int(chat.unreadCount))
+                    } else {
+                        row
+                    }
+                }
+                .tint(.primary)
+                .swipeActions(edge: .trailing, allowsFullSwipe: true) {
+                    Button(role: .destructive) {
+                        onArchiveChat(chat.chatId)
+                    } label: {
+                        Label("Archive", systemImage: "archivebox")
+                    }
+                    .tint(.orange)
                 }
-                .tint(.orange)
             }
         }
         .navigationTitle("Chats")
@@ -99,9 +132,17 @@ struct ChatListView: View {
                             onLogout: onLogout,
                             isDeveloperModeEnabledProvider: isDeveloperModeEnabledProvider,
                             onEnableDeveloperMode: onEnableDeveloperMode,
+                            isReadReceiptsEnabledProvider: isReadReceiptsEnabledProvider,
+                            onSetReadReceiptsEnabled: onSetReadReceiptsEnabled,
+                            onExportBackup: onExportBackup,
+                            onImportBackup: onImportBackup,
                             onWipeProfileCache: onWipeProfileCache,
                             onWipeMediaCache: onWipeMediaCache,
-                            onWipeLocalData: onWipeLocalData
+                            onWipeLocalData: onWipeLocalData,
+                            accounts: state.accounts,
+                            onAddAccount: onAddAccount,
+                            onSwitchAccount: onSwitchAccount,
+                            onRemoveAccount: onRemoveAccount
                         )
                     }
                 }
@@ -136,6 +177,34 @@ struct ChatListView: View {
         }
     }
 
+    @ViewBuilder
+    private func inviteRow(_ invite: InvitationSummary) -> some View {
+        HStack(spacing: 12) {
+            AvatarView(
+                name: invite.inviterName,
+                npub: invite.inviterNpub,
+                pictureUrl: invite.inviterPictureUrl
+            )
+
+            VStack(alignment: .leading, spacing: 2) {
+                Text(invite.groupName ?? "Group invite")
+                    .font(.headline)
+                    .lineLimit(1)
+                Text("From \(invite.inviterName ?? String(invite.inviterNpub.prefix(12))) · \(invite.memberCount) members")
+                    .font(.caption)
+                    .foregroundStyle(.secondary)
+                    .lineLimit(1)
+            }
+
+            Spacer(minLength: 0)
+
+            Button("Decline") { onDeclineInvite(invite.inviteId) }
+                .buttonStyle(.bordered)
+            Button("Accept") { onAcceptInvite(invite.inviteId) }
+                .buttonStyle(.borderedProminent)
+        }
+    }
+
     @ViewBuilder
     private func groupAvatar(_ chat: ChatSummary) -> some View {
         ZStack {
@@ -155,13 +224,17 @@ struct ChatListView: View {
         ChatListView(
             state: ChatListViewState(
                 chats: PreviewAppState.chatListEmpty.chatList,
+                invitations: [],
                 myNpub: PreviewAppState.sampleNpub,
                 myProfile: PreviewAppState.chatListEmpty.myProfile,
-                agentButton: nil
+                agentButton: nil,
+                accounts: []
             ),
             onLogout: {},
             onOpenChat: { _ in },
             onArchiveChat: { _ in },
+            onAcceptInvite: { _ in },
+            onDeclineInvite: { _ in },
             onNewChat: {},
             onNewGroupChat: {},
             onEnsureAgent: {},
@@ -170,9 +243,16 @@ struct ChatListView: View {
             onUploadProfilePhoto: { _, _ in },
             isDeveloperModeEnabledProvider: { false },
             onEnableDeveloperMode: {},
+            isReadReceiptsEnabledProvider: { false },
+            onSetReadReceiptsEnabled: { _ in },
+            onExportBackup: { _ in FileManager.default.temporaryDirectory },
+            onImportBackup: { _, _ in },
             onWipeProfileCache: {},
             onWipeMediaCache: {},
             onWipeLocalData: {},
+            onAddAccount: { _ in },
+            onSwitchAccount: { _ in },
+            onRemoveAccount: { _ in },
             nsecProvider: { nil }
         )
     }
@@ -183,13 +263,17 @@ struct ChatListView: View {
         ChatListView(
             state: ChatListViewState(
                 chats: PreviewAppState.chatListPopulated.chatList,
+                invitations: [],
                 myNpub: PreviewAppState.sampleNpub,
                 myProfile: PreviewAppState.chatListPopulated.myProfile,
-                agentButton: nil
+                agentButton: nil,
+                accounts: []
             ),
             onLogout: {},
             onOpenChat: { _ in },
             onArchiveChat: { _ in },
+            onAcceptInvite: { _ in },
+            onDeclineInvite: { _ in },
             onNewChat: {},
             onNewGroupChat: {},
             onEnsureAgent: {},
@@ -198,9 +282,16 @@ struct ChatListView: View {
             onUploadProfilePhoto: { _, _ in },
             isDeveloperModeEnabledProvider: { false },
             onEnableDeveloperMode: {},
+            isReadReceiptsEnabledProvider: { false },
+            onSetReadReceiptsEnabled: { _ in },
+            onExportBackup: { _ in FileManager.default.temporaryDirectory },
+            onImportBackup: { _, _ in },
             onWipeProfileCache: {},
             onWipeMediaCache: {},
             onWipeLocalData: {},
+            onAddAccount: { _ in },
+            onSwitchAccount: { _ in },
+            onRemoveAccount: { _ in },
             nsecProvider: { nil }
         )
     }
@@ -211,13 +302,17 @@ struct ChatListView: View {
         ChatListView(
             state: ChatListViewState(
                 chats: PreviewAppState.chatListLongNames.chatList,
+                invitations: [],
                 myNpub: PreviewAppState.sampleNpub,
                 myProfile: PreviewAppState.chatListLongNames.myProfile,
-                agentButton: nil
+                agentButton: nil,
+                accounts: []
             ),
             onLogout: {},
             onOpenChat: { _ in },
             onArchiveChat: { _ in },
+            onAcceptInvite: { _ in },
+            onDeclineInvite: { _ in },
             onNewChat: {},
             onNewGroupChat: {},
             onEnsureAgent: {},
@@ -226,9 +321,16 @@ struct ChatListView: View {
             onUploadProfilePhoto: { _, _ in },
             isDeveloperModeEnabledProvider: { false },
             onEnableDeveloperMode: {},
+            isReadReceiptsEnabledProvider: { false },
+            onSetReadReceiptsEnabled: { _ in },
+            onExportBackup: { _ in FileManager.default.temporaryDirectory },
+            onImportBackup: { _, _ in },
             onWipeProfileCache: {},
             onWipeMediaCache: {},
             onWipeLocalData: {},
+            onAddAccount: { _ in },
+            onSwitchAccount: { _ in },
+            onRemoveAccount: { _ in },
             nsecProvider: { nil }
         )
     }
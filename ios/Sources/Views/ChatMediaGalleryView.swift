@@ -3,11 +3,15 @@ import SwiftUI
 struct ChatMediaGalleryView: View {
     let chatId: String
     let items: [MediaGalleryItem]
+    /// Call recordings are stored encrypted; this asks Rust to decrypt one.
+    var onOpenRecording: ((String) -> Void)? = nil
 
     @State private var fullscreenAttachment: ChatMediaAttachment?
 
     private var viewableAttachments: [ChatMediaAttachment] {
-        items.compactMap { $0.attachment.localPath != nil ? $0.attachment : nil }
+        items.compactMap {
+            $0.attachment.localPath != nil && $0.attachment.kind != .voiceNote ? $0.attachment : nil
+        }
     }
 
     private let columns = [
@@ -58,13 +62,27 @@ struct ChatMediaGalleryView: View {
     @ViewBuilder
     private func mediaThumbnail(_ item: MediaGalleryItem) -> some View {
         let attachment = item.attachment
-        if let localPath = attachment.localPath {
-            ThumbnailImage(url: URL(fileURLWithPath: localPath))
+        let isVideo = attachment.kind == .video
+        // Only call recordings are audio here; videos show their poster frame
+        // since the file itself can't be drawn as an image.
+        if attachment.kind == .voiceNote {
+            recordingTile(attachment)
+        } else if let previewPath = isVideo ? attachment.thumbnailPath : attachment.localPath {
+            ThumbnailImage(url: URL(fileURLWithPath: previewPath))
+                .overlay {
+                    if isVideo {
+                        Image(systemName: "play.fill")
+                            .font(.title3)
+                            .foregroundStyle(.white)
+                            .shadow(radius: 2)
+                    }
+                }
                 .overlay(
                     GeometryReader { geo in
                         Color.clear
                             .contentShape(Rectangle())
                             .onTapGesture {
+                                guard attachment.localPath != nil else { return }
                                 ImageViewerTransition.sourceFrame = geo.frame(in: .global)
                                 fullscreenAttachment = attachment
                             }
@@ -80,6 +98,27 @@ struct ChatMediaGalleryView: View {
                 }
         }
     }
+
+    @ViewBuilder
+    private func recordingTile(_ attachment: ChatMediaAttachment) -> some View {
+        let tile = Rectangle()
+            .fill(Color(.systemGray5))
+            .aspectRatio(1, contentMode: .fit)
+            .overlay {
+                VStack(spacing: 6) {
+                    Image(systemName: attachment.localPath == nil ? "lock.fill" : "waveform")
+                        .font(.title2)
+                    Text("Call Recording")
+                        .font(.caption2)
+                }
+                .foregroundStyle(.secondary)
+            }
+        if let localPath = attachment.localPath {
+            ShareLink(item: URL(fileURLWithPath: localPath)) { tile }
+        } else {
+            tile.onTapGesture { onOpenRecording?(attachment.originalHashHex) }
+        }
+    }
 }
 
 /// Loads a local image thumbnail asynchronously to avoid blocking the main thread.
@@ -29,6 +29,10 @@ struct ChatView: View {
     let onSendPoll: (@MainActor (String, String, [String]) -> Void)?
     let onLoadOlderMessages: (@MainActor (String, String, UInt32) -> Void)?
     let onRetryMessage: (@MainActor (String, String) -> Void)?
+    let onEditMessage: (@MainActor (String, String) -> Void)?
+    let onDeleteMessage: (@MainActor (String) -> Void)?
+    let onSetDisappearingTimer: (@MainActor (UInt64?) -> Void)?
+    let onSetNotificationMode: (@MainActor (ChatNotificationMode) -> Void)?
     @State private var selectedPhotoItems: [PhotosPickerItem] = []
     @State private var stagedMedia: [StagedMediaItem] = []
     @State private var showFileImporter = false
@@ -43,6 +47,7 @@ struct ChatView: View {
     @State private var mentionQuery = ""
     @State private var insertedMentions: [(display: String, npub: String)] = []
     @State private var replyDraftMessage: ChatMessage?
+    @State private var editDraftMessage: ChatMessage?
     @State private var fullscreenImageAttachment: ChatMediaAttachment?
     @State private var fullscreenImageAttachments: [ChatMediaAttachment] = []
     @State private var showPollComposer = false
@@ -71,7 +76,11 @@ struct ChatView: View {
         onHypernoteAction: (@MainActor (String, String, String, [String: String]) -> Void)? = nil,
         onSendPoll: (@MainActor (String, String, [String]) -> Void)? = nil,
         onLoadOlderMessages: (@MainActor (String, String, UInt32) -> Void)? = nil,
-        onRetryMessage: (@MainActor (String, String) -> Void)? = nil
+        onRetryMessage: (@MainActor (String, String) -> Void)? = nil,
+        onEditMessage: (@MainActor (String, String) -> Void)? = nil,
+        onDeleteMessage: (@MainActor (String) -> Void)? = nil,
+        onSetDisappearingTimer: (@MainActor (UInt64?) -> Void)? = nil,
+        onSetNotificationMode: (@MainActor (ChatNotificationMode) -> Void)? = nil
     ) {
         self.chatId = chatId
         self.state = state
@@ -94,6 +103,10 @@ struct ChatView: View {
         self.onSendPoll = onSendPoll
         self.onLoadOlderMessages = onLoadOlderMessages
         self.onRetryMessage = onRetryMessage
+        self.onEditMessage = onEditMessage
+        self.onDeleteMessage = onDeleteMessage
+        self.onSetDisappearingTimer = onSetDisappearingTimer
+        self.onSetNotificationMode = onSetNotificationMode
         _voiceRecorder = State(initialValue: VoiceRecorder(dispatchAction: onVoiceRecordingAction))
     }
 
@@ -141,6 +154,37 @@ struct ChatView: View {
         .toolbarBackground(.hidden, for: .navigationBar)
         .toolbar {
             if chat.isGroup {
+                ToolbarItem(placement: .topBarTrailing) {
+                    ChatCallToolbarButton(
+                        callForChat: callFor(chat),
+                        hasLiveCallElsewhere: hasLiveCallElsewhere(chat: chat),
+                        onStartCall: {
+                            onStartCall()
+                        },
+                        onStartVideoCall: {
+                            onStartVideoCall()
+                        },
+                        onOpenCallScreen: {
+                            onOpenCallScreen()
+                        }
+                    )
+                }
+                if let onSetNotificationMode {
+                    ToolbarItem(placement: .topBarTrailing) {
+                        NotificationModeMenu(
+                            mode: chat.notificationMode,
+                            onSelect: onSetNotificationMode
+                        )
+                    }
+                }
+                if let onSetDisappearingTimer {
+                    ToolbarItem(placement: .topBarTrailing) {
+                        DisappearingTimerMenu(
+                            currentSecs: chat.disappearingTimerSecs,
+                            onSelect: onSetDisappearingTimer
+                        )
+                    }
+                }
                 ToolbarItem(placement: .topBarTrailing) {
                     Button {
                         onGroupInfo?()
@@ -161,14 +205,43 @@ struct ChatView: View {
                                 pictureUrl: peer.pictureUrl,
                                 size: 24
                             )
-                            Text(chatTitle(chat))
-                                .font(.headline)
-                                .foregroundStyle(.primary)
+                            VStack(alignment: .leading, spacing: 0) {
+                                Text(chatTitle(chat))
+                                    .font(.headline)
+                                    .foregroundStyle(.primary)
+                                if let secs = chat.disappearingTimerSecs {
+                                    Text("Disappearing · \(DisappearingTimerOption.label(for: secs))")
+                                        .font(.caption2)
+                                        .foregroundStyle(.secondary)
+                                }
+                                if let label = NotificationModeOption.label(for: chat.notificationMode) {
+                                    Text(label)
+                                        .font(.caption2)
+                                        .foregroundStyle(.secondary)
+                                }
+                            }
                         }
                     }
                     .buttonStyle(.plain)
                 }
 
+                if let onSetNotificationMode {
+                    ToolbarItem(placement: .topBarTrailing) {
+                        NotificationModeMenu(
+                            mode: chat.notificationMode,
+                            onSelect: onSetNotificationMode
+                        )
+                    }
+                }
+                if let onSetDisappearingTimer {
+                    ToolbarItem(placement: .topBarTrailing) {
+                        DisappearingTimerMenu(
+                            currentSecs: chat.disappearingTimerSecs,
+                            onSelect: onSetDisappearingTimer
+                        )
+                    }
+                }
+
                 ToolbarItem(placement: .topBarTrailing) {
                     ChatCallToolbarButton(
                         callForChat: callFor(chat),
@@ -242,6 +315,7 @@ struct ChatView: View {
                                         }
                                     },
                                     onReply: {
+                                        editDraftMessage = nil
                                         replyDraftMessage = message
                                         isInputFocused = true
                                         withAnimation(.easeOut(duration: 0.15)) {
@@ -250,6 +324,33 @@ struct ChatView: View {
                                             showContextActionCard = false
                                         }
                                     },
+                                    onEdit: message.isMine && onEditMessage != nil ? {
+                                        replyDraftMessage = nil
+                                        editDraftMessage = message
+                                        messageText = message.displayContent
+                                        insertedMentions = message.mentions.map {
+                                            (display: "@\($0.displayName)", npub: $0.npub)
+                                        }
+                                        isInputFocused = true
+                                        withAnimation(.easeOut(duration: 0.15)) {
+                                            contextMenuMessage = nil
+                                            activeReactionMessageId = nil
+                                            showContextActionCard = false
+                                        }
+                                    } : nil,
+                                    onDelete: message.isMine && onDeleteMessage != nil ? {
+                                        onDeleteMessage?(message.id)
+                                        if editDraftMessage?.id == message.id {
+                                            editDraftMessage = nil
+                                            messageText = ""
+                                            insertedMentions = []
+                                        }
+                                        withAnimation(.easeOut(duration: 0.15)) {
+                                            contextMenuMessage = nil
+                                            activeReactionMessageId = nil
+                                            showContextActionCard = false
+                                        }
+                                    } : nil,
                                     onSaveMedia: message.media.first(where: {
                                         $0.kind == .image && $0.localPath != nil
                                     }) != nil ? {
@@ -537,6 +638,13 @@ struct ChatView: View {
         for mention in insertedMentions {
             wire = wire.replacingOccurrences(of: mention.display, with: "nostr:\(mention.npub)")
         }
+        if let editing = editDraftMessage {
+            onEditMessage?(editing.id, wire)
+            messageText = ""
+            insertedMentions = []
+            editDraftMessage = nil
+            return
+        }
         onSendMessage(wire, replyDraftMessage?.id)
         messageText = ""
         insertedMentions = []
@@ -636,7 +744,39 @@ struct ChatView: View {
                     }
                 }
 
-                if let replying = replyDraftMessage {
+                if let editing = editDraftMessage {
+                    HStack(spacing: 10) {
+                        VStack(alignment: .leading, spacing: 2) {
+                            Text("Editing message")
+                                .font(.caption.weight(.semibold))
+                                .foregroundStyle(.secondary)
+                            Text(replySnippet(editing))
+                                .font(.caption)
+                                .foregroundStyle(.secondary)
+                                .lineLimit(1)
+                        }
+                        Spacer()
+                        Button {
+                            editDraftMessage = nil
+                            messageText = ""
+                            insertedMentions = []
+                        } label: {
+                            Image(systemName: "xmark.circle.fill")
+                                .font(.body)
+                                .foregroundStyle(.tertiary)
+                        }
+                        .buttonStyle(.plain)
+                    }
+                    .padding(.horizontal, 12)
+                    .padding(.vertical, 8)
+                    .background(.ultraThinMaterial)
+                    .overlay(alignment: .leading) {
+                        Rectangle()
+                            .fill(Color.orange)
+                            .frame(width: 3)
+                    }
+                    .padding(.horizontal, 12)
+                } else if let replying = replyDraftMessage {
                     HStack(spacing: 10) {
                         VStack(alignment: .leading, spacing: 2) {
                             Text("Replying to \(replySenderLabel(replying))")
@@ -940,6 +1080,8 @@ private struct QuickReactionBar: View {
 private struct MessageActionCard: View {
     let onCopy: () -> Void
     let onReply: () -> Void
+    var onEdit: (() -> Void)? = nil
+    var onDelete: (() -> Void)? = nil
     var onSaveMedia: (() -> Void)? = nil
 
     var body: some View {
@@ -973,6 +1115,31 @@ private struct MessageActionCard: View {
                 }
                 .buttonStyle(.plain)
             }
+
+            if let onEdit {
+                Button {
+                    onEdit()
+                } label: {
+                    Label("Edit", systemImage: "pencil")
+                        .font(.body.weight(.medium))
+                        .frame(maxWidth: .infinity, alignment: .leading)
+                }
+                .buttonStyle(.plain)
+                .accessibilityIdentifier(TestIds.chatActionEdit)
+            }
+
+            if let onDelete {
+                Button(role: .destructive) {
+                    onDelete()
+                } label: {
+                    Label("Delete", systemImage: "trash")
+                        .font(.body.weight(.medium))
+                        .foregroundStyle(.red)
+                        .frame(maxWidth: .infinity, alignment: .leading)
+                }
+                .buttonStyle(.plain)
+                .accessibilityIdentifier(TestIds.chatActionDelete)
+            }
         }
         .padding(14)
         .frame(width: 220, alignment: .leading)
@@ -0,0 +1,46 @@
+import SwiftUI
+
+enum DisappearingTimerOption {
+    static let choices: [UInt64?] = [nil, 300, 3_600, 86_400, 604_800]
+
+    static func label(for secs: UInt64?) -> String {
+        guard let secs else { return "Off" }
+        let units: [(UInt64, String)] = [
+            (604_800, "week"),
+            (86_400, "day"),
+            (3_600, "hour"),
+            (60, "minute"),
+        ]
+        let (count, unit) = units
+            .first { secs >= $0.0 && secs % $0.0 == 0 }
+            .map { (secs / $0.0, $0.1) } ?? (secs, "second")
+        return count == 1 ? "1 \(unit)" : "\(count) \(unit)s"
+    }
+}
+
+struct DisappearingTimerMenu: View {
+    let currentSecs: UInt64?
+    let onSelect: @MainActor (UInt64?) -> Void
+
+    var body: some View {
+        Menu {
+            Section("Disappearing messages") {
+                ForEach(DisappearingTimerOption.choices, id: \.self) { option in
+                    Button {
+                        onSelect(option)
+                    } label: {
+                        if option == currentSecs {
+                            Label(DisappearingTimerOption.label(for: option), systemImage: "checkmark")
+                        } else {
+                            Text(DisappearingTimerOption.label(for: option))
+                        }
+                    }
+                }
+            }
+        } label: {
+            Image(systemName: currentSecs == nil ? "timer" : "timer.circle.fill")
+        }
+        .accessibilityLabel("Disappearing messages")
+        .accessibilityIdentifier(TestIds.chatDisappearingTimer)
+    }
+}
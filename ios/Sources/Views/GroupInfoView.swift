@@ -6,18 +6,25 @@ struct GroupInfoView: View {
     let state: GroupInfoViewState
     let onAddMembers: @MainActor ([String]) -> Void
     let onRemoveMember: @MainActor (String) -> Void
+    let onSetAdmin: @MainActor (String, Bool) -> Void
     let onLeaveGroup: @MainActor () -> Void
     let onRenameGroup: @MainActor (String) -> Void
     let onTapMember: (@MainActor (String) -> Void)?
     let onSaveGroupProfile: @MainActor (String, String) -> Void
     let onUploadGroupProfilePhoto: @MainActor (Data, String) -> Void
     let onOpenMediaGallery: (@MainActor () -> Void)?
+    let onCreateInviteLink: @MainActor (Bool, Bool) -> Void
+    let onRevokeInviteLink: @MainActor (String) -> Void
+    let onApproveJoinRequest: @MainActor (String) -> Void
+    let onDeclineJoinRequest: @MainActor (String) -> Void
     @State private var npubInput = ""
     @State private var showScanner = false
     @State private var isEditing = false
     @State private var editedName = ""
     @State private var copiedGroupId = false
     @State private var showGroupProfileSheet = false
+    @State private var inviteRequiresApproval = false
+    @State private var presentedInviteLink: PresentedInviteLink?
 
     var body: some View {
         if let chat = state.chat {
@@ -135,6 +142,20 @@ struct GroupInfoView: View {
                                 }
                             }
                         }
+                        .swipeActions(edge: .leading) {
+                            if chat.isAdmin {
+                                Button {
+                                    onSetAdmin(member.pubkey, !member.isAdmin)
+                                } label: {
+                                    if member.isAdmin {
+                                        Label("Remove Admin", systemImage: "shield.slash")
+                                    } else {
+                                        Label("Make Admin", systemImage: "shield")
+                                    }
+                                }
+                                .tint(.orange)
+                            }
+                        }
                     }
                 }
 
@@ -158,6 +179,35 @@ struct GroupInfoView: View {
                             .accessibilityIdentifier(TestIds.groupInfoAddButton)
                         }
                     }
+
+                    if !chat.joinRequests.isEmpty {
+                        Section("Join Requests") {
+                            ForEach(chat.joinRequests, id: \.pubkey) { request in
+                                joinRequestRow(request)
+                            }
+                        }
+                    }
+
+                    Section {
+                        ForEach(chat.inviteLinks, id: \.token) { link in
+                            inviteLinkRow(link)
+                        }
+                        Toggle("Require approval", isOn: $inviteRequiresApproval)
+                        Button {
+                            onCreateInviteLink(true, inviteRequiresApproval)
+                        } label: {
+                            Label("New Invite Link", systemImage: "link.badge.plus")
+                        }
+                        Button {
+                            onCreateInviteLink(false, inviteRequiresApproval)
+                        } label: {
+                            Label("New One-Time Link", systemImage: "link")
+                        }
+                    } header: {
+                        Text("Invite Links")
+                    } footer: {
+                        Text("Anyone with a link can ask to join. Revoke a link to stop it working.")
+                    }
                 }
 
                 Section {
@@ -194,6 +244,9 @@ struct GroupInfoView: View {
                     npubInput = scanned
                 }
             }
+            .sheet(item: $presentedInviteLink) { link in
+                InviteLinkQrSheet(url: link.url)
+            }
             .sheet(isPresented: $showGroupProfileSheet) {
                 GroupProfileSheet(
                     profile: chat.myGroupProfile,
@@ -210,6 +263,150 @@ struct GroupInfoView: View {
         if npub.count <= 20 { return npub }
         return String(npub.prefix(12)) + "..." + String(npub.suffix(4))
     }
+
+    private func inviteLinkUrl(_ link: GroupInviteLinkInfo) -> String? {
+        groupInviteLinkUrl(
+            scheme: IOSExternalSignerBridge.callbackScheme(),
+            adminNpub: link.adminNpub,
+            token: link.token
+        )
+    }
+
+    private func inviteLinkRow(_ link: GroupInviteLinkInfo) -> some View {
+        Button {
+            if let url = inviteLinkUrl(link) {
+                presentedInviteLink = PresentedInviteLink(url: url)
+            }
+        } label: {
+            HStack(spacing: 8) {
+                Image(systemName: link.multiUse ? "link" : "1.circle")
+                    .foregroundStyle(.blue)
+                VStack(alignment: .leading, spacing: 1) {
+                    Text(link.multiUse ? "Invite link" : "One-time link")
+                        .font(.body)
+                    Text(inviteLinkSubtitle(link))
+                        .font(.caption2)
+                        .foregroundStyle(.tertiary)
+                }
+                Spacer()
+                Image(systemName: "qrcode")
+                    .foregroundStyle(.secondary)
+            }
+        }
+        .buttonStyle(.plain)
+        .swipeActions(edge: .trailing) {
+            Button(role: .destructive) {
+                onRevokeInviteLink(link.token)
+            } label: {
+                Label("Revoke", systemImage: "xmark.circle")
+            }
+        }
+    }
+
+    private func inviteLinkSubtitle(_ link: GroupInviteLinkInfo) -> String {
+        var parts: [String] = []
+        if link.multiUse {
+            parts.append(link.useCount == 1 ? "Used once" : "Used \(link.useCount) times")
+        }
+        if link.requiresApproval {
+            parts.append("Requires approval")
+        }
+        return parts.isEmpty ? "Unused" : parts.joined(separator: " · ")
+    }
+
+    private func joinRequestRow(_ request: GroupJoinRequest) -> some View {
+        HStack(spacing: 8) {
+            AvatarView(
+                name: request.name,
+                npub: request.npub,
+                pictureUrl: request.pictureUrl,
+                size: 28
+            )
+            Text(request.name ?? truncated(request.npub))
+                .font(.body)
+                .lineLimit(1)
+            Spacer()
+            Button {
+                onDeclineJoinRequest(request.pubkey)
+            } label: {
+                Image(systemName: "xmark.circle.fill")
+                    .foregroundStyle(.red)
+            }
+            .buttonStyle(.borderless)
+            Button {
+                onApproveJoinRequest(request.pubkey)
+            } label: {
+                Image(systemName: "checkmark.circle.fill")
+                    .foregroundStyle(.green)
+            }
+            .buttonStyle(.borderless)
+        }
+    }
+}
+
+private struct PresentedInviteLink: Identifiable {
+    let url: String
+    var id: String { url }
+}
+
+private struct InviteLinkQrSheet: View {
+    let url: String
+    private let cachedQr: UIImage?
+    @Environment(\.dismiss) private var dismiss
+    @State private var copied = false
+
+    init(url: String) {
+        self.url = url
+        self.cachedQr = QRCodeImage.make(from: url)
+    }
+
+    var body: some View {
+        NavigationStack {
+            VStack(spacing: 20) {
+                if let img = cachedQr {
+                    Image(uiImage: img)
+                        .interpolation(.none)
+                        .resizable()
+                        .scaledToFit()
+                        .frame(width: 220, height: 220)
+                        .background(.white)
+                        .clipShape(.rect(cornerRadius: 12))
+                }
+                Text(url)
+                    .font(.caption.monospaced())
+                    .foregroundStyle(.secondary)
+                    .lineLimit(2)
+                    .truncationMode(.middle)
+                    .padding(.horizontal)
+                HStack(spacing: 12) {
+                    Button {
+                        UIPasteboard.general.string = url
+                        copied = true
+                    } label: {
+                        Label(copied ? "Copied!" : "Copy", systemImage: "doc.on.doc")
+                    }
+                    .buttonStyle(.bordered)
+                    if let shareUrl = URL(string: url) {
+                        ShareLink(item: shareUrl) {
+                            Label("Share", systemImage: "square.and.arrow.up")
+                        }
+                        .buttonStyle(.borderedProminent)
+                    }
+                }
+                Spacer()
+            }
+            .padding(.top, 24)
+            .navigationTitle("Invite Link")
+            .navigationBarTitleDisplayMode(.inline)
+            .toolbar {
+                ToolbarItem(placement: .topBarTrailing) {
+                    Button("Close") {
+                        dismiss()
+                    }
+                }
+            }
+        }
+    }
 }
 
 struct GroupProfileSheet: View {
@@ -340,12 +537,17 @@ struct GroupProfileSheet: View {
             state: GroupInfoViewState(chat: nil),
             onAddMembers: { _ in },
             onRemoveMember: { _ in },
+            onSetAdmin: { _, _ in },
             onLeaveGroup: {},
             onRenameGroup: { _ in },
             onTapMember: nil,
             onSaveGroupProfile: { _, _ in },
             onUploadGroupProfilePhoto: { _, _ in },
-            onOpenMediaGallery: nil
+            onOpenMediaGallery: nil,
+            onCreateInviteLink: { _, _ in },
+            onRevokeInviteLink: { _ in },
+            onApproveJoinRequest: { _ in },
+            onDeclineJoinRequest: { _ in }
         )
     }
 }
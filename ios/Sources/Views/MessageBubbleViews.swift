@@ -124,6 +124,11 @@ struct MessageGroupRow: View {
                                 .font(.caption2)
                                 .foregroundStyle(.red)
                         }
+                    } else if case .sent = delivery,
+                              let seen = seenByText(group.messages.last?.seenBy ?? []) {
+                        Text(seen)
+                            .font(.caption2)
+                            .foregroundStyle(.secondary)
                     } else {
                         Text(deliveryText(delivery))
                             .font(.caption2)
@@ -464,7 +469,9 @@ private struct MessageBubble: View {
         let segments = message.segments.isEmpty ? fallbackSegments() : message.segments
 
         VStack(alignment: message.isMine ? .trailing : .leading, spacing: 0) {
-            if let hypernote = message.hypernote {
+            if message.deleted {
+                deletedBubble
+            } else if let hypernote = message.hypernote {
                 VStack(alignment: .leading, spacing: 0) {
                     replyPreviewSection
                     HypernoteRenderer(
@@ -737,6 +744,23 @@ private struct MessageBubble: View {
         .clipShape(UnevenRoundedRectangleCompat(cornerRadii: bubbleRadii, style: .continuous))
     }
 
+    private var deletedBubble: some View {
+        VStack(alignment: .leading, spacing: 3) {
+            Label("This message was deleted", systemImage: "trash")
+                .font(.subheadline.italic())
+                .foregroundStyle(message.isMine ? Color.white.opacity(0.85) : Color.secondary)
+
+            Text(timestampText)
+                .font(.caption2)
+                .foregroundStyle(message.isMine ? Color.white.opacity(0.78) : Color.secondary.opacity(0.9))
+        }
+        .padding(.horizontal, 12)
+        .padding(.top, 8)
+        .padding(.bottom, 6)
+        .background(message.isMine ? Color.blue.opacity(0.6) : Color.gray.opacity(0.12))
+        .clipShape(UnevenRoundedRectangleCompat(cornerRadii: bubbleRadii, style: .continuous))
+    }
+
     private func markdownBubble(text: String) -> some View {
         VStack(alignment: .leading, spacing: 3) {
             Markdown(text)
@@ -756,7 +780,8 @@ private struct MessageBubble: View {
     }
 
     private var timestampText: String {
-        message.displayTimestamp
+        guard message.editedAt != nil, !message.deleted else { return message.displayTimestamp }
+        return "\(message.displayTimestamp) · edited"
     }
 
     private func fallbackSegments() -> [MessageSegment] {
@@ -805,7 +830,7 @@ private struct MessageBubble: View {
     }
 
     private func handleLongPress() {
-        guard let onLongPressMessage else { return }
+        guard let onLongPressMessage, !message.deleted else { return }
         let impactFeedback = UIImpactFeedbackGenerator(style: .medium)
         impactFeedback.impactOccurred()
         onLongPressMessage(message, bubbleFrameRef.frame)
@@ -948,6 +973,15 @@ func deliveryText(_ d: MessageDeliveryState) -> String {
     }
 }
 
+func seenByText(_ readers: [MessageReader]) -> String? {
+    guard !readers.isEmpty else { return nil }
+    let names = readers.compactMap { $0.name }
+    guard let first = names.first else { return "Seen" }
+    if readers.count == 1 { return "Seen by \(first)" }
+    if readers.count == 2, names.count == 2 { return "Seen by \(first), \(names[1])" }
+    return "Seen by \(first) and \(readers.count - 1) others"
+}
+
 // MARK: - Pika HTML view
 
 private struct PikaHtmlView: View {
@@ -12,9 +12,17 @@ struct MyNpubQrSheet: View {
     let onLogout: @MainActor () -> Void
     let isDeveloperModeEnabledProvider: @MainActor () -> Bool
     let onEnableDeveloperMode: @MainActor () -> Void
+    let isReadReceiptsEnabledProvider: @MainActor () -> Bool
+    let onSetReadReceiptsEnabled: @MainActor (Bool) -> Void
+    let onExportBackup: @MainActor (_ passphrase: String) -> URL
+    let onImportBackup: @MainActor (_ fileURL: URL, _ passphrase: String) -> Void
     let onWipeProfileCache: @MainActor () -> Void
     let onWipeMediaCache: @MainActor () -> Void
     let onWipeLocalData: @MainActor () -> Void
+    let accounts: [AccountSummary]
+    let onAddAccount: @MainActor (_ nsec: String) -> Void
+    let onSwitchAccount: @MainActor (_ pubkey: String) -> Void
+    let onRemoveAccount: @MainActor (_ pubkey: String) -> Void
     private let cachedNpubQr: UIImage?
 
     @Environment(\.dismiss) private var dismiss
@@ -25,6 +33,12 @@ struct MyNpubQrSheet: View {
     @State private var isLoadingPhoto = false
     @State private var appVersionTapCount = 0
     @State private var developerModeEnabled = false
+    @State private var readReceiptsEnabled = false
+    @State private var backupPassphrase = ""
+    @State private var exportedBackupURL: URL?
+    @State private var showBackupImporter = false
+    @State private var newAccountNsec = ""
+    @State private var accountPendingRemoval: AccountSummary?
     @State private var nameDraft = ""
     @State private var aboutDraft = ""
     @State private var didSyncDrafts = false
@@ -47,9 +61,17 @@ struct MyNpubQrSheet: View {
         onLogout: @MainActor @escaping () -> Void,
         isDeveloperModeEnabledProvider: @MainActor @escaping () -> Bool,
         onEnableDeveloperMode: @MainActor @escaping () -> Void,
+        isReadReceiptsEnabledProvider: @MainActor @escaping () -> Bool,
+        onSetReadReceiptsEnabled: @MainActor @escaping (Bool) -> Void,
+        onExportBackup: @MainActor @escaping (_ passphrase: String) -> URL,
+        onImportBackup: @MainActor @escaping (_ fileURL: URL, _ passphrase: String) -> Void,
         onWipeProfileCache: @MainActor @escaping () -> Void,
         onWipeMediaCache: @MainActor @escaping () -> Void,
         onWipeLocalData: @MainActor @escaping () -> Void,
+        accounts: [AccountSummary],
+        onAddAccount: @MainActor @escaping (_ nsec: String) -> Void,
+        onSwitchAccount: @MainActor @escaping (_ pubkey: String) -> Void,
+        onRemoveAccount: @MainActor @escaping (_ pubkey: String) -> Void,
         showLogoutConfirm: Bool = false
     ) {
         self.npub = npub
@@ -61,9 +83,17 @@ struct MyNpubQrSheet: View {
         self.onLogout = onLogout
         self.isDeveloperModeEnabledProvider = isDeveloperModeEnabledProvider
         self.onEnableDeveloperMode = onEnableDeveloperMode
+        self.isReadReceiptsEnabledProvider = isReadReceiptsEnabledProvider
+        self.onSetReadReceiptsEnabled = onSetReadReceiptsEnabled
+        self.onExportBackup = onExportBackup
+        self.onImportBackup = onImportBackup
         self.onWipeProfileCache = onWipeProfileCache
         self.onWipeMediaCache = onWipeMediaCache
         self.onWipeLocalData = onWipeLocalData
+        self.accounts = accounts
+        self.onAddAccount = onAddAccount
+        self.onSwitchAccount = onSwitchAccount
+        self.onRemoveAccount = onRemoveAccount
         self.cachedNpubQr = QRCodeImage.make(from: npub)
         self._showLogoutConfirm = State(initialValue: showLogoutConfirm)
     }
@@ -188,6 +218,13 @@ struct MyNpubQrSheet: View {
             NavigationLink("Notifications") {
                 NotificationSettingsView()
             }
+            Toggle("Send Read Receipts", isOn: Binding(
+                get: { readReceiptsEnabled },
+                set: { enabled in
+                    readReceiptsEnabled = enabled
+                    onSetReadReceiptsEnabled(enabled)
+                }
+            ))
             appVersionRow
             Button("Log out", role: .destructive) {
                 showLogoutConfirm = true
@@ -196,6 +233,94 @@ struct MyNpubQrSheet: View {
         }
     }
 
+    @ViewBuilder
+    private var accountsSection: some View {
+        Section {
+            ForEach(accounts, id: \.pubkey) { account in
+                accountRow(account)
+            }
+            SecureField("nsec of another account", text: $newAccountNsec)
+                .textInputAutocapitalization(.never)
+                .autocorrectionDisabled()
+            Button("Add Account") {
+                onAddAccount(newAccountNsec)
+                newAccountNsec = ""
+                dismiss()
+            }
+            .disabled(newAccountNsec.trimmingCharacters(in: .whitespacesAndNewlines).isEmpty)
+        } header: {
+            Text("Accounts")
+        } footer: {
+            Text("Each account keeps its own chats on this device. Removing an account deletes its local data.")
+        }
+    }
+
+    @ViewBuilder
+    private func accountRow(_ account: AccountSummary) -> some View {
+        let name = account.name.flatMap { $0.isEmpty ? nil : $0 }
+        HStack(spacing: 12) {
+            AvatarView(name: name, npub: account.npub, pictureUrl: account.pictureUrl, size: 32)
+            Text(name ?? shortNpub(account.npub))
+                .lineLimit(1)
+            Spacer()
+            if account.unreadCount > 0 {
+                Text("\(account.unreadCount)")
+                    .font(.caption.weight(.semibold))
+                    .foregroundStyle(.white)
+                    .padding(.horizontal, 7)
+                    .padding(.vertical, 2)
+                    .background(Color.accentColor, in: Capsule())
+            }
+            if account.isActive {
+                Image(systemName: "checkmark")
+                    .foregroundStyle(Color.accentColor)
+            }
+        }
+        .contentShape(Rectangle())
+        .onTapGesture {
+            guard !account.isActive else { return }
+            onSwitchAccount(account.pubkey)
+            dismiss()
+        }
+        .swipeActions {
+            if !account.isActive {
+                Button("Remove", role: .destructive) {
+                    accountPendingRemoval = account
+                }
+            }
+        }
+    }
+
+    private func shortNpub(_ npub: String) -> String {
+        guard npub.count > 16 else { return npub }
+        return "\(npub.prefix(10))…\(npub.suffix(4))"
+    }
+
+    @ViewBuilder
+    private var backupSection: some View {
+        Section {
+            SecureField("Backup passphrase", text: $backupPassphrase)
+                .textContentType(.newPassword)
+            Button("Export Backup") {
+                exportedBackupURL = onExportBackup(backupPassphrase)
+            }
+            .disabled(backupPassphrase.isEmpty)
+            if let exportedBackupURL {
+                ShareLink(item: exportedBackupURL) {
+                    Label("Share Backup File", systemImage: "square.and.arrow.up")
+                }
+            }
+            Button("Restore Backup…") {
+                showBackupImporter = true
+            }
+            .disabled(backupPassphrase.isEmpty)
+        } header: {
+            Text("Backup")
+        } footer: {
+            Text("Backups contain your chats and encryption state, protected by this passphrase. Restoring replaces this device's data for the same account.")
+        }
+    }
+
     @ViewBuilder
     private var developerSection: some View {
         if developerModeEnabled {
@@ -231,6 +356,8 @@ struct MyNpubQrSheet: View {
                     accountKeySection(nsec)
                 }
                 settingsSection
+                accountsSection
+                backupSection
                 developerSection
             }
             .scrollContentBackground(.hidden)
@@ -255,9 +382,20 @@ struct MyNpubQrSheet: View {
             }
             .task {
                 developerModeEnabled = isDeveloperModeEnabledProvider()
+                readReceiptsEnabled = isReadReceiptsEnabledProvider()
                 onRefreshProfile()
                 syncDraftsIfNeeded(force: false)
             }
+            .fileImporter(
+                isPresented: $showBackupImporter,
+                allowedContentTypes: [.item],
+                allowsMultipleSelection: false
+            ) { result in
+                if case .success(let urls) = result, let url = urls.first {
+                    onImportBackup(url, backupPassphrase)
+                    backupPassphrase = ""
+                }
+            }
             .onChangeCompat(of: selectedPhoto) { item in
                 handlePhotoSelection(item)
             }
@@ -280,6 +418,22 @@ struct MyNpubQrSheet: View {
             } message: {
                 Text("You can log back in with your private key.")
             }
+            .confirmationDialog(
+                "Remove account?",
+                isPresented: Binding(
+                    get: { accountPendingRemoval != nil },
+                    set: { if !$0 { accountPendingRemoval = nil } }
+                ),
+                titleVisibility: .visible,
+                presenting: accountPendingRemoval
+            ) { account in
+                Button("Remove", role: .destructive) {
+                    onRemoveAccount(account.pubkey)
+                }
+                Button("Cancel", role: .cancel) {}
+            } message: { _ in
+                Text("Its chats and keys will be deleted from this device.")
+            }
             .confirmationDialog("Wipe all local data?", isPresented: $showWipeLocalDataConfirm, titleVisibility: .visible) {
                 Button("Wipe All Local Data", role: .destructive) {
                     onWipeLocalData()
@@ -581,9 +735,17 @@ struct MyNpubQrSheet: View {
         onLogout: {},
         isDeveloperModeEnabledProvider: { false },
         onEnableDeveloperMode: {},
+        isReadReceiptsEnabledProvider: { false },
+        onSetReadReceiptsEnabled: { _ in },
+        onExportBackup: { _ in FileManager.default.temporaryDirectory },
+        onImportBackup: { _, _ in },
         onWipeProfileCache: {},
         onWipeMediaCache: {},
-        onWipeLocalData: {}
+        onWipeLocalData: {},
+        accounts: [],
+        onAddAccount: { _ in },
+        onSwitchAccount: { _ in },
+        onRemoveAccount: { _ in }
     )
 }
 
@@ -598,9 +760,17 @@ struct MyNpubQrSheet: View {
         onLogout: {},
         isDeveloperModeEnabledProvider: { false },
         onEnableDeveloperMode: {},
+        isReadReceiptsEnabledProvider: { false },
+        onSetReadReceiptsEnabled: { _ in },
+        onExportBackup: { _ in FileManager.default.temporaryDirectory },
+        onImportBackup: { _, _ in },
         onWipeProfileCache: {},
         onWipeMediaCache: {},
         onWipeLocalData: {},
+        accounts: [],
+        onAddAccount: { _ in },
+        onSwitchAccount: { _ in },
+        onRemoveAccount: { _ in },
         showLogoutConfirm: true
     )
 }
@@ -4,6 +4,7 @@ import UIKit
 struct NewChatView: View {
     let state: NewChatViewState
     let onCreateChat: @MainActor (String) -> Void
+    let onJoinGroup: @MainActor (String) -> Void
     let onRefreshFollowList: @MainActor () -> Void
     @State private var searchText = ""
     @State private var npubInput = ""
@@ -205,9 +206,14 @@ struct NewChatView: View {
     }
 
     private func handleIncomingPeer(_ input: String) {
+        let trimmed = input.trimmingCharacters(in: .whitespacesAndNewlines)
+        if isGroupInviteLink(input: trimmed) {
+            onJoinGroup(trimmed)
+            return
+        }
         let peer = normalizePeerKey(input: input)
         guard isValidPeerKey(input: peer) else {
-            invalidNpubMessage = "Enter, paste, or scan a valid code (npub1… or 64-character hex public key)."
+            invalidNpubMessage = "Enter, paste, or scan a valid code (npub1…, 64-character hex public key, or group invite link)."
             showInvalidNpubAlert = true
             return
         }
@@ -228,9 +234,11 @@ struct NewChatView: View {
                         .accessibilityIdentifier(TestIds.newChatPeerNpub)
 
                     Button("Start Chat") {
+                        let trimmed = npubInput.trimmingCharacters(in: .whitespacesAndNewlines)
                         let peer = normalizePeerKey(input: npubInput)
-                        handleIncomingPeer(peer)
-                        if isValidPeerKey(input: peer) {
+                        let isInviteLink = isGroupInviteLink(input: trimmed)
+                        handleIncomingPeer(isInviteLink ? trimmed : peer)
+                        if isInviteLink || isValidPeerKey(input: peer) {
                             npubInput = ""
                             showManualEntrySheet = false
                         }
@@ -269,6 +277,7 @@ struct NewChatView: View {
                 myNpub: nil
             ),
             onCreateChat: { _ in },
+            onJoinGroup: { _ in },
             onRefreshFollowList: {}
         )
     }
@@ -284,6 +293,7 @@ struct NewChatView: View {
                 myNpub: nil
             ),
             onCreateChat: { _ in },
+            onJoinGroup: { _ in },
             onRefreshFollowList: {}
         )
     }
@@ -299,6 +309,7 @@ struct NewChatView: View {
                 myNpub: nil
             ),
             onCreateChat: { _ in },
+            onJoinGroup: { _ in },
             onRefreshFollowList: {}
         )
     }
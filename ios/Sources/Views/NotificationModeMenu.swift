@@ -0,0 +1,85 @@
+import SwiftUI
+
+enum NotificationModeOption {
+    static let snoozeChoices: [(String, TimeInterval)] = [
+        ("1 hour", 3_600),
+        ("8 hours", 28_800),
+        ("1 day", 86_400),
+        ("1 week", 604_800),
+    ]
+
+    static func isMuted(_ mode: ChatNotificationMode) -> Bool {
+        switch mode {
+        case .muted, .mutedUntil:
+            return true
+        case .all, .mentionsOnly:
+            return false
+        }
+    }
+
+    /// Short status for the chat header; `nil` when every message notifies.
+    static func label(for mode: ChatNotificationMode) -> String? {
+        switch mode {
+        case .all:
+            return nil
+        case .mentionsOnly:
+            return "Mentions only"
+        case .mutedUntil(let until):
+            let end = Date(timeIntervalSince1970: TimeInterval(until))
+            return "Muted until \(end.formatted(date: .omitted, time: .shortened))"
+        case .muted:
+            return "Muted"
+        }
+    }
+}
+
+struct NotificationModeMenu: View {
+    let mode: ChatNotificationMode
+    let onSelect: @MainActor (ChatNotificationMode) -> Void
+
+    var body: some View {
+        Menu {
+            Section("Notifications") {
+                Button {
+                    onSelect(.all)
+                } label: {
+                    if case .all = mode {
+                        Label("All messages", systemImage: "checkmark")
+                    } else {
+                        Text("All messages")
+                    }
+                }
+                Button {
+                    onSelect(.mentionsOnly)
+                } label: {
+                    if case .mentionsOnly = mode {
+                        Label("Mentions only", systemImage: "checkmark")
+                    } else {
+                        Text("Mentions only")
+                    }
+                }
+                Menu("Mute for…") {
+                    ForEach(NotificationModeOption.snoozeChoices, id: \.1) { label, seconds in
+                        Button(label) {
+                            let until = Int64(Date().addingTimeInterval(seconds).timeIntervalSince1970)
+                            onSelect(.mutedUntil(until: until))
+                        }
+                    }
+                }
+                Button {
+                    onSelect(.muted)
+                } label: {
+                    if case .muted = mode {
+                        Label("Mute", systemImage: "checkmark")
+                    } else {
+                        Text("Mute")
+                    }
+                }
+            }
+        } label: {
+            Image(systemName: NotificationModeOption.isMuted(mode) ? "bell.slash" : "bell")
+        }
+        .accessibilityLabel("Notifications")
+        .accessibilityIdentifier(TestIds.chatNotificationMode)
+    }
+}
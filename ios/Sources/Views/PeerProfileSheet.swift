@@ -8,6 +8,8 @@ struct PeerProfileSheet: View {
     let onStartVideoCall: @MainActor () -> Void
     let onFollow: @MainActor () -> Void
     let onUnfollow: @MainActor () -> Void
+    let onBlock: @MainActor () -> Void
+    let onUnblock: @MainActor () -> Void
     let onOpenMediaGallery: (@MainActor () -> Void)?
     let onClose: @MainActor () -> Void
     private let cachedProfileQr: UIImage?
@@ -30,6 +32,8 @@ struct PeerProfileSheet: View {
         onStartVideoCall: @MainActor @escaping () -> Void,
         onFollow: @MainActor @escaping () -> Void,
         onUnfollow: @MainActor @escaping () -> Void,
+        onBlock: @MainActor @escaping () -> Void,
+        onUnblock: @MainActor @escaping () -> Void,
         onOpenMediaGallery: (@MainActor () -> Void)?,
         onClose: @MainActor @escaping () -> Void
     ) {
@@ -39,6 +43,8 @@ struct PeerProfileSheet: View {
         self.onStartVideoCall = onStartVideoCall
         self.onFollow = onFollow
         self.onUnfollow = onUnfollow
+        self.onBlock = onBlock
+        self.onUnblock = onUnblock
         self.onOpenMediaGallery = onOpenMediaGallery
         self.onClose = onClose
         self.cachedProfileQr = QRCodeImage.make(from: profile.npub)
@@ -172,6 +178,23 @@ struct PeerProfileSheet: View {
                 .foregroundStyle(.primary)
             }
         }
+
+        Section {
+            Button(role: profile.isBlocked ? nil : .destructive) {
+                if profile.isBlocked {
+                    onUnblock()
+                } else {
+                    onBlock()
+                }
+            } label: {
+                Label(
+                    profile.isBlocked ? "Unblock" : "Block",
+                    systemImage: profile.isBlocked ? "hand.raised.slash" : "hand.raised"
+                )
+            }
+        } footer: {
+            Text("Blocked people can't invite you, and their messages, typing and calls are hidden.")
+        }
     }
 
     @ViewBuilder
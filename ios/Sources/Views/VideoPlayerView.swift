@@ -55,12 +55,43 @@ struct VideoAttachmentView: View {
                     )
                 }
         } else {
-            // Auto-downloading: show placeholder with spinner
+            // Auto-downloading: show the sender's poster (or blurhash) with a spinner
             ZStack {
-                placeholder
+                posterOrPlaceholder
                 ProgressView().tint(.white)
             }
             .frame(width: videoSize.width, height: videoSize.height)
+            .overlay(alignment: .bottomTrailing) { durationBadge }
+        }
+    }
+
+    @ViewBuilder
+    private var posterOrPlaceholder: some View {
+        if let posterPath = attachment.thumbnailPath,
+           let poster = UIImage(contentsOfFile: posterPath) {
+            Image(uiImage: poster)
+                .resizable()
+                .scaledToFill()
+                .frame(width: videoSize.width, height: videoSize.height)
+                .clipped()
+        } else if let hash = attachment.blurhash {
+            BlurhashView(hash: hash, size: videoSize)
+        } else {
+            placeholder
+        }
+    }
+
+    @ViewBuilder
+    private var durationBadge: some View {
+        if let durationMs = attachment.durationMs {
+            let seconds = Int(durationMs / 1000)
+            Text(String(format: "%d:%02d", seconds / 60, seconds % 60))
+                .font(.caption2.monospacedDigit())
+                .foregroundStyle(.white)
+                .padding(.horizontal, 6)
+                .padding(.vertical, 2)
+                .background(.black.opacity(0.5), in: Capsule())
+                .padding(6)
         }
     }
 
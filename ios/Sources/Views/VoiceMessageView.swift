@@ -1,6 +1,5 @@
 import SwiftUI
 import AVFoundation
-import Accelerate
 import Perception
 
 struct VoiceMessageView: View {
@@ -33,13 +32,16 @@ struct VoiceMessageView: View {
             }
 
             StaticWaveformView(
-                samples: player.waveformSamples,
+                samples: waveformSamples,
                 progress: player.progress,
                 isMine: isMine,
-                maxBarHeight: 24
+                maxBarHeight: 24,
+                onSeek: { fraction in
+                    player.seek(url: URL(fileURLWithPath: localPath), to: fraction)
+                }
             )
 
-            Text(player.isPlaying ? formatTime(player.currentTime) : formatTime(player.duration))
+            Text(formatTime(player.isPlaying || player.currentTime > 0 ? player.currentTime : displayDuration))
                 .font(.caption.monospacedDigit())
                 .foregroundStyle(isMine ? .white.opacity(0.78) : .secondary)
         }
@@ -47,23 +49,48 @@ struct VoiceMessageView: View {
         .padding(.vertical, 10)
         .frame(width: 220)
         .onAppear {
-            player.loadWaveform(from: localPath)
+            player.loadDuration(from: localPath)
         }
     }
 
+    /// Levels from the sender's imeta tag, or decoded by Rust after download.
+    private var waveformSamples: [CGFloat] {
+        (attachment.waveform ?? []).map { CGFloat($0) }
+    }
+
+    private var displayDuration: TimeInterval {
+        if player.duration > 0 { return player.duration }
+        return TimeInterval(attachment.durationMs ?? 0) / 1000
+    }
+
     private var downloadRow: some View {
         HStack(spacing: 10) {
             Image(systemName: "waveform")
                 .font(.title3)
                 .foregroundStyle(isMine ? .white.opacity(0.8) : .secondary)
 
-            VStack(alignment: .leading, spacing: 2) {
-                Text("Voice Message")
-                    .font(.subheadline)
-                    .foregroundStyle(isMine ? .white : .primary)
-                Text(attachment.mimeType)
-                    .font(.caption2)
-                    .foregroundStyle(isMine ? .white.opacity(0.6) : .secondary)
+            if waveformSamples.isEmpty {
+                VStack(alignment: .leading, spacing: 2) {
+                    Text("Voice Message")
+                        .font(.subheadline)
+                        .foregroundStyle(isMine ? .white : .primary)
+                    Text(attachment.mimeType)
+                        .font(.caption2)
+                        .foregroundStyle(isMine ? .white.opacity(0.6) : .secondary)
+                }
+            } else {
+                StaticWaveformView(
+                    samples: waveformSamples,
+                    progress: 0,
+                    isMine: isMine,
+                    maxBarHeight: 24
+                )
+            }
+
+            if let durationMs = attachment.durationMs {
+                Text(formatTime(TimeInterval(durationMs) / 1000))
+                    .font(.caption.monospacedDigit())
+                    .foregroundStyle(isMine ? .white.opacity(0.78) : .secondary)
             }
 
             Spacer(minLength: 0)
@@ -98,7 +125,6 @@ final class VoiceMessagePlayer: NSObject {
     private(set) var currentTime: TimeInterval = 0
     private(set) var duration: TimeInterval = 0
     private(set) var progress: CGFloat = 0
-    private(set) var waveformSamples: [CGFloat] = []
 
     private var audioPlayer: AVAudioPlayer?
     private var timer: Timer?
@@ -112,24 +138,25 @@ final class VoiceMessagePlayer: NSObject {
         }
     }
 
-    func loadWaveform(from path: String) {
-        guard waveformSamples.isEmpty else { return }
-        let url = URL(fileURLWithPath: path)
-        Task.detached { [weak self] in
-            let samples = VoiceMessagePlayer.extractWaveform(from: url, sampleCount: 20)
-            await MainActor.run {
-                self?.waveformSamples = samples
-            }
-        }
-        // Also load duration
+    func loadDuration(from path: String) {
+        guard duration == 0 else { return }
         do {
-            let player = try AVAudioPlayer(contentsOf: url)
+            let player = try AVAudioPlayer(contentsOf: URL(fileURLWithPath: path))
             duration = player.duration
         } catch {
-            // Duration will stay 0
+            // Fall back to the duration from the imeta tag.
         }
     }
 
+    /// Jump to `fraction` of the clip, loading it first if it hasn't been played yet.
+    func seek(url: URL, to fraction: CGFloat) {
+        guard let player = loadPlayer(url: url) else { return }
+        let clamped = max(0, min(1, fraction))
+        player.currentTime = player.duration * TimeInterval(clamped)
+        currentTime = player.currentTime
+        progress = clamped
+    }
+
     // MARK: - Private
 
     private func play(url: URL) {
@@ -137,19 +164,34 @@ final class VoiceMessagePlayer: NSObject {
             let session = AVAudioSession.sharedInstance()
             try session.setCategory(.playAndRecord, mode: .default, options: [.defaultToSpeaker])
             try session.setActive(true)
+        } catch {
+            print("VoiceMessagePlayer: playback error: \(error)")
+            return
+        }
+        guard let player = loadPlayer(url: url) else { return }
+        player.play()
+        isPlaying = true
+        startTimer()
+    }
 
+    /// Reuse the current player for `url` so playback resumes where it was paused or scrubbed to.
+    private func loadPlayer(url: URL) -> AVAudioPlayer? {
+        if let audioPlayer, currentURL == url {
+            return audioPlayer
+        }
+        do {
             let player = try AVAudioPlayer(contentsOf: url)
             player.delegate = self
-            player.play()
-
+            player.prepareToPlay()
             audioPlayer = player
             currentURL = url
             duration = player.duration
-            isPlaying = true
-
-            startTimer()
+            currentTime = 0
+            progress = 0
+            return player
         } catch {
             print("VoiceMessagePlayer: playback error: \(error)")
+            return nil
         }
     }
 
@@ -184,41 +226,6 @@ final class VoiceMessagePlayer: NSObject {
         progress = 0
         stopTimer()
     }
-
-    // Extract waveform samples from audio file
-    private nonisolated static func extractWaveform(from url: URL, sampleCount: Int) -> [CGFloat] {
-        guard let audioFile = try? AVAudioFile(forReading: url) else { return [] }
-        let format = audioFile.processingFormat
-        let frameCount = AVAudioFrameCount(audioFile.length)
-        guard frameCount > 0,
-              let buffer = AVAudioPCMBuffer(pcmFormat: format, frameCapacity: frameCount)
-        else { return [] }
-
-        do {
-            try audioFile.read(into: buffer)
-        } catch {
-            return []
-        }
-
-        guard let channelData = buffer.floatChannelData?[0] else { return [] }
-        let totalFrames = Int(buffer.frameLength)
-        let samplesPerBin = max(1, totalFrames / sampleCount)
-        var samples: [CGFloat] = []
-
-        for i in 0..<sampleCount {
-            let start = i * samplesPerBin
-            let end = min(start + samplesPerBin, totalFrames)
-            guard start < totalFrames else { break }
-
-            var rms: Float = 0
-            vDSP_measqv(channelData.advanced(by: start), 1, &rms, vDSP_Length(end - start))
-            rms = sqrtf(rms)
-            let db = 20 * log10f(max(rms, 1e-6))
-            let normalized = CGFloat(max(0, min(1, (db + 50) / 50)))
-            samples.append(normalized)
-        }
-        return samples
-    }
 }
 
 extension VoiceMessagePlayer: @preconcurrency AVAudioPlayerDelegate {
@@ -236,14 +243,29 @@ private struct StaticWaveformView: View {
     let progress: CGFloat
     let isMine: Bool
     var maxBarHeight: CGFloat = 28
+    /// Called with the tapped or dragged-to position in `0...1`; nil disables scrubbing.
+    var onSeek: ((CGFloat) -> Void)? = nil
 
     private let barWidth: CGFloat = 3
     private let barSpacing: CGFloat = 2
 
+    /// Bars that fit in a chat bubble; longer waveforms keep the peak of each group.
+    private let maxBars = 28
+
+    private var bars: [CGFloat] {
+        guard samples.count > maxBars else { return samples }
+        return (0..<maxBars).map { bar in
+            let start = bar * samples.count / maxBars
+            let end = max(start + 1, (bar + 1) * samples.count / maxBars)
+            return samples[start..<end].max() ?? 0
+        }
+    }
+
     var body: some View {
+        let bars = bars
         HStack(alignment: .center, spacing: barSpacing) {
-            ForEach(Array(samples.enumerated()), id: \.offset) { index, level in
-                let barProgress = samples.isEmpty ? 0 : CGFloat(index) / CGFloat(samples.count)
+            ForEach(Array(bars.enumerated()), id: \.offset) { index, level in
+                let barProgress = bars.isEmpty ? 0 : CGFloat(index) / CGFloat(bars.count)
                 let isPlayed = barProgress <= progress
                 RoundedRectangle(cornerRadius: 1.5)
                     .fill(barColor(isPlayed: isPlayed))
@@ -251,6 +273,17 @@ private struct StaticWaveformView: View {
             }
         }
         .frame(height: maxBarHeight)
+        .contentShape(Rectangle())
+        .gesture(
+            DragGesture(minimumDistance: 0)
+                .onChanged { value in
+                    guard let onSeek else { return }
+                    let count = CGFloat(max(bars.count, 1))
+                    let width = count * barWidth + (count - 1) * barSpacing
+                    onSeek(value.location.x / width)
+                },
+            including: onSeek == nil ? .none : .all
+        )
     }
 
     private func barColor(isPlayed: Bool) -> Color {
@@ -15,18 +15,26 @@ private func makeTestState(rev: UInt64, toast: String? = nil) -> AppState {
             fetchingFollowList: false
         ),
         chatList: [],
+        invitations: [],
         currentChat: nil,
         followList: [],
+        blockedUsers: [],
         peerProfile: nil,
         activeCall: nil,
         callTimeline: [],
+        callReport: nil,
         toast: toast,
         developerMode: false,
+        readReceiptsEnabled: false,
+        autoAcceptFollowedInvites: true,
+        blockListSyncEnabled: false,
         updateRequired: false,
         agentButton: nil,
         agentProvisioning: nil,
         voiceRecording: nil,
-        mediaGallery: nil
+        mediaGallery: nil,
+        messageSearch: nil,
+        accounts: []
     )
 }
 
@@ -45,18 +53,26 @@ final class AppManagerTests: XCTestCase {
                 fetchingFollowList: false
             ),
             chatList: [],
+            invitations: [],
             currentChat: nil,
             followList: [],
+            blockedUsers: [],
             peerProfile: nil,
             activeCall: nil,
             callTimeline: [],
+            callReport: nil,
             toast: toast,
             developerMode: false,
+            readReceiptsEnabled: false,
+            autoAcceptFollowedInvites: true,
+            blockListSyncEnabled: false,
             updateRequired: false,
             agentButton: nil,
             agentProvisioning: nil,
             voiceRecording: nil,
-            mediaGallery: nil
+            mediaGallery: nil,
+            messageSearch: nil,
+            accounts: []
         )
     }
 
@@ -127,6 +143,35 @@ final class AppManagerTests: XCTestCase {
         XCTAssertEqual(observedRev, 5)
     }
 
+    func testSwitchAccountUsesStoredNsec() async {
+        let core = MockCore(state: makeState(rev: 1))
+        let store = MockAuthStore()
+        store.saveAccountNsec("nsec1other", pubkey: "pk-other")
+        let manager = await MainActor.run { AppManager(core: core, authStore: store) }
+
+        await MainActor.run {
+            manager.switchAccount(pubkey: "pk-other")
+            manager.switchAccount(pubkey: "pk-unknown")
+        }
+
+        XCTAssertEqual(core.dispatchedActions, [
+            .addAccount(nsec: "nsec1other"),
+            .switchAccount(pubkey: "pk-unknown"),
+        ])
+    }
+
+    func testAccountCreatedFilesNsecUnderAccount() async {
+        let core = MockCore(state: makeState(rev: 1))
+        let store = MockAuthStore()
+        let manager = await MainActor.run { AppManager(core: core, authStore: store) }
+
+        await MainActor.run {
+            manager.apply(update: .accountCreated(rev: 2, nsec: "nsec1new", pubkey: "pk", npub: "npub"))
+        }
+
+        XCTAssertEqual(store.accountNsec(pubkey: "pk"), "nsec1new")
+    }
+
     func testAgentButtonHiddenForBunkerAuthWithoutLocalNsec() async {
         var state = makeState(rev: 1)
         state.auth = .loggedIn(
@@ -246,6 +291,17 @@ final class ChatDeepLinkTests: XCTestCase {
 
         XCTAssertEqual(core.dispatchedActions, [.createChat(peerNpub: validNpub)])
     }
+
+    func testOnOpenURL_dispatchesJoinGroupViaInviteLink() async {
+        let core = MockCore(state: makeTestState(rev: 1))
+        let store = MockAuthStore()
+        let manager = await MainActor.run { AppManager(core: core, authStore: store) }
+
+        let link = "pika://join/\(validNpub)/\(String(repeating: "0", count: 32))"
+        await MainActor.run { manager.onOpenURL(URL(string: link)!) }
+
+        XCTAssertEqual(core.dispatchedActions, [.joinGroupViaInviteLink(link: link)])
+    }
 }
 
 final class MockCore: AppCore, @unchecked Sendable {
@@ -282,6 +338,7 @@ final class MockCore: AppCore, @unchecked Sendable {
 
 final class MockAuthStore: AuthStore {
     var stored: StoredAuth?
+    var accountNsecs: [String: String] = [:]
 
     init(stored: StoredAuth? = nil) {
         self.stored = stored
@@ -307,4 +364,16 @@ final class MockAuthStore: AuthStore {
     func clear() {
         stored = nil
     }
+
+    func saveAccountNsec(_ nsec: String, pubkey: String) {
+        accountNsecs[pubkey] = nsec
+    }
+
+    func accountNsec(pubkey: String) -> String? {
+        accountNsecs[pubkey]
+    }
+
+    func removeAccountNsec(pubkey: String) {
+        accountNsecs[pubkey] = nil
+    }
 }
@@ -68,13 +68,15 @@ export type PikachatDaemonOutMsg =
       sample_rate: number;
       channels: number;
     }
+  | { type: "call_transcript"; call_id: string; text: string }
   | {
       type: "group_created";
       nostr_group_id: string;
       mls_group_id: string;
       peer_pubkey: string;
       member_count: number;
-    };
+    }
+  | { type: "group_commit_rejected"; nostr_group_id: string; reason: string };
 
 export type PikachatDaemonInCmd =
   | { cmd: "publish_keypackage"; request_id: string; relays: string[] }
@@ -105,7 +107,13 @@ export type PikachatDaemonInCmd =
   | { cmd: "accept_call"; request_id: string; call_id: string }
   | { cmd: "reject_call"; request_id: string; call_id: string; reason?: string }
   | { cmd: "end_call"; request_id: string; call_id: string; reason?: string }
-  | { cmd: "send_audio_response"; request_id: string; call_id: string; tts_text: string }
+  | {
+      cmd: "send_audio_response";
+      request_id: string;
+      call_id: string;
+      tts_text: string;
+      tts_backend?: "openai" | "local" | "fixture";
+    }
   | {
       cmd: "send_audio_file";
       request_id: string;
@@ -115,6 +123,8 @@ export type PikachatDaemonInCmd =
       channels?: number;
     }
   | { cmd: "init_group"; request_id: string; peer_pubkey: string; group_name?: string }
+  | { cmd: "promote_admin"; request_id: string; nostr_group_id: string; pubkey: string }
+  | { cmd: "demote_admin"; request_id: string; nostr_group_id: string; pubkey: string }
   | {
       cmd: "send_media";
       request_id: string;
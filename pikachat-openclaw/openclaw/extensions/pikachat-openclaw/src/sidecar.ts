@@ -332,6 +332,7 @@ export class PikachatDaemonClient {
   async sendAudioResponse(
     callId: string,
     ttsText: string,
+    ttsBackend?: "openai" | "local" | "fixture",
   ): Promise<{
     call_id: string;
     frames_published: number;
@@ -345,6 +346,7 @@ export class PikachatDaemonClient {
       cmd: "send_audio_response",
       call_id: callId,
       tts_text: ttsText,
+      ...(ttsBackend ? { tts_backend: ttsBackend } : {}),
     } as any);
     const framesPublished = (result as any)?.frames_published;
     if (typeof framesPublished !== "number" || !Number.isFinite(framesPublished)) {
@@ -1,4 +1,4 @@
-use crate::state::Screen;
+use crate::state::{ChatNotificationMode, Screen};
 use std::collections::HashMap;
 
 #[derive(uniffi::Record, Debug, Clone)]
@@ -36,6 +36,18 @@ pub enum AppAction {
         client_nsec: String,
     },
     Logout,
+    /// Sign in another account and switch to it; the current one stays signed in.
+    AddAccount {
+        nsec: String,
+    },
+    /// Make another account from `AppState.accounts` the active one.
+    SwitchAccount {
+        pubkey: String,
+    },
+    /// Sign an account out of this device and delete its local data.
+    RemoveAccount {
+        pubkey: String,
+    },
     WipeLocalData,
     RefreshMyProfile,
     SaveMyProfile {
@@ -109,6 +121,23 @@ pub enum AppAction {
     EndCall,
     ToggleMute,
     ToggleCamera,
+    ToggleAudioProcessing,
+    StartScreenShare,
+    StopScreenShare,
+    ToggleCallRecording,
+    /// Posts the ended call's captions to its chat as one message.
+    SaveCallCaptions {
+        call_id: String,
+    },
+
+    // Invitations
+    /// Join the group behind an entry of `AppState.invitations`.
+    AcceptInvite {
+        invite_id: String,
+    },
+    DeclineInvite {
+        invite_id: String,
+    },
 
     // Group chat
     CreateGroupChat {
@@ -130,6 +159,36 @@ pub enum AppAction {
         chat_id: String,
         name: String,
     },
+    PromoteAdmin {
+        chat_id: String,
+        pubkey: String,
+    },
+    DemoteAdmin {
+        chat_id: String,
+        pubkey: String,
+    },
+    /// Mint a revocable invite link; it shows up in `ChatViewState.invite_links`.
+    CreateGroupInviteLink {
+        chat_id: String,
+        multi_use: bool,
+        requires_approval: bool,
+    },
+    RevokeGroupInviteLink {
+        chat_id: String,
+        token: String,
+    },
+    /// Ask the admin behind a `<scheme>://join/<npub>/<token>` link to add us.
+    JoinGroupViaInviteLink {
+        link: String,
+    },
+    ApproveJoinRequest {
+        chat_id: String,
+        pubkey: String,
+    },
+    DeclineJoinRequest {
+        chat_id: String,
+        pubkey: String,
+    },
     SaveGroupProfile {
         chat_id: String,
         name: String,
@@ -163,6 +222,24 @@ pub enum AppAction {
         message_id: String,
         emoji: String,
     },
+    EditMessage {
+        chat_id: String,
+        message_id: String,
+        content: String,
+    },
+    DeleteMessage {
+        chat_id: String,
+        message_id: String,
+    },
+    /// `None` (or zero) turns disappearing messages off for the chat.
+    SetDisappearingTimer {
+        chat_id: String,
+        expire_after_secs: Option<u64>,
+    },
+    SetChatNotificationMode {
+        chat_id: String,
+        mode: ChatNotificationMode,
+    },
     TypingStarted {
         chat_id: String,
     },
@@ -170,6 +247,25 @@ pub enum AppAction {
     // UI
     ClearToast,
     EnableDeveloperMode,
+    SetReadReceiptsEnabled {
+        enabled: bool,
+    },
+    SetAutoAcceptFollowedInvites {
+        enabled: bool,
+    },
+    SetBlockListSyncEnabled {
+        enabled: bool,
+    },
+    /// Write a passphrase-encrypted backup of the logged-in account to `path`.
+    ExportBackup {
+        path: String,
+        passphrase: String,
+    },
+    /// Restore a backup made by `ExportBackup` into the logged-in account.
+    ImportBackup {
+        path: String,
+        passphrase: String,
+    },
     WipeProfileCache,
     VoiceRecordingStart,
     VoiceRecordingPause,
@@ -195,8 +291,27 @@ pub enum AppAction {
         chat_id: String,
     },
     ClearMediaGallery,
+    /// Decrypts a finished call recording so its gallery item gets a `local_path`.
+    OpenCallRecording {
+        chat_id: String,
+        original_hash_hex: String,
+    },
     WipeMediaCache,
 
+    // Call quality
+    /// Loads a call's quality samples and summary into `AppState.call_report`.
+    LoadCallReport {
+        call_id: String,
+    },
+    ClearCallReport,
+
+    // Message search
+    SearchMessages {
+        query: String,
+        chat_id: Option<String>,
+    },
+    ClearMessageSearch,
+
     // Peer profile
     OpenPeerProfile {
         pubkey: String,
@@ -218,6 +333,14 @@ pub enum AppAction {
         pubkey: String,
     },
 
+    // Block list
+    BlockUser {
+        pubkey: String,
+    },
+    UnblockUser {
+        pubkey: String,
+    },
+
     // Agent
     EnsureAgent,
 }
@@ -237,6 +360,9 @@ impl AppAction {
             AppAction::RestoreSessionExternalSigner { .. } => "RestoreSessionExternalSigner",
             AppAction::RestoreSessionBunker { .. } => "RestoreSessionBunker",
             AppAction::Logout => "Logout",
+            AppAction::AddAccount { .. } => "AddAccount",
+            AppAction::SwitchAccount { .. } => "SwitchAccount",
+            AppAction::RemoveAccount { .. } => "RemoveAccount",
             AppAction::WipeLocalData => "WipeLocalData",
             AppAction::RefreshMyProfile => "RefreshMyProfile",
             AppAction::SaveMyProfile { .. } => "SaveMyProfile",
@@ -262,13 +388,27 @@ impl AppAction {
             AppAction::EndCall => "EndCall",
             AppAction::ToggleMute => "ToggleMute",
             AppAction::ToggleCamera => "ToggleCamera",
+            AppAction::ToggleAudioProcessing => "ToggleAudioProcessing",
+            AppAction::StartScreenShare => "StartScreenShare",
+            AppAction::StopScreenShare => "StopScreenShare",
+            AppAction::ToggleCallRecording => "ToggleCallRecording",
+            AppAction::SaveCallCaptions { .. } => "SaveCallCaptions",
 
             // Group chat
+            AppAction::AcceptInvite { .. } => "AcceptInvite",
+            AppAction::DeclineInvite { .. } => "DeclineInvite",
             AppAction::CreateGroupChat { .. } => "CreateGroupChat",
             AppAction::AddGroupMembers { .. } => "AddGroupMembers",
             AppAction::RemoveGroupMembers { .. } => "RemoveGroupMembers",
             AppAction::LeaveGroup { .. } => "LeaveGroup",
             AppAction::RenameGroup { .. } => "RenameGroup",
+            AppAction::PromoteAdmin { .. } => "PromoteAdmin",
+            AppAction::DemoteAdmin { .. } => "DemoteAdmin",
+            AppAction::CreateGroupInviteLink { .. } => "CreateGroupInviteLink",
+            AppAction::RevokeGroupInviteLink { .. } => "RevokeGroupInviteLink",
+            AppAction::JoinGroupViaInviteLink { .. } => "JoinGroupViaInviteLink",
+            AppAction::ApproveJoinRequest { .. } => "ApproveJoinRequest",
+            AppAction::DeclineJoinRequest { .. } => "DeclineJoinRequest",
             AppAction::SaveGroupProfile { .. } => "SaveGroupProfile",
             AppAction::UploadGroupProfileImage { .. } => "UploadGroupProfileImage",
 
@@ -279,11 +419,20 @@ impl AppAction {
             // Chat management
             AppAction::ArchiveChat { .. } => "ArchiveChat",
             AppAction::ReactToMessage { .. } => "ReactToMessage",
+            AppAction::EditMessage { .. } => "EditMessage",
+            AppAction::DeleteMessage { .. } => "DeleteMessage",
+            AppAction::SetDisappearingTimer { .. } => "SetDisappearingTimer",
+            AppAction::SetChatNotificationMode { .. } => "SetChatNotificationMode",
             AppAction::TypingStarted { .. } => "TypingStarted",
 
             // UI
             AppAction::ClearToast => "ClearToast",
             AppAction::EnableDeveloperMode => "EnableDeveloperMode",
+            AppAction::SetReadReceiptsEnabled { .. } => "SetReadReceiptsEnabled",
+            AppAction::SetAutoAcceptFollowedInvites { .. } => "SetAutoAcceptFollowedInvites",
+            AppAction::SetBlockListSyncEnabled { .. } => "SetBlockListSyncEnabled",
+            AppAction::ExportBackup { .. } => "ExportBackup",
+            AppAction::ImportBackup { .. } => "ImportBackup",
             AppAction::WipeProfileCache => "WipeProfileCache",
             AppAction::VoiceRecordingStart => "VoiceRecordingStart",
             AppAction::VoiceRecordingPause => "VoiceRecordingPause",
@@ -301,6 +450,11 @@ impl AppAction {
             // Media gallery
             AppAction::LoadMediaGallery { .. } => "LoadMediaGallery",
             AppAction::ClearMediaGallery => "ClearMediaGallery",
+            AppAction::LoadCallReport { .. } => "LoadCallReport",
+            AppAction::ClearCallReport => "ClearCallReport",
+            AppAction::OpenCallRecording { .. } => "OpenCallRecording",
+            AppAction::SearchMessages { .. } => "SearchMessages",
+            AppAction::ClearMessageSearch => "ClearMessageSearch",
             AppAction::WipeMediaCache => "WipeMediaCache",
 
             // Peer profile
@@ -315,6 +469,8 @@ impl AppAction {
             AppAction::RefreshFollowList => "RefreshFollowList",
             AppAction::FollowUser { .. } => "FollowUser",
             AppAction::UnfollowUser { .. } => "UnfollowUser",
+            AppAction::BlockUser { .. } => "BlockUser",
+            AppAction::UnblockUser { .. } => "UnblockUser",
             AppAction::EnsureAgent => "EnsureAgent",
         }
     }
@@ -0,0 +1,618 @@
+// Multiple accounts: a registry of every account signed in on this device,
+// per-account storage dirs, and background watchers for the inactive ones.
+//
+// Secrets never touch the registry (spec-v2: native stores nsecs). Signers
+// are kept in memory for accounts used during this run, so switching back to
+// one doesn't need native to hand its credentials over again.
+
+use std::path::{Path, PathBuf};
+
+use super::backup::remove_sqlite_files;
+use super::host_context::runtime_for_mdk;
+use super::*;
+use crate::state::AccountSummary;
+use pika_marmot_runtime::runtime::{connect_runtime_relays, subscribe_group_messages_combined};
+
+const ACCOUNTS_FILE: &str = "accounts.json";
+const ACCOUNTS_DIR: &str = "accounts";
+
+/// Per-account JSON state kept next to the account's databases.
+pub(super) const ACCOUNT_JSON_FILES: [&str; 4] = [
+    "archived_chats.json",
+    "call_timeline.json",
+    "disappearing_timers.json",
+    "read_receipts.json",
+];
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(super) struct AccountRegistry {
+    active: Option<String>,
+    accounts: Vec<StoredAccount>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct StoredAccount {
+    pubkey: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    picture_url: Option<String>,
+    /// Unread counts per chat while the account isn't active.
+    #[serde(default)]
+    unread_counts: HashMap<String, u32>,
+    /// Chats as of the last time the account was looked at, for push.
+    #[serde(default)]
+    chat_ids: Vec<String>,
+    /// Chats muted for good, which stay unsubscribed from push.
+    #[serde(default)]
+    muted_chat_ids: Vec<String>,
+}
+
+impl AccountRegistry {
+    fn get_mut(&mut self, pubkey_hex: &str) -> Option<&mut StoredAccount> {
+        self.accounts.iter_mut().find(|a| a.pubkey == pubkey_hex)
+    }
+
+    fn contains(&self, pubkey_hex: &str) -> bool {
+        self.accounts.iter().any(|a| a.pubkey == pubkey_hex)
+    }
+}
+
+/// Credentials for an account signed in during this run.
+pub(super) struct AccountSigner {
+    signer: Arc<dyn NostrSigner>,
+    local_keys: Option<Keys>,
+    auth_mode: SessionAuthMode,
+}
+
+/// An inactive account's MLS state, fed by its own relay subscription so
+/// unread counts keep moving. Welcomes need the account's signer, so new
+/// groups only show up once the account is active again.
+pub(super) struct BackgroundAccount {
+    mdk: PikaMdk,
+    client: Option<Client>,
+    alive: Arc<AtomicBool>,
+}
+
+pub(super) fn load_registry(data_dir: &str) -> AccountRegistry {
+    std::fs::read_to_string(Path::new(data_dir).join(ACCOUNTS_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+pub(super) fn account_dir_path(data_dir: &str, pubkey_hex: &str) -> PathBuf {
+    Path::new(data_dir).join(ACCOUNTS_DIR).join(pubkey_hex)
+}
+
+/// Storage dir of the account that was active last; the data dir itself when
+/// no account is signed in.
+pub(super) fn active_account_dir(data_dir: &str, registry: &AccountRegistry) -> String {
+    match &registry.active {
+        Some(pubkey_hex) => account_dir_path(data_dir, pubkey_hex)
+            .to_string_lossy()
+            .into_owned(),
+        None => data_dir.to_string(),
+    }
+}
+
+/// Before accounts had their own dirs, their files lived in the data dir root.
+/// The first account signed in after upgrading takes them over.
+fn migrate_legacy_files(root: &Path, dir: &Path) {
+    let mut files: Vec<String> = Vec::new();
+    for db in [
+        profile_db::PROFILE_DB_FILE,
+        chat_media_db::CHAT_MEDIA_DB_FILE,
+    ] {
+        for suffix in ["", "-wal", "-shm"] {
+            files.push(format!("{db}{suffix}"));
+        }
+    }
+    files.extend(ACCOUNT_JSON_FILES.iter().map(|f| f.to_string()));
+    for file in files {
+        if let Err(e) = std::fs::rename(root.join(&file), dir.join(&file)) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(%e, %file, "accounts: failed to migrate legacy file");
+            }
+        }
+    }
+}
+
+impl AppCore {
+    fn save_accounts(&self) {
+        let path = Path::new(&self.data_dir).join(ACCOUNTS_FILE);
+        let tmp_path = path.with_extension("json.tmp");
+        if let Ok(json) = serde_json::to_string(&self.accounts) {
+            if std::fs::write(&tmp_path, &json).is_ok() {
+                let _ = std::fs::rename(&tmp_path, &path);
+            }
+        }
+    }
+
+    pub(super) fn remember_account_signer(
+        &mut self,
+        pubkey_hex: &str,
+        signer: Arc<dyn NostrSigner>,
+        local_keys: Option<Keys>,
+        auth_mode: SessionAuthMode,
+    ) {
+        self.account_signers.insert(
+            pubkey_hex.to_string(),
+            AccountSigner {
+                signer,
+                local_keys,
+                auth_mode,
+            },
+        );
+    }
+
+    /// Snapshot the active account into the registry before its session is
+    /// torn down for another one.
+    pub(super) fn park_active_account(&mut self) {
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let pubkey_hex = sess.pubkey.to_hex();
+        let chat_ids: Vec<String> = sess.groups.keys().cloned().collect();
+        let muted_chat_ids: Vec<String> = self.muted_chat_ids().cloned().collect();
+        let unread_counts = std::mem::take(&mut self.unread_counts);
+        let profile = &self.state.my_profile;
+        let name = Some(profile.name.trim().to_string()).filter(|n| !n.is_empty());
+        let picture_url = profile.picture_url.clone();
+        let Some(entry) = self.accounts.get_mut(&pubkey_hex) else {
+            return;
+        };
+        entry.name = name;
+        entry.picture_url = picture_url;
+        entry.unread_counts = unread_counts;
+        entry.chat_ids = chat_ids;
+        entry.muted_chat_ids = muted_chat_ids;
+        self.save_accounts();
+    }
+
+    /// Register `pubkey_hex` if needed, make it the active account and point
+    /// the per-account stores at its dir.
+    pub(super) fn activate_account(&mut self, pubkey_hex: &str) {
+        let first_account = self.accounts.accounts.is_empty();
+        if !self.accounts.contains(pubkey_hex) {
+            self.accounts.accounts.push(StoredAccount {
+                pubkey: pubkey_hex.to_string(),
+                ..Default::default()
+            });
+        }
+        self.accounts.active = Some(pubkey_hex.to_string());
+
+        let dir = account_dir_path(&self.data_dir, pubkey_hex);
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            tracing::warn!(%e, path = %dir.display(), "accounts: failed to create account dir");
+        }
+        let dir = dir.to_string_lossy().into_owned();
+        if dir != self.account_dir {
+            self.profile_db = None;
+            self.chat_media_db = None;
+            self.call_quality_db = None;
+            if first_account && self.account_dir == self.data_dir {
+                migrate_legacy_files(Path::new(&self.data_dir), Path::new(&dir));
+            }
+            self.account_dir = dir;
+            self.open_account_stores();
+        }
+
+        if let Some(entry) = self.accounts.get_mut(pubkey_hex) {
+            self.unread_counts = std::mem::take(&mut entry.unread_counts);
+        }
+        self.save_accounts();
+    }
+
+    /// (Re)open the per-account stores in `account_dir` and drop in-memory
+    /// state that belonged to whichever account used them before.
+    pub(super) fn open_account_stores(&mut self) {
+        self.profile_db = match profile_db::open_profile_db(&self.account_dir) {
+            Ok(conn) => Some(conn),
+            Err(e) => {
+                tracing::warn!(%e, "failed to open profile cache db");
+                None
+            }
+        };
+        self.chat_media_db = match chat_media_db::open_chat_media_db(&self.account_dir) {
+            Ok(conn) => Some(conn),
+            Err(e) => {
+                tracing::warn!(%e, "failed to open chat media db");
+                None
+            }
+        };
+        self.call_quality_db = call_quality::open_call_quality_db(&self.account_dir);
+        self.call_quality = None;
+        self.profiles = self
+            .profile_db
+            .as_ref()
+            .map(profile_db::load_profiles)
+            .unwrap_or_default();
+        self.pending_sends = PendingSends::load(self.profile_db.as_ref());
+        self.failed_sends = FailedSends::load(self.profile_db.as_ref());
+        self.state.developer_mode = self
+            .profile_db
+            .as_ref()
+            .map(profile_db::load_developer_mode)
+            .unwrap_or(false);
+        self.state.read_receipts_enabled = self
+            .profile_db
+            .as_ref()
+            .map(profile_db::load_read_receipts)
+            .unwrap_or(false);
+        self.state.auto_accept_followed_invites = self
+            .profile_db
+            .as_ref()
+            .map(profile_db::load_auto_accept_followed_invites)
+            .unwrap_or(true);
+        self.load_block_list();
+        self.media_cache.clear();
+        self.local_path_cache.clear();
+        self.voice_waveform_requests.clear();
+        self.archived_chats.clear();
+        self.loaded_count.clear();
+        self.unread_counts.clear();
+        self.delivery_overrides.clear();
+        self.local_outbox.clear();
+        self.typing_state.clear();
+        self.last_typing_sent.clear();
+        self.pending_media_sends.clear();
+        self.pending_media_batch_sends.clear();
+        self.pending_media_downloads.clear();
+        self.last_outgoing_ts = 0;
+        self.state.current_chat = None;
+        self.state.chat_list = vec![];
+        self.state.invitations = vec![];
+        self.state.follow_list = vec![];
+        self.state.peer_profile = None;
+        self.state.media_gallery = None;
+        self.state.call_report = None;
+    }
+
+    pub(super) fn switch_account(&mut self, pubkey: String) {
+        let pubkey = match PublicKey::parse(pubkey.trim()) {
+            Ok(pk) => pk,
+            Err(e) => {
+                self.toast(format!("Invalid account: {e}"));
+                return;
+            }
+        };
+        let pubkey_hex = pubkey.to_hex();
+        if self.session.as_ref().map(|s| s.pubkey) == Some(pubkey) {
+            return;
+        }
+        if !self.accounts.contains(&pubkey_hex) {
+            self.toast("Account not found");
+            return;
+        }
+        let Some(account) = self.account_signers.get(&pubkey_hex) else {
+            self.toast("Sign in to this account again to switch to it");
+            return;
+        };
+        let signer = account.signer.clone();
+        let local_keys = account.local_keys.clone();
+        let auth_mode = account.auth_mode.clone();
+        if let Err(e) = self.start_session_with_signer(pubkey, signer, local_keys, auth_mode) {
+            self.toast(format!("Switch failed: {e:#}"));
+        }
+    }
+
+    pub(super) fn remove_account(&mut self, pubkey: String) {
+        let pubkey_hex = match PublicKey::parse(pubkey.trim()) {
+            Ok(pk) => pk.to_hex(),
+            Err(e) => {
+                self.toast(format!("Invalid account: {e}"));
+                return;
+            }
+        };
+        if self.session.as_ref().map(|s| s.pubkey.to_hex()) == Some(pubkey_hex.clone()) {
+            self.logout_active_account();
+            return;
+        }
+        if !self.accounts.contains(&pubkey_hex) {
+            self.toast("Account not found");
+            return;
+        }
+        self.forget_account(&pubkey_hex);
+        self.sync_push_subscriptions();
+        self.refresh_account_summaries();
+        self.emit_state();
+    }
+
+    /// Sign the active account out and delete its local data. Another account
+    /// signed in during this run takes over; otherwise back to the login screen.
+    pub(super) fn logout_active_account(&mut self) {
+        self.clear_pending_nostr_connect_login();
+        let pubkey_hex = self.session.as_ref().map(|s| s.pubkey.to_hex());
+        let others_remain = self
+            .accounts
+            .accounts
+            .iter()
+            .any(|a| Some(&a.pubkey) != pubkey_hex.as_ref());
+        if !others_remain {
+            self.clear_push_subscriptions();
+        }
+        self.stop_session();
+        if let Some(pubkey_hex) = &pubkey_hex {
+            self.forget_account(pubkey_hex);
+        }
+        self.state.auth = AuthState::LoggedOut;
+        self.emit_auth();
+        self.handle_auth_transition(false);
+        // Drops the signed-out account's chats; other accounts keep theirs.
+        self.sync_push_subscriptions();
+
+        let next = self
+            .accounts
+            .accounts
+            .iter()
+            .find_map(|a| Some((a.pubkey.clone(), self.account_signers.get(&a.pubkey)?)))
+            .map(|(pubkey_hex, account)| {
+                (
+                    pubkey_hex,
+                    account.signer.clone(),
+                    account.local_keys.clone(),
+                    account.auth_mode.clone(),
+                )
+            });
+        if let Some((pubkey_hex, signer, local_keys, auth_mode)) = next {
+            if let Ok(pubkey) = PublicKey::from_hex(&pubkey_hex) {
+                if let Err(e) =
+                    self.start_session_with_signer(pubkey, signer, local_keys, auth_mode)
+                {
+                    self.toast(format!("Switch failed: {e:#}"));
+                }
+            }
+        }
+        self.sync_background_accounts();
+        self.refresh_account_summaries();
+        self.emit_state();
+    }
+
+    /// Drop an account from this device: registry entry, signer, background
+    /// watcher, MLS state and its storage dir.
+    fn forget_account(&mut self, pubkey_hex: &str) {
+        self.stop_background_account(pubkey_hex);
+        self.account_signers.remove(pubkey_hex);
+        self.accounts.accounts.retain(|a| a.pubkey != pubkey_hex);
+
+        let dir = account_dir_path(&self.data_dir, pubkey_hex);
+        let was_active = self.accounts.active.as_deref() == Some(pubkey_hex);
+        if was_active {
+            self.accounts.active = None;
+            self.profile_db = None;
+            self.chat_media_db = None;
+            self.call_quality_db = None;
+        }
+        remove_sqlite_files(&crate::mdk_support::mdk_db_path(&self.data_dir, pubkey_hex));
+        remove_sqlite_files(&crate::mdk_support::search_db_path(
+            &self.data_dir,
+            pubkey_hex,
+        ));
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(%e, path = %dir.display(), "accounts: failed to delete account dir");
+            }
+        }
+        if was_active {
+            self.account_dir = self.data_dir.clone();
+            self.open_account_stores();
+        }
+        self.save_accounts();
+    }
+
+    /// Forget every account without touching disk; used after a local wipe.
+    pub(super) fn reset_accounts(&mut self) {
+        let watched: Vec<String> = self.background_accounts.keys().cloned().collect();
+        for pubkey_hex in watched {
+            self.stop_background_account(&pubkey_hex);
+        }
+        self.accounts = AccountRegistry::default();
+        self.account_signers.clear();
+        self.account_dir = self.data_dir.clone();
+        self.state.accounts = vec![];
+    }
+
+    pub(super) fn has_accounts(&self) -> bool {
+        !self.accounts.accounts.is_empty()
+    }
+
+    /// Chats of inactive accounts that should stay subscribed for push.
+    pub(super) fn inactive_account_chat_ids(&self) -> impl Iterator<Item = &String> {
+        let active = self.accounts.active.as_deref();
+        self.accounts
+            .accounts
+            .iter()
+            .filter(move |a| Some(a.pubkey.as_str()) != active)
+            .flat_map(|a| {
+                a.chat_ids
+                    .iter()
+                    .filter(|id| !a.muted_chat_ids.contains(id))
+            })
+    }
+
+    pub(super) fn refresh_account_summaries(&mut self) {
+        let active = self.session.as_ref().map(|s| s.pubkey.to_hex());
+        self.state.accounts = self
+            .accounts
+            .accounts
+            .iter()
+            .map(|a| {
+                let is_active = active.as_deref() == Some(a.pubkey.as_str());
+                let (name, picture_url, unread_count): (Option<String>, Option<String>, u32) =
+                    if is_active {
+                        let profile = &self.state.my_profile;
+                        (
+                            Some(profile.name.trim().to_string()).filter(|n| !n.is_empty()),
+                            profile.picture_url.clone(),
+                            self.unread_counts.values().sum(),
+                        )
+                    } else {
+                        (
+                            a.name.clone(),
+                            a.picture_url.clone(),
+                            a.unread_counts.values().sum(),
+                        )
+                    };
+                AccountSummary {
+                    npub: PublicKey::from_hex(&a.pubkey)
+                        .ok()
+                        .and_then(|pk| pk.to_bech32().ok())
+                        .unwrap_or_else(|| a.pubkey.clone()),
+                    pubkey: a.pubkey.clone(),
+                    name,
+                    picture_url,
+                    unread_count,
+                    is_active,
+                }
+            })
+            .collect();
+    }
+
+    /// Start watchers for inactive accounts and stop the ones that no longer
+    /// need one (removed, or now active).
+    pub(super) fn sync_background_accounts(&mut self) {
+        let active = self.session.as_ref().map(|s| s.pubkey.to_hex());
+        let wanted: HashSet<String> = match active {
+            Some(active) => self
+                .accounts
+                .accounts
+                .iter()
+                .map(|a| a.pubkey.clone())
+                .filter(|pk| *pk != active)
+                .collect(),
+            None => HashSet::new(),
+        };
+        let stale: Vec<String> = self
+            .background_accounts
+            .keys()
+            .filter(|pk| !wanted.contains(*pk))
+            .cloned()
+            .collect();
+        for pubkey_hex in stale {
+            self.stop_background_account(&pubkey_hex);
+        }
+        for pubkey_hex in wanted {
+            if !self.background_accounts.contains_key(&pubkey_hex) {
+                self.start_background_account(&pubkey_hex);
+            }
+        }
+    }
+
+    fn start_background_account(&mut self, pubkey_hex: &str) {
+        let Ok(pubkey) = PublicKey::from_hex(pubkey_hex) else {
+            return;
+        };
+        let mdk = match open_mdk(&self.data_dir, &pubkey, &self.keychain_group) {
+            Ok(mdk) => mdk,
+            Err(e) => {
+                tracing::warn!(%e, account = %pubkey_hex, "accounts: failed to open background mdk");
+                return;
+            }
+        };
+
+        let mut relays: BTreeSet<RelayUrl> = self.all_session_relays().into_iter().collect();
+        let mut h_values: Vec<String> = Vec::new();
+        for group in mdk.get_groups().unwrap_or_default() {
+            if let Ok(set) = mdk.get_relays(&group.mls_group_id) {
+                relays.extend(set);
+            }
+            h_values.push(hex::encode(group.nostr_group_id));
+        }
+        if let Some(entry) = self.accounts.get_mut(pubkey_hex) {
+            if entry.chat_ids != h_values {
+                entry.chat_ids = h_values.clone();
+                self.save_accounts();
+            }
+        }
+
+        let alive = Arc::new(AtomicBool::new(true));
+        let client = self.network_enabled().then(Client::default);
+        if let Some(client) = client.clone() {
+            let relays: Vec<RelayUrl> = relays.into_iter().collect();
+            let account_pubkey = pubkey_hex.to_string();
+            let tx = self.core_sender.clone();
+            let alive = alive.clone();
+            self.runtime.spawn(async move {
+                let mut rx = client.notifications();
+                connect_runtime_relays(&client, &relays, false, Some(Duration::from_secs(4)))
+                    .await;
+                if !alive.load(Ordering::SeqCst) {
+                    return;
+                }
+                if let Err(e) = subscribe_group_messages_combined(&client, &h_values).await {
+                    tracing::warn!(%e, account = %account_pubkey, "accounts: background subscribe failed");
+                    return;
+                }
+                let mut seen: HashSet<EventId> = HashSet::new();
+                while alive.load(Ordering::SeqCst) {
+                    match rx.recv().await {
+                        Ok(RelayPoolNotification::Event { event, .. }) => {
+                            if event.kind != Kind::MlsGroupMessage || !seen.insert(event.id) {
+                                continue;
+                            }
+                            let _ = tx.send(CoreMsg::Internal(Box::new(
+                                InternalEvent::BackgroundGroupMessageReceived {
+                                    account_pubkey: account_pubkey.clone(),
+                                    event: (*event).clone(),
+                                },
+                            )));
+                        }
+                        Ok(_) => {}
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+
+        self.background_accounts.insert(
+            pubkey_hex.to_string(),
+            BackgroundAccount { mdk, client, alive },
+        );
+    }
+
+    pub(super) fn stop_background_account(&mut self, pubkey_hex: &str) {
+        let Some(account) = self.background_accounts.remove(pubkey_hex) else {
+            return;
+        };
+        account.alive.store(false, Ordering::SeqCst);
+        if let Some(client) = account.client {
+            self.runtime.spawn(async move {
+                client.unsubscribe_all().await;
+                client.shutdown().await;
+            });
+        }
+    }
+
+    pub(super) fn handle_background_group_message(&mut self, account_pubkey: String, event: Event) {
+        let Some(account) = self.background_accounts.get(&account_pubkey) else {
+            return;
+        };
+        let outcome = match runtime_for_mdk(&account.mdk).process_event(&event) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                tracing::debug!(%e, account = %account_pubkey, "accounts: background message not processed");
+                return;
+            }
+        };
+        let Some(ConversationEvent::Application(message)) = outcome else {
+            return;
+        };
+        if !message.classification.increments_unread()
+            || message.message.pubkey.to_hex() == account_pubkey
+        {
+            return;
+        }
+        let Some(entry) = self.accounts.get_mut(&account_pubkey) else {
+            return;
+        };
+        *entry
+            .unread_counts
+            .entry(message.nostr_group_id_hex)
+            .or_insert(0) += 1;
+        self.save_accounts();
+        self.refresh_account_summaries();
+        self.emit_state();
+    }
+}
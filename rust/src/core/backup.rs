@@ -0,0 +1,231 @@
+// Account backup/restore: the account's MLS state, local caches and per-chat
+// settings packed into one passphrase-encrypted archive (see backup_archive).
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail};
+
+use super::accounts::ACCOUNT_JSON_FILES;
+use super::backup_archive::{self, ArchiveEntries};
+use super::*;
+
+const MDK_DB_ENTRY: &str = "mdk.sqlite3";
+const MDK_WAL_ENTRY: &str = "mdk.sqlite3-wal";
+const MDK_KEY_ENTRY: &str = "mdk.key";
+
+fn sidecar_path(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Remove a SQLite database together with its WAL/SHM files so a restored copy
+/// isn't replayed against a stale journal.
+pub(super) fn remove_sqlite_files(db_path: &Path) {
+    for path in [
+        db_path.to_path_buf(),
+        sidecar_path(db_path, "-wal"),
+        sidecar_path(db_path, "-shm"),
+    ] {
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(%e, path = %path.display(), "backup: failed to remove db file");
+            }
+        }
+    }
+}
+
+fn session_auth_mode(mode: &AuthMode) -> SessionAuthMode {
+    match mode {
+        AuthMode::LocalNsec => SessionAuthMode::LocalNsec,
+        AuthMode::ExternalSigner {
+            signer_package,
+            current_user,
+            ..
+        } => SessionAuthMode::ExternalSigner {
+            signer_package: signer_package.clone(),
+            current_user: current_user.clone(),
+        },
+        AuthMode::BunkerSigner { bunker_uri } => SessionAuthMode::BunkerSigner {
+            bunker_uri: bunker_uri.clone(),
+        },
+    }
+}
+
+impl AppCore {
+    pub(super) fn export_backup(&mut self, path: String, passphrase: String) {
+        match self.write_backup(Path::new(&path), &passphrase) {
+            Ok(()) => self.toast("Backup saved"),
+            Err(e) => {
+                tracing::warn!(err = %format!("{e:#}"), "backup export failed");
+                self.toast(format!("Backup failed: {e:#}"));
+            }
+        }
+    }
+
+    pub(super) fn import_backup(&mut self, path: String, passphrase: String) {
+        match self.restore_backup(Path::new(&path), &passphrase) {
+            Ok(()) => self.toast("Backup restored"),
+            Err(e) => {
+                tracing::warn!(err = %format!("{e:#}"), "backup import failed");
+                self.toast(format!("Restore failed: {e:#}"));
+            }
+        }
+    }
+
+    fn write_backup(&self, path: &Path, passphrase: &str) -> anyhow::Result<()> {
+        if passphrase.is_empty() {
+            bail!("a passphrase is required");
+        }
+        let Some(sess) = self.session.as_ref() else {
+            bail!("not logged in");
+        };
+        let pubkey_hex = sess.pubkey.to_hex();
+        let root = Path::new(&self.account_dir);
+        let mut entries = ArchiveEntries::new();
+
+        // MDK keeps its connection private, so copy the database and its WAL
+        // as-is. All writes go through this actor, so nothing lands in between.
+        let mdk_path = crate::mdk_support::mdk_db_path(&self.data_dir, &pubkey_hex);
+        entries.insert(
+            MDK_DB_ENTRY.to_string(),
+            std::fs::read(&mdk_path).context("read mls database")?,
+        );
+        if let Ok(wal) = std::fs::read(sidecar_path(&mdk_path, "-wal")) {
+            entries.insert(MDK_WAL_ENTRY.to_string(), wal);
+        }
+        let mdk_key =
+            crate::mdk_support::mdk_db_key(&self.data_dir, &pubkey_hex, &self.keychain_group)
+                .context("load mls database key")?;
+        entries.insert(MDK_KEY_ENTRY.to_string(), mdk_key.to_vec());
+
+        for (conn, file) in [
+            (self.profile_db.as_ref(), profile_db::PROFILE_DB_FILE),
+            (
+                self.chat_media_db.as_ref(),
+                chat_media_db::CHAT_MEDIA_DB_FILE,
+            ),
+        ] {
+            if let Some(conn) = conn {
+                conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+                    .with_context(|| format!("checkpoint {file}"))?;
+            }
+            if let Ok(bytes) = std::fs::read(root.join(file)) {
+                entries.insert(file.to_string(), bytes);
+            }
+        }
+        for file in ACCOUNT_JSON_FILES {
+            if let Ok(bytes) = std::fs::read(root.join(file)) {
+                entries.insert(file.to_string(), bytes);
+            }
+        }
+
+        let sealed = backup_archive::seal(&pubkey_hex, &entries, passphrase, now_seconds())?;
+        let tmp = sidecar_path(path, ".tmp");
+        std::fs::write(&tmp, sealed).context("write backup")?;
+        std::fs::rename(&tmp, path).context("write backup")?;
+        Ok(())
+    }
+
+    /// Replace this account's local state with a backup. Only restores into the
+    /// account that made the backup; the session is restarted on the restored data.
+    fn restore_backup(&mut self, path: &Path, passphrase: &str) -> anyhow::Result<()> {
+        let Some(sess) = self.session.as_ref() else {
+            bail!("log in to the backed up account first");
+        };
+        let pubkey = sess.pubkey;
+        let pubkey_hex = pubkey.to_hex();
+        let archive = std::fs::read(path).context("read backup")?;
+        let entries = backup_archive::open(&archive, passphrase, &pubkey_hex)?;
+        if !entries.contains_key(MDK_DB_ENTRY) {
+            bail!("backup is missing the mls database");
+        }
+        let mdk_key: [u8; 32] = entries
+            .get(MDK_KEY_ENTRY)
+            .and_then(|key| key.as_slice().try_into().ok())
+            .ok_or_else(|| anyhow!("backup is missing the mls database key"))?;
+
+        // Capture what's needed to log back in before tearing the session down.
+        let local_keys = sess.local_keys.clone();
+        let signer: Arc<dyn NostrSigner> = match local_keys.clone() {
+            Some(keys) => Arc::new(keys),
+            None => {
+                let client = sess.client.clone();
+                self.runtime
+                    .block_on(async move { client.signer().await })
+                    .context("signer unavailable")?
+            }
+        };
+        let auth_mode = match &self.state.auth {
+            AuthState::LoggedIn { mode, .. } => session_auth_mode(mode),
+            AuthState::LoggedOut => SessionAuthMode::LocalNsec,
+        };
+
+        self.stop_session();
+        self.profile_db = None;
+        self.chat_media_db = None;
+        let written = self.install_backup_files(&pubkey_hex, &entries, &mdk_key);
+        self.open_account_stores();
+        // Come back up even if installing failed part-way, so the user isn't
+        // left logged out; the error is still reported.
+        let started = self.start_session_with_signer(pubkey, signer, local_keys, auth_mode);
+        written.and(started)
+    }
+
+    fn install_backup_files(
+        &self,
+        pubkey_hex: &str,
+        entries: &ArchiveEntries,
+        mdk_key: &[u8; 32],
+    ) -> anyhow::Result<()> {
+        let root = Path::new(&self.account_dir);
+
+        let mdk_path = crate::mdk_support::mdk_db_path(&self.data_dir, pubkey_hex);
+        if let Some(parent) = mdk_path.parent() {
+            std::fs::create_dir_all(parent).context("create mls dir")?;
+        }
+        remove_sqlite_files(&mdk_path);
+        if let Some(bytes) = entries.get(MDK_DB_ENTRY) {
+            std::fs::write(&mdk_path, bytes).context("write mls database")?;
+        }
+        if let Some(bytes) = entries.get(MDK_WAL_ENTRY) {
+            std::fs::write(sidecar_path(&mdk_path, "-wal"), bytes).context("write mls wal")?;
+        }
+        crate::mdk_support::set_mdk_db_key(
+            &self.data_dir,
+            pubkey_hex,
+            &self.keychain_group,
+            mdk_key,
+        )
+        .context("store mls database key")?;
+
+        // The search index is derived from MLS storage; drop it so it's rebuilt.
+        remove_sqlite_files(&crate::mdk_support::search_db_path(
+            &self.data_dir,
+            pubkey_hex,
+        ));
+
+        for file in [
+            profile_db::PROFILE_DB_FILE,
+            chat_media_db::CHAT_MEDIA_DB_FILE,
+        ] {
+            let path = root.join(file);
+            remove_sqlite_files(&path);
+            if let Some(bytes) = entries.get(file) {
+                std::fs::write(&path, bytes).with_context(|| format!("write {file}"))?;
+            }
+        }
+        for file in ACCOUNT_JSON_FILES {
+            let path = root.join(file);
+            match entries.get(file) {
+                Some(bytes) => {
+                    std::fs::write(&path, bytes).with_context(|| format!("write {file}"))?
+                }
+                None => {
+                    let _ = std::fs::remove_file(&path);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Context};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+const MAGIC: &[u8; 8] = b"PIKABAK1";
+const FORMAT_VERSION: u32 = 1;
+// scrypt cost; tests use a cheap setting so round trips stay fast in debug builds.
+const KDF_LOG_N: u8 = if cfg!(test) { 8 } else { 16 };
+const KDF_R: u32 = 8;
+const KDF_P: u32 = 1;
+
+/// Cleartext header. It's authenticated as AAD, so the account check can run
+/// before the (slow) key derivation without trusting an attacker-edited pubkey.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ArchiveHeader {
+    version: u32,
+    pubkey: String,
+    created_at: i64,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: String,
+    nonce: String,
+}
+
+/// Named files, keyed by their archive name (not a filesystem path).
+pub(super) type ArchiveEntries = BTreeMap<String, Vec<u8>>;
+
+/// Encrypt `entries` for `pubkey_hex` under a key derived from `passphrase`.
+pub(super) fn seal(
+    pubkey_hex: &str,
+    entries: &ArchiveEntries,
+    passphrase: &str,
+    created_at: i64,
+) -> anyhow::Result<Vec<u8>> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; 24];
+    OsRng.fill_bytes(&mut nonce);
+
+    let header = ArchiveHeader {
+        version: FORMAT_VERSION,
+        pubkey: pubkey_hex.to_string(),
+        created_at,
+        log_n: KDF_LOG_N,
+        r: KDF_R,
+        p: KDF_P,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+    };
+    let header_json = serde_json::to_vec(&header).context("encode backup header")?;
+    let key = derive_key(passphrase, &header)?;
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &encode_entries(entries),
+                aad: &header_json,
+            },
+        )
+        .map_err(|_| anyhow!("encrypt backup"))?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + 4 + header_json.len() + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(header_json.len() as u32).to_le_bytes());
+    out.extend_from_slice(&header_json);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt an archive made by [`seal`]. Archives for any account other than
+/// `expected_pubkey_hex` are rejected before the passphrase is tried.
+pub(super) fn open(
+    archive: &[u8],
+    passphrase: &str,
+    expected_pubkey_hex: &str,
+) -> anyhow::Result<ArchiveEntries> {
+    let (header, header_json, ciphertext) = split_archive(archive)?;
+    if !header.pubkey.eq_ignore_ascii_case(expected_pubkey_hex) {
+        bail!("backup belongs to a different account");
+    }
+    let nonce = hex::decode(&header.nonce).context("decode backup nonce")?;
+    if nonce.len() != 24 {
+        bail!("invalid backup nonce");
+    }
+    let key = derive_key(passphrase, &header)?;
+    let plaintext = XChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header_json,
+            },
+        )
+        .map_err(|_| anyhow!("wrong passphrase or corrupted backup"))?;
+    decode_entries(&plaintext)
+}
+
+fn split_archive(archive: &[u8]) -> anyhow::Result<(ArchiveHeader, &[u8], &[u8])> {
+    let rest = archive
+        .strip_prefix(MAGIC.as_slice())
+        .ok_or_else(|| anyhow!("not a Pika backup"))?;
+    let (len, rest) = take_u32(rest).ok_or_else(|| anyhow!("truncated backup header"))?;
+    if rest.len() < len as usize {
+        bail!("truncated backup header");
+    }
+    let (header_json, ciphertext) = rest.split_at(len as usize);
+    let header: ArchiveHeader =
+        serde_json::from_slice(header_json).context("decode backup header")?;
+    if header.version != FORMAT_VERSION {
+        bail!("unsupported backup version {}", header.version);
+    }
+    // The header is only authenticated after the key is derived, so an edited
+    // cost would otherwise make us burn arbitrary CPU and memory first.
+    if (header.log_n, header.r, header.p) != (KDF_LOG_N, KDF_R, KDF_P) {
+        bail!("unsupported backup kdf parameters");
+    }
+    Ok((header, header_json, ciphertext))
+}
+
+fn derive_key(passphrase: &str, header: &ArchiveHeader) -> anyhow::Result<[u8; 32]> {
+    let salt = hex::decode(&header.salt).context("decode backup salt")?;
+    let params = scrypt::Params::new(header.log_n, header.r, header.p, 32)
+        .map_err(|e| anyhow!("invalid backup kdf params: {e}"))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key)
+        .map_err(|e| anyhow!("derive backup key: {e}"))?;
+    Ok(key)
+}
+
+// Entry encoding: repeated (u32 name_len, name, u64 data_len, data), little-endian.
+fn encode_entries(entries: &ArchiveEntries) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (name, data) in entries {
+        out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        out.extend_from_slice(data);
+    }
+    out
+}
+
+fn decode_entries(mut bytes: &[u8]) -> anyhow::Result<ArchiveEntries> {
+    let mut entries = ArchiveEntries::new();
+    while !bytes.is_empty() {
+        let (name_len, rest) = take_u32(bytes).ok_or_else(|| anyhow!("truncated backup"))?;
+        let (name, rest) =
+            take(rest, name_len as usize).ok_or_else(|| anyhow!("truncated backup"))?;
+        let name = std::str::from_utf8(name).context("backup entry name")?;
+        let (data_len, rest) = take_u64(rest).ok_or_else(|| anyhow!("truncated backup"))?;
+        let (data, rest) =
+            take(rest, data_len as usize).ok_or_else(|| anyhow!("truncated backup"))?;
+        entries.insert(name.to_string(), data.to_vec());
+        bytes = rest;
+    }
+    Ok(entries)
+}
+
+fn take(bytes: &[u8], len: usize) -> Option<(&[u8], &[u8])> {
+    (bytes.len() >= len).then(|| bytes.split_at(len))
+}
+
+fn take_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let (head, rest) = take(bytes, 4)?;
+    Some((u32::from_le_bytes(head.try_into().ok()?), rest))
+}
+
+fn take_u64(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let (head, rest) = take(bytes, 8)?;
+    Some((u64::from_le_bytes(head.try_into().ok()?), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBKEY: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    fn sample() -> ArchiveEntries {
+        let mut entries = ArchiveEntries::new();
+        entries.insert("mdk.sqlite3".to_string(), vec![1, 2, 3, 4]);
+        entries.insert("archived_chats.json".to_string(), b"[\"abc\"]".to_vec());
+        entries.insert("empty".to_string(), vec![]);
+        entries
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let sealed = seal(PUBKEY, &sample(), "correct horse", 1_700_000_000).unwrap();
+        assert_eq!(split_archive(&sealed).unwrap().0.pubkey, PUBKEY);
+        assert_eq!(open(&sealed, "correct horse", PUBKEY).unwrap(), sample());
+    }
+
+    #[test]
+    fn rejects_wrong_passphrase_and_other_accounts() {
+        let sealed = seal(PUBKEY, &sample(), "correct horse", 0).unwrap();
+        let err = open(&sealed, "battery staple", PUBKEY).unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+
+        let other = "bb".repeat(32);
+        let err = open(&sealed, "correct horse", &other).unwrap_err();
+        assert!(err.to_string().contains("different account"));
+    }
+
+    #[test]
+    fn header_is_authenticated() {
+        let mut sealed = seal(PUBKEY, &sample(), "pw", 0).unwrap();
+        let other = "bb".repeat(32);
+        // Rewrite the cleartext pubkey in place and try to open as the other account.
+        let at = sealed
+            .windows(PUBKEY.len())
+            .position(|w| w == PUBKEY.as_bytes())
+            .unwrap();
+        sealed[at..at + PUBKEY.len()].copy_from_slice(other.as_bytes());
+        assert_eq!(split_archive(&sealed).unwrap().0.pubkey, other);
+        let err = open(&sealed, "pw", &other).unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+
+        assert!(open(b"garbage", "pw", PUBKEY).is_err());
+        assert!(open(&sealed[..sealed.len() - 1], "pw", PUBKEY).is_err());
+    }
+
+    #[test]
+    fn rejects_edited_kdf_cost_before_deriving_key() {
+        let sealed = seal(PUBKEY, &sample(), "pw", 0).unwrap();
+        let (mut header, header_json, ciphertext) = split_archive(&sealed).unwrap();
+        header.log_n = 40;
+        header.r = u32::MAX;
+        let edited_json = serde_json::to_vec(&header).unwrap();
+        assert_ne!(edited_json, header_json);
+
+        let mut edited = MAGIC.to_vec();
+        edited.extend_from_slice(&(edited_json.len() as u32).to_le_bytes());
+        edited.extend_from_slice(&edited_json);
+        edited.extend_from_slice(ciphertext);
+        let err = open(&edited, "pw", PUBKEY).unwrap_err();
+        assert!(err.to_string().contains("kdf parameters"));
+    }
+}
@@ -0,0 +1,463 @@
+//! Block list. Blocked pubkeys live in `profile_db`; their welcomes are
+//! dropped, their messages hidden and their typing and call signals ignored.
+//! With `block_list_sync_enabled` on, the list is mirrored into the private
+//! (NIP-44 encrypted) half of our NIP-51 mute list so it follows us across
+//! devices. Public mute entries and non-pubkey private entries written by
+//! other clients are kept as they are.
+
+use std::time::Instant;
+
+use anyhow::{anyhow, bail};
+
+use super::*;
+use crate::state::BlockedUser;
+
+const MUTE_LIST_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What a mute list sync does once it has the latest event from relays.
+#[derive(Debug, Clone)]
+pub(super) enum BlockListSync {
+    /// Adopt the relay copy.
+    Pull,
+    /// Publish the local list as-is (after a local block or unblock).
+    Push(Vec<String>),
+    /// Publish the union of both (when sync is first turned on).
+    Merge(Vec<String>),
+}
+
+/// Our mute list as relays reported it.
+#[derive(Debug)]
+enum RemoteMuteList {
+    Found(Event),
+    /// Every connected relay answered and none had one, so nothing has been
+    /// published yet. An empty fetch that ran into the timeout is an error.
+    Missing,
+}
+
+/// Fetch our latest mute list. Fails unless a relay is connected and all of
+/// them answered, so an unreachable or slow relay can't pass for "no list".
+async fn fetch_mute_list(
+    client: &Client,
+    my_pubkey: PublicKey,
+    relays: &[RelayUrl],
+) -> anyhow::Result<RemoteMuteList> {
+    let mut connected = Vec::new();
+    for url in relays {
+        if client
+            .relay(url.clone())
+            .await
+            .is_ok_and(|relay| relay.is_connected())
+        {
+            connected.push(url.clone());
+        }
+    }
+    if connected.is_empty() {
+        bail!("no relay connected");
+    }
+
+    let filter = Filter::new()
+        .author(my_pubkey)
+        .kind(Kind::MuteList)
+        .limit(1);
+    let started = Instant::now();
+    let latest = client
+        .fetch_events_from(connected, filter, MUTE_LIST_FETCH_TIMEOUT)
+        .await
+        .context("fetch mute list")?
+        .into_iter()
+        .filter(|e| e.verify().is_ok())
+        .max_by_key(|e| e.created_at);
+    match latest {
+        Some(event) => Ok(RemoteMuteList::Found(event)),
+        None if started.elapsed() < MUTE_LIST_FETCH_TIMEOUT => Ok(RemoteMuteList::Missing),
+        None => bail!("mute list fetch timed out"),
+    }
+}
+
+/// Blocked pubkeys among the decrypted private tags of a mute list.
+fn muted_pubkeys(private_tags: &[Vec<String>]) -> Vec<String> {
+    private_tags
+        .iter()
+        .filter(|t| t.first().map(String::as_str) == Some("p"))
+        .filter_map(|t| t.get(1).cloned())
+        .collect()
+}
+
+/// Replaces the `p` entries of `private_tags` with `pubkeys`, keeping
+/// everything else (muted words, hashtags, threads) in place.
+fn with_muted_pubkeys(private_tags: Vec<Vec<String>>, pubkeys: &[String]) -> Vec<Vec<String>> {
+    let mut tags: Vec<Vec<String>> = private_tags
+        .into_iter()
+        .filter(|t| t.first().map(String::as_str) != Some("p"))
+        .collect();
+    tags.extend(pubkeys.iter().map(|pk| vec!["p".to_string(), pk.clone()]));
+    tags
+}
+
+/// The pubkey set a sync should leave on relays, given what is there now.
+fn sync_target(mode: &BlockListSync, remote: &[String]) -> Vec<String> {
+    let mut target: Vec<String> = match mode {
+        BlockListSync::Pull => remote.to_vec(),
+        BlockListSync::Push(local) => local.clone(),
+        BlockListSync::Merge(local) => remote.iter().chain(local).cloned().collect(),
+    };
+    target.sort();
+    target.dedup();
+    target
+}
+
+/// Fetch our latest mute list and bring its private pubkeys in line with
+/// `mode`. Like the contact list this is a replaceable event, so we refuse
+/// to publish unless the fetch succeeded. Returns the blocked pubkeys and
+/// whether relays hold a list saying so: a missing list is reported as
+/// empty but unconfirmed.
+async fn sync_mute_list(
+    client: Client,
+    my_pubkey: PublicKey,
+    relays: Vec<RelayUrl>,
+    mode: BlockListSync,
+) -> anyhow::Result<(Vec<String>, bool)> {
+    let current = match fetch_mute_list(&client, my_pubkey, &relays).await? {
+        RemoteMuteList::Found(event) => Some(event),
+        RemoteMuteList::Missing => None,
+    };
+
+    let signer = client.signer().await.context("signer unavailable")?;
+    let private_tags: Vec<Vec<String>> = match current.as_ref() {
+        Some(ev) if !ev.content.is_empty() => {
+            let json = signer
+                .nip44_decrypt(&my_pubkey, &ev.content)
+                .await
+                .map_err(|e| anyhow!("decrypt mute list: {e}"))?;
+            serde_json::from_str(&json).context("parse mute list")?
+        }
+        _ => vec![],
+    };
+
+    let mut remote = muted_pubkeys(&private_tags);
+    remote.sort();
+    remote.dedup();
+    let target = sync_target(&mode, &remote);
+    if target == remote {
+        return Ok((target, current.is_some()));
+    }
+
+    let json = serde_json::to_string(&with_muted_pubkeys(private_tags, &target))?;
+    let content = signer
+        .nip44_encrypt(&my_pubkey, &json)
+        .await
+        .map_err(|e| anyhow!("encrypt mute list: {e}"))?;
+    let public_tags: Vec<Tag> = current.map(|e| e.tags.to_vec()).unwrap_or_default();
+    let event = client
+        .sign_event_builder(EventBuilder::new(Kind::MuteList, content).tags(public_tags))
+        .await
+        .context("sign mute list")?;
+    let output = client
+        .send_event_to(relays, &event)
+        .await
+        .context("publish mute list")?;
+    if output.success.is_empty() {
+        let err = output
+            .failed
+            .values()
+            .next()
+            .cloned()
+            .unwrap_or_else(|| "no relay accepted".into());
+        bail!("mute list rejected: {err}");
+    }
+    tracing::info!(blocked = target.len(), "mute list published");
+    Ok((target, true))
+}
+
+impl AppCore {
+    pub(super) fn is_blocked(&self, pubkey_hex: &str) -> bool {
+        self.blocked_pubkeys.contains(pubkey_hex)
+    }
+
+    /// Load the block list and its sync setting for the open account.
+    /// Callers emit.
+    pub(super) fn load_block_list(&mut self) {
+        let conn = self.profile_db.as_ref();
+        self.blocked_pubkeys = conn
+            .map(profile_db::load_blocks)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        self.block_list_dirty = conn.is_some_and(profile_db::load_block_list_dirty);
+        self.state.block_list_sync_enabled = conn.is_some_and(profile_db::load_block_list_sync);
+        self.refresh_blocked_users();
+    }
+
+    /// Rebuild `state.blocked_users` from the in-memory set. Callers emit.
+    pub(super) fn refresh_blocked_users(&mut self) {
+        let mut users: Vec<BlockedUser> = self
+            .blocked_pubkeys
+            .iter()
+            .map(|hex| {
+                let cached = self.profiles.get(hex);
+                BlockedUser {
+                    npub: PublicKey::from_hex(hex)
+                        .ok()
+                        .and_then(|pk| pk.to_bech32().ok())
+                        .unwrap_or_else(|| hex.clone()),
+                    name: cached.and_then(|p| p.name.clone()),
+                    picture_url: cached.and_then(|p| p.display_picture_url(&self.data_dir, hex)),
+                    pubkey: hex.clone(),
+                }
+            })
+            .collect();
+        users.sort_by(|a, b| match (&a.name, &b.name) {
+            (Some(na), Some(nb)) => na.to_lowercase().cmp(&nb.to_lowercase()),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.npub.cmp(&b.npub),
+        });
+        self.state.blocked_users = users;
+    }
+
+    pub(super) fn block_user(&mut self, pubkey_hex: &str) {
+        let Ok(pk) = PublicKey::from_hex(pubkey_hex.trim()) else {
+            self.toast("Invalid pubkey");
+            return;
+        };
+        if self.session.as_ref().is_some_and(|s| s.pubkey == pk) {
+            self.toast("You can't block yourself");
+            return;
+        }
+        let hex = pk.to_hex();
+        if !self.blocked_pubkeys.insert(hex.clone()) {
+            return;
+        }
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::add_block(conn, &hex, now_seconds());
+        }
+        for typers in self.typing_state.values_mut() {
+            typers.remove(&hex);
+        }
+        self.decline_invites_from(&pk);
+        tracing::info!(pubkey = %hex, "user_blocked");
+        self.block_list_changed(&hex, true);
+    }
+
+    pub(super) fn unblock_user(&mut self, pubkey_hex: &str) {
+        let hex = pubkey_hex.trim().to_lowercase();
+        if !self.blocked_pubkeys.remove(&hex) {
+            return;
+        }
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::remove_block(conn, &hex);
+        }
+        tracing::info!(pubkey = %hex, "user_unblocked");
+        self.block_list_changed(&hex, false);
+    }
+
+    pub(super) fn set_block_list_sync_enabled(&mut self, enabled: bool) {
+        if self.state.block_list_sync_enabled == enabled {
+            return;
+        }
+        self.state.block_list_sync_enabled = enabled;
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::save_block_list_sync(conn, enabled);
+        }
+        if enabled {
+            let local = self.blocked_pubkeys.iter().cloned().collect();
+            self.start_block_list_sync(BlockListSync::Merge(local));
+        }
+        self.emit_state();
+    }
+
+    /// Bring the block list in line with relays: push local changes that
+    /// never made it out, otherwise adopt the relay copy.
+    pub(super) fn fetch_block_list(&mut self) {
+        if self.block_list_dirty {
+            let local = self.blocked_pubkeys.iter().cloned().collect();
+            self.start_block_list_sync(BlockListSync::Push(local));
+        } else {
+            self.start_block_list_sync(BlockListSync::Pull);
+        }
+    }
+
+    pub(super) fn handle_block_list_synced(
+        &mut self,
+        token: u64,
+        pubkeys: Vec<String>,
+        confirmed: bool,
+    ) {
+        // A later local change supersedes this result.
+        if token != self.block_list_sync_token || !self.state.block_list_sync_enabled {
+            return;
+        }
+        self.set_block_list_dirty(false);
+        let my_pubkey = self.session.as_ref().map(|s| s.pubkey);
+        let synced: HashSet<String> = pubkeys
+            .iter()
+            .filter_map(|hex| PublicKey::from_hex(hex).ok())
+            .filter(|pk| Some(*pk) != my_pubkey)
+            .map(|pk| pk.to_hex())
+            .collect();
+        if synced == self.blocked_pubkeys {
+            return;
+        }
+        // Relays without a mute list at all say nothing about our blocks.
+        // Keep the local list rather than unblocking everyone; an empty list
+        // that is actually published is applied like any other.
+        if synced.is_empty() && !confirmed {
+            tracing::warn!(
+                blocked = self.blocked_pubkeys.len(),
+                "no mute list on relays; keeping local block list"
+            );
+            return;
+        }
+        if let Some(conn) = self.profile_db.as_ref() {
+            let now = now_seconds();
+            for pk in synced.difference(&self.blocked_pubkeys) {
+                profile_db::add_block(conn, pk, now);
+            }
+            for pk in self.blocked_pubkeys.difference(&synced) {
+                profile_db::remove_block(conn, pk);
+            }
+        }
+        let newly_blocked: Vec<PublicKey> = synced
+            .difference(&self.blocked_pubkeys)
+            .filter_map(|hex| PublicKey::from_hex(hex).ok())
+            .collect();
+        self.blocked_pubkeys = synced;
+        for pk in &newly_blocked {
+            self.decline_invites_from(pk);
+        }
+        tracing::info!(blocked = self.blocked_pubkeys.len(), "block_list_synced");
+        self.refresh_blocked_users();
+        if let Some(pp) = self.state.peer_profile.as_mut() {
+            pp.is_blocked = self.blocked_pubkeys.contains(&pp.pubkey);
+        }
+        self.refresh_after_block_list_change();
+    }
+
+    pub(super) fn handle_block_list_sync_failed(&mut self, token: u64, error: String) {
+        tracing::warn!(%error, "block list sync failed");
+        if token == self.block_list_sync_token && self.block_list_dirty {
+            self.toast("Couldn't sync block list; will retry");
+        }
+    }
+
+    fn block_list_changed(&mut self, pubkey_hex: &str, blocked: bool) {
+        if let Some(pp) = self.state.peer_profile.as_mut() {
+            if pp.pubkey == pubkey_hex {
+                pp.is_blocked = blocked;
+            }
+        }
+        self.refresh_blocked_users();
+        if self.state.block_list_sync_enabled {
+            self.set_block_list_dirty(true);
+            let local = self.blocked_pubkeys.iter().cloned().collect();
+            self.start_block_list_sync(BlockListSync::Push(local));
+        }
+        self.refresh_after_block_list_change();
+    }
+
+    fn refresh_after_block_list_change(&mut self) {
+        self.refresh_chat_list_from_storage();
+        if let Some(chat_id) = self.state.current_chat.as_ref().map(|c| c.chat_id.clone()) {
+            self.refresh_current_chat(&chat_id);
+        }
+        self.emit_state();
+    }
+
+    fn set_block_list_dirty(&mut self, dirty: bool) {
+        if self.block_list_dirty == dirty {
+            return;
+        }
+        self.block_list_dirty = dirty;
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::save_block_list_dirty(conn, dirty);
+        }
+    }
+
+    /// Decline any staged invitations `pubkey` sent us.
+    fn decline_invites_from(&mut self, pubkey: &PublicKey) {
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let pending = sess.mdk.get_pending_welcomes(None).unwrap_or_default();
+        let mut rumor = None;
+        for welcome in pending.iter().filter(|w| w.welcomer == *pubkey) {
+            match sess.mdk.decline_welcome(welcome) {
+                Ok(_) => {
+                    tracing::info!(
+                        nostr_group_id = %hex::encode(welcome.nostr_group_id),
+                        "welcome_declined (sender blocked)"
+                    );
+                    rumor = Some(welcome.event.clone());
+                }
+                Err(e) => tracing::warn!(%e, "decline_welcome failed"),
+            }
+        }
+        if let Some(rumor) = rumor {
+            self.rotate_key_package_after_welcome(&rumor);
+        }
+    }
+
+    fn start_block_list_sync(&mut self, mode: BlockListSync) {
+        if !self.state.block_list_sync_enabled || !self.network_enabled() {
+            return;
+        }
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let client = sess.client.clone();
+        let my_pubkey = sess.pubkey;
+        let relays = self.default_relays();
+        self.block_list_sync_token = self.block_list_sync_token.wrapping_add(1);
+        let token = self.block_list_sync_token;
+        let tx = self.core_sender.clone();
+        self.runtime.spawn(async move {
+            let event = match sync_mute_list(client, my_pubkey, relays, mode).await {
+                Ok((pubkeys, confirmed)) => InternalEvent::BlockListSynced {
+                    token,
+                    pubkeys,
+                    confirmed,
+                },
+                Err(e) => InternalEvent::BlockListSyncFailed {
+                    token,
+                    error: format!("{e:#}"),
+                },
+            };
+            let _ = tx.send(CoreMsg::Internal(Box::new(event)));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(hex: &str) -> Vec<String> {
+        vec!["p".to_string(), hex.to_string()]
+    }
+
+    #[test]
+    fn muted_pubkeys_are_replaced_without_touching_other_entries() {
+        let word = vec!["word".to_string(), "spam".to_string()];
+        let tags = vec![p("aaa"), word.clone(), p("bbb")];
+        assert_eq!(muted_pubkeys(&tags), vec!["aaa", "bbb"]);
+
+        let updated = with_muted_pubkeys(tags, &["ccc".to_string()]);
+        assert_eq!(updated, vec![word, p("ccc")]);
+    }
+
+    #[test]
+    fn sync_target_follows_mode() {
+        let remote = vec!["aaa".to_string(), "bbb".to_string()];
+        let local = vec!["ccc".to_string(), "aaa".to_string()];
+
+        assert_eq!(sync_target(&BlockListSync::Pull, &remote), remote);
+        assert_eq!(
+            sync_target(&BlockListSync::Push(local.clone()), &remote),
+            vec!["aaa", "ccc"]
+        );
+        assert_eq!(
+            sync_target(&BlockListSync::Merge(local), &remote),
+            vec!["aaa", "bbb", "ccc"]
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use super::*;
-use crate::state::CallStatus;
+use crate::state::{CallCaption, CallStatus};
 use pika_marmot_runtime::call::{
-    derive_relay_auth_token as derive_shared_relay_auth_token, parse_call_signal,
+    call_directory, derive_relay_auth_token as derive_shared_relay_auth_token, parse_call_signal,
     DEFAULT_CALL_BROADCAST_PREFIX,
 };
 use pika_marmot_runtime::call_runtime::{
     GroupCallContext, InboundCallPolicy, InboundCallSignalOutcome, InboundSignalContext,
-    PendingIncomingCall, PendingOutgoingCall, PreparedAcceptedCall,
+    JoinedCallParticipant, LiveGroupCall, PendingIncomingCall, PendingOutgoingCall,
+    PreparedAcceptedCall,
 };
 
 pub(super) use pika_marmot_runtime::call::{
@@ -52,6 +53,36 @@ impl std::fmt::Display for CallEndReason {
     }
 }
 
+/// Live captions shown on the call screen at once; the rest stay in the transcript.
+const VISIBLE_CALL_CAPTIONS: usize = 3;
+
+/// Every caption of the latest call, kept past hang-up so it can be saved to
+/// the chat. Replaced when captions arrive for another call.
+#[derive(Debug, Default)]
+pub(super) struct CallTranscript {
+    pub(super) call_id: String,
+    pub(super) captions: Vec<CallCaption>,
+}
+
+/// Chat message text for a saved transcript, with offsets from the call's start.
+fn format_call_transcript(started_at: Option<i64>, captions: &[CallCaption]) -> String {
+    let start = started_at
+        .or_else(|| captions.first().map(|c| c.at))
+        .unwrap_or(0);
+    let mut out = String::from("Call captions");
+    for caption in captions {
+        let offset = (caption.at - start).max(0);
+        out.push_str(&format!(
+            "\n[{:02}:{:02}] {}: {}",
+            offset / 60,
+            offset % 60,
+            caption.speaker_name,
+            caption.text
+        ));
+    }
+    out
+}
+
 impl AppCore {
     fn has_live_call(&self) -> bool {
         self.state
@@ -73,6 +104,7 @@ impl AppCore {
         &self,
         call_id: &str,
         include_video: bool,
+        group_call: bool,
     ) -> Option<CallSessionParams> {
         let moq_url = self
             .config
@@ -97,6 +129,8 @@ impl AppCore {
         let mut tracks = vec![CallTrackSpec::audio0_opus_default()];
         if include_video {
             tracks.push(CallTrackSpec::video0_h264_default());
+            tracks.push(CallTrackSpec::data0_default());
+            tracks.push(CallTrackSpec::screen0_h264_default());
         }
 
         Some(CallSessionParams {
@@ -104,9 +138,18 @@ impl AppCore {
             broadcast_base: format!("{prefix}/{call_id}"),
             relay_auth: String::new(),
             tracks,
+            group_call,
         })
     }
 
+    fn is_group_chat(&self, chat_id: &str) -> bool {
+        self.session
+            .as_ref()
+            .and_then(|s| s.groups.get(chat_id))
+            .map(|g| g.is_group)
+            .unwrap_or(false)
+    }
+
     fn current_peer_npub(&self, chat_id: &str) -> Option<String> {
         let entry = self.session.as_ref()?.groups.get(chat_id)?;
         if entry.is_group {
@@ -282,6 +325,7 @@ impl AppCore {
 
     fn end_call_local(&mut self, reason: CallEndReason) {
         let previous = self.state.active_call.clone();
+        self.finish_call_recording();
         let mut should_emit = false;
         if let Some(call) = self.state.active_call.as_mut() {
             call.set_status(CallStatus::Ended {
@@ -324,11 +368,22 @@ impl AppCore {
 
         let network_enabled = self.network_enabled();
         let call_id = uuid::Uuid::new_v4().to_string();
-        let Some(peer_npub) = self.current_peer_npub(chat_id) else {
+        let is_group_call = self.is_group_chat(chat_id);
+        // Group calls have no single peer; the caller is recorded as the initiator.
+        let peer_npub = if is_group_call {
+            self.session
+                .as_ref()
+                .and_then(|s| s.pubkey.to_bech32().ok())
+        } else {
+            self.current_peer_npub(chat_id)
+        };
+        let Some(peer_npub) = peer_npub else {
             self.toast("Chat peer not found");
             return;
         };
-        let Some(mut session) = self.call_session_from_config(&call_id, is_video_call) else {
+        let Some(mut session) =
+            self.call_session_from_config(&call_id, is_video_call, is_group_call)
+        else {
             self.toast("Call config missing: set `call_moq_url` in pika_config.json");
             return;
         };
@@ -336,7 +391,7 @@ impl AppCore {
         if !network_enabled {
             let previous = self.state.active_call.clone();
             self.cancel_call_duration_ticks();
-            self.state.active_call = Some(crate::state::CallState::new(
+            let mut call = crate::state::CallState::new(
                 call_id.clone(),
                 chat_id.to_string(),
                 peer_npub,
@@ -345,7 +400,9 @@ impl AppCore {
                 false,
                 is_video_call,
                 None,
-            ));
+            );
+            call.is_group_call = is_group_call;
+            self.state.active_call = Some(call);
             self.call_session_params = Some(session);
             self.schedule_call_offer_timeout();
             self.emit_call_state_with_previous(previous);
@@ -364,6 +421,12 @@ impl AppCore {
                 return;
             }
         };
+        // Any member of the group may answer a group call.
+        let invitee_pubkey_hex = if is_group_call {
+            String::new()
+        } else {
+            peer_pubkey_hex.clone()
+        };
         session.relay_auth = match self.derive_relay_auth_token(
             chat_id,
             &call_id,
@@ -380,7 +443,7 @@ impl AppCore {
 
         let previous = self.state.active_call.clone();
         self.cancel_call_duration_ticks();
-        self.state.active_call = Some(crate::state::CallState::new(
+        let mut call = crate::state::CallState::new(
             call_id.clone(),
             chat_id.to_string(),
             peer_npub,
@@ -389,7 +452,9 @@ impl AppCore {
             false,
             is_video_call,
             None,
-        ));
+        );
+        call.is_group_call = is_group_call;
+        self.state.active_call = Some(call);
         self.call_session_params = Some(session.clone());
         self.schedule_call_offer_timeout();
         self.emit_call_state_with_previous(previous);
@@ -397,7 +462,7 @@ impl AppCore {
         let payload = match self.session.as_ref() {
             Some(sess) => match sess.host_context().prepare_outgoing_call_invite(
                 chat_id,
-                &peer_pubkey_hex,
+                &invitee_pubkey_hex,
                 &call_id,
                 &session,
             ) {
@@ -420,6 +485,7 @@ impl AppCore {
             moq_url = %session.moq_url,
             broadcast_base = %session.broadcast_base,
             tracks = session.tracks.len(),
+            is_group_call,
             "call_invite"
         );
         if let Err(e) = self.publish_call_signal(chat_id, payload, "Call invite publish failed") {
@@ -462,9 +528,12 @@ impl AppCore {
         }
         if let Err(e) = self.call_runtime.on_call_connecting(
             &active.call_id,
+            &prepared.incoming.from_pubkey_hex,
             &prepared.incoming.session,
             prepared.media_crypto,
             self.config.call_audio_backend.as_deref(),
+            active.is_audio_processing_enabled,
+            self.call_stt_backend(),
             self.core_sender.clone(),
         ) {
             self.toast(format!("Call runtime start failed: {e}"));
@@ -472,6 +541,9 @@ impl AppCore {
             return;
         }
         self.call_session_params = Some(prepared.incoming.session);
+        if let Some(call) = self.state.active_call.as_mut() {
+            call.add_participant(&active.peer_npub);
+        }
         self.update_call_status(CallStatus::Connecting);
     }
 
@@ -582,6 +654,7 @@ impl AppCore {
         call.is_muted = !call.is_muted;
         self.call_runtime.set_muted(&call.call_id, call.is_muted);
         self.emit_call_state();
+        self.publish_group_call_media_state();
     }
 
     pub(super) fn handle_toggle_camera_action(&mut self) {
@@ -601,6 +674,483 @@ impl AppCore {
         self.call_runtime
             .set_camera_enabled(&call.call_id, call.is_camera_enabled);
         self.emit_call_state();
+        self.publish_group_call_media_state();
+    }
+
+    pub(super) fn handle_toggle_audio_processing_action(&mut self) {
+        let Some(call) = self.state.active_call.as_mut() else {
+            return;
+        };
+        if !call.status.is_live() {
+            return;
+        }
+        call.is_audio_processing_enabled = !call.is_audio_processing_enabled;
+        self.call_runtime
+            .set_audio_processing(&call.call_id, call.is_audio_processing_enabled);
+        self.emit_call_state();
+    }
+
+    pub(super) fn handle_set_screen_share_action(&mut self, enabled: bool) {
+        let Some(call) = self.state.active_call.as_ref() else {
+            return;
+        };
+        if !matches!(call.status, CallStatus::Connecting | CallStatus::Active)
+            || call.is_screen_sharing == enabled
+        {
+            return;
+        }
+        // Calls offered by clients that predate screen sharing have no screen0
+        // track for us to publish on.
+        let has_screen_track = self
+            .call_session_params
+            .as_ref()
+            .is_some_and(|session| session.tracks.iter().any(|t| t.name == "screen0"));
+        if enabled && !has_screen_track {
+            self.toast("Screen sharing isn't available on this call");
+            return;
+        }
+        let Some(call) = self.state.active_call.as_mut() else {
+            return;
+        };
+        call.is_screen_sharing = enabled;
+        self.call_runtime
+            .set_screen_sharing(&call.call_id, call.is_screen_sharing);
+        self.emit_call_state();
+    }
+
+    pub(super) fn handle_toggle_call_recording_action(&mut self) {
+        let Some(call) = self.state.active_call.as_ref() else {
+            return;
+        };
+        if !matches!(call.status, CallStatus::Connecting | CallStatus::Active) {
+            return;
+        }
+        if call.is_recording {
+            self.finish_call_recording();
+            self.publish_call_recording_state(false);
+        } else {
+            let call_id = call.call_id.clone();
+            if !self.call_runtime.start_recording(&call_id) {
+                self.toast("Call media isn't running yet");
+                return;
+            }
+            if let Some(call) = self.state.active_call.as_mut() {
+                call.is_recording = true;
+            }
+            self.publish_call_recording_state(true);
+        }
+        self.emit_call_state();
+    }
+
+    /// Stops a running recording and stores it with the chat's media. The caller
+    /// emits the call state.
+    fn finish_call_recording(&mut self) {
+        let Some(call) = self.state.active_call.as_mut() else {
+            return;
+        };
+        if !call.is_recording {
+            return;
+        }
+        call.is_recording = false;
+        let call_id = call.call_id.clone();
+        let chat_id = call.chat_id.clone();
+        let Some(recording) = self.call_runtime.stop_recording(&call_id) else {
+            return;
+        };
+        if let Err(e) = self.store_call_recording(&chat_id, &recording) {
+            self.toast(format!("Saving call recording failed: {e}"));
+        }
+    }
+
+    /// Lets everyone else on the call show that we're recording.
+    fn publish_call_recording_state(&mut self, recording: bool) {
+        let Some(call) = self.state.active_call.clone() else {
+            return;
+        };
+        let payload = match self.session.as_ref() {
+            Some(sess) => match sess
+                .host_context()
+                .prepare_call_recording_signal(&call.call_id, recording)
+            {
+                Ok(signal) => signal.payload_json,
+                Err(err) => {
+                    self.toast(format!("Serialize call recording state failed: {err}"));
+                    return;
+                }
+            },
+            None => return,
+        };
+        if let Err(e) = self.publish_call_signal(
+            &call.chat_id,
+            payload,
+            "Call recording state publish failed",
+        ) {
+            tracing::warn!(call_id = %call.call_id, "call recording state publish failed: {e}");
+        }
+    }
+
+    pub(super) fn handle_call_caption(
+        &mut self,
+        call_id: String,
+        participant_id: Option<String>,
+        text: String,
+    ) {
+        if self.state.active_call.as_ref().map(|c| c.call_id.as_str()) != Some(call_id.as_str()) {
+            return;
+        }
+        let is_local = participant_id.is_none();
+        let speaker = match participant_id {
+            Some(hex) => PublicKey::parse(&hex).ok(),
+            None => self.session.as_ref().map(|s| s.pubkey),
+        };
+        let Some(speaker) = speaker else {
+            return;
+        };
+        let speaker_name = if is_local && !self.state.my_profile.name.trim().is_empty() {
+            self.state.my_profile.name.clone()
+        } else {
+            self.peer_display_name(&speaker)
+        };
+        let caption = CallCaption {
+            speaker_npub: speaker.to_bech32().unwrap_or_else(|_| speaker.to_hex()),
+            speaker_name,
+            is_local,
+            text,
+            at: now_seconds(),
+        };
+
+        if self.call_transcript.call_id != call_id {
+            self.call_transcript = CallTranscript {
+                call_id,
+                captions: Vec::new(),
+            };
+        }
+        self.call_transcript.captions.push(caption);
+        let captions = &self.call_transcript.captions;
+        let visible = captions[captions.len().saturating_sub(VISIBLE_CALL_CAPTIONS)..].to_vec();
+        if let Some(call) = self.state.active_call.as_mut() {
+            call.captions = visible;
+        }
+        self.emit_call_state();
+    }
+
+    pub(super) fn handle_save_call_captions_action(&mut self, call_id: &str) {
+        let Some(call) = self.state.active_call.as_ref() else {
+            return;
+        };
+        if call.call_id != call_id {
+            return;
+        }
+        if call.is_live {
+            self.toast("Captions can be saved once the call ends");
+            return;
+        }
+        if self.call_transcript.call_id != call_id || self.call_transcript.captions.is_empty() {
+            self.toast("No captions to save");
+            return;
+        }
+        let chat_id = call.chat_id.clone();
+        let content = format_call_transcript(call.started_at, &self.call_transcript.captions);
+        self.call_transcript = CallTranscript::default();
+        if let Some(call) = self.state.active_call.as_mut() {
+            call.captions.clear();
+        }
+        self.emit_call_state();
+        self.handle_action(AppAction::SendMessage {
+            chat_id,
+            content,
+            kind: None,
+            reply_to_message_id: None,
+        });
+    }
+
+    /// Tells the other members of a connected group call about our mute/camera state.
+    fn publish_group_call_media_state(&mut self) {
+        let Some(call) = self.state.active_call.clone() else {
+            return;
+        };
+        if !call.is_group_call
+            || !matches!(call.status, CallStatus::Connecting | CallStatus::Active)
+        {
+            return;
+        }
+        let payload = match self.session.as_ref() {
+            Some(sess) => match sess.host_context().prepare_call_media_state_signal(
+                &call.call_id,
+                call.is_muted,
+                call.is_video_call && call.is_camera_enabled,
+            ) {
+                Ok(signal) => signal.payload_json,
+                Err(err) => {
+                    self.toast(format!("Serialize call media state failed: {err}"));
+                    return;
+                }
+            },
+            None => return,
+        };
+        if let Err(e) =
+            self.publish_call_signal(&call.chat_id, payload, "Call media state publish failed")
+        {
+            tracing::warn!(call_id = %call.call_id, "call media state publish failed: {e}");
+        }
+    }
+
+    fn live_group_call(&self) -> Option<LiveGroupCall> {
+        let call = self.state.active_call.as_ref()?;
+        if !call.is_group_call
+            || !matches!(call.status, CallStatus::Connecting | CallStatus::Active)
+        {
+            return None;
+        }
+        let session = self.call_session_params.clone()?;
+        Some(LiveGroupCall {
+            call_id: call.call_id.clone(),
+            target_id: call.chat_id.clone(),
+            session,
+            participant_pubkeys_hex: call
+                .participants
+                .iter()
+                .filter_map(|p| PublicKey::parse(&p.npub).ok())
+                .map(|pk| pk.to_hex())
+                .collect(),
+        })
+    }
+
+    fn handle_group_call_participants_joined(
+        &mut self,
+        chat_id: &str,
+        joined: Vec<JoinedCallParticipant>,
+    ) {
+        let Some(local_pubkey_hex) = self.current_pubkey_hex() else {
+            return;
+        };
+        let Some(session) = self.call_session_params.clone() else {
+            return;
+        };
+        // The member with the lowest pubkey among those already connected publishes
+        // the roster, so newcomers learn about everyone without N duplicate copies.
+        let is_roster_owner = self.live_group_call().is_some_and(|live| {
+            live.participant_pubkeys_hex
+                .iter()
+                .all(|pubkey_hex| local_pubkey_hex.as_str() < pubkey_hex.as_str())
+        });
+
+        let previous = self.state.active_call.clone();
+        let mut added = false;
+        for participant in joined {
+            let Ok(pubkey) = PublicKey::parse(&participant.pubkey_hex) else {
+                continue;
+            };
+            let npub = pubkey.to_bech32().unwrap_or_else(|_| pubkey.to_hex());
+            if let Err(e) = self.call_runtime.add_participant(
+                &participant.call_id,
+                &participant.pubkey_hex,
+                &participant.media_crypto,
+            ) {
+                tracing::warn!(
+                    call_id = %participant.call_id,
+                    participant = %participant.pubkey_hex,
+                    "group call participant subscribe failed: {e}"
+                );
+                continue;
+            }
+            if let Some(call) = self.state.active_call.as_mut() {
+                added |= call.add_participant(&npub);
+            }
+        }
+        if !added {
+            return;
+        }
+        self.emit_call_state_with_previous(previous);
+
+        let Some(call) = self.state.active_call.clone() else {
+            return;
+        };
+        if is_roster_owner {
+            let mut roster = vec![(local_pubkey_hex, session.tracks.clone())];
+            roster.extend(
+                call.participants
+                    .iter()
+                    .filter_map(|p| PublicKey::parse(&p.npub).ok())
+                    .map(|pk| (pk.to_hex(), session.tracks.clone())),
+            );
+            let directory = call_directory(&roster);
+            let payload = self.session.as_ref().and_then(|sess| {
+                sess.host_context()
+                    .prepare_call_directory_signal(&call.call_id, &directory)
+                    .ok()
+                    .map(|signal| signal.payload_json)
+            });
+            if let Some(payload) = payload {
+                let _ = self.publish_call_signal(chat_id, payload, "Call directory publish failed");
+            }
+        }
+        if call.is_muted || (call.is_video_call && !call.is_camera_enabled) {
+            self.publish_group_call_media_state();
+        }
+        // Newcomers must learn they're joining a call that's being recorded.
+        if call.is_recording {
+            self.publish_call_recording_state(true);
+        }
+    }
+
+    fn handle_group_call_participant_left(
+        &mut self,
+        call_id: &str,
+        pubkey_hex: &str,
+        reason: String,
+    ) {
+        let Some(active) = self.state.active_call.as_ref() else {
+            return;
+        };
+        if active.call_id != call_id {
+            return;
+        }
+        let Ok(pubkey) = PublicKey::parse(pubkey_hex) else {
+            return;
+        };
+        let npub = pubkey.to_bech32().unwrap_or_else(|_| pubkey.to_hex());
+        let previous = self.state.active_call.clone();
+        let mut now_alone = false;
+        if let Some(call) = self.state.active_call.as_mut() {
+            if !call.remove_participant(&npub) {
+                return;
+            }
+            now_alone = call.participants.is_empty();
+        }
+        self.call_runtime.remove_participant(call_id, pubkey_hex);
+        if now_alone {
+            // Last other member left; there's nobody to talk to.
+            self.end_call_local(CallEndReason::Remote(reason));
+            return;
+        }
+        self.emit_call_state_with_previous(previous);
+    }
+
+    pub(super) fn handle_call_participants_speaking(
+        &mut self,
+        call_id: String,
+        participant_ids: Vec<String>,
+    ) {
+        let Some(call) = self.state.active_call.as_ref() else {
+            return;
+        };
+        if call.call_id != call_id {
+            return;
+        }
+        let speaking: Vec<String> = participant_ids
+            .iter()
+            .filter_map(|hex| PublicKey::parse(hex).ok())
+            .filter_map(|pk| pk.to_bech32().ok())
+            .collect();
+        let previous = self.state.active_call.clone();
+        let mut changed = false;
+        if let Some(call) = self.state.active_call.as_mut() {
+            for participant in call.participants.iter_mut() {
+                let is_speaking = speaking.contains(&participant.npub);
+                if participant.is_speaking != is_speaking {
+                    participant.is_speaking = is_speaking;
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.emit_call_state_with_previous(previous);
+        }
+    }
+
+    /// Re-keys the live call in `chat_id` after a commit moved its group to a new
+    /// MLS epoch. Participants who are no longer members are dropped, so they stop
+    /// receiving anything sealed under the new keys.
+    pub(super) fn rotate_call_media_keys(&mut self, chat_id: &str) {
+        let Some(call) = self.state.active_call.clone() else {
+            return;
+        };
+        if call.chat_id != chat_id
+            || !matches!(call.status, CallStatus::Connecting | CallStatus::Active)
+        {
+            return;
+        }
+        let Some(session) = self.call_session_params.clone() else {
+            return;
+        };
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let Some(group) = sess.groups.get(chat_id) else {
+            return;
+        };
+        let members: Vec<String> = match sess.mdk.get_members(&group.mls_group_id) {
+            Ok(members) => members.iter().map(|pk| pk.to_hex()).collect(),
+            Err(e) => {
+                tracing::warn!(call_id = %call.call_id, "load members for call rekey failed: {e}");
+                return;
+            }
+        };
+        let local_pubkey_hex = sess.pubkey.to_hex();
+        if !members.contains(&local_pubkey_hex) {
+            self.end_call_local(CallEndReason::Remote("removed_from_group".to_string()));
+            return;
+        }
+
+        // The cached index still holds the pre-commit roster at this point.
+        let mut member_removed = group
+            .members
+            .iter()
+            .any(|m| !members.contains(&m.pubkey.to_hex()));
+
+        let mut rotated = Vec::new();
+        let mut removed = Vec::new();
+        let mut failed = None;
+        for (participant_id, current) in self.call_runtime.participant_media_crypto(&call.call_id) {
+            if !members.contains(&participant_id) {
+                member_removed = true;
+                removed.push(participant_id);
+                continue;
+            }
+            match sess.host_context().rotate_call_media_crypto(
+                GroupCallContext {
+                    mls_group_id: &group.mls_group_id,
+                    local_pubkey_hex: &local_pubkey_hex,
+                },
+                &call.call_id,
+                &session,
+                &participant_id,
+                &current,
+            ) {
+                Ok(Some(next)) => rotated.push((participant_id, next)),
+                Ok(None) => {}
+                Err(e) => failed = Some(e),
+            }
+        }
+        if let Some(e) = failed {
+            // Carrying on with stale keys would keep removed members able to listen.
+            self.toast(format!("Call media rekey failed: {e}"));
+            self.end_call_local(CallEndReason::RuntimeError);
+            return;
+        }
+        if !rotated.is_empty() {
+            tracing::info!(
+                call_id = %call.call_id,
+                epoch = rotated[0].1.tx_keys.epoch,
+                generation = rotated[0].1.tx_keys.generation,
+                member_removed,
+                "call media keys rotated"
+            );
+            self.call_runtime
+                .rotate_media_keys(&call.call_id, rotated, member_removed);
+        }
+        for participant_id in removed {
+            if call.is_group_call {
+                self.handle_group_call_participant_left(
+                    &call.call_id,
+                    &participant_id,
+                    "removed_from_group".to_string(),
+                );
+            } else {
+                self.end_call_local(CallEndReason::Remote("removed_from_group".to_string()));
+            }
+        }
     }
 
     fn send_call_reject(&mut self, chat_id: &str, call_id: &str, reason: &str) {
@@ -625,15 +1175,6 @@ impl AppCore {
         sender_pubkey: &PublicKey,
         signal: ParsedCallSignal,
     ) {
-        // Calls are MVP-only for 1:1 DMs. If a call invite arrives on a group chat,
-        // reject it to avoid wedging state with no UI controls.
-        let is_group_chat = self
-            .session
-            .as_ref()
-            .and_then(|s| s.groups.get(chat_id))
-            .map(|g| g.is_group)
-            .unwrap_or(false);
-
         let peer_npub = sender_pubkey
             .to_bech32()
             .unwrap_or_else(|_| sender_pubkey.to_hex());
@@ -664,6 +1205,7 @@ impl AppCore {
                         is_video_call: active.is_video_call,
                     })
             });
+        let live_group_call = self.live_group_call();
 
         match sess.host_context().handle_inbound_call_signal(
             InboundSignalContext {
@@ -674,11 +1216,12 @@ impl AppCore {
                     local_pubkey_hex: &local_pubkey_hex,
                 },
                 policy: InboundCallPolicy {
-                    allow_group_calls: !is_group_chat,
+                    allow_group_calls: true,
                     allow_video_calls: true,
                 },
                 has_live_call: self.has_live_call(),
                 pending_outgoing: pending_outgoing.as_ref(),
+                live_group_call: live_group_call.as_ref(),
             },
             signal,
         ) {
@@ -697,7 +1240,7 @@ impl AppCore {
                 self.call_session_params = Some(incoming.session.clone());
                 let previous = self.state.active_call.clone();
                 self.cancel_call_duration_ticks();
-                self.state.active_call = Some(crate::state::CallState::new(
+                let mut call = crate::state::CallState::new(
                     incoming.call_id.clone(),
                     chat_id.to_string(),
                     peer_npub,
@@ -706,16 +1249,24 @@ impl AppCore {
                     false,
                     incoming.is_video_call,
                     None,
-                ));
+                );
+                call.is_group_call = incoming.session.group_call;
+                self.state.active_call = Some(call);
                 self.schedule_call_offer_timeout();
                 self.emit_call_state_with_previous(previous);
             }
             InboundCallSignalOutcome::OutgoingAccepted(accepted) => {
                 if let Err(e) = self.call_runtime.on_call_connecting(
                     &accepted.pending.call_id,
+                    &peer_pubkey_hex,
                     &accepted.session,
                     accepted.media_crypto,
                     self.config.call_audio_backend.as_deref(),
+                    self.state
+                        .active_call
+                        .as_ref()
+                        .is_none_or(|call| call.is_audio_processing_enabled),
+                    self.call_stt_backend(),
                     self.core_sender.clone(),
                 ) {
                     self.toast(format!("Call runtime start failed: {e}"));
@@ -723,6 +1274,9 @@ impl AppCore {
                     return;
                 }
                 self.call_session_params = Some(accepted.session);
+                if let Some(call) = self.state.active_call.as_mut() {
+                    call.add_participant(&peer_npub);
+                }
                 self.update_call_status(CallStatus::Connecting);
             }
             InboundCallSignalOutcome::IncomingAcceptFailed(failure) => {
@@ -750,8 +1304,64 @@ impl AppCore {
                 if active.call_id != ended.call_id || active.chat_id != chat_id {
                     return;
                 }
+                // While a group call is still ringing, other members declining is
+                // irrelevant; only the caller hanging up stops the ring.
+                if active.is_group_call && active.peer_npub != peer_npub {
+                    return;
+                }
                 self.end_call_local(CallEndReason::Remote(ended.reason));
             }
+            InboundCallSignalOutcome::ParticipantsJoined(joined) => {
+                self.handle_group_call_participants_joined(chat_id, joined);
+            }
+            InboundCallSignalOutcome::ParticipantLeft(left) => {
+                self.handle_group_call_participant_left(
+                    &left.call_id,
+                    &left.pubkey_hex,
+                    left.reason,
+                );
+            }
+            InboundCallSignalOutcome::ParticipantMediaState(state) => {
+                let Ok(pubkey) = PublicKey::parse(&state.pubkey_hex) else {
+                    return;
+                };
+                let npub = pubkey.to_bech32().unwrap_or_else(|_| pubkey.to_hex());
+                let previous = self.state.active_call.clone();
+                let mut changed = false;
+                if let Some(call) = self.state.active_call.as_mut() {
+                    if call.call_id != state.call_id {
+                        return;
+                    }
+                    if let Some(participant) = call.participants.iter_mut().find(|p| p.npub == npub)
+                    {
+                        participant.is_muted = state.muted;
+                        participant.is_camera_enabled = state.camera_enabled;
+                        changed = true;
+                    }
+                }
+                if changed {
+                    self.emit_call_state_with_previous(previous);
+                }
+            }
+            InboundCallSignalOutcome::ParticipantRecording(state) => {
+                let Ok(pubkey) = PublicKey::parse(&state.pubkey_hex) else {
+                    return;
+                };
+                let npub = pubkey.to_bech32().unwrap_or_else(|_| pubkey.to_hex());
+                let previous = self.state.active_call.clone();
+                let Some(call) = self.state.active_call.as_mut() else {
+                    return;
+                };
+                if call.call_id != state.call_id || call.chat_id != chat_id {
+                    return;
+                }
+                let Some(participant) = call.participants.iter_mut().find(|p| p.npub == npub)
+                else {
+                    return;
+                };
+                participant.is_recording = state.recording;
+                self.emit_call_state_with_previous(previous);
+            }
         }
     }
 
@@ -781,6 +1391,7 @@ mod tests {
             broadcast_base: format!("pika/calls/{call_id}"),
             relay_auth: "capv1_test_token".to_string(),
             tracks: vec![CallTrackSpec::audio0_opus_default()],
+            group_call: false,
         };
         let json = build_call_signal_json(call_id, OutgoingCallSignal::Invite(&session)).unwrap();
         let parsed = parse_call_signal(&json);
@@ -814,4 +1425,25 @@ mod tests {
         assert!(!valid_relay_auth_token("capv1_short"));
         assert!(!valid_relay_auth_token("notcap_0123456789abcdef"));
     }
+
+    #[test]
+    fn formats_transcript_with_offsets_from_call_start() {
+        let caption = |name: &str, text: &str, at: i64| CallCaption {
+            speaker_npub: String::new(),
+            speaker_name: name.to_string(),
+            is_local: false,
+            text: text.to_string(),
+            at,
+        };
+        let captions = vec![
+            caption("alice", "hi", 1_005),
+            caption("bob", "hello, can you hear me?", 1_072),
+        ];
+        assert_eq!(
+            format_call_transcript(Some(1_000), &captions),
+            "Call captions\n[00:05] alice: hi\n[01:12] bob: hello, can you hear me?"
+        );
+        // Without a start time (never connected), offsets count from the first caption.
+        assert!(format_call_transcript(None, &captions).contains("[00:00] alice: hi"));
+    }
 }
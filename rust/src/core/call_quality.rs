@@ -0,0 +1,146 @@
+//! Call quality history: a sample every couple of seconds from the call
+//! runtime's stats, stored per account in `call_quality.sqlite3` next to the
+//! call timeline, and read back as a post-call report.
+
+use std::path::Path;
+
+use pika_media::quality::{summarize, QualityCounters, QualitySample, QualitySampler};
+use pika_media::quality_store::{self, QUALITY_DB_FILE};
+
+use super::*;
+use crate::state::{CallQualitySample, CallQualitySummary, CallReportState};
+
+/// How often a sample is stored while a call is live.
+const CALL_QUALITY_SAMPLE_INTERVAL_MS: i64 = 2_000;
+/// Reports are kept for this many of the most recent calls.
+const CALL_QUALITY_KEEP_CALLS: usize = 100;
+
+/// Samples for the call currently feeding runtime stats.
+pub(super) struct CallQualityRecorder {
+    call_id: String,
+    chat_id: String,
+    sampler: QualitySampler,
+}
+
+pub(super) fn open_call_quality_db(account_dir: &str) -> Option<rusqlite::Connection> {
+    match quality_store::open_quality_db(&Path::new(account_dir).join(QUALITY_DB_FILE)) {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            tracing::warn!(%e, "failed to open call quality db");
+            None
+        }
+    }
+}
+
+fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+pub(super) fn call_report_state(call_id: String, samples: &[QualitySample]) -> CallReportState {
+    CallReportState {
+        call_id,
+        samples: samples
+            .iter()
+            .map(|s| CallQualitySample {
+                at_ms: s.at_ms,
+                rtt_ms: s.rtt_ms,
+                jitter_ms: s.jitter_ms,
+                loss_pct: s.loss_pct,
+                concealed_frames: s.concealed_frames,
+                tx_bitrate_bps: s.tx_bitrate_bps,
+                rx_bitrate_bps: s.rx_bitrate_bps,
+                video_fps: s.video_fps,
+            })
+            .collect(),
+        summary: summarize(samples).map(|s| CallQualitySummary {
+            score: s.score,
+            sample_count: s.sample_count,
+            duration_ms: s.duration_ms,
+            avg_rtt_ms: s.avg_rtt_ms,
+            avg_jitter_ms: s.avg_jitter_ms,
+            max_jitter_ms: s.max_jitter_ms,
+            avg_loss_pct: s.avg_loss_pct,
+            max_loss_pct: s.max_loss_pct,
+            concealed_frames: s.concealed_frames,
+            avg_tx_bitrate_bps: s.avg_tx_bitrate_bps,
+            avg_rx_bitrate_bps: s.avg_rx_bitrate_bps,
+            avg_video_fps: s.avg_video_fps,
+        }),
+    }
+}
+
+impl AppCore {
+    pub(super) fn record_call_quality(
+        &mut self,
+        call_id: &str,
+        counters: QualityCounters,
+        rtt_ms: Option<u32>,
+        jitter_ms: u32,
+    ) {
+        let Some(chat_id) = self
+            .state
+            .active_call
+            .as_ref()
+            .filter(|call| call.call_id == call_id)
+            .map(|call| call.chat_id.clone())
+        else {
+            return;
+        };
+        if self.call_quality.as_ref().map(|r| r.call_id.as_str()) != Some(call_id) {
+            self.call_quality = Some(CallQualityRecorder {
+                call_id: call_id.to_string(),
+                chat_id,
+                sampler: QualitySampler::new(CALL_QUALITY_SAMPLE_INTERVAL_MS),
+            });
+            if let Some(conn) = self.call_quality_db.as_ref() {
+                if let Err(e) = quality_store::prune_quality_calls(conn, CALL_QUALITY_KEEP_CALLS) {
+                    tracing::warn!(%e, "failed to prune call quality history");
+                }
+            }
+        }
+        let Some(recorder) = self.call_quality.as_mut() else {
+            return;
+        };
+        let Some(sample) = recorder
+            .sampler
+            .observe(now_millis(), counters, rtt_ms, jitter_ms)
+        else {
+            return;
+        };
+        if let Some(conn) = self.call_quality_db.as_ref() {
+            if let Err(e) = quality_store::insert_quality_sample(
+                conn,
+                &recorder.call_id,
+                &recorder.chat_id,
+                &sample,
+            ) {
+                tracing::warn!(%e, "failed to store call quality sample");
+            }
+        }
+    }
+
+    pub(super) fn load_call_report(&mut self, call_id: String) {
+        let samples = match self.call_quality_db.as_ref() {
+            Some(conn) => quality_store::load_quality_samples(conn, &call_id).unwrap_or_else(|e| {
+                tracing::warn!(%e, "failed to load call quality samples");
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        self.state.call_report = Some(call_report_state(call_id, &samples));
+        self.emit_state();
+    }
+
+    pub(super) fn clear_call_quality_history(&mut self) {
+        self.call_quality = None;
+        self.state.call_report = None;
+        if let Some(conn) = self.call_quality_db.as_ref() {
+            if let Err(e) = quality_store::clear_quality_samples(conn) {
+                tracing::warn!(%e, "failed to clear call quality history");
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Call recordings: Ogg Opus files that never leave this device. They're
+//! encrypted with the chat's media scheme like any attachment and listed in the
+//! chat's media gallery; opening one decrypts it into the media cache.
+
+use std::path::{Path, PathBuf};
+
+use mdk_core::encrypted_media::types::MediaReference;
+use pika_marmot_runtime::media::UploadedBlob;
+
+use super::chat_media::{media_file_path, write_media_file};
+use super::chat_media_db::{self, ChatMediaRecord, LOCAL_ONLY_URL};
+use super::*;
+
+const CALL_RECORDING_MIME: &str = "audio/ogg";
+
+/// Ciphertext of a finished recording. Lives outside the chat media root so
+/// wiping the media cache doesn't take recordings with it.
+fn call_recording_path(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+) -> PathBuf {
+    Path::new(data_dir)
+        .join("call_recordings")
+        .join(account_pubkey)
+        .join(chat_id)
+        .join(format!("{original_hash_hex}.enc"))
+}
+
+fn call_recording_filename() -> String {
+    format!(
+        "call-recording-{}.ogg",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+fn reference_from_record(record: &ChatMediaRecord) -> Option<MediaReference> {
+    let original_hash: [u8; 32] = hex::decode(&record.original_hash_hex)
+        .ok()?
+        .try_into()
+        .ok()?;
+    let nonce: [u8; 12] = hex::decode(&record.nonce_hex).ok()?.try_into().ok()?;
+    Some(MediaReference {
+        url: record.url.clone(),
+        original_hash,
+        nonce,
+        mime_type: record.mime_type.clone(),
+        filename: record.filename.clone(),
+        scheme_version: record.scheme_version.clone(),
+        dimensions: None,
+    })
+}
+
+impl AppCore {
+    /// Encrypts a finished recording, keeps the ciphertext on disk and records
+    /// it with the chat's media.
+    pub(super) fn store_call_recording(
+        &mut self,
+        chat_id: &str,
+        recording: &[u8],
+    ) -> Result<(), String> {
+        let Some(sess) = self.session.as_ref() else {
+            return Err("not logged in".to_string());
+        };
+        let Some(group) = sess.groups.get(chat_id) else {
+            return Err("chat not found".to_string());
+        };
+        let filename = call_recording_filename();
+        let prepared = sess
+            .host_context()
+            .prepare_upload(
+                &group.mls_group_id,
+                recording,
+                Some(CALL_RECORDING_MIME),
+                Some(&filename),
+            )
+            .map_err(|e| format!("{e:#}"))?;
+        let stored = sess.host_context().finish_upload(
+            &group.mls_group_id,
+            &prepared.upload,
+            UploadedBlob {
+                blossom_server: String::new(),
+                uploaded_url: LOCAL_ONLY_URL.to_string(),
+                descriptor_sha256_hex: hex::encode(prepared.upload.encrypted_hash),
+            },
+        );
+        let account_pubkey = sess.pubkey.to_hex();
+        let attachment = stored.attachment;
+
+        write_media_file(
+            &call_recording_path(
+                &self.data_dir,
+                &account_pubkey,
+                chat_id,
+                &attachment.original_hash_hex,
+            ),
+            &prepared.encrypted_data,
+        )?;
+
+        let record = ChatMediaRecord {
+            account_pubkey,
+            chat_id: chat_id.to_string(),
+            original_hash_hex: attachment.original_hash_hex,
+            encrypted_hash_hex: attachment.encrypted_hash_hex.unwrap_or_default(),
+            url: LOCAL_ONLY_URL.to_string(),
+            mime_type: attachment.mime_type,
+            filename: attachment.filename,
+            nonce_hex: attachment.nonce_hex,
+            scheme_version: attachment.scheme_version,
+            created_at: now_seconds(),
+        };
+        if let Some(conn) = self.chat_media_db.as_ref() {
+            chat_media_db::upsert_chat_media(conn, &record)
+                .map_err(|e| format!("save recording record failed: {e}"))?;
+        }
+
+        if self
+            .state
+            .media_gallery
+            .as_ref()
+            .is_some_and(|gallery| gallery.chat_id == chat_id)
+        {
+            self.load_media_gallery(chat_id);
+        }
+        Ok(())
+    }
+
+    /// Decrypts a recording into the media cache, then refreshes the gallery so
+    /// its item carries a `local_path`.
+    pub(super) fn open_call_recording(&mut self, chat_id: &str, original_hash_hex: &str) {
+        if let Err(e) = self.decrypt_call_recording(chat_id, original_hash_hex) {
+            self.toast(format!("Opening call recording failed: {e}"));
+            return;
+        }
+        self.load_media_gallery(chat_id);
+    }
+
+    fn decrypt_call_recording(&self, chat_id: &str, original_hash_hex: &str) -> Result<(), String> {
+        let Some(sess) = self.session.as_ref() else {
+            return Err("not logged in".to_string());
+        };
+        let Some(group) = sess.groups.get(chat_id) else {
+            return Err("chat not found".to_string());
+        };
+        let account_pubkey = sess.pubkey.to_hex();
+        let target_hash = original_hash_hex.trim().to_ascii_lowercase();
+        let record = self
+            .chat_media_db
+            .as_ref()
+            .and_then(|conn| {
+                chat_media_db::get_chat_media(conn, &account_pubkey, chat_id, &target_hash)
+            })
+            .filter(|record| record.url == LOCAL_ONLY_URL)
+            .ok_or_else(|| "recording not found".to_string())?;
+
+        let local_path = media_file_path(
+            &self.data_dir,
+            &account_pubkey,
+            chat_id,
+            &record.original_hash_hex,
+            &record.filename,
+        );
+        if local_path.exists() {
+            return Ok(());
+        }
+
+        let reference =
+            reference_from_record(&record).ok_or_else(|| "invalid recording record".to_string())?;
+        let encrypted = std::fs::read(call_recording_path(
+            &self.data_dir,
+            &account_pubkey,
+            chat_id,
+            &record.original_hash_hex,
+        ))
+        .map_err(|e| format!("read recording failed: {e}"))?;
+        let decrypted = sess
+            .host_context()
+            .decrypt_downloaded_media(
+                &group.mls_group_id,
+                &reference,
+                &encrypted,
+                (!record.encrypted_hash_hex.is_empty())
+                    .then_some(record.encrypted_hash_hex.as_str()),
+            )
+            .map_err(|e| format!("{e:#}"))?;
+        write_media_file(&local_path, &decrypted.decrypted_data)
+    }
+}
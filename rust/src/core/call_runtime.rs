@@ -1,21 +1,32 @@
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc::TryRecvError;
 use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use flume::Sender;
-use pika_media::codec_opus::{OpusCodec, OpusPacket};
-use pika_media::crypto::{decrypt_frame, encrypt_frame, FrameInfo};
-use pika_media::jitter::JitterBuffer;
+use pika_media::audio_processing::AudioProcessor;
+use pika_media::codec_opus::{OpusCodec, OpusConfig, OpusPacket};
+use pika_media::crypto::{
+    encrypt_frame, tx_rotation_delay, FrameInfo, FrameKeyMaterial, FrameKeyRing, FrameKeySchedule,
+    KEY_ROTATION_RX_WINDOW,
+};
+use pika_media::jitter::{AdaptiveJitterBuffer, Playout};
 use pika_media::network::NetworkRelay;
+use pika_media::recording::OggOpusWriter;
 use pika_media::session::{
     InMemoryRelay, MediaFrame, MediaSession, MediaSessionError, SessionConfig,
 };
+use pika_media::stt::{SpeechSegment, SpeechSegmenter, SttBackend};
 use pika_media::subscription::MediaFrameSubscription;
-use pika_media::tracks::{broadcast_path, TrackAddress};
+use pika_media::tracks::{broadcast_path, TrackAddress, TrackCatalog, TrackSpec};
+use pika_media::video_adapt::{
+    h264_is_keyframe, FeedbackMessage, ReceiverStats, VideoFeedback, VideoProfile,
+    VideoRateController, KEYFRAME_REQUEST_INTERVAL_US, REPORT_INTERVAL_US, VIDEO_PROFILES,
+};
 
+use crate::state::CallVideoProfile;
 use crate::updates::{CoreMsg, InternalEvent};
 use crate::VideoFrameReceiver;
 
@@ -28,29 +39,52 @@ const FRAME_DURATION_US: u64 = (FRAME_DURATION_MS as u64) * 1_000;
 const FRAME_DURATION: Duration = Duration::from_millis(FRAME_DURATION_MS as u64);
 const FRAME_SAMPLES: usize = 960; // 20ms @ 48kHz mono.
 const JITTER_MAX_FRAMES: usize = 12;
-const JITTER_TARGET_FRAMES: usize = 3;
+const JITTER_MIN_TARGET_FRAMES: usize = 2;
+const JITTER_MAX_TARGET_FRAMES: usize = 8;
 const MAX_RX_FRAMES_PER_TICK: usize = 4;
 const STATS_EMIT_INTERVAL_TICKS: u64 = 5;
+const SPEAKING_LEVEL_THRESHOLD: u32 = 600;
+const SPEAKING_HOLD_TICKS: u64 = 15; // 300ms hangover so pauses between words don't flicker.
 const RX_REPLAY_WINDOW_FRAMES: u64 = 128;
+const SILENT_FRAME: [i16; FRAME_SAMPLES] = [0; FRAME_SAMPLES];
 
 const VIDEO_FRAME_DURATION_MS: u32 = 33;
 const VIDEO_FRAME_DURATION: Duration = Duration::from_millis(VIDEO_FRAME_DURATION_MS as u64);
-const VIDEO_FRAME_DURATION_US: u64 = (VIDEO_FRAME_DURATION_MS as u64) * 1_000;
+/// A remote screen share with no frames for this long has stopped.
+const SCREEN_SHARE_IDLE_US: u64 = 2_000_000;
 
 #[derive(Debug, Default)]
 struct SharedVideoStats {
     tx_count: AtomicU64,
     rx_count: AtomicU64,
+    tx_bytes: AtomicU64,
+    rx_bytes: AtomicU64,
     rx_decrypt_fail: AtomicU64,
+    /// Index into `VIDEO_PROFILES` our video is currently sent at.
+    profile_level: AtomicUsize,
 }
 
-#[derive(Debug)]
 struct CallWorker {
     stop: Arc<AtomicBool>,
     muted: Arc<AtomicBool>,
+    audio_processing: Arc<AtomicBool>,
+    video: Option<VideoWorker>,
+    transport: Arc<MediaTransport>,
+    session: CallSessionParams,
+    audio_participants: std::sync::mpsc::Sender<ParticipantCommand>,
+    /// Keys currently in use per remote participant, kept for epoch rotation.
+    media_crypto: HashMap<String, CallMediaCryptoContext>,
+    recorder: Arc<Mutex<Option<CallRecorder>>>,
+}
+
+/// Handles to a video call's video thread.
+struct VideoWorker {
+    frame_tx: std::sync::mpsc::Sender<Vec<u8>>,
+    screen_frame_tx: std::sync::mpsc::Sender<Vec<u8>>,
+    stop: Arc<AtomicBool>,
     camera_enabled: Arc<AtomicBool>,
-    video_stop: Option<Arc<AtomicBool>>,
-    video_frame_tx: Option<std::sync::mpsc::Sender<Vec<u8>>>,
+    screen_sharing: Arc<AtomicBool>,
+    participants: std::sync::mpsc::Sender<ParticipantCommand>,
 }
 
 type SharedVideoFrameReceiver = Arc<RwLock<Option<Arc<dyn VideoFrameReceiver>>>>;
@@ -163,9 +197,12 @@ impl CallRuntime {
     pub(super) fn on_call_connecting(
         &mut self,
         call_id: &str,
+        peer_id: &str,
         session: &CallSessionParams,
         media_crypto: CallMediaCryptoContext,
         audio_backend_mode: Option<&str>,
+        audio_processing: bool,
+        stt_backend: Option<Box<dyn SttBackend>>,
         tx: Sender<CoreMsg>,
     ) -> Result<(), String> {
         self.on_call_ended(call_id);
@@ -186,136 +223,339 @@ impl CallRuntime {
         };
         transport.connect().map_err(to_string_error)?;
 
-        let media_ctx = media_crypto;
-        let local_path =
-            broadcast_path(&session.broadcast_base, &media_ctx.local_participant_label)?;
-        let peer_path = broadcast_path(&session.broadcast_base, &media_ctx.peer_participant_label)?;
+        let local_path = broadcast_path(
+            &session.broadcast_base,
+            &media_crypto.local_participant_label,
+        )?;
         let publish_track = TrackAddress {
             broadcast_path: local_path.clone(),
             track_name: "audio0".to_string(),
         };
-        let subscribe_track = TrackAddress {
-            broadcast_path: peer_path.clone(),
-            track_name: "audio0".to_string(),
-        };
-        let rx = transport
-            .subscribe(&subscribe_track)
-            .map_err(to_string_error)?;
-        let tx_keys = media_ctx.tx_keys;
-        let rx_keys = media_ctx.rx_keys;
+        let mut tx_keys = FrameKeySchedule::new(media_crypto.tx_keys.clone());
+        let (audio_participants_tx, audio_participants_rx) =
+            std::sync::mpsc::channel::<ParticipantCommand>();
+        // Which remote participant's video the platform decoder is fed from. The
+        // audio thread moves it to whoever most recently started speaking.
+        let video_focus: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
 
         // Video setup: use the same MoQ transport for video (shared QUIC connection)
-        let has_video = media_ctx.video_tx_keys.is_some();
+        let has_video = media_crypto.video_tx_keys.is_some();
         let video_stats_shared = Arc::new(SharedVideoStats::default());
         let transport = Arc::new(transport);
-        let video_frame_tx = if has_video {
-            let video_publish_track = TrackAddress {
-                broadcast_path: local_path,
-                track_name: "video0".to_string(),
-            };
-            let video_subscribe_track = TrackAddress {
-                broadcast_path: peer_path,
-                track_name: "video0".to_string(),
-            };
-
-            let video_rx = transport
-                .subscribe(&video_subscribe_track)
-                .map_err(to_string_error)?;
-            let Some(video_tx_keys) = media_ctx.video_tx_keys else {
+        let video = if has_video {
+            let Some(video_tx_keys) = media_crypto.video_tx_keys.clone() else {
                 return Err("video_tx_keys missing despite has_video check".to_string());
             };
-            let Some(video_rx_keys) = media_ctx.video_rx_keys else {
-                return Err("video_rx_keys missing despite has_video check".to_string());
-            };
+            let mut camera = TrackPublisher::new(
+                TrackAddress {
+                    broadcast_path: local_path.clone(),
+                    track_name: "video0".to_string(),
+                },
+                video_tx_keys,
+            );
+            // Calls offered by older clients have no screen track.
+            let mut screen = media_crypto.screen_tx_keys.clone().map(|keys| {
+                TrackPublisher::new(
+                    TrackAddress {
+                        broadcast_path: local_path.clone(),
+                        track_name: "screen0".to_string(),
+                    },
+                    keys,
+                )
+            });
+            // Peers on sessions without a data track get no feedback and keep
+            // receiving the top profile.
+            let mut feedback = media_crypto.data_tx_keys.clone().map(|tx_keys| {
+                VideoFeedbackChannel::new(
+                    TrackPublisher::new(
+                        TrackAddress {
+                            broadcast_path: local_path,
+                            track_name: "data0".to_string(),
+                        },
+                        tx_keys,
+                    ),
+                    media_crypto.local_participant_label.clone(),
+                    transport.clone(),
+                )
+            });
 
             let (vtx, vrx) = std::sync::mpsc::channel::<Vec<u8>>();
+            let (stx, srx) = std::sync::mpsc::channel::<Vec<u8>>();
+            let (video_participants_tx, video_participants_rx) =
+                std::sync::mpsc::channel::<ParticipantCommand>();
             let call_id_for_video = call_id.to_string();
             let stop_for_video = Arc::new(AtomicBool::new(false));
             let stop_for_video_thread = stop_for_video.clone();
             let camera_enabled_for_video = Arc::new(AtomicBool::new(true));
             let camera_for_thread = camera_enabled_for_video.clone();
+            let screen_sharing = Arc::new(AtomicBool::new(false));
+            let screen_sharing_for_thread = screen_sharing.clone();
             let video_receiver = self.video_frame_receiver.clone();
             let video_stats_for_thread = video_stats_shared.clone();
             let video_transport = transport.clone();
+            let video_focus_for_thread = video_focus.clone();
 
             thread::spawn(move || {
-                let mut seq = 0u64;
-                let mut tx_counter = 0u32;
                 let mut next_tick = Instant::now();
-                let mut replay_window = ReplayWindow::default();
                 let mut video_publish_error_logged = false;
+                let mut peers: Vec<RemoteVideo> = Vec::new();
+                let mut forwarding: Option<String> = None;
+                let mut screens: Vec<RemoteVideo> = Vec::new();
+                // Whose screen share the platform is shown, and when it last
+                // delivered a frame.
+                let mut screen_source: Option<(String, u64)> = None;
+                let started = Instant::now();
 
                 while !stop_for_video_thread.load(Ordering::Relaxed) {
-                    // TX: send platform video frames
-                    if camera_for_thread.load(Ordering::Relaxed) {
-                        while let Ok(payload) = vrx.try_recv() {
-                            let is_keyframe = seq.is_multiple_of(60);
-                            let frame_info = FrameInfo {
-                                counter: tx_counter,
-                                group_seq: seq,
-                                frame_idx: 0,
-                                keyframe: is_keyframe,
-                            };
-                            if tx_counter < u32::MAX {
-                                tx_counter = tx_counter.saturating_add(1);
+                    while let Ok(command) = video_participants_rx.try_recv() {
+                        match command {
+                            ParticipantCommand::Add(participant) => match participant.track {
+                                CallTrack::Video => {
+                                    peers
+                                        .retain(|p| p.participant_id != participant.participant_id);
+                                    peers.push(RemoteVideo::new(*participant));
+                                }
+                                CallTrack::Screen => {
+                                    screens
+                                        .retain(|p| p.participant_id != participant.participant_id);
+                                    screens.push(RemoteVideo::new(*participant));
+                                }
+                                CallTrack::Data => {
+                                    if let Some(feedback) = feedback.as_mut() {
+                                        feedback.add_peer(*participant);
+                                    }
+                                }
+                                CallTrack::Audio => {}
+                            },
+                            ParticipantCommand::Remove(participant_id) => {
+                                peers.retain(|p| p.participant_id != participant_id);
+                                screens.retain(|p| p.participant_id != participant_id);
+                                if let Some(feedback) = feedback.as_mut() {
+                                    feedback
+                                        .peers
+                                        .retain(|p| p.participant_id != participant_id);
+                                }
                             }
-                            if let Ok(encrypted) =
-                                encrypt_frame(&payload, &video_tx_keys, frame_info)
-                            {
-                                let frame = MediaFrame {
-                                    seq,
-                                    timestamp_us: seq.saturating_mul(VIDEO_FRAME_DURATION_US),
-                                    keyframe: is_keyframe,
-                                    payload: encrypted,
-                                };
-                                match video_transport.publish(&video_publish_track, frame) {
-                                    Ok(_) => {
-                                        seq = seq.saturating_add(1);
-                                        video_stats_for_thread
-                                            .tx_count
-                                            .fetch_add(1, Ordering::Relaxed);
+                            ParticipantCommand::RotateTx { track, keys, delay } => match track {
+                                CallTrack::Video => camera.rotate(keys, delay),
+                                CallTrack::Screen => {
+                                    if let Some(screen) = screen.as_mut() {
+                                        screen.rotate(keys, delay);
                                     }
-                                    Err(e) => {
-                                        if !video_publish_error_logged {
-                                            video_publish_error_logged = true;
-                                            tracing::error!("video publish failed: {e}");
-                                        }
+                                }
+                                CallTrack::Data => {
+                                    if let Some(feedback) = feedback.as_mut() {
+                                        feedback.publisher.rotate(keys, delay);
                                     }
                                 }
+                                CallTrack::Audio => {}
+                            },
+                            ParticipantCommand::RotateRx {
+                                track,
+                                participant_id,
+                                keys,
+                            } => {
+                                let ring = match track {
+                                    CallTrack::Video => peers
+                                        .iter_mut()
+                                        .find(|p| p.participant_id == participant_id)
+                                        .map(|p| &mut p.keys),
+                                    CallTrack::Screen => screens
+                                        .iter_mut()
+                                        .find(|p| p.participant_id == participant_id)
+                                        .map(|p| &mut p.keys),
+                                    CallTrack::Data => feedback
+                                        .as_mut()
+                                        .and_then(|feedback| {
+                                            feedback
+                                                .peers
+                                                .iter_mut()
+                                                .find(|p| p.participant_id == participant_id)
+                                        })
+                                        .map(|p| &mut p.keys),
+                                    CallTrack::Audio => None,
+                                };
+                                if let Some(ring) = ring {
+                                    ring.rotate(keys, KEY_ROTATION_RX_WINDOW);
+                                }
                             }
                         }
                     }
 
-                    // RX: receive remote video frames
-                    for _ in 0..4 {
-                        match video_rx.try_recv() {
-                            Ok(inbound) => match decrypt_frame(&inbound.payload, &video_rx_keys) {
-                                Ok(decrypted) => {
-                                    if !replay_window.allow(decrypted.info.group_seq) {
-                                        continue;
-                                    }
+                    let now_us = started.elapsed().as_micros() as u64;
+
+                    // Feedback about our own video: switch encoder profile or
+                    // ask it for a keyframe.
+                    if let Some(feedback) = feedback.as_mut() {
+                        if feedback.poll() {
+                            let level = feedback.rate.level();
+                            video_stats_for_thread
+                                .profile_level
+                                .store(level, Ordering::Relaxed);
+                            let profile = call_video_profile(feedback.rate.profile());
+                            with_video_receiver(&video_receiver, |receiver| {
+                                receiver.on_video_profile(call_id_for_video.clone(), profile)
+                            });
+                        }
+                        if camera_for_thread.load(Ordering::Relaxed)
+                            && feedback.rate.take_keyframe_request(now_us)
+                        {
+                            with_video_receiver(&video_receiver, |receiver| {
+                                receiver.on_keyframe_request(call_id_for_video.clone())
+                            });
+                        }
+                    }
+
+                    // TX: send platform video frames
+                    if camera_for_thread.load(Ordering::Relaxed) {
+                        while let Ok(payload) = vrx.try_recv() {
+                            let keyframe = h264_is_keyframe(&payload);
+                            match camera.publish(&video_transport, &payload, keyframe, now_us) {
+                                Ok(()) => {
                                     video_stats_for_thread
-                                        .rx_count
+                                        .tx_count
                                         .fetch_add(1, Ordering::Relaxed);
-                                    if let Some(ref receiver_lock) = video_receiver {
-                                        if let Ok(guard) = receiver_lock.read() {
-                                            if let Some(ref receiver) = *guard {
-                                                receiver.on_video_frame(
-                                                    call_id_for_video.clone(),
-                                                    decrypted.payload,
-                                                );
+                                    video_stats_for_thread
+                                        .tx_bytes
+                                        .fetch_add(payload.len() as u64, Ordering::Relaxed);
+                                }
+                                Err(e) => {
+                                    if !video_publish_error_logged {
+                                        video_publish_error_logged = true;
+                                        tracing::error!("video publish failed: {e}");
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // TX: screen share frames. Anything queued after sharing
+                    // stopped is dropped.
+                    while let Ok(payload) = srx.try_recv() {
+                        let Some(screen) = screen.as_mut() else {
+                            continue;
+                        };
+                        if !screen_sharing_for_thread.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        let keyframe = h264_is_keyframe(&payload);
+                        if let Err(e) = screen.publish(&video_transport, &payload, keyframe, now_us)
+                        {
+                            if !video_publish_error_logged {
+                                video_publish_error_logged = true;
+                                tracing::error!("screen share publish failed: {e}");
+                            }
+                        }
+                    }
+
+                    // RX: receive remote video frames. Only the focused participant is
+                    // forwarded, since platforms render a single remote stream.
+                    let focus = video_focus_for_thread
+                        .lock()
+                        .ok()
+                        .and_then(|focus| focus.clone())
+                        .filter(|id| peers.iter().any(|p| &p.participant_id == id))
+                        .or_else(|| peers.first().map(|p| p.participant_id.clone()));
+                    for peer in peers.iter_mut() {
+                        for _ in 0..4 {
+                            match peer.rx.try_recv() {
+                                Ok(inbound) => match peer.keys.decrypt(&inbound.payload) {
+                                    Ok(decrypted) => {
+                                        if !peer.replay_window.allow(decrypted.info.group_seq) {
+                                            continue;
+                                        }
+                                        video_stats_for_thread
+                                            .rx_count
+                                            .fetch_add(1, Ordering::Relaxed);
+                                        video_stats_for_thread.rx_bytes.fetch_add(
+                                            inbound.payload.len() as u64,
+                                            Ordering::Relaxed,
+                                        );
+                                        let gap = peer.stats.on_frame(
+                                            decrypted.info.group_seq,
+                                            inbound.timestamp_us,
+                                            now_us,
+                                        );
+                                        if focus.as_deref() != Some(peer.participant_id.as_str()) {
+                                            continue;
+                                        }
+                                        if forwarding != focus {
+                                            // Switching streams mid-call: the decoder
+                                            // can't start from a delta frame.
+                                            if forwarding.is_some() && !decrypted.info.keyframe {
+                                                peer.request_keyframe(feedback.as_mut(), now_us);
+                                                continue;
                                             }
+                                            forwarding = focus.clone();
                                         }
+                                        if gap && !decrypted.info.keyframe {
+                                            peer.request_keyframe(feedback.as_mut(), now_us);
+                                        }
+                                        with_video_receiver(&video_receiver, |receiver| {
+                                            receiver.on_video_frame(
+                                                call_id_for_video.clone(),
+                                                decrypted.payload,
+                                            )
+                                        });
                                     }
+                                    Err(_) => {
+                                        video_stats_for_thread
+                                            .rx_decrypt_fail
+                                            .fetch_add(1, Ordering::Relaxed);
+                                    }
+                                },
+                                Err(TryRecvError::Empty) => break,
+                                Err(TryRecvError::Disconnected) => break,
+                            }
+                        }
+                    }
+
+                    // RX: remote screen shares. One is shown at a time; once it
+                    // goes quiet the next sharer takes over from a keyframe.
+                    if screen_source.as_ref().is_some_and(|(_, last_us)| {
+                        now_us.saturating_sub(*last_us) > SCREEN_SHARE_IDLE_US
+                    }) {
+                        screen_source = None;
+                    }
+                    for peer in screens.iter_mut() {
+                        for _ in 0..4 {
+                            let Ok(inbound) = peer.rx.try_recv() else {
+                                break;
+                            };
+                            let Ok(decrypted) = peer.keys.decrypt(&inbound.payload) else {
+                                video_stats_for_thread
+                                    .rx_decrypt_fail
+                                    .fetch_add(1, Ordering::Relaxed);
+                                continue;
+                            };
+                            if !peer.replay_window.allow(decrypted.info.group_seq) {
+                                continue;
+                            }
+                            match &screen_source {
+                                Some((id, _)) if *id != peer.participant_id => continue,
+                                None if !decrypted.info.keyframe => continue,
+                                _ => {}
+                            }
+                            screen_source = Some((peer.participant_id.clone(), now_us));
+                            with_video_receiver(&video_receiver, |receiver| {
+                                receiver
+                                    .on_screen_frame(call_id_for_video.clone(), decrypted.payload)
+                            });
+                        }
+                    }
+
+                    if let Some(feedback) = feedback.as_mut() {
+                        if now_us >= feedback.next_report_us {
+                            feedback.next_report_us = now_us.saturating_add(REPORT_INTERVAL_US);
+                            for peer in peers.iter_mut() {
+                                if let Some(report) = peer.stats.take_report() {
+                                    feedback.send(
+                                        &peer.label,
+                                        VideoFeedback::Report(report),
+                                        now_us,
+                                    );
                                 }
-                                Err(_) => {
-                                    video_stats_for_thread
-                                        .rx_decrypt_fail
-                                        .fetch_add(1, Ordering::Relaxed);
-                                }
-                            },
-                            Err(TryRecvError::Empty) => break,
-                            Err(TryRecvError::Disconnected) => break,
+                            }
                         }
                     }
 
@@ -329,7 +569,14 @@ impl CallRuntime {
                 }
             });
 
-            Some((vtx, stop_for_video, camera_enabled_for_video))
+            Some(VideoWorker {
+                frame_tx: vtx,
+                screen_frame_tx: stx,
+                stop: stop_for_video,
+                camera_enabled: camera_enabled_for_video,
+                screen_sharing,
+                participants: video_participants_tx,
+            })
         } else {
             None
         };
@@ -339,11 +586,20 @@ impl CallRuntime {
         let stop_for_thread = stop.clone();
         let muted = Arc::new(AtomicBool::new(false));
         let muted_for_thread = muted.clone();
+        let audio_processing = Arc::new(AtomicBool::new(audio_processing));
+        let audio_processing_for_thread = audio_processing.clone();
         let tx_for_thread = tx.clone();
         let audio_backend_mode: Option<String> = audio_backend_mode.map(|s| s.to_owned());
         let video_stats_for_audio = video_stats_shared.clone();
+        let transport_for_audio = transport.clone();
+        let video_focus_for_audio = video_focus.clone();
+        let recorder: Arc<Mutex<Option<CallRecorder>>> = Arc::new(Mutex::new(None));
+        let recorder_for_audio = recorder.clone();
+        let mut captioner = stt_backend
+            .map(|backend| CallCaptioner::new(spawn_captions_thread(call_id, backend, tx.clone())));
         thread::spawn(move || {
-            let mut audio_backend = match AudioBackend::try_new(audio_backend_mode.as_deref()) {
+            let transport = transport_for_audio;
+            let audio_backend = match AudioBackend::try_new(audio_backend_mode.as_deref()) {
                 Ok(v) => v,
                 Err(err) => {
                     let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(InternalEvent::Toast(
@@ -352,33 +608,78 @@ impl CallRuntime {
                     AudioBackend::synthetic()
                 }
             };
+            let mut audio = CallAudio::new(
+                audio_backend,
+                audio_processing_for_thread.load(Ordering::Relaxed),
+            );
             let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
                 InternalEvent::CallRuntimeConnected {
                     call_id: call_id_owned.clone(),
                 },
             )));
 
-            let codec = OpusCodec;
+            let codec = OpusCodec::default();
             let mut seq = 0u64;
             let mut tx_frames = 0u64;
-            let mut rx_frames = 0u64;
-            let mut jitter =
-                JitterBuffer::<Vec<i16>>::with_target(JITTER_MAX_FRAMES, JITTER_TARGET_FRAMES);
+            let mut tx_bytes = 0u64;
+            let mut peers: Vec<RemoteAudio> = Vec::new();
+            let mut departed = RemoteAudioTotals::default();
             let mut tick = 0u64;
             let mut next_tick = Instant::now();
             let mut tx_counter = 0u32;
-            let mut crypto_rx_dropped = 0u64;
-            let mut replay_rx_dropped = 0u64;
             let mut tx_crypto_error_reported = false;
             let mut rx_crypto_error_reported = false;
             let mut tx_counter_exhausted = false;
             let mut tx_counter_exhausted_reported = false;
-            let mut replay_window = ReplayWindow::default();
-            let mut rx_disconnected = false;
-            let mut rx_empty_ticks = 0u64;
+            let mut speaking: Vec<String> = Vec::new();
             let runtime_start = Instant::now();
 
             while !stop_for_thread.load(Ordering::Relaxed) {
+                while let Ok(command) = audio_participants_rx.try_recv() {
+                    match command {
+                        ParticipantCommand::Add(participant) => {
+                            if let Some(pos) = peers
+                                .iter()
+                                .position(|p| p.participant_id == participant.participant_id)
+                            {
+                                departed.absorb(&peers.remove(pos));
+                            }
+                            peers.push(RemoteAudio::new(*participant));
+                        }
+                        ParticipantCommand::Remove(participant_id) => {
+                            if let Some(pos) = peers
+                                .iter()
+                                .position(|p| p.participant_id == participant_id)
+                            {
+                                departed.absorb(&peers.remove(pos));
+                            }
+                            if let Some(captioner) = captioner.as_mut() {
+                                captioner.finish_remote(&participant_id);
+                            }
+                        }
+                        // New epoch keys take effect after `delay`, so peers still
+                        // on the previous epoch keep hearing us unless a member was
+                        // removed. Only audio0 keys are sent to this thread.
+                        ParticipantCommand::RotateTx { keys, delay, .. } => {
+                            tx_keys.rotate(keys, delay)
+                        }
+                        ParticipantCommand::RotateRx {
+                            participant_id,
+                            keys,
+                            ..
+                        } => {
+                            if let Some(peer) = peers
+                                .iter_mut()
+                                .find(|p| p.participant_id == participant_id)
+                            {
+                                peer.keys.rotate(keys, KEY_ROTATION_RX_WINDOW);
+                            }
+                        }
+                    }
+                }
+
+                audio.set_processing(audio_processing_for_thread.load(Ordering::Relaxed));
+                let mut mic_frame: Option<Vec<i16>> = None;
                 if !muted_for_thread.load(Ordering::Relaxed) {
                     if tx_counter_exhausted {
                         if !tx_counter_exhausted_reported {
@@ -391,8 +692,9 @@ impl CallRuntime {
                             )));
                         }
                     } else {
-                        let pcm = audio_backend.capture_pcm_frame();
+                        let pcm = audio.capture_frame();
                         let packet = codec.encode_pcm_i16(&pcm);
+                        mic_frame = Some(pcm);
                         let frame_info = FrameInfo {
                             counter: tx_counter,
                             group_seq: seq,
@@ -404,21 +706,22 @@ impl CallRuntime {
                         } else {
                             tx_counter = tx_counter.saturating_add(1);
                         }
-                        let encrypted_payload = match encrypt_frame(&packet.0, &tx_keys, frame_info)
-                        {
-                            Ok(payload) => payload,
-                            Err(err) => {
-                                if !tx_crypto_error_reported {
-                                    tx_crypto_error_reported = true;
-                                    let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
-                                        InternalEvent::Toast(format!(
-                                            "Call media encryption failed: {err}"
-                                        )),
-                                    )));
+                        let encrypted_payload =
+                            match encrypt_frame(&packet.0, tx_keys.current(), frame_info) {
+                                Ok(payload) => payload,
+                                Err(err) => {
+                                    if !tx_crypto_error_reported {
+                                        tx_crypto_error_reported = true;
+                                        let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
+                                            InternalEvent::Toast(format!(
+                                                "Call media encryption failed: {err}"
+                                            )),
+                                        )));
+                                    }
+                                    continue;
                                 }
-                                continue;
-                            }
-                        };
+                            };
+                        let payload_len = encrypted_payload.len() as u64;
                         let frame = MediaFrame {
                             seq,
                             timestamp_us: seq.saturating_mul(FRAME_DURATION_US),
@@ -427,30 +730,41 @@ impl CallRuntime {
                         };
                         if transport.publish(&publish_track, frame).is_ok() {
                             tx_frames = tx_frames.saturating_add(1);
+                            tx_bytes = tx_bytes.saturating_add(payload_len);
                             seq = seq.saturating_add(1);
                         }
                     }
+                } else if audio.processing {
+                    // Keep the echo canceller's capture timeline in step with playback.
+                    audio.capture_frame();
                 }
 
-                let mut got_frame_this_tick = false;
-                if !rx_disconnected {
-                    for _ in 0..MAX_RX_FRAMES_PER_TICK {
-                        match rx.try_recv() {
-                            Ok(inbound) => {
-                                got_frame_this_tick = true;
-                                match decrypt_frame(&inbound.payload, &rx_keys) {
+                let arrival_us = runtime_start.elapsed().as_micros() as u64;
+                let mut decoded: Vec<Vec<i16>> = Vec::with_capacity(peers.len());
+                for peer in peers.iter_mut() {
+                    if !peer.rx_disconnected {
+                        for _ in 0..MAX_RX_FRAMES_PER_TICK {
+                            match peer.rx.try_recv() {
+                                Ok(inbound) => match peer.keys.decrypt(&inbound.payload) {
                                     Ok(decrypted) => {
-                                        if !replay_window.allow(decrypted.info.group_seq) {
-                                            replay_rx_dropped = replay_rx_dropped.saturating_add(1);
+                                        if !peer.replay_window.allow(decrypted.info.group_seq) {
+                                            peer.replay_dropped =
+                                                peer.replay_dropped.saturating_add(1);
                                             continue;
                                         }
-                                        rx_frames = rx_frames.saturating_add(1);
-                                        let pcm =
-                                            codec.decode_to_pcm_i16(&OpusPacket(decrypted.payload));
-                                        let _ = jitter.push(pcm);
+                                        peer.rx_frames = peer.rx_frames.saturating_add(1);
+                                        peer.rx_bytes = peer
+                                            .rx_bytes
+                                            .saturating_add(inbound.payload.len() as u64);
+                                        let _ = peer.jitter.push(
+                                            decrypted.info.group_seq,
+                                            inbound.timestamp_us,
+                                            arrival_us,
+                                            OpusPacket(decrypted.payload),
+                                        );
                                     }
                                     Err(err) => {
-                                        crypto_rx_dropped = crypto_rx_dropped.saturating_add(1);
+                                        peer.crypto_dropped = peer.crypto_dropped.saturating_add(1);
                                         if !rx_crypto_error_reported {
                                             rx_crypto_error_reported = true;
                                             let _ = tx_for_thread.send(CoreMsg::Internal(
@@ -460,51 +774,125 @@ impl CallRuntime {
                                             ));
                                         }
                                     }
+                                },
+                                Err(TryRecvError::Empty) => break,
+                                Err(TryRecvError::Disconnected) => {
+                                    peer.rx_disconnected = true;
+                                    let elapsed = runtime_start.elapsed();
+                                    let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
+                                        InternalEvent::Toast(format!(
+                                            "Call rx channel disconnected after {:.1}s (rx={}, crypto_drop={})",
+                                            elapsed.as_secs_f64(),
+                                            peer.rx_frames,
+                                            peer.crypto_dropped
+                                        )),
+                                    )));
+                                    break;
                                 }
                             }
-                            Err(TryRecvError::Empty) => break,
-                            Err(TryRecvError::Disconnected) => {
-                                rx_disconnected = true;
-                                let elapsed = runtime_start.elapsed();
-                                let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
-                                    InternalEvent::Toast(format!(
-                                        "Call rx channel disconnected after {:.1}s (rx={rx_frames}, crypto_drop={crypto_rx_dropped})",
-                                        elapsed.as_secs_f64()
-                                    )),
-                                )));
-                                break;
+                        }
+                    }
+                    if let Some(playout) = peer.jitter.pop_for_playout() {
+                        let pcm = match playout {
+                            Playout::Frame(packet) => {
+                                let pcm = peer.codec.decode_to_pcm_i16(&packet);
+                                if pcm_level(&pcm) >= SPEAKING_LEVEL_THRESHOLD {
+                                    peer.speaking_until_tick =
+                                        tick.saturating_add(SPEAKING_HOLD_TICKS);
+                                }
+                                pcm
                             }
+                            Playout::Conceal {
+                                next: Some(next), ..
+                            } => peer.codec.recover_from_next_pcm_i16(&next),
+                            Playout::Conceal { run, .. } => peer.codec.conceal_pcm_i16(run),
+                        };
+                        if let Some(captioner) = captioner.as_mut() {
+                            captioner.push_remote(&peer.participant_id, &pcm);
                         }
+                        decoded.push(pcm);
                     }
                 }
-                if !got_frame_this_tick && !rx_disconnected {
-                    rx_empty_ticks = rx_empty_ticks.saturating_add(1);
-                } else if got_frame_this_tick {
-                    rx_empty_ticks = 0;
+                let remote_mix = (!decoded.is_empty()).then(|| mix_pcm_frames(&decoded));
+                audio.play_frame(remote_mix.as_deref());
+                if let Ok(mut recorder) = recorder_for_audio.lock() {
+                    if let Some(recorder) = recorder.as_mut() {
+                        recorder.record(mic_frame.as_deref(), remote_mix.as_deref());
+                    }
                 }
-                if let Some(playback_pcm) = jitter.pop_for_playout() {
-                    audio_backend.play_pcm_frame(&playback_pcm);
+                if let (Some(captioner), Some(mic)) = (captioner.as_mut(), mic_frame.as_deref()) {
+                    captioner.push_local(mic);
+                }
+
+                let now_speaking: Vec<String> = peers
+                    .iter()
+                    .filter(|p| p.speaking_until_tick > tick)
+                    .map(|p| p.participant_id.clone())
+                    .collect();
+                if now_speaking != speaking {
+                    if let Some(started) = now_speaking.iter().find(|id| !speaking.contains(id)) {
+                        if let Ok(mut focus) = video_focus_for_audio.lock() {
+                            *focus = Some(started.clone());
+                        }
+                    }
+                    speaking = now_speaking;
+                    let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
+                        InternalEvent::CallParticipantsSpeaking {
+                            call_id: call_id_owned.clone(),
+                            participant_ids: speaking.clone(),
+                        },
+                    )));
                 }
 
                 tick = tick.saturating_add(1);
                 if tick.is_multiple_of(STATS_EMIT_INTERVAL_TICKS) {
+                    let mut totals = departed.clone();
+                    for peer in &peers {
+                        totals.absorb(peer);
+                    }
                     let _ = tx_for_thread.send(CoreMsg::Internal(Box::new(
                         InternalEvent::CallRuntimeStats {
                             call_id: call_id_owned.clone(),
                             tx_frames,
-                            rx_frames,
-                            rx_dropped: jitter
-                                .dropped()
-                                .saturating_add(crypto_rx_dropped)
-                                .saturating_add(replay_rx_dropped),
-                            jitter_buffer_ms: (jitter.len() as u32)
+                            rx_frames: totals.rx_frames,
+                            rx_dropped: totals.dropped,
+                            jitter_buffer_ms: peers
+                                .iter()
+                                .map(|p| p.jitter.len() as u32)
+                                .max()
+                                .unwrap_or(0)
+                                .saturating_mul(FRAME_DURATION_MS),
+                            jitter_target_ms: peers
+                                .iter()
+                                .map(|p| p.jitter.target_frames() as u32)
+                                .max()
+                                .unwrap_or(0)
                                 .saturating_mul(FRAME_DURATION_MS),
+                            rx_jitter_ms: peers
+                                .iter()
+                                .map(|p| (p.jitter.stats().jitter_us / 1_000) as u32)
+                                .max()
+                                .unwrap_or(0),
+                            rx_reordered: totals.reordered,
+                            rx_late: totals.late,
+                            rx_concealed: totals.concealed,
+                            tx_bytes: tx_bytes.saturating_add(
+                                video_stats_for_audio.tx_bytes.load(Ordering::Relaxed),
+                            ),
+                            rx_bytes: totals.rx_bytes.saturating_add(
+                                video_stats_for_audio.rx_bytes.load(Ordering::Relaxed),
+                            ),
                             last_rtt_ms: None,
                             video_tx: video_stats_for_audio.tx_count.load(Ordering::Relaxed),
                             video_rx: video_stats_for_audio.rx_count.load(Ordering::Relaxed),
                             video_rx_decrypt_fail: video_stats_for_audio
                                 .rx_decrypt_fail
                                 .load(Ordering::Relaxed),
+                            video_profile: has_video.then(|| {
+                                let level =
+                                    video_stats_for_audio.profile_level.load(Ordering::Relaxed);
+                                call_video_profile(VIDEO_PROFILES[level])
+                            }),
                         },
                     )));
                 }
@@ -517,53 +905,196 @@ impl CallRuntime {
                     next_tick = now;
                 }
             }
+            if let Some(captioner) = captioner.as_mut() {
+                captioner.finish();
+            }
         });
 
-        let (video_sender, video_stop, camera_enabled) = match video_frame_tx {
-            Some((sender, vstop, cam)) => (Some(sender), Some(vstop), cam),
-            None => (None, None, Arc::new(AtomicBool::new(false))),
+        let mut worker = CallWorker {
+            stop,
+            muted,
+            audio_processing,
+            video,
+            transport,
+            session: session.clone(),
+            audio_participants: audio_participants_tx,
+            media_crypto: HashMap::new(),
+            recorder,
         };
-
-        self.workers.insert(
-            call_id.to_string(),
-            CallWorker {
-                stop,
-                muted,
-                camera_enabled,
-                video_stop,
-                video_frame_tx: video_sender,
-            },
-        );
+        worker.subscribe_participant(peer_id, &media_crypto)?;
+        self.workers.insert(call_id.to_string(), worker);
         Ok(())
     }
 
+    /// Starts receiving a group call member who joined after the call connected.
+    pub(super) fn add_participant(
+        &mut self,
+        call_id: &str,
+        participant_id: &str,
+        media_crypto: &CallMediaCryptoContext,
+    ) -> Result<(), String> {
+        let Some(worker) = self.workers.get_mut(call_id) else {
+            return Err("no media worker for call".to_string());
+        };
+        worker.subscribe_participant(participant_id, media_crypto)
+    }
+
+    pub(super) fn remove_participant(&mut self, call_id: &str, participant_id: &str) {
+        if let Some(worker) = self.workers.get_mut(call_id) {
+            worker.media_crypto.remove(participant_id);
+            let _ = worker
+                .audio_participants
+                .send(ParticipantCommand::Remove(participant_id.to_string()));
+            if let Some(video) = &worker.video {
+                let _ = video
+                    .participants
+                    .send(ParticipantCommand::Remove(participant_id.to_string()));
+            }
+        }
+    }
+
+    /// Media keys each remote participant of the call is currently using.
+    pub(super) fn participant_media_crypto(
+        &self,
+        call_id: &str,
+    ) -> Vec<(String, CallMediaCryptoContext)> {
+        self.workers
+            .get(call_id)
+            .map(|worker| {
+                worker
+                    .media_crypto
+                    .iter()
+                    .map(|(id, crypto)| (id.clone(), crypto.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Switches a running call to keys derived from a new MLS epoch. Outgoing
+    /// frames move to the new generation after `KEY_ROTATION_OVERLAP`, or right
+    /// away when the commit removed a member so they cannot keep listening;
+    /// inbound frames sealed under the previous one are accepted for
+    /// `KEY_ROTATION_RX_WINDOW`.
+    pub(super) fn rotate_media_keys(
+        &mut self,
+        call_id: &str,
+        rotated: Vec<(String, CallMediaCryptoContext)>,
+        member_removed: bool,
+    ) {
+        let Some(worker) = self.workers.get_mut(call_id) else {
+            return;
+        };
+        let delay = tx_rotation_delay(member_removed);
+        // Sender keys only depend on the local member, so any entry carries them.
+        if let Some((_, crypto)) = rotated.first() {
+            let _ = worker
+                .audio_participants
+                .send(ParticipantCommand::RotateTx {
+                    track: CallTrack::Audio,
+                    keys: crypto.tx_keys.clone(),
+                    delay,
+                });
+            if let Some(video) = &worker.video {
+                for track in CallTrack::VIDEO_THREAD {
+                    if let Some(keys) = track.tx_keys(crypto) {
+                        let _ = video.participants.send(ParticipantCommand::RotateTx {
+                            track,
+                            keys,
+                            delay,
+                        });
+                    }
+                }
+            }
+        }
+        for (participant_id, crypto) in rotated {
+            let _ = worker
+                .audio_participants
+                .send(ParticipantCommand::RotateRx {
+                    track: CallTrack::Audio,
+                    participant_id: participant_id.clone(),
+                    keys: crypto.rx_keys.clone(),
+                });
+            if let Some(video) = &worker.video {
+                for track in CallTrack::VIDEO_THREAD {
+                    if let Some(keys) = track.rx_keys(&crypto) {
+                        let _ = video.participants.send(ParticipantCommand::RotateRx {
+                            track,
+                            participant_id: participant_id.clone(),
+                            keys,
+                        });
+                    }
+                }
+            }
+            worker.media_crypto.insert(participant_id, crypto);
+        }
+    }
+
     pub(super) fn set_muted(&mut self, call_id: &str, muted: bool) {
         if let Some(worker) = self.workers.get(call_id) {
             worker.muted.store(muted, Ordering::Relaxed);
         }
     }
 
+    pub(super) fn set_audio_processing(&mut self, call_id: &str, enabled: bool) {
+        if let Some(worker) = self.workers.get(call_id) {
+            worker.audio_processing.store(enabled, Ordering::Relaxed);
+        }
+    }
+
     pub(super) fn set_camera_enabled(&mut self, call_id: &str, enabled: bool) {
         if let Some(worker) = self.workers.get(call_id) {
-            worker.camera_enabled.store(enabled, Ordering::Relaxed);
+            if let Some(video) = &worker.video {
+                video.camera_enabled.store(enabled, Ordering::Relaxed);
+            }
         }
     }
 
+    pub(super) fn set_screen_sharing(&mut self, call_id: &str, sharing: bool) {
+        if let Some(video) = self.workers.get(call_id).and_then(|w| w.video.as_ref()) {
+            video.screen_sharing.store(sharing, Ordering::Relaxed);
+        }
+    }
+
+    /// Starts mixing the call into a recording. Returns false if the call has
+    /// no running media worker.
+    pub(super) fn start_recording(&mut self, call_id: &str) -> bool {
+        let Some(worker) = self.workers.get(call_id) else {
+            return false;
+        };
+        if let Ok(mut recorder) = worker.recorder.lock() {
+            recorder.get_or_insert_with(CallRecorder::new);
+        }
+        true
+    }
+
+    /// Stops the call's recording and returns the finished Ogg Opus file.
+    pub(super) fn stop_recording(&mut self, call_id: &str) -> Option<Vec<u8>> {
+        let worker = self.workers.get(call_id)?;
+        let recorder = worker.recorder.lock().ok()?.take()?;
+        Some(recorder.writer.finish())
+    }
+
     pub(super) fn send_video_frame(&self, call_id: &str, payload: Vec<u8>) {
         if let Some(worker) = self.workers.get(call_id) {
-            if let Some(ref tx) = worker.video_frame_tx {
-                let _ = tx.send(payload);
+            if let Some(video) = &worker.video {
+                let _ = video.frame_tx.send(payload);
             } else {
                 tracing::warn!(call_id, "send_video_frame: no video_frame_tx channel");
             }
         }
     }
 
+    pub(super) fn send_screen_frame(&self, call_id: &str, payload: Vec<u8>) {
+        if let Some(video) = self.workers.get(call_id).and_then(|w| w.video.as_ref()) {
+            let _ = video.screen_frame_tx.send(payload);
+        }
+    }
+
     pub(super) fn on_call_ended(&mut self, call_id: &str) {
         if let Some(worker) = self.workers.remove(call_id) {
             worker.stop.store(true, Ordering::Relaxed);
-            if let Some(vstop) = &worker.video_stop {
-                vstop.store(true, Ordering::Relaxed);
+            if let Some(video) = &worker.video {
+                video.stop.store(true, Ordering::Relaxed);
             }
         }
     }
@@ -576,10 +1107,601 @@ impl CallRuntime {
     }
 }
 
+impl CallWorker {
+    /// Subscribes to every track in the participant's catalog and hands the
+    /// subscriptions to the audio/video threads.
+    fn subscribe_participant(
+        &mut self,
+        participant_id: &str,
+        media_crypto: &CallMediaCryptoContext,
+    ) -> Result<(), String> {
+        let catalog = TrackCatalog {
+            broadcast_path: broadcast_path(
+                &self.session.broadcast_base,
+                &media_crypto.peer_participant_label,
+            )?,
+            tracks: self.session.tracks.iter().map(TrackSpec::from).collect(),
+        };
+        for track in &catalog.tracks {
+            let address = TrackAddress {
+                broadcast_path: catalog.broadcast_path.clone(),
+                track_name: track.name.clone(),
+            };
+            let Some(kind) = CallTrack::from_name(&track.name) else {
+                continue;
+            };
+            let commands = match (kind, &self.video) {
+                (CallTrack::Audio, _) => &self.audio_participants,
+                (_, Some(video)) => &video.participants,
+                (_, None) => continue,
+            };
+            let Some(keys) = kind.rx_keys(media_crypto) else {
+                continue;
+            };
+            let rx = self
+                .transport
+                .subscribe(&address)
+                .map_err(to_string_error)?;
+            commands
+                .send(ParticipantCommand::Add(Box::new(RemoteParticipant {
+                    participant_id: participant_id.to_string(),
+                    label: media_crypto.peer_participant_label.clone(),
+                    track: kind,
+                    rx,
+                    keys,
+                })))
+                .map_err(|_| "call media worker stopped".to_string())?;
+        }
+        self.media_crypto
+            .insert(participant_id.to_string(), media_crypto.clone());
+        Ok(())
+    }
+}
+
+/// Receive side of one remote participant's track, handed to a media thread.
+struct RemoteParticipant {
+    participant_id: String,
+    /// Opaque broadcast label; video feedback addresses senders by it.
+    label: String,
+    track: CallTrack,
+    rx: MediaFrameSubscription,
+    keys: FrameKeyMaterial,
+}
+
+/// A call track we know how to handle. `audio0` goes to the audio thread,
+/// everything else to the video thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallTrack {
+    Audio,
+    Video,
+    Screen,
+    /// Receiver feedback on our video.
+    Data,
+}
+
+impl CallTrack {
+    const VIDEO_THREAD: [Self; 3] = [Self::Video, Self::Screen, Self::Data];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "audio0" => Some(Self::Audio),
+            "video0" => Some(Self::Video),
+            "screen0" => Some(Self::Screen),
+            "data0" => Some(Self::Data),
+            _ => None,
+        }
+    }
+
+    fn tx_keys(self, crypto: &CallMediaCryptoContext) -> Option<FrameKeyMaterial> {
+        match self {
+            Self::Audio => Some(crypto.tx_keys.clone()),
+            Self::Video => crypto.video_tx_keys.clone(),
+            Self::Screen => crypto.screen_tx_keys.clone(),
+            Self::Data => crypto.data_tx_keys.clone(),
+        }
+    }
+
+    fn rx_keys(self, crypto: &CallMediaCryptoContext) -> Option<FrameKeyMaterial> {
+        match self {
+            Self::Audio => Some(crypto.rx_keys.clone()),
+            Self::Video => crypto.video_rx_keys.clone(),
+            Self::Screen => crypto.screen_rx_keys.clone(),
+            Self::Data => crypto.data_rx_keys.clone(),
+        }
+    }
+}
+
+enum ParticipantCommand {
+    Add(Box<RemoteParticipant>),
+    Remove(String),
+    RotateTx {
+        track: CallTrack,
+        keys: FrameKeyMaterial,
+        delay: Duration,
+    },
+    RotateRx {
+        track: CallTrack,
+        participant_id: String,
+        keys: FrameKeyMaterial,
+    },
+}
+
+/// Sending side of one of our tracks: seals each frame under the track's keys
+/// with its own frame counter.
+struct TrackPublisher {
+    address: TrackAddress,
+    keys: FrameKeySchedule,
+    tx_counter: u32,
+    seq: u64,
+}
+
+impl TrackPublisher {
+    fn new(address: TrackAddress, keys: FrameKeyMaterial) -> Self {
+        Self {
+            address,
+            keys: FrameKeySchedule::new(keys),
+            tx_counter: 0,
+            seq: 0,
+        }
+    }
+
+    fn rotate(&mut self, keys: FrameKeyMaterial, delay: Duration) {
+        self.keys.rotate(keys, delay);
+    }
+
+    fn publish(
+        &mut self,
+        transport: &MediaTransport,
+        payload: &[u8],
+        keyframe: bool,
+        timestamp_us: u64,
+    ) -> Result<(), String> {
+        // The counter is the AES-GCM nonce; never seal two frames with one.
+        if self.tx_counter == u32::MAX {
+            return Err("frame counter exhausted".to_string());
+        }
+        let frame_info = FrameInfo {
+            counter: self.tx_counter,
+            group_seq: self.seq,
+            frame_idx: 0,
+            keyframe,
+        };
+        self.tx_counter += 1;
+        let encrypted =
+            encrypt_frame(payload, self.keys.current(), frame_info).map_err(|e| e.to_string())?;
+        let frame = MediaFrame {
+            seq: self.seq,
+            timestamp_us,
+            keyframe,
+            payload: encrypted,
+        };
+        transport
+            .publish(&self.address, frame)
+            .map_err(to_string_error)?;
+        self.seq = self.seq.saturating_add(1);
+        Ok(())
+    }
+}
+
+struct RemoteVideo {
+    participant_id: String,
+    label: String,
+    rx: MediaFrameSubscription,
+    keys: FrameKeyRing,
+    replay_window: ReplayWindow,
+    stats: ReceiverStats,
+    last_keyframe_request_us: Option<u64>,
+}
+
+impl RemoteVideo {
+    fn new(participant: RemoteParticipant) -> Self {
+        Self {
+            participant_id: participant.participant_id,
+            label: participant.label,
+            rx: participant.rx,
+            keys: FrameKeyRing::new(participant.keys),
+            replay_window: ReplayWindow::default(),
+            stats: ReceiverStats::new(),
+            last_keyframe_request_us: None,
+        }
+    }
+
+    /// Asks this sender for a keyframe, at most once per
+    /// `KEYFRAME_REQUEST_INTERVAL_US`.
+    fn request_keyframe(&mut self, feedback: Option<&mut VideoFeedbackChannel>, now_us: u64) {
+        let Some(feedback) = feedback else {
+            return;
+        };
+        if self
+            .last_keyframe_request_us
+            .is_some_and(|last| now_us.saturating_sub(last) < KEYFRAME_REQUEST_INTERVAL_US)
+        {
+            return;
+        }
+        self.last_keyframe_request_us = Some(now_us);
+        feedback.send(&self.label, VideoFeedback::KeyframeRequest, now_us);
+    }
+}
+
+/// Video rate control over the call data track. We report what we receive to
+/// each sender, and their reports about our video pick the profile the
+/// platform encoder should use.
+struct VideoFeedbackChannel {
+    transport: Arc<MediaTransport>,
+    publisher: TrackPublisher,
+    local_label: String,
+    peers: Vec<RemoteFeedback>,
+    rate: VideoRateController,
+    next_report_us: u64,
+}
+
+struct RemoteFeedback {
+    participant_id: String,
+    rx: MediaFrameSubscription,
+    keys: FrameKeyRing,
+    replay_window: ReplayWindow,
+}
+
+impl VideoFeedbackChannel {
+    fn new(publisher: TrackPublisher, local_label: String, transport: Arc<MediaTransport>) -> Self {
+        Self {
+            transport,
+            publisher,
+            local_label,
+            peers: Vec::new(),
+            rate: VideoRateController::new(),
+            next_report_us: REPORT_INTERVAL_US,
+        }
+    }
+
+    fn add_peer(&mut self, participant: RemoteParticipant) {
+        self.peers
+            .retain(|p| p.participant_id != participant.participant_id);
+        self.peers.push(RemoteFeedback {
+            participant_id: participant.participant_id,
+            rx: participant.rx,
+            keys: FrameKeyRing::new(participant.keys),
+            replay_window: ReplayWindow::default(),
+        });
+    }
+
+    /// Publishes feedback about `sender`'s video.
+    fn send(&mut self, sender: &str, feedback: VideoFeedback, now_us: u64) {
+        let payload = FeedbackMessage {
+            sender: sender.to_string(),
+            feedback,
+        }
+        .encode();
+        let _ = self
+            .publisher
+            .publish(&self.transport, &payload, true, now_us);
+    }
+
+    /// Applies feedback about our own video. Returns true when the profile
+    /// changed.
+    fn poll(&mut self) -> bool {
+        let mut changed = false;
+        for peer in self.peers.iter_mut() {
+            while let Ok(inbound) = peer.rx.try_recv() {
+                let Ok(decrypted) = peer.keys.decrypt(&inbound.payload) else {
+                    continue;
+                };
+                if !peer.replay_window.allow(decrypted.info.group_seq) {
+                    continue;
+                }
+                let Ok(message) = FeedbackMessage::decode(&decrypted.payload) else {
+                    continue;
+                };
+                if message.sender != self.local_label {
+                    continue;
+                }
+                match message.feedback {
+                    VideoFeedback::Report(report) => changed |= self.rate.on_report(&report),
+                    VideoFeedback::KeyframeRequest => self.rate.on_keyframe_request(),
+                }
+            }
+        }
+        changed
+    }
+}
+
+fn call_video_profile(profile: VideoProfile) -> CallVideoProfile {
+    CallVideoProfile {
+        width: profile.width,
+        height: profile.height,
+        fps: profile.fps,
+        bitrate_bps: profile.bitrate_bps,
+    }
+}
+
+fn with_video_receiver(
+    receiver: &Option<SharedVideoFrameReceiver>,
+    f: impl FnOnce(&dyn VideoFrameReceiver),
+) {
+    if let Some(receiver_lock) = receiver {
+        if let Ok(guard) = receiver_lock.read() {
+            if let Some(receiver) = guard.as_ref() {
+                f(receiver.as_ref());
+            }
+        }
+    }
+}
+
+/// Per-participant audio receive state. Each sender gets its own jitter buffer
+/// and decoder so loss/reordering on one stream doesn't disturb the others.
+struct RemoteAudio {
+    participant_id: String,
+    rx: MediaFrameSubscription,
+    keys: FrameKeyRing,
+    codec: OpusCodec,
+    jitter: AdaptiveJitterBuffer<OpusPacket>,
+    replay_window: ReplayWindow,
+    rx_frames: u64,
+    rx_bytes: u64,
+    crypto_dropped: u64,
+    replay_dropped: u64,
+    rx_disconnected: bool,
+    speaking_until_tick: u64,
+}
+
+impl RemoteAudio {
+    fn new(participant: RemoteParticipant) -> Self {
+        Self {
+            participant_id: participant.participant_id,
+            rx: participant.rx,
+            keys: FrameKeyRing::new(participant.keys),
+            codec: OpusCodec::default(),
+            jitter: AdaptiveJitterBuffer::new(
+                FRAME_DURATION_US,
+                JITTER_MIN_TARGET_FRAMES,
+                JITTER_MAX_TARGET_FRAMES,
+                JITTER_MAX_FRAMES,
+            ),
+            replay_window: ReplayWindow::default(),
+            rx_frames: 0,
+            rx_bytes: 0,
+            crypto_dropped: 0,
+            replay_dropped: 0,
+            rx_disconnected: false,
+            speaking_until_tick: 0,
+        }
+    }
+}
+
+/// Receive counters summed across participants, including ones that already left.
+#[derive(Debug, Default, Clone)]
+struct RemoteAudioTotals {
+    rx_frames: u64,
+    rx_bytes: u64,
+    dropped: u64,
+    reordered: u64,
+    late: u64,
+    concealed: u64,
+}
+
+impl RemoteAudioTotals {
+    fn absorb(&mut self, peer: &RemoteAudio) {
+        let stats = peer.jitter.stats();
+        self.rx_frames = self.rx_frames.saturating_add(peer.rx_frames);
+        self.rx_bytes = self.rx_bytes.saturating_add(peer.rx_bytes);
+        self.dropped = self
+            .dropped
+            .saturating_add(stats.dropped)
+            .saturating_add(peer.crypto_dropped)
+            .saturating_add(peer.replay_dropped);
+        self.reordered = self.reordered.saturating_add(stats.reordered);
+        self.late = self.late.saturating_add(stats.late);
+        self.concealed = self.concealed.saturating_add(stats.concealed);
+    }
+}
+
+/// Mean absolute sample value of a PCM frame.
+fn pcm_level(pcm: &[i16]) -> u32 {
+    if pcm.is_empty() {
+        return 0;
+    }
+    let sum: u64 = pcm.iter().map(|s| s.unsigned_abs() as u64).sum();
+    (sum / pcm.len() as u64) as u32
+}
+
+/// Sums per-participant frames into one playback frame, saturating at i16 range.
+fn mix_pcm_frames(frames: &[Vec<i16>]) -> Vec<i16> {
+    if let [only] = frames {
+        return only.clone();
+    }
+    let len = frames.iter().map(Vec::len).max().unwrap_or(0);
+    let mut mixed = vec![0i32; len];
+    for frame in frames {
+        for (acc, sample) in mixed.iter_mut().zip(frame) {
+            *acc += *sample as i32;
+        }
+    }
+    mixed
+        .into_iter()
+        .map(|s| s.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+        .collect()
+}
+
+/// Mixes our mic with everyone we hear into one Ogg Opus stream. Runs on the
+/// audio thread; encoding is separate from the call's own encoder so the file
+/// doesn't carry FEC meant for the network.
+struct CallRecorder {
+    codec: OpusCodec,
+    writer: OggOpusWriter,
+}
+
+impl CallRecorder {
+    fn new() -> Self {
+        let codec = OpusCodec::new(OpusConfig {
+            inband_fec: false,
+            expected_loss_percent: 0,
+            ..OpusConfig::default()
+        })
+        .unwrap_or_default();
+        Self {
+            codec,
+            writer: OggOpusWriter::new(1, 1, SAMPLE_RATE),
+        }
+    }
+
+    /// Appends one 20ms tick. Missing sides (muted mic, nobody talking) are silence.
+    fn record(&mut self, mic: Option<&[i16]>, remote: Option<&[i16]>) {
+        let pcm = match (mic, remote) {
+            (Some(mic), Some(remote)) => mix_pcm_frames(&[mic.to_vec(), remote.to_vec()]),
+            (Some(pcm), None) | (None, Some(pcm)) => pcm.to_vec(),
+            (None, None) => SILENT_FRAME.to_vec(),
+        };
+        let packet = self.codec.encode_pcm_i16(&pcm);
+        self.writer.push_packet(&packet.0, FRAME_SAMPLES as u32);
+    }
+}
+
+/// Cuts our mic and each remote participant into speech segments for the
+/// captions thread. Runs on the audio thread; transcription doesn't.
+struct CallCaptioner {
+    local: SpeechSegmenter,
+    remote: HashMap<String, SpeechSegmenter>,
+    segments: std::sync::mpsc::Sender<(Option<String>, SpeechSegment)>,
+}
+
+impl CallCaptioner {
+    fn new(segments: std::sync::mpsc::Sender<(Option<String>, SpeechSegment)>) -> Self {
+        Self {
+            local: SpeechSegmenter::new(SAMPLE_RATE, 1),
+            remote: HashMap::new(),
+            segments,
+        }
+    }
+
+    fn push_local(&mut self, pcm: &[i16]) {
+        self.local.push(pcm);
+        if let Some(segment) = self.local.pop_segment() {
+            let _ = self.segments.send((None, segment));
+        }
+    }
+
+    fn push_remote(&mut self, participant_id: &str, pcm: &[i16]) {
+        let segmenter = self
+            .remote
+            .entry(participant_id.to_string())
+            .or_insert_with(|| SpeechSegmenter::new(SAMPLE_RATE, 1));
+        segmenter.push(pcm);
+        if let Some(segment) = segmenter.pop_segment() {
+            let _ = self
+                .segments
+                .send((Some(participant_id.to_string()), segment));
+        }
+    }
+
+    /// Sends what a departing participant was still saying.
+    fn finish_remote(&mut self, participant_id: &str) {
+        if let Some(segment) = self
+            .remote
+            .remove(participant_id)
+            .and_then(|mut segmenter| segmenter.flush())
+        {
+            let _ = self
+                .segments
+                .send((Some(participant_id.to_string()), segment));
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Some(segment) = self.local.flush() {
+            let _ = self.segments.send((None, segment));
+        }
+        let ids: Vec<String> = self.remote.keys().cloned().collect();
+        for id in ids {
+            self.finish_remote(&id);
+        }
+    }
+}
+
+/// Transcribes segments until the audio thread drops its sender, so the tail
+/// of the call is still captioned after hang-up.
+fn spawn_captions_thread(
+    call_id: &str,
+    mut backend: Box<dyn SttBackend>,
+    tx: Sender<CoreMsg>,
+) -> std::sync::mpsc::Sender<(Option<String>, SpeechSegment)> {
+    let (segments, rx) = std::sync::mpsc::channel::<(Option<String>, SpeechSegment)>();
+    let call_id = call_id.to_string();
+    thread::spawn(move || {
+        let mut error_reported = false;
+        for (participant_id, segment) in rx {
+            match backend.transcribe(&segment) {
+                Ok(text) if text.trim().is_empty() => {}
+                Ok(text) => {
+                    let _ = tx.send(CoreMsg::Internal(Box::new(InternalEvent::CallCaption {
+                        call_id: call_id.clone(),
+                        participant_id,
+                        text: text.trim().to_string(),
+                    })));
+                }
+                Err(err) => {
+                    if !error_reported {
+                        error_reported = true;
+                        let _ = tx.send(CoreMsg::Internal(Box::new(InternalEvent::Toast(
+                            format!("Call captions failed: {err}"),
+                        ))));
+                    }
+                }
+            }
+        }
+    });
+    segments
+}
+
 fn to_string_error(err: MediaSessionError) -> String {
     err.to_string()
 }
 
+/// The call's audio device plus the capture processing stage, keeping the echo
+/// reference in step with what the device actually plays.
+#[derive(Debug)]
+struct CallAudio {
+    backend: AudioBackend,
+    processor: AudioProcessor,
+    processing: bool,
+}
+
+impl CallAudio {
+    fn new(backend: AudioBackend, processing: bool) -> Self {
+        Self {
+            backend,
+            processor: AudioProcessor::default(),
+            processing,
+        }
+    }
+
+    fn set_processing(&mut self, enabled: bool) {
+        if enabled && !self.processing {
+            // The echo path may have changed while processing was off.
+            self.processor = AudioProcessor::default();
+        }
+        self.processing = enabled;
+    }
+
+    fn capture_frame(&mut self) -> Vec<i16> {
+        let mut pcm = self.backend.capture_pcm_frame();
+        if self.processing {
+            self.processor.process_capture(&mut pcm);
+        }
+        pcm
+    }
+
+    /// Plays one tick of remote audio; `None` when nothing was due.
+    fn play_frame(&mut self, pcm: Option<&[i16]>) {
+        if let Some(pcm) = pcm {
+            self.backend.play_pcm_frame(pcm);
+        }
+        if self.processing {
+            self.processor
+                .push_render_frame(pcm.unwrap_or(&SILENT_FRAME));
+        }
+    }
+}
+
 #[derive(Debug)]
 enum AudioBackend {
     Synthetic(SyntheticAudio),
@@ -633,6 +1755,37 @@ struct SyntheticAudio {
     /// Pre-loaded PCM at 48kHz mono, read sequentially and looped.
     fixture_pcm: Option<Vec<i16>>,
     fixture_pos: usize,
+    echo: Option<SyntheticEcho>,
+}
+
+/// Simulated speaker-to-microphone leak: whatever was played comes back into
+/// capture at half amplitude after a fixed delay.
+#[derive(Debug)]
+struct SyntheticEcho {
+    /// Played audio not yet "emitted" by the simulated speaker.
+    playback: std::collections::VecDeque<i16>,
+    /// Emitted audio still travelling to the microphone.
+    path: std::collections::VecDeque<i16>,
+}
+
+impl SyntheticEcho {
+    fn new(delay_ms: u32) -> Self {
+        let delay_samples = (SAMPLE_RATE / 1_000 * delay_ms) as usize;
+        Self {
+            playback: std::collections::VecDeque::new(),
+            path: std::iter::repeat_n(0, delay_samples).collect(),
+        }
+    }
+
+    /// Echo to add to the next capture frame. The speaker emits one frame per
+    /// capture tick, silence if nothing was played.
+    fn next_frame(&mut self) -> Vec<i16> {
+        for _ in 0..FRAME_SAMPLES {
+            let emitted = self.playback.pop_front().unwrap_or(0);
+            self.path.push_back(emitted / 2);
+        }
+        self.path.drain(..FRAME_SAMPLES).collect()
+    }
 }
 
 impl SyntheticAudio {
@@ -640,11 +1793,19 @@ impl SyntheticAudio {
         let fixture_pcm = std::env::var("PIKA_AUDIO_FIXTURE")
             .ok()
             .and_then(|path| Self::load_wav_fixture(&path));
+        let echo_delay_ms = std::env::var("PIKA_AUDIO_ECHO_MS")
+            .ok()
+            .and_then(|ms| ms.trim().parse().ok());
+        Self::with_fixture(fixture_pcm, echo_delay_ms)
+    }
+
+    fn with_fixture(fixture_pcm: Option<Vec<i16>>, echo_delay_ms: Option<u32>) -> Self {
         Self {
             phase: 0.0,
             sample_counter: 0,
             fixture_pcm,
             fixture_pos: 0,
+            echo: echo_delay_ms.map(SyntheticEcho::new),
         }
     }
 
@@ -686,6 +1847,16 @@ impl SyntheticAudio {
     }
 
     fn capture_pcm_frame(&mut self) -> Vec<i16> {
+        let mut out = self.source_pcm_frame();
+        if let Some(echo) = self.echo.as_mut() {
+            for (sample, echo) in out.iter_mut().zip(echo.next_frame()) {
+                *sample = sample.saturating_add(echo);
+            }
+        }
+        out
+    }
+
+    fn source_pcm_frame(&mut self) -> Vec<i16> {
         if let Some(ref pcm) = self.fixture_pcm {
             let mut out = Vec::with_capacity(FRAME_SAMPLES);
             for _ in 0..FRAME_SAMPLES {
@@ -717,7 +1888,14 @@ impl SyntheticAudio {
         out
     }
 
-    fn play_pcm_frame(&mut self, _pcm: &[i16]) {}
+    fn play_pcm_frame(&mut self, pcm: &[i16]) {
+        if let Some(echo) = self.echo.as_mut() {
+            echo.playback.extend(pcm);
+            while echo.playback.len() > (SAMPLE_RATE as usize * 2) {
+                echo.playback.pop_front();
+            }
+        }
+    }
 }
 
 struct CpalAudio {
@@ -977,7 +2155,101 @@ fn pop_playback_to_f32(
 
 #[cfg(test)]
 mod tests {
-    use super::ReplayWindow;
+    use super::{
+        mix_pcm_frames, pcm_level, AudioBackend, CallAudio, CallRecorder, ReplayWindow,
+        SyntheticAudio, FRAME_SAMPLES, SAMPLE_RATE,
+    };
+
+    fn write_wav_fixture(path: &std::path::Path, pcm: &[i16]) {
+        let data_len = (pcm.len() * 2) as u32;
+        let mut wav = Vec::with_capacity(44 + pcm.len() * 2);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in pcm {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        std::fs::write(path, wav).expect("write wav fixture");
+    }
+
+    fn frame_energy(pcm: &[i16]) -> f64 {
+        pcm.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / pcm.len() as f64
+    }
+
+    /// Runs a call's local audio against a WAV fixture talker and a speech-like
+    /// far end that leaks back into the mic. Returns per-frame capture energy.
+    fn run_fixture_call(fixture: &std::path::Path, processing: bool) -> Vec<f64> {
+        let pcm = SyntheticAudio::load_wav_fixture(fixture.to_str().expect("utf-8 path"));
+        assert!(pcm.is_some(), "fixture should load");
+        let backend = AudioBackend::Synthetic(SyntheticAudio::with_fixture(pcm, Some(60)));
+        let mut audio = CallAudio::new(backend, processing);
+
+        let mut rng = 0x5eed_u64;
+        let levels = [8_000.0, 1_500.0, 6_000.0, 500.0, 3_000.0, 7_000.0, 1_000.0];
+        (0..150)
+            .map(|frame| {
+                let captured = audio.capture_frame();
+                let level = levels[(frame / 9) % levels.len()];
+                let remote: Vec<i16> = (0..FRAME_SAMPLES)
+                    .map(|_| {
+                        rng = rng
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        let unit = (rng >> 33) as f64 / (1u64 << 31) as f64 * 2.0 - 1.0;
+                        (unit * level) as i16
+                    })
+                    .collect();
+                audio.play_frame(Some(&remote));
+                frame_energy(&captured)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn audio_processing_cancels_echo_and_keeps_fixture_speech() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("talker.wav");
+        // 1.5s of silence, then the local user talks for 1.5s.
+        let talk_start = SAMPLE_RATE as usize * 3 / 2;
+        let fixture: Vec<i16> = (0..SAMPLE_RATE as usize * 3)
+            .map(|i| {
+                if i < talk_start {
+                    0
+                } else {
+                    let t = i as f64 / SAMPLE_RATE as f64;
+                    ((2.0 * std::f64::consts::PI * 300.0 * t).sin() * 4_000.0) as i16
+                }
+            })
+            .collect();
+        write_wav_fixture(&path, &fixture);
+        let talker_energy = frame_energy(&fixture[talk_start..]);
+
+        let raw = run_fixture_call(&path, false);
+        let processed = run_fixture_call(&path, true);
+
+        let echo_only = 40..75;
+        let raw_echo: f64 = raw[echo_only.clone()].iter().sum();
+        let processed_echo: f64 = processed[echo_only].iter().sum();
+        let erle_db = 10.0 * (raw_echo / processed_echo).log10();
+        assert!(erle_db > 20.0, "echo only reduced by {erle_db:.1} dB");
+
+        let talking = 100..150;
+        let processed_talk = processed[talking.clone()].iter().sum::<f64>() / talking.len() as f64;
+        let level_db = 10.0 * (processed_talk / talker_energy).log10();
+        assert!(
+            level_db.abs() < 2.0,
+            "talker level moved by {level_db:.1} dB"
+        );
+    }
 
     #[test]
     fn replay_window_accepts_in_order_and_fresh_out_of_order() {
@@ -996,4 +2268,33 @@ mod tests {
         assert!(!w.allow(1000), "duplicate frame must be rejected");
         assert!(!w.allow(800), "stale frame outside window must be rejected");
     }
+
+    #[test]
+    fn mix_sums_participants_and_saturates() {
+        let mixed = mix_pcm_frames(&[vec![100, -200, 30_000], vec![50, -50, 10_000]]);
+        assert_eq!(mixed, vec![150, -250, i16::MAX]);
+        assert_eq!(mix_pcm_frames(&[vec![1, 2, 3]]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn pcm_level_is_mean_absolute_amplitude() {
+        assert_eq!(pcm_level(&[]), 0);
+        assert_eq!(pcm_level(&[0; 960]), 0);
+        assert_eq!(pcm_level(&[1000, -1000, 1000, -1000]), 1000);
+    }
+
+    #[test]
+    fn recorder_keeps_one_packet_per_tick_including_silence() {
+        let mut recorder = CallRecorder::new();
+        let tone: Vec<i16> = (0..FRAME_SAMPLES)
+            .map(|i| if i % 48 < 24 { 4_000 } else { -4_000 })
+            .collect();
+        recorder.record(Some(&tone), None);
+        recorder.record(Some(&tone), Some(&tone));
+        recorder.record(None, None);
+        assert_eq!(recorder.writer.duration_ms(), 60);
+        let file = recorder.writer.finish();
+        assert!(file.starts_with(b"OggS"));
+        assert!(file.windows(8).any(|w| w == b"OpusHead"));
+    }
 }
@@ -4,16 +4,34 @@ use std::path::{Path, PathBuf};
 use ::image::GenericImageView as _;
 use base64::Engine;
 use mdk_core::encrypted_media::types::MediaReference;
-use pika_marmot_runtime::media::{upload_encrypted_blob, UploadedBlob, MAX_CHAT_MEDIA_BYTES};
+use pika_marmot_runtime::media::{
+    imeta_preview, imeta_tag_with_preview, MediaPreview, UploadedBlob, MAX_CHAT_MEDIA_BYTES,
+    MAX_INLINE_THUMBNAIL_BYTES, MAX_WAVEFORM_LEVEL,
+};
+use pika_marmot_runtime::transfer::{
+    download_blob_resumable, upload_blob_resumable, TransferProgress,
+};
 use sha2::{Digest, Sha256};
 
 use crate::state::{ChatMediaAttachment, ChatMediaKind, MediaGalleryItem, MediaGalleryState};
+use crate::{AudioWaveform, MediaProcessor, VideoPoster};
 
-use super::chat_media_db::{self, ChatMediaRecord};
+use super::chat_media_db::{self, ChatMediaRecord, MediaTransferRecord, TransferDirection};
 use super::*;
 
 const RESIZE_MAX_DIMENSION: u32 = 1600;
 
+/// Videos larger than this are re-encoded by the platform before upload, when it can.
+const VIDEO_TARGET_BYTES: usize = 16 * 1024 * 1024;
+/// Largest video accepted from the picker when a platform transcoder is available.
+const MAX_VIDEO_SOURCE_BYTES: usize = 128 * 1024 * 1024;
+/// Longest edge of the inline poster thumbnail; halved again if the JPEG is too big.
+const POSTER_MAX_DIMENSION: u32 = 320;
+/// Bars in a voice-note waveform, whether sent in the imeta tag or decoded locally.
+const VOICE_NOTE_WAVEFORM_BARS: usize = 64;
+
+pub(super) type SharedMediaProcessor = Arc<RwLock<Option<Arc<dyn MediaProcessor>>>>;
+
 /// Resize large JPEG/PNG images to fit within 1600×1600 (longest edge).
 ///
 /// Returns `Some((jpeg_bytes, width, height))` when the image was resized.
@@ -63,6 +81,8 @@ struct SingleMediaPreprocessed {
     width: Option<u32>,
     height: Option<u32>,
     blurhash: Option<String>,
+    thumbnail_jpeg: Option<Vec<u8>>,
+    duration_ms: Option<u64>,
 }
 
 /// Run the expensive media preprocessing off the main actor thread.
@@ -73,6 +93,7 @@ fn preprocess_single_media(
     data_base64: &str,
     mime_type: &str,
     filename: &str,
+    media_processor: Option<&dyn MediaProcessor>,
 ) -> Result<SingleMediaPreprocessed, String> {
     let decoded = base64::engine::general_purpose::STANDARD
         .decode(data_base64)
@@ -80,23 +101,42 @@ fn preprocess_single_media(
     if decoded.is_empty() {
         return Err("Pick media first".into());
     }
+
+    let video_processor = media_processor.filter(|_| is_video_mime(mime_type));
+    let (decoded, mime_type, filename, poster) = match video_processor {
+        Some(processor) => {
+            if decoded.len() > MAX_VIDEO_SOURCE_BYTES {
+                return Err("Video too large (max 128 MB)".into());
+            }
+            let (data, mime_type, filename) = match (decoded.len() > VIDEO_TARGET_BYTES)
+                .then(|| transcode_video(data_dir, processor, &decoded, filename))
+                .flatten()
+            {
+                Some((bytes, mp4_filename)) => (bytes, "video/mp4".to_string(), mp4_filename),
+                None => (decoded, mime_type.to_string(), filename.to_string()),
+            };
+            let poster = extract_video_poster(data_dir, processor, &data);
+            (data, mime_type, filename, poster)
+        }
+        None => (decoded, mime_type.to_string(), filename.to_string(), None),
+    };
     if decoded.len() > MAX_CHAT_MEDIA_BYTES {
         return Err("Media too large (max 32 MB)".into());
     }
 
     let (media_data, media_mime, was_resized, resize_dims) =
-        match maybe_resize_image(&decoded, mime_type) {
+        match maybe_resize_image(&decoded, &mime_type) {
             Some((resized_bytes, w, h, out_mime)) => {
                 (resized_bytes, out_mime.to_string(), true, Some((w, h)))
             }
-            None => (decoded, mime_type.to_string(), false, None),
+            None => (decoded, mime_type, false, None),
         };
 
     let pre_hash = Sha256::digest(&media_data);
     let pre_hash_hex = hex::encode(pre_hash);
 
     let local_filename = if was_resized {
-        let stem = filename.rsplitn(2, '.').last().unwrap_or(filename);
+        let stem = filename.rsplitn(2, '.').last().unwrap_or(filename.as_str());
         let ext = if media_mime == "image/png" {
             "png"
         } else {
@@ -104,7 +144,7 @@ fn preprocess_single_media(
         };
         format!("{stem}.{ext}")
     } else {
-        filename.to_string()
+        filename
     };
 
     let local_path = media_file_path(
@@ -116,10 +156,28 @@ fn preprocess_single_media(
     );
     write_media_file(&local_path, &media_data).map_err(|e| format!("Media cache failed: {e}"))?;
 
-    let (width, height) = resize_dims
-        .map(|(w, h)| (Some(w), Some(h)))
-        .unwrap_or((None, None));
-    let blurhash = compute_blurhash(&media_data);
+    let (width, height, blurhash, thumbnail_jpeg, duration_ms) = match poster {
+        Some(poster) => {
+            let preview = poster_preview(&poster);
+            let (width, height) = preview
+                .dimensions
+                .map(|(w, h)| (Some(w), Some(h)))
+                .unwrap_or((None, None));
+            (
+                width,
+                height,
+                preview.blurhash,
+                preview.thumbnail_jpeg,
+                preview.duration_ms,
+            )
+        }
+        None => {
+            let (width, height) = resize_dims
+                .map(|(w, h)| (Some(w), Some(h)))
+                .unwrap_or((None, None));
+            (width, height, compute_blurhash(&media_data), None, None)
+        }
+    };
 
     Ok(SingleMediaPreprocessed {
         media_data,
@@ -130,9 +188,100 @@ fn preprocess_single_media(
         width,
         height,
         blurhash,
+        thumbnail_jpeg,
+        duration_ms,
     })
 }
 
+fn is_video_mime(mime_type: &str) -> bool {
+    mime_type.trim().to_ascii_lowercase().starts_with("video/")
+}
+
+/// Scratch file handed to the platform video processor.
+fn video_scratch_path(data_dir: &str, extension: &str) -> PathBuf {
+    media_root(data_dir)
+        .join(".video")
+        .join(format!("{}.{extension}", uuid::Uuid::new_v4()))
+}
+
+/// Ask the platform to re-encode a video below [`VIDEO_TARGET_BYTES`].
+/// Returns the MP4 bytes and renamed file, or `None` if that didn't make it smaller.
+fn transcode_video(
+    data_dir: &str,
+    processor: &dyn MediaProcessor,
+    data: &[u8],
+    filename: &str,
+) -> Option<(Vec<u8>, String)> {
+    let input_path = video_scratch_path(data_dir, "in");
+    let output_path = video_scratch_path(data_dir, "mp4");
+    let transcoded = write_media_file(&input_path, data).is_ok()
+        && processor.transcode(
+            input_path.to_string_lossy().to_string(),
+            output_path.to_string_lossy().to_string(),
+            VIDEO_TARGET_BYTES as u64,
+        );
+    let output = if transcoded {
+        std::fs::read(&output_path).ok()
+    } else {
+        None
+    };
+    let _ = std::fs::remove_file(&input_path);
+    let _ = std::fs::remove_file(&output_path);
+
+    let output = output.filter(|bytes| !bytes.is_empty() && bytes.len() < data.len());
+    if output.is_none() {
+        tracing::warn!("video transcode failed; sending the original");
+    }
+    let stem = filename.rsplitn(2, '.').last().unwrap_or(filename);
+    output.map(|bytes| (bytes, format!("{stem}.mp4")))
+}
+
+/// Ask the platform for a poster frame and the duration of a video.
+fn extract_video_poster(
+    data_dir: &str,
+    processor: &dyn MediaProcessor,
+    data: &[u8],
+) -> Option<VideoPoster> {
+    let path = video_scratch_path(data_dir, "in");
+    if let Err(e) = write_media_file(&path, data) {
+        tracing::warn!(%e, "failed to stage video for poster extraction");
+        return None;
+    }
+    let poster = processor.extract_poster(path.to_string_lossy().to_string());
+    let _ = std::fs::remove_file(&path);
+    poster
+}
+
+/// Build the imeta preview for a video from the poster frame the platform decoded.
+fn poster_preview(poster: &VideoPoster) -> MediaPreview {
+    let thumbnail_jpeg = encode_inline_thumbnail(&poster.image_data);
+    MediaPreview {
+        blurhash: thumbnail_jpeg.as_deref().and_then(compute_blurhash),
+        thumbnail_jpeg,
+        duration_ms: Some(poster.duration_ms).filter(|ms| *ms > 0),
+        dimensions: Some((poster.width, poster.height)).filter(|(w, h)| *w > 0 && *h > 0),
+        waveform: None,
+    }
+}
+
+/// Shrink an image into a JPEG small enough to inline in an imeta tag.
+fn encode_inline_thumbnail(image_data: &[u8]) -> Option<Vec<u8>> {
+    let img = ::image::load_from_memory(image_data).ok()?;
+    [POSTER_MAX_DIMENSION, POSTER_MAX_DIMENSION / 2]
+        .into_iter()
+        .find_map(|max_dimension| {
+            // JPEG has no alpha channel; flatten before encoding.
+            let thumb = img.thumbnail(max_dimension, max_dimension).to_rgb8();
+            let mut buf = Cursor::new(Vec::new());
+            let encoder = ::image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buf, 70);
+            ::image::DynamicImage::ImageRgb8(thumb)
+                .write_with_encoder(encoder)
+                .ok()?;
+            let jpeg = buf.into_inner();
+            (jpeg.len() <= MAX_INLINE_THUMBNAIL_BYTES).then_some(jpeg)
+        })
+}
+
 /// Encode a small blurhash (4×3 components) from image bytes.
 /// Returns `None` for non-image data or decode failures.
 fn compute_blurhash(data: &[u8]) -> Option<String> {
@@ -293,22 +442,246 @@ pub(super) fn media_root(data_dir: &str) -> PathBuf {
     Path::new(data_dir).join("chat_media")
 }
 
-pub(super) fn media_file_path(
+/// Directory holding every cached file for one attachment.
+pub(super) fn media_dir(
     data_dir: &str,
     account_pubkey: &str,
     chat_id: &str,
     original_hash_hex: &str,
-    filename: &str,
 ) -> PathBuf {
-    let name = sanitize_filename(filename);
     media_root(data_dir)
         .join(account_pubkey)
         .join(chat_id)
         .join(original_hash_hex)
-        .join(name)
 }
 
-fn write_media_file(path: &Path, data: &[u8]) -> Result<(), String> {
+pub(super) fn media_file_path(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+    filename: &str,
+) -> PathBuf {
+    let name = sanitize_filename(filename);
+    media_dir(data_dir, account_pubkey, chat_id, original_hash_hex).join(name)
+}
+
+/// Ciphertext staged on disk while an upload is in flight, so it can resume after a restart.
+fn upload_staging_path(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+) -> PathBuf {
+    media_dir(data_dir, account_pubkey, chat_id, original_hash_hex).join(".upload.enc")
+}
+
+/// Partial ciphertext of an in-flight download.
+fn download_part_path(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+) -> PathBuf {
+    media_dir(data_dir, account_pubkey, chat_id, original_hash_hex).join(".download.part")
+}
+
+/// Poster frame from an imeta tag's inline `thumb`, cached next to the media file.
+pub(super) fn media_thumbnail_path(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+) -> PathBuf {
+    media_dir(data_dir, account_pubkey, chat_id, original_hash_hex).join(".thumb.jpg")
+}
+
+/// Write the inline poster carried by `tag`, if any, unless it is already cached.
+pub(super) fn cache_imeta_thumbnail(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+    tag: &Tag,
+) {
+    let path = media_thumbnail_path(data_dir, account_pubkey, chat_id, original_hash_hex);
+    if path.exists() {
+        return;
+    }
+    if let Some(jpeg) = imeta_preview(tag).thumbnail_jpeg {
+        write_media_thumbnail(data_dir, account_pubkey, chat_id, original_hash_hex, &jpeg);
+    }
+}
+
+/// Write a poster JPEG to [`media_thumbnail_path`], returning the path on success.
+fn write_media_thumbnail(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+    jpeg: &[u8],
+) -> Option<String> {
+    let path = media_thumbnail_path(data_dir, account_pubkey, chat_id, original_hash_hex);
+    match write_media_file(&path, jpeg) {
+        Ok(()) => Some(path.to_string_lossy().to_string()),
+        Err(e) => {
+            tracing::warn!(%e, "failed to cache media thumbnail");
+            None
+        }
+    }
+}
+
+/// Copy the preview fields of `tag` (blurhash, duration, poster, waveform) onto `att`.
+/// The poster path assumes [`cache_imeta_thumbnail`] ran for the same tag. Voice
+/// notes without a waveform fall back to one decoded after download, if cached.
+fn apply_imeta_preview(
+    att: &mut ChatMediaAttachment,
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    tag: &Tag,
+) {
+    let preview = imeta_preview(tag);
+    if preview.blurhash.is_some() {
+        att.blurhash = preview.blurhash;
+    }
+    att.duration_ms = preview.duration_ms;
+    if preview.thumbnail_jpeg.is_some() {
+        let path = media_thumbnail_path(data_dir, account_pubkey, chat_id, &att.original_hash_hex);
+        att.thumbnail_path = Some(path.to_string_lossy().to_string());
+    }
+    if let Some(waveform) = preview.waveform {
+        att.waveform = Some(waveform_levels(&waveform));
+    } else if matches!(att.kind, ChatMediaKind::VoiceNote) && att.local_path.is_some() {
+        let path = media_waveform_path(data_dir, account_pubkey, chat_id, &att.original_hash_hex);
+        if let Some(cached) = read_cached_waveform(&path) {
+            att.waveform = Some(waveform_levels(&cached.levels));
+            att.duration_ms = att.duration_ms.or(cached.duration_ms);
+        }
+    }
+}
+
+/// Attach `preview` to an outgoing imeta tag, keeping the tag as-is if that fails.
+fn with_media_preview(tag: Tag, preview: &MediaPreview) -> Tag {
+    if preview.is_empty() {
+        return tag;
+    }
+    match imeta_tag_with_preview(&tag, preview) {
+        Ok(tag) => tag,
+        Err(e) => {
+            tracing::warn!(%e, "failed to add preview to imeta tag");
+            tag
+        }
+    }
+}
+
+/// Waveform decoded locally for a voice note whose sender didn't include one.
+fn media_waveform_path(
+    data_dir: &str,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+) -> PathBuf {
+    media_dir(data_dir, account_pubkey, chat_id, original_hash_hex).join(".waveform.json")
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct CachedWaveform {
+    levels: Vec<u8>,
+    duration_ms: Option<u64>,
+}
+
+fn read_cached_waveform(path: &Path) -> Option<CachedWaveform> {
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Downsample recorder levels (`0.0..=1.0`) into at most [`VOICE_NOTE_WAVEFORM_BARS`]
+/// peaks scaled to `0..=MAX_WAVEFORM_LEVEL`.
+fn compact_waveform(levels: &[f32]) -> Vec<u8> {
+    if levels.is_empty() {
+        return Vec::new();
+    }
+    let bars = levels.len().min(VOICE_NOTE_WAVEFORM_BARS);
+    (0..bars)
+        .map(|bar| {
+            let start = bar * levels.len() / bars;
+            let end = ((bar + 1) * levels.len() / bars).max(start + 1);
+            let peak = levels[start..end]
+                .iter()
+                .copied()
+                .filter(|level| level.is_finite())
+                .fold(0.0f32, f32::max)
+                .clamp(0.0, 1.0);
+            (peak * MAX_WAVEFORM_LEVEL as f32).round() as u8
+        })
+        .collect()
+}
+
+fn waveform_levels(waveform: &[u8]) -> Vec<f32> {
+    waveform
+        .iter()
+        .map(|level| f32::from((*level).min(MAX_WAVEFORM_LEVEL)) / f32::from(MAX_WAVEFORM_LEVEL))
+        .collect()
+}
+
+/// Preview for a voice note just recorded, built from the levels the platform
+/// reported while recording.
+pub(super) fn voice_note_preview(levels: &[f32], duration_secs: f64) -> MediaPreview {
+    let waveform = compact_waveform(levels);
+    MediaPreview {
+        duration_ms: (duration_secs.is_finite() && duration_secs > 0.0)
+            .then(|| (duration_secs * 1000.0).round() as u64),
+        waveform: (!waveform.is_empty()).then_some(waveform),
+        ..MediaPreview::default()
+    }
+}
+
+/// Ask the platform to decode a downloaded voice note's waveform, caching the result
+/// next to the file. A previously cached waveform is returned without decoding.
+fn decode_voice_note_waveform(
+    processor: &dyn MediaProcessor,
+    local_path: &str,
+    cache_path: &Path,
+) -> Option<CachedWaveform> {
+    if let Some(cached) = read_cached_waveform(cache_path) {
+        return Some(cached);
+    }
+    let AudioWaveform {
+        levels,
+        duration_ms,
+    } = processor.audio_waveform(local_path.to_string(), VOICE_NOTE_WAVEFORM_BARS as u32)?;
+    let cached = CachedWaveform {
+        levels: compact_waveform(&levels),
+        duration_ms: Some(duration_ms).filter(|ms| *ms > 0),
+    };
+    if cached.levels.is_empty() {
+        return None;
+    }
+    match serde_json::to_vec(&cached) {
+        Ok(json) => {
+            if let Err(e) = write_media_file(cache_path, &json) {
+                tracing::warn!(%e, "failed to cache voice note waveform");
+            }
+        }
+        Err(e) => tracing::warn!(%e, "failed to encode voice note waveform"),
+    }
+    Some(cached)
+}
+
+fn download_transfer_id(chat_id: &str, original_hash_hex: &str) -> String {
+    format!("download:{chat_id}:{original_hash_hex}")
+}
+
+fn upload_progress_fraction(transferred_bytes: u64, total_bytes: u64) -> f32 {
+    if total_bytes == 0 {
+        0.0
+    } else {
+        (transferred_bytes as f64 / total_bytes as f64).min(1.0) as f32
+    }
+}
+
+pub(super) fn write_media_file(path: &Path, data: &[u8]) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| format!("create media dir failed: {e}"))?;
     }
@@ -415,7 +788,17 @@ fn attachment_from_record(
         scheme_version: record.scheme_version.clone(),
         local_path,
         upload_progress: None,
+        transferred_bytes: None,
+        total_bytes: None,
         blurhash: None,
+        thumbnail_path: path_if_exists(&media_thumbnail_path(
+            data_dir,
+            account_pubkey,
+            chat_id,
+            &record.original_hash_hex,
+        )),
+        duration_ms: None,
+        waveform: None,
     }
 }
 
@@ -476,7 +859,12 @@ impl AppCore {
             scheme_version: reference.scheme_version.clone(),
             local_path,
             upload_progress: None,
+            transferred_bytes: None,
+            total_bytes: None,
             blurhash: None,
+            thumbnail_path: None,
+            duration_ms: None,
+            waveform: None,
         }
     }
 
@@ -523,6 +911,7 @@ impl AppCore {
             if let Some(cached_path) = path_cache.and_then(|c| c.get(&hash)) {
                 att.local_path = Some(cached_path.clone());
             }
+            apply_imeta_preview(&mut att, &self.data_dir, account_pubkey, chat_id, tag);
             out.push(att);
         }
         out
@@ -557,6 +946,14 @@ impl AppCore {
 
             let original_hash_hex = hex::encode(reference.original_hash);
 
+            cache_imeta_thumbnail(
+                &self.data_dir,
+                account_pubkey,
+                chat_id,
+                &original_hash_hex,
+                tag,
+            );
+
             // Persist media metadata so the gallery can list all media without
             // scanning the full message store.  Skip the write if we already
             // have this hash in the pre-loaded cache.
@@ -590,12 +987,14 @@ impl AppCore {
                 .map(|r| r.encrypted_hash_hex.clone())
                 .filter(|h| !h.is_empty());
 
-            out.push(self.attachment_from_reference(
+            let mut att = self.attachment_from_reference(
                 chat_id,
                 account_pubkey,
                 &reference,
                 encrypted_hash_hex,
-            ));
+            );
+            apply_imeta_preview(&mut att, &self.data_dir, account_pubkey, chat_id, tag);
+            out.push(att);
         }
 
         out
@@ -641,6 +1040,11 @@ impl AppCore {
         // Show a placeholder bubble immediately while preprocessing runs in background.
         let temp_rumor_id = uuid::Uuid::new_v4().to_string();
         let kind = infer_media_kind(&mime_type, &filename);
+        // The recording that was just stopped, if this is it being sent.
+        let voice_note = matches!(kind, ChatMediaKind::VoiceNote)
+            .then(|| self.voice_note_preview.take())
+            .flatten()
+            .unwrap_or_default();
         let temp_attachment = ChatMediaAttachment {
             original_hash_hex: String::new(),
             encrypted_hash_hex: None,
@@ -654,7 +1058,12 @@ impl AppCore {
             scheme_version: String::new(),
             local_path: None,
             upload_progress: Some(0.0),
+            transferred_bytes: None,
+            total_bytes: None,
             blurhash: None,
+            thumbnail_path: None,
+            duration_ms: voice_note.duration_ms,
+            waveform: voice_note.waveform.as_deref().map(waveform_levels),
         };
 
         self.delivery_overrides
@@ -695,6 +1104,7 @@ impl AppCore {
         // --- Heavy work: decode, resize, hash, blurhash, file write — off main thread ---
         let tx = self.core_sender.clone();
         let data_dir = self.data_dir.clone();
+        let media_processor = self.current_media_processor();
         self.runtime.spawn_blocking(move || {
             let result = preprocess_single_media(
                 &data_dir,
@@ -703,6 +1113,7 @@ impl AppCore {
                 &data_base64,
                 &mime_type,
                 &filename,
+                media_processor.as_deref(),
             );
             match result {
                 Ok(pp) => {
@@ -720,6 +1131,9 @@ impl AppCore {
                             width: pp.width,
                             height: pp.height,
                             blurhash: pp.blurhash,
+                            thumbnail_jpeg: pp.thumbnail_jpeg,
+                            duration_ms: pp.duration_ms.or(voice_note.duration_ms),
+                            waveform: voice_note.waveform,
                             error: None,
                         },
                     )));
@@ -739,6 +1153,9 @@ impl AppCore {
                             width: None,
                             height: None,
                             blurhash: None,
+                            thumbnail_jpeg: None,
+                            duration_ms: None,
+                            waveform: None,
                             error: Some(e),
                         },
                     )));
@@ -762,6 +1179,9 @@ impl AppCore {
         width: Option<u32>,
         height: Option<u32>,
         blurhash: Option<String>,
+        thumbnail_jpeg: Option<Vec<u8>>,
+        duration_ms: Option<u64>,
+        waveform: Option<Vec<u8>>,
         error: Option<String>,
     ) {
         if let Some(e) = error {
@@ -790,7 +1210,9 @@ impl AppCore {
                     att.width = width;
                     att.height = height;
                     att.local_path = Some(local_path.clone());
-                    att.blurhash = blurhash;
+                    att.blurhash = blurhash.clone();
+                    att.duration_ms = duration_ms;
+                    att.waveform = waveform.as_deref().map(waveform_levels);
                 }
             }
         }
@@ -831,8 +1253,34 @@ impl AppCore {
                 }
             };
             let upload = prepared.upload;
+            let preview = MediaPreview {
+                blurhash,
+                thumbnail_jpeg,
+                duration_ms,
+                waveform,
+                // MDK records dimensions for images it can decode; videos rely on the poster.
+                dimensions: upload
+                    .dimensions
+                    .is_none()
+                    .then(|| width.zip(height))
+                    .flatten(),
+            };
+            let imeta_tag = with_media_preview(
+                sess.host_context()
+                    .pending_imeta_tag(&group.mls_group_id, &upload),
+                &preview,
+            );
 
             let original_hash_hex = hex::encode(upload.original_hash);
+            let thumbnail_path = preview.thumbnail_jpeg.as_deref().and_then(|jpeg| {
+                write_media_thumbnail(
+                    &self.data_dir,
+                    &account_pubkey,
+                    &chat_id,
+                    &original_hash_hex,
+                    jpeg,
+                )
+            });
 
             // If MDK re-encoded, the hash may differ. Update the outbox entry.
             if original_hash_hex != pre_hash_hex {
@@ -880,16 +1328,36 @@ impl AppCore {
                             att.width = Some(w);
                             att.height = Some(h);
                         }
+                        att.transferred_bytes = Some(0);
+                        att.total_bytes = Some(encrypted_data.len() as u64);
+                        att.thumbnail_path = thumbnail_path;
                     }
                 }
             }
 
+            self.journal_media_upload(
+                &request_id,
+                &account_pubkey,
+                &chat_id,
+                &temp_rumor_id,
+                &original_hash_hex,
+                &expected_hash_hex,
+                &upload_mime,
+                &local_filename,
+                &caption,
+                &imeta_tag,
+                &encrypted_data,
+            );
+
             self.pending_media_sends.insert(
                 request_id.clone(),
                 PendingMediaSend {
                     chat_id: chat_id.clone(),
                     caption,
-                    upload,
+                    imeta_tag,
+                    original_hash_hex: original_hash_hex.clone(),
+                    upload_mime: upload_mime.clone(),
+                    encrypted_hash_hex: expected_hash_hex.clone(),
                     account_pubkey,
                     temp_rumor_id,
                     encrypted_data: encrypted_data.clone(),
@@ -981,6 +1449,95 @@ impl AppCore {
                 auto_download_count += 1;
             }
         }
+
+        self.request_missing_voice_waveforms(&chat_id_str);
+    }
+
+    /// Decode waveforms for downloaded voice notes in the open chat whose sender
+    /// didn't include one, e.g. older clients.
+    fn request_missing_voice_waveforms(&mut self, chat_id: &str) {
+        let Some(processor) = self.current_media_processor() else {
+            return;
+        };
+        let Some(account_pubkey) = self.session.as_ref().map(|sess| sess.pubkey.to_hex()) else {
+            return;
+        };
+        let Some(chat) = self
+            .state
+            .current_chat
+            .as_ref()
+            .filter(|chat| chat.chat_id == chat_id)
+        else {
+            return;
+        };
+        let mut missing: Vec<(String, String)> = chat
+            .messages
+            .iter()
+            .flat_map(|msg| msg.media.iter())
+            .filter(|att| {
+                matches!(att.kind, ChatMediaKind::VoiceNote)
+                    && att.waveform.is_none()
+                    && !att.original_hash_hex.is_empty()
+                    && !self
+                        .voice_waveform_requests
+                        .contains(&att.original_hash_hex)
+            })
+            .filter_map(|att| Some((att.original_hash_hex.clone(), att.local_path.clone()?)))
+            .collect();
+        let mut seen = HashSet::new();
+        missing.retain(|(hash, _)| seen.insert(hash.clone()));
+        if missing.is_empty() {
+            return;
+        }
+        self.voice_waveform_requests
+            .extend(missing.iter().map(|(hash, _)| hash.clone()));
+
+        let tx = self.core_sender.clone();
+        let data_dir = self.data_dir.clone();
+        let chat_id = chat_id.to_string();
+        self.runtime.spawn_blocking(move || {
+            for (original_hash_hex, local_path) in missing {
+                let cache_path =
+                    media_waveform_path(&data_dir, &account_pubkey, &chat_id, &original_hash_hex);
+                let Some(decoded) =
+                    decode_voice_note_waveform(processor.as_ref(), &local_path, &cache_path)
+                else {
+                    tracing::debug!(%original_hash_hex, "could not decode voice note waveform");
+                    continue;
+                };
+                let _ = tx.send(CoreMsg::Internal(Box::new(
+                    InternalEvent::ChatMediaWaveformDecoded {
+                        chat_id: chat_id.clone(),
+                        original_hash_hex,
+                        levels: decoded.levels,
+                        duration_ms: decoded.duration_ms,
+                    },
+                )));
+            }
+        });
+    }
+
+    pub(super) fn handle_chat_media_waveform_decoded(
+        &mut self,
+        chat_id: String,
+        original_hash_hex: String,
+        levels: Vec<u8>,
+        duration_ms: Option<u64>,
+    ) {
+        let waveform = waveform_levels(&levels);
+        self.mutate_current_chat_messages(&chat_id, |msgs| {
+            let mut changed = false;
+            for att in msgs
+                .iter_mut()
+                .flat_map(|m| m.media.iter_mut())
+                .filter(|att| att.original_hash_hex == original_hash_hex && att.waveform.is_none())
+            {
+                att.waveform = Some(waveform.clone());
+                att.duration_ms = att.duration_ms.or(duration_ms);
+                changed = true;
+            }
+            changed
+        });
     }
 
     /// Clean up an optimistic outbox entry, optionally remove the cached media file,
@@ -1100,10 +1657,17 @@ impl AppCore {
             local_filename: String,
             pre_hash_hex: String,
             local_path: PathBuf,
+            preview: MediaPreview,
         }
         let mut preprocessed = Vec::with_capacity(decoded_items.len());
+        let media_processor = self.current_media_processor();
 
         for di in &decoded_items {
+            // Posters only: transcoding here would stall the actor for too long.
+            let poster = media_processor
+                .as_deref()
+                .filter(|_| is_video_mime(&di.mime_type))
+                .and_then(|processor| extract_video_poster(&self.data_dir, processor, &di.data));
             let (media_data, media_mime, was_resized, resize_dims) =
                 match maybe_resize_image(&di.data, &di.mime_type) {
                     Some((resized_bytes, w, h, out_mime)) => {
@@ -1139,11 +1703,28 @@ impl AppCore {
                 return;
             }
 
-            let (width, height) = resize_dims
+            let preview = match poster {
+                Some(poster) => poster_preview(&poster),
+                None => MediaPreview {
+                    blurhash: compute_blurhash(&media_data),
+                    ..MediaPreview::default()
+                },
+            };
+            let (width, height) = preview
+                .dimensions
+                .or(resize_dims)
                 .map(|(w, h)| (Some(w), Some(h)))
                 .unwrap_or((None, None));
             let kind = infer_media_kind(&media_mime, &local_filename);
-            let blurhash = compute_blurhash(&media_data);
+            let thumbnail_path = preview.thumbnail_jpeg.as_deref().and_then(|jpeg| {
+                write_media_thumbnail(
+                    &self.data_dir,
+                    &account_pubkey,
+                    &chat_id,
+                    &pre_hash_hex,
+                    jpeg,
+                )
+            });
 
             temp_attachments.push(ChatMediaAttachment {
                 original_hash_hex: pre_hash_hex.clone(),
@@ -1158,7 +1739,12 @@ impl AppCore {
                 scheme_version: String::new(),
                 local_path: Some(local_path.to_string_lossy().to_string()),
                 upload_progress: Some(0.0),
-                blurhash,
+                transferred_bytes: None,
+                total_bytes: None,
+                blurhash: preview.blurhash.clone(),
+                thumbnail_path,
+                duration_ms: preview.duration_ms,
+                waveform: None,
             });
 
             preprocessed.push(PreprocessedItem {
@@ -1167,6 +1753,7 @@ impl AppCore {
                 local_filename,
                 pre_hash_hex,
                 local_path,
+                preview,
             });
         }
 
@@ -1266,12 +1853,22 @@ impl AppCore {
                             let _ = std::fs::remove_file(&pp.local_path);
                         }
                     }
-                    // Update outbox attachment hash and local_path.
+                    let thumbnail_path = pp.preview.thumbnail_jpeg.as_deref().and_then(|jpeg| {
+                        write_media_thumbnail(
+                            &self.data_dir,
+                            &account_pubkey,
+                            &chat_id,
+                            &original_hash_hex,
+                            jpeg,
+                        )
+                    });
+                    // Update outbox attachment hash, local_path and poster.
                     if let Some(outbox) = self.local_outbox.get_mut(&chat_id) {
                         if let Some(entry) = outbox.get_mut(&temp_rumor_id) {
                             if let Some(att) = entry.media.get_mut(i) {
                                 att.original_hash_hex = original_hash_hex.clone();
                                 att.local_path = path_if_exists(&final_local_path);
+                                att.thumbnail_path = thumbnail_path;
                             }
                         }
                     }
@@ -1300,6 +1897,7 @@ impl AppCore {
                     upload,
                     encrypted_data,
                     uploaded_url: None,
+                    preview: pp.preview.clone(),
                 });
             }
         }
@@ -1349,13 +1947,26 @@ impl AppCore {
         signer_keys: nostr_sdk::Keys,
     ) {
         let tx = self.core_sender.clone();
+        let client = self.http_client.clone();
         self.runtime.spawn(async move {
-            let result = upload_encrypted_blob(
+            let progress_tx = tx.clone();
+            let progress_request_id = request_id.clone();
+            let result = upload_blob_resumable(
+                &client,
                 &signer_keys,
-                encrypted_data,
+                &encrypted_data,
                 &upload_mime,
                 &expected_hash_hex,
                 &blossom_servers,
+                move |progress: TransferProgress| {
+                    let _ = progress_tx.send(CoreMsg::Internal(Box::new(
+                        InternalEvent::ChatMediaTransferProgress {
+                            request_id: progress_request_id.clone(),
+                            transferred_bytes: progress.transferred_bytes,
+                            total_bytes: progress.total_bytes,
+                        },
+                    )));
+                },
             )
             .await;
             let event = match result {
@@ -1376,6 +1987,306 @@ impl AppCore {
         });
     }
 
+    fn current_media_processor(&self) -> Option<Arc<dyn MediaProcessor>> {
+        match self.media_processor.read() {
+            Ok(slot) => slot.clone(),
+            Err(poison) => poison.into_inner().clone(),
+        }
+    }
+
+    /// Journal a single-item upload and stage its ciphertext so it survives a restart.
+    #[allow(clippy::too_many_arguments)]
+    fn journal_media_upload(
+        &self,
+        request_id: &str,
+        account_pubkey: &str,
+        chat_id: &str,
+        temp_rumor_id: &str,
+        original_hash_hex: &str,
+        encrypted_hash_hex: &str,
+        mime_type: &str,
+        filename: &str,
+        caption: &str,
+        imeta_tag: &Tag,
+        encrypted_data: &[u8],
+    ) {
+        let Some(conn) = self.chat_media_db.as_ref() else {
+            return;
+        };
+        let staging_path =
+            upload_staging_path(&self.data_dir, account_pubkey, chat_id, original_hash_hex);
+        if let Err(e) = write_media_file(&staging_path, encrypted_data) {
+            tracing::warn!(%e, "failed to stage media upload; it won't resume after restart");
+            return;
+        }
+        let imeta_tag_json = match serde_json::to_string(imeta_tag.as_slice()) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!(%e, "failed to serialize pending imeta tag");
+                return;
+            }
+        };
+        let record = MediaTransferRecord {
+            transfer_id: request_id.to_string(),
+            account_pubkey: account_pubkey.to_string(),
+            chat_id: chat_id.to_string(),
+            direction: TransferDirection::Upload,
+            message_id: temp_rumor_id.to_string(),
+            original_hash_hex: original_hash_hex.to_string(),
+            encrypted_hash_hex: encrypted_hash_hex.to_string(),
+            mime_type: mime_type.to_string(),
+            filename: filename.to_string(),
+            staging_path: staging_path.to_string_lossy().to_string(),
+            caption: caption.to_string(),
+            imeta_tag_json,
+            transferred_bytes: 0,
+            total_bytes: encrypted_data.len() as i64,
+            created_at: now_seconds(),
+        };
+        if let Err(e) = chat_media_db::upsert_media_transfer(conn, &record) {
+            tracing::warn!(%e, "failed to journal media upload");
+        }
+    }
+
+    /// Drop a finished (or abandoned) transfer from the journal along with its staged bytes.
+    fn forget_media_transfer(&self, transfer_id: &str, staging_path: &Path) {
+        if let Some(conn) = self.chat_media_db.as_ref() {
+            if let Err(e) = chat_media_db::delete_media_transfer(conn, transfer_id) {
+                tracing::warn!(%e, "failed to remove media transfer from journal");
+            }
+        }
+        let _ = std::fs::remove_file(staging_path);
+    }
+
+    pub(super) fn handle_chat_media_transfer_progress(
+        &mut self,
+        request_id: String,
+        transferred_bytes: u64,
+        total_bytes: u64,
+    ) {
+        let fraction = upload_progress_fraction(transferred_bytes, total_bytes);
+
+        // Uploads: locate the optimistic bubble and the attachment slot being sent.
+        let upload_target = if let Some(pending) = self.pending_media_sends.get(&request_id) {
+            Some((pending.chat_id.clone(), pending.temp_rumor_id.clone(), 0))
+        } else {
+            self.pending_media_batch_sends.values().find_map(|batch| {
+                let index = batch
+                    .items
+                    .iter()
+                    .position(|item| item.request_id == request_id)?;
+                Some((batch.chat_id.clone(), batch.temp_rumor_id.clone(), index))
+            })
+        };
+
+        if let Some((chat_id, temp_rumor_id, index)) = upload_target {
+            if let Some(att) = self
+                .local_outbox
+                .get_mut(&chat_id)
+                .and_then(|outbox| outbox.get_mut(&temp_rumor_id))
+                .and_then(|entry| entry.media.get_mut(index))
+            {
+                att.upload_progress = Some(fraction);
+                att.transferred_bytes = Some(transferred_bytes);
+                att.total_bytes = Some(total_bytes);
+            }
+            self.mutate_current_chat_messages(&chat_id, |msgs| {
+                let Some(att) = msgs
+                    .iter_mut()
+                    .find(|m| m.id == temp_rumor_id)
+                    .and_then(|m| m.media.get_mut(index))
+                else {
+                    return false;
+                };
+                att.upload_progress = Some(fraction);
+                att.transferred_bytes = Some(transferred_bytes);
+                att.total_bytes = Some(total_bytes);
+                true
+            });
+            if let Some(conn) = self.chat_media_db.as_ref() {
+                let _ = chat_media_db::update_media_transfer_progress(
+                    conn,
+                    &request_id,
+                    transferred_bytes as i64,
+                    total_bytes as i64,
+                );
+            }
+            return;
+        }
+
+        let Some(pending) = self.pending_media_downloads.get(&request_id) else {
+            return;
+        };
+        let chat_id = pending.chat_id.clone();
+        let original_hash_hex = hex::encode(pending.reference.original_hash);
+        self.mutate_current_chat_messages(&chat_id, |msgs| {
+            let mut changed = false;
+            for att in msgs
+                .iter_mut()
+                .flat_map(|m| m.media.iter_mut())
+                .filter(|att| att.original_hash_hex == original_hash_hex)
+            {
+                att.transferred_bytes = Some(transferred_bytes);
+                att.total_bytes = Some(total_bytes);
+                changed = true;
+            }
+            changed
+        });
+        if let Some(conn) = self.chat_media_db.as_ref() {
+            let _ = chat_media_db::update_media_transfer_progress(
+                conn,
+                &download_transfer_id(&chat_id, &original_hash_hex),
+                transferred_bytes as i64,
+                total_bytes as i64,
+            );
+        }
+    }
+
+    /// Pick up journaled uploads and downloads left unfinished by an earlier run.
+    pub(super) fn resume_media_transfers(&mut self) {
+        let Some(account_pubkey) = self.session.as_ref().map(|s| s.pubkey.to_hex()) else {
+            return;
+        };
+        let records = self
+            .chat_media_db
+            .as_ref()
+            .map(|conn| chat_media_db::get_media_transfers(conn, &account_pubkey))
+            .unwrap_or_default();
+
+        for record in records {
+            match record.direction {
+                TransferDirection::Upload => self.resume_media_upload(record),
+                TransferDirection::Download => {
+                    if !self.is_media_download_pending(&record.chat_id, &record.original_hash_hex) {
+                        self.download_chat_media(
+                            record.chat_id,
+                            record.message_id,
+                            record.original_hash_hex,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn resume_media_upload(&mut self, record: MediaTransferRecord) {
+        let staging_path = PathBuf::from(&record.staging_path);
+        if self.pending_media_sends.contains_key(&record.transfer_id) {
+            return;
+        }
+        let (group_known, local_keys) = match self.session.as_ref() {
+            Some(sess) => (
+                sess.groups.contains_key(&record.chat_id),
+                sess.local_keys.clone(),
+            ),
+            None => return,
+        };
+        let imeta_tag = serde_json::from_str::<Vec<String>>(&record.imeta_tag_json)
+            .ok()
+            .and_then(|values| Tag::parse(values).ok());
+        let encrypted_data = std::fs::read(&staging_path).ok();
+        let (Some(imeta_tag), Some(encrypted_data), true) =
+            (imeta_tag, encrypted_data, group_known)
+        else {
+            tracing::warn!(
+                transfer_id = %record.transfer_id,
+                "dropping unresumable media upload"
+            );
+            self.forget_media_transfer(&record.transfer_id, &staging_path);
+            return;
+        };
+        let Some(local_keys) = local_keys else {
+            return;
+        };
+
+        let local_path = path_if_exists(&media_file_path(
+            &self.data_dir,
+            &record.account_pubkey,
+            &record.chat_id,
+            &record.original_hash_hex,
+            &record.filename,
+        ));
+        let total_bytes = encrypted_data.len() as u64;
+        let transferred_bytes = (record.transferred_bytes.max(0) as u64).min(total_bytes);
+        let mut attachment = ChatMediaAttachment {
+            original_hash_hex: record.original_hash_hex.clone(),
+            encrypted_hash_hex: Some(record.encrypted_hash_hex.clone()),
+            url: String::new(),
+            mime_type: record.mime_type.clone(),
+            filename: record.filename.clone(),
+            kind: infer_media_kind(&record.mime_type, &record.filename),
+            width: None,
+            height: None,
+            nonce_hex: String::new(),
+            scheme_version: String::new(),
+            local_path,
+            upload_progress: Some(upload_progress_fraction(transferred_bytes, total_bytes)),
+            transferred_bytes: Some(transferred_bytes),
+            total_bytes: Some(total_bytes),
+            blurhash: None,
+            thumbnail_path: None,
+            duration_ms: None,
+            waveform: None,
+        };
+
+        apply_imeta_preview(
+            &mut attachment,
+            &self.data_dir,
+            &record.account_pubkey,
+            &record.chat_id,
+            &imeta_tag,
+        );
+
+        self.delivery_overrides
+            .entry(record.chat_id.clone())
+            .or_default()
+            .insert(record.message_id.clone(), MessageDeliveryState::Pending);
+        self.outbox_seq = self.outbox_seq.wrapping_add(1);
+        let seq = self.outbox_seq;
+        self.local_outbox
+            .entry(record.chat_id.clone())
+            .or_default()
+            .insert(
+                record.message_id.clone(),
+                LocalOutgoing {
+                    content: record.caption.clone(),
+                    timestamp: record.created_at,
+                    sender_pubkey: record.account_pubkey.clone(),
+                    reply_to_message_id: None,
+                    seq,
+                    media: vec![attachment],
+                    kind: Kind::ChatMessage,
+                },
+            );
+        self.pending_media_sends.insert(
+            record.transfer_id.clone(),
+            PendingMediaSend {
+                chat_id: record.chat_id.clone(),
+                caption: record.caption,
+                imeta_tag,
+                original_hash_hex: record.original_hash_hex.clone(),
+                upload_mime: record.mime_type.clone(),
+                encrypted_hash_hex: record.encrypted_hash_hex.clone(),
+                account_pubkey: record.account_pubkey,
+                temp_rumor_id: record.message_id,
+                encrypted_data: encrypted_data.clone(),
+            },
+        );
+
+        self.refresh_current_chat_if_open(&record.chat_id);
+        self.refresh_chat_list_from_storage();
+
+        let blossom_servers = self.blossom_servers();
+        self.spawn_media_upload(
+            record.transfer_id,
+            blossom_servers,
+            encrypted_data,
+            record.mime_type,
+            record.encrypted_hash_hex,
+            local_keys,
+        );
+    }
+
     pub(super) fn handle_chat_media_upload_completed(
         &mut self,
         request_id: String,
@@ -1436,6 +2347,17 @@ impl AppCore {
             return;
         };
 
+        // The upload either landed or can't be salvaged; the journal entry is done.
+        self.forget_media_transfer(
+            &request_id,
+            &upload_staging_path(
+                &self.data_dir,
+                &pending.account_pubkey,
+                &pending.chat_id,
+                &pending.original_hash_hex,
+            ),
+        );
+
         // Helper: clean up the optimistic outbox entry and delivery override.
         let cleanup_optimistic = |s: &mut Self| {
             if let Some(outbox) = s.local_outbox.get_mut(&pending.chat_id) {
@@ -1459,7 +2381,7 @@ impl AppCore {
             return;
         };
 
-        let expected_hash_hex = hex::encode(pending.upload.encrypted_hash);
+        let expected_hash_hex = pending.encrypted_hash_hex.clone();
         if !descriptor_hash.eq_ignore_ascii_case(&expected_hash_hex) {
             cleanup_optimistic(self);
             self.toast("Media upload failed: uploaded hash mismatch");
@@ -1477,15 +2399,21 @@ impl AppCore {
             return;
         };
 
-        let completed = sess.host_context().finish_upload(
+        let completed = match sess.host_context().finish_pending_upload(
             &group.mls_group_id,
-            &pending.upload,
+            &pending.imeta_tag,
             UploadedBlob {
                 blossom_server: "app-local".to_string(),
                 uploaded_url,
                 descriptor_sha256_hex: descriptor_hash,
             },
-        );
+        ) {
+            Ok(completed) => completed,
+            Err(e) => {
+                self.toast(format!("Media upload failed: {e}"));
+                return;
+            }
+        };
 
         if let Some(conn) = self.chat_media_db.as_ref() {
             let record = ChatMediaRecord {
@@ -1510,12 +2438,20 @@ impl AppCore {
                 .insert(record.original_hash_hex.clone(), record);
         }
 
-        let media = vec![self.attachment_from_reference(
+        let mut attachment = self.attachment_from_reference(
             &pending.chat_id,
             &pending.account_pubkey,
             &completed.reference,
             Some(expected_hash_hex),
-        )];
+        );
+        apply_imeta_preview(
+            &mut attachment,
+            &self.data_dir,
+            &pending.account_pubkey,
+            &pending.chat_id,
+            &completed.imeta_tag,
+        );
+        let media = vec![attachment];
 
         self.publish_chat_message_with_tags(
             pending.chat_id,
@@ -1717,8 +2653,9 @@ impl AppCore {
                             descriptor_sha256_hex: hex::encode(item.upload.encrypted_hash),
                         },
                     );
+                    let imeta_tag = with_media_preview(completed.imeta_tag, &item.preview);
                     UploadedMedia {
-                        imeta_tag: completed.imeta_tag,
+                        imeta_tag,
                         reference: completed.reference,
                         encrypted_hash_hex: completed
                             .attachment
@@ -1762,12 +2699,20 @@ impl AppCore {
             }
 
             imeta_tags.push(um.imeta_tag.clone());
-            media.push(self.attachment_from_reference(
+            let mut attachment = self.attachment_from_reference(
                 &batch.chat_id,
                 &batch.account_pubkey,
                 &um.reference,
                 Some(um.encrypted_hash_hex.clone()),
-            ));
+            );
+            apply_imeta_preview(
+                &mut attachment,
+                &self.data_dir,
+                &batch.account_pubkey,
+                &batch.chat_id,
+                &um.imeta_tag,
+            );
+            media.push(attachment);
         }
 
         self.publish_chat_message_with_tags(
@@ -1804,7 +2749,7 @@ impl AppCore {
             self.last_outgoing_ts
         };
 
-        let (client, wrapper, relays, rumor_id_hex) = {
+        let (client, wrapper, relays, rumor_id, rumor_id_hex) = {
             let Some(sess) = self.session.as_mut() else {
                 return;
             };
@@ -1874,6 +2819,7 @@ impl AppCore {
                 }
             };
             let wrapper = prepared.wrapper;
+            let rumor_id = prepared.rumor_id;
 
             self.pending_sends
                 .insert(&chat_id, &rumor_id_hex, &wrapper, self.profile_db.as_ref());
@@ -1889,9 +2835,10 @@ impl AppCore {
                 vec![]
             };
 
-            (sess.client.clone(), wrapper, relays, rumor_id_hex)
+            (sess.client.clone(), wrapper, relays, rumor_id, rumor_id_hex)
         };
 
+        self.index_stored_message_for_search(&chat_id, &rumor_id);
         self.prune_local_outbox(&chat_id);
         self.refresh_chat_list_from_storage();
         self.refresh_current_chat_if_open(&chat_id);
@@ -1958,7 +2905,7 @@ impl AppCore {
             return;
         }
 
-        let (request_id, url) = {
+        let (request_id, url, part_path) = {
             let Some(sess) = self.session.as_mut() else {
                 return;
             };
@@ -2030,6 +2977,33 @@ impl AppCore {
                     .filter(|h| !h.is_empty())
             });
 
+            let part_path =
+                download_part_path(&self.data_dir, &account_pubkey, &chat_id, &target_hash);
+            if let Some(conn) = self.chat_media_db.as_ref() {
+                let record = MediaTransferRecord {
+                    transfer_id: download_transfer_id(&chat_id, &target_hash),
+                    account_pubkey: account_pubkey.clone(),
+                    chat_id: chat_id.clone(),
+                    direction: TransferDirection::Download,
+                    message_id: message_id.clone(),
+                    original_hash_hex: target_hash.clone(),
+                    encrypted_hash_hex: encrypted_hash_hex.clone().unwrap_or_default(),
+                    mime_type: reference.mime_type.clone(),
+                    filename: reference.filename.clone(),
+                    staging_path: part_path.to_string_lossy().to_string(),
+                    caption: String::new(),
+                    imeta_tag_json: String::new(),
+                    transferred_bytes: std::fs::metadata(&part_path)
+                        .map(|m| m.len() as i64)
+                        .unwrap_or(0),
+                    total_bytes: 0,
+                    created_at: now_seconds(),
+                };
+                if let Err(e) = chat_media_db::upsert_media_transfer(conn, &record) {
+                    tracing::warn!(%e, "failed to journal media download");
+                }
+            }
+
             let request_id = uuid::Uuid::new_v4().to_string();
             self.pending_media_downloads.insert(
                 request_id.clone(),
@@ -2042,57 +3016,42 @@ impl AppCore {
                 },
             );
 
-            (request_id, reference.url)
+            (request_id, reference.url, part_path)
         };
 
         let tx = self.core_sender.clone();
         let client = self.http_client.clone();
         self.runtime.spawn(async move {
-            let response = match client.get(&url).send().await {
-                Ok(response) => response,
-                Err(e) => {
-                    let _ = tx.send(CoreMsg::Internal(Box::new(
-                        InternalEvent::ChatMediaDownloadFetched {
-                            request_id,
-                            encrypted_data: None,
-                            error: Some(format!("Media download failed: {e}")),
+            let progress_tx = tx.clone();
+            let progress_request_id = request_id.clone();
+            let result = download_blob_resumable(
+                &client,
+                &url,
+                &part_path,
+                move |progress: TransferProgress| {
+                    let _ = progress_tx.send(CoreMsg::Internal(Box::new(
+                        InternalEvent::ChatMediaTransferProgress {
+                            request_id: progress_request_id.clone(),
+                            transferred_bytes: progress.transferred_bytes,
+                            total_bytes: progress.total_bytes,
                         },
                     )));
-                    return;
-                }
+                },
+            )
+            .await;
+            let event = match result {
+                Ok(encrypted_data) => InternalEvent::ChatMediaDownloadFetched {
+                    request_id,
+                    encrypted_data: Some(encrypted_data),
+                    error: None,
+                },
+                Err(e) => InternalEvent::ChatMediaDownloadFetched {
+                    request_id,
+                    encrypted_data: None,
+                    error: Some(format!("Media download failed: {e}")),
+                },
             };
-
-            if !response.status().is_success() {
-                let _ = tx.send(CoreMsg::Internal(Box::new(
-                    InternalEvent::ChatMediaDownloadFetched {
-                        request_id,
-                        encrypted_data: None,
-                        error: Some(format!("Media download failed: HTTP {}", response.status())),
-                    },
-                )));
-                return;
-            }
-
-            match response.bytes().await {
-                Ok(bytes) => {
-                    let _ = tx.send(CoreMsg::Internal(Box::new(
-                        InternalEvent::ChatMediaDownloadFetched {
-                            request_id,
-                            encrypted_data: Some(bytes.to_vec()),
-                            error: None,
-                        },
-                    )));
-                }
-                Err(e) => {
-                    let _ = tx.send(CoreMsg::Internal(Box::new(
-                        InternalEvent::ChatMediaDownloadFetched {
-                            request_id,
-                            encrypted_data: None,
-                            error: Some(format!("Media download failed: {e}")),
-                        },
-                    )));
-                }
-            }
+            let _ = tx.send(CoreMsg::Internal(Box::new(event)));
         });
     }
 
@@ -2105,12 +3064,38 @@ impl AppCore {
         let Some(pending) = self.pending_media_downloads.remove(&request_id) else {
             return;
         };
+        let original_hash_hex = hex::encode(pending.reference.original_hash);
 
         if let Some(e) = error {
+            // Keep the journal entry and partial file so the next attempt resumes.
+            self.mutate_current_chat_messages(&pending.chat_id, |msgs| {
+                let mut changed = false;
+                for att in msgs
+                    .iter_mut()
+                    .flat_map(|m| m.media.iter_mut())
+                    .filter(|att| att.original_hash_hex == original_hash_hex)
+                {
+                    att.transferred_bytes = None;
+                    att.total_bytes = None;
+                    changed = true;
+                }
+                changed
+            });
             self.toast(e);
             return;
         }
 
+        // Anything past this point either caches the file or proves the bytes are bad.
+        self.forget_media_transfer(
+            &download_transfer_id(&pending.chat_id, &original_hash_hex),
+            &download_part_path(
+                &self.data_dir,
+                &pending.account_pubkey,
+                &pending.chat_id,
+                &original_hash_hex,
+            ),
+        );
+
         let Some(encrypted_data) = encrypted_data else {
             self.toast("Media download failed: empty response");
             return;
@@ -2159,6 +3144,7 @@ impl AppCore {
         if !self.update_media_local_path_in_place(&pending.chat_id, &original_hash_hex, path_str) {
             self.refresh_current_chat_if_open(&pending.chat_id);
         }
+        self.request_missing_voice_waveforms(&pending.chat_id);
     }
 
     pub(super) fn load_media_gallery(&mut self, chat_id: &str) {
@@ -2606,6 +3592,7 @@ mod tests {
             &b64,
             "image/jpeg",
             "photo.jpg",
+            None,
         );
         let pp = result.expect("preprocessing should succeed");
         assert_eq!(pp.media_mime, "image/jpeg");
@@ -2634,6 +3621,7 @@ mod tests {
             &b64,
             "image/jpeg",
             "big.jpg",
+            None,
         )
         .expect("preprocessing should succeed");
         assert_eq!(pp.width, Some(1600));
@@ -2646,7 +3634,8 @@ mod tests {
         let data_dir = dir.path().to_string_lossy().to_string();
         let b64 = base64::engine::general_purpose::STANDARD.encode(b"");
 
-        let result = preprocess_single_media(&data_dir, "a", "c", &b64, "image/jpeg", "empty.jpg");
+        let result =
+            preprocess_single_media(&data_dir, "a", "c", &b64, "image/jpeg", "empty.jpg", None);
         assert!(result.is_err());
     }
 
@@ -2655,11 +3644,249 @@ mod tests {
         let dir = tempfile::tempdir().expect("tempdir");
         let data_dir = dir.path().to_string_lossy().to_string();
 
-        let result =
-            preprocess_single_media(&data_dir, "a", "c", "not-base64!!!", "image/jpeg", "x.jpg");
+        let result = preprocess_single_media(
+            &data_dir,
+            "a",
+            "c",
+            "not-base64!!!",
+            "image/jpeg",
+            "x.jpg",
+            None,
+        );
         assert!(result.is_err());
     }
 
+    struct FakeMediaProcessor {
+        poster: Vec<u8>,
+        transcoded: Option<Vec<u8>>,
+    }
+
+    impl crate::MediaProcessor for FakeMediaProcessor {
+        fn extract_poster(&self, path: String) -> Option<VideoPoster> {
+            assert!(Path::new(&path).exists(), "video should be staged on disk");
+            Some(VideoPoster {
+                image_data: self.poster.clone(),
+                width: 1280,
+                height: 720,
+                duration_ms: 4_500,
+            })
+        }
+
+        fn transcode(&self, _input_path: String, output_path: String, _max_bytes: u64) -> bool {
+            match &self.transcoded {
+                Some(bytes) => std::fs::write(output_path, bytes).is_ok(),
+                None => false,
+            }
+        }
+
+        fn audio_waveform(&self, path: String, bars: u32) -> Option<AudioWaveform> {
+            assert!(Path::new(&path).exists(), "audio should be on disk");
+            Some(AudioWaveform {
+                levels: (0..bars).map(|i| i as f32 / bars as f32).collect(),
+                duration_ms: 3_000,
+            })
+        }
+    }
+
+    #[test]
+    fn preprocess_single_media_adds_video_poster() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().to_string_lossy().to_string();
+        let b64 = base64::engine::general_purpose::STANDARD.encode(b"not really a video");
+        let processor = FakeMediaProcessor {
+            poster: make_jpeg(1280, 720),
+            transcoded: None,
+        };
+
+        let pp = preprocess_single_media(
+            &data_dir,
+            "a",
+            "c",
+            &b64,
+            "video/quicktime",
+            "clip.mov",
+            Some(&processor),
+        )
+        .expect("preprocessing should succeed");
+        assert_eq!(pp.media_mime, "video/quicktime");
+        assert_eq!(pp.local_filename, "clip.mov");
+        assert_eq!((pp.width, pp.height), (Some(1280), Some(720)));
+        assert_eq!(pp.duration_ms, Some(4_500));
+        assert!(pp.blurhash.is_some());
+        let thumb = pp.thumbnail_jpeg.expect("poster thumbnail");
+        assert!(thumb.len() <= MAX_INLINE_THUMBNAIL_BYTES);
+        let img = ::image::load_from_memory(&thumb).expect("thumbnail decodes");
+        assert_eq!(img.dimensions(), (320, 180));
+        // Scratch copies handed to the processor are cleaned up.
+        let scratch = media_root(&data_dir).join(".video");
+        assert_eq!(std::fs::read_dir(scratch).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn preprocess_single_media_transcodes_oversized_video() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().to_string_lossy().to_string();
+        let b64 =
+            base64::engine::general_purpose::STANDARD.encode(vec![0u8; VIDEO_TARGET_BYTES + 1]);
+        let processor = FakeMediaProcessor {
+            poster: make_jpeg(64, 64),
+            transcoded: Some(b"small mp4".to_vec()),
+        };
+
+        let pp = preprocess_single_media(
+            &data_dir,
+            "a",
+            "c",
+            &b64,
+            "video/quicktime",
+            "clip.mov",
+            Some(&processor),
+        )
+        .expect("preprocessing should succeed");
+        assert_eq!(pp.media_mime, "video/mp4");
+        assert_eq!(pp.local_filename, "clip.mp4");
+        assert_eq!(pp.media_data, b"small mp4");
+    }
+
+    #[test]
+    fn video_without_processor_is_sent_as_is() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().to_string_lossy().to_string();
+        let b64 = base64::engine::general_purpose::STANDARD.encode(b"video bytes");
+
+        let pp = preprocess_single_media(&data_dir, "a", "c", &b64, "video/mp4", "v.mp4", None)
+            .expect("preprocessing should succeed");
+        assert_eq!(pp.media_data, b"video bytes");
+        assert!(pp.thumbnail_jpeg.is_none());
+        assert!(pp.blurhash.is_none());
+    }
+
+    #[test]
+    fn imeta_thumbnail_is_cached_and_applied() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().to_string_lossy().to_string();
+        let thumb = encode_inline_thumbnail(&make_jpeg(640, 480)).expect("thumbnail");
+        let tag = imeta_tag_with_preview(
+            &Tag::parse(["imeta", "url https://blossom.example/abc", "m video/mp4"]).unwrap(),
+            &MediaPreview {
+                blurhash: Some("LKO2?U%2Tw=w]~RBVZRi};RPxuwH".to_string()),
+                thumbnail_jpeg: Some(thumb.clone()),
+                duration_ms: Some(61_000),
+                dimensions: Some((640, 480)),
+                waveform: None,
+            },
+        )
+        .unwrap();
+
+        cache_imeta_thumbnail(&data_dir, "acc", "chat", "hash", &tag);
+        let path = media_thumbnail_path(&data_dir, "acc", "chat", "hash");
+        assert_eq!(std::fs::read(&path).unwrap(), thumb);
+
+        let mut att = attachment_from_record(
+            &data_dir,
+            "chat",
+            "acc",
+            &ChatMediaRecord {
+                account_pubkey: "acc".to_string(),
+                chat_id: "chat".to_string(),
+                original_hash_hex: "hash".to_string(),
+                encrypted_hash_hex: String::new(),
+                url: "https://blossom.example/abc".to_string(),
+                mime_type: "video/mp4".to_string(),
+                filename: "v.mp4".to_string(),
+                nonce_hex: String::new(),
+                scheme_version: String::new(),
+                created_at: 0,
+            },
+        );
+        // Gallery items built from records pick up the cached poster.
+        assert_eq!(
+            att.thumbnail_path.as_deref(),
+            Some(path.to_string_lossy().as_ref())
+        );
+
+        att.thumbnail_path = None;
+        apply_imeta_preview(&mut att, &data_dir, "acc", "chat", &tag);
+        assert_eq!(
+            att.thumbnail_path.as_deref(),
+            Some(path.to_string_lossy().as_ref())
+        );
+        assert_eq!(att.duration_ms, Some(61_000));
+        assert!(att.blurhash.is_some());
+    }
+
+    #[test]
+    fn compact_waveform_keeps_peaks_per_bar() {
+        assert!(compact_waveform(&[]).is_empty());
+        assert_eq!(compact_waveform(&[0.0, 0.5, 1.0]), vec![0, 50, 100]);
+
+        let mut levels = vec![0.1; VOICE_NOTE_WAVEFORM_BARS * 10];
+        levels[5] = 0.9;
+        levels[levels.len() - 1] = 2.0;
+        let waveform = compact_waveform(&levels);
+        assert_eq!(waveform.len(), VOICE_NOTE_WAVEFORM_BARS);
+        assert_eq!(waveform[0], 90);
+        assert_eq!(waveform[1], 10);
+        assert_eq!(waveform[VOICE_NOTE_WAVEFORM_BARS - 1], 100);
+    }
+
+    #[test]
+    fn voice_note_preview_rounds_duration() {
+        let preview = voice_note_preview(&[0.25, 0.75], 2.3);
+        assert_eq!(preview.duration_ms, Some(2_300));
+        assert_eq!(preview.waveform, Some(vec![25, 75]));
+        assert!(voice_note_preview(&[], 0.0).is_empty());
+    }
+
+    #[test]
+    fn voice_note_waveform_is_decoded_once_and_applied() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().to_string_lossy().to_string();
+        let local_path = media_file_path(&data_dir, "acc", "chat", "hash", "voice_1.m4a");
+        write_media_file(&local_path, b"aac").unwrap();
+        let cache_path = media_waveform_path(&data_dir, "acc", "chat", "hash");
+        let processor = FakeMediaProcessor {
+            poster: Vec::new(),
+            transcoded: None,
+        };
+
+        let decoded =
+            decode_voice_note_waveform(&processor, &local_path.to_string_lossy(), &cache_path)
+                .expect("decoded");
+        assert_eq!(decoded.levels.len(), VOICE_NOTE_WAVEFORM_BARS);
+        assert_eq!(decoded.duration_ms, Some(3_000));
+        assert_eq!(read_cached_waveform(&cache_path), Some(decoded.clone()));
+
+        // Older clients send no waveform; the cached decode fills in once the file is local.
+        let tag = Tag::parse(["imeta", "url https://blossom.example/abc", "m audio/mp4"]).unwrap();
+        let mut att = attachment_from_record(
+            &data_dir,
+            "chat",
+            "acc",
+            &ChatMediaRecord {
+                account_pubkey: "acc".to_string(),
+                chat_id: "chat".to_string(),
+                original_hash_hex: "hash".to_string(),
+                encrypted_hash_hex: String::new(),
+                url: "https://blossom.example/abc".to_string(),
+                mime_type: "audio/mp4".to_string(),
+                filename: "voice_1.m4a".to_string(),
+                nonce_hex: String::new(),
+                scheme_version: String::new(),
+                created_at: 0,
+            },
+        );
+        apply_imeta_preview(&mut att, &data_dir, "acc", "chat", &tag);
+        assert_eq!(att.waveform, Some(waveform_levels(&decoded.levels)));
+        assert_eq!(att.duration_ms, Some(3_000));
+
+        // A sender waveform wins over the local decode.
+        let tag = imeta_tag_with_preview(&tag, &voice_note_preview(&[1.0], 1.0)).unwrap();
+        apply_imeta_preview(&mut att, &data_dir, "acc", "chat", &tag);
+        assert_eq!(att.waveform, Some(vec![1.0]));
+        assert_eq!(att.duration_ms, Some(1_000));
+    }
+
     #[test]
     fn app_media_prepare_uses_shared_runtime_service() {
         let inviter_dir = tempfile::tempdir().expect("inviter tempdir");
@@ -16,7 +16,50 @@ pub(super) struct ChatMediaRecord {
     pub(super) created_at: i64,
 }
 
-const CHAT_MEDIA_DB_FILE: &str = "chat_media.sqlite3";
+pub(super) const CHAT_MEDIA_DB_FILE: &str = "chat_media.sqlite3";
+
+/// `url` of media that only exists on this device (call recordings). Everything
+/// else was uploaded to or downloaded from Blossom.
+pub(super) const LOCAL_ONLY_URL: &str = "";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TransferDirection {
+    Upload,
+    Download,
+}
+
+impl TransferDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            TransferDirection::Upload => "upload",
+            TransferDirection::Download => "download",
+        }
+    }
+}
+
+/// A journaled upload or download, kept until the transfer finishes so it can be
+/// resumed after the app restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct MediaTransferRecord {
+    pub(super) transfer_id: String,
+    pub(super) account_pubkey: String,
+    pub(super) chat_id: String,
+    pub(super) direction: TransferDirection,
+    /// Uploads: the optimistic outbox id. Downloads: the message carrying the attachment.
+    pub(super) message_id: String,
+    pub(super) original_hash_hex: String,
+    pub(super) encrypted_hash_hex: String,
+    pub(super) mime_type: String,
+    pub(super) filename: String,
+    /// Ciphertext on disk: the staged blob for uploads, the `.part` file for downloads.
+    pub(super) staging_path: String,
+    /// Uploads only: the caption and pending imeta tag (JSON array) to publish.
+    pub(super) caption: String,
+    pub(super) imeta_tag_json: String,
+    pub(super) transferred_bytes: i64,
+    pub(super) total_bytes: i64,
+    pub(super) created_at: i64,
+}
 
 fn record_from_row(row: &rusqlite::Row) -> rusqlite::Result<ChatMediaRecord> {
     Ok(ChatMediaRecord {
@@ -54,6 +97,24 @@ pub(super) fn open_chat_media_db(data_dir: &str) -> rusqlite::Result<Connection>
             created_at INTEGER NOT NULL,
             PRIMARY KEY (account_pubkey, chat_id, original_hash_hex)
         );
+
+        CREATE TABLE IF NOT EXISTS media_transfers (
+            transfer_id TEXT PRIMARY KEY,
+            account_pubkey TEXT NOT NULL,
+            chat_id TEXT NOT NULL,
+            direction TEXT NOT NULL,
+            message_id TEXT NOT NULL,
+            original_hash_hex TEXT NOT NULL,
+            encrypted_hash_hex TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            filename TEXT NOT NULL,
+            staging_path TEXT NOT NULL,
+            caption TEXT NOT NULL,
+            imeta_tag_json TEXT NOT NULL,
+            transferred_bytes INTEGER NOT NULL,
+            total_bytes INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        );
         "#,
     )?;
     Ok(conn)
@@ -132,6 +193,22 @@ pub(super) fn get_chat_media(
     .flatten()
 }
 
+pub(super) fn delete_chat_media(
+    conn: &Connection,
+    account_pubkey: &str,
+    chat_id: &str,
+    original_hash_hex: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"
+        DELETE FROM chat_media
+        WHERE account_pubkey = ?1 AND chat_id = ?2 AND original_hash_hex = ?3
+        "#,
+        params![account_pubkey, chat_id, original_hash_hex],
+    )?;
+    Ok(())
+}
+
 pub(super) fn get_all_chat_media_map(
     conn: &Connection,
     account_pubkey: &str,
@@ -185,7 +262,8 @@ pub(super) fn get_all_chat_media(
     rows.filter_map(|r| r.ok()).collect()
 }
 
-/// Like `get_all_chat_media` but only returns image/* and video/* records.
+/// Like `get_all_chat_media` but only returns image/* and video/* records, plus
+/// call recordings (which have no url, see [`LOCAL_ONLY_URL`]).
 /// The LIMIT is applied *after* filtering so the gallery always gets up to 500
 /// actual gallery items.
 pub(super) fn get_gallery_media(
@@ -208,7 +286,7 @@ pub(super) fn get_gallery_media(
             created_at
         FROM chat_media
         WHERE account_pubkey = ?1 AND chat_id = ?2
-          AND (mime_type LIKE 'image/%' OR mime_type LIKE 'video/%')
+          AND (mime_type LIKE 'image/%' OR mime_type LIKE 'video/%' OR url = '')
         ORDER BY created_at DESC
         LIMIT 500
         "#,
@@ -231,6 +309,164 @@ pub(super) fn get_gallery_media(
     rows.filter_map(|r| r.ok()).collect()
 }
 
+/// Forgets every record that can be fetched again, keeping local-only media.
+pub(super) fn clear_cached_chat_media(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM chat_media WHERE url != ?1",
+        params![LOCAL_ONLY_URL],
+    )?;
+    Ok(())
+}
+
+pub(super) fn upsert_media_transfer(
+    conn: &Connection,
+    record: &MediaTransferRecord,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"
+        INSERT INTO media_transfers (
+            transfer_id,
+            account_pubkey,
+            chat_id,
+            direction,
+            message_id,
+            original_hash_hex,
+            encrypted_hash_hex,
+            mime_type,
+            filename,
+            staging_path,
+            caption,
+            imeta_tag_json,
+            transferred_bytes,
+            total_bytes,
+            created_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+        ON CONFLICT(transfer_id) DO UPDATE SET
+            message_id = excluded.message_id,
+            encrypted_hash_hex = excluded.encrypted_hash_hex,
+            mime_type = excluded.mime_type,
+            filename = excluded.filename,
+            staging_path = excluded.staging_path,
+            caption = excluded.caption,
+            imeta_tag_json = excluded.imeta_tag_json,
+            transferred_bytes = excluded.transferred_bytes,
+            total_bytes = excluded.total_bytes
+        "#,
+        params![
+            record.transfer_id,
+            record.account_pubkey,
+            record.chat_id,
+            record.direction.as_str(),
+            record.message_id,
+            record.original_hash_hex,
+            record.encrypted_hash_hex,
+            record.mime_type,
+            record.filename,
+            record.staging_path,
+            record.caption,
+            record.imeta_tag_json,
+            record.transferred_bytes,
+            record.total_bytes,
+            record.created_at,
+        ],
+    )?;
+    Ok(())
+}
+
+pub(super) fn update_media_transfer_progress(
+    conn: &Connection,
+    transfer_id: &str,
+    transferred_bytes: i64,
+    total_bytes: i64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"
+        UPDATE media_transfers
+        SET transferred_bytes = ?2, total_bytes = ?3
+        WHERE transfer_id = ?1
+        "#,
+        params![transfer_id, transferred_bytes, total_bytes],
+    )?;
+    Ok(())
+}
+
+pub(super) fn delete_media_transfer(conn: &Connection, transfer_id: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM media_transfers WHERE transfer_id = ?1",
+        params![transfer_id],
+    )?;
+    Ok(())
+}
+
+/// Every unfinished transfer for an account, oldest first.
+pub(super) fn get_media_transfers(
+    conn: &Connection,
+    account_pubkey: &str,
+) -> Vec<MediaTransferRecord> {
+    let mut stmt = match conn.prepare(
+        r#"
+        SELECT
+            transfer_id,
+            account_pubkey,
+            chat_id,
+            direction,
+            message_id,
+            original_hash_hex,
+            encrypted_hash_hex,
+            mime_type,
+            filename,
+            staging_path,
+            caption,
+            imeta_tag_json,
+            transferred_bytes,
+            total_bytes,
+            created_at
+        FROM media_transfers
+        WHERE account_pubkey = ?1
+        ORDER BY created_at ASC
+        "#,
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!(%e, "failed to prepare get_media_transfers query");
+            return vec![];
+        }
+    };
+
+    let rows = match stmt.query_map(params![account_pubkey], |row| {
+        let direction: String = row.get(3)?;
+        Ok(MediaTransferRecord {
+            transfer_id: row.get(0)?,
+            account_pubkey: row.get(1)?,
+            chat_id: row.get(2)?,
+            direction: if direction == "upload" {
+                TransferDirection::Upload
+            } else {
+                TransferDirection::Download
+            },
+            message_id: row.get(4)?,
+            original_hash_hex: row.get(5)?,
+            encrypted_hash_hex: row.get(6)?,
+            mime_type: row.get(7)?,
+            filename: row.get(8)?,
+            staging_path: row.get(9)?,
+            caption: row.get(10)?,
+            imeta_tag_json: row.get(11)?,
+            transferred_bytes: row.get(12)?,
+            total_bytes: row.get(13)?,
+            created_at: row.get(14)?,
+        })
+    }) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(%e, "failed to query get_media_transfers");
+            return vec![];
+        }
+    };
+
+    rows.filter_map(|r| r.ok()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,6 +532,21 @@ mod tests {
         assert_eq!(got, second);
     }
 
+    #[test]
+    fn delete_removes_only_matching_record() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let conn = open_chat_media_db(&dir.path().to_string_lossy()).expect("open db");
+        upsert_chat_media(&conn, &sample_record("acc-a", "chat-a", "hash-a", 111))
+            .expect("upsert a");
+        upsert_chat_media(&conn, &sample_record("acc-a", "chat-a", "hash-b", 222))
+            .expect("upsert b");
+
+        delete_chat_media(&conn, "acc-a", "chat-a", "hash-a").expect("delete");
+
+        assert!(get_chat_media(&conn, "acc-a", "chat-a", "hash-a").is_none());
+        assert!(get_chat_media(&conn, "acc-a", "chat-a", "hash-b").is_some());
+    }
+
     #[test]
     fn keys_are_isolated_by_account_chat_and_original_hash() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -548,4 +799,106 @@ mod tests {
             "whitespace-padded mime must not match gallery filter"
         );
     }
+
+    #[test]
+    fn gallery_includes_local_only_recordings() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let conn = open_chat_media_db(&dir.path().to_string_lossy()).expect("open db");
+
+        let mut recording = sample_record("acc-a", "chat-a", "hash-1", 100);
+        recording.mime_type = "audio/ogg".to_string();
+        recording.filename = "call-recording.ogg".to_string();
+        recording.url = LOCAL_ONLY_URL.to_string();
+
+        let mut voice_note = sample_record("acc-a", "chat-a", "hash-2", 200);
+        voice_note.mime_type = "audio/mp4".to_string();
+
+        upsert_chat_media(&conn, &recording).expect("upsert recording");
+        upsert_chat_media(&conn, &voice_note).expect("upsert voice note");
+
+        let results = get_gallery_media(&conn, "acc-a", "chat-a");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].original_hash_hex, "hash-1");
+    }
+
+    #[test]
+    fn clearing_cache_keeps_local_only_media() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let conn = open_chat_media_db(&dir.path().to_string_lossy()).expect("open db");
+
+        let mut recording = sample_record("acc-a", "chat-a", "hash-1", 100);
+        recording.url = LOCAL_ONLY_URL.to_string();
+        upsert_chat_media(&conn, &recording).expect("upsert recording");
+        upsert_chat_media(&conn, &sample_record("acc-a", "chat-a", "hash-2", 200))
+            .expect("upsert photo");
+
+        clear_cached_chat_media(&conn).expect("clear");
+        let remaining = get_all_chat_media(&conn, "acc-a", "chat-a");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].original_hash_hex, "hash-1");
+    }
+
+    fn sample_transfer(
+        transfer_id: &str,
+        account_pubkey: &str,
+        direction: TransferDirection,
+        created_at: i64,
+    ) -> MediaTransferRecord {
+        MediaTransferRecord {
+            transfer_id: transfer_id.to_string(),
+            account_pubkey: account_pubkey.to_string(),
+            chat_id: "chat-a".to_string(),
+            direction,
+            message_id: format!("msg-{transfer_id}"),
+            original_hash_hex: "hash-a".to_string(),
+            encrypted_hash_hex: "enc-a".to_string(),
+            mime_type: "video/mp4".to_string(),
+            filename: "clip.mp4".to_string(),
+            staging_path: format!("/tmp/{transfer_id}.part"),
+            caption: String::new(),
+            imeta_tag_json: String::new(),
+            transferred_bytes: 0,
+            total_bytes: 4096,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn media_transfers_round_trip_and_survive_reopen() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().to_string_lossy().to_string();
+        let upload = sample_transfer("t-1", "acc-a", TransferDirection::Upload, 10);
+        let download = sample_transfer("t-2", "acc-a", TransferDirection::Download, 20);
+        let other_account = sample_transfer("t-3", "acc-b", TransferDirection::Upload, 5);
+
+        {
+            let conn = open_chat_media_db(&data_dir).expect("open db");
+            upsert_media_transfer(&conn, &download).expect("upsert download");
+            upsert_media_transfer(&conn, &upload).expect("upsert upload");
+            upsert_media_transfer(&conn, &other_account).expect("upsert other");
+        }
+
+        let conn = open_chat_media_db(&data_dir).expect("reopen db");
+        assert_eq!(get_media_transfers(&conn, "acc-a"), vec![upload, download]);
+        assert_eq!(get_media_transfers(&conn, "acc-b"), vec![other_account]);
+    }
+
+    #[test]
+    fn media_transfer_progress_updates_and_delete() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let conn = open_chat_media_db(&dir.path().to_string_lossy()).expect("open db");
+        upsert_media_transfer(
+            &conn,
+            &sample_transfer("t-1", "acc-a", TransferDirection::Upload, 10),
+        )
+        .expect("upsert");
+
+        update_media_transfer_progress(&conn, "t-1", 1024, 4096).expect("progress");
+        let got = get_media_transfers(&conn, "acc-a");
+        assert_eq!(got[0].transferred_bytes, 1024);
+        assert_eq!(got[0].total_bytes, 4096);
+
+        delete_media_transfer(&conn, "t-1").expect("delete");
+        assert!(get_media_transfers(&conn, "acc-a").is_empty());
+    }
 }
@@ -0,0 +1,126 @@
+// Per-chat notification modes. They live in `profile_db` so the notification
+// service extension can filter pushes by them. Muting a chat for good also
+// drops its push-server subscription; snoozed and mentions-only chats stay
+// subscribed so a snooze ends on time even while the app isn't running.
+
+use super::*;
+
+impl AppCore {
+    pub(super) fn load_chat_notification_modes(&mut self) {
+        self.chat_notification_modes = self
+            .profile_db
+            .as_ref()
+            .map(profile_db::load_chat_notification_modes)
+            .unwrap_or_default();
+        self.schedule_chat_snooze_expiry();
+    }
+
+    /// The chat's mode right now; a snooze that has run out reads as `All`.
+    pub(super) fn chat_notification_mode(&self, chat_id: &str) -> ChatNotificationMode {
+        match self.chat_notification_modes.get(chat_id) {
+            Some(ChatNotificationMode::MutedUntil { until }) if *until <= now_seconds() => {
+                ChatNotificationMode::All
+            }
+            Some(mode) => *mode,
+            None => ChatNotificationMode::All,
+        }
+    }
+
+    /// Chats the push server shouldn't send us anything for.
+    pub(super) fn muted_chat_ids(&self) -> impl Iterator<Item = &String> {
+        self.chat_notification_modes
+            .iter()
+            .filter(|(_, mode)| **mode == ChatNotificationMode::Muted)
+            .map(|(chat_id, _)| chat_id)
+    }
+
+    pub(super) fn set_chat_notification_mode(&mut self, chat_id: &str, mode: ChatNotificationMode) {
+        if !self
+            .session
+            .as_ref()
+            .is_some_and(|sess| sess.groups.contains_key(chat_id))
+        {
+            self.toast("Chat not found");
+            return;
+        }
+        if matches!(mode, ChatNotificationMode::MutedUntil { until } if until <= now_seconds()) {
+            self.toast("Snooze must end in the future");
+            return;
+        }
+        if self.chat_notification_mode(chat_id) == mode {
+            return;
+        }
+
+        if mode == ChatNotificationMode::All {
+            self.chat_notification_modes.remove(chat_id);
+        } else {
+            self.chat_notification_modes
+                .insert(chat_id.to_string(), mode);
+        }
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::save_chat_notification_mode(conn, chat_id, mode);
+        }
+        tracing::info!(chat_id, ?mode, "chat_notification_mode_set");
+        self.schedule_chat_snooze_expiry();
+        self.chat_notification_modes_changed();
+    }
+
+    fn schedule_chat_snooze_expiry(&mut self) {
+        let now = now_seconds();
+        let next = self
+            .chat_notification_modes
+            .values()
+            .filter_map(|mode| match mode {
+                ChatNotificationMode::MutedUntil { until } if *until > now => Some(*until),
+                _ => None,
+            })
+            .min();
+        match next {
+            Some(until) => self.chat_snooze_timer.schedule(
+                &self.runtime,
+                &self.core_sender,
+                Duration::from_secs((until - now) as u64),
+                |token| InternalEvent::ChatSnoozeExpired { token },
+            ),
+            None => self.chat_snooze_timer.cancel(),
+        }
+    }
+
+    /// Drop snoozes that have run out so the muted indicator clears.
+    pub(super) fn handle_chat_snooze_expired(&mut self, token: u64) {
+        if !self.chat_snooze_timer.is_current(token) || !self.is_logged_in() {
+            return;
+        }
+        let now = now_seconds();
+        let expired: Vec<String> = self
+            .chat_notification_modes
+            .iter()
+            .filter(|(_, mode)| {
+                matches!(mode, ChatNotificationMode::MutedUntil { until } if *until <= now)
+            })
+            .map(|(chat_id, _)| chat_id.clone())
+            .collect();
+        for chat_id in &expired {
+            self.chat_notification_modes.remove(chat_id);
+            if let Some(conn) = self.profile_db.as_ref() {
+                profile_db::save_chat_notification_mode(conn, chat_id, ChatNotificationMode::All);
+            }
+        }
+        self.schedule_chat_snooze_expiry();
+        if !expired.is_empty() {
+            self.chat_notification_modes_changed();
+        }
+    }
+
+    /// Re-derive chat summaries (and with them push subscriptions) plus the
+    /// open chat's mode.
+    fn chat_notification_modes_changed(&mut self) {
+        if let Some(chat_id) = self.state.current_chat.as_ref().map(|c| c.chat_id.clone()) {
+            let mode = self.chat_notification_mode(&chat_id);
+            if let Some(chat) = self.state.current_chat.as_mut() {
+                chat.notification_mode = mode;
+            }
+        }
+        self.refresh_chat_list_from_storage();
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::BTreeSet;
 use std::path::Path;
 
 use nostr_sdk::prelude::RelayUrl;
+use pika_media::stt::{FixtureStt, SttBackend, WhisperCliStt};
 use pika_relay_profiles::{
     app_default_key_package_relays, app_default_message_relays, LEGACY_APP_DEFAULT_MESSAGE_RELAYS,
 };
@@ -23,6 +24,10 @@ pub(super) struct AppConfig {
     pub(super) call_moq_url: Option<String>,
     pub(super) call_broadcast_prefix: Option<String>,
     pub(super) call_audio_backend: Option<String>,
+    // Live call captions: "fixture", or "whisper" to run whisper.cpp locally.
+    pub(super) call_stt_backend: Option<String>,
+    pub(super) call_stt_whisper_model: Option<String>,
+    pub(super) call_stt_whisper_bin: Option<String>,
     pub(super) notification_url: Option<String>,
     pub(super) agent_api_url: Option<String>,
     // Dev-only: run a one-shot QUIC+TLS probe on startup and log PASS/FAIL.
@@ -164,6 +169,30 @@ impl AppCore {
         set.into_iter().collect()
     }
 
+    /// Speech-to-text engine for call captions; `None` leaves calls uncaptioned.
+    pub(super) fn call_stt_backend(&self) -> Option<Box<dyn SttBackend>> {
+        match self.config.call_stt_backend.as_deref()?.trim() {
+            "fixture" => Some(Box::new(FixtureStt::default())),
+            "whisper" => {
+                let model = self
+                    .config
+                    .call_stt_whisper_model
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())?;
+                let binary = self
+                    .config
+                    .call_stt_whisper_bin
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or("whisper-cli");
+                Some(Box::new(WhisperCliStt::new(binary, model)))
+            }
+            _ => None,
+        }
+    }
+
     pub(super) fn external_signer_enabled(&self) -> bool {
         if let Some(enabled) = self.config.enable_external_signer {
             return enabled;
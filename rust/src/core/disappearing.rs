@@ -0,0 +1,418 @@
+// Disappearing messages: per-chat expiry timer agreed through in-group rumors,
+// plus a periodic purge of expired messages and their cached media.
+
+use pika_marmot_runtime::message::DisappearingTimerSetting;
+
+use super::*;
+
+const DISAPPEARING_PURGE_INTERVAL: Duration = Duration::from_secs(30);
+const DISAPPEARING_PURGE_PAGE: usize = 200;
+
+/// Latest timer setting seen for a chat. Only messages created at or after
+/// `set_at` are subject to `expire_after_secs`; `0` means the timer is off.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(super) struct DisappearingTimer {
+    pub(super) expire_after_secs: u64,
+    pub(super) set_at: i64,
+}
+
+impl DisappearingTimer {
+    fn is_active(&self) -> bool {
+        self.expire_after_secs > 0
+    }
+}
+
+impl AppCore {
+    fn disappearing_timers_path(&self) -> std::path::PathBuf {
+        std::path::Path::new(&self.account_dir).join("disappearing_timers.json")
+    }
+
+    pub(super) fn load_disappearing_timers(&mut self) {
+        let path = self.disappearing_timers_path();
+        self.disappearing_timers = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        if self
+            .disappearing_timers
+            .values()
+            .any(DisappearingTimer::is_active)
+        {
+            self.schedule_disappearing_purge();
+        }
+    }
+
+    fn save_disappearing_timers(&self) {
+        let path = self.disappearing_timers_path();
+        if let Ok(json) = serde_json::to_string(&self.disappearing_timers) {
+            let _ = std::fs::write(&path, json);
+        }
+    }
+
+    pub(super) fn disappearing_timer_secs(&self, chat_id: &str) -> Option<u64> {
+        self.disappearing_timers
+            .get(chat_id)
+            .filter(|timer| timer.is_active())
+            .map(|timer| timer.expire_after_secs)
+    }
+
+    pub(super) fn set_disappearing_timer(
+        &mut self,
+        chat_id: String,
+        expire_after_secs: Option<u64>,
+    ) {
+        if !self.is_logged_in() {
+            self.toast("Please log in first");
+            return;
+        }
+        let expire_after_secs = expire_after_secs.filter(|secs| *secs > 0);
+        if self.disappearing_timer_secs(&chat_id) == expire_after_secs {
+            return;
+        }
+
+        let created_at = Timestamp::now();
+        let prepared = match self.prepare_outbound_action_for_chat(
+            &chat_id,
+            OutboundConversationAction::DisappearingTimer {
+                expire_after_secs,
+                created_at,
+            },
+        ) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::warn!(err = %e, "disappearing timer create_message failed");
+                self.toast(format!("Update failed: {e}"));
+                return;
+            }
+        };
+
+        // Fire-and-forget publish; the rumor is already in local storage.
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let my_pubkey = sess.pubkey;
+        let client = sess.client.clone();
+        self.runtime.spawn(async move {
+            let _ = client.send_event(&prepared.wrapper).await;
+        });
+
+        self.record_disappearing_timer(
+            &chat_id,
+            &prepared.rumor_id.to_hex(),
+            &my_pubkey,
+            DisappearingTimerSetting::new(expire_after_secs),
+            created_at.as_secs() as i64,
+        );
+        self.refresh_chat_list_from_storage();
+        self.refresh_current_chat_if_open(&chat_id);
+    }
+
+    /// Apply a timer rumor received from the group. Returns `true` when it
+    /// changed the chat's setting.
+    pub(super) fn apply_disappearing_timer(
+        &mut self,
+        chat_id: &str,
+        msg: &message_types::Message,
+    ) -> bool {
+        let Some(setting) = DisappearingTimerSetting::parse(&msg.content) else {
+            tracing::warn!(chat_id, "ignoring malformed disappearing timer message");
+            return false;
+        };
+        self.record_disappearing_timer(
+            chat_id,
+            &msg.id.to_hex(),
+            &msg.pubkey,
+            setting,
+            msg.created_at.as_secs() as i64,
+        )
+    }
+
+    fn record_disappearing_timer(
+        &mut self,
+        chat_id: &str,
+        rumor_id_hex: &str,
+        actor: &PublicKey,
+        setting: DisappearingTimerSetting,
+        set_at: i64,
+    ) -> bool {
+        // Newest setting wins so every member converges regardless of
+        // delivery order.
+        if self
+            .disappearing_timers
+            .get(chat_id)
+            .is_some_and(|current| current.set_at > set_at)
+        {
+            return false;
+        }
+        let timer = DisappearingTimer {
+            expire_after_secs: setting.expire_after_secs().unwrap_or(0),
+            set_at,
+        };
+        if self.disappearing_timers.get(chat_id) == Some(&timer) {
+            return false;
+        }
+        self.disappearing_timers
+            .insert(chat_id.to_string(), timer.clone());
+        self.save_disappearing_timers();
+
+        let is_mine = self
+            .session
+            .as_ref()
+            .is_some_and(|sess| sess.pubkey == *actor);
+        let actor_name = if is_mine {
+            "You".to_string()
+        } else {
+            self.peer_display_name(actor)
+        };
+        self.append_call_timeline_event(
+            format!("disappearing:{rumor_id_hex}"),
+            chat_id.to_string(),
+            disappearing_timer_timeline_text(&actor_name, setting.expire_after_secs()),
+            set_at,
+        );
+
+        if timer.is_active() {
+            self.schedule_disappearing_purge();
+        }
+        true
+    }
+
+    /// Resend the chat's active timer after our commit added members. They
+    /// can't decrypt the rumor that set it, so without this they would keep
+    /// every message forever. The original `set_at` is kept so the resend does
+    /// not move the window for existing members.
+    pub(super) fn rebroadcast_disappearing_timer(&mut self, chat_id: &str) {
+        let Some(timer) = self
+            .disappearing_timers
+            .get(chat_id)
+            .filter(|timer| timer.is_active())
+            .cloned()
+        else {
+            return;
+        };
+        let prepared = match self.prepare_outbound_action_for_chat(
+            chat_id,
+            OutboundConversationAction::DisappearingTimer {
+                expire_after_secs: Some(timer.expire_after_secs),
+                created_at: Timestamp::from_secs(timer.set_at.max(0) as u64),
+            },
+        ) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::warn!(err = %e, chat_id, "disappearing timer rebroadcast failed");
+                return;
+            }
+        };
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let client = sess.client.clone();
+        self.runtime.spawn(async move {
+            let _ = client.send_event(&prepared.wrapper).await;
+        });
+    }
+
+    /// A message that arrives late, below the purge high-water mark, would be
+    /// skipped by the next purge; pull the mark back so it gets scanned.
+    pub(super) fn note_disappearing_message(&mut self, chat_id: &str, created_at: i64) {
+        if let Some(purged_through) = self.disappearing_purged_through.get_mut(chat_id) {
+            if created_at <= *purged_through {
+                *purged_through = created_at - 1;
+            }
+        }
+    }
+
+    fn schedule_disappearing_purge(&mut self) {
+        self.disappearing_purge_timer.schedule(
+            &self.runtime,
+            &self.core_sender,
+            DISAPPEARING_PURGE_INTERVAL,
+            |token| InternalEvent::DisappearingPurgeTick { token },
+        );
+    }
+
+    pub(super) fn handle_disappearing_purge_tick(&mut self, token: u64) {
+        if !self.disappearing_purge_timer.is_current(token) || !self.is_logged_in() {
+            return;
+        }
+        self.purge_expired_messages();
+        if self
+            .disappearing_timers
+            .values()
+            .any(DisappearingTimer::is_active)
+        {
+            self.schedule_disappearing_purge();
+        }
+    }
+
+    /// Scrub every chat-visible message whose timer has run out, then drop the
+    /// media records and cached files those messages referenced.
+    pub(super) fn purge_expired_messages(&mut self) {
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let account = sess.pubkey.to_hex();
+        let now = now_seconds();
+        let host = sess.host_context();
+
+        let mut purged_chats: Vec<String> = Vec::new();
+        let mut expired_media: Vec<(String, String)> = Vec::new(); // (chat_id, original_hash_hex)
+        let mut purged_through: Vec<(String, i64)> = Vec::new();
+        for (chat_id, timer) in &self.disappearing_timers {
+            if !timer.is_active() {
+                continue;
+            }
+            let cutoff = now - timer.expire_after_secs as i64;
+            if cutoff < timer.set_at {
+                continue;
+            }
+            let Some(group) = sess.groups.get(chat_id) else {
+                continue;
+            };
+            let manager = sess.mdk.media_manager(group.mls_group_id.clone());
+            // Messages come back newest first, so the scan can stop at the
+            // first one older than the timer or already covered by a purge.
+            let floor = self
+                .disappearing_purged_through
+                .get(chat_id)
+                .map_or(timer.set_at, |through| (*through + 1).max(timer.set_at));
+
+            let mut purged_any = false;
+            let mut failed_any = false;
+            let mut offset = 0;
+            'pages: loop {
+                let batch = sess
+                    .mdk
+                    .get_messages(
+                        &group.mls_group_id,
+                        Some(Pagination::new(Some(DISAPPEARING_PURGE_PAGE), Some(offset))),
+                    )
+                    .unwrap_or_default();
+                for m in &batch {
+                    let created_at = m.created_at.as_secs() as i64;
+                    if created_at < floor {
+                        break 'pages;
+                    }
+                    if created_at > cutoff || m.state == message_types::MessageState::Deleted {
+                        continue;
+                    }
+                    if !classify_app_message(m).is_some_and(|k| k.is_chat_visible()) {
+                        continue;
+                    }
+                    for tag in m.tags.iter().filter(|t| chat_media::is_imeta_tag(t)) {
+                        if let Ok(reference) = manager.parse_imeta_tag(tag) {
+                            expired_media
+                                .push((chat_id.clone(), hex::encode(reference.original_hash)));
+                        }
+                    }
+                    match host.expire_message(m) {
+                        Ok(()) => purged_any = true,
+                        Err(e) => {
+                            tracing::warn!(err = %e, chat_id, "expire message failed");
+                            failed_any = true;
+                            continue;
+                        }
+                    }
+                    if let Some(conn) = self.search_db.as_ref() {
+                        if let Err(e) = search_db::remove_message(conn, &m.id.to_hex()) {
+                            tracing::warn!(%e, "failed to drop expired message from search index");
+                        }
+                    }
+                }
+                if batch.len() < DISAPPEARING_PURGE_PAGE {
+                    break;
+                }
+                offset += batch.len();
+            }
+            if !failed_any {
+                purged_through.push((chat_id.clone(), cutoff));
+            }
+            if purged_any {
+                purged_chats.push(chat_id.clone());
+            }
+        }
+        self.disappearing_purged_through.extend(purged_through);
+
+        for (chat_id, hash) in expired_media {
+            if let Some(conn) = self.chat_media_db.as_ref() {
+                if let Err(e) = chat_media_db::delete_chat_media(conn, &account, &chat_id, &hash) {
+                    tracing::warn!(%e, "failed to delete expired chat media record");
+                }
+            }
+            if let Some(cache) = self.media_cache.get_mut(&chat_id) {
+                cache.remove(&hash);
+            }
+            if let Some(cache) = self.local_path_cache.get_mut(&chat_id) {
+                cache.remove(&hash);
+            }
+            let dir = chat_media::media_dir(&self.data_dir, &account, &chat_id, &hash);
+            if dir.exists() {
+                let _ = std::fs::remove_dir_all(&dir);
+            }
+        }
+
+        if purged_chats.is_empty() {
+            return;
+        }
+        self.refresh_chat_list_from_storage();
+        for chat_id in purged_chats {
+            self.refresh_current_chat_if_open(&chat_id);
+        }
+    }
+}
+
+fn disappearing_timer_timeline_text(actor: &str, expire_after_secs: Option<u64>) -> String {
+    match expire_after_secs {
+        Some(secs) => format!(
+            "{actor} set messages to disappear after {}",
+            format_timer_duration(secs)
+        ),
+        None => format!("{actor} turned off disappearing messages"),
+    }
+}
+
+fn format_timer_duration(secs: u64) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (7 * 24 * 3600, "week"),
+        (24 * 3600, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+    ];
+    let (count, unit) = UNITS
+        .iter()
+        .find(|(size, _)| secs >= *size && secs % size == 0)
+        .map(|(size, unit)| (secs / size, *unit))
+        .unwrap_or((secs, "second"));
+    if count == 1 {
+        format!("1 {unit}")
+    } else {
+        format!("{count} {unit}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_duration_uses_largest_whole_unit() {
+        assert_eq!(format_timer_duration(30), "30 seconds");
+        assert_eq!(format_timer_duration(300), "5 minutes");
+        assert_eq!(format_timer_duration(3600), "1 hour");
+        assert_eq!(format_timer_duration(5400), "90 minutes");
+        assert_eq!(format_timer_duration(86_400), "1 day");
+        assert_eq!(format_timer_duration(604_800), "1 week");
+    }
+
+    #[test]
+    fn timeline_text_covers_on_and_off() {
+        assert_eq!(
+            disappearing_timer_timeline_text("You", Some(3600)),
+            "You set messages to disappear after 1 hour"
+        );
+        assert_eq!(
+            disappearing_timer_timeline_text("Alice", None),
+            "Alice turned off disappearing messages"
+        );
+    }
+}
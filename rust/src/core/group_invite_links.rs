@@ -0,0 +1,437 @@
+// Group invite links. Admins mint revocable tokens for a group; whoever opens
+// `<scheme>://join/<admin npub>/<token>` gift-wraps a join request to that
+// admin, whose client adds them straight away or stages the request for
+// approval. Tokens, pending requests and the admins we asked to let us in all
+// live in profile_db.
+
+use pika_marmot_runtime::invite_link::{
+    GroupInviteLink, JoinRequest, INVITE_TOKEN_LEN, JOIN_REQUEST_KIND, JOIN_REQUEST_TTL_SECS,
+};
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use super::profile_db::GroupInviteLinkRow;
+use super::*;
+use crate::state::{GroupInviteLinkInfo, GroupJoinRequest};
+
+impl AppCore {
+    pub(super) fn group_invite_links(&self, chat_id: &str) -> Vec<GroupInviteLinkInfo> {
+        let (Some(sess), Some(conn)) = (self.session.as_ref(), self.profile_db.as_ref()) else {
+            return vec![];
+        };
+        let admin_npub = sess
+            .pubkey
+            .to_bech32()
+            .unwrap_or_else(|_| sess.pubkey.to_hex());
+        profile_db::load_group_invite_links(conn, chat_id)
+            .into_iter()
+            .map(|link| GroupInviteLinkInfo {
+                token: link.token,
+                admin_npub: admin_npub.clone(),
+                multi_use: link.multi_use,
+                requires_approval: link.requires_approval,
+                created_at: link.created_at,
+                use_count: link.use_count,
+            })
+            .collect()
+    }
+
+    pub(super) fn group_join_requests(&self, chat_id: &str) -> Vec<GroupJoinRequest> {
+        let Some(conn) = self.profile_db.as_ref() else {
+            return vec![];
+        };
+        let requests = profile_db::load_group_join_requests(conn, chat_id);
+        let mut missing_profile_pubkeys: HashSet<PublicKey> = HashSet::new();
+        let requests = requests
+            .into_iter()
+            .map(|(hex, requested_at)| {
+                let cached = self.profiles.get(&hex);
+                let pk = PublicKey::from_hex(&hex).ok();
+                if let (None, Some(pk)) = (cached, pk) {
+                    missing_profile_pubkeys.insert(pk);
+                }
+                GroupJoinRequest {
+                    npub: pk
+                        .and_then(|pk| pk.to_bech32().ok())
+                        .unwrap_or_else(|| hex.clone()),
+                    name: cached.and_then(|p| p.name.clone()),
+                    picture_url: cached.and_then(|p| p.display_picture_url(&self.data_dir, &hex)),
+                    requested_at,
+                    pubkey: hex,
+                }
+            })
+            .collect();
+        self.fetch_missing_profiles(missing_profile_pubkeys);
+        requests
+    }
+
+    pub(super) fn create_group_invite_link(
+        &mut self,
+        chat_id: &str,
+        multi_use: bool,
+        requires_approval: bool,
+    ) {
+        if !self.require_group_admin(chat_id) {
+            return;
+        }
+        let Some(conn) = self.profile_db.as_ref() else {
+            self.toast("Couldn't create invite link");
+            return;
+        };
+        let mut bytes = [0u8; INVITE_TOKEN_LEN / 2];
+        OsRng.fill_bytes(&mut bytes);
+        let link = GroupInviteLinkRow {
+            token: hex::encode(bytes),
+            chat_id: chat_id.to_string(),
+            multi_use,
+            requires_approval,
+            created_at: now_seconds(),
+            use_count: 0,
+        };
+        profile_db::save_group_invite_link(conn, &link);
+        tracing::info!(
+            chat_id,
+            multi_use,
+            requires_approval,
+            "group_invite_link_created"
+        );
+        self.refresh_current_chat_if_open(chat_id);
+    }
+
+    pub(super) fn revoke_group_invite_link(&mut self, chat_id: &str, token: &str) {
+        if !self.require_group_admin(chat_id) {
+            return;
+        }
+        let Some(conn) = self.profile_db.as_ref() else {
+            return;
+        };
+        match profile_db::find_group_invite_link(conn, token.trim()) {
+            Some(link) if link.chat_id == chat_id => {
+                profile_db::delete_group_invite_link(conn, &link.token);
+                tracing::info!(chat_id, "group_invite_link_revoked");
+            }
+            _ => self.toast("Invite link not found"),
+        }
+        self.refresh_current_chat_if_open(chat_id);
+    }
+
+    /// Gift-wrap a join request to the admin behind `link`. Their welcome is
+    /// joined without a trip through the invitations inbox.
+    pub(super) fn join_group_via_invite_link(&mut self, link: &str) {
+        let Some(link) = GroupInviteLink::parse(link) else {
+            self.toast("Invalid invite link");
+            return;
+        };
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        if link.admin == sess.pubkey {
+            self.toast("That's your own invite link");
+            return;
+        }
+        let admin_hex = link.admin.to_hex();
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::save_sent_join_request(conn, &admin_hex, now_seconds());
+        }
+        tracing::info!(admin = %admin_hex, "group_join_request_sent");
+        if !self.network_enabled() {
+            return;
+        }
+
+        let client = sess.client.clone();
+        let my_pubkey = sess.pubkey;
+        let relays = self.default_relays();
+        let tx = self.core_sender.clone();
+        self.runtime.spawn(async move {
+            let toast = |msg: String| {
+                let _ = tx.send(CoreMsg::Internal(Box::new(InternalEvent::Toast(msg))));
+            };
+            for r in relays.iter().cloned() {
+                let _ = client.add_relay(r).await;
+            }
+            client.connect().await;
+            client.wait_for_connection(Duration::from_secs(4)).await;
+            let signer = match client.signer().await {
+                Ok(signer) => signer,
+                Err(e) => {
+                    toast(format!("Couldn't send join request: {e}"));
+                    return;
+                }
+            };
+            let rumor =
+                EventBuilder::new(JOIN_REQUEST_KIND, JoinRequest::new(link.token).to_content())
+                    .build(my_pubkey);
+            let expires = Timestamp::from_secs(Timestamp::now().as_secs() + JOIN_REQUEST_TTL_SECS);
+            let giftwrap = match EventBuilder::gift_wrap(
+                &signer,
+                &link.admin,
+                rumor,
+                [Tag::expiration(expires)],
+            )
+            .await
+            {
+                Ok(giftwrap) => giftwrap,
+                Err(e) => {
+                    toast(format!("Couldn't send join request: {e}"));
+                    return;
+                }
+            };
+            match super::relay_publish::publish_event_with_retry(
+                &client,
+                &relays,
+                &giftwrap,
+                4,
+                "join request publish",
+                true,
+            )
+            .await
+            {
+                super::relay_publish::PublishOutcome::Ok => {
+                    toast("Join request sent".into());
+                }
+                super::relay_publish::PublishOutcome::Err(e) => {
+                    toast(format!("Couldn't send join request: {e}"));
+                }
+            }
+        });
+    }
+
+    /// Whether we asked `admin_hex` to let us in recently. Forgets the request.
+    pub(super) fn take_sent_join_request(&self, admin_hex: &str) -> bool {
+        let Some(conn) = self.profile_db.as_ref() else {
+            return false;
+        };
+        let since = now_seconds() - JOIN_REQUEST_TTL_SECS as i64;
+        if !profile_db::has_sent_join_request(conn, admin_hex, since) {
+            return false;
+        }
+        profile_db::remove_sent_join_request(conn, admin_hex);
+        true
+    }
+
+    pub(super) fn handle_join_request(&mut self, mut rumor: UnsignedEvent) {
+        let Some(request) = JoinRequest::parse(&rumor.content) else {
+            tracing::debug!("join request ignored (malformed)");
+            return;
+        };
+        let now = now_seconds();
+        let since = now - JOIN_REQUEST_TTL_SECS as i64;
+        if (rumor.created_at.as_secs() as i64) < since {
+            tracing::debug!("join request ignored (expired)");
+            return;
+        }
+        let (Some(sess), Some(conn)) = (self.session.as_ref(), self.profile_db.as_ref()) else {
+            return;
+        };
+        rumor.ensure_id();
+        if !profile_db::mark_join_request_handled(conn, &rumor.id().to_hex(), now, since) {
+            tracing::debug!("join request ignored (already handled)");
+            return;
+        }
+        let Some(link) = profile_db::find_group_invite_link(conn, &request.token) else {
+            tracing::info!("join request ignored (unknown or revoked link)");
+            return;
+        };
+        let requester = rumor.pubkey;
+        let requester_hex = requester.to_hex();
+        let Some(entry) = sess.groups.get(&link.chat_id) else {
+            tracing::info!(chat_id = %link.chat_id, "join request ignored (group gone)");
+            return;
+        };
+        if !entry.admin_pubkeys.contains(&sess.pubkey.to_hex()) {
+            tracing::info!(chat_id = %link.chat_id, "join request ignored (no longer admin)");
+            return;
+        }
+        if entry.members.iter().any(|m| m.pubkey == requester) {
+            tracing::debug!(chat_id = %link.chat_id, "join request ignored (already a member)");
+            return;
+        }
+        let group_name = entry
+            .group_name
+            .clone()
+            .filter(|name| !name.trim().is_empty());
+
+        tracing::info!(
+            chat_id = %link.chat_id,
+            requester = %requester_hex,
+            requires_approval = link.requires_approval,
+            "group_join_request_received"
+        );
+
+        if link.requires_approval {
+            profile_db::save_group_join_request(
+                conn,
+                &link.chat_id,
+                &requester_hex,
+                &link.token,
+                now,
+            );
+            let name = self.peer_display_name(&requester);
+            match group_name {
+                Some(group) => self.toast(format!("{name} wants to join \"{group}\"")),
+                None => self.toast(format!("{name} wants to join a group")),
+            }
+            self.refresh_current_chat_if_open(&link.chat_id);
+        } else {
+            self.add_via_invite_link(&link.chat_id, requester, link.token);
+        }
+    }
+
+    pub(super) fn approve_join_request(&mut self, chat_id: &str, pubkey: &str) {
+        let Some((requester, token)) = self.take_join_request(chat_id, pubkey) else {
+            return;
+        };
+        // A one-time link may have let someone else in since this request came in.
+        let link_open = self
+            .profile_db
+            .as_ref()
+            .and_then(|conn| profile_db::find_group_invite_link(conn, &token))
+            .is_some();
+        if !link_open {
+            self.toast("That invite link was revoked or already used");
+            self.refresh_current_chat_if_open(chat_id);
+            return;
+        }
+        self.add_via_invite_link(chat_id, requester, token);
+    }
+
+    pub(super) fn decline_join_request(&mut self, chat_id: &str, pubkey: &str) {
+        if self.take_join_request(chat_id, pubkey).is_some() {
+            self.refresh_current_chat_if_open(chat_id);
+        }
+    }
+
+    /// Queue `requester` to be added to `chat_id`. Only one commit per group
+    /// can be in flight, so joins arriving meanwhile wait their turn.
+    fn add_via_invite_link(&mut self, chat_id: &str, requester: PublicKey, token: String) {
+        let in_flight = self
+            .pending_invite_link_joins
+            .contains_key(&(chat_id.to_string(), requester.to_hex()));
+        let queue = self
+            .queued_invite_link_joins
+            .entry(chat_id.to_string())
+            .or_default();
+        if !in_flight && !queue.iter().any(|(pk, _)| *pk == requester) {
+            queue.push((requester, token));
+        }
+        self.start_invite_link_joins(chat_id);
+        self.refresh_current_chat_if_open(chat_id);
+    }
+
+    /// Add everyone queued for `chat_id` in one commit, unless an add or other
+    /// group update is still in flight there.
+    fn start_invite_link_joins(&mut self, chat_id: &str) {
+        if self.pending_group_ops.contains(chat_id)
+            || self
+                .pending_invite_link_joins
+                .keys()
+                .any(|(chat, _)| chat == chat_id)
+        {
+            return;
+        }
+        let Some(queued) = self
+            .queued_invite_link_joins
+            .remove(chat_id)
+            .filter(|queued| !queued.is_empty())
+        else {
+            return;
+        };
+        let mut requesters = Vec::with_capacity(queued.len());
+        for (requester, token) in queued {
+            self.pending_invite_link_joins
+                .insert((chat_id.to_string(), requester.to_hex()), token);
+            requesters.push(requester);
+        }
+        tracing::info!(
+            chat_id,
+            count = requesters.len(),
+            "invite_link_joins_started"
+        );
+        self.add_group_members(chat_id.to_string(), requesters);
+    }
+
+    /// Count a use of the link each newly added member joined through, now
+    /// that the commit adding them is published. One-time links go away.
+    pub(super) fn use_invite_links_for_joins(&mut self, chat_id: &str, added: &[PublicKey]) {
+        if let Some(conn) = self.profile_db.as_ref() {
+            for pk in added {
+                let Some(token) = self
+                    .pending_invite_link_joins
+                    .remove(&(chat_id.to_string(), pk.to_hex()))
+                else {
+                    continue;
+                };
+                match profile_db::find_group_invite_link(conn, &token) {
+                    Some(link) if link.multi_use => {
+                        profile_db::record_group_invite_link_use(conn, &token)
+                    }
+                    Some(_) => profile_db::delete_group_invite_link(conn, &token),
+                    None => continue,
+                }
+                tracing::info!(chat_id, "group_invite_link_used");
+            }
+        }
+        self.start_invite_link_joins(chat_id);
+    }
+
+    /// Forget in-flight link joins whose add failed, then move on to the next
+    /// queued ones.
+    pub(super) fn drop_invite_link_joins(&mut self, chat_id: &str, peers: &[PublicKey]) {
+        for pk in peers {
+            if self
+                .pending_invite_link_joins
+                .remove(&(chat_id.to_string(), pk.to_hex()))
+                .is_some()
+            {
+                tracing::info!(chat_id, requester = %pk.to_hex(), "invite_link_join_failed");
+            }
+        }
+        self.start_invite_link_joins(chat_id);
+    }
+
+    /// Put in-flight link joins back at the front of the queue when their
+    /// commit couldn't start because another group update got there first.
+    /// Returns whether any of `peers` joined through a link.
+    pub(super) fn requeue_invite_link_joins(&mut self, chat_id: &str, peers: &[PublicKey]) -> bool {
+        let mut requeued = Vec::new();
+        for pk in peers {
+            if let Some(token) = self
+                .pending_invite_link_joins
+                .remove(&(chat_id.to_string(), pk.to_hex()))
+            {
+                requeued.push((*pk, token));
+            }
+        }
+        if requeued.is_empty() {
+            return false;
+        }
+        let queue = self
+            .queued_invite_link_joins
+            .entry(chat_id.to_string())
+            .or_default();
+        requeued.append(queue);
+        *queue = requeued;
+        true
+    }
+
+    /// Pops a pending request, returning the requester and its link token.
+    fn take_join_request(&mut self, chat_id: &str, pubkey: &str) -> Option<(PublicKey, String)> {
+        let requester = match PublicKey::parse(pubkey.trim()) {
+            Ok(pk) => pk,
+            Err(e) => {
+                self.toast(format!("Invalid pubkey: {e}"));
+                return None;
+            }
+        };
+        if !self.require_group_admin(chat_id) {
+            return None;
+        }
+        let conn = self.profile_db.as_ref()?;
+        let Some(token) = profile_db::remove_group_join_request(conn, chat_id, &requester.to_hex())
+        else {
+            self.toast("Join request not found");
+            return None;
+        };
+        Some((requester, token))
+    }
+}
@@ -0,0 +1,71 @@
+// Group roles. Only admins may commit membership or group-data changes
+// (MIP-03), so every such action checks our role before building a commit,
+// and commits MDK refuses for lacking one are surfaced rather than dropped.
+
+use super::*;
+
+impl AppCore {
+    /// Toasts and returns false unless we're an admin of `chat_id`.
+    pub(super) fn require_group_admin(&mut self, chat_id: &str) -> bool {
+        let Some(sess) = self.session.as_ref() else {
+            return false;
+        };
+        let Some(entry) = sess.groups.get(chat_id) else {
+            self.toast("Chat not found");
+            return false;
+        };
+        if entry.admin_pubkeys.contains(&sess.pubkey.to_hex()) {
+            return true;
+        }
+        self.toast("Only group admins can do that");
+        false
+    }
+
+    pub(super) fn change_group_admin(&mut self, chat_id: &str, pubkey: &str, change: AdminChange) {
+        let target = match PublicKey::parse(pubkey.trim()) {
+            Ok(pk) => pk,
+            Err(e) => {
+                self.toast(format!("Invalid pubkey: {e}"));
+                return;
+            }
+        };
+        if !self.require_group_admin(chat_id) {
+            return;
+        }
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let Some(entry) = sess.groups.get(chat_id) else {
+            return;
+        };
+        let prepared =
+            match sess
+                .host_context()
+                .prepare_admin_change(&entry.mls_group_id, target, change)
+            {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    self.toast(format!("Couldn't update admins: {e}"));
+                    return;
+                }
+            };
+        tracing::info!(chat_id, target = %target.to_hex(), ?change, "group_admin_change");
+        self.publish_prepared_evolution(chat_id, prepared);
+    }
+
+    pub(super) fn handle_rejected_commit(&mut self, chat_id: &str, reason: &str) {
+        tracing::warn!(chat_id, reason, "rejected commit from non-admin");
+        let name = self
+            .session
+            .as_ref()
+            .and_then(|sess| sess.groups.get(chat_id))
+            .and_then(|entry| entry.group_name.clone())
+            .filter(|name| !name.trim().is_empty());
+        match name {
+            Some(name) => self.toast(format!(
+                "Ignored a change to \"{name}\" from someone who isn't an admin"
+            )),
+            None => self.toast("Ignored a group change from someone who isn't an admin"),
+        }
+    }
+}
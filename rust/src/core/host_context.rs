@@ -5,7 +5,6 @@ pub(super) struct AppHostContext<'a> {
     session: &'a Session,
 }
 
-#[cfg(test)]
 pub(super) fn runtime_for_mdk(mdk: &PikaMdk) -> MarmotRuntime<'_> {
     MarmotRuntime::new(mdk)
 }
@@ -79,6 +78,16 @@ impl<'a> AppHostContext<'a> {
             .prepare_add_members(&group.mls_group_id, key_package_events)
     }
 
+    pub(super) fn prepare_admin_change(
+        &self,
+        mls_group_id: &GroupId,
+        target: PublicKey,
+        change: AdminChange,
+    ) -> anyhow::Result<PreparedMembershipEvolution> {
+        self.runtime()
+            .prepare_admin_change(mls_group_id, &self.session.pubkey, target, change)
+    }
+
     pub(super) fn prepare_evolution(
         &self,
         mls_group_id: GroupId,
@@ -101,6 +110,18 @@ impl<'a> AppHostContext<'a> {
         self.runtime().finalize_published_evolution(prepared)
     }
 
+    pub(super) fn get_messages(
+        &self,
+        chat_id: &str,
+        pagination: Option<Pagination>,
+    ) -> anyhow::Result<Vec<message_types::Message>> {
+        self.runtime().get_messages(chat_id, pagination)
+    }
+
+    pub(super) fn expire_message(&self, message: &message_types::Message) -> anyhow::Result<()> {
+        self.runtime().expire_message(message)
+    }
+
     pub(super) fn process_event(&self, event: &Event) -> anyhow::Result<Option<ConversationEvent>> {
         self.runtime().process_event(event)
     }
@@ -133,6 +154,24 @@ impl<'a> AppHostContext<'a> {
             .finish_upload(mls_group_id, upload, uploaded_blob)
     }
 
+    pub(super) fn pending_imeta_tag(
+        &self,
+        mls_group_id: &GroupId,
+        upload: &EncryptedMediaUpload,
+    ) -> Tag {
+        self.runtime().pending_imeta_tag(mls_group_id, upload)
+    }
+
+    pub(super) fn finish_pending_upload(
+        &self,
+        mls_group_id: &GroupId,
+        pending_imeta_tag: &Tag,
+        uploaded_blob: pika_marmot_runtime::media::UploadedBlob,
+    ) -> anyhow::Result<pika_marmot_runtime::media::RuntimeMediaUploadResult> {
+        self.runtime()
+            .finish_pending_upload(mls_group_id, pending_imeta_tag, uploaded_blob)
+    }
+
     pub(super) fn decrypt_downloaded_media(
         &self,
         mls_group_id: &GroupId,
@@ -189,6 +228,46 @@ impl<'a> AppHostContext<'a> {
         self.runtime().prepare_end_call_signal(call_id, reason)
     }
 
+    pub(super) fn prepare_call_directory_signal(
+        &self,
+        call_id: &str,
+        directory: &pika_media::directory::DirectoryMessage,
+    ) -> Result<pika_marmot_runtime::call_runtime::PreparedCallSignal, String> {
+        self.runtime()
+            .prepare_call_directory_signal(call_id, directory)
+    }
+
+    pub(super) fn prepare_call_media_state_signal(
+        &self,
+        call_id: &str,
+        muted: bool,
+        camera_enabled: bool,
+    ) -> Result<pika_marmot_runtime::call_runtime::PreparedCallSignal, String> {
+        self.runtime()
+            .prepare_call_media_state_signal(call_id, muted, camera_enabled)
+    }
+
+    pub(super) fn prepare_call_recording_signal(
+        &self,
+        call_id: &str,
+        recording: bool,
+    ) -> Result<pika_marmot_runtime::call_runtime::PreparedCallSignal, String> {
+        self.runtime()
+            .prepare_call_recording_signal(call_id, recording)
+    }
+
+    pub(super) fn rotate_call_media_crypto(
+        &self,
+        group: GroupCallContext<'_>,
+        call_id: &str,
+        session: &call_control::CallSessionParams,
+        peer_pubkey_hex: &str,
+        current: &pika_marmot_runtime::call::CallMediaCryptoContext,
+    ) -> Result<Option<pika_marmot_runtime::call::CallMediaCryptoContext>, String> {
+        self.runtime()
+            .rotate_call_media_crypto(group, call_id, session, peer_pubkey_hex, current)
+    }
+
     pub(super) fn handle_inbound_call_signal(
         &self,
         ctx: InboundSignalContext<'_>,
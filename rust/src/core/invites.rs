@@ -0,0 +1,168 @@
+//! Invitation inbox. MLS welcomes are staged when they arrive and wait in
+//! `AppState.invitations` until the user accepts or declines them. Only
+//! welcomes from people we follow may be joined straight away, and only
+//! while `auto_accept_followed_invites` is on.
+
+use mdk_storage_traits::welcomes::types::Welcome;
+
+use super::*;
+use crate::state::InvitationSummary;
+
+impl AppCore {
+    /// Whether a welcome from `inviter_hex` is joined without asking.
+    pub(super) fn auto_accepts_invite_from(&self, inviter_hex: &str) -> bool {
+        self.state.auto_accept_followed_invites
+            && self
+                .profile_db
+                .as_ref()
+                .is_some_and(|conn| profile_db::is_followed(conn, inviter_hex))
+    }
+
+    /// Rebuild `state.invitations` from MDK's pending welcomes. Callers emit.
+    pub(super) fn refresh_invitations(&mut self) {
+        let Some(sess) = self.session.as_ref() else {
+            self.state.invitations = vec![];
+            return;
+        };
+        let pending = match sess.mdk.get_pending_welcomes(None) {
+            Ok(pending) => pending,
+            Err(e) => {
+                tracing::warn!(%e, "get_pending_welcomes failed");
+                return;
+            }
+        };
+
+        let mut missing_profile_pubkeys: HashSet<PublicKey> = HashSet::new();
+        let mut invitations: Vec<InvitationSummary> = pending
+            .iter()
+            .map(|w| {
+                let hex = w.welcomer.to_hex();
+                let cached = self.profiles.get(&hex);
+                if cached.is_none() {
+                    missing_profile_pubkeys.insert(w.welcomer);
+                }
+                let group_name = w.group_name.trim();
+                InvitationSummary {
+                    invite_id: w.wrapper_event_id.to_hex(),
+                    chat_id: hex::encode(w.nostr_group_id),
+                    group_name: (!group_name.is_empty()).then(|| group_name.to_string()),
+                    member_count: w.member_count,
+                    inviter_npub: w.welcomer.to_bech32().unwrap_or_else(|_| hex.clone()),
+                    inviter_name: cached.and_then(|p| p.name.clone()),
+                    inviter_picture_url: cached
+                        .and_then(|p| p.display_picture_url(&self.data_dir, &hex)),
+                    inviter_followed: self
+                        .profile_db
+                        .as_ref()
+                        .is_some_and(|conn| profile_db::is_followed(conn, &hex)),
+                    received_at: w.event.created_at.as_secs() as i64,
+                    inviter_pubkey: hex,
+                }
+            })
+            .collect();
+        invitations.sort_by_key(|i| std::cmp::Reverse(i.received_at));
+        self.state.invitations = invitations;
+        self.fetch_missing_profiles(missing_profile_pubkeys);
+    }
+
+    /// Join a staged welcome's group. No subscription work or backlog
+    /// catch-up here; `refresh_all_from_storage` resubscribes afterwards.
+    pub(super) fn join_welcome(&mut self, welcome: &Welcome) -> bool {
+        let Some(sess) = self.session.as_ref() else {
+            return false;
+        };
+        let client = sess.client.clone();
+        let mut seen = HashSet::new();
+        let accept_result = self.runtime.block_on(async {
+            accept_welcome_and_catch_up(&sess.mdk, &client, &[], welcome, &mut seen, 0, |_| async {
+                Ok(())
+            })
+            .await
+        });
+        match accept_result {
+            Ok(_) => {
+                tracing::info!(
+                    nostr_group_id = %hex::encode(welcome.nostr_group_id),
+                    group_name = %welcome.group_name,
+                    "welcome_accepted"
+                );
+                true
+            }
+            Err(e) => {
+                tracing::error!(%e, "accept_welcome failed");
+                self.toast(format!("Welcome accept failed: {e}"));
+                false
+            }
+        }
+    }
+
+    /// The welcome used up our key package: delete it best-effort and
+    /// publish a fresh one.
+    pub(super) fn rotate_key_package_after_welcome(&mut self, rumor: &UnsignedEvent) {
+        if !self.network_enabled() {
+            return;
+        }
+        if let Some(kp_event_id) = referenced_key_package_event_id(rumor) {
+            self.delete_event_best_effort(kp_event_id);
+        }
+        self.ensure_key_package_published_best_effort();
+    }
+
+    fn pending_welcome_by_invite_id(&mut self, invite_id: &str) -> Option<Welcome> {
+        let sess = self.session.as_ref()?;
+        let wrapper_id = EventId::from_hex(invite_id.trim()).ok();
+        let found = wrapper_id.and_then(|id| match sess.mdk.get_pending_welcomes(None) {
+            Ok(pending) => find_pending_welcome(&pending, &id).cloned(),
+            Err(e) => {
+                tracing::warn!(%e, "get_pending_welcomes failed");
+                None
+            }
+        });
+        if found.is_none() {
+            self.toast("Invitation not found");
+            self.refresh_chat_list_from_storage();
+        }
+        found
+    }
+
+    pub(super) fn accept_invite(&mut self, invite_id: &str) {
+        let Some(welcome) = self.pending_welcome_by_invite_id(invite_id) else {
+            return;
+        };
+        if self.join_welcome(&welcome) {
+            self.rotate_key_package_after_welcome(&welcome.event);
+        }
+        self.refresh_all_from_storage();
+    }
+
+    pub(super) fn decline_invite(&mut self, invite_id: &str) {
+        let Some(welcome) = self.pending_welcome_by_invite_id(invite_id) else {
+            return;
+        };
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        if let Err(e) = sess.mdk.decline_welcome(&welcome) {
+            tracing::error!(%e, "decline_welcome failed");
+            self.toast(format!("Decline failed: {e}"));
+            return;
+        }
+        tracing::info!(
+            nostr_group_id = %hex::encode(welcome.nostr_group_id),
+            "welcome_declined"
+        );
+        self.rotate_key_package_after_welcome(&welcome.event);
+        self.refresh_chat_list_from_storage();
+    }
+
+    pub(super) fn set_auto_accept_followed_invites(&mut self, enabled: bool) {
+        if self.state.auto_accept_followed_invites == enabled {
+            return;
+        }
+        self.state.auto_accept_followed_invites = enabled;
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::save_auto_accept_followed_invites(conn, enabled);
+        }
+        self.emit_state();
+    }
+}
@@ -1,18 +1,32 @@
+mod accounts;
 mod agent;
+mod backup;
+mod backup_archive;
+mod blocks;
 mod call_control;
+mod call_quality;
+mod call_recording;
 mod call_runtime;
 mod chat_media;
 mod chat_media_db;
+mod chat_notifications;
 mod config;
+mod disappearing;
+mod group_invite_links;
 mod group_profile;
+mod group_roles;
 mod host_context;
 mod interop;
+mod invites;
 mod min_version;
 mod profile;
 mod profile_db;
 mod profile_pics;
 mod push;
+mod read_receipts;
 mod relay_publish;
+mod search;
+mod search_db;
 mod session;
 mod storage;
 
@@ -25,6 +39,7 @@ use std::time::Duration;
 use anyhow::Context;
 use flume::Sender;
 use hypernote_protocol as hn;
+use pika_media::quality::QualityCounters;
 
 use crate::actions::AppAction;
 use crate::bunker_signer::{
@@ -38,9 +53,9 @@ use crate::external_signer::{
 use crate::mdk_support::{open_mdk, PikaMdk};
 use crate::state::now_seconds;
 use crate::state::{
-    AuthMode, AuthState, BusyState, CallDebugStats, CallStatus, ChatMediaAttachment, ChatMessage,
-    ChatSummary, ChatViewState, MessageDeliveryState, MyProfileState, Screen, VoiceRecordingPhase,
-    VoiceRecordingState,
+    AuthMode, AuthState, BusyState, CallDebugStats, CallStatus, CallVideoProfile,
+    ChatMediaAttachment, ChatMessage, ChatNotificationMode, ChatSummary, ChatViewState,
+    MessageDeliveryState, MyProfileState, Screen, VoiceRecordingPhase, VoiceRecordingState,
 };
 use crate::updates::{AppUpdate, CoreMsg, InternalEvent};
 
@@ -56,8 +71,9 @@ use pika_marmot_runtime::call_runtime::{
 use pika_marmot_runtime::conversation::{
     ConversationEvent, RuntimeApplicationMessage, RuntimeGroupUpdate, RuntimeGroupUpdateKind,
 };
+use pika_marmot_runtime::invite_link::JOIN_REQUEST_KIND;
 use pika_marmot_runtime::membership::{
-    EvolutionPublishStatus, MembershipUpdateResult, PreparedMembershipEvolution,
+    AdminChange, EvolutionPublishStatus, MembershipUpdateResult, PreparedMembershipEvolution,
 };
 #[cfg(test)]
 pub(crate) use pika_marmot_runtime::message::TYPING_INDICATOR_KIND;
@@ -74,7 +90,8 @@ use pika_marmot_runtime::welcome::{accept_welcome_and_catch_up, find_pending_wel
 pub(crate) fn load_cached_profiles(data_dir: &str) -> Vec<crate::state::FollowListEntry> {
     use nostr_sdk::ToBech32;
 
-    let conn = match profile_db::open_profile_db(data_dir) {
+    let account_dir = accounts::active_account_dir(data_dir, &accounts::load_registry(data_dir));
+    let conn = match profile_db::open_profile_db(&account_dir) {
         Ok(c) => c,
         Err(_) => return vec![],
     };
@@ -224,6 +241,10 @@ async fn fetch_key_packages_for_peers(
 }
 
 fn classify_app_message(msg: &message_types::Message) -> Option<AppMessageKind> {
+    // Expired (disappearing) messages keep their row but have been scrubbed.
+    if msg.state == message_types::MessageState::Deleted {
+        return None;
+    }
     let kind = msg.kind;
     let classified = classify_shared_message(kind, &msg.content, msg.tags.iter());
     classified.or_else(|| {
@@ -502,7 +523,11 @@ struct LocalOutgoing {
 struct PendingMediaSend {
     chat_id: String,
     caption: String,
-    upload: EncryptedMediaUpload,
+    /// imeta tag built before the upload; it gets the Blossom URL once the blob lands.
+    imeta_tag: Tag,
+    original_hash_hex: String,
+    upload_mime: String,
+    encrypted_hash_hex: String,
     account_pubkey: String,
     temp_rumor_id: String,
     encrypted_data: Vec<u8>,
@@ -524,6 +549,8 @@ struct BatchMediaItem {
     upload: EncryptedMediaUpload,
     encrypted_data: Vec<u8>,
     uploaded_url: Option<String>,
+    /// Blurhash / poster to add to the imeta tag once the blob is uploaded.
+    preview: pika_marmot_runtime::media::MediaPreview,
 }
 
 #[derive(Debug, Clone)]
@@ -684,11 +711,17 @@ pub struct AppCore {
     bunker_signer_connector: SharedBunkerSignerConnector,
 
     data_dir: String,
+    // Storage dir of the active account (profile/media dbs, per-chat JSON
+    // state); the data dir itself while no account is signed in.
+    account_dir: String,
     keychain_group: String,
     config: config::AppConfig,
     runtime: tokio::runtime::Runtime,
 
     session: Option<Session>,
+    accounts: accounts::AccountRegistry,
+    account_signers: HashMap<String, accounts::AccountSigner>, // pubkey hex -> signer
+    background_accounts: HashMap<String, accounts::BackgroundAccount>, // pubkey hex -> watcher
 
     subs_recompute_in_flight: bool,
     subs_recompute_dirty: bool,
@@ -720,6 +753,10 @@ pub struct AppCore {
     group_profiles: HashMap<String, HashMap<String, ProfileCache>>, // chat_id -> (pubkey -> profile)
     profile_db: Option<rusqlite::Connection>,
     chat_media_db: Option<rusqlite::Connection>,
+    // Per-account call quality samples behind post-call reports.
+    call_quality_db: Option<rusqlite::Connection>,
+    // Per-account encrypted full-text index; open only while logged in.
+    search_db: Option<rusqlite::Connection>,
 
     // Shared HTTP client (profile pic downloads, push notifications).
     http_client: reqwest::Client,
@@ -727,6 +764,20 @@ pub struct AppCore {
 
     // Archived chat IDs -- hidden from the chat list but data stays in MDK.
     archived_chats: HashSet<String>,
+    // Blocked pubkeys (hex), mirrored from profile_db.
+    blocked_pubkeys: HashSet<String>,
+    // A block list change has not reached the relay mute list yet.
+    block_list_dirty: bool,
+    block_list_sync_token: u64,
+    // Latest disappearing-message setting seen per chat (chat_id -> timer).
+    disappearing_timers: HashMap<String, disappearing::DisappearingTimer>,
+    disappearing_purge_timer: TimerToken,
+    // Cutoff of the last clean purge per chat; older messages are already gone.
+    disappearing_purged_through: HashMap<String, i64>,
+    chat_notification_modes: HashMap<String, ChatNotificationMode>,
+    chat_snooze_timer: TimerToken,
+    // Newest read receipt per member (chat_id -> reader pubkey hex -> marker).
+    read_markers: HashMap<String, HashMap<String, read_receipts::ReadMarker>>,
 
     // Push notification state.
     push_device_id: String,
@@ -750,17 +801,33 @@ pub struct AppCore {
     /// (commit + merge + welcome delivery in flight). A second mutation on the
     /// same group is rejected with a toast while the lock is held.
     pending_group_ops: HashSet<String>,
+    /// Invite link behind each in-flight join, keyed by (chat_id, requester
+    /// hex). The link is only used up once the add commit is published.
+    pending_invite_link_joins: HashMap<(String, String), String>,
+    /// Link joins waiting for the group's in-flight add or update to finish,
+    /// with their link token. Each group drains them in one batched add.
+    queued_invite_link_joins: HashMap<String, Vec<(PublicKey, String)>>,
 
     app_version: String,
     last_min_version_check: Option<std::time::Instant>,
 
     call_runtime: call_runtime::CallRuntime,
+    // Platform video/audio decoding (posters, transcoding, waveforms), when provided.
+    media_processor: chat_media::SharedMediaProcessor,
     call_session_params: Option<call_control::CallSessionParams>,
+    call_transcript: call_control::CallTranscript,
+    call_quality: Option<call_quality::CallQualityRecorder>,
     call_timeline_logged_keys: HashSet<String>,
     toast_dismiss_timer: TimerToken,
     call_duration_timer: TimerToken,
     call_offer_timeout_timer: TimerToken,
     voice_recording_timer: TimerToken,
+    // Every level of the current recording; `VoiceRecordingState` only keeps the tail.
+    voice_note_levels: Vec<f32>,
+    // Waveform and duration of the last stopped recording, taken by the next voice-note send.
+    voice_note_preview: Option<pika_marmot_runtime::media::MediaPreview>,
+    // Voice notes we already asked the platform to decode a waveform for.
+    voice_waveform_requests: HashSet<String>,
     pending_nostr_connect_login: Option<PendingNostrConnectLogin>,
     next_nostr_connect_attempt_id: u64,
     agent_allowlist_state: AgentAllowlistState,
@@ -806,20 +873,23 @@ impl AppCore {
 
         profile_pics::ensure_dir(&data_dir);
 
-        let profile_db = match profile_db::open_profile_db(&data_dir) {
+        let accounts = accounts::load_registry(&data_dir);
+        let account_dir = accounts::active_account_dir(&data_dir, &accounts);
+        let profile_db = match profile_db::open_profile_db(&account_dir) {
             Ok(conn) => Some(conn),
             Err(e) => {
                 tracing::warn!(%e, "failed to open profile cache db");
                 None
             }
         };
-        let chat_media_db = match chat_media_db::open_chat_media_db(&data_dir) {
+        let chat_media_db = match chat_media_db::open_chat_media_db(&account_dir) {
             Ok(conn) => Some(conn),
             Err(e) => {
                 tracing::warn!(%e, "failed to open chat media db");
                 None
             }
         };
+        let call_quality_db = call_quality::open_call_quality_db(&account_dir);
         let profiles = profile_db
             .as_ref()
             .map(profile_db::load_profiles)
@@ -831,6 +901,14 @@ impl AppCore {
             .as_ref()
             .map(profile_db::load_developer_mode)
             .unwrap_or(false);
+        let read_receipts_enabled = profile_db
+            .as_ref()
+            .map(profile_db::load_read_receipts)
+            .unwrap_or(false);
+        let auto_accept_followed_invites = profile_db
+            .as_ref()
+            .map(profile_db::load_auto_accept_followed_invites)
+            .unwrap_or(true);
 
         let push_device_id = Self::load_or_create_push_device_id(&data_dir);
         let push_subscribed_chat_ids = Self::load_push_subscriptions(&data_dir);
@@ -846,12 +924,16 @@ impl AppCore {
             external_signer_bridge,
             bunker_signer_connector,
             data_dir,
+            account_dir,
             keychain_group,
             app_version,
             last_min_version_check: None,
             config,
             runtime,
             session: None,
+            accounts,
+            account_signers: HashMap::new(),
+            background_accounts: HashMap::new(),
             subs_recompute_in_flight: false,
             subs_recompute_dirty: false,
             subs_force_reconnect: false,
@@ -870,9 +952,20 @@ impl AppCore {
             typing_state: HashMap::new(),
             last_typing_sent: HashMap::new(),
             chat_media_db,
+            call_quality_db,
+            search_db: None,
             http_client: reqwest::Client::new(),
             pfp_semaphore: profile_pics::new_download_semaphore(),
             archived_chats: HashSet::new(),
+            blocked_pubkeys: HashSet::new(),
+            block_list_dirty: false,
+            block_list_sync_token: 0,
+            disappearing_timers: HashMap::new(),
+            disappearing_purge_timer: TimerToken::new(),
+            disappearing_purged_through: HashMap::new(),
+            chat_notification_modes: HashMap::new(),
+            chat_snooze_timer: TimerToken::new(),
+            read_markers: HashMap::new(),
             push_device_id,
             push_apns_token: None,
             push_subscribed_chat_ids,
@@ -882,13 +975,21 @@ impl AppCore {
             media_cache: HashMap::new(),
             local_path_cache: HashMap::new(),
             pending_group_ops: HashSet::new(),
+            pending_invite_link_joins: HashMap::new(),
+            queued_invite_link_joins: HashMap::new(),
             call_runtime: call_runtime::CallRuntime::default(),
+            media_processor: Arc::new(RwLock::new(None)),
             call_session_params: None,
+            call_transcript: call_control::CallTranscript::default(),
+            call_quality: None,
             call_timeline_logged_keys: HashSet::new(),
             toast_dismiss_timer: TimerToken::new(),
             call_duration_timer: TimerToken::new(),
             call_offer_timeout_timer: TimerToken::new(),
             voice_recording_timer: TimerToken::new(),
+            voice_note_levels: Vec::new(),
+            voice_note_preview: None,
+            voice_waveform_requests: HashSet::new(),
             pending_nostr_connect_login: None,
             next_nostr_connect_attempt_id: 1,
             agent_allowlist_state: AgentAllowlistState::Unknown,
@@ -902,6 +1003,10 @@ impl AppCore {
             agent_flow_start: None,
         };
         this.state.developer_mode = developer_mode;
+        this.state.read_receipts_enabled = read_receipts_enabled;
+        this.state.auto_accept_followed_invites = auto_accept_followed_invites;
+        this.load_block_list();
+        this.refresh_account_summaries();
 
         if run_moq_probe {
             if let Some(moq_url) = moq_probe_url {
@@ -936,8 +1041,17 @@ impl AppCore {
         self.call_runtime.set_video_frame_receiver(receiver);
     }
 
+    pub fn set_media_processor(
+        &mut self,
+        processor: std::sync::Arc<
+            std::sync::RwLock<Option<std::sync::Arc<dyn crate::MediaProcessor>>>,
+        >,
+    ) {
+        self.media_processor = processor;
+    }
+
     fn archived_chats_path(&self) -> std::path::PathBuf {
-        std::path::Path::new(&self.data_dir).join("archived_chats.json")
+        std::path::Path::new(&self.account_dir).join("archived_chats.json")
     }
 
     fn load_archived_chats(&mut self) {
@@ -957,7 +1071,7 @@ impl AppCore {
     }
 
     fn call_timeline_path(&self) -> std::path::PathBuf {
-        std::path::Path::new(&self.data_dir).join("call_timeline.json")
+        std::path::Path::new(&self.account_dir).join("call_timeline.json")
     }
 
     fn load_call_timeline(&mut self) {
@@ -1031,6 +1145,58 @@ impl AppCore {
         }
     }
 
+    /// Resolves a message the local user may edit or delete: it has to be loaded
+    /// in the open chat, authored by us, and not already deleted.
+    fn own_message_for_revision(
+        &mut self,
+        chat_id: &str,
+        message_id: &str,
+    ) -> Option<nostr_sdk::prelude::EventId> {
+        let target = self
+            .state
+            .current_chat
+            .as_ref()
+            .filter(|chat| chat.chat_id == chat_id)
+            .and_then(|chat| chat.messages.iter().find(|m| m.id == message_id))
+            .map(|m| (m.is_mine, m.deleted));
+        match target {
+            None => {
+                self.toast("Message not found");
+                None
+            }
+            Some((false, _)) => {
+                self.toast("You can only change your own messages");
+                None
+            }
+            Some((true, true)) => None,
+            Some((true, false)) => nostr_sdk::prelude::EventId::parse(message_id).ok(),
+        }
+    }
+
+    fn publish_message_revision(&mut self, chat_id: &str, action: OutboundConversationAction) {
+        let prepared = match self.prepare_outbound_action_for_chat(chat_id, action) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::warn!(err = %e, "message revision create_message failed");
+                self.toast(format!("Update failed: {e}"));
+                return;
+            }
+        };
+
+        // Fire-and-forget publish, same as reactions; storage already has it.
+        let client = match self.session.as_ref() {
+            Some(sess) => sess.client.clone(),
+            None => return,
+        };
+        self.runtime.spawn(async move {
+            let _ = client.send_event(&prepared.wrapper).await;
+        });
+
+        self.index_stored_message_for_search(chat_id, &prepared.rumor_id);
+        self.refresh_current_chat(chat_id);
+        self.refresh_chat_list_from_storage();
+    }
+
     fn prepare_outbound_action_for_chat(
         &self,
         chat_id: &str,
@@ -1302,6 +1468,11 @@ impl AppCore {
         );
     }
 
+    fn discard_voice_note(&mut self) {
+        self.voice_note_levels.clear();
+        self.voice_note_preview = None;
+    }
+
     fn start_voice_recording_ticks(&mut self) {
         self.schedule_voice_recording_tick();
     }
@@ -3037,6 +3208,7 @@ impl AppCore {
             self.state.router.screen_stack.clear();
             self.state.active_call = None;
             self.state.voice_recording = None;
+            self.discard_voice_note();
             self.sync_agent_menu_item_state();
             self.call_session_params = None;
             self.emit_router();
@@ -3055,9 +3227,13 @@ impl AppCore {
             self.state.current_chat = None;
             self.state.active_call = None;
             self.state.voice_recording = None;
+            self.discard_voice_note();
             self.state.media_gallery = None;
+            self.state.message_search = None;
             self.state.call_timeline = vec![];
+            self.clear_call_quality_history();
             self.state.chat_list = vec![];
+            self.state.invitations = vec![];
             self.state.busy = BusyState::idle();
             self.loaded_count.clear();
             self.unread_counts.clear();
@@ -3068,13 +3244,17 @@ impl AppCore {
             self.pending_media_batch_sends.clear();
             self.media_cache.clear();
             self.local_path_cache.clear();
+            self.voice_waveform_requests.clear();
             self.pending_media_downloads.clear();
             self.local_outbox.clear();
             self.profiles.clear();
             if let Some(conn) = self.profile_db.as_ref() {
                 profile_db::clear_all(conn);
             }
-            profile_pics::clear_cache(&self.data_dir);
+            // Profile pictures are shared by every account on the device.
+            if !self.has_accounts() {
+                profile_pics::clear_cache(&self.data_dir);
+            }
             self.state.my_profile = MyProfileState::empty();
             self.state.follow_list = vec![];
             self.state.peer_profile = None;
@@ -3092,14 +3272,32 @@ impl AppCore {
     }
 
     fn wipe_local_data(&mut self) {
-        // Drop SQLite handles before deleting files.
+        // Drop SQLite handles before deleting files. The search index lives
+        // under the account's mls dir and is rebuilt from storage on next login.
         self.profile_db = None;
+        self.search_db = None;
+        self.call_quality_db = None;
+        self.call_quality = None;
         self.archived_chats.clear();
+        self.disappearing_timers.clear();
+        self.disappearing_purge_timer.cancel();
+        self.disappearing_purged_through.clear();
+        self.chat_notification_modes.clear();
+        self.chat_snooze_timer.cancel();
+        self.read_markers.clear();
         self.push_subscribed_chat_ids.clear();
         self.push_apns_token = None;
+        self.reset_accounts();
         self.state.toast = None;
         self.state.developer_mode = false;
+        self.state.read_receipts_enabled = false;
+        self.state.auto_accept_followed_invites = true;
+        self.blocked_pubkeys.clear();
+        self.block_list_dirty = false;
+        self.state.blocked_users = vec![];
+        self.state.block_list_sync_enabled = false;
         self.state.voice_recording = None;
+        self.discard_voice_note();
         self.cancel_call_duration_ticks();
         self.cancel_call_offer_timeout();
         self.cancel_voice_recording_ticks();
@@ -3147,13 +3345,7 @@ impl AppCore {
         self.config = config::load_app_config(&self.data_dir);
 
         profile_pics::ensure_dir(&self.data_dir);
-        self.profile_db = match profile_db::open_profile_db(&self.data_dir) {
-            Ok(conn) => Some(conn),
-            Err(e) => {
-                tracing::warn!(%e, "wipe: failed to reopen profile cache db");
-                None
-            }
-        };
+        self.open_account_stores();
         self.push_device_id = Self::load_or_create_push_device_id(&self.data_dir);
     }
 
@@ -3237,29 +3429,61 @@ impl AppCore {
                 rx_frames,
                 rx_dropped,
                 jitter_buffer_ms,
+                jitter_target_ms,
+                rx_jitter_ms,
+                rx_reordered,
+                rx_late,
+                rx_concealed,
+                tx_bytes,
+                rx_bytes,
                 last_rtt_ms,
                 video_tx,
                 video_rx,
                 video_rx_decrypt_fail,
+                video_profile,
             } => self.handle_call_runtime_stats(
                 call_id,
                 tx_frames,
                 rx_frames,
                 rx_dropped,
                 jitter_buffer_ms,
+                jitter_target_ms,
+                rx_jitter_ms,
+                rx_reordered,
+                rx_late,
+                rx_concealed,
+                tx_bytes,
+                rx_bytes,
                 last_rtt_ms,
                 video_tx,
                 video_rx,
                 video_rx_decrypt_fail,
+                video_profile,
             ),
+            InternalEvent::CallParticipantsSpeaking {
+                call_id,
+                participant_ids,
+            } => self.handle_call_participants_speaking(call_id, participant_ids),
+            InternalEvent::CallCaption {
+                call_id,
+                participant_id,
+                text,
+            } => self.handle_call_caption(call_id, participant_id, text),
             InternalEvent::CallDurationTick { token } => self.handle_call_duration_tick(token),
             InternalEvent::CallOfferTimeout { token } => self.dispatch_call_offer_timeout(token),
             InternalEvent::VoiceRecordingDurationTick { token } => {
                 self.handle_voice_recording_duration_tick(token)
             }
+            InternalEvent::DisappearingPurgeTick { token } => {
+                self.handle_disappearing_purge_tick(token)
+            }
+            InternalEvent::ChatSnoozeExpired { token } => self.handle_chat_snooze_expired(token),
             InternalEvent::VideoFrameFromPlatform { payload } => {
                 self.handle_video_frame_from_platform(payload)
             }
+            InternalEvent::ScreenFrameFromPlatform { payload } => {
+                self.handle_screen_frame_from_platform(payload)
+            }
             InternalEvent::KeyPackagePublished { token, ok, error } => {
                 self.handle_key_package_published(token, ok, error)
             }
@@ -3308,6 +3532,24 @@ impl AppCore {
                 encrypted_data,
                 error,
             } => self.handle_chat_media_download_fetched(request_id, encrypted_data, error),
+            InternalEvent::ChatMediaTransferProgress {
+                request_id,
+                transferred_bytes,
+                total_bytes,
+            } => {
+                self.handle_chat_media_transfer_progress(request_id, transferred_bytes, total_bytes)
+            }
+            InternalEvent::ChatMediaWaveformDecoded {
+                chat_id,
+                original_hash_hex,
+                levels,
+                duration_ms,
+            } => self.handle_chat_media_waveform_decoded(
+                chat_id,
+                original_hash_hex,
+                levels,
+                duration_ms,
+            ),
             InternalEvent::ChatMediaLocalPathsResolved { chat_id, resolved } => {
                 self.handle_media_local_paths_resolved(chat_id, resolved);
             }
@@ -3324,6 +3566,9 @@ impl AppCore {
                 width,
                 height,
                 blurhash,
+                thumbnail_jpeg,
+                duration_ms,
+                waveform,
                 error,
             } => self.handle_chat_media_preprocessed(
                 chat_id,
@@ -3338,6 +3583,9 @@ impl AppCore {
                 width,
                 height,
                 blurhash,
+                thumbnail_jpeg,
+                duration_ms,
+                waveform,
                 error,
             ),
             InternalEvent::PeerKeyPackageFetched {
@@ -3438,10 +3686,24 @@ impl AppCore {
             InternalEvent::ContactListModifyFailed { pubkey, revert_to } => {
                 self.handle_contact_list_modify_failed(pubkey, revert_to)
             }
+            InternalEvent::BlockListSynced {
+                token,
+                pubkeys,
+                confirmed,
+            } => self.handle_block_list_synced(token, pubkeys, confirmed),
+            InternalEvent::BlockListSyncFailed { token, error } => {
+                self.handle_block_list_sync_failed(token, error)
+            }
             InternalEvent::GroupMessageReceived { event } => {
                 tracing::debug!(event_id = %event.id.to_hex(), "group_message_received");
                 self.handle_group_message(event);
             }
+            InternalEvent::BackgroundGroupMessageReceived {
+                account_pubkey,
+                event,
+            } => {
+                self.handle_background_group_message(account_pubkey, event);
+            }
             InternalEvent::MinVersionChecked { update_required } => {
                 if update_required != self.state.update_required {
                     self.state.update_required = update_required;
@@ -3454,14 +3716,17 @@ impl AppCore {
                     return;
                 }
                 self.cache_missing_profile_pics();
+                self.backfill_search_index();
                 self.refresh_my_profile(false);
                 self.hydrate_follow_list_from_cache();
                 self.refresh_follow_list();
+                self.fetch_block_list();
                 if self.network_enabled() {
                     self.publish_key_package_relays_best_effort();
                     self.ensure_key_package_published_best_effort();
                     self.recompute_subscriptions();
                     self.check_min_version();
+                    self.resume_media_transfers();
                 }
                 self.register_push_device();
             }
@@ -3473,6 +3738,7 @@ impl AppCore {
                 self.refresh_all_from_storage();
                 self.refresh_my_profile(false);
                 self.refresh_follow_list();
+                self.fetch_block_list();
             }
         }
     }
@@ -3594,10 +3860,18 @@ impl AppCore {
         rx_frames: u64,
         rx_dropped: u64,
         jitter_buffer_ms: u32,
+        jitter_target_ms: u32,
+        rx_jitter_ms: u32,
+        rx_reordered: u64,
+        rx_late: u64,
+        rx_concealed: u64,
+        tx_bytes: u64,
+        rx_bytes: u64,
         last_rtt_ms: Option<u32>,
         video_tx: u64,
         video_rx: u64,
         video_rx_decrypt_fail: u64,
+        video_profile: Option<CallVideoProfile>,
     ) {
         if let Some(call) = self.state.active_call.as_ref() {
             if call.call_id == call_id {
@@ -3617,15 +3891,33 @@ impl AppCore {
                         rx_frames,
                         rx_dropped,
                         jitter_buffer_ms,
+                        jitter_target_ms,
+                        rx_jitter_ms,
+                        rx_reordered,
+                        rx_late,
+                        rx_concealed,
                         last_rtt_ms,
                         video_tx,
                         video_rx,
                         video_rx_decrypt_fail,
+                        video_profile,
                     });
                 }
                 if should_tick {
                     self.ensure_call_duration_ticks();
                 }
+                self.record_call_quality(
+                    &call_id,
+                    QualityCounters {
+                        rx_frames,
+                        rx_concealed,
+                        tx_bytes,
+                        rx_bytes,
+                        video_rx_frames: video_rx,
+                    },
+                    last_rtt_ms,
+                    rx_jitter_ms,
+                );
                 self.emit_call_state_with_previous(previous);
             }
         }
@@ -3682,6 +3974,14 @@ impl AppCore {
         }
     }
 
+    fn handle_screen_frame_from_platform(&mut self, payload: Vec<u8>) {
+        if let Some(call) = self.state.active_call.as_ref() {
+            if call.is_screen_sharing {
+                self.call_runtime.send_screen_frame(&call.call_id, payload);
+            }
+        }
+    }
+
     fn handle_agent_allowlist_resolved(
         &mut self,
         token: u64,
@@ -3888,11 +4188,21 @@ impl AppCore {
             rumor_kind = rumor.kind.as_u16(),
             "giftwrap_received"
         );
-        let Some(sess) = self.session.as_mut() else {
+        if self.session.is_none() {
             tracing::warn!("giftwrap_received but no session");
             return;
-        };
-
+        }
+        if self.blocked_pubkeys.contains(&rumor.pubkey.to_hex()) {
+            tracing::info!(
+                wrapper_id = %wrapper.id.to_hex(),
+                "giftwrap dropped (sender blocked)"
+            );
+            return;
+        }
+        if rumor.kind == JOIN_REQUEST_KIND {
+            self.handle_join_request(rumor);
+            return;
+        }
         if rumor.kind != Kind::MlsWelcome {
             tracing::debug!(
                 kind = rumor.kind.as_u16(),
@@ -3900,6 +4210,9 @@ impl AppCore {
             );
             return;
         }
+        let Some(sess) = self.session.as_mut() else {
+            return;
+        };
 
         let welcome = match sess.mdk.process_welcome(&wrapper.id, &rumor) {
             Ok(w) => w,
@@ -3931,13 +4244,14 @@ impl AppCore {
             return;
         }
 
-        // The app is currently eager here: it accepts MLS welcomes as soon as
-        // they arrive, unlike the daemon/CLI stage-first flows.
-        tracing::info!(
-            nostr_group_id = %nostr_group_hex,
-            group_name = %welcome.group_name,
-            "welcome_accepted"
-        );
+        // Re-delivered welcomes we already declined come back non-pending.
+        if welcome.state != mdk_storage_traits::welcomes::types::WelcomeState::Pending {
+            tracing::debug!(
+                nostr_group_id = %nostr_group_hex,
+                "welcome skipped (already handled)"
+            );
+            return;
+        }
 
         let staged_welcome = match sess.mdk.get_pending_welcomes(None) {
             Ok(pending) => find_pending_welcome(&pending, &wrapper.id)
@@ -3952,35 +4266,22 @@ impl AppCore {
                 welcome
             }
         };
-        let client = sess.client.clone();
-        let mut seen = HashSet::new();
-        // Keep app policy unchanged here: eager accept, no subscription work,
-        // and no backlog catch-up in this path.
-        let accept_result = self.runtime.block_on(async {
-            accept_welcome_and_catch_up(
-                &sess.mdk,
-                &client,
-                &[],
-                &staged_welcome,
-                &mut seen,
-                0,
-                |_| async { Ok(()) },
-            )
-            .await
-        });
-
-        if let Err(e) = accept_result {
-            tracing::error!(%e, "accept_welcome failed");
-            self.toast(format!("Welcome accept failed: {e}"));
-            return;
-        }
 
-        // Rotate the referenced key package: delete best-effort, publish fresh.
-        if self.network_enabled() {
-            if let Some(kp_event_id) = referenced_key_package_event_id(&rumor) {
-                self.delete_event_best_effort(kp_event_id);
+        // Stage first, like the daemon: only followed inviters skip the inbox.
+        // Welcomes answering one of our join requests skip the inbox too.
+        let welcomer_hex = staged_welcome.welcomer.to_hex();
+        if self.auto_accepts_invite_from(&welcomer_hex)
+            || self.take_sent_join_request(&welcomer_hex)
+        {
+            if self.join_welcome(&staged_welcome) {
+                self.rotate_key_package_after_welcome(&rumor);
             }
-            self.ensure_key_package_published_best_effort();
+        } else {
+            tracing::info!(
+                nostr_group_id = %nostr_group_hex,
+                group_name = %staged_welcome.group_name,
+                "welcome_staged"
+            );
         }
 
         self.refresh_all_from_storage();
@@ -4015,6 +4316,76 @@ impl AppCore {
         }
     }
 
+    /// Fetch the peers' key packages, then commit their addition to `chat_id`.
+    /// Callers have already checked that we're an admin.
+    fn add_group_members(&mut self, chat_id: String, peer_pubkeys: Vec<PublicKey>) {
+        self.set_busy(|b| b.creating_chat = true);
+
+        let (client, tx) = {
+            let Some(sess) = self.session.as_ref() else {
+                self.set_busy(|b| b.creating_chat = false);
+                return;
+            };
+            (sess.client.clone(), self.core_sender.clone())
+        };
+        let fallback_kp_relays = self.key_package_relays();
+        let fallback_popular_relays = self.default_relays();
+        let peer_names: HashMap<PublicKey, String> = peer_pubkeys
+            .iter()
+            .map(|pk| (*pk, self.peer_display_name(pk)))
+            .collect();
+
+        // Fetch key packages then add members.
+        self.runtime.spawn(async move {
+            // Ensure relays are connected before fetches.
+            for r in fallback_kp_relays
+                .iter()
+                .chain(fallback_popular_relays.iter())
+            {
+                let _ = client.add_relay(r.clone()).await;
+            }
+            client.connect().await;
+            client.wait_for_connection(Duration::from_secs(5)).await;
+
+            let fetched = fetch_key_packages_for_peers(
+                &client,
+                &peer_pubkeys,
+                &fallback_kp_relays,
+                &fallback_popular_relays,
+            )
+            .await;
+
+            if !fetched.failed_peers.is_empty() {
+                let names: Vec<String> = fetched
+                    .failed_peers
+                    .iter()
+                    .map(|(pk, e)| {
+                        let hex = pk.to_hex();
+                        let name = peer_names.get(pk).map(|s| s.as_str()).unwrap_or(&hex[..8]);
+                        format!("{name}: {e}")
+                    })
+                    .collect();
+                let _ = tx.send(CoreMsg::Internal(Box::new(InternalEvent::Toast(format!(
+                    "Failed to fetch key packages for: {}",
+                    names.join(", ")
+                )))));
+            }
+
+            // Sent even when nothing was found, so link joins waiting on this
+            // add are released.
+            let _ = tx.send(CoreMsg::Internal(Box::new(
+                InternalEvent::GroupKeyPackagesFetched {
+                    peer_pubkeys,
+                    group_name: String::new(),
+                    existing_chat_id: Some(chat_id),
+                    key_package_events: fetched.key_package_events,
+                    failed_peers: fetched.failed_peers,
+                    candidate_kp_relays: fetched.candidate_kp_relays,
+                },
+            )));
+        });
+    }
+
     fn handle_group_key_packages_fetched(
         &mut self,
         peer_pubkeys: Vec<PublicKey>,
@@ -4027,6 +4398,9 @@ impl AppCore {
         let network_enabled = self.network_enabled();
 
         if key_package_events.is_empty() {
+            if let Some(chat_id) = existing_chat_id.as_deref() {
+                self.drop_invite_link_joins(chat_id, &peer_pubkeys);
+            }
             self.set_busy(|b| b.creating_chat = false);
             let names: Vec<String> = failed_peers
                 .iter()
@@ -4052,11 +4426,14 @@ impl AppCore {
         }
 
         if let Some(chat_id) = existing_chat_id {
+            let failed: Vec<PublicKey> = failed_peers.iter().map(|(pk, _)| *pk).collect();
+            self.drop_invite_link_joins(&chat_id, &failed);
             let Some(sess) = self.session.as_ref() else {
                 self.set_busy(|b| b.creating_chat = false);
                 return;
             };
             if !sess.groups.contains_key(&chat_id) {
+                self.drop_invite_link_joins(&chat_id, &peer_pubkeys);
                 self.set_busy(|b| b.creating_chat = false);
                 self.toast("Chat not found");
                 return;
@@ -4070,6 +4447,7 @@ impl AppCore {
             let prepared = match self.prepare_membership_evolution_for_chat(&chat_id, &kp_events) {
                 Ok(prepared) => prepared,
                 Err(e) => {
+                    self.drop_invite_link_joins(&chat_id, &peer_pubkeys);
                     self.set_busy(|b| b.creating_chat = false);
                     if e.to_string().contains("parse key package") {
                         self.toast(format!("Invalid key package: {}", e.root_cause()));
@@ -4184,6 +4562,7 @@ impl AppCore {
         self.pending_group_ops.remove(&chat_id);
 
         if !ok {
+            self.drop_invite_link_joins(&chat_id, &prepared.added_pubkeys);
             self.toast(format!(
                 "Group update failed: {}",
                 error.unwrap_or_else(|| "unknown".into())
@@ -4200,7 +4579,6 @@ impl AppCore {
         }
 
         let has_added = !finalized.added_pubkeys.is_empty();
-
         if let Some(plan) = finalized.welcome_delivery.clone() {
             if self.network_enabled() {
                 let fallback_relays = self.default_relays();
@@ -4215,9 +4593,19 @@ impl AppCore {
             }
         }
 
+        if finalized.merge_error.is_none() {
+            self.use_invite_links_for_joins(&chat_id, &finalized.added_pubkeys);
+        } else {
+            self.drop_invite_link_joins(&chat_id, &finalized.added_pubkeys);
+        }
+
         // Rebroadcast per-group profiles to newly added members.
         if has_added {
             self.rebroadcast_group_profiles(&chat_id, &finalized.mls_group_id);
+            self.rebroadcast_disappearing_timer(&chat_id);
+        }
+        if finalized.merge_error.is_none() {
+            self.rotate_call_media_keys(&chat_id);
         }
 
         self.refresh_all_from_storage();
@@ -4360,6 +4748,7 @@ impl AppCore {
             let next = self.my_profile_state();
             if next != self.state.my_profile {
                 self.state.my_profile = next;
+                self.refresh_account_summaries();
                 changed = true;
             }
         }
@@ -4460,6 +4849,12 @@ impl AppCore {
             Some(ConversationEvent::GroupUpdate(update)) => {
                 self.handle_runtime_group_update(update);
             }
+            Some(ConversationEvent::RejectedCommit {
+                nostr_group_id_hex,
+                reason,
+            }) => {
+                self.handle_rejected_commit(&nostr_group_id_hex, &reason);
+            }
             Some(
                 ConversationEvent::UnresolvedGroup { .. } | ConversationEvent::PreviouslyFailed,
             ) => {
@@ -4475,6 +4870,7 @@ impl AppCore {
                 &update.nostr_group_id_hex,
                 &update.mls_group_id,
             );
+            self.rotate_call_media_keys(&update.nostr_group_id_hex);
         }
         self.refresh_chat_list_from_storage();
         self.refresh_current_chat_if_open(&update.nostr_group_id_hex);
@@ -4488,13 +4884,21 @@ impl AppCore {
             AppMessageKind::TypingIndicator => {
                 let sender_hex = msg.pubkey.to_hex();
                 let my_hex = self.session.as_ref().map(|s| s.pubkey.to_hex());
-                if my_hex.as_deref() != Some(sender_hex.as_str()) {
+                if my_hex.as_deref() != Some(sender_hex.as_str()) && !self.is_blocked(&sender_hex) {
                     self.update_typing(&chat_id, &sender_hex, msg.created_at.as_secs() as i64 + 10);
                     self.refresh_typing_if_open(&chat_id);
                 }
             }
+            AppMessageKind::ReadReceipt => {
+                if self.apply_read_receipt(&chat_id, &msg) {
+                    self.refresh_current_chat_if_open(&chat_id);
+                }
+            }
             AppMessageKind::CallSignal => {
-                if let Some(signal) = self.maybe_parse_call_signal(&msg.pubkey, &msg.content) {
+                if self.is_blocked(&msg.pubkey.to_hex()) {
+                    tracing::debug!(%chat_id, "call signal ignored (sender blocked)");
+                } else if let Some(signal) = self.maybe_parse_call_signal(&msg.pubkey, &msg.content)
+                {
                     self.handle_incoming_call_signal(&chat_id, &msg.pubkey, signal);
                 }
                 self.refresh_chat_list_from_storage();
@@ -4503,10 +4907,18 @@ impl AppCore {
             kind @ (AppMessageKind::Chat
             | AppMessageKind::Reaction
             | AppMessageKind::Hypernote
-            | AppMessageKind::HypernoteResponse) => {
+            | AppMessageKind::HypernoteResponse
+            | AppMessageKind::Edit
+            | AppMessageKind::Deletion) => {
+                self.note_disappearing_message(&chat_id, msg.created_at.as_secs() as i64);
+                // Stored like any other message, but never shown, counted or indexed.
+                if self.is_blocked(&msg.pubkey.to_hex()) {
+                    return;
+                }
                 if matches!(kind, AppMessageKind::Chat) {
                     self.update_typing(&chat_id, &msg.pubkey.to_hex(), 0);
                 }
+                self.index_message_for_search(&chat_id, kind, &msg);
 
                 let current = self.state.current_chat.as_ref().map(|c| c.chat_id.as_str());
                 if current != Some(chat_id.as_str()) && kind.increments_unread() {
@@ -4520,6 +4932,9 @@ impl AppCore {
 
                 self.refresh_chat_list_from_storage();
                 self.refresh_current_chat_if_open(&chat_id);
+                if kind.increments_unread() {
+                    self.send_read_receipt_if_needed(&chat_id);
+                }
             }
             AppMessageKind::GroupProfile => {
                 // Determine profile owner: if the rumor has a `p` tag, this is
@@ -4566,6 +4981,12 @@ impl AppCore {
                 self.refresh_chat_list_from_storage();
                 self.refresh_current_chat_if_open(&chat_id);
             }
+            AppMessageKind::DisappearingTimer => {
+                if self.apply_disappearing_timer(&chat_id, &msg) {
+                    self.refresh_chat_list_from_storage();
+                    self.refresh_current_chat_if_open(&chat_id);
+                }
+            }
         }
     }
 
@@ -4605,7 +5026,10 @@ impl AppCore {
                     self.toast(format!("Create account failed: {e:#}"));
                 }
             }
-            AppAction::Login { nsec } | AppAction::RestoreSession { nsec } => {
+            // Starting a session parks the current account, so adding one is a login.
+            AppAction::Login { nsec }
+            | AppAction::RestoreSession { nsec }
+            | AppAction::AddAccount { nsec } => {
                 self.set_busy(|b| {
                     b.logging_in = true;
                     b.creating_account = false;
@@ -4685,23 +5109,13 @@ impl AppCore {
                 self.restore_bunker_session(bunker_uri, client_nsec);
             }
             AppAction::Logout => {
-                self.clear_pending_nostr_connect_login();
-                // Delete the MLS database before tearing down the session so stale
-                // ratchet state doesn't persist across logins.
-                if let Some(sess) = self.session.as_ref() {
-                    let db_path =
-                        crate::mdk_support::mdk_db_path(&self.data_dir, &sess.pubkey.to_hex());
-                    if let Err(e) = std::fs::remove_file(&db_path) {
-                        tracing::warn!(%e, path = %db_path.display(), "failed to delete mdk db on logout");
-                    } else {
-                        tracing::info!(path = %db_path.display(), "deleted mdk db on logout");
-                    }
-                }
-                self.clear_push_subscriptions();
-                self.stop_session();
-                self.state.auth = AuthState::LoggedOut;
-                self.emit_auth();
-                self.handle_auth_transition(false);
+                self.logout_active_account();
+            }
+            AppAction::SwitchAccount { pubkey } => {
+                self.switch_account(pubkey);
+            }
+            AppAction::RemoveAccount { pubkey } => {
+                self.remove_account(pubkey);
             }
             AppAction::WipeLocalData => {
                 self.clear_push_subscriptions();
@@ -4811,6 +5225,64 @@ impl AppCore {
                 // Refresh chat to pick up the reaction from storage.
                 self.refresh_current_chat(&chat_id);
             }
+            AppAction::EditMessage {
+                chat_id,
+                message_id,
+                content,
+            } => {
+                if !self.is_logged_in() {
+                    return;
+                }
+                let content = content.trim().to_string();
+                if content.is_empty() {
+                    self.toast("Message can't be empty");
+                    return;
+                }
+                let Some(target_event_id) = self.own_message_for_revision(&chat_id, &message_id)
+                else {
+                    return;
+                };
+                self.publish_message_revision(
+                    &chat_id,
+                    OutboundConversationAction::Edit {
+                        target_event_id,
+                        content,
+                        created_at: Timestamp::now(),
+                    },
+                );
+            }
+            AppAction::DeleteMessage {
+                chat_id,
+                message_id,
+            } => {
+                if !self.is_logged_in() {
+                    return;
+                }
+                let Some(target_event_id) = self.own_message_for_revision(&chat_id, &message_id)
+                else {
+                    return;
+                };
+                self.publish_message_revision(
+                    &chat_id,
+                    OutboundConversationAction::Delete {
+                        target_event_id,
+                        created_at: Timestamp::now(),
+                    },
+                );
+            }
+            AppAction::SetDisappearingTimer {
+                chat_id,
+                expire_after_secs,
+            } => {
+                self.set_disappearing_timer(chat_id, expire_after_secs);
+            }
+            AppAction::SetChatNotificationMode { chat_id, mode } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.set_chat_notification_mode(&chat_id, mode);
+            }
             AppAction::TypingStarted { chat_id } => {
                 if !self.is_logged_in() {
                     return;
@@ -4864,6 +5336,21 @@ impl AppCore {
                 }
                 self.emit_state();
             }
+            AppAction::SetReadReceiptsEnabled { enabled } => {
+                self.set_read_receipts_enabled(enabled);
+            }
+            AppAction::SetAutoAcceptFollowedInvites { enabled } => {
+                self.set_auto_accept_followed_invites(enabled);
+            }
+            AppAction::SetBlockListSyncEnabled { enabled } => {
+                self.set_block_list_sync_enabled(enabled);
+            }
+            AppAction::ExportBackup { path, passphrase } => {
+                self.export_backup(path, passphrase);
+            }
+            AppAction::ImportBackup { path, passphrase } => {
+                self.import_backup(path, passphrase);
+            }
             AppAction::WipeProfileCache => {
                 if let Some(conn) = self.profile_db.as_ref() {
                     profile_db::clear_all(conn);
@@ -4875,6 +5362,7 @@ impl AppCore {
                 self.toast("Profile cache wiped");
             }
             AppAction::VoiceRecordingStart => {
+                self.discard_voice_note();
                 self.state.voice_recording = Some(VoiceRecordingState {
                     phase: VoiceRecordingPhase::Recording,
                     duration_secs: 0.0,
@@ -4917,6 +5405,11 @@ impl AppCore {
                     return;
                 }
                 recording.phase = VoiceRecordingPhase::Done;
+                let duration_secs = recording.duration_secs;
+                self.voice_note_preview = Some(chat_media::voice_note_preview(
+                    &self.voice_note_levels,
+                    duration_secs,
+                ));
                 self.cancel_voice_recording_ticks();
                 self.emit_state();
             }
@@ -4925,6 +5418,7 @@ impl AppCore {
                     return;
                 }
                 self.state.voice_recording = None;
+                self.discard_voice_note();
                 self.cancel_voice_recording_ticks();
                 self.emit_state();
             }
@@ -4936,6 +5430,7 @@ impl AppCore {
                     return;
                 }
                 recording.levels.push(level);
+                self.voice_note_levels.push(level);
                 if recording.levels.len() > 300 {
                     let drop_count = recording.levels.len() - 300;
                     recording.levels.drain(0..drop_count);
@@ -5016,13 +5511,45 @@ impl AppCore {
                 self.state.media_gallery = None;
                 self.emit_state();
             }
+            AppAction::LoadCallReport { call_id } => {
+                if !self.is_logged_in() {
+                    return;
+                }
+                self.load_call_report(call_id);
+            }
+            AppAction::ClearCallReport => {
+                self.state.call_report = None;
+                self.emit_state();
+            }
+            AppAction::OpenCallRecording {
+                chat_id,
+                original_hash_hex,
+            } => {
+                if !self.is_logged_in() {
+                    return;
+                }
+                self.open_call_recording(&chat_id, &original_hash_hex);
+            }
+            AppAction::SearchMessages { query, chat_id } => {
+                if !self.is_logged_in() {
+                    return;
+                }
+                self.search_messages(query, chat_id);
+            }
+            AppAction::ClearMessageSearch => {
+                self.state.message_search = None;
+                self.emit_state();
+            }
             AppAction::WipeMediaCache => {
+                // Call recordings aren't a cache: their ciphertext lives outside
+                // the media root and their records stay.
                 if let Some(conn) = self.chat_media_db.as_ref() {
-                    let _ = conn.execute("DELETE FROM chat_media", []);
+                    let _ = chat_media_db::clear_cached_chat_media(conn);
                 }
                 let _ = std::fs::remove_dir_all(chat_media::media_root(&self.data_dir));
                 self.media_cache.clear();
                 self.local_path_cache.clear();
+                self.voice_waveform_requests.clear();
                 self.state.media_gallery = None;
                 self.emit_state();
                 self.toast("Media cache wiped");
@@ -5056,6 +5583,7 @@ impl AppCore {
                 };
 
                 let is_followed = self.state.follow_list.iter().any(|f| f.pubkey == pubkey);
+                let is_blocked = self.is_blocked(&pubkey);
                 self.state.peer_profile = Some(crate::state::PeerProfileState {
                     pubkey: pubkey.clone(),
                     npub,
@@ -5063,6 +5591,7 @@ impl AppCore {
                     about,
                     picture_url,
                     is_followed,
+                    is_blocked,
                 });
                 self.emit_state();
                 self.fetch_peer_profile(&pubkey);
@@ -5090,6 +5619,20 @@ impl AppCore {
                 }
                 self.unfollow_user(&pubkey);
             }
+            AppAction::BlockUser { pubkey } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.block_user(&pubkey);
+            }
+            AppAction::UnblockUser { pubkey } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.unblock_user(&pubkey);
+            }
             AppAction::RefreshMyProfile => {
                 if !self.is_logged_in() {
                     self.toast("Please log in first");
@@ -5270,6 +5813,7 @@ impl AppCore {
                 self.unread_counts.insert(chat_id.clone(), 0);
                 self.refresh_chat_list_from_storage();
                 self.emit_router();
+                self.send_read_receipt_if_needed(&chat_id);
             }
             AppAction::SendMessage {
                 chat_id,
@@ -5374,8 +5918,8 @@ impl AppCore {
                         (
                             k.clone(),
                             p.encrypted_data.clone(),
-                            p.upload.mime_type.clone(),
-                            hex::encode(p.upload.encrypted_hash),
+                            p.upload_mime.clone(),
+                            p.encrypted_hash_hex.clone(),
                         )
                     })
                 {
@@ -5611,6 +6155,21 @@ impl AppCore {
             AppAction::ToggleCamera => {
                 self.handle_toggle_camera_action();
             }
+            AppAction::ToggleAudioProcessing => {
+                self.handle_toggle_audio_processing_action();
+            }
+            AppAction::StartScreenShare => {
+                self.handle_set_screen_share_action(true);
+            }
+            AppAction::StopScreenShare => {
+                self.handle_set_screen_share_action(false);
+            }
+            AppAction::ToggleCallRecording => {
+                self.handle_toggle_call_recording_action();
+            }
+            AppAction::SaveCallCaptions { call_id } => {
+                self.handle_save_call_captions_action(&call_id);
+            }
             AppAction::LoadOlderMessages {
                 chat_id,
                 before_message_id,
@@ -5739,93 +6298,26 @@ impl AppCore {
                         }
                     }
                 }
-                self.set_busy(|b| b.creating_chat = true);
-
-                let (client, tx) = {
-                    let Some(sess) = self.session.as_ref() else {
-                        self.set_busy(|b| b.creating_chat = false);
-                        return;
-                    };
-                    (sess.client.clone(), self.core_sender.clone())
-                };
-                let fallback_kp_relays = self.key_package_relays();
-                let fallback_popular_relays = self.default_relays();
-                let chat_id_clone = chat_id.clone();
-                let peer_names: HashMap<PublicKey, String> = peer_pubkeys
-                    .iter()
-                    .map(|pk| (*pk, self.peer_display_name(pk)))
-                    .collect();
-
-                // Fetch key packages then add members.
-                self.runtime.spawn(async move {
-                    // Ensure relays are connected before fetches.
-                    for r in fallback_kp_relays
-                        .iter()
-                        .chain(fallback_popular_relays.iter())
-                    {
-                        let _ = client.add_relay(r.clone()).await;
-                    }
-                    client.connect().await;
-                    client.wait_for_connection(Duration::from_secs(5)).await;
-
-                    let fetched = fetch_key_packages_for_peers(
-                        &client,
-                        &peer_pubkeys,
-                        &fallback_kp_relays,
-                        &fallback_popular_relays,
-                    )
-                    .await;
-
-                    if !fetched.failed_peers.is_empty() {
-                        let names: Vec<String> = fetched
-                            .failed_peers
-                            .iter()
-                            .map(|(pk, e)| {
-                                let hex = pk.to_hex();
-                                let name =
-                                    peer_names.get(pk).map(|s| s.as_str()).unwrap_or(&hex[..8]);
-                                format!("{name}: {e}")
-                            })
-                            .collect();
-                        let _ = tx.send(CoreMsg::Internal(Box::new(InternalEvent::Toast(
-                            format!("Failed to fetch key packages for: {}", names.join(", ")),
-                        ))));
-                    }
-
-                    if fetched.key_package_events.is_empty() {
-                        let _ = tx.send(CoreMsg::Internal(Box::new(InternalEvent::Toast(
-                            "No key packages found for any peer".into(),
-                        ))));
-                        return;
-                    }
-
-                    let _ = tx.send(CoreMsg::Internal(Box::new(
-                        InternalEvent::GroupKeyPackagesFetched {
-                            peer_pubkeys,
-                            group_name: String::new(),
-                            existing_chat_id: Some(chat_id_clone),
-                            key_package_events: fetched.key_package_events,
-                            failed_peers: fetched.failed_peers,
-                            candidate_kp_relays: fetched.candidate_kp_relays,
-                        },
-                    )));
-                });
-            }
-            AppAction::RemoveGroupMembers {
-                chat_id,
-                member_pubkeys,
-            } => {
-                if !self.is_logged_in() {
-                    self.toast("Please log in first");
-                    return;
-                }
-                let Some(sess) = self.session.as_mut() else {
-                    return;
-                };
-                let Some(entry) = sess.groups.get(&chat_id).cloned() else {
-                    self.toast("Chat not found");
-                    return;
-                };
+                if !self.require_group_admin(&chat_id) {
+                    return;
+                }
+                self.add_group_members(chat_id, peer_pubkeys);
+            }
+            AppAction::RemoveGroupMembers {
+                chat_id,
+                member_pubkeys,
+            } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                let Some(sess) = self.session.as_ref() else {
+                    return;
+                };
+                let Some(entry) = sess.groups.get(&chat_id).cloned() else {
+                    self.toast("Chat not found");
+                    return;
+                };
 
                 let mut pubkeys: Vec<PublicKey> = Vec::new();
                 for hex in &member_pubkeys {
@@ -5837,6 +6329,12 @@ impl AppCore {
                         }
                     }
                 }
+                if !self.require_group_admin(&chat_id) {
+                    return;
+                }
+                let Some(sess) = self.session.as_mut() else {
+                    return;
+                };
 
                 let result = match sess.mdk.remove_members(&entry.mls_group_id, &pubkeys) {
                     Ok(r) => r,
@@ -5861,6 +6359,20 @@ impl AppCore {
 
                 self.publish_prepared_evolution(&chat_id, prepared);
             }
+            AppAction::AcceptInvite { invite_id } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.accept_invite(&invite_id);
+            }
+            AppAction::DeclineInvite { invite_id } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.decline_invite(&invite_id);
+            }
             AppAction::LeaveGroup { chat_id } => {
                 if !self.is_logged_in() {
                     self.toast("Please log in first");
@@ -5917,11 +6429,13 @@ impl AppCore {
                     self.toast("Please log in first");
                     return;
                 }
+                if !self.require_group_admin(&chat_id) {
+                    return;
+                }
                 let Some(sess) = self.session.as_mut() else {
                     return;
                 };
                 let Some(entry) = sess.groups.get(&chat_id).cloned() else {
-                    self.toast("Chat not found");
                     return;
                 };
 
@@ -5949,6 +6463,59 @@ impl AppCore {
 
                 self.publish_prepared_evolution(&chat_id, prepared);
             }
+            AppAction::PromoteAdmin { chat_id, pubkey } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.change_group_admin(&chat_id, &pubkey, AdminChange::Promote);
+            }
+            AppAction::DemoteAdmin { chat_id, pubkey } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.change_group_admin(&chat_id, &pubkey, AdminChange::Demote);
+            }
+            AppAction::CreateGroupInviteLink {
+                chat_id,
+                multi_use,
+                requires_approval,
+            } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.create_group_invite_link(&chat_id, multi_use, requires_approval);
+            }
+            AppAction::RevokeGroupInviteLink { chat_id, token } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.revoke_group_invite_link(&chat_id, &token);
+            }
+            AppAction::JoinGroupViaInviteLink { link } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.join_group_via_invite_link(&link);
+            }
+            AppAction::ApproveJoinRequest { chat_id, pubkey } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.approve_join_request(&chat_id, &pubkey);
+            }
+            AppAction::DeclineJoinRequest { chat_id, pubkey } => {
+                if !self.is_logged_in() {
+                    self.toast("Please log in first");
+                    return;
+                }
+                self.decline_join_request(&chat_id, &pubkey);
+            }
             AppAction::SaveGroupProfile {
                 chat_id,
                 name,
@@ -5980,7 +6547,10 @@ impl AppCore {
     ///
     fn publish_prepared_evolution(&mut self, chat_id: &str, prepared: PreparedMembershipEvolution) {
         if self.pending_group_ops.contains(chat_id) {
-            self.toast("A group update is already in progress, please wait".to_string());
+            // Link joins go back in line; anything else is the user's to retry.
+            if !self.requeue_invite_link_joins(chat_id, &prepared.added_pubkeys) {
+                self.toast("A group update is already in progress, please wait".to_string());
+            }
             return;
         }
         self.pending_group_ops.insert(chat_id.to_string());
@@ -6160,6 +6730,97 @@ mod tests {
         assert_eq!(second.state.call_timeline.len(), 1);
     }
 
+    #[test]
+    fn call_report_loads_stored_samples_with_a_summary() {
+        use pika_media::quality::QualitySample;
+        use pika_media::quality_store;
+
+        let tempdir = tempfile::tempdir().expect("tempdir");
+        let mut core = make_core(tempdir.path().to_string_lossy().into_owned());
+
+        // No active call: nothing gets recorded.
+        core.record_call_quality("call-1", QualityCounters::default(), None, 5);
+        assert!(core.call_quality.is_none());
+
+        let conn = core.call_quality_db.as_ref().expect("quality db");
+        for (at_ms, loss_pct) in [(10_000, 0.0), (12_000, 5.0)] {
+            let sample = QualitySample {
+                at_ms,
+                rtt_ms: Some(80),
+                jitter_ms: 10,
+                loss_pct,
+                concealed_frames: 4,
+                tx_bitrate_bps: 40_000,
+                rx_bitrate_bps: 38_000,
+                video_fps: 0.0,
+            };
+            quality_store::insert_quality_sample(conn, "call-1", "chat-1", &sample).unwrap();
+        }
+
+        core.load_call_report("call-1".into());
+        let report = core.state.call_report.as_ref().expect("report");
+        assert_eq!(report.samples.len(), 2);
+        assert_eq!(report.samples[1].loss_pct, 5.0);
+        let summary = report.summary.as_ref().expect("summary");
+        assert_eq!(summary.sample_count, 2);
+        assert_eq!(summary.duration_ms, 2_000);
+        assert_eq!(summary.avg_rtt_ms, Some(80));
+        assert_eq!(summary.concealed_frames, 8);
+
+        core.load_call_report("unknown".into());
+        let report = core.state.call_report.as_ref().expect("report");
+        assert!(report.samples.is_empty());
+        assert!(report.summary.is_none());
+    }
+
+    #[test]
+    fn call_captions_show_the_latest_and_save_only_after_the_call() {
+        use crate::state::{CallState, CallStatus};
+        use nostr_sdk::prelude::Keys;
+
+        let tempdir = tempfile::tempdir().expect("tempdir");
+        let mut core = make_core(tempdir.path().to_string_lossy().into_owned());
+        core.state.active_call = Some(CallState::new(
+            "call-1".into(),
+            "chat-1".into(),
+            String::new(),
+            CallStatus::Active,
+            Some(1_700_000_000),
+            false,
+            false,
+            None,
+        ));
+        let speaker = Keys::generate().public_key().to_hex();
+        for i in 0..5 {
+            core.handle_call_caption("call-1".into(), Some(speaker.clone()), format!("line {i}"));
+        }
+        core.handle_call_caption("other-call".into(), Some(speaker.clone()), "stray".into());
+
+        let call = core.state.active_call.as_ref().unwrap();
+        let texts: Vec<&str> = call.captions.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, ["line 2", "line 3", "line 4"]);
+        assert!(!call.captions[0].is_local);
+        assert_eq!(core.call_transcript.captions.len(), 5);
+
+        core.handle_save_call_captions_action("call-1");
+        assert_eq!(
+            core.state.toast.as_deref(),
+            Some("Captions can be saved once the call ends")
+        );
+        assert_eq!(core.call_transcript.captions.len(), 5);
+
+        core.state
+            .active_call
+            .as_mut()
+            .unwrap()
+            .set_status(CallStatus::Ended {
+                reason: "user_hangup".into(),
+            });
+        core.handle_save_call_captions_action("call-1");
+        assert!(core.state.active_call.as_ref().unwrap().captions.is_empty());
+        assert!(core.call_transcript.captions.is_empty());
+    }
+
     #[test]
     fn prune_chat_routes_removes_chat_and_group_info_for_target_chat() {
         let mut stack = vec![
@@ -6516,7 +7177,7 @@ mod tests {
 
         /// Creates a core with a real MDK session and a group in storage.
         /// Returns (core, chat_id_hex, creator_keys, group_id).
-        fn make_core_with_group() -> (AppCore, String, Keys, GroupId) {
+        pub(super) fn make_core_with_group() -> (AppCore, String, Keys, GroupId) {
             let tempdir = tempfile::tempdir().expect("tempdir");
             let data_dir = tempdir.path().to_string_lossy().into_owned();
             // Leak tempdir so it lives for the test duration.
@@ -6689,6 +7350,126 @@ mod tests {
             assert!(!has_self, "own typing indicators should be ignored");
         }
 
+        #[test]
+        fn blocked_members_typing_and_messages_are_ignored() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            let other = Keys::generate();
+            core.block_user(&other.public_key().to_hex());
+            assert_eq!(core.state.blocked_users.len(), 1);
+
+            let tags: Tags = vec![Tag::custom(TagKind::d(), vec!["pika"])]
+                .into_iter()
+                .collect();
+            let typing = make_test_message(
+                &other.public_key(),
+                Kind::Custom(20_067),
+                "typing",
+                &group_id,
+                tags,
+            );
+            core.handle_message_processing_result(MessageProcessingResult::ApplicationMessage(
+                typing,
+            ));
+            assert!(core.typing_state.get(&chat_id).is_none_or(|m| m.is_empty()));
+
+            let chat = make_test_message(
+                &other.public_key(),
+                Kind::ChatMessage,
+                "buy now",
+                &group_id,
+                Tags::new(),
+            );
+            core.handle_message_processing_result(MessageProcessingResult::ApplicationMessage(
+                chat,
+            ));
+            assert_eq!(core.unread_counts.get(&chat_id), None);
+
+            core.unblock_user(&other.public_key().to_hex());
+            assert!(core.state.blocked_users.is_empty());
+        }
+
+        #[test]
+        fn missing_block_list_sync_keeps_local_blocks() {
+            let (mut core, _chat_id, _keys, _group_id) = make_core_with_group();
+            let other = Keys::generate().public_key().to_hex();
+            core.block_user(&other);
+            core.state.block_list_sync_enabled = true;
+
+            let token = core.block_list_sync_token;
+            core.handle_block_list_synced(token, vec![], false);
+
+            assert!(core.is_blocked(&other));
+            assert_eq!(core.state.blocked_users.len(), 1);
+            if let Some(conn) = core.profile_db.as_ref() {
+                assert_eq!(super::super::profile_db::load_blocks(conn), vec![other]);
+            }
+        }
+
+        #[test]
+        fn confirmed_empty_block_list_sync_unblocks() {
+            let (mut core, _chat_id, _keys, _group_id) = make_core_with_group();
+            let other = Keys::generate().public_key().to_hex();
+            core.block_user(&other);
+            core.state.block_list_sync_enabled = true;
+
+            // Unblocked everyone on another device.
+            let token = core.block_list_sync_token;
+            core.handle_block_list_synced(token, vec![], true);
+
+            assert!(!core.is_blocked(&other));
+            assert!(core.state.blocked_users.is_empty());
+            if let Some(conn) = core.profile_db.as_ref() {
+                assert!(super::super::profile_db::load_blocks(conn).is_empty());
+            }
+        }
+
+        #[test]
+        fn notification_modes_drive_muted_indicator_and_push_set() {
+            let (mut core, chat_id, _keys, _group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            let summary = |core: &AppCore| {
+                core.state
+                    .chat_list
+                    .iter()
+                    .find(|c| c.chat_id == chat_id)
+                    .cloned()
+                    .expect("chat summary")
+            };
+            assert!(!summary(&core).is_muted);
+
+            core.set_chat_notification_mode(&chat_id, ChatNotificationMode::MentionsOnly);
+            assert_eq!(
+                summary(&core).notification_mode,
+                ChatNotificationMode::MentionsOnly
+            );
+            assert!(!summary(&core).is_muted);
+            assert_eq!(core.muted_chat_ids().count(), 0);
+
+            core.set_chat_notification_mode(&chat_id, ChatNotificationMode::Muted);
+            assert!(summary(&core).is_muted);
+            assert!(core.muted_chat_ids().any(|id| *id == chat_id));
+
+            // Snoozed chats stay subscribed, and a snooze that ran out reads as `All`.
+            core.chat_notification_modes.insert(
+                chat_id.clone(),
+                ChatNotificationMode::MutedUntil {
+                    until: now_seconds() - 1,
+                },
+            );
+            assert_eq!(core.muted_chat_ids().count(), 0);
+            assert_eq!(
+                core.chat_notification_mode(&chat_id),
+                ChatNotificationMode::All
+            );
+
+            core.chat_notification_modes.clear();
+            core.load_chat_notification_modes();
+            assert_eq!(
+                core.chat_notification_mode(&chat_id),
+                ChatNotificationMode::Muted
+            );
+        }
+
         #[test]
         fn chat_message_increments_unread() {
             let (mut core, chat_id, _keys, group_id) = make_core_with_group();
@@ -6809,6 +7590,10 @@ mod tests {
                 can_load_older: false,
                 typing_members: vec![],
                 my_group_profile: None,
+                disappearing_timer_secs: None,
+                notification_mode: ChatNotificationMode::All,
+                invite_links: vec![],
+                join_requests: vec![],
             });
             let other = Keys::generate();
             let msg = make_test_message(
@@ -7728,21 +8513,44 @@ mod tests {
         }
     }
 
-    mod message_handler_validation {
+    mod disappearing_tests {
         use super::*;
-        use crate::actions::AppAction;
+        use crate::mdk_support::open_mdk;
+        use mdk_core::prelude::{message_types, GroupId, NostrGroupConfigData};
+        use nostr_sdk::prelude::*;
+        use pika_marmot_runtime::message::{DisappearingTimerSetting, DISAPPEARING_TIMER_KIND};
 
-        /// Create a core with a minimal session (logged in, no groups registered).
-        fn make_logged_in_core() -> (AppCore, tempfile::TempDir) {
-            let tmp = tempfile::tempdir().unwrap();
-            let data_dir = tmp.path().to_string_lossy().into_owned();
-            let mut core = make_core(data_dir.clone());
-            let keys = nostr_sdk::Keys::generate();
-            let mdk = crate::mdk_support::open_mdk(&data_dir, &keys.public_key(), "").unwrap();
-            let client = nostr_sdk::Client::builder().signer(keys.clone()).build();
+        fn make_core_with_group() -> (AppCore, String, Keys, GroupId) {
+            let tempdir = tempfile::tempdir().expect("tempdir");
+            let data_dir = tempdir.path().to_string_lossy().into_owned();
+            std::mem::forget(tempdir);
+
+            let creator = Keys::generate();
+            let pubkey = creator.public_key();
+            let mdk = open_mdk(&data_dir, &pubkey, "").expect("open_mdk");
+
+            let config = NostrGroupConfigData::new(
+                "Test".to_string(),
+                String::new(),
+                None,
+                None,
+                None,
+                vec![RelayUrl::parse("wss://test.relay").unwrap()],
+                vec![pubkey],
+            );
+            let result = mdk
+                .create_group(&pubkey, vec![], config)
+                .expect("create_group");
+            let group_id = result.group.mls_group_id.clone();
+            let chat_id = hex::encode(result.group.nostr_group_id);
+            mdk.merge_pending_commit(&group_id)
+                .expect("merge_pending_commit");
+
+            let mut core = make_core(data_dir);
+            let client = Client::builder().signer(creator.clone()).build();
             core.session = Some(super::super::Session {
-                pubkey: keys.public_key(),
-                local_keys: Some(keys),
+                pubkey,
+                local_keys: Some(creator.clone()),
                 mdk,
                 client,
                 alive: Arc::new(std::sync::atomic::AtomicBool::new(true)),
@@ -7750,13 +8558,858 @@ mod tests {
                 group_sub: None,
                 groups: std::collections::HashMap::new(),
             });
-            (core, tmp)
-        }
 
-        #[test]
-        fn send_message_rejects_when_not_logged_in() {
-            let tmp = tempfile::tempdir().unwrap();
-            let mut core = make_core(tmp.path().to_string_lossy().into_owned());
+            (core, chat_id, creator, group_id)
+        }
+
+        fn timer_message(
+            sender: &PublicKey,
+            group_id: &GroupId,
+            expire_after_secs: Option<u64>,
+            created_at: u64,
+        ) -> message_types::Message {
+            let content = DisappearingTimerSetting::new(expire_after_secs).to_content();
+            let created_at = Timestamp::from(created_at);
+            let event = UnsignedEvent::new(
+                *sender,
+                created_at,
+                DISAPPEARING_TIMER_KIND,
+                Tags::new(),
+                content.clone(),
+            );
+            let mut id_bytes = [0u8; 32];
+            id_bytes[..8].copy_from_slice(&created_at.as_secs().to_be_bytes());
+            message_types::Message {
+                id: EventId::from_byte_array(id_bytes),
+                pubkey: *sender,
+                kind: DISAPPEARING_TIMER_KIND,
+                mls_group_id: group_id.clone(),
+                created_at,
+                processed_at: created_at,
+                content,
+                tags: Tags::new(),
+                event,
+                wrapper_event_id: EventId::all_zeros(),
+                epoch: None,
+                state: message_types::MessageState::Processed,
+            }
+        }
+
+        pub(super) fn store_chat_message(
+            core: &AppCore,
+            group_id: &GroupId,
+            content: &str,
+            at: i64,
+        ) -> EventId {
+            let sess = core.session.as_ref().unwrap();
+            let mut rumor = UnsignedEvent::new(
+                sess.pubkey,
+                Timestamp::from(at as u64),
+                Kind::ChatMessage,
+                Tags::new(),
+                content.to_string(),
+            );
+            rumor.ensure_id();
+            let rumor_id = rumor.id();
+            sess.mdk
+                .create_message(group_id, rumor)
+                .expect("create_message");
+            rumor_id
+        }
+
+        #[test]
+        fn incoming_timer_updates_summary_and_timeline() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            let other = Keys::generate().public_key();
+
+            core.handle_app_message(
+                &chat_id,
+                timer_message(&other, &group_id, Some(3600), 1_000),
+            );
+
+            assert_eq!(core.disappearing_timer_secs(&chat_id), Some(3600));
+            let summary = core
+                .state
+                .chat_list
+                .iter()
+                .find(|c| c.chat_id == chat_id)
+                .expect("chat summary");
+            assert_eq!(summary.disappearing_timer_secs, Some(3600));
+            let event = core
+                .state
+                .call_timeline
+                .iter()
+                .find(|e| e.chat_id == chat_id)
+                .expect("timeline event");
+            assert!(event
+                .text
+                .ends_with("set messages to disappear after 1 hour"));
+            assert_eq!(event.timestamp, 1_000);
+        }
+
+        #[test]
+        fn older_timer_does_not_override_newer_one() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            let other = Keys::generate().public_key();
+
+            core.handle_app_message(&chat_id, timer_message(&other, &group_id, None, 2_000));
+            core.handle_app_message(&chat_id, timer_message(&other, &group_id, Some(60), 1_000));
+
+            assert_eq!(core.disappearing_timer_secs(&chat_id), None);
+            assert_eq!(
+                core.state
+                    .call_timeline
+                    .iter()
+                    .filter(|e| e.chat_id == chat_id)
+                    .count(),
+                1
+            );
+        }
+
+        #[test]
+        fn timer_message_does_not_increment_unread() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            let other = Keys::generate().public_key();
+
+            core.handle_app_message(&chat_id, timer_message(&other, &group_id, Some(60), 1_000));
+
+            assert_eq!(*core.unread_counts.get(&chat_id).unwrap_or(&0), 0);
+        }
+
+        #[test]
+        fn timers_persist_across_reload() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            let other = Keys::generate().public_key();
+            core.handle_app_message(
+                &chat_id,
+                timer_message(&other, &group_id, Some(86_400), 1_000),
+            );
+
+            core.disappearing_timers.clear();
+            core.load_disappearing_timers();
+
+            assert_eq!(core.disappearing_timer_secs(&chat_id), Some(86_400));
+        }
+
+        #[test]
+        fn purge_scrubs_only_expired_messages_after_timer_was_set() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            let now = now_seconds();
+            store_chat_message(&core, &group_id, "before timer", now - 20_000);
+            store_chat_message(&core, &group_id, "expired", now - 7_200);
+            store_chat_message(&core, &group_id, "fresh", now - 60);
+            core.refresh_all_from_storage();
+            core.disappearing_timers.insert(
+                chat_id.clone(),
+                super::super::disappearing::DisappearingTimer {
+                    expire_after_secs: 3_600,
+                    set_at: now - 10_000,
+                },
+            );
+
+            core.purge_expired_messages();
+
+            let sess = core.session.as_ref().unwrap();
+            let stored = sess.mdk.get_messages(&group_id, None).expect("messages");
+            let live: Vec<&str> = stored
+                .iter()
+                .filter(|m| m.state != message_types::MessageState::Deleted)
+                .map(|m| m.content.as_str())
+                .collect();
+            assert!(live.contains(&"before timer"));
+            assert!(live.contains(&"fresh"));
+            assert!(!live.contains(&"expired"));
+            assert!(stored.iter().all(|m| m.content != "expired"));
+        }
+
+        #[test]
+        fn purge_rescans_below_high_water_mark_only_for_late_messages() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            let now = now_seconds();
+            store_chat_message(&core, &group_id, "expired", now - 7_200);
+            core.refresh_all_from_storage();
+            core.disappearing_timers.insert(
+                chat_id.clone(),
+                super::super::disappearing::DisappearingTimer {
+                    expire_after_secs: 3_600,
+                    set_at: now - 10_000,
+                },
+            );
+            core.purge_expired_messages();
+            let mark = *core
+                .disappearing_purged_through
+                .get(&chat_id)
+                .expect("purge records its cutoff");
+            assert!(mark >= now - 3_600);
+
+            // Delivered late, with a timestamp the last purge already covered.
+            store_chat_message(&core, &group_id, "late", now - 5_000);
+            core.note_disappearing_message(&chat_id, now - 5_000);
+            core.purge_expired_messages();
+
+            let sess = core.session.as_ref().unwrap();
+            let stored = sess.mdk.get_messages(&group_id, None).expect("messages");
+            assert!(stored.iter().all(|m| m.content != "late"));
+        }
+    }
+
+    mod search_tests {
+        use super::disappearing_tests::{make_core_with_group, store_chat_message};
+        use super::*;
+        use crate::actions::AppAction;
+        use mdk_core::prelude::{message_types, GroupId};
+        use nostr_sdk::prelude::*;
+        use pika_marmot_runtime::message::{MESSAGE_DELETION_KIND, MESSAGE_EDIT_KIND};
+
+        fn inbound_message(
+            sender: &PublicKey,
+            group_id: &GroupId,
+            kind: Kind,
+            tags: Tags,
+            content: &str,
+            created_at: u64,
+        ) -> message_types::Message {
+            let created_at = Timestamp::from(created_at);
+            let event = UnsignedEvent::new(*sender, created_at, kind, tags.clone(), content);
+            let mut id_bytes = [0u8; 32];
+            id_bytes[..8].copy_from_slice(&created_at.as_secs().to_be_bytes());
+            message_types::Message {
+                id: EventId::from_byte_array(id_bytes),
+                pubkey: *sender,
+                kind,
+                mls_group_id: group_id.clone(),
+                created_at,
+                processed_at: created_at,
+                content: content.to_string(),
+                tags,
+                event,
+                wrapper_event_id: EventId::all_zeros(),
+                epoch: None,
+                state: message_types::MessageState::Processed,
+            }
+        }
+
+        fn search(core: &mut AppCore, query: &str) -> Vec<crate::state::MessageSearchResult> {
+            core.handle_action(AppAction::SearchMessages {
+                query: query.into(),
+                chat_id: None,
+            });
+            core.state
+                .message_search
+                .as_ref()
+                .map(|s| s.results.clone())
+                .unwrap_or_default()
+        }
+
+        fn open_index(core: &mut AppCore) {
+            let pubkey_hex = core.session.as_ref().unwrap().pubkey.to_hex();
+            core.open_search_index(&pubkey_hex);
+        }
+
+        #[test]
+        fn backfill_indexes_stored_messages() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            let now = now_seconds();
+            store_chat_message(&core, &group_id, "meet at the harbour", now - 60);
+            store_chat_message(&core, &group_id, "bring snacks", now - 30);
+            core.refresh_all_from_storage();
+            open_index(&mut core);
+
+            core.backfill_search_index();
+
+            let results = search(&mut core, "harbour");
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].chat_id, chat_id);
+            assert!(results[0].snippet.contains("harbour"));
+        }
+
+        #[test]
+        fn inbound_edits_and_deletions_update_index() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            open_index(&mut core);
+            let other = Keys::generate().public_key();
+
+            let original = inbound_message(
+                &other,
+                &group_id,
+                Kind::ChatMessage,
+                Tags::new(),
+                "pizza tonight",
+                1_000,
+            );
+            let original_id = original.id;
+            core.handle_app_message(&chat_id, original);
+            assert_eq!(search(&mut core, "pizza").len(), 1);
+
+            core.handle_app_message(
+                &chat_id,
+                inbound_message(
+                    &other,
+                    &group_id,
+                    MESSAGE_EDIT_KIND,
+                    Tags::from_list(vec![Tag::event(original_id)]),
+                    "tacos tonight",
+                    1_001,
+                ),
+            );
+            assert!(search(&mut core, "pizza").is_empty());
+            assert_eq!(search(&mut core, "tacos").len(), 1);
+
+            core.handle_app_message(
+                &chat_id,
+                inbound_message(
+                    &other,
+                    &group_id,
+                    MESSAGE_DELETION_KIND,
+                    Tags::from_list(vec![Tag::event(original_id)]),
+                    "",
+                    1_002,
+                ),
+            );
+            assert!(search(&mut core, "tacos").is_empty());
+        }
+
+        #[test]
+        fn own_sends_are_indexed() {
+            let (mut core, chat_id, _keys, _group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            open_index(&mut core);
+
+            core.handle_action(AppAction::SendMessage {
+                chat_id: chat_id.clone(),
+                content: "remember the milk".into(),
+                kind: None,
+                reply_to_message_id: None,
+            });
+
+            let results = search(&mut core, "milk");
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].chat_id, chat_id);
+        }
+    }
+
+    mod read_receipt_tests {
+        use super::disappearing_tests::{make_core_with_group, store_chat_message};
+        use super::*;
+        use crate::actions::AppAction;
+        use mdk_core::prelude::{message_types, GroupId};
+        use nostr_sdk::prelude::*;
+        use pika_marmot_runtime::message::READ_RECEIPT_KIND;
+
+        fn receipt_message(
+            reader: &PublicKey,
+            group_id: &GroupId,
+            target: EventId,
+            created_at: u64,
+        ) -> message_types::Message {
+            let created_at = Timestamp::from(created_at);
+            let tags = Tags::from_list(vec![
+                Tag::custom(TagKind::d(), ["pika"]),
+                Tag::event(target),
+            ]);
+            let event =
+                UnsignedEvent::new(*reader, created_at, READ_RECEIPT_KIND, tags.clone(), "read");
+            let mut id_bytes = [0u8; 32];
+            id_bytes[..8].copy_from_slice(&created_at.as_secs().to_be_bytes());
+            message_types::Message {
+                id: EventId::from_byte_array(id_bytes),
+                pubkey: *reader,
+                kind: READ_RECEIPT_KIND,
+                mls_group_id: group_id.clone(),
+                created_at,
+                processed_at: created_at,
+                content: "read".to_string(),
+                tags,
+                event,
+                wrapper_event_id: EventId::all_zeros(),
+                epoch: None,
+                state: message_types::MessageState::Processed,
+            }
+        }
+
+        fn seen_by(core: &AppCore, message_id: &EventId) -> Vec<String> {
+            core.state
+                .current_chat
+                .as_ref()
+                .and_then(|c| c.messages.iter().find(|m| m.id == message_id.to_hex()))
+                .map(|m| m.seen_by.iter().map(|r| r.pubkey.clone()).collect())
+                .unwrap_or_default()
+        }
+
+        #[test]
+        fn receipt_marks_messages_up_to_target_as_seen() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            let now = now_seconds();
+            let first = store_chat_message(&core, &group_id, "first", now - 60);
+            let second = store_chat_message(&core, &group_id, "second", now - 30);
+            let third = store_chat_message(&core, &group_id, "third", now - 10);
+            core.refresh_all_from_storage();
+            core.handle_action(AppAction::OpenChat {
+                chat_id: chat_id.clone(),
+            });
+            let reader = Keys::generate().public_key();
+
+            core.handle_app_message(
+                &chat_id,
+                receipt_message(&reader, &group_id, second, now as u64),
+            );
+
+            assert_eq!(seen_by(&core, &first), vec![reader.to_hex()]);
+            assert_eq!(seen_by(&core, &second), vec![reader.to_hex()]);
+            assert!(seen_by(&core, &third).is_empty());
+
+            // A late receipt for an older message never moves the marker back.
+            core.handle_app_message(
+                &chat_id,
+                receipt_message(&reader, &group_id, first, now as u64 + 1),
+            );
+            assert_eq!(seen_by(&core, &second), vec![reader.to_hex()]);
+
+            // Markers survive a restart.
+            core.read_markers.clear();
+            core.load_read_markers();
+            core.refresh_current_chat(&chat_id);
+            assert_eq!(seen_by(&core, &second), vec![reader.to_hex()]);
+        }
+
+        #[test]
+        fn receipt_for_unknown_message_is_ignored() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            core.refresh_all_from_storage();
+            let reader = Keys::generate().public_key();
+
+            core.handle_app_message(
+                &chat_id,
+                receipt_message(&reader, &group_id, EventId::all_zeros(), 1_000),
+            );
+
+            assert!(core.read_markers.get(&chat_id).is_none());
+        }
+
+        #[test]
+        fn receipts_are_only_sent_when_enabled() {
+            let (mut core, chat_id, _keys, group_id) = make_core_with_group();
+            let incoming = store_chat_message(&core, &group_id, "hello", now_seconds() - 30);
+            core.refresh_all_from_storage();
+            core.handle_action(AppAction::OpenChat {
+                chat_id: chat_id.clone(),
+            });
+            // Stored messages in the fixture are ours; pretend this one came
+            // from a peer so there's something to acknowledge.
+            core.state.current_chat.as_mut().unwrap().messages[0].is_mine = false;
+            let my_hex = core.session.as_ref().unwrap().pubkey.to_hex();
+
+            assert!(!core.state.read_receipts_enabled);
+            core.send_read_receipt_if_needed(&chat_id);
+            assert!(core
+                .read_markers
+                .get(&chat_id)
+                .and_then(|readers| readers.get(&my_hex))
+                .is_none());
+
+            core.handle_action(AppAction::SetReadReceiptsEnabled { enabled: true });
+
+            assert!(core.state.read_receipts_enabled);
+            let mine = core
+                .read_markers
+                .get(&chat_id)
+                .and_then(|readers| readers.get(&my_hex))
+                .expect("own marker after sending");
+            assert_eq!(mine.message_id, incoming.to_hex());
+        }
+    }
+
+    mod backup_tests {
+        use super::*;
+        use crate::actions::AppAction;
+        use crate::mdk_support::{open_mdk, PikaMdk};
+        use crate::updates::InternalEvent;
+        use mdk_core::prelude::{GroupId, NostrGroupConfigData};
+        use nostr_sdk::prelude::*;
+
+        const PASSPHRASE: &str = "correct horse battery staple";
+
+        fn make_offline_core(keys: &Keys) -> (AppCore, tempfile::TempDir) {
+            let tmp = tempfile::tempdir().expect("tempdir");
+            std::fs::write(
+                tmp.path().join("pika_config.json"),
+                r#"{"disable_network":true}"#,
+            )
+            .expect("write config");
+            let mut core = make_core(tmp.path().to_string_lossy().into_owned());
+            core.start_session(keys.clone()).expect("start session");
+            (core, tmp)
+        }
+
+        pub(super) fn inviter_message(
+            mdk: &PikaMdk,
+            inviter: &Keys,
+            group_id: &GroupId,
+            content: &str,
+        ) -> Event {
+            let rumor = UnsignedEvent::new(
+                inviter.public_key(),
+                Timestamp::now(),
+                Kind::ChatMessage,
+                Tags::new(),
+                content.to_string(),
+            );
+            mdk.create_message(group_id, rumor).expect("create message")
+        }
+
+        /// `core` joins a new group created by `inviter`; returns (group id, chat id).
+        pub(super) fn join_group(
+            core: &mut AppCore,
+            keys: &Keys,
+            inviter: &Keys,
+            inviter_mdk: &PikaMdk,
+        ) -> (GroupId, String) {
+            let relay = RelayUrl::parse("wss://test.relay").expect("relay url");
+            let key_package = {
+                let mdk = &core.session.as_ref().expect("session").mdk;
+                let (content, tags, _hash_ref) = mdk
+                    .create_key_package_for_event(&keys.public_key(), vec![relay.clone()])
+                    .expect("create key package");
+                EventBuilder::new(Kind::MlsKeyPackage, content)
+                    .tags(tags)
+                    .sign_with_keys(keys)
+                    .expect("sign key package")
+            };
+            let config = NostrGroupConfigData::new(
+                "Backup test".to_string(),
+                String::new(),
+                None,
+                None,
+                None,
+                vec![relay],
+                vec![inviter.public_key(), keys.public_key()],
+            );
+            let created = inviter_mdk
+                .create_group(&inviter.public_key(), vec![key_package], config)
+                .expect("create group");
+            let group_id = created.group.mls_group_id.clone();
+            inviter_mdk
+                .merge_pending_commit(&group_id)
+                .expect("merge pending commit");
+            let welcome_rumor = created
+                .welcome_rumors
+                .into_iter()
+                .next()
+                .expect("welcome rumor");
+            let wrapper = tokio::runtime::Runtime::new()
+                .expect("tokio runtime")
+                .block_on(EventBuilder::gift_wrap(
+                    inviter,
+                    &keys.public_key(),
+                    welcome_rumor.clone(),
+                    Vec::<Tag>::new(),
+                ))
+                .expect("gift wrap");
+            let invite_id = wrapper.id.to_hex();
+            core.handle_internal(InternalEvent::GiftWrapReceived {
+                wrapper,
+                rumor: welcome_rumor,
+            });
+            core.handle_action(AppAction::AcceptInvite { invite_id });
+            (group_id, hex::encode(created.group.nostr_group_id))
+        }
+
+        fn stored_contents(core: &AppCore, group_id: &GroupId) -> Vec<String> {
+            core.session
+                .as_ref()
+                .expect("session")
+                .mdk
+                .get_messages(group_id, None)
+                .expect("messages")
+                .into_iter()
+                .map(|m| m.content)
+                .collect()
+        }
+
+        fn export(core: &mut AppCore, dir: &tempfile::TempDir) -> String {
+            let path = dir.path().join("account.pikabackup");
+            core.handle_action(AppAction::ExportBackup {
+                path: path.to_string_lossy().into_owned(),
+                passphrase: PASSPHRASE.to_string(),
+            });
+            assert_eq!(core.state.toast.as_deref(), Some("Backup saved"));
+            path.to_string_lossy().into_owned()
+        }
+
+        #[test]
+        fn restored_account_keeps_receiving_group_messages() {
+            let keys = Keys::generate();
+            let inviter = Keys::generate();
+            let inviter_dir = tempfile::tempdir().expect("tempdir");
+            let inviter_mdk = open_mdk(
+                &inviter_dir.path().to_string_lossy(),
+                &inviter.public_key(),
+                "",
+            )
+            .expect("open inviter mdk");
+
+            let (mut original, original_dir) = make_offline_core(&keys);
+            let (group_id, chat_id) = join_group(&mut original, &keys, &inviter, &inviter_mdk);
+            original.handle_internal(InternalEvent::GroupMessageReceived {
+                event: inviter_message(&inviter_mdk, &inviter, &group_id, "before backup"),
+            });
+            original.archived_chats.insert(chat_id.clone());
+            original.save_archived_chats();
+            let backup_path = export(&mut original, &original_dir);
+
+            // Fresh data dir, same account: the restore replaces its empty state.
+            let (mut restored, _restored_dir) = make_offline_core(&keys);
+            restored.handle_action(AppAction::ImportBackup {
+                path: backup_path,
+                passphrase: PASSPHRASE.to_string(),
+            });
+            assert_eq!(restored.state.toast.as_deref(), Some("Backup restored"));
+            assert!(restored.is_logged_in());
+            assert!(restored
+                .session
+                .as_ref()
+                .expect("session")
+                .groups
+                .contains_key(&chat_id));
+            assert!(restored.archived_chats.contains(&chat_id));
+            assert_eq!(stored_contents(&restored, &group_id), vec!["before backup"]);
+
+            restored.handle_internal(InternalEvent::GroupMessageReceived {
+                event: inviter_message(&inviter_mdk, &inviter, &group_id, "after restore"),
+            });
+            let contents = stored_contents(&restored, &group_id);
+            assert!(
+                contents.iter().any(|c| c == "after restore"),
+                "restored MLS state should decrypt new group messages: {contents:?}"
+            );
+        }
+
+        #[test]
+        fn restore_rejects_backup_from_another_account() {
+            let (mut original, original_dir) = make_offline_core(&Keys::generate());
+            let backup_path = export(&mut original, &original_dir);
+
+            let (mut other, _other_dir) = make_offline_core(&Keys::generate());
+            other.handle_action(AppAction::ImportBackup {
+                path: backup_path,
+                passphrase: PASSPHRASE.to_string(),
+            });
+            let toast = other.state.toast.clone().unwrap_or_default();
+            assert!(toast.contains("different account"), "{toast}");
+            assert!(other.is_logged_in());
+        }
+
+        #[test]
+        fn restore_rejects_wrong_passphrase() {
+            let keys = Keys::generate();
+            let (mut original, original_dir) = make_offline_core(&keys);
+            let backup_path = export(&mut original, &original_dir);
+
+            let (mut restored, _restored_dir) = make_offline_core(&keys);
+            restored.handle_action(AppAction::ImportBackup {
+                path: backup_path,
+                passphrase: "not the passphrase".to_string(),
+            });
+            let toast = restored.state.toast.clone().unwrap_or_default();
+            assert!(toast.contains("wrong passphrase"), "{toast}");
+            assert!(restored.is_logged_in());
+        }
+    }
+
+    mod accounts_tests {
+        use super::backup_tests::{inviter_message, join_group};
+        use super::*;
+        use crate::actions::AppAction;
+        use crate::mdk_support::open_mdk;
+        use crate::updates::InternalEvent;
+        use nostr_sdk::prelude::*;
+
+        fn make_offline_core() -> (AppCore, tempfile::TempDir) {
+            let tmp = tempfile::tempdir().expect("tempdir");
+            std::fs::write(
+                tmp.path().join("pika_config.json"),
+                r#"{"disable_network":true}"#,
+            )
+            .expect("write config");
+            let core = make_core(tmp.path().to_string_lossy().into_owned());
+            (core, tmp)
+        }
+
+        fn active_pubkey(core: &AppCore) -> PublicKey {
+            core.session.as_ref().expect("session").pubkey
+        }
+
+        fn summary<'a>(core: &'a AppCore, keys: &Keys) -> &'a crate::state::AccountSummary {
+            let pubkey_hex = keys.public_key().to_hex();
+            core.state
+                .accounts
+                .iter()
+                .find(|a| a.pubkey == pubkey_hex)
+                .expect("account summary")
+        }
+
+        #[test]
+        fn switching_accounts_swaps_per_account_storage() {
+            let (mut core, tmp) = make_offline_core();
+            let alice = Keys::generate();
+            let bob = Keys::generate();
+
+            core.start_session(alice.clone()).expect("start session");
+            core.archived_chats.insert("alice-chat".to_string());
+            core.save_archived_chats();
+
+            core.handle_action(AppAction::AddAccount {
+                nsec: bob.secret_key().to_bech32().expect("nsec"),
+            });
+            assert_eq!(active_pubkey(&core), bob.public_key());
+            assert!(core.archived_chats.is_empty());
+            assert_eq!(core.state.accounts.len(), 2);
+            assert!(summary(&core, &bob).is_active);
+            assert!(!summary(&core, &alice).is_active);
+            assert!(tmp
+                .path()
+                .join("accounts")
+                .join(alice.public_key().to_hex())
+                .join("archived_chats.json")
+                .exists());
+
+            core.handle_action(AppAction::SwitchAccount {
+                pubkey: alice.public_key().to_bech32().expect("npub"),
+            });
+            assert_eq!(active_pubkey(&core), alice.public_key());
+            assert!(core.archived_chats.contains("alice-chat"));
+            assert!(summary(&core, &alice).is_active);
+        }
+
+        #[test]
+        fn first_account_takes_over_legacy_files() {
+            let (mut core, tmp) = make_offline_core();
+            std::fs::write(tmp.path().join("archived_chats.json"), r#"["legacy"]"#)
+                .expect("write legacy file");
+            let keys = Keys::generate();
+
+            core.start_session(keys.clone()).expect("start session");
+            assert!(core.archived_chats.contains("legacy"));
+            assert!(!tmp.path().join("archived_chats.json").exists());
+
+            // Later accounts start empty.
+            core.start_session(Keys::generate())
+                .expect("start second session");
+            assert!(core.archived_chats.is_empty());
+        }
+
+        #[test]
+        fn inactive_account_keeps_unread_counts_and_push_chats() {
+            let (mut core, _tmp) = make_offline_core();
+            let alice = Keys::generate();
+            let inviter = Keys::generate();
+            let inviter_dir = tempfile::tempdir().expect("tempdir");
+            let inviter_mdk = open_mdk(
+                &inviter_dir.path().to_string_lossy(),
+                &inviter.public_key(),
+                "",
+            )
+            .expect("open inviter mdk");
+
+            core.start_session(alice.clone()).expect("start session");
+            let (group_id, chat_id) = join_group(&mut core, &alice, &inviter, &inviter_mdk);
+
+            core.start_session(Keys::generate())
+                .expect("start second session");
+            assert!(core.inactive_account_chat_ids().any(|id| *id == chat_id));
+            core.handle_internal(InternalEvent::BackgroundGroupMessageReceived {
+                account_pubkey: alice.public_key().to_hex(),
+                event: inviter_message(&inviter_mdk, &inviter, &group_id, "while away"),
+            });
+            assert_eq!(summary(&core, &alice).unread_count, 1);
+
+            core.handle_action(AppAction::SwitchAccount {
+                pubkey: alice.public_key().to_hex(),
+            });
+            assert_eq!(active_pubkey(&core), alice.public_key());
+            let chat = core
+                .state
+                .chat_list
+                .iter()
+                .find(|c| c.chat_id == chat_id)
+                .expect("chat");
+            assert_eq!(chat.unread_count, 1);
+            assert_eq!(chat.last_message.as_deref(), Some("while away"));
+        }
+
+        #[test]
+        fn removing_inactive_account_deletes_its_data() {
+            let (mut core, tmp) = make_offline_core();
+            let alice = Keys::generate();
+            let bob = Keys::generate();
+            core.start_session(alice.clone()).expect("start session");
+            core.start_session(bob.clone())
+                .expect("start second session");
+            let alice_hex = alice.public_key().to_hex();
+
+            core.handle_action(AppAction::RemoveAccount {
+                pubkey: alice_hex.clone(),
+            });
+            assert_eq!(core.state.accounts.len(), 1);
+            assert!(summary(&core, &bob).is_active);
+            assert!(!tmp.path().join("accounts").join(&alice_hex).exists());
+            assert!(!crate::mdk_support::mdk_db_path(&core.data_dir, &alice_hex).exists());
+
+            // Signing out the last account returns to the login screen.
+            core.handle_action(AppAction::Logout);
+            assert!(!core.is_logged_in());
+            assert!(core.state.accounts.is_empty());
+        }
+
+        #[test]
+        fn logout_falls_back_to_another_signed_in_account() {
+            let (mut core, _tmp) = make_offline_core();
+            let alice = Keys::generate();
+            let bob = Keys::generate();
+            core.start_session(alice.clone()).expect("start session");
+            core.start_session(bob.clone())
+                .expect("start second session");
+
+            core.handle_action(AppAction::Logout);
+            assert_eq!(active_pubkey(&core), alice.public_key());
+            assert_eq!(core.state.accounts.len(), 1);
+        }
+    }
+
+    mod message_handler_validation {
+        use super::*;
+        use crate::actions::AppAction;
+
+        /// Create a core with a minimal session (logged in, no groups registered).
+        fn make_logged_in_core() -> (AppCore, tempfile::TempDir) {
+            let tmp = tempfile::tempdir().unwrap();
+            let data_dir = tmp.path().to_string_lossy().into_owned();
+            let mut core = make_core(data_dir.clone());
+            let keys = nostr_sdk::Keys::generate();
+            let mdk = crate::mdk_support::open_mdk(&data_dir, &keys.public_key(), "").unwrap();
+            let client = nostr_sdk::Client::builder().signer(keys.clone()).build();
+            core.session = Some(super::super::Session {
+                pubkey: keys.public_key(),
+                local_keys: Some(keys),
+                mdk,
+                client,
+                alive: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+                giftwrap_sub: None,
+                group_sub: None,
+                groups: std::collections::HashMap::new(),
+            });
+            (core, tmp)
+        }
+
+        #[test]
+        fn send_message_rejects_when_not_logged_in() {
+            let tmp = tempfile::tempdir().unwrap();
+            let mut core = make_core(tmp.path().to_string_lossy().into_owned());
 
             core.handle_action(AppAction::SendMessage {
                 chat_id: "chat1".into(),
@@ -7831,6 +9484,7 @@ mod tests {
         use crate::state::{AuthMode, AuthState};
         use crate::updates::InternalEvent;
         use nostr_sdk::{Keys, ToBech32};
+        use pika_marmot_runtime::conversation::ConversationEvent;
         use pika_marmot_runtime::membership::PreparedMembershipEvolution;
 
         /// Create a core with a minimal session (logged in, no groups registered).
@@ -8215,6 +9869,350 @@ mod tests {
             assert_eq!(core.state.toast.as_deref(), Some("Chat not found"));
         }
 
+        fn insert_group(core: &mut AppCore, chat_id: &str, admin_pubkeys: Vec<String>) {
+            core.session.as_mut().unwrap().groups.insert(
+                chat_id.to_string(),
+                super::super::GroupIndexEntry {
+                    mls_group_id: mdk_core::prelude::GroupId::from_slice(&[1]),
+                    is_group: true,
+                    group_name: Some("Test".into()),
+                    members: vec![],
+                    admin_pubkeys,
+                },
+            );
+        }
+
+        #[test]
+        fn membership_and_metadata_changes_require_admin() {
+            let (mut core, _tmp) = make_logged_in_core();
+            let other = Keys::generate().public_key().to_hex();
+            insert_group(&mut core, "chat1", vec![other.clone()]);
+
+            core.handle_action(AppAction::RenameGroup {
+                chat_id: "chat1".into(),
+                name: "New name".into(),
+            });
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("Only group admins can do that")
+            );
+
+            core.state.toast = None;
+            core.handle_action(AppAction::RemoveGroupMembers {
+                chat_id: "chat1".into(),
+                member_pubkeys: vec![other.clone()],
+            });
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("Only group admins can do that")
+            );
+
+            core.state.toast = None;
+            core.handle_action(AppAction::PromoteAdmin {
+                chat_id: "chat1".into(),
+                pubkey: other.clone(),
+            });
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("Only group admins can do that")
+            );
+            assert!(core.pending_group_ops.is_empty());
+        }
+
+        #[test]
+        fn admin_change_rejects_unknown_chat_and_bad_pubkey() {
+            let (mut core, _tmp) = make_logged_in_core();
+
+            core.handle_action(AppAction::DemoteAdmin {
+                chat_id: "nonexistent".into(),
+                pubkey: Keys::generate().public_key().to_hex(),
+            });
+            assert_eq!(core.state.toast.as_deref(), Some("Chat not found"));
+
+            core.handle_action(AppAction::DemoteAdmin {
+                chat_id: "nonexistent".into(),
+                pubkey: "not-a-key".into(),
+            });
+            assert!(core
+                .state
+                .toast
+                .as_deref()
+                .unwrap()
+                .contains("Invalid pubkey"));
+        }
+
+        #[test]
+        fn rejected_commit_surfaces_warning() {
+            let (mut core, _tmp) = make_logged_in_core();
+            insert_group(&mut core, "chat1", vec![]);
+
+            core.handle_conversation_event(Some(ConversationEvent::RejectedCommit {
+                nostr_group_id_hex: "chat1".into(),
+                reason: "commit from non-admin".into(),
+            }));
+
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("Ignored a change to \"Test\" from someone who isn't an admin")
+            );
+        }
+
+        #[test]
+        fn invite_links_are_admin_only_and_revocable() {
+            let (mut core, _tmp) = make_logged_in_core();
+            let me = core.session.as_ref().unwrap().pubkey;
+            insert_group(
+                &mut core,
+                "chat1",
+                vec![Keys::generate().public_key().to_hex()],
+            );
+            core.handle_action(AppAction::CreateGroupInviteLink {
+                chat_id: "chat1".into(),
+                multi_use: true,
+                requires_approval: false,
+            });
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("Only group admins can do that")
+            );
+            assert!(core.group_invite_links("chat1").is_empty());
+
+            core.state.toast = None;
+            insert_group(&mut core, "chat2", vec![me.to_hex()]);
+            core.handle_action(AppAction::CreateGroupInviteLink {
+                chat_id: "chat2".into(),
+                multi_use: true,
+                requires_approval: false,
+            });
+            let links = core.group_invite_links("chat2");
+            assert_eq!(links.len(), 1);
+            assert_eq!(links[0].admin_npub, me.to_bech32().unwrap());
+            assert!(crate::is_group_invite_link(
+                &crate::group_invite_link_url("pika", &links[0].admin_npub, &links[0].token)
+                    .unwrap()
+            ));
+
+            core.handle_action(AppAction::RevokeGroupInviteLink {
+                chat_id: "chat2".into(),
+                token: links[0].token.clone(),
+            });
+            assert!(core.group_invite_links("chat2").is_empty());
+            assert!(core.state.toast.is_none());
+            core.handle_action(AppAction::RevokeGroupInviteLink {
+                chat_id: "chat2".into(),
+                token: links[0].token.clone(),
+            });
+            assert_eq!(core.state.toast.as_deref(), Some("Invite link not found"));
+        }
+
+        fn join_request_gift_wrap(
+            requester: &Keys,
+            token: &str,
+        ) -> (nostr_sdk::Event, nostr_sdk::UnsignedEvent) {
+            use pika_marmot_runtime::invite_link::{JoinRequest, JOIN_REQUEST_KIND};
+            let wrapper = nostr_sdk::EventBuilder::new(nostr_sdk::Kind::GiftWrap, "")
+                .sign_with_keys(&Keys::generate())
+                .unwrap();
+            let rumor = nostr_sdk::EventBuilder::new(
+                JOIN_REQUEST_KIND,
+                JoinRequest::new(token).to_content(),
+            )
+            .build(requester.public_key());
+            (wrapper, rumor)
+        }
+
+        #[test]
+        fn approval_links_stage_each_join_request_once() {
+            let (mut core, _tmp) = make_logged_in_core();
+            core.config.disable_network = Some(true);
+            let me = core.session.as_ref().unwrap().pubkey.to_hex();
+            insert_group(&mut core, "chat1", vec![me]);
+            core.handle_action(AppAction::CreateGroupInviteLink {
+                chat_id: "chat1".into(),
+                multi_use: false,
+                requires_approval: true,
+            });
+            let token = core.group_invite_links("chat1")[0].token.clone();
+
+            let requester = Keys::generate();
+            let requester_hex = requester.public_key().to_hex();
+            let (wrapper, rumor) = join_request_gift_wrap(&requester, &token);
+            core.handle_internal(InternalEvent::GiftWrapReceived {
+                wrapper: wrapper.clone(),
+                rumor: rumor.clone(),
+            });
+            let requests = core.group_join_requests("chat1");
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].pubkey, requester_hex);
+            assert!(core
+                .state
+                .toast
+                .as_deref()
+                .unwrap()
+                .ends_with("wants to join \"Test\""));
+            // Staging a request doesn't use up the link, and neither does declining it.
+            assert_eq!(core.group_invite_links("chat1").len(), 1);
+
+            core.handle_action(AppAction::DeclineJoinRequest {
+                chat_id: "chat1".into(),
+                pubkey: requester_hex.clone(),
+            });
+            assert!(core.group_join_requests("chat1").is_empty());
+            core.handle_action(AppAction::DeclineJoinRequest {
+                chat_id: "chat1".into(),
+                pubkey: requester_hex,
+            });
+            assert_eq!(core.state.toast.as_deref(), Some("Join request not found"));
+
+            assert_eq!(core.group_invite_links("chat1")[0].use_count, 0);
+
+            // Relays redeliver gift wraps; a handled request stays handled.
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert!(core.group_join_requests("chat1").is_empty());
+
+            // Revoked or unknown tokens are ignored.
+            core.handle_action(AppAction::RevokeGroupInviteLink {
+                chat_id: "chat1".into(),
+                token: token.clone(),
+            });
+            let (wrapper, rumor) = join_request_gift_wrap(&Keys::generate(), &token);
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert!(core.group_join_requests("chat1").is_empty());
+        }
+
+        fn evolution_adding(added: Vec<nostr_sdk::PublicKey>) -> PreparedMembershipEvolution {
+            PreparedMembershipEvolution {
+                mls_group_id: mdk_core::prelude::GroupId::from_slice(&[1]),
+                nostr_group_id_hex: "chat1".to_string(),
+                evolution_event: nostr_sdk::EventBuilder::new(nostr_sdk::Kind::Custom(444), "")
+                    .sign_with_keys(&Keys::generate())
+                    .expect("dummy event"),
+                added_pubkeys: added,
+                welcome_rumors: vec![],
+            }
+        }
+
+        #[test]
+        fn one_time_link_survives_a_join_that_never_lands() {
+            let (mut core, _tmp) = make_logged_in_core();
+            core.config.disable_network = Some(true);
+            let me = core.session.as_ref().unwrap().pubkey.to_hex();
+            insert_group(&mut core, "chat1", vec![me]);
+            core.handle_action(AppAction::CreateGroupInviteLink {
+                chat_id: "chat1".into(),
+                multi_use: false,
+                requires_approval: false,
+            });
+            let token = core.group_invite_links("chat1")[0].token.clone();
+
+            // The first add starts; the second request waits behind it.
+            let first = Keys::generate();
+            let (wrapper, rumor) = join_request_gift_wrap(&first, &token);
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            let second = Keys::generate();
+            let (wrapper, rumor) = join_request_gift_wrap(&second, &token);
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert_eq!(core.pending_invite_link_joins.len(), 1);
+            assert_eq!(core.queued_invite_link_joins["chat1"].len(), 1);
+
+            // The first commit never lands, so the link still lets the next
+            // person in and nothing is left behind for the failed join.
+            core.handle_group_evolution_published(
+                "chat1".to_string(),
+                evolution_adding(vec![first.public_key()]),
+                false,
+                Some("relay error".to_string()),
+            );
+            assert_eq!(core.group_invite_links("chat1").len(), 1);
+            assert_eq!(
+                core.pending_invite_link_joins.keys().collect::<Vec<_>>(),
+                vec![&("chat1".to_string(), second.public_key().to_hex())]
+            );
+            assert!(core.queued_invite_link_joins.is_empty());
+
+            // Once a commit adding them is published, it's used up.
+            core.use_invite_links_for_joins("chat1", &[second.public_key()]);
+            assert!(core.group_invite_links("chat1").is_empty());
+            assert!(core.pending_invite_link_joins.is_empty());
+        }
+
+        #[test]
+        fn multi_use_link_adds_every_requester() {
+            let (mut core, _tmp) = make_logged_in_core();
+            core.config.disable_network = Some(true);
+            let me = core.session.as_ref().unwrap().pubkey.to_hex();
+            insert_group(&mut core, "chat1", vec![me]);
+            core.handle_action(AppAction::CreateGroupInviteLink {
+                chat_id: "chat1".into(),
+                multi_use: true,
+                requires_approval: false,
+            });
+            let token = core.group_invite_links("chat1")[0].token.clone();
+
+            // Both requests come in while another group update is publishing.
+            core.pending_group_ops.insert("chat1".to_string());
+            let first = Keys::generate();
+            let second = Keys::generate();
+            for requester in [&first, &second] {
+                let (wrapper, rumor) = join_request_gift_wrap(requester, &token);
+                core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            }
+            assert!(core.pending_invite_link_joins.is_empty());
+            assert_eq!(core.queued_invite_link_joins["chat1"].len(), 2);
+
+            // Once it's done, they go out together in one add.
+            core.handle_group_evolution_published(
+                "chat1".to_string(),
+                evolution_adding(vec![]),
+                true,
+                None,
+            );
+            assert_eq!(core.pending_invite_link_joins.len(), 2);
+            assert!(core.queued_invite_link_joins.is_empty());
+
+            // Another update beats that add to publishing; the joins wait for
+            // it instead of being dropped.
+            core.state.toast = None;
+            core.pending_group_ops.insert("chat1".to_string());
+            core.publish_prepared_evolution(
+                "chat1",
+                evolution_adding(vec![first.public_key(), second.public_key()]),
+            );
+            assert!(core.state.toast.is_none());
+            assert!(core.pending_invite_link_joins.is_empty());
+            assert_eq!(core.queued_invite_link_joins["chat1"].len(), 2);
+            core.handle_group_evolution_published(
+                "chat1".to_string(),
+                evolution_adding(vec![]),
+                true,
+                None,
+            );
+            assert_eq!(core.pending_invite_link_joins.len(), 2);
+
+            core.use_invite_links_for_joins("chat1", &[first.public_key(), second.public_key()]);
+            assert_eq!(core.group_invite_links("chat1")[0].use_count, 2);
+            assert!(core.pending_invite_link_joins.is_empty());
+        }
+
+        #[test]
+        fn join_via_invite_link_rejects_bad_and_own_links() {
+            let (mut core, _tmp) = make_logged_in_core();
+            core.config.disable_network = Some(true);
+            core.handle_action(AppAction::JoinGroupViaInviteLink {
+                link: "pika://join/garbage".into(),
+            });
+            assert_eq!(core.state.toast.as_deref(), Some("Invalid invite link"));
+
+            let me = core.session.as_ref().unwrap().pubkey.to_bech32().unwrap();
+            core.handle_action(AppAction::JoinGroupViaInviteLink {
+                link: format!("pika://join/{me}/{}", "ab".repeat(16)),
+            });
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("That's your own invite link")
+            );
+        }
+
         #[test]
         fn concurrent_group_mutation_rejected_while_pending() {
             let (mut core, _tmp) = make_logged_in_core();
@@ -8306,7 +10304,9 @@ mod tests {
         use crate::updates::InternalEvent;
         use mdk_core::prelude::NostrGroupConfigData;
         use nostr_sdk::nips::nip19::ToBech32;
-        use nostr_sdk::prelude::{Client, Event, EventBuilder, Keys, Kind, RelayUrl, Tag};
+        use nostr_sdk::prelude::{
+            Client, Event, EventBuilder, Keys, Kind, RelayUrl, Tag, UnsignedEvent,
+        };
         use std::sync::Arc;
 
         fn make_logged_in_core() -> (AppCore, tempfile::TempDir, Keys) {
@@ -8341,22 +10341,28 @@ mod tests {
                 .expect("sign key package")
         }
 
-        #[test]
-        fn gift_wrap_received_accepts_immediately_and_skips_duplicate_pending_state() {
-            let (mut core, inviter_dir, invitee_keys) = make_logged_in_core();
+        /// A gift-wrapped welcome into a fresh group created by a new inviter.
+        fn welcome_from_new_inviter(
+            core: &AppCore,
+            invitee_keys: &Keys,
+            inviter_dir: &std::path::Path,
+            group_name: &str,
+        ) -> (Keys, Event, UnsignedEvent) {
             let inviter_keys = Keys::generate();
             let inviter_mdk = crate::mdk_support::open_mdk(
-                &inviter_dir.path().join("inviter").to_string_lossy(),
+                &inviter_dir
+                    .join(inviter_keys.public_key().to_hex())
+                    .to_string_lossy(),
                 &inviter_keys.public_key(),
                 "",
             )
             .expect("open inviter mdk");
             let invitee_kp = {
                 let invitee_mdk = &core.session.as_ref().expect("session").mdk;
-                make_key_package_event(invitee_mdk, &invitee_keys)
+                make_key_package_event(invitee_mdk, invitee_keys)
             };
             let config = NostrGroupConfigData::new(
-                "App welcome test".to_string(),
+                group_name.to_string(),
                 String::new(),
                 None,
                 None,
@@ -8384,34 +10390,70 @@ mod tests {
                     .await
                     .expect("gift wrap")
                 });
+            (inviter_keys, wrapper, welcome_rumor)
+        }
+
+        fn active_group_count(core: &AppCore) -> usize {
+            core.session
+                .as_ref()
+                .expect("session")
+                .mdk
+                .get_groups()
+                .expect("get groups")
+                .iter()
+                .filter(|g| g.state == mdk_storage_traits::groups::types::GroupState::Active)
+                .count()
+        }
+
+        fn pending_welcome_count(core: &AppCore) -> usize {
+            core.session
+                .as_ref()
+                .expect("session")
+                .mdk
+                .get_pending_welcomes(None)
+                .expect("pending welcomes")
+                .len()
+        }
+
+        #[test]
+        fn gift_wrap_received_stages_stranger_invites_until_accepted() {
+            let (mut core, inviter_dir, invitee_keys) = make_logged_in_core();
+            let (inviter_keys, wrapper, welcome_rumor) = welcome_from_new_inviter(
+                &core,
+                &invitee_keys,
+                inviter_dir.path(),
+                "App welcome test",
+            );
 
             core.handle_internal(InternalEvent::GiftWrapReceived {
                 wrapper: wrapper.clone(),
                 rumor: welcome_rumor.clone(),
             });
 
-            let groups = core
-                .session
-                .as_ref()
-                .expect("session")
-                .mdk
-                .get_groups()
-                .expect("get groups");
             assert_eq!(
-                groups.len(),
-                1,
-                "app accepts welcome immediately on receipt"
-            );
-            assert!(
-                core.session
-                    .as_ref()
-                    .expect("session")
-                    .mdk
-                    .get_pending_welcomes(None)
-                    .expect("pending welcomes")
-                    .is_empty(),
-                "app should not leave the welcome staged after eager accept"
+                active_group_count(&core),
+                0,
+                "strangers' welcomes are staged"
             );
+            assert_eq!(pending_welcome_count(&core), 1);
+            assert!(core.state.chat_list.is_empty());
+            assert_eq!(core.state.invitations.len(), 1);
+            let invite = core.state.invitations[0].clone();
+            assert_eq!(invite.invite_id, wrapper.id.to_hex());
+            assert_eq!(invite.inviter_pubkey, inviter_keys.public_key().to_hex());
+            assert_eq!(invite.group_name.as_deref(), Some("App welcome test"));
+            assert_eq!(invite.member_count, 2);
+            assert!(!invite.inviter_followed);
+
+            core.handle_action(AppAction::AcceptInvite {
+                invite_id: invite.invite_id.clone(),
+            });
+
+            assert_eq!(active_group_count(&core), 1);
+            assert_eq!(pending_welcome_count(&core), 0);
+            assert!(core.state.invitations.is_empty());
+            assert_eq!(core.state.chat_list.len(), 1);
+            assert_eq!(core.state.chat_list[0].chat_id, invite.chat_id);
 
             core.handle_internal(InternalEvent::GiftWrapReceived {
                 wrapper,
@@ -8419,26 +10461,138 @@ mod tests {
             });
 
             assert_eq!(
-                core.session
-                    .as_ref()
-                    .expect("session")
-                    .mdk
-                    .get_groups()
-                    .expect("get groups")
-                    .len(),
+                active_group_count(&core),
                 1,
                 "re-delivered welcomes should not create duplicate active groups"
             );
-            assert!(
-                core.session
-                    .as_ref()
-                    .expect("session")
-                    .mdk
-                    .get_pending_welcomes(None)
-                    .expect("pending welcomes")
-                    .is_empty(),
+            assert_eq!(
+                pending_welcome_count(&core),
+                0,
                 "re-delivered welcomes should stay skipped once the group is active"
             );
+            assert!(core.state.invitations.is_empty());
+        }
+
+        #[test]
+        fn followed_inviters_are_auto_accepted_only_while_the_policy_is_on() {
+            let (mut core, inviter_dir, invitee_keys) = make_logged_in_core();
+            assert!(core.state.auto_accept_followed_invites);
+
+            let (inviter_keys, wrapper, rumor) =
+                welcome_from_new_inviter(&core, &invitee_keys, inviter_dir.path(), "Friends");
+            super::super::profile_db::add_follow(
+                core.profile_db.as_ref().expect("profile db"),
+                &inviter_keys.public_key().to_hex(),
+            );
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert_eq!(active_group_count(&core), 1);
+            assert!(core.state.invitations.is_empty());
+
+            core.handle_action(AppAction::SetAutoAcceptFollowedInvites { enabled: false });
+            let (inviter_keys, wrapper, rumor) =
+                welcome_from_new_inviter(&core, &invitee_keys, inviter_dir.path(), "Work");
+            super::super::profile_db::add_follow(
+                core.profile_db.as_ref().expect("profile db"),
+                &inviter_keys.public_key().to_hex(),
+            );
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert_eq!(active_group_count(&core), 1);
+            assert_eq!(core.state.invitations.len(), 1);
+            assert!(core.state.invitations[0].inviter_followed);
+        }
+
+        #[test]
+        fn welcomes_answering_our_join_request_skip_the_inbox() {
+            let (mut core, inviter_dir, invitee_keys) = make_logged_in_core();
+            core.config.disable_network = Some(true);
+            let (inviter_keys, wrapper, rumor) =
+                welcome_from_new_inviter(&core, &invitee_keys, inviter_dir.path(), "Book club");
+            core.handle_action(AppAction::JoinGroupViaInviteLink {
+                link: format!(
+                    "pikadev://join/{}/{}",
+                    inviter_keys.public_key().to_bech32().unwrap(),
+                    "ab".repeat(16)
+                ),
+            });
+            assert!(core.state.toast.is_none());
+
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert_eq!(active_group_count(&core), 1);
+            assert!(core.state.invitations.is_empty());
+            assert!(!core.take_sent_join_request(&inviter_keys.public_key().to_hex()));
+        }
+
+        #[test]
+        fn declined_invites_leave_the_inbox_without_joining() {
+            let (mut core, inviter_dir, invitee_keys) = make_logged_in_core();
+            let (_inviter_keys, wrapper, rumor) =
+                welcome_from_new_inviter(&core, &invitee_keys, inviter_dir.path(), "Spam");
+            core.handle_internal(InternalEvent::GiftWrapReceived {
+                wrapper: wrapper.clone(),
+                rumor: rumor.clone(),
+            });
+            assert_eq!(core.state.invitations.len(), 1);
+
+            core.handle_action(AppAction::DeclineInvite {
+                invite_id: wrapper.id.to_hex(),
+            });
+            assert!(core.state.invitations.is_empty());
+            assert_eq!(pending_welcome_count(&core), 0);
+            assert_eq!(active_group_count(&core), 0);
+            assert!(core.state.chat_list.is_empty());
+
+            // A re-delivered copy of a declined welcome stays out of the inbox.
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert!(core.state.invitations.is_empty());
+            assert_eq!(active_group_count(&core), 0);
+
+            core.handle_action(AppAction::AcceptInvite {
+                invite_id: "not-an-event-id".into(),
+            });
+            assert_eq!(core.state.toast.as_deref(), Some("Invitation not found"));
+        }
+
+        #[test]
+        fn blocking_declines_staged_invites_and_drops_new_ones() {
+            let (mut core, inviter_dir, invitee_keys) = make_logged_in_core();
+            let (inviter_keys, wrapper, rumor) =
+                welcome_from_new_inviter(&core, &invitee_keys, inviter_dir.path(), "Spam");
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert_eq!(core.state.invitations.len(), 1);
+
+            let inviter_hex = inviter_keys.public_key().to_hex();
+            core.handle_action(AppAction::BlockUser {
+                pubkey: inviter_hex.clone(),
+            });
+            assert!(core.state.invitations.is_empty());
+            assert_eq!(pending_welcome_count(&core), 0);
+            assert_eq!(core.state.blocked_users.len(), 1);
+            assert_eq!(core.state.blocked_users[0].pubkey, inviter_hex);
+
+            // Even a followed inviter can't get back in while blocked.
+            let (inviter_keys, wrapper, rumor) =
+                welcome_from_new_inviter(&core, &invitee_keys, inviter_dir.path(), "Spam 2");
+            let inviter_hex = inviter_keys.public_key().to_hex();
+            super::super::profile_db::add_follow(
+                core.profile_db.as_ref().expect("profile db"),
+                &inviter_hex,
+            );
+            core.handle_action(AppAction::BlockUser {
+                pubkey: inviter_hex,
+            });
+            core.handle_internal(InternalEvent::GiftWrapReceived { wrapper, rumor });
+            assert_eq!(pending_welcome_count(&core), 0);
+            assert_eq!(active_group_count(&core), 0);
+            assert!(core.state.invitations.is_empty());
+
+            core.handle_action(AppAction::BlockUser {
+                pubkey: invitee_keys.public_key().to_hex(),
+            });
+            assert_eq!(
+                core.state.toast.as_deref(),
+                Some("You can't block yourself")
+            );
+            assert_eq!(core.state.blocked_users.len(), 2);
         }
 
         #[test]
@@ -8486,6 +10640,7 @@ mod tests {
                 broadcast_base: format!("pika/calls/{call_id}"),
                 relay_auth: String::new(),
                 tracks: vec![crate::core::call_control::CallTrackSpec::audio0_opus_default()],
+                group_call: false,
             };
             session.relay_auth = core
                 .derive_relay_auth_token(
@@ -255,6 +255,7 @@ impl AppCore {
         let next = self.my_profile_state();
         if next != self.state.my_profile {
             self.state.my_profile = next;
+            self.refresh_account_summaries();
             self.emit_state();
         }
     }
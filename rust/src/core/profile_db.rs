@@ -3,6 +3,9 @@ use std::collections::HashMap;
 use rusqlite::Connection;
 
 use super::ProfileCache;
+use crate::state::ChatNotificationMode;
+
+pub(super) const PROFILE_DB_FILE: &str = "profiles.sqlite3";
 
 const SCHEMA: &str = "
     CREATE TABLE IF NOT EXISTS profiles (
@@ -19,6 +22,38 @@ const SCHEMA: &str = "
     CREATE TABLE IF NOT EXISTS follows (
         pubkey TEXT PRIMARY KEY
     );
+    CREATE TABLE IF NOT EXISTS blocks (
+        pubkey TEXT PRIMARY KEY,
+        blocked_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS group_invite_links (
+        token TEXT PRIMARY KEY,
+        chat_id TEXT NOT NULL,
+        multi_use INTEGER NOT NULL,
+        requires_approval INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        use_count INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE TABLE IF NOT EXISTS group_join_requests (
+        chat_id TEXT NOT NULL,
+        pubkey TEXT NOT NULL,
+        token TEXT NOT NULL,
+        requested_at INTEGER NOT NULL,
+        PRIMARY KEY (chat_id, pubkey)
+    );
+    CREATE TABLE IF NOT EXISTS handled_join_requests (
+        rumor_id TEXT PRIMARY KEY,
+        handled_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS sent_join_requests (
+        admin_pubkey TEXT PRIMARY KEY,
+        sent_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS chat_notification_modes (
+        chat_id TEXT PRIMARY KEY,
+        mode TEXT NOT NULL,
+        muted_until INTEGER
+    );
     CREATE TABLE IF NOT EXISTS app_settings (
         key TEXT PRIMARY KEY,
         value TEXT NOT NULL
@@ -36,7 +71,7 @@ const SCHEMA: &str = "
 ";
 
 pub fn open_profile_db(data_dir: &str) -> Result<Connection, rusqlite::Error> {
-    let path = std::path::Path::new(data_dir).join("profiles.sqlite3");
+    let path = std::path::Path::new(data_dir).join(PROFILE_DB_FILE);
 
     // Migration: delete and recreate if the schema is missing required columns.
     if path.exists() {
@@ -253,6 +288,53 @@ pub fn save_developer_mode(conn: &Connection, enabled: bool) {
     }
 }
 
+/// Whether we send read receipts. Off unless the user opts in.
+pub fn load_read_receipts(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'read_receipts'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|value| matches!(value.as_str(), "1" | "true" | "TRUE"))
+    .unwrap_or(false)
+}
+
+pub fn save_read_receipts(conn: &Connection, enabled: bool) {
+    let value = if enabled { "1" } else { "0" };
+    if let Err(e) = conn.execute(
+        "INSERT INTO app_settings (key, value)
+         VALUES ('read_receipts', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [value],
+    ) {
+        tracing::warn!(%e, enabled, "failed to save read receipts setting");
+    }
+}
+
+/// Whether invitations from people we follow are joined without asking.
+/// On unless the user turns it off.
+pub fn load_auto_accept_followed_invites(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'auto_accept_followed_invites'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|value| matches!(value.as_str(), "1" | "true" | "TRUE"))
+    .unwrap_or(true)
+}
+
+pub fn save_auto_accept_followed_invites(conn: &Connection, enabled: bool) {
+    let value = if enabled { "1" } else { "0" };
+    if let Err(e) = conn.execute(
+        "INSERT INTO app_settings (key, value)
+         VALUES ('auto_accept_followed_invites', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [value],
+    ) {
+        tracing::warn!(%e, enabled, "failed to save invite auto-accept setting");
+    }
+}
+
 // ── Follow cache ─────────────────────────────────────────────────────
 
 pub fn load_follows(conn: &Connection) -> Vec<String> {
@@ -296,6 +378,13 @@ pub fn save_follows(conn: &Connection, pubkeys: &[String]) {
     }
 }
 
+pub fn is_followed(conn: &Connection, pubkey: &str) -> bool {
+    conn.query_row("SELECT 1 FROM follows WHERE pubkey = ?1", [pubkey], |_| {
+        Ok(())
+    })
+    .is_ok()
+}
+
 pub fn add_follow(conn: &Connection, pubkey: &str) {
     if let Err(e) = conn.execute(
         "INSERT OR IGNORE INTO follows (pubkey) VALUES (?1)",
@@ -311,6 +400,364 @@ pub fn remove_follow(conn: &Connection, pubkey: &str) {
     }
 }
 
+// ── Block list ───────────────────────────────────────────────────────
+// Not a cache: survives logout, unlike `follows`.
+
+/// Oldest block first.
+pub fn load_blocks(conn: &Connection) -> Vec<String> {
+    let mut stmt = match conn.prepare("SELECT pubkey FROM blocks ORDER BY blocked_at, pubkey") {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!(%e, "failed to prepare blocks load query");
+            return vec![];
+        }
+    };
+    let rows = match stmt.query_map([], |row| row.get::<_, String>(0)) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(%e, "failed to query blocks");
+            return vec![];
+        }
+    };
+    rows.flatten().collect()
+}
+
+pub fn add_block(conn: &Connection, pubkey: &str, blocked_at: i64) {
+    if let Err(e) = conn.execute(
+        "INSERT OR IGNORE INTO blocks (pubkey, blocked_at) VALUES (?1, ?2)",
+        rusqlite::params![pubkey, blocked_at],
+    ) {
+        tracing::warn!(%e, pubkey, "failed to save block");
+    }
+}
+
+pub fn remove_block(conn: &Connection, pubkey: &str) {
+    if let Err(e) = conn.execute("DELETE FROM blocks WHERE pubkey = ?1", [pubkey]) {
+        tracing::warn!(%e, pubkey, "failed to remove block");
+    }
+}
+
+/// Whether the block list is mirrored to our encrypted mute list on relays.
+/// Off unless the user opts in.
+pub fn load_block_list_sync(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'block_list_sync'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|value| matches!(value.as_str(), "1" | "true" | "TRUE"))
+    .unwrap_or(false)
+}
+
+pub fn save_block_list_sync(conn: &Connection, enabled: bool) {
+    let value = if enabled { "1" } else { "0" };
+    if let Err(e) = conn.execute(
+        "INSERT INTO app_settings (key, value)
+         VALUES ('block_list_sync', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [value],
+    ) {
+        tracing::warn!(%e, enabled, "failed to save block list sync setting");
+    }
+}
+
+/// Whether a local block list change has not reached relays yet.
+pub fn load_block_list_dirty(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'block_list_dirty'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|value| matches!(value.as_str(), "1" | "true" | "TRUE"))
+    .unwrap_or(false)
+}
+
+pub fn save_block_list_dirty(conn: &Connection, dirty: bool) {
+    let value = if dirty { "1" } else { "0" };
+    if let Err(e) = conn.execute(
+        "INSERT INTO app_settings (key, value)
+         VALUES ('block_list_dirty', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [value],
+    ) {
+        tracing::warn!(%e, dirty, "failed to save block list sync state");
+    }
+}
+
+// ── Chat notification modes ──────────────────────────────────────────
+// Also read by the notification service extension, so the encoding is
+// shared: `mode` is "mentions" or "muted", and a muted row with
+// `muted_until` set is a snooze. Chats with no row get every notification.
+
+pub fn load_chat_notification_modes(conn: &Connection) -> HashMap<String, ChatNotificationMode> {
+    let mut stmt =
+        match conn.prepare("SELECT chat_id, mode, muted_until FROM chat_notification_modes") {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::warn!(%e, "failed to prepare chat notification modes query");
+                return HashMap::new();
+            }
+        };
+    let rows = match stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<i64>>(2)?,
+        ))
+    }) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(%e, "failed to query chat notification modes");
+            return HashMap::new();
+        }
+    };
+    rows.flatten()
+        .filter_map(|(chat_id, mode, muted_until)| {
+            let mode = match (mode.as_str(), muted_until) {
+                ("mentions", _) => ChatNotificationMode::MentionsOnly,
+                ("muted", Some(until)) => ChatNotificationMode::MutedUntil { until },
+                ("muted", None) => ChatNotificationMode::Muted,
+                _ => return None,
+            };
+            Some((chat_id, mode))
+        })
+        .collect()
+}
+
+pub fn save_chat_notification_mode(conn: &Connection, chat_id: &str, mode: ChatNotificationMode) {
+    let row = match mode {
+        ChatNotificationMode::All => None,
+        ChatNotificationMode::MentionsOnly => Some(("mentions", None)),
+        ChatNotificationMode::MutedUntil { until } => Some(("muted", Some(until))),
+        ChatNotificationMode::Muted => Some(("muted", None)),
+    };
+    let result = match row {
+        Some((mode, muted_until)) => conn.execute(
+            "INSERT INTO chat_notification_modes (chat_id, mode, muted_until)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(chat_id) DO UPDATE SET mode = excluded.mode,
+                                                muted_until = excluded.muted_until",
+            rusqlite::params![chat_id, mode, muted_until],
+        ),
+        None => conn.execute(
+            "DELETE FROM chat_notification_modes WHERE chat_id = ?1",
+            [chat_id],
+        ),
+    };
+    if let Err(e) = result {
+        tracing::warn!(%e, chat_id, "failed to save chat notification mode");
+    }
+}
+
+// ── Group invite links ───────────────────────────────────────────────
+// Links we minted as an admin, join requests waiting for our approval, and
+// the admins we asked to let us in (so their welcome skips the inbox).
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInviteLinkRow {
+    pub token: String,
+    pub chat_id: String,
+    pub multi_use: bool,
+    pub requires_approval: bool,
+    pub created_at: i64,
+    pub use_count: u32,
+}
+
+fn invite_link_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<GroupInviteLinkRow> {
+    Ok(GroupInviteLinkRow {
+        token: row.get(0)?,
+        chat_id: row.get(1)?,
+        multi_use: row.get(2)?,
+        requires_approval: row.get(3)?,
+        created_at: row.get(4)?,
+        use_count: row.get(5)?,
+    })
+}
+
+pub fn save_group_invite_link(conn: &Connection, link: &GroupInviteLinkRow) {
+    if let Err(e) = conn.execute(
+        "INSERT OR REPLACE INTO group_invite_links
+            (token, chat_id, multi_use, requires_approval, created_at, use_count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![
+            link.token,
+            link.chat_id,
+            link.multi_use,
+            link.requires_approval,
+            link.created_at,
+            link.use_count,
+        ],
+    ) {
+        tracing::warn!(%e, chat_id = %link.chat_id, "failed to save group invite link");
+    }
+}
+
+/// Newest link first.
+pub fn load_group_invite_links(conn: &Connection, chat_id: &str) -> Vec<GroupInviteLinkRow> {
+    let mut stmt = match conn.prepare(
+        "SELECT token, chat_id, multi_use, requires_approval, created_at, use_count
+         FROM group_invite_links
+         WHERE chat_id = ?1
+         ORDER BY created_at DESC, token",
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!(%e, "failed to prepare group invite links query");
+            return vec![];
+        }
+    };
+    let rows = match stmt.query_map([chat_id], invite_link_from_row) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(%e, "failed to query group invite links");
+            return vec![];
+        }
+    };
+    rows.flatten().collect()
+}
+
+pub fn find_group_invite_link(conn: &Connection, token: &str) -> Option<GroupInviteLinkRow> {
+    conn.query_row(
+        "SELECT token, chat_id, multi_use, requires_approval, created_at, use_count
+         FROM group_invite_links
+         WHERE token = ?1",
+        [token],
+        invite_link_from_row,
+    )
+    .ok()
+}
+
+pub fn record_group_invite_link_use(conn: &Connection, token: &str) {
+    if let Err(e) = conn.execute(
+        "UPDATE group_invite_links SET use_count = use_count + 1 WHERE token = ?1",
+        [token],
+    ) {
+        tracing::warn!(%e, "failed to record group invite link use");
+    }
+}
+
+pub fn delete_group_invite_link(conn: &Connection, token: &str) {
+    if let Err(e) = conn.execute("DELETE FROM group_invite_links WHERE token = ?1", [token]) {
+        tracing::warn!(%e, "failed to delete group invite link");
+    }
+}
+
+/// Oldest request first.
+pub fn load_group_join_requests(conn: &Connection, chat_id: &str) -> Vec<(String, i64)> {
+    let mut stmt = match conn.prepare(
+        "SELECT pubkey, requested_at FROM group_join_requests
+         WHERE chat_id = ?1
+         ORDER BY requested_at, pubkey",
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!(%e, "failed to prepare group join requests query");
+            return vec![];
+        }
+    };
+    let rows = match stmt.query_map([chat_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    }) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(%e, "failed to query group join requests");
+            return vec![];
+        }
+    };
+    rows.flatten().collect()
+}
+
+/// `token` is the invite link the request came through; it's only used up
+/// once the requester is actually added.
+pub fn save_group_join_request(
+    conn: &Connection,
+    chat_id: &str,
+    pubkey: &str,
+    token: &str,
+    requested_at: i64,
+) {
+    if let Err(e) = conn.execute(
+        "INSERT OR IGNORE INTO group_join_requests (chat_id, pubkey, token, requested_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![chat_id, pubkey, token, requested_at],
+    ) {
+        tracing::warn!(%e, chat_id, pubkey, "failed to save group join request");
+    }
+}
+
+/// Returns the invite link token of the request, if one was pending.
+pub fn remove_group_join_request(conn: &Connection, chat_id: &str, pubkey: &str) -> Option<String> {
+    match conn.query_row(
+        "DELETE FROM group_join_requests WHERE chat_id = ?1 AND pubkey = ?2 RETURNING token",
+        [chat_id, pubkey],
+        |row| row.get(0),
+    ) {
+        Ok(token) => Some(token),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => {
+            tracing::warn!(%e, chat_id, pubkey, "failed to remove group join request");
+            None
+        }
+    }
+}
+
+/// Records a join request rumor; false if we handled it before. Relays
+/// redeliver gift wraps on every subscribe, so this keeps a declined or
+/// departed requester from coming back. Rows handled before `since` are
+/// dropped; callers ignore requests that old anyway.
+pub fn mark_join_request_handled(conn: &Connection, rumor_id: &str, now: i64, since: i64) -> bool {
+    if let Err(e) = conn.execute(
+        "DELETE FROM handled_join_requests WHERE handled_at < ?1",
+        [since],
+    ) {
+        tracing::warn!(%e, "failed to expire handled join requests");
+    }
+    match conn.execute(
+        "INSERT OR IGNORE INTO handled_join_requests (rumor_id, handled_at) VALUES (?1, ?2)",
+        rusqlite::params![rumor_id, now],
+    ) {
+        Ok(n) => n > 0,
+        Err(e) => {
+            tracing::warn!(%e, rumor_id, "failed to record handled join request");
+            false
+        }
+    }
+}
+
+pub fn save_sent_join_request(conn: &Connection, admin_pubkey: &str, sent_at: i64) {
+    if let Err(e) = conn.execute(
+        "INSERT INTO sent_join_requests (admin_pubkey, sent_at)
+         VALUES (?1, ?2)
+         ON CONFLICT(admin_pubkey) DO UPDATE SET sent_at = excluded.sent_at",
+        rusqlite::params![admin_pubkey, sent_at],
+    ) {
+        tracing::warn!(%e, admin_pubkey, "failed to save sent join request");
+    }
+}
+
+/// Whether we asked `admin_pubkey` to let us in at or after `since`. Older
+/// requests are forgotten along the way.
+pub fn has_sent_join_request(conn: &Connection, admin_pubkey: &str, since: i64) -> bool {
+    if let Err(e) = conn.execute("DELETE FROM sent_join_requests WHERE sent_at < ?1", [since]) {
+        tracing::warn!(%e, "failed to expire sent join requests");
+    }
+    conn.query_row(
+        "SELECT 1 FROM sent_join_requests WHERE admin_pubkey = ?1",
+        [admin_pubkey],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
+pub fn remove_sent_join_request(conn: &Connection, admin_pubkey: &str) {
+    if let Err(e) = conn.execute(
+        "DELETE FROM sent_join_requests WHERE admin_pubkey = ?1",
+        [admin_pubkey],
+    ) {
+        tracing::warn!(%e, admin_pubkey, "failed to remove sent join request");
+    }
+}
+
 // -- Failed sends --
 
 pub fn load_failed_sends(conn: &Connection) -> HashMap<String, String> {
@@ -590,6 +1037,119 @@ mod tests {
 
         remove_follow(&conn, "aaa");
         assert_eq!(load_follows(&conn), vec!["bbb"]);
+        assert!(is_followed(&conn, "bbb"));
+        assert!(!is_followed(&conn, "aaa"));
+    }
+
+    #[test]
+    fn blocks_add_remove_and_survive_clear_all() {
+        let conn = test_db();
+        assert!(load_blocks(&conn).is_empty());
+
+        add_block(&conn, "bbb", 20);
+        add_block(&conn, "aaa", 10);
+        add_block(&conn, "bbb", 30); // duplicate keeps the original time
+        assert_eq!(load_blocks(&conn), vec!["aaa", "bbb"]);
+
+        clear_all(&conn);
+        assert_eq!(load_blocks(&conn), vec!["aaa", "bbb"]);
+
+        remove_block(&conn, "aaa");
+        assert_eq!(load_blocks(&conn), vec!["bbb"]);
+
+        assert!(!load_block_list_sync(&conn));
+        save_block_list_sync(&conn, true);
+        assert!(load_block_list_sync(&conn));
+        assert!(!load_block_list_dirty(&conn));
+        save_block_list_dirty(&conn, true);
+        assert!(load_block_list_dirty(&conn));
+    }
+
+    #[test]
+    fn chat_notification_modes_roundtrip() {
+        let conn = test_db();
+        assert!(load_chat_notification_modes(&conn).is_empty());
+
+        save_chat_notification_mode(&conn, "a", ChatNotificationMode::MentionsOnly);
+        save_chat_notification_mode(&conn, "b", ChatNotificationMode::MutedUntil { until: 500 });
+        save_chat_notification_mode(&conn, "c", ChatNotificationMode::Muted);
+        let modes = load_chat_notification_modes(&conn);
+        assert_eq!(modes.len(), 3);
+        assert_eq!(modes["a"], ChatNotificationMode::MentionsOnly);
+        assert_eq!(modes["b"], ChatNotificationMode::MutedUntil { until: 500 });
+        assert_eq!(modes["c"], ChatNotificationMode::Muted);
+
+        // Changing mode replaces the row; `All` removes it.
+        save_chat_notification_mode(&conn, "b", ChatNotificationMode::Muted);
+        save_chat_notification_mode(&conn, "a", ChatNotificationMode::All);
+        let modes = load_chat_notification_modes(&conn);
+        assert_eq!(modes.len(), 2);
+        assert_eq!(modes["b"], ChatNotificationMode::Muted);
+    }
+
+    #[test]
+    fn group_invite_links_and_join_requests_roundtrip() {
+        let conn = test_db();
+        let link = |token: &str, created_at: i64| GroupInviteLinkRow {
+            token: token.into(),
+            chat_id: "chat1".into(),
+            multi_use: true,
+            requires_approval: false,
+            created_at,
+            use_count: 0,
+        };
+        save_group_invite_link(&conn, &link("t1", 10));
+        save_group_invite_link(&conn, &link("t2", 20));
+        let tokens: Vec<String> = load_group_invite_links(&conn, "chat1")
+            .into_iter()
+            .map(|l| l.token)
+            .collect();
+        assert_eq!(tokens, vec!["t2", "t1"]);
+        assert!(load_group_invite_links(&conn, "chat2").is_empty());
+
+        record_group_invite_link_use(&conn, "t1");
+        assert_eq!(find_group_invite_link(&conn, "t1").unwrap().use_count, 1);
+        delete_group_invite_link(&conn, "t1");
+        assert_eq!(find_group_invite_link(&conn, "t1"), None);
+
+        save_group_join_request(&conn, "chat1", "bbb", "t2", 30);
+        save_group_join_request(&conn, "chat1", "aaa", "t2", 20);
+        save_group_join_request(&conn, "chat1", "bbb", "t3", 40); // duplicate keeps the first
+        assert_eq!(
+            load_group_join_requests(&conn, "chat1"),
+            vec![("aaa".to_string(), 20), ("bbb".to_string(), 30)]
+        );
+        assert_eq!(
+            remove_group_join_request(&conn, "chat1", "aaa").as_deref(),
+            Some("t2")
+        );
+        assert_eq!(remove_group_join_request(&conn, "chat1", "aaa"), None);
+
+        clear_all(&conn);
+        assert_eq!(load_group_invite_links(&conn, "chat1").len(), 1);
+        assert_eq!(load_group_join_requests(&conn, "chat1").len(), 1);
+    }
+
+    #[test]
+    fn handled_join_requests_dedupe_until_expired() {
+        let conn = test_db();
+        assert!(mark_join_request_handled(&conn, "r1", 10, 0));
+        assert!(!mark_join_request_handled(&conn, "r1", 20, 0));
+        assert!(mark_join_request_handled(&conn, "r2", 30, 15));
+        assert!(mark_join_request_handled(&conn, "r1", 40, 15));
+    }
+
+    #[test]
+    fn sent_join_requests_expire() {
+        let conn = test_db();
+        save_sent_join_request(&conn, "old", 10);
+        save_sent_join_request(&conn, "new", 100);
+        assert!(has_sent_join_request(&conn, "new", 50));
+        assert!(!has_sent_join_request(&conn, "old", 50));
+        save_sent_join_request(&conn, "old", 60);
+        assert!(has_sent_join_request(&conn, "old", 50));
+        remove_sent_join_request(&conn, "old");
+        assert!(!has_sent_join_request(&conn, "old", 0));
     }
 
     #[test]
@@ -604,6 +1164,29 @@ mod tests {
         assert!(!load_developer_mode(&conn));
     }
 
+    #[test]
+    fn read_receipts_roundtrip() {
+        let conn = test_db();
+        assert!(!load_read_receipts(&conn));
+
+        save_read_receipts(&conn, true);
+        assert!(load_read_receipts(&conn));
+        assert!(!load_developer_mode(&conn));
+
+        save_read_receipts(&conn, false);
+        assert!(!load_read_receipts(&conn));
+    }
+
+    #[test]
+    fn auto_accept_followed_invites_defaults_on() {
+        let conn = test_db();
+        assert!(load_auto_accept_followed_invites(&conn));
+
+        save_auto_accept_followed_invites(&conn, false);
+        assert!(!load_auto_accept_followed_invites(&conn));
+        assert!(!load_read_receipts(&conn));
+    }
+
     #[test]
     fn failed_sends_roundtrip() {
         let conn = test_db();
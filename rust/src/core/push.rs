@@ -94,11 +94,17 @@ impl AppCore {
             return;
         }
 
+        // The device gets pushes for every account on it, not just the active one.
+        // Chats muted for good are left out; every other mode is filtered by the NSE.
+        let muted: HashSet<&String> = self.muted_chat_ids().collect();
         let current_ids: HashSet<String> = self
             .state
             .chat_list
             .iter()
-            .map(|c| c.chat_id.clone())
+            .map(|c| &c.chat_id)
+            .filter(|id| !muted.contains(id))
+            .chain(self.inactive_account_chat_ids())
+            .cloned()
             .collect();
 
         let to_subscribe: Vec<String> = current_ids
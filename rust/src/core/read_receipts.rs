@@ -0,0 +1,203 @@
+// Read receipts: ephemeral "read up to here" markers exchanged in-group, kept
+// as the newest marker per reader so each message can list who has seen it.
+
+use crate::state::MessageReader;
+
+use super::*;
+
+/// Newest message a member has told us they read in a chat.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(super) struct ReadMarker {
+    pub(super) message_id: String,
+    pub(super) message_ts: i64,
+    pub(super) read_at: i64,
+}
+
+impl AppCore {
+    fn read_markers_path(&self) -> std::path::PathBuf {
+        std::path::Path::new(&self.account_dir).join("read_receipts.json")
+    }
+
+    pub(super) fn load_read_markers(&mut self) {
+        let path = self.read_markers_path();
+        self.read_markers = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+    }
+
+    fn save_read_markers(&self) {
+        let path = self.read_markers_path();
+        if let Ok(json) = serde_json::to_string(&self.read_markers) {
+            let _ = std::fs::write(&path, json);
+        }
+    }
+
+    pub(super) fn set_read_receipts_enabled(&mut self, enabled: bool) {
+        if self.state.read_receipts_enabled == enabled {
+            return;
+        }
+        self.state.read_receipts_enabled = enabled;
+        if let Some(conn) = self.profile_db.as_ref() {
+            profile_db::save_read_receipts(conn, enabled);
+        }
+        self.emit_state();
+        if let Some(chat_id) = self.state.current_chat.as_ref().map(|c| c.chat_id.clone()) {
+            self.send_read_receipt_if_needed(&chat_id);
+        }
+    }
+
+    /// Apply a receipt received from the group. Returns `true` when it moved the
+    /// sender's marker forward.
+    pub(super) fn apply_read_receipt(
+        &mut self,
+        chat_id: &str,
+        msg: &message_types::Message,
+    ) -> bool {
+        let Some(target_hex) = storage::first_event_tag_id(&msg.tags) else {
+            tracing::warn!(chat_id, "ignoring read receipt without target");
+            return false;
+        };
+        let Ok(target_id) = EventId::from_hex(&target_hex) else {
+            return false;
+        };
+        // Markers are positioned by the target's own timestamp; a receipt for a
+        // message we don't have can't be placed, so drop it.
+        let message_ts = {
+            let Some(sess) = self.session.as_ref() else {
+                return false;
+            };
+            let Some(group) = sess.groups.get(chat_id) else {
+                return false;
+            };
+            match sess.mdk.get_message(&group.mls_group_id, &target_id) {
+                Ok(Some(target)) => target.created_at.as_secs() as i64,
+                Ok(None) => return false,
+                Err(e) => {
+                    tracing::warn!(%e, chat_id, "read receipt target lookup failed");
+                    return false;
+                }
+            }
+        };
+        self.record_read_marker(
+            chat_id,
+            &msg.pubkey.to_hex(),
+            ReadMarker {
+                message_id: target_hex,
+                message_ts,
+                read_at: msg.created_at.as_secs() as i64,
+            },
+        )
+    }
+
+    fn record_read_marker(&mut self, chat_id: &str, reader_hex: &str, marker: ReadMarker) -> bool {
+        let readers = self.read_markers.entry(chat_id.to_string()).or_default();
+        // Receipts can arrive out of order; only ever move a marker forward.
+        if readers
+            .get(reader_hex)
+            .is_some_and(|current| current.message_ts >= marker.message_ts)
+        {
+            return false;
+        }
+        readers.insert(reader_hex.to_string(), marker);
+        self.save_read_markers();
+        true
+    }
+
+    /// Tell the group we've read up to the newest incoming message of the open
+    /// chat. No-op when receipts are off or nothing new has been read.
+    pub(super) fn send_read_receipt_if_needed(&mut self, chat_id: &str) {
+        if !self.state.read_receipts_enabled {
+            return;
+        }
+        let Some(chat) = self
+            .state
+            .current_chat
+            .as_ref()
+            .filter(|c| c.chat_id == chat_id)
+        else {
+            return;
+        };
+        let Some(newest) = chat.messages.iter().rev().find(|m| !m.is_mine) else {
+            return;
+        };
+        let (target_hex, message_ts) = (newest.id.clone(), newest.timestamp);
+        let Ok(target_event_id) = EventId::from_hex(&target_hex) else {
+            return;
+        };
+        let Some(my_hex) = self.session.as_ref().map(|s| s.pubkey.to_hex()) else {
+            return;
+        };
+        if self
+            .read_markers
+            .get(chat_id)
+            .and_then(|readers| readers.get(&my_hex))
+            .is_some_and(|mine| mine.message_ts >= message_ts)
+        {
+            return;
+        }
+
+        let created_at = Timestamp::now();
+        let prepared = match self.prepare_outbound_action_for_chat(
+            chat_id,
+            OutboundConversationAction::ReadReceipt {
+                target_event_id,
+                created_at,
+            },
+        ) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::warn!(err = %e, "read receipt create_message failed");
+                return;
+            }
+        };
+        let Some(client) = self.session.as_ref().map(|s| s.client.clone()) else {
+            return;
+        };
+        self.runtime.spawn(async move {
+            let _ = client.send_event(&prepared.wrapper).await;
+        });
+
+        self.record_read_marker(
+            chat_id,
+            &my_hex,
+            ReadMarker {
+                message_id: target_hex,
+                message_ts,
+                read_at: created_at.as_secs() as i64,
+            },
+        );
+    }
+
+    /// Members (other than the author and us) whose marker is at or past `message`.
+    pub(super) fn message_readers(
+        &self,
+        chat_id: &str,
+        message: &ChatMessage,
+        my_pubkey_hex: &str,
+        sender_names: &HashMap<String, String>,
+    ) -> Vec<MessageReader> {
+        let Some(readers) = self.read_markers.get(chat_id) else {
+            return vec![];
+        };
+        let mut seen_by: Vec<MessageReader> = readers
+            .iter()
+            .filter(|(reader, marker)| {
+                reader.as_str() != message.sender_pubkey
+                    && reader.as_str() != my_pubkey_hex
+                    && marker.message_ts >= message.timestamp
+            })
+            .map(|(reader, marker)| MessageReader {
+                pubkey: reader.clone(),
+                name: sender_names.get(reader).cloned(),
+                read_at: marker.read_at,
+            })
+            .collect();
+        seen_by.sort_by(|a, b| {
+            a.read_at
+                .cmp(&b.read_at)
+                .then_with(|| a.pubkey.cmp(&b.pubkey))
+        });
+        seen_by
+    }
+}
@@ -0,0 +1,207 @@
+// Local message search: an encrypted FTS5 index per account, fed from live
+// traffic and backfilled from MDK storage for chats it hasn't seen yet.
+
+use crate::state::{MessageSearchResult, MessageSearchState};
+
+use super::*;
+
+const SEARCH_BACKFILL_PAGE: usize = 200;
+const SEARCH_RESULT_LIMIT: usize = 50;
+
+impl AppCore {
+    pub(super) fn open_search_index(&mut self, pubkey_hex: &str) {
+        let key = match crate::mdk_support::search_db_key(
+            &self.data_dir,
+            pubkey_hex,
+            &self.keychain_group,
+        ) {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::warn!(%e, "failed to load search index key");
+                self.search_db = None;
+                return;
+            }
+        };
+        let path = crate::mdk_support::search_db_path(&self.data_dir, pubkey_hex);
+        self.search_db = match search_db::open_search_db(&path, &key) {
+            Ok(conn) => Some(conn),
+            Err(e) => {
+                tracing::warn!(%e, "failed to open search index");
+                None
+            }
+        };
+    }
+
+    /// Index every chat that has no rows yet. Runs once per chat; after that the
+    /// index is kept current by `index_message_for_search`.
+    pub(super) fn backfill_search_index(&self) {
+        let (Some(sess), Some(conn)) = (self.session.as_ref(), self.search_db.as_ref()) else {
+            return;
+        };
+        let host = sess.host_context();
+        for chat_id in sess.groups.keys() {
+            if search_db::is_chat_indexed(conn, chat_id) {
+                continue;
+            }
+            let mut messages = Vec::new();
+            let mut offset = 0;
+            loop {
+                let batch = match host.get_messages(
+                    chat_id,
+                    Some(Pagination::new(Some(SEARCH_BACKFILL_PAGE), Some(offset))),
+                ) {
+                    Ok(batch) => batch,
+                    Err(e) => {
+                        tracing::warn!(err = %e, chat_id, "search backfill get_messages failed");
+                        break;
+                    }
+                };
+                let len = batch.len();
+                messages.extend(batch);
+                if len < SEARCH_BACKFILL_PAGE {
+                    break;
+                }
+                offset += len;
+            }
+
+            // Originals first, then edits/deletions oldest to newest, so each
+            // revision finds its target and the latest edit wins.
+            let mut entries: Vec<(AppMessageKind, &message_types::Message)> = messages
+                .iter()
+                .filter_map(|m| classify_app_message(m).map(|kind| (kind, m)))
+                .collect();
+            entries.sort_by_key(|(kind, m)| {
+                (
+                    matches!(kind, AppMessageKind::Edit | AppMessageKind::Deletion),
+                    m.created_at,
+                )
+            });
+
+            let result = conn.unchecked_transaction().and_then(|tx| {
+                for (kind, m) in &entries {
+                    index_rumor(&tx, chat_id, *kind, &m.id.to_hex(), &m.event)?;
+                }
+                search_db::mark_chat_indexed(&tx, chat_id)?;
+                tx.commit()
+            });
+            if let Err(e) = result {
+                tracing::warn!(%e, chat_id, "search backfill failed");
+            }
+        }
+    }
+
+    pub(super) fn index_message_for_search(
+        &self,
+        chat_id: &str,
+        kind: AppMessageKind,
+        msg: &message_types::Message,
+    ) {
+        // Best-effort: a search index failure must never block message handling.
+        let Some(conn) = self.search_db.as_ref() else {
+            return;
+        };
+        if let Err(e) = index_rumor(conn, chat_id, kind, &msg.id.to_hex(), &msg.event) {
+            tracing::warn!(%e, chat_id, "failed to update search index");
+        }
+    }
+
+    /// Index a rumor we just created locally (our own sends, edits, deletions);
+    /// those never come back through `handle_runtime_application_message`.
+    pub(super) fn index_stored_message_for_search(&self, chat_id: &str, rumor_id: &EventId) {
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let Some(group) = sess.groups.get(chat_id) else {
+            return;
+        };
+        match sess.mdk.get_message(&group.mls_group_id, rumor_id) {
+            Ok(Some(msg)) => {
+                if let Some(kind) = classify_app_message(&msg) {
+                    self.index_message_for_search(chat_id, kind, &msg);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(%e, chat_id, "search index lookup failed"),
+        }
+    }
+
+    pub(super) fn search_messages(&mut self, query: String, chat_id: Option<String>) {
+        let hits = match self.search_db.as_ref() {
+            Some(conn) => search_db::search(conn, &query, chat_id.as_deref(), SEARCH_RESULT_LIMIT),
+            None => vec![],
+        };
+
+        let Some(sess) = self.session.as_ref() else {
+            return;
+        };
+        let my_pubkey_hex = sess.pubkey.to_hex();
+        let mut sender_names: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut results = Vec::with_capacity(hits.len());
+        for hit in hits {
+            // Chats we've left stay in the index until the next wipe; hide them.
+            let Some(group) = sess.groups.get(&hit.chat_id) else {
+                continue;
+            };
+            if self.is_blocked(&hit.sender_pubkey) {
+                continue;
+            }
+            let names = sender_names.entry(hit.chat_id.clone()).or_insert_with(|| {
+                self.build_sender_names(&hit.chat_id, &group.members, &my_pubkey_hex)
+            });
+            let chat_name = self
+                .state
+                .chat_list
+                .iter()
+                .find(|c| c.chat_id == hit.chat_id)
+                .map(|c| c.display_name.clone())
+                .unwrap_or_default();
+            results.push(MessageSearchResult {
+                sender_name: names.get(&hit.sender_pubkey).cloned(),
+                chat_id: hit.chat_id,
+                chat_name,
+                message_id: hit.message_id,
+                sender_pubkey: hit.sender_pubkey,
+                snippet: hit.snippet,
+                timestamp: hit.created_at,
+            });
+        }
+
+        self.state.message_search = Some(MessageSearchState {
+            query,
+            chat_id,
+            results,
+        });
+        self.emit_state();
+    }
+}
+
+fn index_rumor(
+    conn: &rusqlite::Connection,
+    chat_id: &str,
+    kind: AppMessageKind,
+    message_id: &str,
+    rumor: &UnsignedEvent,
+) -> rusqlite::Result<()> {
+    let sender = rumor.pubkey.to_hex();
+    match kind {
+        AppMessageKind::Chat | AppMessageKind::Hypernote => search_db::index_message(
+            conn,
+            &search_db::SearchRecord {
+                message_id: message_id.to_string(),
+                chat_id: chat_id.to_string(),
+                sender_pubkey: sender,
+                content: rumor.content.clone(),
+                created_at: rumor.created_at.as_secs() as i64,
+            },
+        ),
+        AppMessageKind::Edit => match storage::first_event_tag_id(&rumor.tags) {
+            Some(target) => search_db::apply_edit(conn, &target, &sender, &rumor.content),
+            None => Ok(()),
+        },
+        AppMessageKind::Deletion => match storage::first_event_tag_id(&rumor.tags) {
+            Some(target) => search_db::apply_deletion(conn, &target, &sender),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,296 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// One indexed chat message. `content` is the latest text from the author.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SearchRecord {
+    pub(super) message_id: String,
+    pub(super) chat_id: String,
+    pub(super) sender_pubkey: String,
+    pub(super) content: String,
+    pub(super) created_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SearchHit {
+    pub(super) message_id: String,
+    pub(super) chat_id: String,
+    pub(super) sender_pubkey: String,
+    pub(super) snippet: String,
+    pub(super) created_at: i64,
+}
+
+/// Open (or create) the SQLCipher-encrypted FTS5 index at `path`.
+pub(super) fn open_search_db(path: &Path, key: &[u8; 32]) -> rusqlite::Result<Connection> {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let conn = Connection::open(path)?;
+    conn.execute_batch(&format!("PRAGMA key = \"x'{}'\";", hex::encode(key)))?;
+    conn.execute_batch(
+        r#"
+        PRAGMA journal_mode=WAL;
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS message_fts USING fts5(
+            content,
+            message_id UNINDEXED,
+            chat_id UNINDEXED,
+            sender_pubkey UNINDEXED,
+            created_at UNINDEXED,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );
+
+        CREATE TABLE IF NOT EXISTS indexed_chats (
+            chat_id TEXT PRIMARY KEY NOT NULL
+        );
+        "#,
+    )?;
+    Ok(conn)
+}
+
+/// Insert or replace the row for `record.message_id`.
+pub(super) fn index_message(conn: &Connection, record: &SearchRecord) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM message_fts WHERE message_id = ?1",
+        params![record.message_id],
+    )?;
+    if record.content.trim().is_empty() {
+        return Ok(());
+    }
+    conn.execute(
+        r#"
+        INSERT INTO message_fts (content, message_id, chat_id, sender_pubkey, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        "#,
+        params![
+            record.content,
+            record.message_id,
+            record.chat_id,
+            record.sender_pubkey,
+            record.created_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Replace the indexed text of a message. Only the original author's edits apply.
+pub(super) fn apply_edit(
+    conn: &Connection,
+    message_id: &str,
+    sender_pubkey: &str,
+    content: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE message_fts SET content = ?3 WHERE message_id = ?1 AND sender_pubkey = ?2",
+        params![message_id, sender_pubkey, content],
+    )?;
+    Ok(())
+}
+
+/// Drop a message authored by `sender_pubkey` (author deletions).
+pub(super) fn apply_deletion(
+    conn: &Connection,
+    message_id: &str,
+    sender_pubkey: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM message_fts WHERE message_id = ?1 AND sender_pubkey = ?2",
+        params![message_id, sender_pubkey],
+    )?;
+    Ok(())
+}
+
+/// Drop a message regardless of author (local expiry).
+pub(super) fn remove_message(conn: &Connection, message_id: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM message_fts WHERE message_id = ?1",
+        params![message_id],
+    )?;
+    Ok(())
+}
+
+pub(super) fn is_chat_indexed(conn: &Connection, chat_id: &str) -> bool {
+    conn.query_row(
+        "SELECT 1 FROM indexed_chats WHERE chat_id = ?1",
+        params![chat_id],
+        |_| Ok(()),
+    )
+    .optional()
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+pub(super) fn mark_chat_indexed(conn: &Connection, chat_id: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO indexed_chats (chat_id) VALUES (?1)",
+        params![chat_id],
+    )?;
+    Ok(())
+}
+
+/// Turn free-form user input into an FTS5 query: every whitespace-separated
+/// term becomes a quoted prefix match, so punctuation can't produce syntax errors.
+pub(super) fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| term.replace('"', "\"\""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\"*"))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Best matches first; newer messages break ties.
+pub(super) fn search(
+    conn: &Connection,
+    query: &str,
+    chat_id: Option<&str>,
+    limit: usize,
+) -> Vec<SearchHit> {
+    let Some(match_expr) = fts_query(query) else {
+        return vec![];
+    };
+    let mut stmt = match conn.prepare(
+        r#"
+        SELECT
+            message_id,
+            chat_id,
+            sender_pubkey,
+            snippet(message_fts, 0, '', '', '…', 12),
+            created_at
+        FROM message_fts
+        WHERE message_fts MATCH ?1 AND (?2 IS NULL OR chat_id = ?2)
+        ORDER BY rank, created_at DESC
+        LIMIT ?3
+        "#,
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!(%e, "failed to prepare message search query");
+            return vec![];
+        }
+    };
+
+    let rows = match stmt.query_map(params![match_expr, chat_id, limit as i64], |row| {
+        Ok(SearchHit {
+            message_id: row.get(0)?,
+            chat_id: row.get(1)?,
+            sender_pubkey: row.get(2)?,
+            snippet: row.get(3)?,
+            created_at: row.get(4)?,
+        })
+    }) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(%e, "failed to run message search query");
+            return vec![];
+        }
+    };
+
+    rows.filter_map(|r| r.ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7u8; 32];
+
+    fn record(message_id: &str, chat_id: &str, sender: &str, content: &str) -> SearchRecord {
+        SearchRecord {
+            message_id: message_id.to_string(),
+            chat_id: chat_id.to_string(),
+            sender_pubkey: sender.to_string(),
+            content: content.to_string(),
+            created_at: 100,
+        }
+    }
+
+    fn open_temp() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let conn = open_search_db(&dir.path().join("search.sqlite3"), &KEY).expect("open db");
+        (dir, conn)
+    }
+
+    #[test]
+    fn finds_messages_by_prefix_and_scopes_by_chat() {
+        let (_dir, conn) = open_temp();
+        index_message(&conn, &record("m1", "chat-a", "alice", "Lunch at noon?")).unwrap();
+        index_message(&conn, &record("m2", "chat-b", "bob", "lunchbox forgotten")).unwrap();
+
+        let all = search(&conn, "lunch", None, 10);
+        assert_eq!(all.len(), 2);
+
+        let scoped = search(&conn, "lunch", Some("chat-a"), 10);
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].message_id, "m1");
+    }
+
+    #[test]
+    fn reindexing_replaces_instead_of_duplicating() {
+        let (_dir, conn) = open_temp();
+        index_message(&conn, &record("m1", "chat-a", "alice", "hello world")).unwrap();
+        index_message(&conn, &record("m1", "chat-a", "alice", "hello world")).unwrap();
+
+        assert_eq!(search(&conn, "hello", None, 10).len(), 1);
+    }
+
+    #[test]
+    fn edits_and_deletions_only_apply_to_author() {
+        let (_dir, conn) = open_temp();
+        index_message(&conn, &record("m1", "chat-a", "alice", "original text")).unwrap();
+
+        apply_edit(&conn, "m1", "mallory", "hijacked").unwrap();
+        assert!(search(&conn, "hijacked", None, 10).is_empty());
+
+        apply_edit(&conn, "m1", "alice", "revised text").unwrap();
+        assert!(search(&conn, "original", None, 10).is_empty());
+        assert_eq!(search(&conn, "revised", None, 10).len(), 1);
+
+        apply_deletion(&conn, "m1", "mallory").unwrap();
+        assert_eq!(search(&conn, "revised", None, 10).len(), 1);
+        apply_deletion(&conn, "m1", "alice").unwrap();
+        assert!(search(&conn, "revised", None, 10).is_empty());
+    }
+
+    #[test]
+    fn punctuation_in_query_does_not_error() {
+        let (_dir, conn) = open_temp();
+        index_message(
+            &conn,
+            &record("m1", "chat-a", "alice", "what's \"up\" (now)"),
+        )
+        .unwrap();
+
+        assert_eq!(search(&conn, "what's", None, 10).len(), 1);
+        assert!(search(&conn, "\"( OR", None, 10).is_empty());
+        assert!(search(&conn, "   ", None, 10).is_empty());
+    }
+
+    #[test]
+    fn indexed_chats_are_tracked() {
+        let (_dir, conn) = open_temp();
+        assert!(!is_chat_indexed(&conn, "chat-a"));
+        mark_chat_indexed(&conn, "chat-a").unwrap();
+        mark_chat_indexed(&conn, "chat-a").unwrap();
+        assert!(is_chat_indexed(&conn, "chat-a"));
+        assert!(!is_chat_indexed(&conn, "chat-b"));
+    }
+
+    #[test]
+    fn database_cannot_be_read_with_wrong_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("search.sqlite3");
+        {
+            let conn = open_search_db(&path, &KEY).expect("open db");
+            index_message(&conn, &record("m1", "chat-a", "alice", "secret")).unwrap();
+        }
+        assert!(open_search_db(&path, &[9u8; 32]).is_err());
+    }
+}
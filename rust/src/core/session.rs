@@ -38,7 +38,8 @@ impl AppCore {
         local_keys: Option<Keys>,
         auth_mode: SessionAuthMode,
     ) -> anyhow::Result<()> {
-        // Tear down any existing session first.
+        // Tear down any existing session first, keeping its account around.
+        self.park_active_account();
         self.stop_session();
 
         // Ensure profile pics directory exists (may have been cleared on logout).
@@ -49,9 +50,18 @@ impl AppCore {
 
         tracing::info!(pubkey = %pubkey_hex, npub = %npub, "start_session");
 
+        // The account's background watcher holds its MLS storage open.
+        self.stop_background_account(&pubkey_hex);
+        self.remember_account_signer(
+            &pubkey_hex,
+            signer.clone(),
+            local_keys.clone(),
+            auth_mode.clone(),
+        );
         let bootstrapped =
             bootstrap_runtime_for_app(&self.data_dir, &self.keychain_group, pubkey, signer)?;
         tracing::info!("mdk opened");
+        self.activate_account(&pubkey_hex);
 
         if self.network_enabled() {
             let relays = self.all_session_relays();
@@ -75,6 +85,7 @@ impl AppCore {
         };
 
         self.session = Some(sess);
+        self.open_search_index(&pubkey_hex);
 
         self.state.auth = AuthState::LoggedIn {
             npub,
@@ -97,7 +108,12 @@ impl AppCore {
         // cached picture URLs will be present from the first emission.
         self.load_archived_chats();
         self.load_call_timeline();
+        self.load_disappearing_timers();
+        self.load_chat_notification_modes();
+        self.load_read_markers();
         self.refresh_all_from_storage();
+        self.purge_expired_messages();
+        self.sync_background_accounts();
 
         // Defer remaining init work so any user actions that queued while the
         // actor was busy (e.g. chat taps during loading) are processed first.
@@ -145,6 +161,12 @@ impl AppCore {
             .screen_stack
             .retain(|s| !matches!(s, Screen::AgentProvisioning));
         self.group_profiles.clear();
+        self.disappearing_purge_timer.cancel();
+        self.disappearing_purged_through.clear();
+        self.chat_notification_modes.clear();
+        self.chat_snooze_timer.cancel();
+        self.search_db = None;
+        self.state.message_search = None;
 
         if let Some(sess) = self.session.take() {
             sess.alive.store(false, Ordering::SeqCst);
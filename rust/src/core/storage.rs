@@ -11,7 +11,7 @@ impl AppCore {
     /// Build a sender pubkey → display name lookup from member info + profile cache,
     /// including the current user's name for mention resolution.
     /// Uses group profile if one exists (all-or-nothing), otherwise global.
-    fn build_sender_names(
+    pub(super) fn build_sender_names(
         &self,
         chat_id: &str,
         members: &[super::GroupMember],
@@ -117,6 +117,8 @@ impl AppCore {
     pub(super) fn refresh_chat_list_from_storage(&mut self) {
         let Some(sess) = self.session.as_ref() else {
             self.state.chat_list = vec![];
+            self.state.invitations = vec![];
+            self.refresh_account_summaries();
             self.emit_chat_list();
             return;
         };
@@ -140,6 +142,11 @@ impl AppCore {
             if self.archived_chats.contains(&chat_id) {
                 continue;
             }
+            // Staged welcomes leave a pending group behind; it stays in
+            // `invitations` until the user accepts it.
+            if g.state == mdk_storage_traits::groups::types::GroupState::Pending {
+                continue;
+            }
 
             // Get all members except self.
             let all_members: BTreeSet<PublicKey> =
@@ -214,18 +221,18 @@ impl AppCore {
             // Do not rely on `last_message_id` being populated in all MDK flows.
             // For MVP scale, fetching the newest message per group is cheap and robust.
             // Signal/control messages share the MLS app-message path; skip them in chat previews.
-            let newest = sess
+            let recent = sess
                 .mdk
                 .get_messages(&g.mls_group_id, Some(Pagination::new(Some(20), Some(0))))
-                .ok()
-                .and_then(|v| {
-                    v.into_iter()
-                        .find(|m| m.kind == Kind::ChatMessage || m.kind == super::HYPERNOTE_KIND)
-                });
+                .unwrap_or_default();
+            let newest = recent.iter().find(|m| {
+                (m.kind == Kind::ChatMessage || m.kind == super::HYPERNOTE_KIND)
+                    && m.state != message_types::MessageState::Deleted
+                    && !self.is_blocked(&m.pubkey.to_hex())
+            });
 
-            let stored_last_message = newest.as_ref().map(|m| m.content.clone());
+            let stored_last_message = newest.map(|m| preview_content(m, &recent));
             let stored_last_message_at = newest
-                .as_ref()
                 .map(|m| m.created_at.as_secs() as i64)
                 .or_else(|| g.last_message_at.map(|t| t.as_secs() as i64));
 
@@ -288,6 +295,7 @@ impl AppCore {
                 Some(msg) => msg.clone(),
             };
 
+            let notification_mode = self.chat_notification_mode(&chat_id);
             list.push(ChatSummary {
                 chat_id: chat_id.clone(),
                 is_group,
@@ -299,6 +307,9 @@ impl AppCore {
                 subtitle,
                 last_message_preview,
                 unread_count,
+                disappearing_timer_secs: self.disappearing_timer_secs(&chat_id),
+                notification_mode,
+                is_muted: notification_mode.is_muted(),
             });
 
             index.insert(
@@ -318,15 +329,21 @@ impl AppCore {
             sess.groups = index;
         }
         self.state.chat_list = list;
+        self.refresh_invitations();
+        self.refresh_account_summaries();
         self.emit_chat_list();
         self.sync_push_subscriptions();
 
-        // Fetch missing profiles asynchronously.
-        if !missing_profile_pubkeys.is_empty() && self.network_enabled() {
+        self.fetch_missing_profiles(missing_profile_pubkeys);
+    }
+
+    /// Fetch kind:0 profiles for `pubkeys` in the background; results arrive as
+    /// `ProfilesFetched`.
+    pub(super) fn fetch_missing_profiles(&self, pubkeys: HashSet<PublicKey>) {
+        if !pubkeys.is_empty() && self.network_enabled() {
             if let Some(sess) = self.session.as_ref() {
                 let client = sess.client.clone();
                 let tx = self.core_sender.clone();
-                let pubkeys = missing_profile_pubkeys;
                 self.runtime.spawn(async move {
                     let filter = Filter::new()
                         .authors(pubkeys.clone())
@@ -450,7 +467,8 @@ impl AppCore {
             visible_messages.extend(
                 batch
                     .into_iter()
-                    .filter(|m| classify_app_message(m).is_some_and(|k| k.is_chat_visible())),
+                    .filter(|m| classify_app_message(m).is_some_and(|k| k.is_chat_visible()))
+                    .filter(|m| !self.is_blocked(&m.pubkey.to_hex())),
             );
             if batch_len < target || visible_messages.len() >= target {
                 break;
@@ -491,6 +509,8 @@ impl AppCore {
                     &my_pubkey_hex,
                     &m.tags,
                 );
+                apply_message_revisions(&mut cm, &separated.revisions, &sender_names);
+                cm.seen_by = self.message_readers(chat_id, &cm, &my_pubkey_hex, &sender_names);
                 cm
             })
             .collect();
@@ -509,6 +529,14 @@ impl AppCore {
                         continue;
                     };
                     let hash = hex::encode(reference.original_hash);
+                    // Restore inline posters even for known media; the fast path points at them.
+                    super::chat_media::cache_imeta_thumbnail(
+                        &self.data_dir,
+                        &my_pubkey_hex,
+                        chat_id,
+                        &hash,
+                        tag,
+                    );
                     if let std::collections::hash_map::Entry::Vacant(e) =
                         media_cache_entry.entry(hash)
                     {
@@ -604,6 +632,9 @@ impl AppCore {
                     segments,
                     html_state: None,
                     hypernote: None,
+                    edited_at: None,
+                    deleted: false,
+                    seen_by: vec![],
                 });
             }
             msgs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
@@ -661,6 +692,15 @@ impl AppCore {
                 picture_url: p.display_group_picture_url(&self.data_dir, chat_id, &my_pubkey_hex),
             });
 
+        let (invite_links, join_requests) = if is_admin {
+            (
+                self.group_invite_links(chat_id),
+                self.group_join_requests(chat_id),
+            )
+        } else {
+            (vec![], vec![])
+        };
+
         self.state.current_chat = Some(ChatViewState {
             chat_id: chat_id.to_string(),
             is_group: entry.is_group,
@@ -672,6 +712,10 @@ impl AppCore {
             can_load_older,
             typing_members: typing,
             my_group_profile,
+            disappearing_timer_secs: self.disappearing_timer_secs(chat_id),
+            notification_mode: self.chat_notification_mode(chat_id),
+            invite_links,
+            join_requests,
         });
         self.emit_current_chat();
 
@@ -744,6 +788,11 @@ impl AppCore {
                     .find(|att| att.original_hash_hex == hash)
             });
             if let Some(att) = found {
+                // A cached file means any download of it has finished.
+                if local_path.is_some() {
+                    att.transferred_bytes = None;
+                    att.total_bytes = None;
+                }
                 att.local_path = local_path;
                 true
             } else {
@@ -859,7 +908,8 @@ impl AppCore {
             visible_page.extend(
                 batch
                     .into_iter()
-                    .filter(|m| classify_app_message(m).is_some_and(|k| k.is_chat_visible())),
+                    .filter(|m| classify_app_message(m).is_some_and(|k| k.is_chat_visible()))
+                    .filter(|m| !self.is_blocked(&m.pubkey.to_hex())),
             );
             if batch_len < limit || visible_page.len() >= limit {
                 break;
@@ -876,7 +926,34 @@ impl AppCore {
             return;
         }
 
-        let separated = separate_messages(&visible_page, &sender_names);
+        // Edits and deletions land after the message they target, so for an
+        // older page they usually sit above the boundary, in rows already loaded.
+        let mut newer_revisions = Vec::new();
+        let mut newer_fetched = 0;
+        while newer_fetched < base_offset {
+            let batch = sess
+                .mdk
+                .get_messages(
+                    &entry.mls_group_id,
+                    Some(Pagination::new(
+                        Some((base_offset - newer_fetched).min(limit.max(1))),
+                        Some(newer_fetched),
+                    )),
+                )
+                .unwrap_or_default();
+            if batch.is_empty() {
+                break;
+            }
+            newer_fetched += batch.len();
+            newer_revisions.extend(batch.into_iter().filter(|m| {
+                matches!(
+                    classify_app_message(m),
+                    Some(AppMessageKind::Edit | AppMessageKind::Deletion)
+                )
+            }));
+        }
+
+        let separated = separate_older_page(&visible_page, &newer_revisions, &sender_names);
 
         // Temporarily take the media cache out of self.
         let mut media_cache = self.media_cache.remove(chat_id).unwrap_or_default();
@@ -911,6 +988,8 @@ impl AppCore {
                     m.created_at.as_secs() as i64,
                     &mut media_cache,
                 );
+                apply_message_revisions(&mut cm, &separated.revisions, &sender_names);
+                cm.seen_by = self.message_readers(chat_id, &cm, &my_pubkey_hex, &sender_names);
                 cm
             })
             .collect();
@@ -947,6 +1026,30 @@ impl AppCore {
     }
 }
 
+/// Chat-list preview text for `newest`, honoring an edit or deletion by its
+/// author found among the other recently stored messages.
+fn preview_content(newest: &message_types::Message, recent: &[message_types::Message]) -> String {
+    let target_id = newest.id.to_hex();
+    let mut latest_edit: Option<&message_types::Message> = None;
+    for m in recent {
+        if m.pubkey != newest.pubkey || first_event_tag_id(&m.tags).as_ref() != Some(&target_id) {
+            continue;
+        }
+        match classify_app_message(m) {
+            Some(AppMessageKind::Deletion) => return "Message deleted".to_string(),
+            Some(AppMessageKind::Edit)
+                if latest_edit.is_none_or(|e| m.created_at > e.created_at) =>
+            {
+                latest_edit = Some(m);
+            }
+            _ => {}
+        }
+    }
+    latest_edit
+        .map(|m| m.content.clone())
+        .unwrap_or_else(|| newest.content.clone())
+}
+
 fn truncated_npub(s: &str) -> String {
     if s.len() > 16 {
         format!("{}...", &s[..12])
@@ -1020,7 +1123,7 @@ fn parse_message_segments(content: &str) -> Vec<MessageSegment> {
     segments
 }
 
-fn first_event_tag_id(tags: &Tags) -> Option<String> {
+pub(super) fn first_event_tag_id(tags: &Tags) -> Option<String> {
     tags.iter().find_map(|tag| {
         if tag.kind() == TagKind::e() {
             tag.content().map(|s| s.to_string())
@@ -1042,9 +1145,55 @@ struct SeparatedMessages<'a> {
     /// When a sender reacts multiple times, only the newest reaction is kept.
     reaction_map: HashMap<String, HashMap<String, (String, u64)>>,
     hypernote_responses: Vec<HypernoteResponseMessage>,
+    revisions: MessageRevisions,
     regular: Vec<&'a message_types::Message>,
 }
 
+/// Edits and deletions keyed by the rumor id they target. They're recorded per
+/// sender and only applied when the sender is the original author.
+#[derive(Debug, Default)]
+struct MessageRevisions {
+    /// target_id → sender_pubkey_hex → (content, timestamp), newest edit only.
+    edits: HashMap<String, HashMap<String, (String, u64)>>,
+    /// target_id → senders that deleted it.
+    deletions: HashMap<String, HashSet<String>>,
+}
+
+impl MessageRevisions {
+    fn merge(&mut self, other: MessageRevisions) {
+        for (target_id, by_sender) in other.edits {
+            let edits = self.edits.entry(target_id).or_default();
+            for (sender, (content, ts)) in by_sender {
+                edits
+                    .entry(sender)
+                    .and_modify(|existing| {
+                        if ts > existing.1 {
+                            *existing = (content.clone(), ts);
+                        }
+                    })
+                    .or_insert((content, ts));
+            }
+        }
+        for (target_id, senders) in other.deletions {
+            self.deletions.entry(target_id).or_default().extend(senders);
+        }
+    }
+}
+
+/// Like [`separate_messages`] for a page of older history, also applying the
+/// edits and deletions found in the `newer` rows above it.
+fn separate_older_page<'a>(
+    page: &'a [message_types::Message],
+    newer: &[message_types::Message],
+    sender_names: &HashMap<String, String>,
+) -> SeparatedMessages<'a> {
+    let mut separated = separate_messages(page, sender_names);
+    separated
+        .revisions
+        .merge(separate_messages(newer, sender_names).revisions);
+    separated
+}
+
 /// Separate a flat list of stored messages into reaction map, hypernote
 /// responses, and regular (displayable) messages.
 fn separate_messages<'a>(
@@ -1053,6 +1202,7 @@ fn separate_messages<'a>(
 ) -> SeparatedMessages<'a> {
     let mut reaction_map: HashMap<String, HashMap<String, (String, u64)>> = HashMap::new();
     let mut hypernote_responses: Vec<HypernoteResponseMessage> = Vec::new();
+    let mut revisions = MessageRevisions::default();
     let mut regular_messages = Vec::new();
     for m in messages {
         match classify_app_message(m) {
@@ -1087,6 +1237,32 @@ fn separate_messages<'a>(
                     hypernote_responses.push(response);
                 }
             }
+            Some(AppMessageKind::Edit) => {
+                if let Some(target_id) = first_event_tag_id(&m.tags) {
+                    let ts = m.created_at.as_secs();
+                    let content = m.content.clone();
+                    revisions
+                        .edits
+                        .entry(target_id)
+                        .or_default()
+                        .entry(m.pubkey.to_hex())
+                        .and_modify(|existing| {
+                            if ts > existing.1 {
+                                *existing = (content.clone(), ts);
+                            }
+                        })
+                        .or_insert((content, ts));
+                }
+            }
+            Some(AppMessageKind::Deletion) => {
+                if let Some(target_id) = first_event_tag_id(&m.tags) {
+                    revisions
+                        .deletions
+                        .entry(target_id)
+                        .or_default()
+                        .insert(m.pubkey.to_hex());
+                }
+            }
             Some(AppMessageKind::Chat | AppMessageKind::Hypernote) => {
                 regular_messages.push(m);
             }
@@ -1096,10 +1272,49 @@ fn separate_messages<'a>(
     SeparatedMessages {
         reaction_map,
         hypernote_responses,
+        revisions,
         regular: regular_messages,
     }
 }
 
+/// Apply the author's latest edit to a built message, or turn it into a
+/// tombstone if the author deleted it. Revisions from anyone else are ignored.
+fn apply_message_revisions(
+    cm: &mut ChatMessage,
+    revisions: &MessageRevisions,
+    sender_names: &HashMap<String, String>,
+) {
+    let deleted = revisions
+        .deletions
+        .get(&cm.id)
+        .is_some_and(|senders| senders.contains(&cm.sender_pubkey));
+    if deleted {
+        cm.deleted = true;
+        cm.content.clear();
+        cm.display_content.clear();
+        cm.mentions.clear();
+        cm.segments.clear();
+        cm.reactions.clear();
+        cm.media.clear();
+        cm.html_state = None;
+        cm.hypernote = None;
+        return;
+    }
+    let Some((content, ts)) = revisions
+        .edits
+        .get(&cm.id)
+        .and_then(|by_sender| by_sender.get(&cm.sender_pubkey))
+    else {
+        return;
+    };
+    let (display_content, mentions) = resolve_mentions(content, sender_names);
+    cm.segments = parse_message_segments(&display_content);
+    cm.content = content.clone();
+    cm.display_content = display_content;
+    cm.mentions = mentions;
+    cm.edited_at = Some(*ts as i64);
+}
+
 /// Convert a stored message into a ChatMessage for the UI, including
 /// reaction aggregation and hypernote parsing.
 fn build_chat_message(
@@ -1182,6 +1397,9 @@ fn build_chat_message(
         segments,
         html_state: None,
         hypernote,
+        edited_at: None,
+        deleted: false,
+        seen_by: vec![],
     }
 }
 
@@ -1476,6 +1694,9 @@ mod tests {
             segments: parse_message_segments(&display_content),
             html_state: None,
             hypernote: None,
+            edited_at: None,
+            deleted: false,
+            seen_by: vec![],
         }
     }
 
@@ -1932,12 +2153,163 @@ mod tests {
         assert!(separated.hypernote_responses.is_empty());
     }
 
+    fn revision_of(
+        id_byte: u8,
+        author: &message_types::Message,
+        kind: Kind,
+        content: &str,
+        timestamp: u64,
+    ) -> message_types::Message {
+        let mut tags = Tags::new();
+        tags.push(Tag::event(author.id));
+        let mut m = make_stored_msg(id_byte, kind, content, tags, timestamp);
+        m.pubkey = author.pubkey;
+        m
+    }
+
+    #[test]
+    fn apply_message_revisions_uses_latest_edit_from_author_only() {
+        use pika_marmot_runtime::message::MESSAGE_EDIT_KIND;
+
+        let original = make_stored_msg(1, Kind::ChatMessage, "helo", Tags::new(), 100);
+        let mut impostor = revision_of(4, &original, MESSAGE_EDIT_KIND, "hijacked", 104);
+        impostor.pubkey = PublicKey::from_byte_array([9; 32]);
+        let msgs = vec![
+            revision_of(3, &original, MESSAGE_EDIT_KIND, "hello!", 103),
+            revision_of(2, &original, MESSAGE_EDIT_KIND, "hello", 101),
+            impostor,
+            original.clone(),
+        ];
+
+        let sender_names = HashMap::new();
+        let separated = separate_messages(&msgs, &sender_names);
+        assert_eq!(
+            separated.regular.len(),
+            1,
+            "edits are not rendered on their own"
+        );
+
+        let mut cm = build_chat_message(
+            separated.regular[0],
+            "me",
+            &sender_names,
+            &separated.reaction_map,
+        );
+        apply_message_revisions(&mut cm, &separated.revisions, &sender_names);
+        assert_eq!(cm.content, "hello!");
+        assert_eq!(cm.display_content, "hello!");
+        assert_eq!(cm.edited_at, Some(103));
+        assert!(!cm.deleted);
+    }
+
+    #[test]
+    fn apply_message_revisions_renders_tombstone_for_author_delete() {
+        let original = make_stored_msg(1, Kind::ChatMessage, "oops", Tags::new(), 100);
+        let mut reaction_tags = Tags::new();
+        reaction_tags.push(Tag::event(original.id));
+        let msgs = vec![
+            revision_of(2, &original, Kind::EventDeletion, "", 102),
+            make_stored_msg(3, Kind::Reaction, "+", reaction_tags, 101),
+            original.clone(),
+        ];
+
+        let sender_names = HashMap::new();
+        let separated = separate_messages(&msgs, &sender_names);
+        let mut cm = build_chat_message(
+            separated.regular[0],
+            "me",
+            &sender_names,
+            &separated.reaction_map,
+        );
+        assert_eq!(cm.reactions.len(), 1);
+        apply_message_revisions(&mut cm, &separated.revisions, &sender_names);
+        assert!(cm.deleted);
+        assert!(cm.content.is_empty());
+        assert!(cm.segments.is_empty());
+        assert!(cm.reactions.is_empty());
+    }
+
+    #[test]
+    fn apply_message_revisions_ignores_delete_from_other_member() {
+        let original = make_stored_msg(1, Kind::ChatMessage, "keep me", Tags::new(), 100);
+        let mut deletion = revision_of(2, &original, Kind::EventDeletion, "", 101);
+        deletion.pubkey = PublicKey::from_byte_array([9; 32]);
+        let msgs = vec![deletion, original.clone()];
+
+        let sender_names = HashMap::new();
+        let separated = separate_messages(&msgs, &sender_names);
+        let mut cm = build_chat_message(
+            separated.regular[0],
+            "me",
+            &sender_names,
+            &separated.reaction_map,
+        );
+        apply_message_revisions(&mut cm, &separated.revisions, &sender_names);
+        assert!(!cm.deleted);
+        assert_eq!(cm.content, "keep me");
+    }
+
+    #[test]
+    fn older_page_applies_revisions_from_newer_pages() {
+        use pika_marmot_runtime::message::MESSAGE_EDIT_KIND;
+
+        let edited = make_stored_msg(1, Kind::ChatMessage, "helo", Tags::new(), 100);
+        let deleted = make_stored_msg(2, Kind::ChatMessage, "oops", Tags::new(), 101);
+        // Newest first, as stored; the page boundary falls after the chat message.
+        let newer = vec![
+            revision_of(6, &edited, MESSAGE_EDIT_KIND, "hello", 300),
+            revision_of(5, &deleted, Kind::EventDeletion, "", 250),
+            make_stored_msg(4, Kind::ChatMessage, "newer", Tags::new(), 200),
+        ];
+        let page = vec![
+            revision_of(3, &edited, MESSAGE_EDIT_KIND, "hell", 102),
+            deleted.clone(),
+            edited.clone(),
+        ];
+
+        let sender_names = HashMap::new();
+        let separated = separate_older_page(&page, &newer, &sender_names);
+        assert_eq!(separated.regular.len(), 2, "only the page is rendered");
+        let built: Vec<ChatMessage> = separated
+            .regular
+            .iter()
+            .map(|m| {
+                let mut cm = build_chat_message(m, "me", &sender_names, &separated.reaction_map);
+                apply_message_revisions(&mut cm, &separated.revisions, &sender_names);
+                cm
+            })
+            .collect();
+        assert!(built[0].deleted);
+        assert_eq!(built[1].content, "hello");
+        assert_eq!(built[1].edited_at, Some(300));
+    }
+
+    #[test]
+    fn preview_content_reflects_edits_and_deletes() {
+        use pika_marmot_runtime::message::MESSAGE_EDIT_KIND;
+
+        let original = make_stored_msg(1, Kind::ChatMessage, "draft", Tags::new(), 100);
+        let edited = vec![
+            revision_of(2, &original, MESSAGE_EDIT_KIND, "final", 101),
+            original.clone(),
+        ];
+        assert_eq!(preview_content(&original, &edited), "final");
+
+        let deleted = vec![
+            revision_of(3, &original, Kind::EventDeletion, "", 102),
+            original.clone(),
+        ];
+        assert_eq!(preview_content(&original, &deleted), "Message deleted");
+    }
+
     #[test]
     fn is_chat_visible_excludes_typing_and_call_signals() {
         assert!(AppMessageKind::Chat.is_chat_visible());
         assert!(AppMessageKind::Reaction.is_chat_visible());
         assert!(AppMessageKind::Hypernote.is_chat_visible());
         assert!(AppMessageKind::HypernoteResponse.is_chat_visible());
+        assert!(AppMessageKind::Edit.is_chat_visible());
+        assert!(AppMessageKind::Deletion.is_chat_visible());
         assert!(!AppMessageKind::TypingIndicator.is_chat_visible());
         assert!(!AppMessageKind::CallSignal.is_chat_visible());
     }
@@ -2264,7 +2636,12 @@ mod tests {
             scheme_version: String::new(),
             local_path: None,
             upload_progress: None,
+            transferred_bytes: None,
+            total_bytes: None,
             blurhash: None,
+            thumbnail_path: None,
+            duration_ms: None,
+            waveform: None,
         });
         msg
     }
@@ -2281,6 +2658,10 @@ mod tests {
             can_load_older: false,
             typing_members: vec![],
             my_group_profile: None,
+            disappearing_timer_secs: None,
+            notification_mode: ChatNotificationMode::All,
+            invite_links: vec![],
+            join_requests: vec![],
         }
     }
 
@@ -2358,6 +2739,72 @@ mod tests {
         assert_eq!(att.local_path.as_deref(), Some("/tmp/photo.jpg"));
     }
 
+    #[test]
+    fn update_media_local_path_in_place_clears_transfer_progress() {
+        let mut core = make_test_core();
+        let mut msg = make_msg_with_media("m1", "abc123");
+        msg.media[0].transferred_bytes = Some(512);
+        msg.media[0].total_bytes = Some(1024);
+        core.state.current_chat = Some(make_chat_view("chat1", vec![msg]));
+
+        core.update_media_local_path_in_place(
+            "chat1",
+            "abc123",
+            Some("/tmp/photo.jpg".to_string()),
+        );
+
+        let att = &core.state.current_chat.as_ref().unwrap().messages[0].media[0];
+        assert_eq!(att.transferred_bytes, None);
+        assert_eq!(att.total_bytes, None);
+    }
+
+    #[test]
+    fn media_transfer_progress_updates_pending_upload_bytes() {
+        let mut core = make_test_core();
+        let mut msg = make_msg_with_media("tmp-1", "abc123");
+        msg.media[0].upload_progress = Some(0.0);
+        core.local_outbox
+            .entry("chat1".to_string())
+            .or_default()
+            .insert(
+                "tmp-1".to_string(),
+                LocalOutgoing {
+                    content: String::new(),
+                    timestamp: 1,
+                    sender_pubkey: String::new(),
+                    reply_to_message_id: None,
+                    seq: 1,
+                    media: msg.media.clone(),
+                    kind: Kind::ChatMessage,
+                },
+            );
+        core.state.current_chat = Some(make_chat_view("chat1", vec![msg]));
+        core.pending_media_sends.insert(
+            "req-1".to_string(),
+            PendingMediaSend {
+                chat_id: "chat1".to_string(),
+                caption: String::new(),
+                imeta_tag: Tag::parse(["imeta", "url pending:upload"]).unwrap(),
+                original_hash_hex: "abc123".to_string(),
+                upload_mime: "image/jpeg".to_string(),
+                encrypted_hash_hex: "def456".to_string(),
+                account_pubkey: String::new(),
+                temp_rumor_id: "tmp-1".to_string(),
+                encrypted_data: vec![0; 1024],
+            },
+        );
+
+        core.handle_chat_media_transfer_progress("req-1".to_string(), 256, 1024);
+
+        let att = &core.state.current_chat.as_ref().unwrap().messages[0].media[0];
+        assert_eq!(att.transferred_bytes, Some(256));
+        assert_eq!(att.total_bytes, Some(1024));
+        assert_eq!(att.upload_progress, Some(0.25));
+        let outbox_att = &core.local_outbox["chat1"]["tmp-1"].media[0];
+        assert_eq!(outbox_att.transferred_bytes, Some(256));
+        assert_eq!(outbox_att.upload_progress, Some(0.25));
+    }
+
     #[test]
     fn update_media_local_path_in_place_returns_false_for_unknown_hash() {
         let mut core = make_test_core();
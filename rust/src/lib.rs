@@ -86,6 +86,30 @@ pub fn is_valid_peer_key(input: &str) -> bool {
     nostr_sdk::prelude::PublicKey::parse(&normalized).is_ok()
 }
 
+/// `<scheme>://join/<admin npub>/<token>` for an entry of `ChatViewState.invite_links`.
+#[uniffi::export]
+pub fn group_invite_link_url(scheme: &str, admin_npub: &str, token: &str) -> Option<String> {
+    let admin = nostr_sdk::prelude::PublicKey::parse(admin_npub.trim()).ok()?;
+    let token = token.trim();
+    if !pika_marmot_runtime::invite_link::is_valid_invite_token(token) {
+        return None;
+    }
+    Some(pika_marmot_runtime::invite_link::GroupInviteLink::new(admin, token).to_url(scheme))
+}
+
+/// Whether `input` (a scanned QR code or opened URL) is a group invite link.
+#[uniffi::export]
+pub fn is_group_invite_link(input: &str) -> bool {
+    pika_marmot_runtime::invite_link::GroupInviteLink::parse(input).is_some()
+}
+
+/// Hex pubkey of an nsec, so native credential stores can key secrets by account.
+#[uniffi::export]
+pub fn pubkey_for_nsec(nsec: &str) -> Option<String> {
+    let keys = nostr_sdk::prelude::Keys::parse(nsec.trim()).ok()?;
+    Some(keys.public_key().to_hex())
+}
+
 #[uniffi::export]
 pub fn build_nip98_authorization_header(nsec: &str, method: &str, url: &str) -> Option<String> {
     use nostr_sdk::prelude::{EventBuilder, Keys, Kind, Tag, TagKind};
@@ -162,6 +186,22 @@ mod tests {
         assert!(!is_valid_peer_key("pika://chat/garbage"));
     }
 
+    #[test]
+    fn group_invite_link_url_round_trips_and_is_not_a_peer_key() {
+        use nostr_sdk::ToBech32;
+        let npub = nostr_sdk::prelude::Keys::generate()
+            .public_key()
+            .to_bech32()
+            .unwrap();
+        let token = "0123456789abcdef0123456789abcdef";
+        let url = group_invite_link_url("pikadev", &npub, token).expect("invite link");
+        assert_eq!(url, format!("pikadev://join/{npub}/{token}"));
+        assert!(is_group_invite_link(&url));
+        assert!(!is_valid_peer_key(&url));
+        assert!(!is_group_invite_link(&format!("pika://chat/{npub}")));
+        assert_eq!(group_invite_link_url("pika", &npub, "short"), None);
+    }
+
     #[test]
     fn build_nip98_authorization_header_encodes_expected_event_tags() {
         let keys = nostr_sdk::prelude::Keys::generate();
@@ -215,6 +255,48 @@ pub trait AppReconciler: Send + Sync + 'static {
 #[uniffi::export(callback_interface)]
 pub trait VideoFrameReceiver: Send + Sync + 'static {
     fn on_video_frame(&self, call_id: String, payload: Vec<u8>);
+    /// Receivers reported congestion (or its end); the platform encoder should
+    /// switch to `profile`.
+    fn on_video_profile(&self, call_id: String, profile: CallVideoProfile);
+    /// A receiver lost sync; the platform encoder should make its next frame a
+    /// keyframe.
+    fn on_keyframe_request(&self, call_id: String);
+    /// Screen share NALUs from the remote participant currently sharing, a few
+    /// frames per second. A gap of a couple of seconds means the share stopped.
+    fn on_screen_frame(&self, call_id: String, payload: Vec<u8>);
+}
+
+/// A representative frame decoded from a video by the platform.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct VideoPoster {
+    /// Encoded still image (JPEG or PNG).
+    pub image_data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub duration_ms: u64,
+}
+
+/// Amplitude envelope of an audio file decoded by the platform.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct AudioWaveform {
+    /// Peak levels in `0.0..=1.0`, one per bar.
+    pub levels: Vec<f32>,
+    pub duration_ms: u64,
+}
+
+/// Platform-side media tooling (AVFoundation / MediaCodec) for work the core
+/// can't do itself, like decoding video and AAC. Called from a background
+/// thread; may block.
+#[uniffi::export(callback_interface)]
+pub trait MediaProcessor: Send + Sync + 'static {
+    /// Decode a poster frame and read the duration of the video at `path`.
+    fn extract_poster(&self, path: String) -> Option<VideoPoster>;
+    /// Re-encode the video at `input_path` to H.264/AAC MP4 at `output_path`,
+    /// aiming to stay under `max_bytes`. Returns false if it could not.
+    fn transcode(&self, input_path: String, output_path: String, max_bytes: u64) -> bool;
+    /// Decode the audio at `path` into `bars` peak levels. Used for voice notes
+    /// from clients that don't send a waveform.
+    fn audio_waveform(&self, path: String, bars: u32) -> Option<AudioWaveform>;
 }
 
 #[derive(uniffi::Object)]
@@ -226,6 +308,7 @@ pub struct FfiApp {
     external_signer_bridge: SharedExternalSignerBridgeType,
     bunker_signer_connector: SharedBunkerSignerConnectorType,
     video_frame_receiver: Arc<RwLock<Option<Arc<dyn VideoFrameReceiver>>>>,
+    media_processor: Arc<RwLock<Option<Arc<dyn MediaProcessor>>>>,
 }
 
 #[uniffi::export]
@@ -246,6 +329,8 @@ impl FfiApp {
         ));
         let video_frame_receiver: Arc<RwLock<Option<Arc<dyn VideoFrameReceiver>>>> =
             Arc::new(RwLock::new(None));
+        let media_processor: Arc<RwLock<Option<Arc<dyn MediaProcessor>>>> =
+            Arc::new(RwLock::new(None));
 
         // Actor loop thread (single threaded "app actor").
         let core_tx_for_core = core_tx.clone();
@@ -253,6 +338,7 @@ impl FfiApp {
         let signer_bridge_for_core = external_signer_bridge.clone();
         let bunker_connector_for_core = bunker_signer_connector.clone();
         let video_receiver_for_core = video_frame_receiver.clone();
+        let media_processor_for_core = media_processor.clone();
         thread::spawn(move || {
             let mut core = crate::core::AppCore::new(
                 update_tx,
@@ -265,6 +351,7 @@ impl FfiApp {
                 bunker_connector_for_core,
             );
             core.set_video_frame_receiver(video_receiver_for_core);
+            core.set_media_processor(media_processor_for_core);
             while let Ok(msg) = core_rx.recv() {
                 core.handle_message(msg);
             }
@@ -278,6 +365,7 @@ impl FfiApp {
             external_signer_bridge,
             bunker_signer_connector,
             video_frame_receiver,
+            media_processor,
         })
     }
 
@@ -323,12 +411,32 @@ impl FfiApp {
         }
     }
 
+    pub fn set_media_processor(&self, processor: Box<dyn MediaProcessor>) {
+        let processor: Arc<dyn MediaProcessor> = Arc::from(processor);
+        match self.media_processor.write() {
+            Ok(mut slot) => {
+                *slot = Some(processor);
+            }
+            Err(poison) => {
+                *poison.into_inner() = Some(processor);
+            }
+        }
+    }
+
     pub fn send_video_frame(&self, payload: Vec<u8>) {
         let _ = self.core_tx.send(CoreMsg::Internal(Box::new(
             InternalEvent::VideoFrameFromPlatform { payload },
         )));
     }
 
+    /// Screen share counterpart of `send_video_frame`; dropped unless the
+    /// active call is sharing.
+    pub fn send_screen_frame(&self, payload: Vec<u8>) {
+        let _ = self.core_tx.send(CoreMsg::Internal(Box::new(
+            InternalEvent::ScreenFrameFromPlatform { payload },
+        )));
+    }
+
     pub fn set_external_signer_bridge(&self, bridge: Box<dyn ExternalSignerBridgeTrait>) {
         let bridge: Arc<dyn ExternalSignerBridgeTrait> = Arc::from(bridge);
         match self.external_signer_bridge.write() {
@@ -288,6 +288,203 @@ fn open_mdk_ios_file_key(data_dir: &str, pubkey: &PublicKey) -> Result<PikaMdk>
     Ok(MDK::builder(storage).with_config(mdk_config()).build())
 }
 
+/// Where the MDK SQLCipher key lives when it's kept in a file rather than the
+/// keyring (desktop always, iOS simulator as a fallback).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn mdk_file_key_path(data_dir: &str, pubkey_hex: &str) -> PathBuf {
+    mdk_db_path(data_dir, pubkey_hex).with_extension("key")
+}
+
+#[cfg(all(target_os = "ios", target_env = "sim"))]
+fn mdk_file_key_path(data_dir: &str, pubkey_hex: &str) -> PathBuf {
+    Path::new(data_dir)
+        .join("mls")
+        .join(pubkey_hex)
+        .join("mdk.db.key")
+}
+
+/// Raw SQLCipher key of an account's MDK database. Backups carry it so the
+/// database file can be opened again after a restore on another device.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn mdk_db_key(data_dir: &str, pubkey_hex: &str, _keychain_group: &str) -> Result<[u8; 32]> {
+    read_file_key(&mdk_file_key_path(data_dir, pubkey_hex))
+}
+
+/// Raw SQLCipher key of an account's MDK database. Backups carry it so the
+/// database file can be opened again after a restore on another device.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub fn mdk_db_key(data_dir: &str, pubkey_hex: &str, keychain_group: &str) -> Result<[u8; 32]> {
+    init_keyring_once(keychain_group)?;
+    #[cfg(all(target_os = "ios", target_env = "sim"))]
+    {
+        let key_path = mdk_file_key_path(data_dir, pubkey_hex);
+        if key_path.exists() {
+            return read_file_key(&key_path);
+        }
+    }
+    #[cfg(not(all(target_os = "ios", target_env = "sim")))]
+    let _ = data_dir;
+    // MdkSqliteStorage keeps the raw 32-byte key as the keyring secret.
+    let entry = keyring_core::Entry::new(SERVICE_ID, &db_key_id(pubkey_hex))
+        .context("open mdk keyring entry")?;
+    let bytes = entry.get_secret().context("read mdk keyring key")?;
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow!("invalid mdk keyring key length: {}", bytes.len()))
+}
+
+/// Install the key a restored MDK database was encrypted with, replacing any
+/// key this device generated for the account. Close MDK before calling.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn set_mdk_db_key(
+    data_dir: &str,
+    pubkey_hex: &str,
+    _keychain_group: &str,
+    key: &[u8; 32],
+) -> Result<()> {
+    write_file_key(&mdk_file_key_path(data_dir, pubkey_hex), key)
+}
+
+/// Install the key a restored MDK database was encrypted with, replacing any
+/// key this device generated for the account. Close MDK before calling.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub fn set_mdk_db_key(
+    data_dir: &str,
+    pubkey_hex: &str,
+    keychain_group: &str,
+    key: &[u8; 32],
+) -> Result<()> {
+    init_keyring_once(keychain_group)?;
+    #[cfg(all(target_os = "ios", target_env = "sim"))]
+    {
+        let key_path = mdk_file_key_path(data_dir, pubkey_hex);
+        if key_path.exists() {
+            return write_file_key(&key_path, key);
+        }
+    }
+    #[cfg(not(all(target_os = "ios", target_env = "sim")))]
+    let _ = data_dir;
+    keyring_core::Entry::new(SERVICE_ID, &db_key_id(pubkey_hex))
+        .context("open mdk keyring entry")?
+        .set_secret(key)
+        .context("store mdk keyring key")
+}
+
+pub fn search_db_path(data_dir: &str, pubkey_hex: &str) -> PathBuf {
+    Path::new(data_dir)
+        .join("mls")
+        .join(pubkey_hex)
+        .join("search.sqlite3")
+}
+
+pub fn search_db_key_id(pubkey_hex: &str) -> String {
+    format!("search.db.key.{pubkey_hex}")
+}
+
+/// SQLCipher key for the local message search index. Like the MDK database,
+/// mobile keeps it in the platform keyring and desktop in a file next to the DB.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub fn search_db_key(data_dir: &str, pubkey_hex: &str, keychain_group: &str) -> Result<[u8; 32]> {
+    init_keyring_once(keychain_group)?;
+    let keyring_key = keyring_db_key(&search_db_key_id(pubkey_hex));
+    #[cfg(all(target_os = "ios", target_env = "sim"))]
+    if let Err(e) = &keyring_key {
+        tracing::warn!("search index keyring key failed on iOS; falling back to file key: {e}");
+        return load_or_create_file_key(
+            &search_db_path(data_dir, pubkey_hex).with_extension("key"),
+        );
+    }
+    #[cfg(not(all(target_os = "ios", target_env = "sim")))]
+    let _ = data_dir;
+    keyring_key
+}
+
+/// SQLCipher key for the local message search index. Like the MDK database,
+/// mobile keeps it in the platform keyring and desktop in a file next to the DB.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn search_db_key(data_dir: &str, pubkey_hex: &str, _keychain_group: &str) -> Result<[u8; 32]> {
+    load_or_create_file_key(&search_db_path(data_dir, pubkey_hex).with_extension("key"))
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+fn keyring_db_key(key_id: &str) -> Result<[u8; 32]> {
+    let entry = keyring_core::Entry::new(SERVICE_ID, key_id).context("open keyring entry")?;
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = hex::decode(encoded.trim()).context("decode keyring db key")?;
+            bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow!("invalid keyring db key length: {}", bytes.len()))
+        }
+        Err(keyring_core::Error::NoEntry) => {
+            use rand::rngs::OsRng;
+            use rand::RngCore;
+
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            entry
+                .set_password(&hex::encode(key))
+                .context("store keyring db key")?;
+            Ok(key)
+        }
+        Err(e) => Err(anyhow!(e)).context("read keyring db key"),
+    }
+}
+
+#[cfg(any(
+    not(any(target_os = "android", target_os = "ios")),
+    all(target_os = "ios", target_env = "sim")
+))]
+fn load_or_create_file_key(key_path: &Path) -> Result<[u8; 32]> {
+    if key_path.exists() {
+        return read_file_key(key_path);
+    }
+
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    write_file_key(key_path, &key)?;
+    Ok(key)
+}
+
+#[cfg(any(
+    not(any(target_os = "android", target_os = "ios")),
+    all(target_os = "ios", target_env = "sim")
+))]
+fn read_file_key(key_path: &Path) -> Result<[u8; 32]> {
+    let bytes = std::fs::read(key_path)
+        .with_context(|| format!("read db file key: {}", key_path.display()))?;
+    bytes.as_slice().try_into().map_err(|_| {
+        anyhow!(
+            "invalid db file key length: expected 32 bytes, got {}",
+            bytes.len()
+        )
+    })
+}
+
+#[cfg(any(
+    not(any(target_os = "android", target_os = "ios")),
+    all(target_os = "ios", target_env = "sim")
+))]
+fn write_file_key(key_path: &Path, key: &[u8; 32]) -> Result<()> {
+    if let Some(parent) = key_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create db key dir: {}", parent.display()))?;
+    }
+    std::fs::write(key_path, key)
+        .with_context(|| format!("write db file key: {}", key_path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(key_path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
 #[cfg(all(test, not(any(target_os = "android", target_os = "ios"))))]
 mod tests {
     use super::*;
@@ -129,7 +129,7 @@ fn active_screen(router: &Router) -> Screen {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{AuthMode, AuthState, CallState, CallStatus, ChatViewState};
+    use crate::{AuthMode, AuthState, CallState, CallStatus, ChatNotificationMode, ChatViewState};
 
     fn state_with_router(default_screen: Screen, stack: Vec<Screen>) -> AppState {
         let mut state = AppState::empty();
@@ -238,6 +238,7 @@ mod tests {
             about: None,
             picture_url: None,
             is_followed: false,
+            is_blocked: false,
         });
         let route = project_desktop(&state);
         assert_eq!(
@@ -262,6 +263,10 @@ mod tests {
             can_load_older: false,
             typing_members: vec![],
             my_group_profile: None,
+            disappearing_timer_secs: None,
+            notification_mode: ChatNotificationMode::All,
+            invite_links: vec![],
+            join_requests: vec![],
         });
         let route = project_desktop(&state);
         assert_eq!(route.selected_chat_id, Some("c9".into()));
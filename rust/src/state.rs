@@ -6,6 +6,48 @@ pub struct CallTimelineEvent {
     pub timestamp: i64,
 }
 
+/// One interval of a call's quality history.
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct CallQualitySample {
+    /// Unix time in milliseconds.
+    pub at_ms: i64,
+    pub rtt_ms: Option<u32>,
+    pub jitter_ms: u32,
+    /// Share of audio that had to be concealed, 0 to 100.
+    pub loss_pct: f32,
+    pub concealed_frames: u64,
+    pub tx_bitrate_bps: u64,
+    pub rx_bitrate_bps: u64,
+    pub video_fps: f32,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct CallQualitySummary {
+    /// Estimated mean opinion score, 1.0 (unusable) to 4.5 (excellent).
+    pub score: f32,
+    pub sample_count: u32,
+    pub duration_ms: i64,
+    pub avg_rtt_ms: Option<u32>,
+    pub avg_jitter_ms: u32,
+    pub max_jitter_ms: u32,
+    pub avg_loss_pct: f32,
+    pub max_loss_pct: f32,
+    pub concealed_frames: u64,
+    pub avg_tx_bitrate_bps: u64,
+    pub avg_rx_bitrate_bps: u64,
+    pub avg_video_fps: f32,
+}
+
+/// Post-call quality report, loaded with `LoadCallReport`.
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct CallReportState {
+    pub call_id: String,
+    /// Oldest first.
+    pub samples: Vec<CallQualitySample>,
+    /// `None` when nothing was recorded for the call.
+    pub summary: Option<CallQualitySummary>,
+}
+
 #[derive(uniffi::Record, Clone, Debug)]
 pub struct AppState {
     pub rev: u64,
@@ -14,18 +56,32 @@ pub struct AppState {
     pub my_profile: MyProfileState,
     pub busy: BusyState,
     pub chat_list: Vec<ChatSummary>,
+    /// Group invitations waiting for `AcceptInvite` or `DeclineInvite`, newest first.
+    pub invitations: Vec<InvitationSummary>,
     pub current_chat: Option<ChatViewState>,
     pub follow_list: Vec<FollowListEntry>,
+    /// People whose invites, messages, typing and call signals we ignore.
+    pub blocked_users: Vec<BlockedUser>,
     pub peer_profile: Option<PeerProfileState>,
     pub active_call: Option<CallState>,
     pub call_timeline: Vec<CallTimelineEvent>,
+    pub call_report: Option<CallReportState>,
     pub toast: Option<String>,
     pub developer_mode: bool,
+    /// Privacy setting: send read receipts to the chats we open.
+    pub read_receipts_enabled: bool,
+    /// Privacy setting: join invitations from people we follow without asking.
+    pub auto_accept_followed_invites: bool,
+    /// Privacy setting: mirror `blocked_users` to our encrypted mute list on relays.
+    pub block_list_sync_enabled: bool,
     pub update_required: bool,
     pub agent_button: Option<AgentMenuItemState>,
     pub agent_provisioning: Option<AgentProvisioningState>,
     pub voice_recording: Option<VoiceRecordingState>,
     pub media_gallery: Option<MediaGalleryState>,
+    pub message_search: Option<MessageSearchState>,
+    /// Every account signed in on this device, in the order they were added.
+    pub accounts: Vec<AccountSummary>,
 }
 
 impl AppState {
@@ -40,18 +96,26 @@ impl AppState {
             my_profile: MyProfileState::empty(),
             busy: BusyState::idle(),
             chat_list: vec![],
+            invitations: vec![],
             current_chat: None,
             follow_list: vec![],
+            blocked_users: vec![],
             peer_profile: None,
             active_call: None,
             call_timeline: vec![],
+            call_report: None,
             toast: None,
             developer_mode: false,
+            read_receipts_enabled: false,
+            auto_accept_followed_invites: true,
+            block_list_sync_enabled: false,
             update_required: false,
             agent_button: None,
             agent_provisioning: None,
             voice_recording: None,
             media_gallery: None,
+            message_search: None,
+            accounts: vec![],
         }
     }
 }
@@ -86,6 +150,7 @@ pub struct AgentProvisioningState {
 pub struct CallState {
     pub call_id: String,
     pub chat_id: String,
+    /// The other side of a 1:1 call; for group calls, the member who started it.
     pub peer_npub: String,
     pub status: CallStatus,
     pub is_live: bool,
@@ -96,7 +161,40 @@ pub struct CallState {
     pub is_muted: bool,
     pub is_video_call: bool,
     pub is_camera_enabled: bool,
+    /// Echo cancellation, noise suppression and gain control on our mic.
+    pub is_audio_processing_enabled: bool,
+    /// We're publishing our screen on the call's `screen0` track.
+    pub is_screen_sharing: bool,
+    /// We're recording this call to an encrypted file on this device.
+    pub is_recording: bool,
     pub debug: Option<CallDebugStats>,
+    pub is_group_call: bool,
+    /// Remote participants currently in the call, in join order.
+    pub participants: Vec<CallParticipantState>,
+    /// Latest live captions, oldest first. Once the call has ended these can be
+    /// saved to the chat with `SaveCallCaptions`.
+    pub captions: Vec<CallCaption>,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct CallParticipantState {
+    pub npub: String,
+    pub is_muted: bool,
+    pub is_camera_enabled: bool,
+    pub is_speaking: bool,
+    /// They told us they're recording the call on their device.
+    pub is_recording: bool,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct CallCaption {
+    pub speaker_npub: String,
+    pub speaker_name: String,
+    /// Transcribed from our own mic.
+    pub is_local: bool,
+    pub text: String,
+    /// Unix seconds when the caption arrived.
+    pub at: i64,
 }
 
 #[derive(uniffi::Enum, Clone, Debug)]
@@ -156,10 +254,38 @@ impl CallState {
             is_muted,
             is_video_call,
             is_camera_enabled: is_video_call,
+            is_audio_processing_enabled: true,
+            is_screen_sharing: false,
+            is_recording: false,
             debug,
+            is_group_call: false,
+            participants: Vec::new(),
+            captions: Vec::new(),
         }
     }
 
+    /// Adds a remote participant if not already present. Returns whether it was added.
+    pub fn add_participant(&mut self, npub: &str) -> bool {
+        if self.participants.iter().any(|p| p.npub == npub) {
+            return false;
+        }
+        self.participants.push(CallParticipantState {
+            npub: npub.to_string(),
+            is_muted: false,
+            is_camera_enabled: self.is_video_call,
+            is_speaking: false,
+            is_recording: false,
+        });
+        true
+    }
+
+    /// Removes a remote participant. Returns whether it was present.
+    pub fn remove_participant(&mut self, npub: &str) -> bool {
+        let before = self.participants.len();
+        self.participants.retain(|p| p.npub != npub);
+        self.participants.len() != before
+    }
+
     pub fn set_status(&mut self, status: CallStatus) {
         self.is_live = status.is_live();
         self.should_auto_present_call_screen = status.should_auto_present_call_screen();
@@ -200,10 +326,32 @@ pub struct CallDebugStats {
     pub rx_frames: u64,
     pub rx_dropped: u64,
     pub jitter_buffer_ms: u32,
+    /// Current adaptive playout target; grows with measured network jitter.
+    pub jitter_target_ms: u32,
+    /// Smoothed inter-arrival jitter of the remote audio stream.
+    pub rx_jitter_ms: u32,
+    /// Frames that arrived out of order and were reordered before playout.
+    pub rx_reordered: u64,
+    /// Frames that arrived after their playout slot had already been concealed.
+    pub rx_late: u64,
+    /// Playout slots filled by loss concealment instead of received audio.
+    pub rx_concealed: u64,
     pub last_rtt_ms: Option<u32>,
     pub video_tx: u64,
     pub video_rx: u64,
     pub video_rx_decrypt_fail: u64,
+    /// Profile our outgoing video is currently encoded at. It steps down when
+    /// receivers report congestion.
+    pub video_profile: Option<CallVideoProfile>,
+}
+
+/// Encoder settings for outgoing call video.
+#[derive(uniffi::Record, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CallVideoProfile {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub bitrate_bps: u32,
 }
 
 /// "In flight" flags for long-ish operations that the UI should reflect.
@@ -273,6 +421,17 @@ pub enum AuthState {
     },
 }
 
+#[derive(uniffi::Record, Clone, Debug, PartialEq, Eq)]
+pub struct AccountSummary {
+    pub pubkey: String,
+    pub npub: String,
+    pub name: Option<String>,
+    pub picture_url: Option<String>,
+    /// Unread messages across all of the account's chats.
+    pub unread_count: u32,
+    pub is_active: bool,
+}
+
 #[derive(uniffi::Record, Clone, Debug, PartialEq, Eq)]
 pub struct MyProfileState {
     pub name: String,
@@ -307,6 +466,7 @@ pub struct PeerProfileState {
     pub about: Option<String>,
     pub picture_url: Option<String>,
     pub is_followed: bool,
+    pub is_blocked: bool,
 }
 
 #[derive(uniffi::Record, Clone, Debug)]
@@ -318,6 +478,31 @@ pub struct FollowListEntry {
     pub picture_url: Option<String>,
 }
 
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct BlockedUser {
+    pub pubkey: String,
+    pub npub: String,
+    pub name: Option<String>,
+    pub picture_url: Option<String>,
+}
+
+/// A staged MLS welcome we have not joined yet.
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct InvitationSummary {
+    /// Gift wrap event id (hex); identifies the invite in `AcceptInvite`/`DeclineInvite`.
+    pub invite_id: String,
+    /// The chat this becomes once accepted.
+    pub chat_id: String,
+    pub group_name: Option<String>,
+    pub member_count: u32,
+    pub inviter_pubkey: String,
+    pub inviter_npub: String,
+    pub inviter_name: Option<String>,
+    pub inviter_picture_url: Option<String>,
+    pub inviter_followed: bool,
+    pub received_at: i64,
+}
+
 #[derive(uniffi::Record, Clone, Debug)]
 pub struct ChatSummary {
     pub chat_id: String,
@@ -330,6 +515,31 @@ pub struct ChatSummary {
     pub subtitle: Option<String>,
     pub last_message_preview: String,
     pub unread_count: u32,
+    /// Active disappearing-message timer, in seconds. `None` when off.
+    pub disappearing_timer_secs: Option<u64>,
+    pub notification_mode: ChatNotificationMode,
+    /// Muted or snoozed right now; drives the muted indicator.
+    pub is_muted: bool,
+}
+
+/// How push notifications for a chat are delivered.
+#[derive(uniffi::Enum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChatNotificationMode {
+    #[default]
+    All,
+    /// Only messages that mention us (or reply to us), plus incoming calls.
+    MentionsOnly,
+    /// Silent until `until` (unix seconds), then back to `All`.
+    MutedUntil {
+        until: i64,
+    },
+    Muted,
+}
+
+impl ChatNotificationMode {
+    pub(crate) fn is_muted(&self) -> bool {
+        matches!(self, Self::MutedUntil { .. } | Self::Muted)
+    }
 }
 
 #[derive(uniffi::Record, Clone, Debug)]
@@ -344,6 +554,33 @@ pub struct ChatViewState {
     pub can_load_older: bool,
     pub typing_members: Vec<TypingMember>,
     pub my_group_profile: Option<MyProfileState>,
+    pub disappearing_timer_secs: Option<u64>,
+    pub notification_mode: ChatNotificationMode,
+    /// Invite links we minted for this group. Empty unless we're an admin.
+    pub invite_links: Vec<GroupInviteLinkInfo>,
+    /// People who opened one of our approval-only links. Empty unless we're an admin.
+    pub join_requests: Vec<GroupJoinRequest>,
+}
+
+/// Share with `group_invite_link_url(scheme, admin_npub, token)`.
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct GroupInviteLinkInfo {
+    pub token: String,
+    pub admin_npub: String,
+    /// One-time links are revoked by the first join request.
+    pub multi_use: bool,
+    pub requires_approval: bool,
+    pub created_at: i64,
+    pub use_count: u32,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq)]
+pub struct GroupJoinRequest {
+    pub pubkey: String,
+    pub npub: String,
+    pub name: Option<String>,
+    pub picture_url: Option<String>,
+    pub requested_at: i64,
 }
 
 #[derive(uniffi::Record, Clone, Debug)]
@@ -416,6 +653,20 @@ pub struct ChatMessage {
     pub segments: Vec<MessageSegment>,
     pub html_state: Option<String>,
     pub hypernote: Option<HypernoteData>,
+    /// Timestamp of the author's latest edit; `content` already reflects it.
+    pub edited_at: Option<i64>,
+    /// Deleted by its author. Content, media and reactions are cleared so the UI
+    /// renders a tombstone in place.
+    pub deleted: bool,
+    /// Members whose latest read receipt covers this message, oldest read first.
+    pub seen_by: Vec<MessageReader>,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq, Eq)]
+pub struct MessageReader {
+    pub pubkey: String,
+    pub name: Option<String>,
+    pub read_at: i64,
 }
 
 #[derive(uniffi::Record, Clone, Debug)]
@@ -432,7 +683,17 @@ pub struct ChatMediaAttachment {
     pub scheme_version: String,
     pub local_path: Option<String>,
     pub upload_progress: Option<f32>,
+    /// Bytes moved so far while this attachment is uploading or downloading.
+    pub transferred_bytes: Option<u64>,
+    /// Size of the in-flight transfer, once known.
+    pub total_bytes: Option<u64>,
     pub blurhash: Option<String>,
+    /// Poster frame cached on disk (videos); shown until the media is downloaded.
+    pub thumbnail_path: Option<String>,
+    /// Playback length, when the sender included it.
+    pub duration_ms: Option<u64>,
+    /// Voice-note peak levels in `0.0..=1.0`, from the sender or decoded after download.
+    pub waveform: Option<Vec<f32>>,
 }
 
 #[derive(uniffi::Enum, Clone, Debug)]
@@ -455,6 +716,26 @@ pub struct MediaGalleryItem {
     pub timestamp: i64,
 }
 
+/// Results of the latest `SearchMessages` query. `chat_id` is set when the
+/// search was scoped to a single chat.
+#[derive(uniffi::Record, Clone, Debug, PartialEq, Eq)]
+pub struct MessageSearchState {
+    pub query: String,
+    pub chat_id: Option<String>,
+    pub results: Vec<MessageSearchResult>,
+}
+
+#[derive(uniffi::Record, Clone, Debug, PartialEq, Eq)]
+pub struct MessageSearchResult {
+    pub chat_id: String,
+    pub chat_name: String,
+    pub message_id: String,
+    pub sender_pubkey: String,
+    pub sender_name: Option<String>,
+    pub snippet: String,
+    pub timestamp: i64,
+}
+
 #[derive(uniffi::Record, Clone, Debug)]
 pub struct ReactionSummary {
     pub emoji: String,
@@ -622,4 +903,26 @@ mod tests {
         assert!(call.is_video_call);
         assert!(call.is_camera_enabled);
     }
+
+    #[test]
+    fn call_participants_add_once_and_remove() {
+        let mut call = CallState::new(
+            "call-1".to_string(),
+            "chat-1".to_string(),
+            "npub1test".to_string(),
+            CallStatus::Active,
+            None,
+            false,
+            true,
+            None,
+        );
+        assert!(call.add_participant("npub1a"));
+        assert!(!call.add_participant("npub1a"));
+        assert!(call.add_participant("npub1b"));
+        assert!(call.participants[0].is_camera_enabled);
+        assert!(call.remove_participant("npub1a"));
+        assert!(!call.remove_participant("npub1a"));
+        assert_eq!(call.participants.len(), 1);
+        assert_eq!(call.participants[0].npub, "npub1b");
+    }
 }
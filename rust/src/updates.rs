@@ -1,4 +1,4 @@
-use crate::state::AppState;
+use crate::state::{AppState, CallVideoProfile};
 use crate::AppAction;
 
 #[derive(uniffi::Enum, Clone, Debug)]
@@ -47,6 +47,11 @@ pub enum InternalEvent {
     GroupMessageReceived {
         event: nostr_sdk::prelude::Event,
     },
+    /// Group message for an account that isn't the active one.
+    BackgroundGroupMessageReceived {
+        account_pubkey: String,
+        event: nostr_sdk::prelude::Event,
+    },
 
     // Async results
     PublishMessageResult {
@@ -66,6 +71,20 @@ pub enum InternalEvent {
         encrypted_data: Option<Vec<u8>>,
         error: Option<String>,
     },
+    /// Byte-level progress for an in-flight media upload or download.
+    ChatMediaTransferProgress {
+        request_id: String,
+        transferred_bytes: u64,
+        total_bytes: u64,
+    },
+    /// A downloaded voice note without a sender waveform was decoded by the platform.
+    ChatMediaWaveformDecoded {
+        chat_id: String,
+        original_hash_hex: String,
+        /// Peak levels in `0..=100`.
+        levels: Vec<u8>,
+        duration_ms: Option<u64>,
+    },
     /// Background resolution of local file paths for media attachments.
     ChatMediaLocalPathsResolved {
         chat_id: String,
@@ -88,6 +107,11 @@ pub enum InternalEvent {
         width: Option<u32>,
         height: Option<u32>,
         blurhash: Option<String>,
+        /// Inline poster JPEG for videos.
+        thumbnail_jpeg: Option<Vec<u8>>,
+        duration_ms: Option<u64>,
+        /// Recorder levels for voice notes, `0..=100` per bar.
+        waveform: Option<Vec<u8>>,
         error: Option<String>,
     },
     KeyPackagePublished {
@@ -239,6 +263,19 @@ pub enum InternalEvent {
         revert_to: bool, // revert is_followed to this value
     },
 
+    // Block list (NIP-51 kind 10000, private entries) sync result
+    BlockListSynced {
+        token: u64,
+        pubkeys: Vec<String>,
+        /// Relays hold a mute list with exactly these entries, rather than
+        /// having none at all.
+        confirmed: bool,
+    },
+    BlockListSyncFailed {
+        token: u64,
+        error: String,
+    },
+
     // Synthetic media runtime updates (Phase-1 plumbing).
     CallRuntimeConnected {
         call_id: String,
@@ -249,10 +286,30 @@ pub enum InternalEvent {
         rx_frames: u64,
         rx_dropped: u64,
         jitter_buffer_ms: u32,
+        jitter_target_ms: u32,
+        rx_jitter_ms: u32,
+        rx_reordered: u64,
+        rx_late: u64,
+        rx_concealed: u64,
+        /// Media payload bytes sent and received so far, audio and video.
+        tx_bytes: u64,
+        rx_bytes: u64,
         last_rtt_ms: Option<u32>,
         video_tx: u64,
         video_rx: u64,
         video_rx_decrypt_fail: u64,
+        video_profile: Option<CallVideoProfile>,
+    },
+    CallParticipantsSpeaking {
+        call_id: String,
+        /// Hex pubkeys of remote participants currently speaking.
+        participant_ids: Vec<String>,
+    },
+    CallCaption {
+        call_id: String,
+        /// Hex pubkey of the remote speaker; `None` for our own mic.
+        participant_id: Option<String>,
+        text: String,
     },
     CallDurationTick {
         token: u64,
@@ -263,11 +320,21 @@ pub enum InternalEvent {
     VoiceRecordingDurationTick {
         token: u64,
     },
+    DisappearingPurgeTick {
+        token: u64,
+    },
+    ChatSnoozeExpired {
+        token: u64,
+    },
 
     // Video frame sent from platform (camera capture → H.264 NALUs).
     VideoFrameFromPlatform {
         payload: Vec<u8>,
     },
+    // Screen share frame sent from platform (screen capture → H.264 NALUs).
+    ScreenFrameFromPlatform {
+        payload: Vec<u8>,
+    },
 
     // Min-version check result from server.
     MinVersionChecked {
@@ -612,6 +612,20 @@ fn start_call_toggle_mute_and_end_transitions_state() {
             .unwrap_or(false)
     });
 
+    assert!(app
+        .state()
+        .active_call
+        .as_ref()
+        .is_some_and(|c| c.is_audio_processing_enabled));
+    app.dispatch(AppAction::ToggleAudioProcessing);
+    wait_until("audio processing off", Duration::from_secs(10), || {
+        app.state()
+            .active_call
+            .as_ref()
+            .map(|c| !c.is_audio_processing_enabled)
+            .unwrap_or(false)
+    });
+
     app.dispatch(AppAction::EndCall);
     wait_until("call ended", Duration::from_secs(10), || {
         app.state()
@@ -695,9 +709,9 @@ fn wipe_local_data_removes_persistent_files() {
         mdk_path.exists()
     });
 
-    let profile_db_path = data_dir.join("profiles.sqlite3");
+    let account_dir = data_dir.join("accounts").join(&pubkey);
     wait_until("profile db created", Duration::from_secs(10), || {
-        profile_db_path.exists()
+        account_dir.join("profiles.sqlite3").exists()
     });
 
     let push_device_id_path = data_dir.join("push_device_id.txt");
@@ -723,8 +737,9 @@ fn wipe_local_data_removes_persistent_files() {
 
     assert!(!mdk_path.exists());
     assert!(!data_dir.join("dev_wipe_marker.txt").exists());
+    assert!(!account_dir.exists());
     assert!(migration_sentinel.exists());
-    assert!(profile_db_path.exists());
+    assert!(data_dir.join("profiles.sqlite3").exists());
     let new_push_device_id = std::fs::read_to_string(&push_device_id_path).unwrap();
     assert!(!new_push_device_id.trim().is_empty());
     assert_ne!(old_push_device_id.trim(), new_push_device_id.trim());
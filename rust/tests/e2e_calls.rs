@@ -11,7 +11,7 @@ use pika_relay_profiles::{app_default_key_package_relays, app_default_message_re
 use tempfile::tempdir;
 
 mod support;
-use support::{wait_until, write_config_multi, write_config_with_moq};
+use support::{accept_invite, wait_until, write_config_multi, write_config_with_moq};
 
 #[derive(Clone, Copy, Debug)]
 struct CallStatsSnapshot {
@@ -122,9 +122,7 @@ fn call_over_local_moq_relay() {
     };
 
     let chat_id = create_or_open_dm_chat(&alice, &bob_npub, Duration::from_secs(90));
-    wait_until("bob chat id matches", Duration::from_secs(45), || {
-        bob.state().chat_list.iter().any(|c| c.chat_id == chat_id)
-    });
+    accept_invite(&bob, &chat_id, Duration::from_secs(45));
 
     alice.dispatch(AppAction::StartCall {
         chat_id: chat_id.clone(),
@@ -7,7 +7,7 @@ use pika_core::{AppAction, AuthState, FfiApp};
 use tempfile::tempdir;
 
 mod support;
-use support::{wait_until, write_config};
+use support::{accept_invite, wait_until, write_config};
 
 fn get_npub(app: &FfiApp) -> String {
     match app.state().auth {
@@ -117,10 +117,8 @@ fn group_profile_visible_to_other_member() {
     // Alice creates a group with Bob.
     let chat_id = create_group_chat(&alice, &bob_npub, "ProfileTest", Duration::from_secs(60));
 
-    // Wait for Bob to receive the group.
-    wait_until("bob has group", Duration::from_secs(30), || {
-        bob.state().chat_list.iter().any(|c| c.chat_id == chat_id)
-    });
+    // Bob accepts the invitation.
+    accept_invite(&bob, &chat_id, Duration::from_secs(30));
 
     // Alice sets a group profile.
     alice.dispatch(AppAction::SaveGroupProfile {
@@ -210,10 +208,8 @@ fn new_member_receives_rebroadcasted_group_profiles() {
         Duration::from_secs(60),
     );
 
-    // Wait for Bob to receive the group.
-    wait_until("bob has group", Duration::from_secs(30), || {
-        bob.state().chat_list.iter().any(|c| c.chat_id == chat_id)
-    });
+    // Bob accepts the invitation.
+    accept_invite(&bob, &chat_id, Duration::from_secs(30));
 
     // Alice sets a group profile.
     alice.dispatch(AppAction::SaveGroupProfile {
@@ -357,10 +353,8 @@ fn dm_profile_visible_to_peer() {
     // Alice creates a DM with Bob (1:1 chat, not a named group).
     let chat_id = create_or_open_dm(&alice, &bob_npub, Duration::from_secs(60));
 
-    // Wait for Bob to see the chat.
-    wait_until("bob has dm", Duration::from_secs(30), || {
-        bob.state().chat_list.iter().any(|c| c.chat_id == chat_id)
-    });
+    // Bob accepts the invitation.
+    accept_invite(&bob, &chat_id, Duration::from_secs(30));
 
     // Alice sets a per-chat profile in the DM.
     alice.dispatch(AppAction::SaveGroupProfile {
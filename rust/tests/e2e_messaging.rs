@@ -8,7 +8,7 @@ use pika_core::{AppAction, AuthState, CallStatus, FfiApp};
 use tempfile::tempdir;
 
 mod support;
-use support::{wait_until, write_config};
+use support::{accept_invite, wait_until, write_config};
 
 fn dm_chat_id_for_peer(app: &FfiApp, peer_npub: &str) -> Option<String> {
     let st = app.state();
@@ -85,9 +85,7 @@ fn alice_sends_bob_receives() {
     };
 
     let chat_id = create_or_open_dm_chat(&alice, &bob_npub, Duration::from_secs(60));
-    wait_until("bob chat id matches", Duration::from_secs(20), || {
-        bob.state().chat_list.iter().any(|c| c.chat_id == chat_id)
-    });
+    accept_invite(&bob, &chat_id, Duration::from_secs(20));
 
     alice.dispatch(AppAction::SendMessage {
         chat_id: chat_id.clone(),
@@ -191,6 +189,7 @@ fn call_invite_with_invalid_relay_auth_is_rejected() {
     };
 
     let chat_id = create_or_open_dm_chat(&alice, &bob_npub, Duration::from_secs(60));
+    accept_invite(&bob, &chat_id, Duration::from_secs(20));
     bob.dispatch(AppAction::OpenChat {
         chat_id: chat_id.clone(),
     });
@@ -333,9 +332,7 @@ fn call_end_signal_is_received_by_peer() {
     };
 
     let chat_id = create_or_open_dm_chat(&alice, &bob_npub, Duration::from_secs(60));
-    wait_until("bob sees chat", Duration::from_secs(30), || {
-        bob.state().chat_list.iter().any(|c| c.chat_id == chat_id)
-    });
+    accept_invite(&bob, &chat_id, Duration::from_secs(30));
 
     // Alice starts a call — bob should see it as Ringing.
     alice.dispatch(AppAction::StartCall {
@@ -3,7 +3,7 @@
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use pika_core::{AppReconciler, AppUpdate};
+use pika_core::{AppAction, AppReconciler, AppUpdate, FfiApp};
 
 pub fn wait_until(what: &str, timeout: Duration, f: impl FnMut() -> bool) {
     wait_until_with_poll(what, timeout, Duration::from_millis(50), f);
@@ -25,6 +25,27 @@ pub fn wait_until_with_poll(
     panic!("{what}: condition not met within {timeout:?}");
 }
 
+/// Accepts the invitation into `chat_id` once it reaches `app` and waits for
+/// the chat to show up. Invites from people `app` doesn't follow are staged.
+pub fn accept_invite(app: &FfiApp, chat_id: &str, timeout: Duration) {
+    let mut invite_id = None;
+    wait_until("invitation received", timeout, || {
+        invite_id = app
+            .state()
+            .invitations
+            .iter()
+            .find(|i| i.chat_id == chat_id)
+            .map(|i| i.invite_id.clone());
+        invite_id.is_some()
+    });
+    app.dispatch(AppAction::AcceptInvite {
+        invite_id: invite_id.expect("invite id"),
+    });
+    wait_until("invitation accepted", timeout, || {
+        app.state().chat_list.iter().any(|c| c.chat_id == chat_id)
+    });
+}
+
 pub fn write_config(data_dir: &str, relay_url: &str) {
     let path = std::path::Path::new(data_dir).join("pika_config.json");
     let v = serde_json::json!({
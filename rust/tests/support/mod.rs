@@ -2,8 +2,8 @@ pub mod helpers;
 pub mod infra;
 #[allow(unused_imports)]
 pub use helpers::{
-    wait_until, wait_until_with_poll, write_config, write_config_multi, write_config_with_moq,
-    Collector,
+    accept_invite, wait_until, wait_until_with_poll, write_config, write_config_multi,
+    write_config_with_moq, Collector,
 };
 #[allow(unused_imports)]
 pub use infra::TestInfra;